@@ -1,13 +1,17 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::*;
+use shared_errores::Idioma;
 use tonic::{metadata::MetadataValue, Request};
 
 use salas_grpc::proto::{
-    sala_service_client::SalaServiceClient, ActivarSalaRequest, CrearSalaRequest,
-    DesactivarSalaRequest, ListarSalasRequest, ObtenerSalaRequest,
+    sala_service_client::SalaServiceClient, AccionReservasFuturas, ActivarSalaRequest,
+    ActualizarSalaRequest, CambioSalaResponse, CrearSalaRequest, DesactivarSalaRequest,
+    EliminarSalaRequest, ListarSalasRequest, ObtenerSalaRequest,
+};
+use usuarios_grpc::proto::{
+    usuario_service_client::UsuarioServiceClient, LoginRequest, VerificarSegundoFactorRequest,
 };
-use usuarios_grpc::proto::{usuario_service_client::UsuarioServiceClient, LoginRequest};
 
 const GRPC_URL: &str = "http://localhost:50051";
 
@@ -27,6 +31,9 @@ enum Commands {
         email: String,
         #[arg(short, long)]
         password: String,
+        /// Código de la app de autenticación (o de recuperación) si la cuenta lo pide
+        #[arg(short, long)]
+        codigo: Option<String>,
     },
     /// Gestión de salas (requiere token)
     Sala {
@@ -45,9 +52,30 @@ enum SalaAction {
         nombre: String,
         #[arg(short, long)]
         capacidad: u32,
+        /// Zona horaria IANA de la sala (por defecto Europe/Madrid)
+        #[arg(short, long)]
+        zona_horaria: Option<String>,
+    },
+    /// Listar salas, con filtros y paginación opcionales
+    Listar {
+        /// Solo activas (true) o inactivas (false)
+        #[arg(long)]
+        activa: Option<bool>,
+        /// Texto que debe contener el nombre
+        #[arg(short, long)]
+        nombre: Option<String>,
+        /// Campo de orden: nombre o capacidad
+        #[arg(long, default_value = "")]
+        orden: String,
+        /// Dirección del orden: asc o desc
+        #[arg(long, default_value = "")]
+        direccion: String,
+        #[arg(long, default_value_t = 0)]
+        offset: u32,
+        /// Salas por página (máximo 200)
+        #[arg(short, long, default_value_t = 50)]
+        limite: u32,
     },
-    /// Listar todas las salas
-    Listar,
     /// Obtener una sala por ID
     Obtener {
         #[arg(short, long)]
@@ -63,6 +91,29 @@ enum SalaAction {
         #[arg(short, long)]
         id: String,
     },
+    /// Cambiar nombre, capacidad o zona horaria de una sala
+    Actualizar {
+        #[arg(short, long)]
+        id: String,
+        #[arg(short, long)]
+        nombre: String,
+        #[arg(short, long)]
+        capacidad: u32,
+        /// Nueva zona horaria IANA; si se omite se mantiene la actual
+        #[arg(short, long)]
+        zona_horaria: Option<String>,
+        /// Cancela las reservas futuras afectadas en lugar de rechazar el cambio
+        #[arg(long)]
+        cancelar_reservas: bool,
+    },
+    /// Eliminar una sala
+    Eliminar {
+        #[arg(short, long)]
+        id: String,
+        /// Cancela las reservas futuras de la sala en lugar de rechazar el borrado
+        #[arg(long)]
+        cancelar_reservas: bool,
+    },
 }
 
 #[tokio::main]
@@ -70,8 +121,12 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Login { email, password } => {
-            handle_login(email, password).await?;
+        Commands::Login {
+            email,
+            password,
+            codigo,
+        } => {
+            handle_login(email, password, codigo).await?;
         }
         Commands::Sala { token, action } => {
             handle_sala_action(token, action).await?;
@@ -81,21 +136,42 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn handle_login(email: String, password: String) -> Result<()> {
+async fn handle_login(email: String, password: String, codigo: Option<String>) -> Result<()> {
     println!("{}", "🔐 Iniciando sesión...".cyan());
 
     let mut client = UsuarioServiceClient::connect(GRPC_URL)
         .await
         .context("Error al conectar con el servidor gRPC")?;
 
-    let request = Request::new(LoginRequest { email, password });
+    let mut request = Request::new(LoginRequest { email, password });
+    add_idioma(&mut request);
 
     let response = client
         .login(request)
         .await
         .context("Error al hacer login")?;
 
-    let login_response = response.into_inner();
+    let mut login_response = response.into_inner();
+    if login_response.requiere_segundo_factor {
+        if login_response.inscripcion_requerida {
+            anyhow::bail!("La cuenta debe activar el segundo factor desde la web antes de entrar");
+        }
+        let Some(codigo) = codigo else {
+            anyhow::bail!("La cuenta tiene segundo factor: repite el login con --codigo");
+        };
+
+        let mut request = Request::new(VerificarSegundoFactorRequest {
+            desafio: login_response.desafio,
+            codigo,
+        });
+        add_idioma(&mut request);
+        login_response = client
+            .verificar_segundo_factor(request)
+            .await
+            .context("Código de segundo factor no válido")?
+            .into_inner();
+    }
+
     let usuario = login_response
         .usuario
         .expect("Usuario no encontrado en la respuesta");
@@ -125,8 +201,16 @@ async fn handle_sala_action(token: String, action: SalaAction) -> Result<()> {
         .context("Error al conectar con el servidor gRPC")?;
 
     match action {
-        SalaAction::Crear { nombre, capacidad } => {
-            let mut request = Request::new(CrearSalaRequest { nombre, capacidad });
+        SalaAction::Crear {
+            nombre,
+            capacidad,
+            zona_horaria,
+        } => {
+            let mut request = Request::new(CrearSalaRequest {
+                nombre,
+                capacidad,
+                zona_horaria,
+            });
             add_auth_token(&mut request, &token)?;
 
             match client.crear_sala(request).await {
@@ -140,13 +224,28 @@ async fn handle_sala_action(token: String, action: SalaAction) -> Result<()> {
                 }
             }
         }
-        SalaAction::Listar => {
-            let mut request = Request::new(ListarSalasRequest {});
+        SalaAction::Listar {
+            activa,
+            nombre,
+            orden,
+            direccion,
+            offset,
+            limite,
+        } => {
+            let mut request = Request::new(ListarSalasRequest {
+                activa,
+                nombre,
+                orden,
+                direccion,
+                offset,
+                limite,
+            });
             add_auth_token(&mut request, &token)?;
 
             match client.listar_salas(request).await {
                 Ok(response) => {
-                    let salas = response.into_inner().salas;
+                    let respuesta = response.into_inner();
+                    let salas = respuesta.salas;
                     println!("\n{}", "🏢 Lista de Salas".cyan().bold());
                     println!("{}", "=".repeat(80).cyan());
 
@@ -157,7 +256,17 @@ async fn handle_sala_action(token: String, action: SalaAction) -> Result<()> {
                             print_sala(&sala.id, &sala.nombre, sala.capacidad, sala.activa);
                             println!("{}", "-".repeat(80).dimmed());
                         }
-                        println!("{}", format!("Total: {} salas", salas.len()).cyan());
+                        println!(
+                            "{}",
+                            format!("Mostrando {} de {} salas", salas.len(), respuesta.total)
+                                .cyan()
+                        );
+                        if let Some(siguiente) = respuesta.siguiente_offset {
+                            println!(
+                                "{}",
+                                format!("Siguiente página: --offset {}", siguiente).dimmed()
+                            );
+                        }
                     }
                 }
                 Err(e) => {
@@ -206,6 +315,52 @@ async fn handle_sala_action(token: String, action: SalaAction) -> Result<()> {
                 }
             }
         }
+        SalaAction::Actualizar {
+            id,
+            nombre,
+            capacidad,
+            zona_horaria,
+            cancelar_reservas,
+        } => {
+            let mut request = Request::new(ActualizarSalaRequest {
+                id,
+                nombre,
+                capacidad,
+                zona_horaria,
+                accion: accion_reservas(cancelar_reservas) as i32,
+            });
+            add_auth_token(&mut request, &token)?;
+
+            match client.actualizar_sala(request).await {
+                Ok(response) => {
+                    println!("{}", "✅ Sala actualizada".green().bold());
+                    print_cambio(&response.into_inner());
+                }
+                Err(e) => {
+                    println!("{}", format!("❌ Error: {}", e.message()).red());
+                }
+            }
+        }
+        SalaAction::Eliminar {
+            id,
+            cancelar_reservas,
+        } => {
+            let mut request = Request::new(EliminarSalaRequest {
+                id,
+                accion: accion_reservas(cancelar_reservas) as i32,
+            });
+            add_auth_token(&mut request, &token)?;
+
+            match client.eliminar_sala(request).await {
+                Ok(response) => {
+                    println!("{}", "✅ Sala eliminada".green().bold());
+                    print_cambio(&response.into_inner());
+                }
+                Err(e) => {
+                    println!("{}", format!("❌ Error: {}", e.message()).red());
+                }
+            }
+        }
     }
 
     Ok(())
@@ -215,9 +370,39 @@ fn add_auth_token<T>(request: &mut Request<T>, token: &str) -> Result<()> {
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .context("Error al crear header de autorización")?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(request);
     Ok(())
 }
 
+/// Pide los mensajes de error en el idioma del sistema (`LANG`)
+fn add_idioma<T>(request: &mut Request<T>) {
+    request.metadata_mut().insert(
+        "accept-language",
+        MetadataValue::from_static(Idioma::del_sistema().codigo()),
+    );
+}
+
+fn accion_reservas(cancelar: bool) -> AccionReservasFuturas {
+    if cancelar {
+        AccionReservasFuturas::Cancelar
+    } else {
+        AccionReservasFuturas::Rechazar
+    }
+}
+
+fn print_cambio(cambio: &CambioSalaResponse) {
+    if let Some(sala) = &cambio.sala {
+        print_sala(&sala.id, &sala.nombre, sala.capacidad, sala.activa);
+    }
+    if !cambio.reservas_canceladas.is_empty() {
+        println!(
+            "  {}: {}",
+            "Reservas canceladas".bold(),
+            cambio.reservas_canceladas.len().to_string().yellow()
+        );
+    }
+}
+
 fn print_sala(id: &str, nombre: &str, capacidad: u32, activa: bool) {
     println!("  {}: {}", "ID".bold(), id.dimmed());
     println!("  {}: {}", "Nombre".bold(), nombre);
@@ -644,6 +644,7 @@ pub fn SalasApp(
                                         nombre: s.nombre.clone(),
                                         capacidad: s.capacidad,
                                         activa: s.activa,
+                                        ..Default::default()
                                     }).collect(),
                                     fecha: *fecha_seleccionada.read(),
                                 }
@@ -656,6 +657,7 @@ pub fn SalasApp(
                                         nombre: s.nombre.clone(),
                                         capacidad: s.capacidad,
                                         activa: s.activa,
+                                        ..Default::default()
                                     }).collect(),
                                     fecha_inicio: *fecha_seleccionada.read(),
                                 }
@@ -6,11 +6,43 @@ use salas_grpc::proto::{
     sala_service_client::SalaServiceClient, ActivarSalaRequest, CrearSalaRequest,
     DesactivarSalaRequest, ListarSalasRequest,
 };
+use shared_errores::Idioma;
 use tonic::{metadata::MetadataValue, Request};
 use usuarios_grpc::proto::{usuario_service_client::UsuarioServiceClient, LoginRequest};
 
 use crate::models::{SalaDto, UsuarioInfo, GRPC_URL};
 
+/// Pide los mensajes de error en el idioma del sistema (`LANG`)
+fn add_idioma<T>(request: &mut Request<T>) {
+    request.metadata_mut().insert(
+        "accept-language",
+        MetadataValue::from_static(Idioma::del_sistema().codigo()),
+    );
+}
+
+/// Máximo de elementos por página que acepta el servidor
+const LIMITE_PAGINA: u32 = 200;
+
+/// Pide todas las páginas de un listado, siguiendo `siguiente_offset` hasta
+/// la última
+async fn todas_las_paginas<T, F, Fut>(mut pedir_pagina: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<u32>), String>>,
+{
+    let mut elementos = Vec::new();
+    let mut offset = 0;
+    loop {
+        let (pagina, siguiente) = pedir_pagina(offset).await?;
+        elementos.extend(pagina);
+        match siguiente {
+            // Un offset que no avanza acabaría en un bucle infinito
+            Some(siguiente) if siguiente > offset => offset = siguiente,
+            _ => return Ok(elementos),
+        }
+    }
+}
+
 // ========== Servicios de Usuarios ==========
 
 pub async fn login_usuario(
@@ -21,10 +53,11 @@ pub async fn login_usuario(
         .await
         .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-    let request = Request::new(LoginRequest {
+    let mut request = Request::new(LoginRequest {
         email: email.to_string(),
         password: password.to_string(),
     });
+    add_idioma(&mut request);
 
     let response = client
         .login(request)
@@ -49,24 +82,35 @@ pub async fn login_usuario(
 // ========== Servicios de Salas ==========
 
 pub async fn listar_salas(token: &str) -> Result<Vec<SalaDto>, String> {
-    let mut client = SalaServiceClient::connect(GRPC_URL)
+    let client = SalaServiceClient::connect(GRPC_URL)
         .await
         .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-    let mut request = Request::new(ListarSalasRequest {});
-
-    let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
-        .map_err(|e| format!("Error al crear header: {}", e))?;
-    request.metadata_mut().insert("authorization", auth_value);
-
-    let response = client
-        .listar_salas(request)
-        .await
-        .map_err(|e| format!("Error gRPC: {}", e))?;
+    let salas = todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let mut request = Request::new(ListarSalasRequest {
+                offset,
+                limite: LIMITE_PAGINA,
+                ..Default::default()
+            });
+
+            let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
+                .map_err(|e| format!("Error al crear header: {}", e))?;
+            request.metadata_mut().insert("authorization", auth_value);
+            add_idioma(&mut request);
+
+            let pagina = client
+                .listar_salas(request)
+                .await
+                .map_err(|e| format!("Error gRPC: {}", e))?
+                .into_inner();
+            Ok((pagina.salas, pagina.siguiente_offset))
+        }
+    })
+    .await?;
 
-    let salas = response
-        .into_inner()
-        .salas
+    let salas = salas
         .into_iter()
         .map(|s| SalaDto {
             id: s.id,
@@ -87,11 +131,13 @@ pub async fn crear_sala(nombre: &str, capacidad: u32, token: &str) -> Result<Sal
     let mut request = Request::new(CrearSalaRequest {
         nombre: nombre.to_string(),
         capacidad,
+        zona_horaria: None,
     });
 
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .map_err(|e| format!("Error al crear header: {}", e))?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(&mut request);
 
     let response = client
         .crear_sala(request)
@@ -118,6 +164,7 @@ pub async fn activar_sala(id: &str, token: &str) -> Result<SalaDto, String> {
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .map_err(|e| format!("Error al crear header: {}", e))?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(&mut request);
 
     let response = client
         .activar_sala(request)
@@ -144,6 +191,7 @@ pub async fn desactivar_sala(id: &str, token: &str) -> Result<SalaDto, String> {
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .map_err(|e| format!("Error al crear header: {}", e))?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(&mut request);
 
     let response = client
         .desactivar_sala(request)
@@ -163,22 +211,33 @@ pub async fn desactivar_sala(id: &str, token: &str) -> Result<SalaDto, String> {
 // ========== Servicios de Reservas ==========
 
 pub async fn listar_reservas(token: &str) -> Result<Vec<ProtoReserva>, String> {
-    let mut client = ReservaServiceClient::connect(GRPC_URL)
+    let client = ReservaServiceClient::connect(GRPC_URL)
         .await
         .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-    let mut request = Request::new(ListarReservasRequest {});
-
-    let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
-        .map_err(|e| format!("Error al crear header: {}", e))?;
-    request.metadata_mut().insert("authorization", auth_value);
-
-    let response = client
-        .listar_reservas(request)
-        .await
-        .map_err(|e| format!("Error gRPC: {}", e))?;
-
-    Ok(response.into_inner().reservas)
+    todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let mut request = Request::new(ListarReservasRequest {
+                offset,
+                limite: LIMITE_PAGINA,
+                ..Default::default()
+            });
+
+            let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
+                .map_err(|e| format!("Error al crear header: {}", e))?;
+            request.metadata_mut().insert("authorization", auth_value);
+            add_idioma(&mut request);
+
+            let pagina = client
+                .listar_reservas(request)
+                .await
+                .map_err(|e| format!("Error gRPC: {}", e))?
+                .into_inner();
+            Ok((pagina.reservas, pagina.siguiente_offset))
+        }
+    })
+    .await
 }
 
 pub async fn crear_reserva(
@@ -197,11 +256,13 @@ pub async fn crear_reserva(
         usuario_id: usuario_id.to_string(),
         fecha_inicio: fecha_inicio.to_string(),
         fecha_fin: fecha_fin.to_string(),
+        recursos: Vec::new(),
     });
 
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .map_err(|e| format!("Error al crear header: {}", e))?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(&mut request);
 
     let response = client
         .crear_reserva(request)
@@ -224,6 +285,7 @@ pub async fn cancelar_reserva(id: &str, token: &str) -> Result<ProtoReserva, Str
     let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
         .map_err(|e| format!("Error al crear header: {}", e))?;
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(&mut request);
 
     let response = client
         .cancelar_reserva(request)
@@ -25,7 +25,7 @@ pub fn view_calendario_diario(
         .iter()
         .filter(|r| {
             if let Ok(inicio) = DateTime::parse_from_rfc3339(&r.fecha_inicio) {
-                let inicio_local = inicio.with_timezone(&Local);
+                let inicio_local = inicio;
                 inicio_local.date_naive() == fecha.date_naive()
             } else {
                 false
@@ -72,8 +72,8 @@ pub fn view_calendario_diario(
                     DateTime::parse_from_rfc3339(&r.fecha_inicio),
                     DateTime::parse_from_rfc3339(&r.fecha_fin),
                 ) {
-                    let inicio_local = inicio.with_timezone(&Local);
-                    let fin_local = fin.with_timezone(&Local);
+                    let inicio_local = inicio;
+                    let fin_local = fin;
 
                     let hora_inicio = inicio_local.hour() as i32;
                     let hora_fin = fin_local.hour() as i32;
@@ -250,13 +250,17 @@ pub fn view_calendario_semanal(
 }
 
 // Helpers
+
+// Las fechas llegan del servidor con el desfase de la zona horaria de la sala,
+// así que se usan tal cual: las horas se muestran en la hora local de la sala
+// y no en la del equipo donde corre la aplicación.
 fn format_hora_reserva(inicio: &str, fin: &str) -> String {
     if let (Ok(inicio_dt), Ok(fin_dt)) = (
         DateTime::parse_from_rfc3339(inicio),
         DateTime::parse_from_rfc3339(fin),
     ) {
-        let inicio_local = inicio_dt.with_timezone(&Local);
-        let fin_local = fin_dt.with_timezone(&Local);
+        let inicio_local = inicio_dt;
+        let fin_local = fin_dt;
         format!(
             "{:02}:{:02}-{:02}:{:02}",
             inicio_local.hour(),
@@ -278,7 +282,7 @@ fn get_reservas_sala_dia(
         .iter()
         .filter(|r| {
             if let Ok(inicio) = DateTime::parse_from_rfc3339(&r.fecha_inicio) {
-                let inicio_local = inicio.with_timezone(&Local);
+                let inicio_local = inicio;
                 r.sala_id == sala_id && inicio_local.date_naive() == dia.date_naive()
             } else {
                 false
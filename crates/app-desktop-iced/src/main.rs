@@ -15,6 +15,7 @@ use salas_grpc::proto::SalaResponse;
 use models::{SalaDto, GRPC_URL};
 
 use once_cell::sync::Lazy;
+use shared_errores::Idioma;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tonic::transport::Channel;
@@ -1254,6 +1255,8 @@ impl App {
                 nombre: s.nombre.clone(),
                 capacidad: s.capacidad,
                 activa: s.activa,
+                // Las reservas ya llegan con el desfase de su sala
+                ..Default::default()
             })
             .collect();
 
@@ -1453,15 +1456,25 @@ fn add_auth_token<T>(request: &mut Request<T>, token: &str) -> Result<(), String
         .map_err(|e| format!("Error al crear header de autorización: {}", e))?;
 
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(request);
     Ok(())
 }
 
+/// Pide los mensajes de error en el idioma del sistema (`LANG`)
+fn add_idioma<T>(request: &mut Request<T>) {
+    request.metadata_mut().insert(
+        "accept-language",
+        tonic::metadata::MetadataValue::from_static(Idioma::del_sistema().codigo()),
+    );
+}
+
 // -------- API gRPC de Login --------
 
 async fn login_usuario(email: String, password: String) -> Result<(String, UsuarioInfo), String> {
     let mut client = get_usuario_client().await?;
 
-    let request: Request<LoginRequest> = Request::new(LoginRequest { email, password });
+    let mut request: Request<LoginRequest> = Request::new(LoginRequest { email, password });
+    add_idioma(&mut request);
 
     let response = client
         .login(request)
@@ -1486,19 +1499,27 @@ async fn login_usuario(email: String, password: String) -> Result<(String, Usuar
 // -------- API gRPC de Salas --------
 
 async fn listar_salas() -> Result<Vec<SalaDto>, String> {
-    let response = with_sala_retry(|mut client| async move {
-        let mut request: Request<ListarSalasRequest> = Request::new(ListarSalasRequest {});
+    services::todas_las_paginas(|offset| async move {
+        let response = with_sala_retry(move |mut client| async move {
+            let mut request: Request<ListarSalasRequest> = Request::new(ListarSalasRequest {
+                offset,
+                limite: services::LIMITE_PAGINA,
+                ..Default::default()
+            });
 
-        // Agregar token JWT si existe
-        if let Some(token) = get_jwt_token().await {
-            add_auth_token(&mut request, &token).map_err(tonic::Status::internal)?;
-        }
+            // Agregar token JWT si existe
+            if let Some(token) = get_jwt_token().await {
+                add_auth_token(&mut request, &token).map_err(tonic::Status::internal)?;
+            }
 
-        client.listar_salas(request).await
-    })
-    .await?;
+            client.listar_salas(request).await
+        })
+        .await?;
 
-    Ok(response.into_inner().salas)
+        let pagina = response.into_inner();
+        Ok((pagina.salas, pagina.siguiente_offset))
+    })
+    .await
 }
 
 async fn crear_sala(nombre: String, capacidad: u32) -> Result<SalaDto, String> {
@@ -1506,7 +1527,7 @@ async fn crear_sala(nombre: String, capacidad: u32) -> Result<SalaDto, String> {
         let nombre = nombre.clone();
         async move {
             let mut request: Request<CrearSalaRequest> =
-                Request::new(CrearSalaRequest { nombre, capacidad });
+                Request::new(CrearSalaRequest { nombre, capacidad, zona_horaria: None });
 
             // Agregar token JWT si existe
             if let Some(token) = get_jwt_token().await {
@@ -1584,22 +1605,32 @@ async fn connect_reserva_grpc() -> Result<ReservaServiceClient<Channel>, String>
 }
 
 async fn listar_reservas() -> Result<Vec<ProtoReserva>, String> {
-    let mut client = get_reserva_client().await?;
-
-    let mut request: Request<ListarReservasRequest> = Request::new(ListarReservasRequest {});
+    let client = get_reserva_client().await?;
 
-    // Agregar token JWT si existe
-    if let Some(token) = get_jwt_token().await {
-        add_auth_token(&mut request, &token)
-            .map_err(|e| format!("Error al agregar token: {}", e))?;
-    }
+    services::todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let mut request: Request<ListarReservasRequest> = Request::new(ListarReservasRequest {
+                offset,
+                limite: services::LIMITE_PAGINA,
+                ..Default::default()
+            });
 
-    let response = client
-        .listar_reservas(request)
-        .await
-        .map_err(|e| format!("Error al listar reservas: {}", e))?;
+            // Agregar token JWT si existe
+            if let Some(token) = get_jwt_token().await {
+                add_auth_token(&mut request, &token)
+                    .map_err(|e| format!("Error al agregar token: {}", e))?;
+            }
 
-    Ok(response.into_inner().reservas)
+            let pagina = client
+                .listar_reservas(request)
+                .await
+                .map_err(|e| format!("Error al listar reservas: {}", e))?
+                .into_inner();
+            Ok((pagina.reservas, pagina.siguiente_offset))
+        }
+    })
+    .await
 }
 
 async fn crear_reserva(
@@ -1619,6 +1650,7 @@ async fn crear_reserva(
         usuario_id,
         fecha_inicio: fecha_inicio_rfc,
         fecha_fin: fecha_fin_rfc,
+        recursos: Vec::new(),
     });
 
     // Agregar token JWT si existe
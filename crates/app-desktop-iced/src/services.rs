@@ -14,6 +14,29 @@ use usuarios_grpc::proto::{LoginRequest, LoginResponse};
 
 use crate::models::{SalaDto, GRPC_URL};
 
+/// Máximo de elementos por página que acepta el servidor
+pub(crate) const LIMITE_PAGINA: u32 = 200;
+
+/// Pide todas las páginas de un listado, siguiendo `siguiente_offset` hasta
+/// la última
+pub(crate) async fn todas_las_paginas<T, F, Fut>(mut pedir_pagina: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<u32>), String>>,
+{
+    let mut elementos = Vec::new();
+    let mut offset = 0;
+    loop {
+        let (pagina, siguiente) = pedir_pagina(offset).await?;
+        elementos.extend(pagina);
+        match siguiente {
+            // Un offset que no avanza acabaría en un bucle infinito
+            Some(siguiente) if siguiente > offset => offset = siguiente,
+            _ => return Ok(elementos),
+        }
+    }
+}
+
 // ========== Cliente gRPC compartido ==========
 
 async fn get_usuarios_client() -> Result<UsuarioServiceClient<Channel>, String> {
@@ -51,21 +74,36 @@ pub async fn login(email: String, password: String) -> Result<LoginResponse, Str
 // ========== Servicios de Salas ==========
 
 pub async fn listar_salas() -> Result<Vec<SalaDto>, String> {
-    let mut client = get_salas_client().await?;
-
-    let request = Request::new(ListarSalasRequest {});
-
-    client
-        .listar_salas(request)
-        .await
-        .map(|response| response.into_inner().salas)
-        .map_err(|e| format!("Error al listar salas: {}", e))
+    let client = get_salas_client().await?;
+
+    todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let request = Request::new(ListarSalasRequest {
+                offset,
+                limite: LIMITE_PAGINA,
+                ..Default::default()
+            });
+
+            let pagina = client
+                .listar_salas(request)
+                .await
+                .map_err(|e| format!("Error al listar salas: {}", e))?
+                .into_inner();
+            Ok((pagina.salas, pagina.siguiente_offset))
+        }
+    })
+    .await
 }
 
 pub async fn crear_sala(nombre: String, capacidad: u32) -> Result<SalaDto, String> {
     let mut client = get_salas_client().await?;
 
-    let request = Request::new(CrearSalaRequest { nombre, capacidad });
+    let request = Request::new(CrearSalaRequest {
+        nombre,
+        capacidad,
+        zona_horaria: None,
+    });
 
     client
         .crear_sala(request)
@@ -101,15 +139,26 @@ pub async fn desactivar_sala(id: String) -> Result<SalaDto, String> {
 // ========== Servicios de Reservas ==========
 
 pub async fn listar_reservas() -> Result<Vec<ProtoReserva>, String> {
-    let mut client = get_reservas_client().await?;
-
-    let request = Request::new(ListarReservasRequest {});
-
-    client
-        .listar_reservas(request)
-        .await
-        .map(|response| response.into_inner().reservas)
-        .map_err(|e| format!("Error al listar reservas: {}", e))
+    let client = get_reservas_client().await?;
+
+    todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let request = Request::new(ListarReservasRequest {
+                offset,
+                limite: LIMITE_PAGINA,
+                ..Default::default()
+            });
+
+            let pagina = client
+                .listar_reservas(request)
+                .await
+                .map_err(|e| format!("Error al listar reservas: {}", e))?
+                .into_inner();
+            Ok((pagina.reservas, pagina.siguiente_offset))
+        }
+    })
+    .await
 }
 
 pub async fn crear_reserva(
@@ -125,6 +174,7 @@ pub async fn crear_reserva(
         usuario_id,
         fecha_inicio,
         fecha_fin,
+        recursos: Vec::new(),
     });
 
     client
@@ -1,5 +1,6 @@
 slint::include_modules!();
 
+use shared_errores::Idioma;
 use slint::{ModelRc, VecModel};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -173,9 +174,18 @@ fn add_auth_token<T>(request: &mut Request<T>) -> Result<(), String> {
         .map_err(|e| format!("Error al crear header de autorización: {}", e))?;
 
     request.metadata_mut().insert("authorization", auth_value);
+    add_idioma(request);
     Ok(())
 }
 
+/// Pide los mensajes de error en el idioma del sistema (`LANG`)
+fn add_idioma<T>(request: &mut Request<T>) {
+    request.metadata_mut().insert(
+        "accept-language",
+        MetadataValue::from_static(Idioma::del_sistema().codigo()),
+    );
+}
+
 // API functions usando gRPC con tokio runtime
 fn login(email: &str, password: &str) -> Result<String, String> {
     let rt = Runtime::new().map_err(|e| format!("Error al crear runtime: {}", e))?;
@@ -185,10 +195,11 @@ fn login(email: &str, password: &str) -> Result<String, String> {
             .await
             .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-        let request = Request::new(LoginRequest {
+        let mut request = Request::new(LoginRequest {
             email: email.to_string(),
             password: password.to_string(),
         });
+        add_idioma(&mut request);
 
         let response = client
             .login(request)
@@ -207,25 +218,58 @@ fn login(email: &str, password: &str) -> Result<String, String> {
     })
 }
 
+/// Máximo de elementos por página que acepta el servidor
+const LIMITE_PAGINA: u32 = 200;
+
+/// Pide todas las páginas de un listado, siguiendo `siguiente_offset` hasta
+/// la última
+async fn todas_las_paginas<T, F, Fut>(mut pedir_pagina: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<u32>), String>>,
+{
+    let mut elementos = Vec::new();
+    let mut offset = 0;
+    loop {
+        let (pagina, siguiente) = pedir_pagina(offset).await?;
+        elementos.extend(pagina);
+        match siguiente {
+            // Un offset que no avanza acabaría en un bucle infinito
+            Some(siguiente) if siguiente > offset => offset = siguiente,
+            _ => return Ok(elementos),
+        }
+    }
+}
+
 fn listar_salas() -> Result<Vec<SalaDto>, String> {
     let rt = Runtime::new().map_err(|e| format!("Error al crear runtime: {}", e))?;
 
     rt.block_on(async {
-        let mut client = SalaServiceClient::connect(GRPC_URL)
+        let client = SalaServiceClient::connect(GRPC_URL)
             .await
             .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-        let mut request = Request::new(ListarSalasRequest {});
-        add_auth_token(&mut request)?;
-
-        let response = client
-            .listar_salas(request)
-            .await
-            .map_err(|e| format!("Error gRPC: {}", e))?;
+        let salas = todas_las_paginas(|offset| {
+            let mut client = client.clone();
+            async move {
+                let mut request = Request::new(ListarSalasRequest {
+                    offset,
+                    limite: LIMITE_PAGINA,
+                    ..Default::default()
+                });
+                add_auth_token(&mut request)?;
+
+                let pagina = client
+                    .listar_salas(request)
+                    .await
+                    .map_err(|e| format!("Error gRPC: {}", e))?
+                    .into_inner();
+                Ok((pagina.salas, pagina.siguiente_offset))
+            }
+        })
+        .await?;
 
-        let salas = response
-            .into_inner()
-            .salas
+        let salas = salas
             .into_iter()
             .map(|s| SalaDto {
                 id: s.id,
@@ -250,6 +294,7 @@ fn crear_sala(nombre: &str, capacidad: u32) -> Result<SalaDto, String> {
         let mut request = Request::new(CrearSalaRequest {
             nombre: nombre.to_string(),
             capacidad,
+            zona_horaria: None,
         });
         add_auth_token(&mut request)?;
 
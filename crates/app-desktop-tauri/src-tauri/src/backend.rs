@@ -2,8 +2,12 @@ use crate::models::{CrearSalaRequest, SalaDto};
 use reqwest::{Client, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use shared_errores::Idioma;
 use std::sync::{Arc, Mutex};
 
+/// Máximo de elementos por página que acepta el servidor
+const LIMITE_PAGINA: usize = 200;
+
 #[derive(Clone)]
 pub struct BackendApi {
     client: Client,
@@ -32,6 +36,9 @@ impl BackendApi {
     }
 
     fn build_request(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        // Los errores problem+json vienen en el idioma del sistema
+        let request = request.header("Accept-Language", Idioma::del_sistema().codigo());
+
         if let Ok(token_guard) = self.token.lock() {
             if let Some(ref token) = *token_guard {
                 return request.header("Authorization", format!("Bearer {}", token));
@@ -41,13 +48,7 @@ impl BackendApi {
     }
 
     pub async fn listar_salas(&self) -> Result<Vec<SalaDto>, String> {
-        let url = self.endpoint("/salas");
-        log_request("GET", &url);
-        let mut req = self.client.get(&url);
-        req = self.build_request(req);
-        let response = req.send().await.map_err(to_string)?;
-        log_status("GET", &url, response.status());
-        parse_response(response).await
+        self.todas_las_paginas("/salas").await
     }
 
     pub async fn crear_sala(&self, request: CrearSalaRequest) -> Result<SalaDto, String> {
@@ -92,6 +93,40 @@ impl BackendApi {
         parse_response(response).await
     }
 
+    /// Pide todas las páginas de un listado hasta reunir el total que indica
+    /// la cabecera `X-Total-Count`
+    async fn todas_las_paginas<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>, String> {
+        let mut elementos: Vec<T> = Vec::new();
+        loop {
+            let url = self.endpoint(&format!(
+                "{path}?offset={}&limite={LIMITE_PAGINA}",
+                elementos.len()
+            ));
+            log_request("GET", &url);
+            let req = self.build_request(self.client.get(&url));
+            let response = req.send().await.map_err(to_string)?;
+            log_status("GET", &url, response.status());
+
+            let total = response
+                .headers()
+                .get("x-total-count")
+                .and_then(|valor| valor.to_str().ok())
+                .and_then(|valor| valor.parse::<usize>().ok());
+            let pagina: Vec<T> = parse_response(response).await?;
+            let recibidos = pagina.len();
+            elementos.extend(pagina);
+
+            // Sin cabecera de total, una página incompleta es la última
+            let ultima = match total {
+                Some(total) => elementos.len() >= total,
+                None => recibidos < LIMITE_PAGINA,
+            };
+            if ultima || recibidos == 0 {
+                return Ok(elementos);
+            }
+        }
+    }
+
     fn endpoint(&self, path: &str) -> String {
         format!("{}/{}", self.base_url, path.trim_start_matches('/'))
     }
@@ -129,10 +164,16 @@ where
     }
 }
 
+/// Cuerpo `application/problem+json` que devuelve la API
 #[derive(Debug, Deserialize)]
 struct ErrorBody {
-    error: Option<String>,
-    errors: Option<Vec<String>>,
+    detail: Option<String>,
+    errors: Option<Vec<ErrorCampoBody>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorCampoBody {
+    message: String,
 }
 
 async fn parse_error_body(response: reqwest::Response) -> String {
@@ -151,12 +192,13 @@ async fn parse_error_body(response: reqwest::Response) -> String {
             if let Ok(parsed) = serde_json::from_str::<ErrorBody>(&body) {
                 if let Some(errors) = parsed.errors {
                     if !errors.is_empty() {
-                        return errors.join(", ");
+                        let mensajes: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+                        return mensajes.join(", ");
                     }
                 }
 
-                if let Some(error) = parsed.error {
-                    return error;
+                if let Some(detail) = parsed.detail {
+                    return detail;
                 }
             }
 
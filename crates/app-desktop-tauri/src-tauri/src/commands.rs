@@ -128,10 +128,14 @@ pub async fn login_usuario(
             format!("Error de conexión: {}", e)
         })?;
 
-    let grpc_request = Request::new(GrpcLoginRequest {
+    let mut grpc_request = Request::new(GrpcLoginRequest {
         email: request.email.clone(),
         password: request.password,
     });
+    grpc_request.metadata_mut().insert(
+        "accept-language",
+        tonic::metadata::MetadataValue::from_static(shared_errores::Idioma::del_sistema().codigo()),
+    );
 
     let response = client.login(grpc_request).await.map_err(|e| {
         logger.error(&format!("Error en login gRPC: {}", e));
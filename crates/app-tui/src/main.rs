@@ -1,5 +1,6 @@
 use crossterm::{event, terminal, ExecutableCommand};
 use ratatui::{prelude::*, widgets::*};
+use shared_errores::Idioma;
 use tonic::{metadata::MetadataValue, Request};
 
 use salas_grpc::proto::{sala_service_client::SalaServiceClient, ListarSalasRequest};
@@ -355,15 +356,24 @@ fn render_welcome_screen(f: &mut Frame, usuario: &UsuarioInfo) {
     f.render_widget(instructions, area[2]);
 }
 
+/// Pide los mensajes de error en el idioma del sistema (`LANG`)
+fn add_idioma<T>(request: &mut Request<T>) {
+    request.metadata_mut().insert(
+        "accept-language",
+        MetadataValue::from_static(Idioma::del_sistema().codigo()),
+    );
+}
+
 async fn login_usuario(email: String, password: String) -> Result<(UsuarioInfo, String), String> {
     let mut client = UsuarioServiceClient::connect(GRPC_URL)
         .await
         .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-    let request = Request::new(LoginRequest {
+    let mut request = Request::new(LoginRequest {
         email: email.clone(),
         password,
     });
+    add_idioma(&mut request);
 
     let response = client
         .login(request)
@@ -385,26 +395,60 @@ async fn login_usuario(email: String, password: String) -> Result<(UsuarioInfo,
     Ok((usuario, login_response.token))
 }
 
+/// Máximo de elementos por página que acepta el servidor
+const LIMITE_PAGINA: u32 = 200;
+
+/// Pide todas las páginas de un listado, siguiendo `siguiente_offset` hasta
+/// la última
+async fn todas_las_paginas<T, F, Fut>(mut pedir_pagina: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<u32>), String>>,
+{
+    let mut elementos = Vec::new();
+    let mut offset = 0;
+    loop {
+        let (pagina, siguiente) = pedir_pagina(offset).await?;
+        elementos.extend(pagina);
+        match siguiente {
+            // Un offset que no avanza acabaría en un bucle infinito
+            Some(siguiente) if siguiente > offset => offset = siguiente,
+            _ => return Ok(elementos),
+        }
+    }
+}
+
 async fn listar_salas(token: &str) -> Result<Vec<Sala>, String> {
-    let mut client = SalaServiceClient::connect(GRPC_URL)
+    let client = SalaServiceClient::connect(GRPC_URL)
         .await
         .map_err(|e| format!("Error de conexión gRPC: {}", e))?;
 
-    let mut request = Request::new(ListarSalasRequest {});
-
-    // Añadir token JWT al header
-    let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
-        .map_err(|e| format!("Error al crear header de autorización: {}", e))?;
-    request.metadata_mut().insert("authorization", auth_value);
-
-    let response = client
-        .listar_salas(request)
-        .await
-        .map_err(|e| format!("Error gRPC al listar salas: {}", e))?;
+    let salas = todas_las_paginas(|offset| {
+        let mut client = client.clone();
+        async move {
+            let mut request = Request::new(ListarSalasRequest {
+                offset,
+                limite: LIMITE_PAGINA,
+                ..Default::default()
+            });
+
+            // Añadir token JWT al header
+            let auth_value = MetadataValue::try_from(format!("Bearer {}", token))
+                .map_err(|e| format!("Error al crear header de autorización: {}", e))?;
+            request.metadata_mut().insert("authorization", auth_value);
+            add_idioma(&mut request);
+
+            let pagina = client
+                .listar_salas(request)
+                .await
+                .map_err(|e| format!("Error gRPC al listar salas: {}", e))?
+                .into_inner();
+            Ok((pagina.salas, pagina.siguiente_offset))
+        }
+    })
+    .await?;
 
-    let salas = response
-        .into_inner()
-        .salas
+    let salas = salas
         .into_iter()
         .map(|s| Sala {
             id: s.id,
@@ -0,0 +1,315 @@
+use crate::routes::WebState;
+use crate::sesion::{
+    cookie_oidc_state, cookie_oidc_state_eliminada, cookie_sesion, cookie_sesion_eliminada,
+    idioma_pagina, Sesion, COOKIE_OIDC_STATE,
+};
+use crate::templates::{
+    CodigosRecuperacionTemplate, InscripcionSegundoFactorTemplate, LoginTemplate,
+    SegundoFactorTemplate,
+};
+use askama::Template;
+use axum::extract::{ConnectInfo, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::{Extension, Form};
+use axum_extra::extract::cookie::CookieJar;
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::Deserialize;
+use shared_errores::{ErrorDominio, Idioma};
+use std::net::SocketAddr;
+use usuarios_application::{AccesoSegundoFactor, ResultadoLogin};
+use usuarios_domain::UsuarioError;
+
+pub async fn login_page(State(state): State<WebState>, headers: HeaderMap) -> impl IntoResponse {
+    let template = LoginTemplate {
+        email: String::new(),
+        error: None,
+        idioma: idioma_pagina(None, &headers).codigo(),
+        sso: state.oidc_service.is_some(),
+    };
+
+    Html(template.render().unwrap())
+}
+
+#[derive(Deserialize)]
+pub struct LoginForm {
+    pub(crate) email: String,
+    pub(crate) password: String,
+}
+
+pub async fn login_submit(
+    State(state): State<WebState>,
+    conexion: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Form(form): Form<LoginForm>,
+) -> Response {
+    let origen = conexion.map(|Extension(ConnectInfo(addr))| addr.ip());
+
+    let idioma = idioma_pagina(None, &headers);
+
+    match state
+        .auth_service
+        .login(form.email.clone(), form.password, origen)
+        .await
+    {
+        Ok(ResultadoLogin::Completado(respuesta)) => (
+            jar.add(cookie_sesion(respuesta.token, state.cookies_seguras)),
+            Redirect::to("/"),
+        )
+            .into_response(),
+        Ok(ResultadoLogin::SegundoFactor {
+            desafio,
+            inscripcion_requerida,
+        }) => pagina_segundo_factor(&state, desafio, inscripcion_requerida, idioma, None).await,
+        Err(error) => login_fallido(&state, form.email, error, idioma),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SegundoFactorForm {
+    pub(crate) desafio: String,
+    pub(crate) codigo: String,
+}
+
+/// Segundo paso del login: código de la app o de recuperación
+pub async fn segundo_factor_submit(
+    State(state): State<WebState>,
+    conexion: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Form(form): Form<SegundoFactorForm>,
+) -> Response {
+    let origen = conexion.map(|Extension(ConnectInfo(addr))| addr.ip());
+    let idioma = idioma_pagina(None, &headers);
+
+    match state
+        .auth_service
+        .verificar_segundo_factor(form.desafio.clone(), form.codigo, origen)
+        .await
+    {
+        Ok(respuesta) => (
+            jar.add(cookie_sesion(respuesta.token, state.cookies_seguras)),
+            Redirect::to("/"),
+        )
+            .into_response(),
+        Err(error @ UsuarioError::CodigoSegundoFactorInvalido) => {
+            let mensaje = error.mensaje_en(idioma);
+            let pagina =
+                pagina_segundo_factor(&state, form.desafio, false, idioma, Some(mensaje)).await;
+            (StatusCode::UNAUTHORIZED, pagina).into_response()
+        }
+        Err(error) => login_fallido(&state, String::new(), error, idioma),
+    }
+}
+
+/// Inscripción del segundo factor que la política exige durante el login
+///
+/// Abre la sesión y muestra los códigos de recuperación, que no se vuelven a
+/// enseñar.
+pub async fn activar_segundo_factor_submit(
+    State(state): State<WebState>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Form(form): Form<SegundoFactorForm>,
+) -> Response {
+    let idioma = idioma_pagina(None, &headers);
+
+    match state
+        .auth_service
+        .activar_segundo_factor(
+            AccesoSegundoFactor::Desafio(form.desafio.clone()),
+            form.codigo,
+        )
+        .await
+    {
+        Ok(activado) => {
+            let template = CodigosRecuperacionTemplate {
+                codigos: activado.codigos_recuperacion,
+                idioma: idioma.codigo(),
+            };
+            let jar = match activado.login {
+                Some(login) => jar.add(cookie_sesion(login.token, state.cookies_seguras)),
+                None => jar,
+            };
+            (jar, Html(template.render().unwrap())).into_response()
+        }
+        Err(error @ UsuarioError::CodigoSegundoFactorInvalido) => {
+            let mensaje = error.mensaje_en(idioma);
+            let pagina =
+                pagina_segundo_factor(&state, form.desafio, true, idioma, Some(mensaje)).await;
+            (StatusCode::UNAUTHORIZED, pagina).into_response()
+        }
+        Err(error) => login_fallido(&state, String::new(), error, idioma),
+    }
+}
+
+/// Pide el código o, si la política lo exige y la cuenta aún no lo tiene,
+/// inscribe el segundo factor mostrando el QR
+async fn pagina_segundo_factor(
+    state: &WebState,
+    desafio: String,
+    inscripcion_requerida: bool,
+    idioma: Idioma,
+    error: Option<String>,
+) -> Response {
+    if !inscripcion_requerida {
+        let template = SegundoFactorTemplate {
+            desafio,
+            error,
+            idioma: idioma.codigo(),
+        };
+        return Html(template.render().unwrap()).into_response();
+    }
+
+    match state
+        .auth_service
+        .iniciar_segundo_factor(AccesoSegundoFactor::Desafio(desafio.clone()))
+        .await
+    {
+        Ok(inscripcion) => {
+            let template = InscripcionSegundoFactorTemplate {
+                desafio,
+                qr: codigo_qr(&inscripcion.url),
+                secreto: inscripcion.secreto,
+                error,
+                idioma: idioma.codigo(),
+            };
+            Html(template.render().unwrap()).into_response()
+        }
+        Err(error) => login_fallido(state, String::new(), error, idioma),
+    }
+}
+
+fn codigo_qr(url: &str) -> String {
+    QrCode::new(url.as_bytes())
+        .map(|qr| qr.render::<svg::Color>().min_dimensions(200, 200).build())
+        .unwrap_or_default()
+}
+
+/// Vuelve al formulario de login con el motivo
+fn login_fallido(state: &WebState, email: String, error: UsuarioError, idioma: Idioma) -> Response {
+    let (status, mensaje) = match error {
+        UsuarioError::CuentaBloqueada { .. } | UsuarioError::DemasiadosIntentos { .. } => {
+            (StatusCode::TOO_MANY_REQUESTS, error.mensaje_en(idioma))
+        }
+        // El desafío caduca a los pocos minutos: hay que volver a empezar
+        UsuarioError::DesafioSegundoFactorInvalido => {
+            (StatusCode::UNAUTHORIZED, error.mensaje_en(idioma))
+        }
+        // Mismo mensaje para email inexistente y contraseña incorrecta
+        _ => (
+            StatusCode::UNAUTHORIZED,
+            UsuarioError::CredencialesInvalidas.mensaje_en(idioma),
+        ),
+    };
+    let template = LoginTemplate {
+        email,
+        error: Some(mensaje),
+        idioma: idioma.codigo(),
+        sso: state.oidc_service.is_some(),
+    };
+
+    (status, Html(template.render().unwrap())).into_response()
+}
+
+/// Empieza el login con el proveedor de identidad de la empresa
+pub async fn login_oidc(State(state): State<WebState>, jar: CookieJar) -> Response {
+    let Some(oidc) = state.oidc_service else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let inicio = oidc.iniciar_login();
+    (
+        jar.add(cookie_oidc_state(inicio.state, state.cookies_seguras)),
+        Redirect::to(&inicio.url),
+    )
+        .into_response()
+}
+
+/// Parámetros con los que vuelve el proveedor de identidad
+#[derive(Deserialize)]
+pub struct VueltaOidc {
+    pub(crate) code: Option<String>,
+    pub(crate) state: Option<String>,
+    /// Si el usuario cancela o el proveedor no le deja entrar
+    pub(crate) error: Option<String>,
+}
+
+/// Vuelta del proveedor de identidad: abre la sesión como el login normal
+pub async fn login_oidc_callback(
+    State(state): State<WebState>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Query(vuelta): Query<VueltaOidc>,
+) -> Response {
+    let Some(oidc) = state.oidc_service.clone() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    // El state debe ser el que se guardó en este navegador al empezar
+    let guardado = jar.get(COOKIE_OIDC_STATE).map(|c| c.value().to_string());
+    let jar = jar.remove(cookie_oidc_state_eliminada());
+
+    let resultado = match (vuelta.error, vuelta.code, vuelta.state) {
+        (Some(error), _, _) => Err(UsuarioError::IdentidadExternaRechazada(error)),
+        (None, Some(codigo), Some(recibido)) if guardado.as_deref() == Some(recibido.as_str()) => {
+            oidc.completar_login(&codigo, &recibido).await
+        }
+        _ => Err(UsuarioError::OidcEstadoInvalido),
+    };
+
+    match resultado {
+        // Se redirige desde una página propia y no con un 303: la petición
+        // viene del proveedor (otro sitio) y el navegador no enviaría la
+        // cookie de sesión `SameSite=Strict` en la redirección
+        Ok(ResultadoLogin::Completado(respuesta)) => (
+            jar.add(cookie_sesion(respuesta.token, state.cookies_seguras)),
+            Html(r#"<!DOCTYPE html><meta http-equiv="refresh" content="0;url=/"><a href="/">Continuar</a>"#),
+        )
+            .into_response(),
+        Ok(ResultadoLogin::SegundoFactor {
+            desafio,
+            inscripcion_requerida,
+        }) => {
+            let idioma = idioma_pagina(None, &headers);
+            let pagina =
+                pagina_segundo_factor(&state, desafio, inscripcion_requerida, idioma, None).await;
+            (jar, pagina).into_response()
+        }
+        Err(error) => {
+            tracing::warn!("Login con SSO fallido: {}", error);
+            let idioma = idioma_pagina(None, &headers);
+            let status = StatusCode::from_u16(error.categoria().status_http())
+                .unwrap_or(StatusCode::UNAUTHORIZED);
+            let template = LoginTemplate {
+                email: String::new(),
+                error: Some(error.mensaje_en(idioma)),
+                idioma: idioma.codigo(),
+                sso: true,
+            };
+
+            (status, jar, Html(template.render().unwrap())).into_response()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CsrfForm {
+    #[serde(default)]
+    pub(crate) csrf: String,
+}
+
+pub async fn logout(
+    sesion: Sesion,
+    jar: CookieJar,
+    Form(form): Form<CsrfForm>,
+) -> Result<impl IntoResponse, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+
+    Ok((
+        jar.remove(cookie_sesion_eliminada()),
+        Redirect::to("/login"),
+    ))
+}
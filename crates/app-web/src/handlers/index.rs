@@ -1,7 +1,14 @@
+use crate::sesion::Sesion;
 use crate::templates::IndexTemplate;
 use askama::Template;
 use axum::response::{Html, IntoResponse};
 
-pub async fn index() -> impl IntoResponse {
-    Html(IndexTemplate.render().unwrap())
+pub async fn index(sesion: Sesion) -> impl IntoResponse {
+    Html(
+        IndexTemplate {
+            sesion: sesion.view(),
+        }
+        .render()
+        .unwrap(),
+    )
 }
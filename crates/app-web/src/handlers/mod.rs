@@ -1,2 +1,32 @@
+pub mod auth;
 pub mod index;
+pub mod reserva;
 pub mod sala;
+
+use crate::templates::SalaView;
+use salas_domain::Sala;
+use shared_errores::{ErrorDominio, Idioma};
+
+pub(crate) fn sala_view(s: Sala) -> SalaView {
+    SalaView {
+        zona_horaria: s.zona_horaria.name().to_string(),
+        id: s.id,
+        nombre: s.nombre,
+        capacidad: s.capacidad,
+        activa: s.activa,
+        gestionable: false,
+    }
+}
+
+/// Mensajes de un error de dominio para mostrar en los formularios
+///
+/// Si el error trae campos se muestra uno por campo; si no, su mensaje.
+pub(crate) fn mensajes_error<E: ErrorDominio>(error: &E, idioma: Idioma) -> Vec<String> {
+    let campos = error.campos_en(idioma);
+
+    if campos.is_empty() {
+        vec![error.mensaje_en(idioma)]
+    } else {
+        campos.into_iter().map(|c| c.mensaje).collect()
+    }
+}
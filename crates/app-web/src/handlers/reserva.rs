@@ -0,0 +1,436 @@
+use crate::handlers::auth::CsrfForm;
+use crate::handlers::{mensajes_error, sala_view};
+use crate::routes::WebState;
+use crate::sesion::Sesion;
+use crate::templates::{
+    CalendarioTemplate, CeldaCalendario, DisponibilidadTemplate, FilaCalendario,
+    MisReservasTemplate, ReservaFormData, ReservaFormTemplate, ReservaView, ReservasSalasTemplate,
+    SalaView,
+};
+use askama::Template;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::Form;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use reservas_domain::{
+    local_a_utc, EstadoReserva, Frecuencia, HorarioApertura, Recurrencia, Reserva, ReservaError,
+    MAX_REPETICIONES,
+};
+use salas_domain::ZONA_HORARIA_POR_DEFECTO;
+use serde::Deserialize;
+use shared_errores::{i18n, Idioma};
+use std::collections::HashMap;
+use usuarios_auth::Autorizador;
+use usuarios_domain::Permiso;
+
+const DIAS_SEMANA: [&str; 7] = ["Lun", "Mar", "Mié", "Jue", "Vie", "Sáb", "Dom"];
+
+async fn salas_activas(state: &WebState) -> Result<Vec<SalaView>, StatusCode> {
+    let salas = state
+        .sala_service
+        .listar_salas()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut salas: Vec<SalaView> = salas
+        .into_iter()
+        .filter(|s| s.activa)
+        .map(sala_view)
+        .collect();
+    salas.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+
+    Ok(salas)
+}
+
+/// Zona horaria de la sala; si no existe se usa la zona por defecto
+/// y será el servicio quien rechace la reserva.
+async fn zona_sala(state: &WebState, sala_id: &str) -> Result<Tz, StatusCode> {
+    Ok(state
+        .sala_service
+        .obtener_sala(sala_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|s| s.zona_horaria)
+        .unwrap_or(ZONA_HORARIA_POR_DEFECTO))
+}
+
+/// Convierte fecha (YYYY-MM-DD) + hora (HH:MM) del formulario, expresadas
+/// en la hora local de la sala, a UTC
+fn parse_fecha_hora(fecha: &str, hora: &str, zona: &Tz) -> Option<DateTime<Utc>> {
+    let fecha = NaiveDate::parse_from_str(fecha.trim(), "%Y-%m-%d").ok()?;
+    let hora = NaiveTime::parse_from_str(hora.trim(), "%H:%M").ok()?;
+    Some(local_a_utc(zona, fecha.and_time(hora)))
+}
+
+/// Valida y convierte el rango del formulario
+fn rango_formulario(
+    form: &ReservaFormData,
+    zona: &Tz,
+    idioma: Idioma,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), Vec<String>> {
+    let mut errores = Vec::new();
+
+    if form.sala_id.trim().is_empty() {
+        errores.push(i18n::traducir("WEB_SALA_NO_SELECCIONADA", idioma, &[]));
+    }
+
+    let inicio = parse_fecha_hora(&form.fecha, &form.hora_inicio, zona);
+    let fin = parse_fecha_hora(&form.fecha, &form.hora_fin, zona);
+
+    if inicio.is_none() || fin.is_none() {
+        errores.push(i18n::traducir("WEB_FECHA_HORA_INVALIDA", idioma, &[]));
+    }
+
+    match (inicio, fin) {
+        (Some(inicio), Some(fin)) if errores.is_empty() => Ok((inicio, fin)),
+        _ => Err(errores),
+    }
+}
+
+/// Recurrencia opcional del formulario; `None` si la reserva no se repite
+fn recurrencia_formulario(
+    form: &ReservaFormData,
+    idioma: Idioma,
+) -> Result<Option<Recurrencia>, Vec<String>> {
+    if form.frecuencia.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let invalida = ReservaError::RecurrenciaInvalida {
+        max: MAX_REPETICIONES,
+    };
+    let frecuencia = Frecuencia::from_str_opt(&form.frecuencia)
+        .ok_or_else(|| mensajes_error(&invalida, idioma))?;
+    let repeticiones: u32 = form
+        .repeticiones
+        .trim()
+        .parse()
+        .map_err(|_| mensajes_error(&invalida, idioma))?;
+
+    Recurrencia::new(frecuencia, repeticiones)
+        .map(Some)
+        .map_err(|e| mensajes_error(&e, idioma))
+}
+
+// ============= Reservar: listado de salas =============
+
+pub async fn reservas_salas_page(
+    sesion: Sesion,
+    State(state): State<WebState>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let salas = salas_activas(&state).await?;
+    let template = ReservasSalasTemplate {
+        sesion: sesion.view(),
+        salas,
+    };
+
+    Ok(Html(template.render().unwrap()))
+}
+
+// ============= Calendario semanal de una sala =============
+
+#[derive(Deserialize)]
+pub struct SemanaQuery {
+    /// Cualquier día de la semana a mostrar (YYYY-MM-DD); por defecto hoy
+    pub semana: Option<String>,
+}
+
+pub async fn calendario_sala_page(
+    sesion: Sesion,
+    State(state): State<WebState>,
+    Path(id): Path<String>,
+    Query(query): Query<SemanaQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let sala = state
+        .sala_service
+        .obtener_sala(&id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // El calendario se dibuja en la hora local de la sala
+    let zona = sala.zona_horaria();
+    let ahora = Utc::now();
+    let dia = query
+        .semana
+        .as_deref()
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        .unwrap_or_else(|| ahora.with_timezone(&zona).date_naive());
+    let lunes = dia - Duration::days(dia.weekday().num_days_from_monday() as i64);
+    let dias: Vec<NaiveDate> = (0..7).map(|i| lunes + Duration::days(i)).collect();
+
+    let reservas: Vec<Reserva> = state
+        .reserva_service
+        .listar_reservas_por_sala(&id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .filter(|r| r.esta_activa())
+        .collect();
+
+    let horario = HorarioApertura::default();
+    let filas = (horario.apertura..horario.cierre)
+        .map(|hora| {
+            let celdas = dias
+                .iter()
+                .map(|dia| {
+                    let hora = NaiveTime::from_hms_opt(hora, 0, 0).unwrap();
+                    let inicio = local_a_utc(&zona, dia.and_time(hora));
+                    let fin = local_a_utc(&zona, dia.and_time(hora) + Duration::hours(1));
+
+                    CeldaCalendario {
+                        fecha: dia.format("%Y-%m-%d").to_string(),
+                        ocupada: reservas
+                            .iter()
+                            .any(|r| r.fecha_inicio() < fin && inicio < r.fecha_fin()),
+                        pasada: inicio < ahora,
+                    }
+                })
+                .collect();
+
+            FilaCalendario {
+                hora: format!("{:02}:00", hora),
+                hora_fin: format!("{:02}:00", (hora + 1) % 24),
+                celdas,
+            }
+        })
+        .collect();
+
+    let template = CalendarioTemplate {
+        sesion: sesion.view(),
+        sala: sala_view(sala),
+        semana_etiqueta: lunes.format("%d/%m/%Y").to_string(),
+        semana_anterior: (lunes - Duration::days(7)).format("%Y-%m-%d").to_string(),
+        semana_siguiente: (lunes + Duration::days(7)).format("%Y-%m-%d").to_string(),
+        dias: dias
+            .iter()
+            .map(|d| {
+                format!(
+                    "{} {}",
+                    DIAS_SEMANA[d.weekday().num_days_from_monday() as usize],
+                    d.format("%d/%m")
+                )
+            })
+            .collect(),
+        filas,
+    };
+
+    Ok(Html(template.render().unwrap()))
+}
+
+// ============= Disponibilidad =============
+
+pub async fn disponibilidad_page(
+    sesion: Sesion,
+    State(state): State<WebState>,
+    Query(form): Query<ReservaFormData>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let salas = salas_activas(&state).await?;
+    let mut resultado = None;
+    let mut errores = Vec::new();
+
+    // Solo se consulta si el formulario viene relleno
+    if !form.fecha.is_empty() {
+        let zona = zona_sala(&state, &form.sala_id).await?;
+        match rango_formulario(&form, &zona, sesion.idioma) {
+            Ok((inicio, fin)) => match state
+                .reserva_service
+                .verificar_disponibilidad(&form.sala_id, inicio, fin)
+                .await
+            {
+                Ok(disponible) => resultado = Some(disponible),
+                Err(e) => errores = mensajes_error(&e, sesion.idioma),
+            },
+            Err(e) => errores = e,
+        }
+    }
+
+    let template = DisponibilidadTemplate {
+        sesion: sesion.view(),
+        salas,
+        form,
+        resultado,
+        errores,
+    };
+
+    Ok(Html(template.render().unwrap()))
+}
+
+// ============= Nueva reserva =============
+
+pub async fn nueva_reserva_form(
+    sesion: Sesion,
+    State(state): State<WebState>,
+    Query(form): Query<ReservaFormData>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let salas = salas_activas(&state).await?;
+    let template = ReservaFormTemplate {
+        sesion: sesion.view(),
+        salas,
+        form,
+        errores: vec![],
+    };
+
+    Ok(Html(template.render().unwrap()))
+}
+
+pub async fn crear_reserva_submit(
+    sesion: Sesion,
+    State(state): State<WebState>,
+    Form(form): Form<ReservaFormData>,
+) -> Result<Response, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir(Permiso::CrearReservas)?;
+
+    let zona = zona_sala(&state, &form.sala_id).await?;
+    let datos = rango_formulario(&form, &zona, sesion.idioma).and_then(|rango| {
+        recurrencia_formulario(&form, sesion.idioma).map(|recurrencia| (rango, recurrencia))
+    });
+
+    let resultado = match datos {
+        Ok(((inicio, fin), None)) => state
+            .reserva_service
+            .crear_reserva(
+                form.sala_id.clone(),
+                sesion.usuario.id.clone(),
+                sesion.usuario.id.clone(),
+                inicio,
+                fin,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| mensajes_error(&e, sesion.idioma)),
+        Ok(((inicio, fin), Some(recurrencia))) => state
+            .reserva_service
+            .crear_reservas_recurrentes(
+                form.sala_id.clone(),
+                sesion.usuario.id.clone(),
+                sesion.usuario.id.clone(),
+                inicio,
+                fin,
+                recurrencia,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| mensajes_error(&e, sesion.idioma)),
+        Err(e) => Err(e),
+    };
+
+    match resultado {
+        Ok(()) => Ok(
+            Redirect::to(&format!("/reservas?usuario_id={}", sesion.usuario.id)).into_response(),
+        ),
+        Err(errores) => {
+            // Volver a mostrar el formulario con los errores
+            let salas = salas_activas(&state).await?;
+            let template = ReservaFormTemplate {
+                sesion: sesion.view(),
+                salas,
+                form,
+                errores,
+            };
+
+            Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Html(template.render().unwrap()),
+            )
+                .into_response())
+        }
+    }
+}
+
+// ============= Mis reservas =============
+
+pub async fn mis_reservas_page(
+    sesion: Sesion,
+    State(state): State<WebState>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let salas: HashMap<String, (String, Tz)> = state
+        .sala_service
+        .listar_salas()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|s| (s.id, (s.nombre, s.zona_horaria)))
+        .collect();
+
+    let mut reservas = state
+        .reserva_service
+        .listar_reservas_por_usuario(&sesion.usuario.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    reservas.sort_by_key(|r| std::cmp::Reverse(r.fecha_inicio()));
+
+    let ahora = Utc::now();
+    let reservas_view = reservas
+        .iter()
+        .map(|r| {
+            let (sala_nombre, zona_sala) = salas
+                .get(r.sala_id())
+                .cloned()
+                .unwrap_or_else(|| (r.sala_id().to_string(), ZONA_HORARIA_POR_DEFECTO));
+            // Se muestra en la zona del perfil del usuario o, si no tiene, en la de la sala
+            let zona = sesion.usuario.zona_horaria.unwrap_or(zona_sala);
+            let inicio = r.fecha_inicio().with_timezone(&zona);
+            let fin = r.fecha_fin().with_timezone(&zona);
+
+            ReservaView {
+                id: r.id().to_string(),
+                sala_nombre,
+                fecha: inicio.format("%d/%m/%Y").to_string(),
+                hora_inicio: inicio.format("%H:%M").to_string(),
+                hora_fin: fin.format("%H:%M").to_string(),
+                zona: inicio.format("%Z").to_string(),
+                estado: format!("{:?}", r.estado()),
+                cancelable: *r.estado() == EstadoReserva::Activa && r.fecha_fin() > ahora,
+            }
+        })
+        .collect();
+
+    let template = MisReservasTemplate {
+        sesion: sesion.view(),
+        reservas: reservas_view,
+    };
+
+    Ok(Html(template.render().unwrap()))
+}
+
+pub async fn cancelar_reserva(
+    sesion: Sesion,
+    State(state): State<WebState>,
+    Path(id): Path<String>,
+    Form(form): Form<CsrfForm>,
+) -> Result<impl IntoResponse, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+
+    // Solo el dueño de la reserva o quien gestione las reservas de la sala
+    let reserva = state
+        .reserva_service
+        .obtener_reserva(&id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !Autorizador::puede_gestionar_reserva(
+        &sesion.usuario,
+        reserva.usuario_id(),
+        reserva.grupo_id(),
+        reserva.sala_id(),
+    ) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    state
+        .reserva_service
+        .cancelar_reserva(&id)
+        .await
+        .map_err(|e| match e {
+            ReservaError::NoEncontrada => StatusCode::NOT_FOUND,
+            ReservaError::Validacion(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    Ok(Redirect::to("/reservas"))
+}
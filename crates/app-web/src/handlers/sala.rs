@@ -1,32 +1,57 @@
-use crate::templates::{DisponibilidadTemplate, SalaFormTemplate, SalaView, SalasTemplate};
+use crate::handlers::auth::CsrfForm;
+use crate::handlers::{mensajes_error, sala_view};
+use crate::sesion::{Sesion, SesionGestorSalas};
+use crate::templates::{SalaEditarTemplate, SalaFormTemplate, SalaView, SalasTemplate};
 use askama::Template;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::response::{Html, IntoResponse, Redirect};
+use axum::response::{Html, IntoResponse, Redirect, Response};
 use axum::Form;
-use salas_application::SalaService;
+use salas_application::{AccionReservasFuturas, SalaService};
+use salas_domain::{Sala, SalaError};
 use serde::Deserialize;
 use std::sync::Arc;
+use usuarios_domain::Permiso;
 
-pub async fn nuevo_sala_form() -> impl IntoResponse {
-    Html(SalaFormTemplate.render().unwrap())
-}
-pub async fn disponibilidad_page() -> impl IntoResponse {
-    Html(DisponibilidadTemplate.render().unwrap())
+pub async fn nuevo_sala_form(
+    SesionGestorSalas(sesion): SesionGestorSalas,
+) -> Result<impl IntoResponse, StatusCode> {
+    // Crear salas requiere el permiso en todo el sistema
+    sesion.exigir(Permiso::GestionarSalas)?;
+
+    Ok(Html(
+        SalaFormTemplate {
+            sesion: sesion.view(),
+        }
+        .render()
+        .unwrap(),
+    ))
 }
 
 #[derive(Deserialize)]
 pub struct CrearSalaForm {
     pub(crate) nombre: String,
     pub(crate) capacidad: u32,
+    #[serde(default)]
+    pub(crate) zona_horaria: String,
+    #[serde(default)]
+    pub(crate) csrf: String,
 }
 
 pub async fn crear_sala_submit(
+    SesionGestorSalas(sesion): SesionGestorSalas,
     State(service): State<Arc<dyn SalaService + Send + Sync>>,
     Form(form): Form<CrearSalaForm>,
 ) -> Result<Redirect, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir(Permiso::GestionarSalas)?;
+
     service
-        .crear_sala(form.nombre, form.capacidad)
+        .crear_sala(
+            form.nombre,
+            form.capacidad,
+            zona_formulario(form.zona_horaria),
+        )
         .await
         .map_err(|_| StatusCode::BAD_REQUEST)?;
 
@@ -34,6 +59,7 @@ pub async fn crear_sala_submit(
 }
 
 pub async fn listar_salas_page(
+    sesion: Sesion,
     State(service): State<Arc<dyn SalaService + Send + Sync>>,
 ) -> Result<impl IntoResponse, StatusCode> {
     let salas = service
@@ -43,21 +69,34 @@ pub async fn listar_salas_page(
 
     let salas_view: Vec<SalaView> = salas
         .into_iter()
-        .map(|s| SalaView {
-            id: s.id,
-            nombre: s.nombre,
-            capacidad: s.capacidad,
-            activa: s.activa,
+        .map(|sala| {
+            let gestionable = sesion
+                .exigir_en_sala(Permiso::GestionarSalas, &sala.id)
+                .is_ok();
+            SalaView {
+                gestionable,
+                ..sala_view(sala)
+            }
         })
         .collect();
 
-    Ok(Html(SalasTemplate { salas: salas_view }.render().unwrap()))
+    let template = SalasTemplate {
+        sesion: sesion.view(),
+        salas: salas_view,
+    };
+
+    Ok(Html(template.render().unwrap()))
 }
 
 pub async fn activar_sala(
+    SesionGestorSalas(sesion): SesionGestorSalas,
     State(service): State<Arc<dyn SalaService + Send + Sync>>,
     Path(id): Path<String>,
+    Form(form): Form<CsrfForm>,
 ) -> Result<impl IntoResponse, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir_en_sala(Permiso::GestionarSalas, &id)?;
+
     service
         .activar_sala(&id)
         .await
@@ -66,12 +105,144 @@ pub async fn activar_sala(
 }
 
 pub async fn desactivar_sala(
+    SesionGestorSalas(sesion): SesionGestorSalas,
     State(service): State<Arc<dyn SalaService + Send + Sync>>,
     Path(id): Path<String>,
+    Form(form): Form<CsrfForm>,
 ) -> Result<impl IntoResponse, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir_en_sala(Permiso::GestionarSalas, &id)?;
+
     service
         .desactivar_sala(&id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Redirect::to("/salas"))
 }
+
+fn editar_template(sesion: &Sesion, sala: Sala, errores: Vec<String>) -> SalaEditarTemplate {
+    SalaEditarTemplate {
+        sesion: sesion.view(),
+        sala: sala_view(sala),
+        errores,
+    }
+}
+
+/// Campo vacío = zona por defecto al crear / sin cambios al editar
+fn zona_formulario(zona_horaria: String) -> Option<String> {
+    Some(zona_horaria).filter(|z| !z.trim().is_empty())
+}
+
+fn accion(cancelar_reservas: bool) -> AccionReservasFuturas {
+    if cancelar_reservas {
+        AccionReservasFuturas::Cancelar
+    } else {
+        AccionReservasFuturas::Rechazar
+    }
+}
+
+/// Vuelve a pintar el formulario de edición con el error de la operación
+async fn error_edicion(
+    service: &Arc<dyn SalaService + Send + Sync>,
+    sesion: &Sesion,
+    id: &str,
+    error: SalaError,
+) -> Result<Response, StatusCode> {
+    if matches!(error, SalaError::NoEncontrada) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let errores = mensajes_error(&error, sesion.idioma);
+
+    let sala = service
+        .obtener_sala(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let template = editar_template(sesion, sala, errores);
+
+    Ok((
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Html(template.render().unwrap()),
+    )
+        .into_response())
+}
+
+pub async fn editar_sala_form(
+    SesionGestorSalas(sesion): SesionGestorSalas,
+    State(service): State<Arc<dyn SalaService + Send + Sync>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    sesion.exigir_en_sala(Permiso::GestionarSalas, &id)?;
+
+    let sala = service
+        .obtener_sala(&id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Html(
+        editar_template(&sesion, sala, vec![]).render().unwrap(),
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct EditarSalaForm {
+    pub(crate) nombre: String,
+    pub(crate) capacidad: u32,
+    #[serde(default)]
+    pub(crate) zona_horaria: String,
+    #[serde(default)]
+    pub(crate) cancelar_reservas: bool,
+    #[serde(default)]
+    pub(crate) csrf: String,
+}
+
+pub async fn editar_sala_submit(
+    SesionGestorSalas(sesion): SesionGestorSalas,
+    State(service): State<Arc<dyn SalaService + Send + Sync>>,
+    Path(id): Path<String>,
+    Form(form): Form<EditarSalaForm>,
+) -> Result<Response, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir_en_sala(Permiso::GestionarSalas, &id)?;
+
+    match service
+        .actualizar_sala(
+            &id,
+            form.nombre,
+            form.capacidad,
+            zona_formulario(form.zona_horaria),
+            accion(form.cancelar_reservas),
+        )
+        .await
+    {
+        Ok(_) => Ok(Redirect::to("/salas").into_response()),
+        Err(e) => error_edicion(&service, &sesion, &id, e).await,
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EliminarSalaForm {
+    #[serde(default)]
+    pub(crate) cancelar_reservas: bool,
+    #[serde(default)]
+    pub(crate) csrf: String,
+}
+
+pub async fn eliminar_sala_submit(
+    SesionGestorSalas(sesion): SesionGestorSalas,
+    State(service): State<Arc<dyn SalaService + Send + Sync>>,
+    Path(id): Path<String>,
+    Form(form): Form<EliminarSalaForm>,
+) -> Result<Response, StatusCode> {
+    sesion.verificar_csrf(&form.csrf)?;
+    sesion.exigir_en_sala(Permiso::GestionarSalas, &id)?;
+
+    match service
+        .eliminar_sala(&id, accion(form.cancelar_reservas))
+        .await
+    {
+        Ok(_) => Ok(Redirect::to("/salas").into_response()),
+        Err(e) => error_edicion(&service, &sesion, &id, e).await,
+    }
+}
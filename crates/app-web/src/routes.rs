@@ -1,28 +1,121 @@
 // Configuración de rutas para la interfaz web
 
 use crate::handlers;
+use crate::sesion::RUTA_OIDC_CALLBACK;
+use axum::extract::FromRef;
 use axum::{
     routing::{get, post},
     Router,
 };
+use rand_core::{OsRng, RngCore};
+use reservas_application::ReservaService;
 use salas_application::SalaService;
 use std::sync::Arc;
 use tower_http::services::ServeDir;
+use usuarios_application::{AuthService, OidcService};
+
+/// Estado compartido por los handlers de la interfaz web
+#[derive(Clone)]
+pub struct WebState {
+    pub sala_service: Arc<dyn SalaService + Send + Sync>,
+    pub reserva_service: Arc<dyn ReservaService>,
+    pub auth_service: Arc<dyn AuthService + Send + Sync>,
+    /// Login con el proveedor de identidad de la empresa, si está configurado
+    pub oidc_service: Option<Arc<dyn OidcService>>,
+    /// Clave para derivar los tokens CSRF de cada sesión
+    pub csrf_secreto: Arc<[u8]>,
+    /// Si las cookies llevan `Secure` (solo se desactiva para HTTP en desarrollo)
+    pub cookies_seguras: bool,
+}
+
+// Los handlers de salas solo necesitan el servicio de salas
+impl FromRef<WebState> for Arc<dyn SalaService + Send + Sync> {
+    fn from_ref(state: &WebState) -> Self {
+        Arc::clone(&state.sala_service)
+    }
+}
 
 /// Crea el router con todas las rutas de la interfaz web
-pub fn crear_router_web(sala_service: Arc<dyn SalaService + Send + Sync>) -> Router {
+pub fn crear_router_web(
+    sala_service: Arc<dyn SalaService + Send + Sync>,
+    reserva_service: Arc<dyn ReservaService>,
+    auth_service: Arc<dyn AuthService + Send + Sync>,
+    oidc_service: Option<Arc<dyn OidcService>>,
+    cookies_seguras: bool,
+) -> Router {
     let static_files = ServeDir::new("crates/app-web/static");
 
+    // Secreto aleatorio por arranque: al reiniciar basta con recargar el formulario
+    let mut csrf_secreto = [0u8; 32];
+    OsRng.fill_bytes(&mut csrf_secreto);
+
+    let state = WebState {
+        sala_service,
+        reserva_service,
+        auth_service,
+        oidc_service,
+        csrf_secreto: Arc::from(csrf_secreto.as_slice()),
+        cookies_seguras,
+    };
+
     Router::new()
         .route("/", get(handlers::index::index))
+        .route(
+            "/login",
+            get(handlers::auth::login_page).post(handlers::auth::login_submit),
+        )
+        .route(
+            "/login/segundo-factor",
+            post(handlers::auth::segundo_factor_submit),
+        )
+        .route(
+            "/login/segundo-factor/activar",
+            post(handlers::auth::activar_segundo_factor_submit),
+        )
+        .route("/login/oidc", get(handlers::auth::login_oidc))
+        .route(RUTA_OIDC_CALLBACK, get(handlers::auth::login_oidc_callback))
+        .route("/logout", post(handlers::auth::logout))
         .route("/salas", get(handlers::sala::listar_salas_page))
         .route("/salas/nuevo", get(handlers::sala::nuevo_sala_form))
         .route("/salas/crear", post(handlers::sala::crear_sala_submit))
+        .route(
+            "/salas/{id}/editar",
+            get(handlers::sala::editar_sala_form).post(handlers::sala::editar_sala_submit),
+        )
+        .route(
+            "/salas/{id}/eliminar",
+            post(handlers::sala::eliminar_sala_submit),
+        )
         .route("/salas/{id}/activar", post(handlers::sala::activar_sala))
         .route(
             "/salas/{id}/desactivar",
             post(handlers::sala::desactivar_sala),
         )
+        .route(
+            "/disponibilidad",
+            get(handlers::reserva::disponibilidad_page),
+        )
+        .route("/reservas", get(handlers::reserva::mis_reservas_page))
+        .route(
+            "/reservas/salas",
+            get(handlers::reserva::reservas_salas_page),
+        )
+        .route(
+            "/reservas/sala/{id}",
+            get(handlers::reserva::calendario_sala_page),
+        )
+        .route(
+            "/reservas/nueva",
+            get(handlers::reserva::nueva_reserva_form),
+        )
+        .route(
+            "/reservas/crear",
+            post(handlers::reserva::crear_reserva_submit),
+        )
+        .route(
+            "/reservas/{id}/cancelar",
+            post(handlers::reserva::cancelar_reserva),
+        )
         .nest_service("/static", static_files)
-        .with_state(sala_service)
+        .with_state(state)
 }
@@ -0,0 +1,246 @@
+// Sesión web basada en cookie HTTP-only con el JWT de `AuthService::login`
+//
+// El token CSRF de los formularios se deriva del token de sesión con HMAC,
+// así no hace falta guardar estado en el servidor.
+
+use crate::routes::WebState;
+use crate::templates::SesionView;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use shared_errores::rest::idioma_de_cabeceras;
+use shared_errores::Idioma;
+use usuarios_auth::Autorizador;
+use usuarios_domain::{Permiso, UsuarioPublico};
+
+/// Nombre de la cookie que guarda el token de sesión
+pub const COOKIE_SESION: &str = "sesion";
+
+/// Nombre de la cookie que ata un login con SSO al navegador que lo empezó
+pub const COOKIE_OIDC_STATE: &str = "oidc_state";
+
+/// Ruta de la vuelta del proveedor de identidad
+pub const RUTA_OIDC_CALLBACK: &str = "/login/oidc/callback";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Crea la cookie de sesión (HTTP-only, SameSite=Strict)
+///
+/// Es una cookie de sesión del navegador; la caducidad real la marca el JWT.
+/// `segura` la marca `Secure`; solo se desactiva para servir la web por HTTP
+/// en desarrollo, porque el navegador no la devolvería.
+pub fn cookie_sesion(token: String, segura: bool) -> Cookie<'static> {
+    Cookie::build((COOKIE_SESION, token))
+        .path("/")
+        .http_only(true)
+        .secure(segura)
+        .same_site(SameSite::Strict)
+        .build()
+}
+
+/// Cookie con el `state` de un login con SSO en curso
+///
+/// Es `SameSite=Lax` porque la vuelta llega desde el proveedor de identidad
+/// (otro sitio) y con `Strict` el navegador no la enviaría.
+pub fn cookie_oidc_state(state: String, segura: bool) -> Cookie<'static> {
+    Cookie::build((COOKIE_OIDC_STATE, state))
+        .path(RUTA_OIDC_CALLBACK)
+        .http_only(true)
+        .secure(segura)
+        .same_site(SameSite::Lax)
+        .build()
+}
+
+/// Cookie vacía para descartar el `state` ya usado
+pub fn cookie_oidc_state_eliminada() -> Cookie<'static> {
+    Cookie::build((COOKIE_OIDC_STATE, ""))
+        .path(RUTA_OIDC_CALLBACK)
+        .build()
+}
+
+/// Cookie vacía para cerrar la sesión
+pub fn cookie_sesion_eliminada() -> Cookie<'static> {
+    Cookie::build((COOKIE_SESION, "")).path("/").build()
+}
+
+/// Calcula el token CSRF asociado a un token de sesión
+pub fn token_csrf(secreto: &[u8], token_sesion: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secreto).expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(token_sesion.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Verifica un token CSRF en tiempo constante
+pub fn verificar_csrf(secreto: &[u8], token_sesion: &str, csrf: &str) -> bool {
+    let Ok(firma) = hex::decode(csrf.trim()) else {
+        return false;
+    };
+
+    let mut mac =
+        HmacSha256::new_from_slice(secreto).expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(token_sesion.as_bytes());
+    mac.verify_slice(&firma).is_ok()
+}
+
+/// Idioma de la página: el elegido en el perfil o, si no hay, `Accept-Language`
+pub fn idioma_pagina(preferido: Option<Idioma>, headers: &HeaderMap) -> Idioma {
+    preferido
+        .or_else(|| idioma_de_cabeceras(headers))
+        .unwrap_or_default()
+}
+
+/// Usuario autenticado en la interfaz web
+///
+/// Si no hay sesión válida redirige a la página de login.
+pub struct Sesion {
+    pub usuario: UsuarioPublico,
+    /// Idioma de los mensajes que se muestran al usuario
+    pub idioma: Idioma,
+    token: String,
+    csrf: String,
+    secreto: std::sync::Arc<[u8]>,
+}
+
+impl Sesion {
+    /// Datos de la sesión que necesitan las plantillas
+    pub fn view(&self) -> SesionView {
+        SesionView {
+            nombre: self.usuario.nombre.clone(),
+            gestiona_salas: Autorizador::puede(&self.usuario, Permiso::GestionarSalas),
+            gestiona_alguna_sala: Autorizador::puede_en_alguna_sala(
+                &self.usuario,
+                Permiso::GestionarSalas,
+            ),
+            csrf: self.csrf.clone(),
+            idioma: self.idioma.codigo(),
+        }
+    }
+
+    /// Exige un permiso en todo el sistema (403 si no lo tiene)
+    pub fn exigir(&self, permiso: Permiso) -> Result<(), StatusCode> {
+        Autorizador::exigir(&self.usuario, permiso).map_err(|_| StatusCode::FORBIDDEN)
+    }
+
+    /// Exige un permiso sobre una sala (403 si no lo tiene)
+    pub fn exigir_en_sala(&self, permiso: Permiso, sala_id: &str) -> Result<(), StatusCode> {
+        Autorizador::exigir_en_sala(&self.usuario, permiso, sala_id)
+            .map_err(|_| StatusCode::FORBIDDEN)
+    }
+
+    /// Comprueba el token CSRF enviado en un formulario
+    pub fn verificar_csrf(&self, csrf: &str) -> Result<(), StatusCode> {
+        if verificar_csrf(&self.secreto, &self.token, csrf) {
+            Ok(())
+        } else {
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+}
+
+impl FromRequestParts<WebState> for Sesion {
+    type Rejection = Redirect;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &WebState,
+    ) -> Result<Self, Self::Rejection> {
+        let jar = CookieJar::from_headers(&parts.headers);
+        let token = jar
+            .get(COOKIE_SESION)
+            .map(|c| c.value().to_string())
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| Redirect::to("/login"))?;
+
+        // Valida el JWT y que el usuario siga activo
+        let usuario = state
+            .auth_service
+            .validate_token(token.clone())
+            .await
+            .map_err(|_| Redirect::to("/login"))?;
+
+        Ok(Sesion {
+            idioma: idioma_pagina(usuario.idioma, &parts.headers),
+            usuario,
+            csrf: token_csrf(&state.csrf_secreto, &token),
+            token,
+            secreto: state.csrf_secreto.clone(),
+        })
+    }
+}
+
+/// Sesión de alguien que gestiona salas, todas o solo algunas
+///
+/// Los demás usuarios reciben un 403. Cada handler comprueba además el
+/// permiso sobre la sala concreta con [`Sesion::exigir_en_sala`].
+pub struct SesionGestorSalas(pub Sesion);
+
+impl FromRequestParts<WebState> for SesionGestorSalas {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &WebState,
+    ) -> Result<Self, Self::Rejection> {
+        let sesion = Sesion::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        if !Autorizador::puede_en_alguna_sala(&sesion.usuario, Permiso::GestionarSalas) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Acceso denegado. Se requiere permiso para gestionar salas",
+            )
+                .into_response());
+        }
+
+        Ok(SesionGestorSalas(sesion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idioma_del_perfil_gana_a_accept_language() {
+        let mut headers = HeaderMap::new();
+        headers.insert("accept-language", "en-US,en;q=0.9".parse().unwrap());
+
+        assert_eq!(idioma_pagina(None, &headers), Idioma::En);
+        assert_eq!(idioma_pagina(Some(Idioma::Es), &headers), Idioma::Es);
+        assert_eq!(idioma_pagina(None, &HeaderMap::new()), Idioma::Es);
+    }
+
+    #[test]
+    fn cookies_secure_segun_la_configuracion() {
+        assert_eq!(cookie_sesion("t".into(), true).secure(), Some(true));
+        assert_eq!(cookie_sesion("t".into(), false).secure(), Some(false));
+        assert_eq!(cookie_oidc_state("s".into(), false).secure(), Some(false));
+    }
+
+    #[test]
+    fn csrf_valido_para_su_sesion() {
+        let secreto = b"secreto-de-prueba";
+        let csrf = token_csrf(secreto, "token-a");
+
+        assert!(verificar_csrf(secreto, "token-a", &csrf));
+    }
+
+    #[test]
+    fn csrf_rechazado_para_otra_sesion_o_secreto() {
+        let csrf = token_csrf(b"secreto-de-prueba", "token-a");
+
+        assert!(!verificar_csrf(b"secreto-de-prueba", "token-b", &csrf));
+        assert!(!verificar_csrf(b"otro-secreto", "token-a", &csrf));
+        assert!(!verificar_csrf(
+            b"secreto-de-prueba",
+            "token-a",
+            "no-es-hex"
+        ));
+    }
+}
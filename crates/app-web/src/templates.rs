@@ -1,33 +1,198 @@
 // Definición de templates usando Askama
 
 use askama::Template;
+use serde::Deserialize;
 
 // ============= DTOs para templates =============
 
+/// Datos del usuario con sesión iniciada que usa la plantilla base
+#[derive(Debug, Clone)]
+pub struct SesionView {
+    pub nombre: String,
+    /// Puede crear salas y gestionar todas (`sala:manage` en todo el sistema)
+    pub gestiona_salas: bool,
+    /// Gestiona todas las salas o algunas como gestor de salas
+    pub gestiona_alguna_sala: bool,
+    /// Token CSRF que deben incluir todos los formularios POST
+    pub csrf: String,
+    /// Código del idioma para `<html lang>`
+    pub idioma: &'static str,
+}
+
 #[derive(Debug)]
 pub struct SalaView {
     pub id: String,
     pub nombre: String,
     pub capacidad: u32,
     pub activa: bool,
+    /// Zona horaria IANA de la sala
+    pub zona_horaria: String,
+    /// Si el usuario de la sesión puede editarla, activarla o desactivarla
+    pub gestionable: bool,
+}
+
+#[derive(Debug)]
+pub struct ReservaView {
+    pub id: String,
+    pub sala_nombre: String,
+    pub fecha: String,
+    pub hora_inicio: String,
+    pub hora_fin: String,
+    /// Abreviatura de la zona en la que se muestran las horas (CET, CST...)
+    pub zona: String,
+    pub estado: String,
+    pub cancelable: bool,
+}
+
+/// Datos de un formulario de reserva / consulta de disponibilidad
+///
+/// Se usa tanto para leer el formulario como para volver a pintarlo con los
+/// valores introducidos cuando hay errores.
+#[derive(Debug, Default, Deserialize)]
+pub struct ReservaFormData {
+    #[serde(default)]
+    pub sala_id: String,
+    #[serde(default)]
+    pub fecha: String,
+    #[serde(default)]
+    pub hora_inicio: String,
+    #[serde(default)]
+    pub hora_fin: String,
+    /// Vacío si no se repite; `diaria` o `semanal`
+    #[serde(default)]
+    pub frecuencia: String,
+    #[serde(default)]
+    pub repeticiones: String,
+    #[serde(default)]
+    pub csrf: String,
+}
+
+#[derive(Debug)]
+pub struct CeldaCalendario {
+    /// Fecha del día (YYYY-MM-DD)
+    pub fecha: String,
+    pub ocupada: bool,
+    pub pasada: bool,
+}
+
+#[derive(Debug)]
+pub struct FilaCalendario {
+    /// Hora de inicio de la franja (HH:MM)
+    pub hora: String,
+    pub hora_fin: String,
+    pub celdas: Vec<CeldaCalendario>,
 }
 
 // ============= Templates =============
 
 #[derive(Template)]
 #[template(path = "index.html")]
-pub struct IndexTemplate;
+pub struct IndexTemplate {
+    pub sesion: SesionView,
+}
+
+#[derive(Template)]
+#[template(path = "login.html")]
+pub struct LoginTemplate {
+    pub email: String,
+    pub error: Option<String>,
+    pub idioma: &'static str,
+    /// Si se ofrece entrar con el proveedor de identidad de la empresa
+    pub sso: bool,
+}
+
+/// Segundo paso del login: código de la app o de recuperación
+#[derive(Template)]
+#[template(path = "segundo_factor.html")]
+pub struct SegundoFactorTemplate {
+    pub desafio: String,
+    pub error: Option<String>,
+    pub idioma: &'static str,
+}
+
+/// Inscripción del segundo factor que la política exige durante el login
+#[derive(Template)]
+#[template(path = "segundo_factor_inscripcion.html")]
+pub struct InscripcionSegundoFactorTemplate {
+    pub desafio: String,
+    /// Secreto en base32, para introducirlo a mano
+    pub secreto: String,
+    /// Código QR con la URL `otpauth://`, en SVG
+    pub qr: String,
+    pub error: Option<String>,
+    pub idioma: &'static str,
+}
+
+#[derive(Template)]
+#[template(path = "segundo_factor_codigos.html")]
+pub struct CodigosRecuperacionTemplate {
+    pub codigos: Vec<String>,
+    pub idioma: &'static str,
+}
 
 #[derive(Template)]
 #[template(path = "salas_form.html")]
-pub struct SalaFormTemplate;
+pub struct SalaFormTemplate {
+    pub sesion: SesionView,
+}
+
+#[derive(Template)]
+#[template(path = "salas_editar.html")]
+pub struct SalaEditarTemplate {
+    pub sesion: SesionView,
+    pub sala: SalaView,
+    pub errores: Vec<String>,
+}
 
 #[derive(Template)]
 #[template(path = "disponibilidad.html")]
-pub struct DisponibilidadTemplate;
+pub struct DisponibilidadTemplate {
+    pub sesion: SesionView,
+    pub salas: Vec<SalaView>,
+    pub form: ReservaFormData,
+    /// `None` si todavía no se ha consultado
+    pub resultado: Option<bool>,
+    pub errores: Vec<String>,
+}
 
 #[derive(Template)]
 #[template(path = "salas.html")]
 pub struct SalasTemplate {
+    pub sesion: SesionView,
+    pub salas: Vec<SalaView>,
+}
+
+#[derive(Template)]
+#[template(path = "reservas_salas.html")]
+pub struct ReservasSalasTemplate {
+    pub sesion: SesionView,
+    pub salas: Vec<SalaView>,
+}
+
+#[derive(Template)]
+#[template(path = "reservas_calendario.html")]
+pub struct CalendarioTemplate {
+    pub sesion: SesionView,
+    pub sala: SalaView,
+    pub semana_etiqueta: String,
+    pub semana_anterior: String,
+    pub semana_siguiente: String,
+    pub dias: Vec<String>,
+    pub filas: Vec<FilaCalendario>,
+}
+
+#[derive(Template)]
+#[template(path = "reservas_form.html")]
+pub struct ReservaFormTemplate {
+    pub sesion: SesionView,
     pub salas: Vec<SalaView>,
+    pub form: ReservaFormData,
+    pub errores: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "reservas_mis.html")]
+pub struct MisReservasTemplate {
+    pub sesion: SesionView,
+    pub reservas: Vec<ReservaView>,
 }
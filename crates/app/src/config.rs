@@ -0,0 +1,207 @@
+//! Configuración del servidor leída de variables de entorno
+//!
+//! Los valores que no se pueden interpretar detienen el arranque: es mejor
+//! fallar que aplicar sin avisar una política de contraseñas distinta.
+
+use std::path::Path;
+use usuarios_application::MapeoRoles;
+use usuarios_auth::ParametrosArgon2;
+use usuarios_domain::{ClaseCaracter, PoliticaPassword, PoliticaSegundoFactor, Rol};
+use usuarios_infrastructure::{ConfigLdap, ConfigOidc};
+
+/// Interpreta una variable booleana (`1`, `true`, `si`); ausente es `false`
+pub fn activada(nombre: &str) -> bool {
+    std::env::var(nombre)
+        .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "si" | "sí"))
+}
+
+/// Si las cookies de la web llevan `Secure` (`COOKIE_SECURE`)
+///
+/// Por defecto sí; `0`, `false` o `no` lo desactivan para probar la web por
+/// HTTP en local, donde el navegador no devolvería una cookie `Secure`.
+pub fn cookies_seguras() -> bool {
+    std::env::var("COOKIE_SECURE").map_or(true, |v| {
+        !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no")
+    })
+}
+
+/// Lee un número de una variable; ausente devuelve `None`
+fn numero<T: std::str::FromStr>(nombre: &str) -> Option<T> {
+    let valor = std::env::var(nombre).ok()?;
+    Some(
+        valor
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{} debe ser un número, no '{}'", nombre, valor)),
+    )
+}
+
+/// Política de contraseñas nuevas
+///
+/// - `PASSWORD_LONGITUD_MINIMA`: caracteres mínimos (8 por defecto)
+/// - `PASSWORD_CLASES`: lista separada por comas de `mayuscula`, `minuscula`,
+///   `digito` y `simbolo`
+/// - `PASSWORD_PROHIBIR_COMUNES`: rechaza las contraseñas más comunes
+/// - `PASSWORD_LISTA_PROHIBIDAS`: archivo con más contraseñas prohibidas, una por línea
+pub fn politica_password() -> PoliticaPassword {
+    let mut politica = PoliticaPassword::default();
+
+    if let Some(longitud) = numero("PASSWORD_LONGITUD_MINIMA") {
+        politica = politica.with_longitud_minima(longitud);
+    }
+
+    if let Ok(clases) = std::env::var("PASSWORD_CLASES") {
+        let clases: Vec<ClaseCaracter> = clases
+            .split(',')
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| {
+                ClaseCaracter::from_str_opt(c).unwrap_or_else(|| {
+                    panic!(
+                        "PASSWORD_CLASES: clase '{}' desconocida (mayuscula, minuscula, digito, simbolo)",
+                        c.trim()
+                    )
+                })
+            })
+            .collect();
+        politica = politica.with_clases(clases);
+    }
+
+    if activada("PASSWORD_PROHIBIR_COMUNES") {
+        politica = politica.con_comunes();
+    }
+
+    if let Ok(ruta) = std::env::var("PASSWORD_LISTA_PROHIBIDAS") {
+        let contenido = std::fs::read_to_string(Path::new(&ruta)).unwrap_or_else(|e| {
+            panic!(
+                "No se pudo leer PASSWORD_LISTA_PROHIBIDAS ({}): {}",
+                ruta, e
+            )
+        });
+        politica = politica.with_prohibidas(contenido.lines());
+    }
+
+    politica
+}
+
+/// Coste de Argon2 para los hashes nuevos
+///
+/// `ARGON2_MEMORIA_KIB`, `ARGON2_ITERACIONES` y `ARGON2_PARALELISMO`; las que
+/// falten conservan el valor por defecto.
+pub fn parametros_argon2() -> ParametrosArgon2 {
+    let defecto = ParametrosArgon2::default();
+    ParametrosArgon2::new(
+        numero("ARGON2_MEMORIA_KIB").unwrap_or(defecto.memoria_kib),
+        numero("ARGON2_ITERACIONES").unwrap_or(defecto.iteraciones),
+        numero("ARGON2_PARALELISMO").unwrap_or(defecto.paralelismo),
+    )
+    .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Roles que no pueden entrar sin segundo factor (TOTP)
+///
+/// `SEGUNDO_FACTOR_ROLES`: lista separada por comas, p. ej. `admin`; ausente
+/// no se exige a nadie (quien lo active lo usa igualmente).
+pub fn politica_segundo_factor() -> PoliticaSegundoFactor {
+    let Ok(roles) = std::env::var("SEGUNDO_FACTOR_ROLES") else {
+        return PoliticaSegundoFactor::default();
+    };
+
+    PoliticaSegundoFactor::new(
+        roles
+            .split(',')
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| {
+                Rol::from_str_opt(r.trim()).unwrap_or_else(|| {
+                    panic!("SEGUNDO_FACTOR_ROLES: rol '{}' desconocido", r.trim())
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Login con un proveedor OpenID Connect; sin `OIDC_ISSUER` no se ofrece
+///
+/// - `OIDC_ISSUER`: URL del emisor (se descubre el resto de endpoints)
+/// - `OIDC_CLIENT_ID` y `OIDC_CLIENT_SECRET`: cliente registrado en el proveedor
+/// - `OIDC_REDIRECT_URI`: URL pública de `/login/oidc/callback`
+/// - `OIDC_CLAIM_GRUPOS`: claim con los grupos del usuario (`groups` por defecto)
+/// - `OIDC_ROLES`: rol de los usuarios nuevos según su grupo, p. ej.
+///   `it-admins=admin,recepcion=recepcionista`; sin grupo conocido, `usuario`
+pub fn oidc() -> Option<(ConfigOidc, MapeoRoles)> {
+    let emisor = std::env::var("OIDC_ISSUER").ok()?;
+    let requerida = |nombre: &str| {
+        std::env::var(nombre)
+            .unwrap_or_else(|_| panic!("{} es obligatoria si se define OIDC_ISSUER", nombre))
+    };
+
+    let mut config = ConfigOidc::new(
+        emisor,
+        requerida("OIDC_CLIENT_ID"),
+        requerida("OIDC_CLIENT_SECRET"),
+        requerida("OIDC_REDIRECT_URI"),
+    );
+    if let Ok(claim) = std::env::var("OIDC_CLAIM_GRUPOS") {
+        config = config.with_claim_grupos(claim);
+    }
+
+    let roles = std::env::var("OIDC_ROLES")
+        .map(|roles| {
+            MapeoRoles::parsear(&roles)
+                .unwrap_or_else(|regla| panic!("OIDC_ROLES: regla '{}' no válida", regla))
+        })
+        .unwrap_or_default();
+
+    Some((config, roles))
+}
+
+/// Login contra el directorio LDAP de la empresa; sin `LDAP_URL` no se usa
+///
+/// - `LDAP_URL`: `ldap://` o `ldaps://` del servidor
+/// - `LDAP_BIND_DN` y `LDAP_BIND_PASSWORD`: cuenta de servicio para buscar usuarios
+/// - `LDAP_BASE_DN`: rama en la que están los usuarios
+/// - `LDAP_FILTRO`: usuarios que pueden entrar (`(objectClass=person)` por defecto)
+/// - `LDAP_STARTTLS`: pide STARTTLS sobre `ldap://`
+/// - `LDAP_ATRIBUTO_EMAIL`, `LDAP_ATRIBUTO_NOMBRE`, `LDAP_ATRIBUTO_GRUPOS` y
+///   `LDAP_ATRIBUTO_ID`: esquema del directorio (`mail`, `cn`, `memberOf`, `entryUUID`)
+/// - `LDAP_ROLES`: rol según el grupo, con el formato de `OIDC_ROLES`
+/// - `LDAP_SINCRONIZAR_MINUTOS`: cada cuánto se sincronizan los usuarios
+///   (60 por defecto; 0 no sincroniza)
+pub fn ldap() -> Option<(ConfigLdap, MapeoRoles, u64)> {
+    let url = std::env::var("LDAP_URL").ok()?;
+    let requerida = |nombre: &str| {
+        std::env::var(nombre)
+            .unwrap_or_else(|_| panic!("{} es obligatoria si se define LDAP_URL", nombre))
+    };
+
+    let mut config = ConfigLdap::new(
+        url,
+        requerida("LDAP_BIND_DN"),
+        requerida("LDAP_BIND_PASSWORD"),
+        requerida("LDAP_BASE_DN"),
+    );
+    config.starttls = activada("LDAP_STARTTLS");
+    for (variable, campo) in [
+        ("LDAP_FILTRO", &mut config.filtro),
+        ("LDAP_ATRIBUTO_EMAIL", &mut config.atributo_email),
+        ("LDAP_ATRIBUTO_NOMBRE", &mut config.atributo_nombre),
+        ("LDAP_ATRIBUTO_GRUPOS", &mut config.atributo_grupos),
+        ("LDAP_ATRIBUTO_ID", &mut config.atributo_id),
+    ] {
+        if let Ok(valor) = std::env::var(variable) {
+            *campo = valor;
+        }
+    }
+
+    let roles = std::env::var("LDAP_ROLES")
+        .map(|roles| {
+            MapeoRoles::parsear(&roles)
+                .unwrap_or_else(|regla| panic!("LDAP_ROLES: regla '{}' no válida", regla))
+        })
+        .unwrap_or_default();
+
+    Some((
+        config,
+        roles,
+        numero("LDAP_SINCRONIZAR_MINUTOS").unwrap_or(60),
+    ))
+}
@@ -1,8 +1,14 @@
-use axum::http::Method;
+mod config;
+
+use axum::http::{HeaderName, Method};
 use axum::Router;
+use recursos_application::{RecursoService, RecursoServiceImpl};
+use recursos_grpc::RecursoGrpcServer;
+use recursos_infrastructure::FileRecursoRepository;
 use salas_application::SalaServiceImpl;
 use salas_grpc::SalaGrpcServer;
 use salas_infrastructure::FileSalaRepository;
+use shared_limites::{Cuota, LimitePeticionesLayer};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -11,17 +17,35 @@ use tower_http::cors::{Any, CorsLayer};
 
 // Usuarios
 use usuarios_application::{
-    AuthService, AuthServiceImpl, UsuarioRepository, UsuarioService, UsuarioServiceImpl,
+    ApiKeyService, ApiKeyServiceImpl, AuthService, AuthServiceImpl, CuentaService,
+    CuentaServiceImpl, DatosPersonalesService, DatosPersonalesServiceImpl, GrupoService,
+    GrupoServiceImpl, OidcService, OidcServiceImpl, PerfilService, PerfilServiceImpl,
+    SincronizacionDirectorio, UsuarioRepository, UsuarioService, UsuarioServiceImpl,
 };
+use usuarios_auth::grpc::exigir_autenticacion;
+use usuarios_auth::{AutenticacionLayer, Autenticador, PasswordService};
 use usuarios_domain::Rol;
 use usuarios_grpc::UsuarioGrpcServer;
-use usuarios_infrastructure::FileUsuarioRepository;
+use usuarios_infrastructure::{
+    ArchivoEnvioCorreo, FileApiKeyRepository, FileGrupoRepository, FileTokenRepository,
+    FileUsuarioRepository, HttpProveedorOidc, LdapDirectorio,
+};
 
 // Reservas
-use reservas_application::ReservaServiceImpl;
-use reservas_grpc::ReservaGrpcServer;
+use reservas_application::{
+    InformeService, InformeServiceImpl, ReservaService, ReservaServiceImpl, ReservasDeSalaAdapter,
+    ReservasDeUsuarioAdapter,
+};
+use reservas_grpc::{InformeGrpcServer, ReservaGrpcServer};
 use reservas_infrastructure::FileReservaRepository;
 
+// Webhooks
+use webhooks_application::{
+    PublicadorEventos, ReservaServiceConEventos, SalaServiceConEventos, WebhookDispatcher,
+    WebhookService, WebhookServiceImpl,
+};
+use webhooks_infrastructure::{FileEntregaRepository, FileWebhookRepository, ReqwestWebhookSender};
+
 #[tokio::main]
 async fn main() {
     // Inicializar el sistema de logging
@@ -32,6 +56,37 @@ async fn main() {
 
     tracing::info!("🚀 Iniciando servidor de Reservas de Salas");
 
+    // ===== WEBHOOKS =====
+    tracing::info!("🔔 Inicializando sistema de Webhooks...");
+
+    let webhooks_repository = FileWebhookRepository::new(PathBuf::from("./data/webhooks.json"));
+    webhooks_repository
+        .init()
+        .await
+        .expect("Error al inicializar repositorio de webhooks");
+
+    let entregas_repository =
+        FileEntregaRepository::new(PathBuf::from("./data/webhook_entregas.json"));
+    entregas_repository
+        .init()
+        .await
+        .expect("Error al inicializar log de entregas de webhooks");
+
+    // El dispatcher comparte los repositorios (clones con la misma cache)
+    let webhook_dispatcher = WebhookDispatcher::new(
+        Arc::new(webhooks_repository.clone()),
+        Arc::new(entregas_repository.clone()),
+        Arc::new(ReqwestWebhookSender::new()),
+    );
+    let publicador: Arc<dyn PublicadorEventos> = Arc::new(webhook_dispatcher.clone());
+    let webhook_service: Arc<dyn WebhookService> = Arc::new(WebhookServiceImpl::new(
+        webhooks_repository,
+        entregas_repository,
+        webhook_dispatcher,
+    ));
+
+    tracing::info!("✓ Webhooks inicializados (./data/webhooks.json)");
+
     // ===== SALAS =====
     tracing::info!("📦 Inicializando sistema de Salas...");
 
@@ -47,11 +102,17 @@ async fn main() {
 
     tracing::info!("✓ Repositorio de salas inicializado (./data/salas.json)");
 
-    // Crear el servicio compartido
-    let sala_service: Arc<dyn salas_application::SalaService + Send + Sync> =
-        Arc::new(SalaServiceImpl::new(salas_repository));
+    // ===== RECURSOS =====
+    // Proyectores, portátiles, plazas de aparcamiento... reservables como las salas
+    let recursos_repository = FileRecursoRepository::default_path();
+    recursos_repository
+        .init()
+        .await
+        .expect("Error al inicializar repositorio de recursos");
+    let recurso_service: Arc<dyn RecursoService + Send + Sync> =
+        Arc::new(RecursoServiceImpl::new(recursos_repository.clone()));
 
-    tracing::info!("✓ Servicio de salas inicializado");
+    tracing::info!("✓ Repositorio de recursos inicializado (./data/recursos.json)");
 
     // ===== USUARIOS =====
     tracing::info!("👥 Inicializando sistema de Usuarios...");
@@ -69,16 +130,146 @@ async fn main() {
 
     // Crear servicios de usuarios
     let usuarios_repo_arc = Arc::new(usuarios_repository);
-    let auth_service: Arc<dyn AuthService + Send + Sync> =
-        Arc::new(AuthServiceImpl::new(usuarios_repo_arc.clone()));
+
+    // Política de contraseñas y coste de Argon2 (ver config.rs)
+    let politica_password = config::politica_password();
+    let passwords = PasswordService::new(config::parametros_argon2());
+    tracing::info!(
+        "✓ Contraseñas: mínimo {} caracteres, clases {:?}, {} prohibidas; Argon2 {:?}",
+        politica_password.longitud_minima,
+        politica_password.clases,
+        politica_password.total_prohibidas(),
+        passwords.parametros()
+    );
+
+    // Roles que deben entrar con segundo factor (SEGUNDO_FACTOR_ROLES, ver config.rs)
+    let politica_segundo_factor = config::politica_segundo_factor();
+    if !politica_segundo_factor.roles.is_empty() {
+        tracing::info!(
+            "✓ Segundo factor obligatorio para {:?}",
+            politica_segundo_factor.roles
+        );
+    }
+
+    let mut auth_service_impl = AuthServiceImpl::new(usuarios_repo_arc.clone())
+        .with_politica_password(politica_password.clone())
+        .with_password_service(passwords)
+        .with_politica_segundo_factor(politica_segundo_factor.clone());
+
+    // Login contra el directorio LDAP y sincronización periódica (LDAP_URL, ver config.rs)
+    if let Some((config_ldap, roles, minutos)) = config::ldap() {
+        tracing::info!("✓ Login con el directorio {}", config_ldap.url);
+        let directorio = Arc::new(LdapDirectorio::new(config_ldap));
+        auth_service_impl =
+            auth_service_impl.with_verificador_externo(directorio.clone(), roles.clone());
+
+        if minutos > 0 {
+            let sincronizacion =
+                SincronizacionDirectorio::new(usuarios_repo_arc.clone(), directorio, roles);
+            tokio::spawn(async move {
+                let mut intervalo =
+                    tokio::time::interval(std::time::Duration::from_secs(minutos * 60));
+                loop {
+                    intervalo.tick().await;
+                    match sincronizacion.sincronizar().await {
+                        Ok(resumen) => tracing::info!(
+                            "Directorio sincronizado: {} actualizados, {} desactivados",
+                            resumen.actualizados,
+                            resumen.desactivados
+                        ),
+                        Err(e) => tracing::warn!("No se pudo sincronizar el directorio: {}", e),
+                    }
+                }
+            });
+        }
+    }
+    let auth_service_impl = Arc::new(auth_service_impl);
+    let auth_service: Arc<dyn AuthService + Send + Sync> = auth_service_impl.clone();
+
+    // Claves de API de las cuentas de servicio (pantallas, scripts de informes)
+    let api_keys_repository = FileApiKeyRepository::new(PathBuf::from("./data/api_keys.json"));
+    api_keys_repository
+        .init()
+        .await
+        .expect("Error al inicializar repositorio de claves de API");
+    let api_keys_repository = Arc::new(api_keys_repository);
+    let api_key_service_impl = Arc::new(ApiKeyServiceImpl::new(api_keys_repository.clone()));
+    let api_key_service: Arc<dyn ApiKeyService + Send + Sync> = api_key_service_impl.clone();
+
+    // Login con el proveedor de identidad de la empresa (OIDC_ISSUER, ver config.rs)
+    let oidc_service: Option<Arc<dyn OidcService>> = match config::oidc() {
+        Some((config_oidc, roles)) => {
+            let emisor = config_oidc.emisor.clone();
+            let proveedor = HttpProveedorOidc::descubrir(config_oidc)
+                .await
+                .expect("Error al leer la configuración del proveedor OIDC");
+            tracing::info!("✓ Login con SSO mediante {}", emisor);
+            Some(Arc::new(
+                OidcServiceImpl::new(usuarios_repo_arc.clone(), Arc::new(proveedor))
+                    .with_mapeo_roles(roles)
+                    .with_politica_segundo_factor(politica_segundo_factor.clone()),
+            ))
+        }
+        None => None,
+    };
+
+    // Grupos de usuarios (equipos): sus miembros gestionan las reservas del grupo
+    let grupos_repository = FileGrupoRepository::default_path();
+    grupos_repository
+        .init()
+        .await
+        .expect("Error al inicializar repositorio de grupos");
+    let grupos_repository = Arc::new(grupos_repository);
+    let grupo_service_impl = Arc::new(GrupoServiceImpl::new(
+        grupos_repository.clone(),
+        usuarios_repo_arc.clone(),
+    ));
+    let grupo_service: Arc<dyn GrupoService + Send + Sync> = grupo_service_impl.clone();
+    tracing::info!("✓ Repositorio de grupos inicializado (./data/grupos.json)");
+
+    // Autenticación común a REST y gRPC: valida el token una vez por petición
+    // y rechaza a los usuarios desactivados aunque su token siga vigente.
+    // También admite las claves de API (`Authorization: ApiKey <clave>`).
+    let autenticador = Autenticador::new()
+        .with_estado(auth_service_impl)
+        .with_api_keys(api_key_service_impl)
+        .with_politica_segundo_factor(politica_segundo_factor)
+        .with_grupos(grupo_service_impl);
     let usuario_service: Arc<dyn UsuarioService + Send + Sync> =
         Arc::new(UsuarioServiceImpl::new(usuarios_repo_arc.clone()));
 
+    // Recuperación de contraseña y verificación de email. Los correos se
+    // guardan en ./data/correos.log; los enlaces apuntan a URL_BASE_CORREOS.
+    let tokens_repository = FileTokenRepository::new(PathBuf::from("./data/tokens.json"));
+    tokens_repository
+        .init()
+        .await
+        .expect("Error al inicializar repositorio de tokens");
+    let tokens_repository = Arc::new(tokens_repository);
+    let mut cuenta_service_impl = CuentaServiceImpl::new(
+        usuarios_repo_arc.clone(),
+        tokens_repository.clone(),
+        Arc::new(ArchivoEnvioCorreo::new(PathBuf::from("./data/correos.log"))),
+    )
+    .with_politica_password(politica_password)
+    .with_password_service(passwords);
+    if let Ok(url_base) = std::env::var("URL_BASE_CORREOS") {
+        cuenta_service_impl = cuenta_service_impl.with_url_base(url_base);
+    }
+    let cuenta_service: Arc<dyn CuentaService + Send + Sync> = Arc::new(cuenta_service_impl);
+    let perfil_service: Arc<dyn PerfilService + Send + Sync> =
+        Arc::new(PerfilServiceImpl::new(usuarios_repo_arc.clone()));
+
     // Crear usuario admin inicial si no existen usuarios
     if usuarios_repo_arc.listar().await.unwrap().is_empty() {
         tracing::info!("🔧 Creando usuario admin inicial...");
 
-        match auth_service
+        // La contraseña inicial es conocida y debe cambiarse, así que no se
+        // somete a la política: con una estricta no se podría crear el admin
+        let registro_inicial =
+            AuthServiceImpl::new(usuarios_repo_arc.clone()).with_password_service(passwords);
+
+        match registro_inicial
             .register(
                 "Administrador".to_string(),
                 "admin@reservas.com".to_string(),
@@ -88,6 +279,16 @@ async fn main() {
             .await
         {
             Ok(admin_response) => {
+                // El email del admin inicial no necesita verificación
+                if let Ok(Some(mut admin)) =
+                    usuarios_repo_arc.obtener(&admin_response.usuario.id).await
+                {
+                    admin.marcar_email_verificado();
+                    if let Err(e) = usuarios_repo_arc.actualizar(&admin).await {
+                        tracing::error!("❌ Error al verificar el email del admin: {:?}", e);
+                    }
+                }
+
                 tracing::info!("✅ Usuario admin creado exitosamente:");
                 tracing::info!("   📧 Email: {}", admin_response.usuario.email);
                 tracing::info!("   👤 Nombre: {}", admin_response.usuario.nombre);
@@ -122,47 +323,132 @@ async fn main() {
     tracing::info!("✓ Repositorio de reservas inicializado (./data/reservas.json)");
 
     // Crear repositorios adicionales para el servicio de reservas
-    let salas_repo_for_reservas = FileSalaRepository::new(PathBuf::from("./data/salas.json"));
-    salas_repo_for_reservas
-        .init()
-        .await
-        .expect("Error al inicializar repositorio de salas para reservas");
+    // (el de salas comparte cache con el del servicio de salas)
+    let salas_repo_for_reservas = salas_repository.clone();
 
-    let usuarios_repo_for_reservas =
-        FileUsuarioRepository::new(PathBuf::from("./data/usuarios.json"));
-    usuarios_repo_for_reservas
-        .init()
-        .await
-        .expect("Error al inicializar repositorio de usuarios para reservas");
+    // El de usuarios también comparte cache: ve al momento los registros y
+    // las verificaciones de email
+    let usuarios_repo_for_reservas = (*usuarios_repo_arc).clone();
+
+    // Con EXIGIR_EMAIL_VERIFICADO=true solo reservan los usuarios verificados
+    let exigir_email_verificado = config::activada("EXIGIR_EMAIL_VERIFICADO");
+
+    // Servicio de informes de uso (comparte la cache de reservas)
+    let informe_service: Arc<dyn InformeService> = Arc::new(InformeServiceImpl::new(
+        reservas_repository.clone(),
+        salas_repo_for_reservas.clone(),
+    ));
 
     // Crear servicio de reservas con validación de Sala y Usuario
-    let reserva_service_impl = ReservaServiceImpl::new(
-        reservas_repository,
-        salas_repo_for_reservas,
-        usuarios_repo_for_reservas,
+    let reserva_service: Arc<dyn ReservaService> = Arc::new(ReservaServiceConEventos::new(
+        ReservaServiceImpl::new(
+            reservas_repository.clone(),
+            salas_repo_for_reservas,
+            usuarios_repo_for_reservas,
+        )
+        .with_verificacion_email(exigir_email_verificado)
+        .with_recursos(Arc::new(recursos_repository)),
+        Arc::clone(&publicador),
+    ));
+
+    tracing::info!(
+        "✓ Servicio de reservas inicializado (email verificado obligatorio: {})",
+        exigir_email_verificado
     );
 
-    tracing::info!("✓ Servicio de reservas inicializado");
+    // Servicio de salas compartido (publica eventos para los webhooks).
+    // Al editar o eliminar una sala revisa sus reservas futuras.
+    let sala_service: Arc<dyn salas_application::SalaService + Send + Sync> =
+        Arc::new(SalaServiceConEventos::new(
+            SalaServiceImpl::new(salas_repository).with_reservas(Arc::new(
+                ReservasDeSalaAdapter::new(Arc::clone(&reserva_service)),
+            )),
+            Arc::clone(&publicador),
+        ));
+
+    tracing::info!("✓ Servicio de salas inicializado");
+
+    // Exportación y eliminación de cuentas: al eliminar una cuenta cancela sus
+    // reservas futuras y seudonimiza el resto para que los informes no cambien;
+    // la quita también de grupos y delegaciones y borra sus tokens y claves
+    let datos_service: Arc<dyn DatosPersonalesService + Send + Sync> =
+        Arc::new(DatosPersonalesServiceImpl::new(
+            usuarios_repo_arc.clone(),
+            Arc::new(ReservasDeUsuarioAdapter::new(
+                Arc::clone(&reserva_service),
+                reservas_repository,
+            )),
+            grupos_repository,
+            tokens_repository,
+            api_keys_repository,
+        ));
 
     // Configurar CORS para la API REST
     let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_origin(Any);
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_origin(Any)
+        // Total de los listados paginados
+        .expose_headers([HeaderName::from_static(shared_paginacion::TOTAL_HEADER)]);
 
     // Crear routers HTTP
     // Opción 1: Rutas SIN autenticación (para desarrollo/testing)
     // let api_router = salas_api::routes::salas_routes(Arc::clone(&sala_service));
 
     // Opción 2: Rutas CON autenticación (para producción)
-    let api_router = salas_api::routes::salas_routes_with_auth(Arc::clone(&sala_service));
+    let api_router = salas_api::routes::salas_routes_with_auth(Arc::clone(&sala_service))
+        .merge(webhooks_api::webhooks_routes_with_auth(Arc::clone(
+            &webhook_service,
+        )))
+        .merge(reservas_api::informes_routes_with_auth(Arc::clone(
+            &informe_service,
+        )))
+        .merge(reservas_api::reservas_routes_with_auth(Arc::clone(
+            &reserva_service,
+        )))
+        .merge(usuarios_api::auth_routes(Arc::clone(&auth_service)))
+        .merge(usuarios_api::cuenta_routes(Arc::clone(&cuenta_service)))
+        .merge(usuarios_api::perfil_routes(Arc::clone(&perfil_service)))
+        .merge(usuarios_api::datos_personales_routes(Arc::clone(
+            &datos_service,
+        )))
+        .merge(usuarios_api::grupos_routes(Arc::clone(&grupo_service)))
+        .merge(usuarios_api::api_keys_routes_with_auth(Arc::clone(
+            &api_key_service,
+        )))
+        // Traduce los problem+json según el perfil o `Accept-Language`
+        .layer(axum::middleware::from_fn(
+            shared_errores::rest::localizar_problemas,
+        ));
+
+    let web_router = app_web::crear_router_web(
+        Arc::clone(&sala_service),
+        Arc::clone(&reserva_service),
+        Arc::clone(&auth_service),
+        oidc_service,
+        config::cookies_seguras(),
+    );
 
-    let web_router = app_web::crear_router_web(Arc::clone(&sala_service));
+    // Límite de peticiones por IP (el login además bloquea tras fallos repetidos)
+    let cuota = Cuota::default();
+    tracing::info!(
+        "✓ Límite por IP: ráfagas de {} peticiones, {} por segundo",
+        cuota.rafaga,
+        cuota.por_segundo
+    );
 
     // Combinar routers HTTP
     let app = Router::new()
         .merge(web_router)
         .nest("/api", api_router)
-        .layer(cors);
+        .layer(AutenticacionLayer::new(autenticador.clone()))
+        .layer(cors)
+        .layer(LimitePeticionesLayer::new(cuota, shared_limites::ip_axum));
 
     // Configurar servidor HTTP
     let http_addr = SocketAddr::from(([0, 0, 0, 0], 3000));
@@ -170,19 +456,43 @@ async fn main() {
     tracing::info!("  📱 Web UI:  http://localhost:3000");
     tracing::info!("  🔌 API REST: http://localhost:3000/api/salas");
     tracing::info!("  📚 Swagger:  http://localhost:3000/api/swagger-ui");
+    tracing::info!("  🔔 Webhooks: http://localhost:3000/api/webhooks");
+    tracing::info!("  📊 Informes: http://localhost:3000/api/informes/uso");
+    tracing::info!("  📅 Reservas: http://localhost:3000/api/reservas");
+    tracing::info!("  🔐 Login:    http://localhost:3000/api/auth/login");
+    tracing::info!("  🔑 Cuenta:   http://localhost:3000/api/auth/password/olvidada");
+    tracing::info!("  🙋 Perfil:   http://localhost:3000/api/me");
+    tracing::info!("  👪 Grupos:   http://localhost:3000/api/grupos");
+    tracing::info!("  🗝️  API keys: http://localhost:3000/api/api-keys");
 
     // Configurar servidores gRPC
     let grpc_addr = SocketAddr::from(([0, 0, 0, 0], 50051));
     let sala_grpc_server = SalaGrpcServer::new(Arc::clone(&sala_service));
-    let usuario_grpc_server =
-        UsuarioGrpcServer::new(Arc::clone(&auth_service), Arc::clone(&usuario_service));
+    let recurso_grpc_server = RecursoGrpcServer::new(Arc::clone(&recurso_service));
+    let usuario_grpc_server = UsuarioGrpcServer::new(
+        Arc::clone(&auth_service),
+        Arc::clone(&usuario_service),
+        Arc::clone(&cuenta_service),
+        Arc::clone(&perfil_service),
+        Arc::clone(&datos_service),
+        Arc::clone(&grupo_service),
+    );
+    // Todas las llamadas de reservas e informes exigen usuario autenticado
+    use reservas_grpc::proto::informe_service_server::InformeServiceServer;
     use reservas_grpc::proto::reserva_service_server::ReservaServiceServer;
-    let reserva_grpc_server =
-        ReservaServiceServer::new(ReservaGrpcServer::new(reserva_service_impl));
+    let reserva_grpc_server = ReservaServiceServer::with_interceptor(
+        ReservaGrpcServer::from_arc(Arc::clone(&reserva_service)),
+        exigir_autenticacion,
+    );
+    let informe_grpc_server = InformeServiceServer::with_interceptor(
+        InformeGrpcServer::new(Arc::clone(&informe_service)),
+        exigir_autenticacion,
+    );
 
     // Configurar reflexión para grpcurl (incluye todos los servicios)
     let reflection_service = tonic_reflection::server::Builder::configure()
         .register_encoded_file_descriptor_set(salas_grpc::proto::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(recursos_grpc::proto::FILE_DESCRIPTOR_SET)
         .register_encoded_file_descriptor_set(usuarios_grpc::proto::FILE_DESCRIPTOR_SET)
         .register_encoded_file_descriptor_set(reservas_grpc::proto::FILE_DESCRIPTOR_SET)
         .build_v1()
@@ -190,23 +500,32 @@ async fn main() {
 
     tracing::info!("✓ Servidor gRPC escuchando en http://{}", grpc_addr);
     tracing::info!("  🔌 gRPC Salas: http://localhost:50051");
+    tracing::info!("  🔌 gRPC Recursos: http://localhost:50051");
     tracing::info!("  🔌 gRPC Usuarios: http://localhost:50051");
     tracing::info!("  🔌 gRPC Reservas: http://localhost:50051");
 
     // Ejecutar ambos servidores en paralelo
     let http_server = async {
         let listener = tokio::net::TcpListener::bind(http_addr).await.unwrap();
-        axum::serve(listener, app.into_make_service())
-            .await
-            .unwrap();
+        // La IP del cliente la usan el límite de peticiones y el login
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
     };
 
     let grpc_server = async {
         Server::builder()
+            .layer(LimitePeticionesLayer::new(cuota, shared_limites::ip_tonic))
+            .layer(AutenticacionLayer::new(autenticador))
             .add_service(reflection_service)
             .add_service(sala_grpc_server.into_service())
+            .add_service(recurso_grpc_server.into_service())
             .add_service(usuario_grpc_server.into_service())
             .add_service(reserva_grpc_server)
+            .add_service(informe_grpc_server)
             .serve(grpc_addr)
             .await
             .unwrap();
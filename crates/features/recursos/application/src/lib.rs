@@ -0,0 +1,5 @@
+pub mod ports;
+pub mod service;
+
+pub use ports::{RecursoRepository, RecursoService};
+pub use service::RecursoServiceImpl;
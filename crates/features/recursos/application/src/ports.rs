@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use recursos_domain::{Recurso, RecursoError, TipoRecurso};
+
+#[async_trait]
+pub trait RecursoService: Send + Sync {
+    async fn crear_recurso(
+        &self,
+        nombre: String,
+        tipo: TipoRecurso,
+    ) -> Result<Recurso, RecursoError>;
+    async fn obtener_recurso(&self, id: &str) -> Result<Option<Recurso>, RecursoError>;
+    /// Recursos ordenados por nombre, opcionalmente solo los de un tipo
+    async fn listar_recursos(
+        &self,
+        tipo: Option<TipoRecurso>,
+    ) -> Result<Vec<Recurso>, RecursoError>;
+    async fn activar_recurso(&self, id: &str) -> Result<Recurso, RecursoError>;
+    async fn desactivar_recurso(&self, id: &str) -> Result<Recurso, RecursoError>;
+}
+
+#[async_trait]
+pub trait RecursoRepository: Send + Sync {
+    async fn guardar(&self, recurso: &Recurso) -> Result<(), RecursoError>;
+    async fn obtener(&self, id: &str) -> Result<Option<Recurso>, RecursoError>;
+    async fn listar(&self) -> Result<Vec<Recurso>, RecursoError>;
+    async fn actualizar(&self, recurso: &Recurso) -> Result<(), RecursoError>;
+}
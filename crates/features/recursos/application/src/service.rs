@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+use recursos_domain::{Recurso, RecursoError, TipoRecurso};
+use uuid::Uuid;
+
+use crate::ports::{RecursoRepository, RecursoService};
+
+pub struct RecursoServiceImpl<R: RecursoRepository> {
+    repository: R,
+}
+
+impl<R: RecursoRepository> RecursoServiceImpl<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    async fn obtener_existente(&self, id: &str) -> Result<Recurso, RecursoError> {
+        self.repository
+            .obtener(id)
+            .await?
+            .ok_or(RecursoError::NoEncontrado)
+    }
+}
+
+#[async_trait]
+impl<R: RecursoRepository> RecursoService for RecursoServiceImpl<R> {
+    async fn crear_recurso(
+        &self,
+        nombre: String,
+        tipo: TipoRecurso,
+    ) -> Result<Recurso, RecursoError> {
+        let recurso = Recurso::new(Uuid::new_v4().to_string(), nombre, tipo)?;
+        self.repository.guardar(&recurso).await?;
+        Ok(recurso)
+    }
+
+    async fn obtener_recurso(&self, id: &str) -> Result<Option<Recurso>, RecursoError> {
+        self.repository.obtener(id).await
+    }
+
+    async fn listar_recursos(
+        &self,
+        tipo: Option<TipoRecurso>,
+    ) -> Result<Vec<Recurso>, RecursoError> {
+        let mut recursos: Vec<Recurso> = self
+            .repository
+            .listar()
+            .await?
+            .into_iter()
+            .filter(|r| tipo.is_none_or(|tipo| r.tipo() == tipo))
+            .collect();
+        recursos.sort_by(|a, b| a.nombre().cmp(b.nombre()));
+        Ok(recursos)
+    }
+
+    async fn activar_recurso(&self, id: &str) -> Result<Recurso, RecursoError> {
+        let mut recurso = self.obtener_existente(id).await?;
+        recurso.activar();
+        self.repository.actualizar(&recurso).await?;
+        Ok(recurso)
+    }
+
+    async fn desactivar_recurso(&self, id: &str) -> Result<Recurso, RecursoError> {
+        let mut recurso = self.obtener_existente(id).await?;
+        recurso.desactivar();
+        self.repository.actualizar(&recurso).await?;
+        Ok(recurso)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockRecursoRepository {
+        recursos: Mutex<HashMap<String, Recurso>>,
+    }
+
+    #[async_trait]
+    impl RecursoRepository for MockRecursoRepository {
+        async fn guardar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+            self.recursos
+                .lock()
+                .unwrap()
+                .insert(recurso.id.clone(), recurso.clone());
+            Ok(())
+        }
+
+        async fn obtener(&self, id: &str) -> Result<Option<Recurso>, RecursoError> {
+            Ok(self.recursos.lock().unwrap().get(id).cloned())
+        }
+
+        async fn listar(&self) -> Result<Vec<Recurso>, RecursoError> {
+            Ok(self.recursos.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn actualizar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+            self.guardar(recurso).await
+        }
+    }
+
+    #[tokio::test]
+    async fn crear_y_listar_por_tipo() {
+        let service = RecursoServiceImpl::new(MockRecursoRepository::default());
+        service
+            .crear_recurso("Proyector".into(), TipoRecurso::Equipo)
+            .await
+            .unwrap();
+        service
+            .crear_recurso("Plaza 1".into(), TipoRecurso::Aparcamiento)
+            .await
+            .unwrap();
+        service
+            .crear_recurso("Portátil".into(), TipoRecurso::Equipo)
+            .await
+            .unwrap();
+
+        let equipos = service
+            .listar_recursos(Some(TipoRecurso::Equipo))
+            .await
+            .unwrap();
+        let nombres: Vec<&str> = equipos.iter().map(|r| r.nombre()).collect();
+        assert_eq!(nombres, vec!["Portátil", "Proyector"]);
+        assert_eq!(service.listar_recursos(None).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn desactivar_y_activar() {
+        let service = RecursoServiceImpl::new(MockRecursoRepository::default());
+        let recurso = service
+            .crear_recurso("Proyector".into(), TipoRecurso::Equipo)
+            .await
+            .unwrap();
+
+        let recurso = service.desactivar_recurso(recurso.id()).await.unwrap();
+        assert!(!recurso.esta_activo());
+        assert!(service
+            .activar_recurso(recurso.id())
+            .await
+            .unwrap()
+            .esta_activo());
+
+        assert_eq!(
+            service.activar_recurso("no-existe").await.unwrap_err(),
+            RecursoError::NoEncontrado
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use shared_errores::{Categoria, ErrorCampo, ErrorDominio, Idioma};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecursoError {
+    NombreVacio,
+    NombreDemasiadoLargo,
+    /// El tipo no es uno de los conocidos
+    TipoInvalido(String),
+    NoEncontrado,
+    ErrorRepositorio(String),
+}
+
+/// El texto sale del catálogo de mensajes en español
+impl fmt::Display for RecursoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje_en(Idioma::Es))
+    }
+}
+
+impl std::error::Error for RecursoError {}
+
+impl ErrorDominio for RecursoError {
+    fn codigo(&self) -> &'static str {
+        match self {
+            RecursoError::NombreVacio => "RECURSO_NOMBRE_VACIO",
+            RecursoError::NombreDemasiadoLargo => "RECURSO_NOMBRE_DEMASIADO_LARGO",
+            RecursoError::TipoInvalido(_) => "RECURSO_TIPO_INVALIDO",
+            RecursoError::NoEncontrado => "RECURSO_NO_ENCONTRADO",
+            RecursoError::ErrorRepositorio(_) => "RECURSO_ERROR_REPOSITORIO",
+        }
+    }
+
+    fn categoria(&self) -> Categoria {
+        match self {
+            RecursoError::NombreVacio
+            | RecursoError::NombreDemasiadoLargo
+            | RecursoError::TipoInvalido(_) => Categoria::Validacion,
+            RecursoError::NoEncontrado => Categoria::NoEncontrado,
+            RecursoError::ErrorRepositorio(_) => Categoria::Interno,
+        }
+    }
+
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        match self {
+            RecursoError::TipoInvalido(tipo) => vec![("tipo", tipo.clone())],
+            RecursoError::ErrorRepositorio(detalle) => vec![("detalle", detalle.clone())],
+            _ => Vec::new(),
+        }
+    }
+
+    fn campos(&self) -> Vec<ErrorCampo> {
+        match self {
+            RecursoError::NombreVacio | RecursoError::NombreDemasiadoLargo => {
+                vec![ErrorCampo::new("nombre", self.codigo(), self.to_string())]
+            }
+            RecursoError::TipoInvalido(_) => {
+                vec![ErrorCampo::new("tipo", self.codigo(), self.to_string())]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
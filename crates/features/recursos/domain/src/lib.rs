@@ -0,0 +1,5 @@
+pub mod error;
+pub mod recurso;
+
+pub use error::RecursoError;
+pub use recurso::{Recurso, TipoRecurso};
@@ -0,0 +1,130 @@
+use crate::error::RecursoError;
+use serde::{Deserialize, Serialize};
+
+/// Clase de recurso reservable que no es una sala
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoRecurso {
+    /// Material portátil: proyectores, portátiles, cámaras...
+    Equipo,
+    /// Plaza de aparcamiento
+    Aparcamiento,
+    Otro,
+}
+
+impl TipoRecurso {
+    /// Crea el tipo desde un string ("equipo", "aparcamiento" u "otro")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "equipo" => Some(TipoRecurso::Equipo),
+            "aparcamiento" => Some(TipoRecurso::Aparcamiento),
+            "otro" => Some(TipoRecurso::Otro),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TipoRecurso::Equipo => "equipo",
+            TipoRecurso::Aparcamiento => "aparcamiento",
+            TipoRecurso::Otro => "otro",
+        }
+    }
+}
+
+/// Recurso reservable que no es una sala (un proyector, una plaza de
+/// aparcamiento...)
+///
+/// Se reserva con las mismas reglas de solapamiento que las salas, solo o
+/// junto con una sala.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recurso {
+    pub id: String,
+    pub nombre: String,
+    pub tipo: TipoRecurso,
+    pub activo: bool,
+}
+
+impl Recurso {
+    pub fn new(id: String, nombre: String, tipo: TipoRecurso) -> Result<Self, RecursoError> {
+        let nombre = nombre.trim().to_string();
+        if nombre.is_empty() {
+            return Err(RecursoError::NombreVacio);
+        }
+        if nombre.chars().count() > 100 {
+            return Err(RecursoError::NombreDemasiadoLargo);
+        }
+
+        Ok(Self {
+            id,
+            nombre,
+            tipo,
+            activo: true,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn nombre(&self) -> &str {
+        &self.nombre
+    }
+
+    pub fn tipo(&self) -> TipoRecurso {
+        self.tipo
+    }
+
+    pub fn esta_activo(&self) -> bool {
+        self.activo
+    }
+
+    pub fn activar(&mut self) {
+        self.activo = true;
+    }
+
+    pub fn desactivar(&mut self) {
+        self.activo = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crear_recurso_valido() {
+        let recurso = Recurso::new("1".into(), "  Proyector 3 ".into(), TipoRecurso::Equipo)
+            .expect("recurso válido");
+
+        assert_eq!(recurso.nombre(), "Proyector 3");
+        assert_eq!(recurso.tipo(), TipoRecurso::Equipo);
+        assert!(recurso.esta_activo());
+    }
+
+    #[test]
+    fn nombre_vacio_o_demasiado_largo() {
+        assert_eq!(
+            Recurso::new("1".into(), "   ".into(), TipoRecurso::Otro),
+            Err(RecursoError::NombreVacio)
+        );
+        assert_eq!(
+            Recurso::new("1".into(), "a".repeat(101), TipoRecurso::Otro),
+            Err(RecursoError::NombreDemasiadoLargo)
+        );
+    }
+
+    #[test]
+    fn tipo_desde_texto_y_en_json() {
+        assert_eq!(
+            TipoRecurso::from_str_opt(" Aparcamiento "),
+            Some(TipoRecurso::Aparcamiento)
+        );
+        assert_eq!(TipoRecurso::from_str_opt("sala"), None);
+
+        let recurso = Recurso::new("p1".into(), "Plaza 12".into(), TipoRecurso::Aparcamiento)
+            .expect("recurso válido");
+        let json = serde_json::to_string(&recurso).unwrap();
+        assert!(json.contains(r#""tipo":"aparcamiento""#));
+    }
+}
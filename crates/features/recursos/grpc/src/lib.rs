@@ -0,0 +1,11 @@
+pub mod server;
+
+pub use server::RecursoGrpcServer;
+
+// Re-exportar los tipos generados por tonic
+pub mod proto {
+    tonic::include_proto!("recurso");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("recurso_descriptor");
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+use recursos_application::RecursoService;
+use recursos_domain::{Recurso, RecursoError, TipoRecurso};
+use shared_errores::Idioma;
+
+use usuarios_auth::grpc::RequestPrincipalExt;
+use usuarios_domain::Permiso;
+
+use crate::proto::{
+    recurso_service_server::{RecursoService as RecursoServiceTrait, RecursoServiceServer},
+    CrearRecursoRequest, ListarRecursosRequest, ListarRecursosResponse, RecursoRequest,
+    RecursoResponse,
+};
+
+pub struct RecursoGrpcServer {
+    service: Arc<dyn RecursoService + Send + Sync>,
+}
+
+impl RecursoGrpcServer {
+    pub fn new(service: Arc<dyn RecursoService + Send + Sync>) -> Self {
+        Self { service }
+    }
+
+    pub fn into_service(self) -> RecursoServiceServer<Self> {
+        RecursoServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl RecursoServiceTrait for RecursoGrpcServer {
+    async fn crear_recurso(
+        &self,
+        request: Request<CrearRecursoRequest>,
+    ) -> Result<Response<RecursoResponse>, Status> {
+        // Los recursos se gestionan como las salas: requiere sala:manage
+        request.principal_con_permiso(Permiso::GestionarSalas)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let tipo = parsear_tipo(&req.tipo, idioma)?;
+
+        let recurso = self
+            .service
+            .crear_recurso(req.nombre, tipo)
+            .await
+            .map_err(|e| recurso_error_to_status(e, idioma))?;
+
+        Ok(Response::new(recurso_to_proto(&recurso)))
+    }
+
+    async fn obtener_recurso(
+        &self,
+        request: Request<RecursoRequest>,
+    ) -> Result<Response<RecursoResponse>, Status> {
+        // Requiere autenticación
+        request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let recurso = self
+            .service
+            .obtener_recurso(&req.id)
+            .await
+            .map_err(|e| recurso_error_to_status(e, idioma))?
+            .ok_or_else(|| recurso_error_to_status(RecursoError::NoEncontrado, idioma))?;
+
+        Ok(Response::new(recurso_to_proto(&recurso)))
+    }
+
+    async fn listar_recursos(
+        &self,
+        request: Request<ListarRecursosRequest>,
+    ) -> Result<Response<ListarRecursosResponse>, Status> {
+        // Requiere autenticación
+        request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let tipo = req
+            .tipo
+            .as_deref()
+            .map(|tipo| parsear_tipo(tipo, idioma))
+            .transpose()?;
+
+        let recursos = self
+            .service
+            .listar_recursos(tipo)
+            .await
+            .map_err(|e| recurso_error_to_status(e, idioma))?;
+
+        Ok(Response::new(ListarRecursosResponse {
+            recursos: recursos.iter().map(recurso_to_proto).collect(),
+        }))
+    }
+
+    async fn activar_recurso(
+        &self,
+        request: Request<RecursoRequest>,
+    ) -> Result<Response<RecursoResponse>, Status> {
+        // Requiere sala:manage en todo el sistema
+        request.principal_con_permiso(Permiso::GestionarSalas)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let recurso = self
+            .service
+            .activar_recurso(&req.id)
+            .await
+            .map_err(|e| recurso_error_to_status(e, idioma))?;
+
+        Ok(Response::new(recurso_to_proto(&recurso)))
+    }
+
+    async fn desactivar_recurso(
+        &self,
+        request: Request<RecursoRequest>,
+    ) -> Result<Response<RecursoResponse>, Status> {
+        // Requiere sala:manage en todo el sistema
+        request.principal_con_permiso(Permiso::GestionarSalas)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let recurso = self
+            .service
+            .desactivar_recurso(&req.id)
+            .await
+            .map_err(|e| recurso_error_to_status(e, idioma))?;
+
+        Ok(Response::new(recurso_to_proto(&recurso)))
+    }
+}
+
+fn parsear_tipo(tipo: &str, idioma: Idioma) -> Result<TipoRecurso, Status> {
+    TipoRecurso::from_str_opt(tipo).ok_or_else(|| {
+        recurso_error_to_status(RecursoError::TipoInvalido(tipo.trim().to_string()), idioma)
+    })
+}
+
+fn recurso_to_proto(recurso: &Recurso) -> RecursoResponse {
+    RecursoResponse {
+        id: recurso.id().to_string(),
+        nombre: recurso.nombre().to_string(),
+        tipo: recurso.tipo().as_str().to_string(),
+        activo: recurso.esta_activo(),
+    }
+}
+
+// Helper para convertir RecursoError a Status de gRPC (con código y campos en los detalles)
+fn recurso_error_to_status(error: RecursoError, idioma: Idioma) -> Status {
+    shared_errores::grpc::a_status_en(&error, idioma)
+}
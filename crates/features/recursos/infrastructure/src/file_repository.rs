@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use recursos_application::RecursoRepository;
+use recursos_domain::{Recurso, RecursoError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+
+/// Estructura para persistir los recursos en JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecursosData {
+    recursos: HashMap<String, Recurso>,
+}
+
+/// Adaptador de repositorio que guarda los recursos en un archivo JSON
+#[derive(Clone)]
+pub struct FileRecursoRepository {
+    /// Path al archivo JSON donde se guardan los recursos
+    file_path: PathBuf,
+    /// Cache en memoria para mejorar rendimiento
+    cache: Arc<RwLock<HashMap<String, Recurso>>>,
+}
+
+impl FileRecursoRepository {
+    /// Crea un nuevo repositorio de fichero
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/recursos.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/recursos.json"))
+    }
+
+    /// Carga los recursos desde el archivo JSON
+    async fn load_from_file(&self) -> Result<(), RecursoError> {
+        // Si el archivo no existe, no es un error (empezamos con datos vacíos)
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)
+            .await
+            .map_err(|e| RecursoError::ErrorRepositorio(format!("Error al leer archivo: {}", e)))?;
+
+        let data: RecursosData = serde_json::from_str(&contents)
+            .map_err(|e| RecursoError::ErrorRepositorio(format!("Error al parsear JSON: {}", e)))?;
+
+        let mut cache = self.cache.write().await;
+        *cache = data.recursos;
+
+        Ok(())
+    }
+
+    /// Guarda los recursos en el archivo JSON
+    async fn save_to_file(&self) -> Result<(), RecursoError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                RecursoError::ErrorRepositorio(format!("Error al crear directorio: {}", e))
+            })?;
+        }
+
+        let recursos = {
+            let cache = self.cache.read().await;
+            cache.clone()
+        };
+
+        let json = serde_json::to_string_pretty(&RecursosData { recursos }).map_err(|e| {
+            RecursoError::ErrorRepositorio(format!("Error al serializar JSON: {}", e))
+        })?;
+
+        fs::write(&self.file_path, json).await.map_err(|e| {
+            RecursoError::ErrorRepositorio(format!("Error al escribir archivo: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Inicializa el repositorio cargando datos del archivo
+    pub async fn init(&self) -> Result<(), RecursoError> {
+        self.load_from_file().await
+    }
+}
+
+#[async_trait]
+impl RecursoRepository for FileRecursoRepository {
+    async fn guardar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(recurso.id.clone(), recurso.clone());
+        }
+
+        self.save_to_file().await
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Recurso>, RecursoError> {
+        let cache = self.cache.read().await;
+        Ok(cache.get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Recurso>, RecursoError> {
+        let cache = self.cache.read().await;
+        Ok(cache.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+        self.guardar(recurso).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use recursos_domain::TipoRecurso;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_persistencia_en_archivo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("recursos.json");
+
+        {
+            let repo = FileRecursoRepository::new(file_path.clone());
+            repo.init().await.unwrap();
+
+            let mut recurso =
+                Recurso::new("p1".into(), "Plaza 12".into(), TipoRecurso::Aparcamiento).unwrap();
+            repo.guardar(&recurso).await.unwrap();
+            recurso.desactivar();
+            repo.actualizar(&recurso).await.unwrap();
+        }
+
+        let repo = FileRecursoRepository::new(file_path);
+        repo.init().await.unwrap();
+
+        let recurso = repo.obtener("p1").await.unwrap().unwrap();
+        assert_eq!(recurso.nombre(), "Plaza 12");
+        assert_eq!(recurso.tipo(), TipoRecurso::Aparcamiento);
+        assert!(!recurso.esta_activo());
+        assert_eq!(repo.listar().await.unwrap().len(), 1);
+    }
+}
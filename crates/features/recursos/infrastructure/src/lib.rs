@@ -0,0 +1,5 @@
+pub mod file_repository;
+pub mod memory_repository;
+
+pub use file_repository::FileRecursoRepository;
+pub use memory_repository::InMemoryRecursoRepository;
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use recursos_application::RecursoRepository;
+use recursos_domain::{Recurso, RecursoError};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+#[derive(Clone)]
+pub struct InMemoryRecursoRepository {
+    store: Arc<RwLock<HashMap<String, Recurso>>>,
+}
+
+impl InMemoryRecursoRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryRecursoRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RecursoRepository for InMemoryRecursoRepository {
+    async fn guardar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|e| RecursoError::ErrorRepositorio(format!("Error al escribir: {}", e)))?;
+        store.insert(recurso.id.clone(), recurso.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Recurso>, RecursoError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|e| RecursoError::ErrorRepositorio(format!("Error al leer: {}", e)))?;
+        Ok(store.get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Recurso>, RecursoError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|e| RecursoError::ErrorRepositorio(format!("Error al leer: {}", e)))?;
+        Ok(store.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, recurso: &Recurso) -> Result<(), RecursoError> {
+        self.guardar(recurso).await
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use reservas_domain::{EstadoReserva, ParteConjunto, Reserva};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Query `?desde=...&hasta=...` (RFC3339)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RangoFechasQuery {
+    pub desde: DateTime<Utc>,
+    pub hasta: DateTime<Utc>,
+}
+
+/// Query de exportación CSV: rango + agrupación (`sala`, `usuario` o `franja`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportarCsvQuery {
+    pub desde: DateTime<Utc>,
+    pub hasta: DateTime<Utc>,
+    #[serde(default = "agrupacion_por_defecto")]
+    pub agrupar: String,
+}
+
+fn agrupacion_por_defecto() -> String {
+    "sala".to_string()
+}
+
+/// Query de filtros, orden y paginación del listado de reservas
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ListarReservasQuery {
+    /// `activa`, `cancelada` o `completada`
+    pub estado: Option<String>,
+    pub sala_id: Option<String>,
+    /// Solo la tienen en cuenta los administradores
+    pub usuario_id: Option<String>,
+    /// Solo las reservas de ese grupo
+    pub grupo_id: Option<String>,
+    /// Reservas que terminan después de esta fecha (RFC3339)
+    pub desde: Option<DateTime<Utc>>,
+    /// Reservas que empiezan antes de esta fecha (RFC3339)
+    pub hasta: Option<DateTime<Utc>>,
+    /// `fecha_inicio` (por defecto) o `creada`
+    pub orden: Option<String>,
+    /// `asc` (por defecto) o `desc`
+    pub direccion: Option<String>,
+    #[serde(default)]
+    pub offset: u32,
+    /// Reservas por página (por defecto 50, máximo 200)
+    #[serde(default)]
+    pub limite: u32,
+}
+
+/// Reserva con las fechas en la hora local de su sala
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReservaResponse {
+    pub id: String,
+    pub sala_id: String,
+    pub usuario_id: String,
+    /// RFC3339 con el desfase de la sala
+    pub fecha_inicio: String,
+    pub fecha_fin: String,
+    pub estado: EstadoReserva,
+    pub created_at: DateTime<Utc>,
+    /// Zona IANA de la sala (UTC si la sala ya no existe o no hay sala)
+    pub zona_horaria: String,
+    /// Grupo cuyos miembros también pueden gestionarla
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grupo_id: Option<String>,
+    /// Quien la hizo: el organizador (`usuario_id`) o alguien en su nombre
+    pub creado_por: String,
+    /// Reserva conjunta de la que forma parte
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conjunto_id: Option<String>,
+    /// Recursos reservados en el mismo horario; sin `sala_id`, solo ellos
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recursos: Vec<String>,
+}
+
+impl ReservaResponse {
+    pub fn new(reserva: &Reserva, zonas: &HashMap<String, Tz>) -> Self {
+        let zona = zonas
+            .get(reserva.sala_id())
+            .copied()
+            .unwrap_or(chrono_tz::UTC);
+
+        Self {
+            id: reserva.id().to_string(),
+            sala_id: reserva.sala_id().to_string(),
+            usuario_id: reserva.usuario_id().to_string(),
+            fecha_inicio: reserva.fecha_inicio().with_timezone(&zona).to_rfc3339(),
+            fecha_fin: reserva.fecha_fin().with_timezone(&zona).to_rfc3339(),
+            estado: reserva.estado().clone(),
+            created_at: reserva.created_at(),
+            zona_horaria: zona.name().to_string(),
+            grupo_id: reserva.grupo_id().map(str::to_string),
+            creado_por: reserva.creado_por().to_string(),
+            conjunto_id: reserva.conjunto_id().map(str::to_string),
+            recursos: reserva.recursos().to_vec(),
+        }
+    }
+}
+
+/// Body de `POST /reservas/conjuntos`: varias salas, todas o ninguna
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrearReservaConjuntaRequest {
+    /// Organizador; si falta, quien hace la petición (si es otro, debe
+    /// haberle autorizado)
+    #[serde(default)]
+    pub usuario_id: Option<String>,
+    pub partes: Vec<ParteConjunto>,
+}
+
+/// Body de `PUT /reservas/conjuntos/{id}/reprogramar`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReprogramarConjuntoRequest {
+    /// Nuevo inicio de la primera reserva; el resto conserva su separación
+    pub fecha_inicio: DateTime<Utc>,
+}
@@ -0,0 +1,246 @@
+use crate::dtos::{
+    CrearReservaConjuntaRequest, ExportarCsvQuery, ListarReservasQuery, RangoFechasQuery,
+    ReprogramarConjuntoRequest, ReservaResponse,
+};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use reservas_application::{ConsultaReservas, InformeService, ReservaService};
+use reservas_domain::{AgrupacionInforme, EstadoReserva, InformeUso, Reserva, ReservaError};
+use shared_errores::rest::respuesta_problema;
+use shared_errores::ErrorCampo;
+use shared_paginacion::{Paginacion, TOTAL_HEADER};
+use std::sync::Arc;
+use tracing::log::{error, info};
+use usuarios_auth::{Autorizador, PermisoDenegado, Principal};
+
+pub type SharedInformeService = Arc<dyn InformeService>;
+pub type SharedReservaService = Arc<dyn ReservaService>;
+
+/// Lista reservas con filtros, orden y paginación
+///
+/// Sin `reserva:read_any` (en todo el sistema o sobre la sala filtrada)
+/// solo se ven las reservas propias y las de los grupos del usuario.
+/// El total sin paginar va en la cabecera `X-Total-Count`.
+pub async fn listar_reservas(
+    State(service): State<SharedReservaService>,
+    usuario: Principal,
+    Query(query): Query<ListarReservasQuery>,
+) -> Result<Response, AppError> {
+    let estado = query
+        .estado
+        .as_deref()
+        .map(|estado| {
+            EstadoReserva::from_str_opt(estado).ok_or_else(|| {
+                ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                    "estado",
+                    "RESERVA_ESTADO_INVALIDO",
+                )])
+            })
+        })
+        .transpose()?;
+    let ve_todas = Autorizador::ve_todas_las_reservas(&usuario, query.sala_id.as_deref());
+
+    let consulta = ConsultaReservas {
+        estado,
+        sala_id: query.sala_id,
+        usuario_id: query.usuario_id,
+        grupo_id: query.grupo_id,
+        desde: query.desde,
+        hasta: query.hasta,
+        paginacion: Paginacion::new(query.offset, query.limite),
+        ..Default::default()
+    }
+    .ordenada_por(
+        query.orden.as_deref().unwrap_or_default(),
+        query.direccion.as_deref().unwrap_or_default(),
+    )?
+    .visible_para(ve_todas, &usuario.user_id, &usuario.grupos);
+
+    let pagina = service.buscar_reservas(consulta).await?;
+    let zonas = service.zonas_horarias_salas().await?;
+    let response: Vec<ReservaResponse> = pagina
+        .elementos
+        .iter()
+        .map(|r| ReservaResponse::new(r, &zonas))
+        .collect();
+
+    Ok(([(TOTAL_HEADER, pagina.total.to_string())], Json(response)).into_response())
+}
+
+/// Reserva varias salas a la vez: si alguna no está libre no se crea ninguna
+///
+/// Sin `usuario_id` la reserva queda a nombre de quien la hace.
+pub async fn crear_reserva_conjunta(
+    State(service): State<SharedReservaService>,
+    usuario: Principal,
+    Json(body): Json<CrearReservaConjuntaRequest>,
+) -> Result<(StatusCode, Json<Vec<ReservaResponse>>), AppError> {
+    let organizador = body
+        .usuario_id
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| usuario.user_id.clone());
+
+    let reservas = service
+        .crear_reserva_conjunta(organizador, usuario.user_id, body.partes)
+        .await?;
+    info!("Reserva conjunta de {} salas creada", reservas.len());
+
+    Ok((
+        StatusCode::CREATED,
+        Json(respuestas(&service, &reservas).await?),
+    ))
+}
+
+/// Reservas de una reserva conjunta
+///
+/// Hay que poder gestionar o ver cada una de ellas.
+pub async fn obtener_conjunto(
+    State(service): State<SharedReservaService>,
+    usuario: Principal,
+    Path(conjunto_id): Path<String>,
+) -> Result<Json<Vec<ReservaResponse>>, AppError> {
+    let reservas = service.listar_conjunto(&conjunto_id).await?;
+    for reserva in &reservas {
+        Autorizador::exigir_ver_reserva(
+            &usuario,
+            reserva.usuario_id(),
+            reserva.grupo_id(),
+            reserva.sala_id(),
+        )?;
+    }
+
+    Ok(Json(respuestas(&service, &reservas).await?))
+}
+
+/// Cancela todas las reservas activas de una reserva conjunta
+pub async fn cancelar_conjunto(
+    State(service): State<SharedReservaService>,
+    usuario: Principal,
+    Path(conjunto_id): Path<String>,
+) -> Result<Json<Vec<ReservaResponse>>, AppError> {
+    exigir_gestion_conjunto(&service, &usuario, &conjunto_id).await?;
+
+    let reservas = service.cancelar_conjunto(&conjunto_id).await?;
+    Ok(Json(respuestas(&service, &reservas).await?))
+}
+
+/// Mueve una reserva conjunta entera: si alguna sala no está libre no se
+/// mueve ninguna
+pub async fn reprogramar_conjunto(
+    State(service): State<SharedReservaService>,
+    usuario: Principal,
+    Path(conjunto_id): Path<String>,
+    Json(body): Json<ReprogramarConjuntoRequest>,
+) -> Result<Json<Vec<ReservaResponse>>, AppError> {
+    exigir_gestion_conjunto(&service, &usuario, &conjunto_id).await?;
+
+    let reservas = service
+        .reprogramar_conjunto(&conjunto_id, body.fecha_inicio)
+        .await?;
+    Ok(Json(respuestas(&service, &reservas).await?))
+}
+
+/// Comprueba que el usuario pueda gestionar todas las reservas del conjunto
+async fn exigir_gestion_conjunto(
+    service: &SharedReservaService,
+    usuario: &Principal,
+    conjunto_id: &str,
+) -> Result<(), AppError> {
+    for reserva in service.listar_conjunto(conjunto_id).await? {
+        Autorizador::exigir_gestion_reserva(
+            usuario,
+            reserva.usuario_id(),
+            reserva.grupo_id(),
+            reserva.sala_id(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Reservas con las fechas en la hora local de cada sala
+async fn respuestas(
+    service: &SharedReservaService,
+    reservas: &[Reserva],
+) -> Result<Vec<ReservaResponse>, AppError> {
+    let zonas = service.zonas_horarias_salas().await?;
+    Ok(reservas
+        .iter()
+        .map(|r| ReservaResponse::new(r, &zonas))
+        .collect())
+}
+
+/// Informe de uso en formato JSON
+pub async fn generar_informe(
+    State(service): State<SharedInformeService>,
+    Query(query): Query<RangoFechasQuery>,
+) -> Result<Json<InformeUso>, AppError> {
+    info!("Generando informe: {} - {}", query.desde, query.hasta);
+
+    let informe = service
+        .generar_informe(query.desde, query.hasta)
+        .await
+        .map_err(|e| {
+            error!("Error al generar informe: {}", e);
+            AppError::from(e)
+        })?;
+
+    Ok(Json(informe))
+}
+
+/// Exporta una agrupación del informe en CSV
+pub async fn exportar_informe_csv(
+    State(service): State<SharedInformeService>,
+    Query(query): Query<ExportarCsvQuery>,
+) -> Result<Response, AppError> {
+    let agrupacion = AgrupacionInforme::from_str_opt(&query.agrupar).ok_or_else(|| {
+        ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+            "agrupar",
+            "INFORME_AGRUPACION_INVALIDA",
+        )])
+    })?;
+
+    let informe = service.generar_informe(query.desde, query.hasta).await?;
+    let nombre = format!(
+        "attachment; filename=\"informe_{}_{}.csv\"",
+        query.agrupar.trim().to_lowercase(),
+        query.desde.format("%Y%m%d")
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, nombre),
+        ],
+        informe.to_csv(agrupacion),
+    )
+        .into_response())
+}
+
+/// Error de los handlers: de reservas o de autorización sobre una reserva
+pub enum AppError {
+    Reserva(ReservaError),
+    Permiso(PermisoDenegado),
+}
+
+impl From<ReservaError> for AppError {
+    fn from(err: ReservaError) -> Self {
+        AppError::Reserva(err)
+    }
+}
+
+impl From<PermisoDenegado> for AppError {
+    fn from(err: PermisoDenegado) -> Self {
+        AppError::Permiso(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            AppError::Reserva(err) => respuesta_problema(err),
+            AppError::Permiso(err) => respuesta_problema(err),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub mod dtos;
+pub mod handlers;
+pub mod routes;
+
+pub use dtos::{
+    CrearReservaConjuntaRequest, ExportarCsvQuery, ListarReservasQuery, RangoFechasQuery,
+    ReprogramarConjuntoRequest, ReservaResponse,
+};
+pub use handlers::{SharedInformeService, SharedReservaService};
+pub use routes::{informes_routes_with_auth, reservas_routes_with_auth};
@@ -0,0 +1,48 @@
+use crate::handlers::{
+    cancelar_conjunto, crear_reserva_conjunta, exportar_informe_csv, generar_informe,
+    listar_reservas, obtener_conjunto, reprogramar_conjunto, SharedInformeService,
+    SharedReservaService,
+};
+use axum::middleware;
+use axum::routing::{get, post, put};
+use axum::Router;
+use usuarios_auth::rest::{requiere_autenticacion, requiere_permiso};
+use usuarios_domain::Permiso;
+
+/// Rutas de reservas
+/// - Requieren autenticación; sin `reserva:read_any` solo se ven las propias
+///   y las de los grupos del usuario
+/// - Crear una reserva conjunta requiere `reserva:create`; cancelarla o
+///   reprogramarla, poder gestionar todas sus reservas
+pub fn reservas_routes_with_auth(service: SharedReservaService) -> Router {
+    Router::new()
+        .route("/reservas", get(listar_reservas))
+        .route(
+            "/reservas/conjuntos",
+            post(crear_reserva_conjunta).layer(middleware::from_fn_with_state(
+                Permiso::CrearReservas,
+                requiere_permiso,
+            )),
+        )
+        .route("/reservas/conjuntos/{id}", get(obtener_conjunto))
+        .route("/reservas/conjuntos/{id}/cancelar", put(cancelar_conjunto))
+        .route(
+            "/reservas/conjuntos/{id}/reprogramar",
+            put(reprogramar_conjunto),
+        )
+        .layer(middleware::from_fn(requiere_autenticacion))
+        .with_state(service)
+}
+
+/// Rutas de informes de uso
+/// - Todas requieren `informe:read`
+pub fn informes_routes_with_auth(service: SharedInformeService) -> Router {
+    Router::new()
+        .route("/informes/uso", get(generar_informe))
+        .route("/informes/uso/csv", get(exportar_informe_csv))
+        .layer(middleware::from_fn_with_state(
+            Permiso::VerInformes,
+            requiere_permiso,
+        ))
+        .with_state(service)
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use reservas_domain::{EstadoReserva, Reserva, ReservaError};
+use shared_errores::ErrorCampo;
+use shared_paginacion::{Direccion, Pagina, Paginacion};
+use std::cmp::Ordering;
+
+/// Campo por el que se ordena el listado de reservas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrdenReservas {
+    #[default]
+    FechaInicio,
+    /// Fecha en la que se hizo la reserva
+    Creada,
+}
+
+impl OrdenReservas {
+    /// Crea el orden desde un string ("fecha_inicio" o "creada")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "fecha_inicio" => Some(OrdenReservas::FechaInicio),
+            "creada" => Some(OrdenReservas::Creada),
+            _ => None,
+        }
+    }
+}
+
+/// Filtros, orden y página para listar reservas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsultaReservas {
+    pub estado: Option<EstadoReserva>,
+    pub sala_id: Option<String>,
+    pub usuario_id: Option<String>,
+    /// Con `usuario_id`, incluye también las reservas de estos grupos (los
+    /// del usuario, para que vea las de su equipo)
+    pub grupos_del_usuario: Vec<String>,
+    /// Solo reservas de este grupo
+    pub grupo_id: Option<String>,
+    /// Solo reservas que terminan después de esta fecha
+    pub desde: Option<DateTime<Utc>>,
+    /// Solo reservas que empiezan antes de esta fecha
+    pub hasta: Option<DateTime<Utc>>,
+    pub orden: OrdenReservas,
+    pub direccion: Direccion,
+    pub paginacion: Paginacion,
+}
+
+impl ConsultaReservas {
+    /// Fija orden y dirección recibidos como texto; vacío deja el de por defecto
+    pub fn ordenada_por(mut self, orden: &str, direccion: &str) -> Result<Self, ReservaError> {
+        let mut errores = Vec::new();
+
+        if !orden.trim().is_empty() {
+            match OrdenReservas::from_str_opt(orden) {
+                Some(orden) => self.orden = orden,
+                None => errores.push(ErrorCampo::con_codigo("orden", "RESERVA_ORDEN_INVALIDO")),
+            }
+        }
+        if !direccion.trim().is_empty() {
+            match Direccion::from_str_opt(direccion) {
+                Some(direccion) => self.direccion = direccion,
+                None => errores.push(ErrorCampo::con_codigo(
+                    "direccion",
+                    "LISTADO_DIRECCION_INVALIDA",
+                )),
+            }
+        }
+
+        if errores.is_empty() {
+            Ok(self)
+        } else {
+            Err(ReservaError::Validacion(errores))
+        }
+    }
+
+    /// Indica si la reserva pasa los filtros
+    pub fn cumple(&self, reserva: &Reserva) -> bool {
+        self.estado.as_ref().is_none_or(|e| reserva.estado() == e)
+            && self
+                .sala_id
+                .as_deref()
+                .is_none_or(|id| reserva.sala_id() == id)
+            && self
+                .usuario_id
+                .as_deref()
+                .is_none_or(|id| reserva.usuario_id() == id || self.es_de_sus_grupos(reserva))
+            && self
+                .grupo_id
+                .as_deref()
+                .is_none_or(|id| reserva.grupo_id() == Some(id))
+            && self.desde.is_none_or(|desde| reserva.fecha_fin() > desde)
+            && self
+                .hasta
+                .is_none_or(|hasta| reserva.fecha_inicio() < hasta)
+    }
+
+    /// Limita la consulta a lo que puede ver quien la hace: si no ve todas
+    /// las reservas, solo las suyas y las de sus grupos (se ignora el
+    /// `usuario_id` pedido)
+    pub fn visible_para(mut self, ve_todas: bool, usuario_id: &str, grupos: &[String]) -> Self {
+        if !ve_todas {
+            self.usuario_id = Some(usuario_id.to_string());
+            self.grupos_del_usuario = grupos.to_vec();
+        }
+        self
+    }
+
+    fn es_de_sus_grupos(&self, reserva: &Reserva) -> bool {
+        reserva
+            .grupo_id()
+            .is_some_and(|grupo| self.grupos_del_usuario.iter().any(|g| g == grupo))
+    }
+
+    /// Compara dos reservas según el orden pedido; a igualdad decide el ID
+    /// para que las páginas sean estables
+    pub fn comparar(&self, a: &Reserva, b: &Reserva) -> Ordering {
+        let orden = match self.orden {
+            OrdenReservas::FechaInicio => a.fecha_inicio().cmp(&b.fecha_inicio()),
+            OrdenReservas::Creada => a.created_at().cmp(&b.created_at()),
+        };
+        self.direccion
+            .aplicar(orden.then_with(|| a.id().cmp(b.id())))
+    }
+
+    /// Filtra, ordena y pagina; solo se clonan las reservas de la página
+    pub fn aplicar<'a>(&self, reservas: impl IntoIterator<Item = &'a Reserva>) -> Pagina<Reserva> {
+        let mut filtradas: Vec<&Reserva> =
+            reservas.into_iter().filter(|r| self.cumple(r)).collect();
+        filtradas.sort_by(|a, b| self.comparar(a, b));
+        Pagina::desde_ordenados(filtradas, self.paginacion).map(Clone::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use shared_errores::ErrorDominio;
+
+    fn reserva(id: &str, sala: &str, dia: u32, estado: EstadoReserva) -> Reserva {
+        let inicio = Utc.with_ymd_and_hms(2030, 1, dia, 9, 0, 0).unwrap();
+        Reserva::from_existing(
+            id.to_string(),
+            sala.to_string(),
+            "u1".to_string(),
+            inicio,
+            inicio + Duration::hours(1),
+            estado,
+            Utc.with_ymd_and_hms(2029, 12, 31 - dia, 0, 0, 0).unwrap(),
+        )
+    }
+
+    fn reservas() -> Vec<Reserva> {
+        vec![
+            reserva("r1", "s1", 1, EstadoReserva::Activa),
+            reserva("r2", "s1", 2, EstadoReserva::Cancelada),
+            reserva("r3", "s2", 3, EstadoReserva::Activa),
+            reserva("r4", "s1", 4, EstadoReserva::Activa),
+        ]
+    }
+
+    fn ids(pagina: &Pagina<Reserva>) -> Vec<&str> {
+        pagina.elementos.iter().map(|r| r.id()).collect()
+    }
+
+    #[test]
+    fn filtra_por_estado_sala_y_rango() {
+        let reservas = reservas();
+        let consulta = ConsultaReservas {
+            estado: Some(EstadoReserva::Activa),
+            sala_id: Some("s1".into()),
+            desde: Some(Utc.with_ymd_and_hms(2030, 1, 1, 12, 0, 0).unwrap()),
+            ..Default::default()
+        };
+
+        let pagina = consulta.aplicar(&reservas);
+        assert_eq!(ids(&pagina), vec!["r4"]);
+        assert_eq!(pagina.total, 1);
+    }
+
+    #[test]
+    fn ordena_por_creacion_descendente_y_pagina() {
+        let reservas = reservas();
+        let consulta = ConsultaReservas {
+            paginacion: Paginacion::new(0, 2),
+            ..Default::default()
+        }
+        .ordenada_por("creada", "desc")
+        .unwrap();
+
+        // Cuanto más temprana la reserva, más tarde se creó
+        let pagina = consulta.aplicar(&reservas);
+        assert_eq!(ids(&pagina), vec!["r1", "r2"]);
+        assert_eq!(pagina.siguiente_offset(), Some(2));
+    }
+
+    #[test]
+    fn el_usuario_ve_tambien_las_de_sus_grupos() {
+        let mut reservas = reservas();
+        reservas[2].usuario_id = "u2".into();
+        reservas[2].asignar_grupo(Some("equipo".into()));
+        reservas[3].usuario_id = "u2".into();
+
+        let consulta = ConsultaReservas {
+            usuario_id: Some("u1".into()),
+            grupos_del_usuario: vec!["equipo".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&consulta.aplicar(&reservas)), vec!["r1", "r2", "r3"]);
+
+        let del_grupo = ConsultaReservas {
+            grupo_id: Some("equipo".into()),
+            ..consulta
+        };
+        assert_eq!(ids(&del_grupo.aplicar(&reservas)), vec!["r3"]);
+    }
+
+    #[test]
+    fn orden_invalido() {
+        let error = ConsultaReservas::default()
+            .ordenada_por("sala", "")
+            .unwrap_err();
+        assert_eq!(error.campos()[0].codigo, "RESERVA_ORDEN_INVALIDO");
+    }
+}
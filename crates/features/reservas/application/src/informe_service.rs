@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reservas_domain::{HorarioApertura, InformeUso, ReservaError};
+use salas_application::SalaRepository;
+use shared_errores::ErrorCampo;
+
+use crate::repository::ReservaRepository;
+
+/// Trait del servicio de informes de uso (casos de uso)
+#[async_trait]
+pub trait InformeService: Send + Sync {
+    /// Calcula ocupación, horas pico, no-shows, cancelaciones y top de usuarios
+    /// para las reservas que se solapan con el rango [desde, hasta)
+    async fn generar_informe(
+        &self,
+        desde: DateTime<Utc>,
+        hasta: DateTime<Utc>,
+    ) -> Result<InformeUso, ReservaError>;
+}
+
+/// Implementación del servicio de informes
+pub struct InformeServiceImpl<R: ReservaRepository, S: SalaRepository> {
+    repository: R,
+    sala_repository: S,
+    horario: HorarioApertura,
+}
+
+impl<R: ReservaRepository, S: SalaRepository> InformeServiceImpl<R, S> {
+    pub fn new(repository: R, sala_repository: S) -> Self {
+        Self {
+            repository,
+            sala_repository,
+            horario: HorarioApertura::default(),
+        }
+    }
+
+    /// Cambia el horario de apertura usado para calcular la ocupación
+    pub fn with_horario(mut self, horario: HorarioApertura) -> Self {
+        self.horario = horario;
+        self
+    }
+}
+
+#[async_trait]
+impl<R: ReservaRepository, S: SalaRepository> InformeService for InformeServiceImpl<R, S> {
+    async fn generar_informe(
+        &self,
+        desde: DateTime<Utc>,
+        hasta: DateTime<Utc>,
+    ) -> Result<InformeUso, ReservaError> {
+        if hasta <= desde {
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "hasta",
+                "INFORME_RANGO_INVALIDO",
+            )]));
+        }
+
+        let reservas = self.repository.listar().await?;
+        let salas = self
+            .sala_repository
+            .listar()
+            .await
+            .map_err(|e| ReservaError::ErrorRepositorio(format!("Error al listar salas: {}", e)))?
+            .into_iter()
+            .map(|s| (s.id, s.nombre, s.zona_horaria))
+            .collect::<Vec<_>>();
+
+        Ok(InformeUso::calcular(
+            desde,
+            hasta,
+            &reservas,
+            &salas,
+            self.horario,
+            Utc::now(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use reservas_domain::{EstadoReserva, Reserva};
+    use salas_domain::{Sala, SalaError};
+
+    struct MockReservaRepository {
+        reservas: Vec<Reserva>,
+    }
+
+    #[async_trait]
+    impl ReservaRepository for MockReservaRepository {
+        async fn guardar(&self, _reserva: &Reserva) -> Result<(), ReservaError> {
+            Ok(())
+        }
+
+        async fn obtener(&self, _id: &str) -> Result<Option<Reserva>, ReservaError> {
+            Ok(None)
+        }
+
+        async fn listar(&self) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(self.reservas.clone())
+        }
+
+        async fn listar_por_sala(&self, _sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(vec![])
+        }
+
+        async fn listar_por_usuario(
+            &self,
+            _usuario_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(vec![])
+        }
+
+        async fn listar_por_sala_y_rango(
+            &self,
+            _sala_id: &str,
+            _inicio: DateTime<Utc>,
+            _fin: DateTime<Utc>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(vec![])
+        }
+
+        async fn actualizar(&self, _reserva: &Reserva) -> Result<(), ReservaError> {
+            Ok(())
+        }
+
+        async fn eliminar(&self, _id: &str) -> Result<(), ReservaError> {
+            Ok(())
+        }
+    }
+
+    struct MockSalaRepository;
+
+    #[async_trait]
+    impl SalaRepository for MockSalaRepository {
+        async fn guardar(&self, _sala: &Sala) -> Result<(), SalaError> {
+            Ok(())
+        }
+
+        async fn obtener(&self, _id: &str) -> Result<Option<Sala>, SalaError> {
+            Ok(None)
+        }
+
+        async fn listar(&self) -> Result<Vec<Sala>, SalaError> {
+            Ok(vec![
+                Sala::new("sala1".to_string(), "Sala Test".to_string(), 10).unwrap(),
+                Sala::new("sala2".to_string(), "Sala Vacía".to_string(), 10).unwrap(),
+            ])
+        }
+
+        async fn actualizar(&self, _sala: &Sala) -> Result<(), SalaError> {
+            Ok(())
+        }
+
+        async fn eliminar(&self, _id: &str) -> Result<(), SalaError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn generar_informe_incluye_salas_sin_reservas() {
+        let ahora = Utc::now();
+        let reserva = Reserva::from_existing(
+            "r1".to_string(),
+            "sala1".to_string(),
+            "usuario1".to_string(),
+            ahora - Duration::hours(3),
+            ahora - Duration::hours(2),
+            EstadoReserva::Cancelada,
+            ahora - Duration::days(1),
+        );
+        let service = InformeServiceImpl::new(
+            MockReservaRepository {
+                reservas: vec![reserva],
+            },
+            MockSalaRepository,
+        );
+
+        let informe = service
+            .generar_informe(ahora - Duration::days(1), ahora)
+            .await
+            .unwrap();
+
+        assert_eq!(informe.total_reservas, 1);
+        assert_eq!(informe.tasa_cancelacion, 100.0);
+        assert_eq!(informe.por_sala.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn generar_informe_con_rango_invalido() {
+        let service = InformeServiceImpl::new(
+            MockReservaRepository { reservas: vec![] },
+            MockSalaRepository,
+        );
+        let ahora = Utc::now();
+
+        let resultado = service.generar_informe(ahora, ahora).await;
+
+        assert!(matches!(resultado, Err(ReservaError::Validacion(_))));
+    }
+}
@@ -1,5 +1,13 @@
+pub mod consulta;
+pub mod informe_service;
 pub mod repository;
+pub mod reservas_de_sala;
+pub mod reservas_de_usuario;
 pub mod service;
 
+pub use consulta::{ConsultaReservas, OrdenReservas};
+pub use informe_service::{InformeService, InformeServiceImpl};
 pub use repository::ReservaRepository;
+pub use reservas_de_sala::ReservasDeSalaAdapter;
+pub use reservas_de_usuario::ReservasDeUsuarioAdapter;
 pub use service::{ReservaService, ReservaServiceImpl};
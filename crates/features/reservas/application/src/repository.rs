@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use reservas_domain::{Reserva, ReservaError};
+use shared_paginacion::Pagina;
+
+use crate::consulta::ConsultaReservas;
 
 /// Port (interfaz) del repositorio de reservas
 #[async_trait]
@@ -14,12 +17,32 @@ pub trait ReservaRepository: Send + Sync {
     /// Lista todas las reservas
     async fn listar(&self) -> Result<Vec<Reserva>, ReservaError>;
 
+    /// Página de reservas que cumplen la consulta.
+    ///
+    /// Por defecto filtra sobre `listar`; los adaptadores que puedan filtrar
+    /// en origen deberían sobrescribirlo.
+    async fn buscar(&self, consulta: &ConsultaReservas) -> Result<Pagina<Reserva>, ReservaError> {
+        Ok(consulta.aplicar(&self.listar().await?))
+    }
+
     /// Lista reservas de una sala específica
     async fn listar_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError>;
 
     /// Lista reservas de un usuario específico
     async fn listar_por_usuario(&self, usuario_id: &str) -> Result<Vec<Reserva>, ReservaError>;
 
+    /// Lista las reservas de una reserva conjunta
+    ///
+    /// Por defecto filtra sobre `listar`.
+    async fn listar_por_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        Ok(self
+            .listar()
+            .await?
+            .into_iter()
+            .filter(|r| r.conjunto_id() == Some(conjunto_id))
+            .collect())
+    }
+
     /// Lista reservas activas de una sala en un rango de fechas
     async fn listar_por_sala_y_rango(
         &self,
@@ -28,6 +51,28 @@ pub trait ReservaRepository: Send + Sync {
         fin: DateTime<Utc>,
     ) -> Result<Vec<Reserva>, ReservaError>;
 
+    /// Lista reservas activas que ocupan un recurso en un rango de fechas
+    ///
+    /// Por defecto filtra sobre `listar`.
+    async fn listar_por_recurso_y_rango(
+        &self,
+        recurso_id: &str,
+        inicio: DateTime<Utc>,
+        fin: DateTime<Utc>,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        Ok(self
+            .listar()
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.esta_activa()
+                    && r.recursos().iter().any(|id| id == recurso_id)
+                    && r.fecha_inicio() < fin
+                    && inicio < r.fecha_fin()
+            })
+            .collect())
+    }
+
     /// Actualiza una reserva existente
     async fn actualizar(&self, reserva: &Reserva) -> Result<(), ReservaError>;
 
@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use salas_application::ReservasDeSala;
+use salas_domain::SalaError;
+use std::sync::Arc;
+
+use crate::service::ReservaService;
+
+/// Adaptador del port `ReservasDeSala` sobre el servicio de reservas
+///
+/// Cancela a través de `ReservaService`, así los decoradores que haya por
+/// encima (p. ej. los webhooks de `reserva.cancelada`) notifican cada cancelación.
+pub struct ReservasDeSalaAdapter {
+    service: Arc<dyn ReservaService>,
+}
+
+impl ReservasDeSalaAdapter {
+    pub fn new(service: Arc<dyn ReservaService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl ReservasDeSala for ReservasDeSalaAdapter {
+    async fn reservas_futuras(&self, sala_id: &str) -> Result<Vec<String>, SalaError> {
+        let ahora = Utc::now();
+        let reservas = self
+            .service
+            .listar_reservas_por_sala(sala_id)
+            .await
+            .map_err(|e| SalaError::ErrorRepositorio(e.to_string()))?;
+
+        Ok(reservas
+            .into_iter()
+            .filter(|r| r.esta_activa() && r.fecha_fin() > ahora)
+            .map(|r| r.id().to_string())
+            .collect())
+    }
+
+    async fn cancelar_reservas(&self, ids: &[String]) -> Result<(), SalaError> {
+        for id in ids {
+            self.service
+                .cancelar_reserva(id)
+                .await
+                .map_err(|e| SalaError::ErrorRepositorio(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consulta::ConsultaReservas;
+    use chrono::{DateTime, Duration};
+    use chrono_tz::Tz;
+    use reservas_domain::{EstadoReserva, ParteConjunto, Recurrencia, Reserva, ReservaError};
+    use shared_paginacion::Pagina;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    struct MockReservaService {
+        reservas: Vec<Reserva>,
+        canceladas: Mutex<Vec<String>>,
+    }
+
+    fn reserva(id: &str, desde_ahora_horas: i64, estado: EstadoReserva) -> Reserva {
+        let inicio = Utc::now() + Duration::hours(desde_ahora_horas);
+        Reserva::from_existing(
+            id.to_string(),
+            "sala1".to_string(),
+            "user1".to_string(),
+            inicio,
+            inicio + Duration::hours(1),
+            estado,
+            Utc::now(),
+        )
+    }
+
+    #[async_trait]
+    impl ReservaService for MockReservaService {
+        async fn crear_reserva(
+            &self,
+            _sala_id: String,
+            _usuario_id: String,
+            _creado_por: String,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn crear_reservas_recurrentes(
+            &self,
+            _sala_id: String,
+            _usuario_id: String,
+            _creado_por: String,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+            _recurrencia: Recurrencia,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn crear_reserva_conjunta(
+            &self,
+            _usuario_id: String,
+            _creado_por: String,
+            _partes: Vec<ParteConjunto>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn listar_conjunto(&self, _conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn cancelar_conjunto(
+            &self,
+            _conjunto_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn reprogramar_conjunto(
+            &self,
+            _conjunto_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn obtener_reserva(&self, _id: &str) -> Result<Option<Reserva>, ReservaError> {
+            Ok(None)
+        }
+
+        async fn listar_reservas(&self) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(self.reservas.clone())
+        }
+
+        async fn buscar_reservas(
+            &self,
+            consulta: ConsultaReservas,
+        ) -> Result<Pagina<Reserva>, ReservaError> {
+            Ok(consulta.aplicar(&self.reservas))
+        }
+
+        async fn listar_reservas_por_sala(
+            &self,
+            _sala_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(self.reservas.clone())
+        }
+
+        async fn listar_reservas_por_usuario(
+            &self,
+            _usuario_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(vec![])
+        }
+
+        async fn cancelar_reserva(&self, id: &str) -> Result<Reserva, ReservaError> {
+            self.canceladas.lock().unwrap().push(id.to_string());
+            Ok(reserva(id, 1, EstadoReserva::Cancelada))
+        }
+
+        async fn completar_reserva(&self, _id: &str) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn asignar_grupo(
+            &self,
+            _id: &str,
+            _grupo_id: Option<String>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn crear_reserva_con_recursos(
+            &self,
+            _sala_id: Option<String>,
+            _usuario_id: String,
+            _creado_por: String,
+            _recursos: Vec<String>,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn cambiar_recursos(
+            &self,
+            _id: &str,
+            _recursos: Vec<String>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn verificar_disponibilidad(
+            &self,
+            _sala_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<bool, ReservaError> {
+            Ok(true)
+        }
+
+        async fn verificar_disponibilidad_recurso(
+            &self,
+            _recurso_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<bool, ReservaError> {
+            Ok(true)
+        }
+
+        async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError> {
+            Ok(HashMap::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn solo_cuenta_reservas_activas_no_terminadas() {
+        let service = Arc::new(MockReservaService {
+            reservas: vec![
+                reserva("futura", 2, EstadoReserva::Activa),
+                reserva("pasada", -5, EstadoReserva::Activa),
+                reserva("cancelada", 2, EstadoReserva::Cancelada),
+            ],
+            canceladas: Mutex::new(vec![]),
+        });
+        let adapter = ReservasDeSalaAdapter::new(service.clone());
+
+        let futuras = adapter.reservas_futuras("sala1").await.unwrap();
+        assert_eq!(futuras, vec!["futura".to_string()]);
+
+        adapter.cancelar_reservas(&futuras).await.unwrap();
+        assert_eq!(
+            *service.canceladas.lock().unwrap(),
+            vec!["futura".to_string()]
+        );
+    }
+}
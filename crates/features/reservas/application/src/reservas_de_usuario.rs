@@ -0,0 +1,366 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reservas_domain::{EstadoReserva, Reserva};
+use std::sync::Arc;
+use usuarios_application::{ReservaExportada, ReservasDeUsuario};
+use usuarios_domain::UsuarioError;
+
+use crate::repository::ReservaRepository;
+use crate::service::ReservaService;
+
+/// Adaptador del port `ReservasDeUsuario` sobre las reservas
+///
+/// Cancela a través de `ReservaService`, así los decoradores que haya por
+/// encima (p. ej. los webhooks de `reserva.cancelada`) notifican cada
+/// cancelación. El seudónimo se guarda directamente en el repositorio porque
+/// no es un cambio que deba notificarse.
+pub struct ReservasDeUsuarioAdapter<R: ReservaRepository> {
+    service: Arc<dyn ReservaService>,
+    repository: R,
+}
+
+impl<R: ReservaRepository> ReservasDeUsuarioAdapter<R> {
+    pub fn new(service: Arc<dyn ReservaService>, repository: R) -> Self {
+        Self {
+            service,
+            repository,
+        }
+    }
+}
+
+fn estado(estado: &EstadoReserva) -> &'static str {
+    match estado {
+        EstadoReserva::Activa => "activa",
+        EstadoReserva::Cancelada => "cancelada",
+        EstadoReserva::Completada => "completada",
+    }
+}
+
+fn exportar(reserva: Reserva) -> ReservaExportada {
+    ReservaExportada {
+        estado: estado(reserva.estado()).to_string(),
+        id: reserva.id,
+        sala_id: reserva.sala_id,
+        fecha_inicio: reserva.fecha_inicio,
+        fecha_fin: reserva.fecha_fin,
+        created_at: reserva.created_at,
+    }
+}
+
+#[async_trait]
+impl<R: ReservaRepository> ReservasDeUsuario for ReservasDeUsuarioAdapter<R> {
+    async fn reservas(&self, usuario_id: &str) -> Result<Vec<ReservaExportada>, UsuarioError> {
+        let mut reservas = self
+            .repository
+            .listar_por_usuario(usuario_id)
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(e.to_string()))?;
+        reservas.sort_by_key(|r| r.fecha_inicio());
+
+        Ok(reservas.into_iter().map(exportar).collect())
+    }
+
+    async fn cancelar_futuras(&self, usuario_id: &str) -> Result<usize, UsuarioError> {
+        let ahora = Utc::now();
+        let reservas = self
+            .repository
+            .listar_por_usuario(usuario_id)
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(e.to_string()))?;
+
+        let mut canceladas = 0;
+        for reserva in reservas
+            .iter()
+            .filter(|r| r.esta_activa() && r.fecha_fin() > ahora)
+        {
+            self.service
+                .cancelar_reserva(reserva.id())
+                .await
+                .map_err(|e| UsuarioError::ErrorRepositorio(e.to_string()))?;
+            canceladas += 1;
+        }
+        Ok(canceladas)
+    }
+
+    async fn seudonimizar(&self, usuario_id: &str, seudonimo: &str) -> Result<usize, UsuarioError> {
+        // También las que hizo en nombre de otros
+        let reservas: Vec<Reserva> = self
+            .repository
+            .listar()
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(e.to_string()))?
+            .into_iter()
+            .filter(|r| r.usuario_id() == usuario_id || r.creado_por() == usuario_id)
+            .collect();
+
+        for mut reserva in reservas.iter().cloned() {
+            reserva.seudonimizar(usuario_id, seudonimo);
+            self.repository
+                .actualizar(&reserva)
+                .await
+                .map_err(|e| UsuarioError::ErrorRepositorio(e.to_string()))?;
+        }
+        Ok(reservas.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consulta::ConsultaReservas;
+    use chrono::{DateTime, Duration};
+    use chrono_tz::Tz;
+    use reservas_domain::{ParteConjunto, Recurrencia, ReservaError};
+    use shared_paginacion::Pagina;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct MockReservaRepository {
+        reservas: Arc<Mutex<HashMap<String, Reserva>>>,
+    }
+
+    #[async_trait]
+    impl ReservaRepository for MockReservaRepository {
+        async fn guardar(&self, reserva: &Reserva) -> Result<(), ReservaError> {
+            let mut reservas = self.reservas.lock().unwrap();
+            reservas.insert(reserva.id().to_string(), reserva.clone());
+            Ok(())
+        }
+
+        async fn obtener(&self, id: &str) -> Result<Option<Reserva>, ReservaError> {
+            Ok(self.reservas.lock().unwrap().get(id).cloned())
+        }
+
+        async fn listar(&self) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(self.reservas.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn listar_por_sala(&self, _sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn listar_por_usuario(&self, usuario_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+            let reservas = self.reservas.lock().unwrap();
+            Ok(reservas
+                .values()
+                .filter(|r| r.usuario_id() == usuario_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn listar_por_sala_y_rango(
+            &self,
+            _sala_id: &str,
+            _inicio: DateTime<Utc>,
+            _fin: DateTime<Utc>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn actualizar(&self, reserva: &Reserva) -> Result<(), ReservaError> {
+            self.guardar(reserva).await
+        }
+
+        async fn eliminar(&self, id: &str) -> Result<(), ReservaError> {
+            self.reservas.lock().unwrap().remove(id);
+            Ok(())
+        }
+    }
+
+    /// Solo cancela, sobre el mismo repositorio
+    struct MockReservaService {
+        repository: MockReservaRepository,
+    }
+
+    #[async_trait]
+    impl ReservaService for MockReservaService {
+        async fn crear_reserva(
+            &self,
+            _sala_id: String,
+            _usuario_id: String,
+            _creado_por: String,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn crear_reservas_recurrentes(
+            &self,
+            _sala_id: String,
+            _usuario_id: String,
+            _creado_por: String,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+            _recurrencia: Recurrencia,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn crear_reserva_conjunta(
+            &self,
+            _usuario_id: String,
+            _creado_por: String,
+            _partes: Vec<ParteConjunto>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn listar_conjunto(&self, _conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn cancelar_conjunto(
+            &self,
+            _conjunto_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn reprogramar_conjunto(
+            &self,
+            _conjunto_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn obtener_reserva(&self, _id: &str) -> Result<Option<Reserva>, ReservaError> {
+            Ok(None)
+        }
+
+        async fn listar_reservas(&self) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn buscar_reservas(
+            &self,
+            consulta: ConsultaReservas,
+        ) -> Result<Pagina<Reserva>, ReservaError> {
+            Ok(consulta.aplicar(&[]))
+        }
+
+        async fn listar_reservas_por_sala(
+            &self,
+            _sala_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn listar_reservas_por_usuario(
+            &self,
+            _usuario_id: &str,
+        ) -> Result<Vec<Reserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn cancelar_reserva(&self, id: &str) -> Result<Reserva, ReservaError> {
+            let mut reserva = self.repository.obtener(id).await?.unwrap();
+            reserva.cancelar();
+            self.repository.actualizar(&reserva).await?;
+            Ok(reserva)
+        }
+
+        async fn completar_reserva(&self, _id: &str) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn asignar_grupo(
+            &self,
+            _id: &str,
+            _grupo_id: Option<String>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn crear_reserva_con_recursos(
+            &self,
+            _sala_id: Option<String>,
+            _usuario_id: String,
+            _creado_por: String,
+            _recursos: Vec<String>,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn cambiar_recursos(
+            &self,
+            _id: &str,
+            _recursos: Vec<String>,
+        ) -> Result<Reserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn verificar_disponibilidad(
+            &self,
+            _sala_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<bool, ReservaError> {
+            Ok(false)
+        }
+
+        async fn verificar_disponibilidad_recurso(
+            &self,
+            _recurso_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<bool, ReservaError> {
+            Ok(true)
+        }
+
+        async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError> {
+            Ok(HashMap::new())
+        }
+    }
+
+    fn reserva(id: &str, usuario_id: &str, desde_ahora_horas: i64) -> Reserva {
+        let inicio = Utc::now() + Duration::hours(desde_ahora_horas);
+        Reserva::from_existing(
+            id.to_string(),
+            "sala1".to_string(),
+            usuario_id.to_string(),
+            inicio,
+            inicio + Duration::hours(1),
+            EstadoReserva::Activa,
+            Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn cancela_las_futuras_y_seudonimiza_todas() {
+        let repository = MockReservaRepository::default();
+        for r in [
+            reserva("futura", "ana", 2),
+            reserva("pasada", "ana", -5),
+            reserva("ajena", "luis", 2),
+            reserva("por_encargo", "luis", 3).con_creador("ana".to_string()),
+        ] {
+            repository.guardar(&r).await.unwrap();
+        }
+        let service = Arc::new(MockReservaService {
+            repository: repository.clone(),
+        });
+        let adapter = ReservasDeUsuarioAdapter::new(service, repository.clone());
+
+        let exportadas = adapter.reservas("ana").await.unwrap();
+        assert_eq!(exportadas.len(), 2);
+        assert_eq!(exportadas[0].id, "pasada");
+
+        assert_eq!(adapter.cancelar_futuras("ana").await.unwrap(), 1);
+        assert_eq!(adapter.seudonimizar("ana", "eliminado-1").await.unwrap(), 3);
+
+        assert!(adapter.reservas("ana").await.unwrap().is_empty());
+        let futura = repository.obtener("futura").await.unwrap().unwrap();
+        assert_eq!(futura.usuario_id(), "eliminado-1");
+        assert_eq!(futura.estado(), &EstadoReserva::Cancelada);
+        let ajena = repository.obtener("ajena").await.unwrap().unwrap();
+        assert!(ajena.esta_activa());
+        let por_encargo = repository.obtener("por_encargo").await.unwrap().unwrap();
+        assert_eq!(por_encargo.usuario_id(), "luis");
+        assert_eq!(por_encargo.creado_por(), "eliminado-1");
+    }
+}
@@ -1,29 +1,90 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use reservas_domain::{EstadoReserva, Reserva, ReservaError};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use recursos_application::RecursoRepository;
+use reservas_domain::{
+    validar_partes, EstadoReserva, ParteConjunto, Recurrencia, Reserva, ReservaError,
+};
 use salas_application::SalaRepository;
+use salas_domain::{Sala, MAX_MARGEN_MINUTOS};
+use shared_errores::ErrorCampo;
+use shared_paginacion::Pagina;
+use std::collections::HashMap;
+use std::sync::Arc;
 use usuarios_application::UsuarioRepository;
+use uuid::Uuid;
 
+use crate::consulta::ConsultaReservas;
 use crate::repository::ReservaRepository;
 
 /// Trait del servicio de reservas (casos de uso)
 #[async_trait]
 pub trait ReservaService: Send + Sync {
-    /// Crea una nueva reserva
+    /// Crea una nueva reserva a nombre de `usuario_id` (el organizador)
+    ///
+    /// `creado_por` es quien la hace: el propio organizador o alguien a quien
+    /// este ha autorizado a reservar en su nombre.
     async fn crear_reserva(
         &self,
         sala_id: String,
         usuario_id: String,
+        creado_por: String,
         fecha_inicio: DateTime<Utc>,
         fecha_fin: DateTime<Utc>,
     ) -> Result<Reserva, ReservaError>;
 
+    /// Crea una reserva y sus repeticiones, todas o ninguna
+    ///
+    /// Las repeticiones mantienen la hora local de la sala. Organizador y
+    /// creador como en `crear_reserva`.
+    async fn crear_reservas_recurrentes(
+        &self,
+        sala_id: String,
+        usuario_id: String,
+        creado_por: String,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+        recurrencia: Recurrencia,
+    ) -> Result<Vec<Reserva>, ReservaError>;
+
+    /// Reserva varias salas, en el mismo horario o en horarios distintos, como
+    /// una reserva conjunta: si alguna no está libre no se crea ninguna
+    ///
+    /// Organizador y creador como en `crear_reserva`.
+    async fn crear_reserva_conjunta(
+        &self,
+        usuario_id: String,
+        creado_por: String,
+        partes: Vec<ParteConjunto>,
+    ) -> Result<Vec<Reserva>, ReservaError>;
+
+    /// Reservas de una reserva conjunta, por fecha de inicio
+    async fn listar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError>;
+
+    /// Cancela todas las reservas activas de una reserva conjunta
+    async fn cancelar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError>;
+
+    /// Mueve las reservas activas de una reserva conjunta para que la primera
+    /// empiece en `fecha_inicio`, conservando la duración y la separación
+    /// entre ellas; si alguna sala no está libre no se mueve ninguna
+    async fn reprogramar_conjunto(
+        &self,
+        conjunto_id: &str,
+        fecha_inicio: DateTime<Utc>,
+    ) -> Result<Vec<Reserva>, ReservaError>;
+
     /// Obtiene una reserva por su ID
     async fn obtener_reserva(&self, id: &str) -> Result<Option<Reserva>, ReservaError>;
 
     /// Lista todas las reservas
     async fn listar_reservas(&self) -> Result<Vec<Reserva>, ReservaError>;
 
+    /// Lista una página de reservas aplicando filtros y orden
+    async fn buscar_reservas(
+        &self,
+        consulta: ConsultaReservas,
+    ) -> Result<Pagina<Reserva>, ReservaError>;
+
     /// Lista reservas de una sala específica
     async fn listar_reservas_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError>;
 
@@ -39,6 +100,37 @@ pub trait ReservaService: Send + Sync {
     /// Completa una reserva (marca como finalizada)
     async fn completar_reserva(&self, id: &str) -> Result<Reserva, ReservaError>;
 
+    /// Pone una reserva activa a nombre de un grupo (`None` la deja solo a
+    /// nombre de su usuario)
+    async fn asignar_grupo(
+        &self,
+        id: &str,
+        grupo_id: Option<String>,
+    ) -> Result<Reserva, ReservaError>;
+
+    /// Reserva recursos (proyectores, plazas de aparcamiento...) junto con
+    /// una sala o, sin `sala_id`, solo los recursos
+    ///
+    /// Cada recurso debe existir, estar activo y estar libre en ese horario.
+    /// Organizador y creador como en `crear_reserva`.
+    async fn crear_reserva_con_recursos(
+        &self,
+        sala_id: Option<String>,
+        usuario_id: String,
+        creado_por: String,
+        recursos: Vec<String>,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Reserva, ReservaError>;
+
+    /// Sustituye los recursos de una reserva activa, comprobando que los
+    /// nuevos están libres
+    async fn cambiar_recursos(
+        &self,
+        id: &str,
+        recursos: Vec<String>,
+    ) -> Result<Reserva, ReservaError>;
+
     /// Verifica disponibilidad de una sala en un rango de fechas
     async fn verificar_disponibilidad(
         &self,
@@ -46,6 +138,17 @@ pub trait ReservaService: Send + Sync {
         fecha_inicio: DateTime<Utc>,
         fecha_fin: DateTime<Utc>,
     ) -> Result<bool, ReservaError>;
+
+    /// Verifica disponibilidad de un recurso en un rango de fechas
+    async fn verificar_disponibilidad_recurso(
+        &self,
+        recurso_id: &str,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<bool, ReservaError>;
+
+    /// Zona horaria de cada sala, para mostrar las horas en local
+    async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError>;
 }
 
 /// Implementación del servicio de reservas
@@ -53,6 +156,8 @@ pub struct ReservaServiceImpl<R: ReservaRepository, S: SalaRepository, U: Usuari
     repository: R,
     sala_repository: S,
     usuario_repository: U,
+    recursos: Option<Arc<dyn RecursoRepository>>,
+    exigir_email_verificado: bool,
 }
 
 impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServiceImpl<R, S, U> {
@@ -61,47 +166,256 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
             repository,
             sala_repository,
             usuario_repository,
+            recursos: None,
+            exigir_email_verificado: false,
         }
     }
-}
 
-#[async_trait]
-impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaService
-    for ReservaServiceImpl<R, S, U>
-{
-    async fn crear_reserva(
+    /// Conecta el servicio con los recursos para poder reservarlos; sin
+    /// ellos cualquier recurso se considera inexistente
+    pub fn with_recursos(mut self, recursos: Arc<dyn RecursoRepository>) -> Self {
+        self.recursos = Some(recursos);
+        self
+    }
+
+    /// Impide reservar a los usuarios que no han verificado su email
+    pub fn with_verificacion_email(mut self, exigir: bool) -> Self {
+        self.exigir_email_verificado = exigir;
+        self
+    }
+
+    /// Comprueba que la sala existe y está activa y que el usuario existe
+    /// (y, si se exige, que ha verificado su email); si reserva otro en su
+    /// nombre, que el usuario le haya autorizado
+    async fn validar_sala_y_usuario(
         &self,
-        sala_id: String,
-        usuario_id: String,
-        fecha_inicio: DateTime<Utc>,
-        fecha_fin: DateTime<Utc>,
-    ) -> Result<Reserva, ReservaError> {
+        sala_id: &str,
+        usuario_id: &str,
+        creado_por: &str,
+    ) -> Result<Sala, ReservaError> {
+        let sala = self.validar_sala(sala_id).await?;
+        self.validar_usuario(usuario_id, creado_por).await?;
+        Ok(sala)
+    }
+
+    /// Comprueba que la sala existe y está activa
+    async fn validar_sala(&self, sala_id: &str) -> Result<Sala, ReservaError> {
         // Validar que la sala existe
         let sala = self
             .sala_repository
-            .obtener(&sala_id)
+            .obtener(sala_id)
             .await
             .map_err(|e| ReservaError::ErrorRepositorio(format!("Error al verificar sala: {}", e)))?
-            .ok_or_else(|| ReservaError::Validacion(vec!["La sala no existe".to_string()]))?;
+            .ok_or_else(|| {
+                ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                    "sala_id",
+                    "RESERVA_SALA_NO_EXISTE",
+                )])
+            })?;
 
         // Validar que la sala está activa
         if !sala.esta_activa() {
-            return Err(ReservaError::Validacion(vec![
-                "La sala no está activa".to_string()
-            ]));
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "sala_id",
+                "RESERVA_SALA_INACTIVA",
+            )]));
         }
 
+        Ok(sala)
+    }
+
+    /// Comprueba que el usuario existe (y, si se exige, que ha verificado su
+    /// email) y que, si reserva otro en su nombre, le ha autorizado
+    async fn validar_usuario(
+        &self,
+        usuario_id: &str,
+        creado_por: &str,
+    ) -> Result<(), ReservaError> {
         // Validar que el usuario existe
-        self.usuario_repository
-            .obtener(&usuario_id)
+        let usuario = self
+            .usuario_repository
+            .obtener(usuario_id)
             .await
             .map_err(|e| {
                 ReservaError::ErrorRepositorio(format!("Error al verificar usuario: {}", e))
             })?
-            .ok_or_else(|| ReservaError::Validacion(vec!["El usuario no existe".to_string()]))?;
+            .ok_or_else(|| {
+                ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                    "usuario_id",
+                    "RESERVA_USUARIO_NO_EXISTE",
+                )])
+            })?;
+
+        if self.exigir_email_verificado && !usuario.email_verificado {
+            return Err(ReservaError::EmailNoVerificado);
+        }
+
+        if creado_por != usuario_id && !usuario.admite_delegado(creado_por) {
+            return Err(ReservaError::SinDelegacion);
+        }
+
+        Ok(())
+    }
+
+    /// Comprueba que cada recurso existe y está activo
+    async fn validar_recursos(&self, ids: &[String]) -> Result<(), ReservaError> {
+        for id in ids {
+            let recurso = match &self.recursos {
+                Some(recursos) => recursos.obtener(id).await.map_err(|e| {
+                    ReservaError::ErrorRepositorio(format!("Error al verificar recurso: {}", e))
+                })?,
+                None => None,
+            };
+
+            let recurso = recurso.ok_or_else(|| {
+                ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                    "recursos",
+                    "RESERVA_RECURSO_NO_EXISTE",
+                )])
+            })?;
+            if !recurso.esta_activo() {
+                return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                    "recursos",
+                    "RESERVA_RECURSO_INACTIVO",
+                )]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Comprueba que ninguna otra reserva (salvo las de `excluir`) ocupa a la
+    /// vez alguno de los recursos de la reserva
+    async fn exigir_recursos_libres(
+        &self,
+        reserva: &Reserva,
+        excluir: &[String],
+    ) -> Result<(), ReservaError> {
+        for recurso_id in reserva.recursos() {
+            let ocupado = self
+                .repository
+                .listar_por_recurso_y_rango(recurso_id, reserva.fecha_inicio(), reserva.fecha_fin())
+                .await?
+                .iter()
+                .any(|r| r.id() != reserva.id() && !excluir.iter().any(|id| id == r.id()));
+
+            if ocupado {
+                return Err(ReservaError::RecursoNoDisponible(
+                    self.nombre_recurso(recurso_id).await,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Nombre del recurso para los mensajes de error (su ID si no se encuentra)
+    async fn nombre_recurso(&self, id: &str) -> String {
+        let recurso = match &self.recursos {
+            Some(recursos) => recursos.obtener(id).await.ok().flatten(),
+            None => None,
+        };
+        recurso.map_or_else(|| id.to_string(), |r| r.nombre)
+    }
+
+    /// Reservas activas de la reserva conjunta; error si no existe o no
+    /// queda ninguna activa
+    async fn activas_del_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        let reservas = self.listar_conjunto(conjunto_id).await?;
+        let activas: Vec<Reserva> = reservas.into_iter().filter(|r| r.esta_activa()).collect();
+        if activas.is_empty() {
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "estado",
+                "RESERVA_NO_ACTIVA",
+            )]));
+        }
+        Ok(activas)
+    }
+
+    /// Márgenes de preparación y limpieza que tiene ahora la sala (ninguno
+    /// si no existe)
+    async fn margenes_de_sala(&self, sala_id: &str) -> Result<(u32, u32), ReservaError> {
+        Ok(self
+            .sala_repository
+            .obtener(sala_id)
+            .await
+            .map_err(|e| ReservaError::ErrorRepositorio(format!("Error al verificar sala: {}", e)))?
+            .map_or((0, 0), |sala| (sala.margen_antes(), sala.margen_despues())))
+    }
+
+    /// Reservas activas de la sala que pueden chocar con la reserva contando
+    /// los márgenes: se amplía el rango con el máximo que pueden tener las
+    /// demás
+    async fn reservas_cercanas(&self, reserva: &Reserva) -> Result<Vec<Reserva>, ReservaError> {
+        let holgura = Duration::minutes(MAX_MARGEN_MINUTOS.into());
+        self.repository
+            .listar_por_sala_y_rango(
+                reserva.sala_id(),
+                reserva.inicio_ocupado() - holgura,
+                reserva.fin_ocupado() + holgura,
+            )
+            .await
+    }
+
+    /// Comprueba que cada reserva nueva o movida está libre, tanto frente a
+    /// las que ya hay (salvo las de `excluir`) como entre ellas, y que lo
+    /// están también sus recursos
+    async fn exigir_libres(
+        &self,
+        reservas: &[Reserva],
+        excluir: &[String],
+        nombres_salas: &HashMap<String, String>,
+    ) -> Result<(), ReservaError> {
+        for (i, reserva) in reservas.iter().enumerate() {
+            // Todas las reservas de la sala llevan sus márgenes actuales
+            let (antes, despues) = self.margenes_de_sala(reserva.sala_id()).await?;
+            let candidata = reserva.clone().con_margenes(antes, despues);
+            let existentes = self.reservas_cercanas(&candidata).await?;
+            let ocupada = existentes
+                .into_iter()
+                .filter(|r| !excluir.iter().any(|id| id == r.id()))
+                .chain(reservas[..i].iter().cloned())
+                .any(|r| candidata.se_solapa_con(&r.con_margenes(antes, despues)));
+
+            if ocupada {
+                let sala = nombres_salas
+                    .get(reserva.sala_id())
+                    .cloned()
+                    .unwrap_or_else(|| reserva.sala_id().to_string());
+                return Err(ReservaError::ConjuntoNoDisponible(sala));
+            }
+            self.exigir_recursos_libres(reserva, excluir).await?;
+        }
+        Ok(())
+    }
+
+    /// Nombre de cada sala, para los mensajes de error
+    async fn nombres_salas(&self) -> Result<HashMap<String, String>, ReservaError> {
+        let salas =
+            self.sala_repository.listar().await.map_err(|e| {
+                ReservaError::ErrorRepositorio(format!("Error al listar salas: {}", e))
+            })?;
+
+        Ok(salas.into_iter().map(|s| (s.id, s.nombre)).collect())
+    }
+}
+
+#[async_trait]
+impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaService
+    for ReservaServiceImpl<R, S, U>
+{
+    async fn crear_reserva(
+        &self,
+        sala_id: String,
+        usuario_id: String,
+        creado_por: String,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Reserva, ReservaError> {
+        self.validar_sala_y_usuario(&sala_id, &usuario_id, &creado_por)
+            .await?;
 
         // Crear la reserva (valida fechas, duración, etc.)
-        let reserva = Reserva::new(sala_id.clone(), usuario_id, fecha_inicio, fecha_fin)?;
+        let reserva = Reserva::new(sala_id.clone(), usuario_id, fecha_inicio, fecha_fin)?
+            .con_creador(creado_por);
 
         // Verificar disponibilidad (no debe solaparse con otras reservas activas)
         let disponible = self
@@ -109,9 +423,7 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
             .await?;
 
         if !disponible {
-            return Err(ReservaError::Validacion(vec![
-                "La sala no está disponible en el horario solicitado".to_string(),
-            ]));
+            return Err(ReservaError::SalaNoDisponible);
         }
 
         // Guardar en el repositorio
@@ -120,6 +432,123 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
         Ok(reserva)
     }
 
+    async fn crear_reservas_recurrentes(
+        &self,
+        sala_id: String,
+        usuario_id: String,
+        creado_por: String,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+        recurrencia: Recurrencia,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        let sala = self
+            .validar_sala_y_usuario(&sala_id, &usuario_id, &creado_por)
+            .await?;
+        let zona = sala.zona_horaria();
+
+        // Se valida todo antes de guardar nada
+        let mut reservas = Vec::new();
+        for (inicio, fin) in recurrencia.ocurrencias(fecha_inicio, fecha_fin, &zona) {
+            let reserva = Reserva::new(sala_id.clone(), usuario_id.clone(), inicio, fin)?
+                .con_creador(creado_por.clone());
+
+            if !self.verificar_disponibilidad(&sala_id, inicio, fin).await? {
+                return Err(ReservaError::RecurrenciaNoDisponible(
+                    inicio
+                        .with_timezone(&zona)
+                        .format("%d/%m/%Y %H:%M")
+                        .to_string(),
+                ));
+            }
+
+            reservas.push(reserva);
+        }
+
+        for reserva in &reservas {
+            self.repository.guardar(reserva).await?;
+        }
+
+        Ok(reservas)
+    }
+
+    async fn crear_reserva_conjunta(
+        &self,
+        usuario_id: String,
+        creado_por: String,
+        partes: Vec<ParteConjunto>,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        validar_partes(&partes)?;
+
+        // Se valida todo antes de guardar nada
+        let conjunto_id = Uuid::new_v4().to_string();
+        let mut reservas = Vec::with_capacity(partes.len());
+        for parte in partes {
+            self.validar_sala_y_usuario(&parte.sala_id, &usuario_id, &creado_por)
+                .await?;
+            let reserva = Reserva::new(
+                parte.sala_id,
+                usuario_id.clone(),
+                parte.fecha_inicio,
+                parte.fecha_fin,
+            )?
+            .con_creador(creado_por.clone())
+            .en_conjunto(conjunto_id.clone());
+            reservas.push(reserva);
+        }
+        self.exigir_libres(&reservas, &[], &self.nombres_salas().await?)
+            .await?;
+
+        for reserva in &reservas {
+            self.repository.guardar(reserva).await?;
+        }
+
+        Ok(reservas)
+    }
+
+    async fn listar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        let mut reservas = self.repository.listar_por_conjunto(conjunto_id).await?;
+        if reservas.is_empty() {
+            return Err(ReservaError::NoEncontrada);
+        }
+        reservas.sort_by_key(|r| r.fecha_inicio());
+        Ok(reservas)
+    }
+
+    async fn cancelar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        let mut reservas = self.activas_del_conjunto(conjunto_id).await?;
+
+        for reserva in &mut reservas {
+            reserva.cancelar();
+            self.repository.actualizar(reserva).await?;
+        }
+
+        Ok(reservas)
+    }
+
+    async fn reprogramar_conjunto(
+        &self,
+        conjunto_id: &str,
+        fecha_inicio: DateTime<Utc>,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        let mut reservas = self.activas_del_conjunto(conjunto_id).await?;
+
+        // Todas se desplazan lo mismo que la primera
+        let desplazamiento = fecha_inicio - reservas[0].fecha_inicio();
+        for reserva in &mut reservas {
+            let (inicio, fin) = (reserva.fecha_inicio(), reserva.fecha_fin());
+            reserva.reprogramar(inicio + desplazamiento, fin + desplazamiento)?;
+        }
+        let propias: Vec<String> = reservas.iter().map(|r| r.id().to_string()).collect();
+        self.exigir_libres(&reservas, &propias, &self.nombres_salas().await?)
+            .await?;
+
+        for reserva in &reservas {
+            self.repository.actualizar(reserva).await?;
+        }
+
+        Ok(reservas)
+    }
+
     async fn obtener_reserva(&self, id: &str) -> Result<Option<Reserva>, ReservaError> {
         self.repository.obtener(id).await
     }
@@ -128,6 +557,13 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
         self.repository.listar().await
     }
 
+    async fn buscar_reservas(
+        &self,
+        consulta: ConsultaReservas,
+    ) -> Result<Pagina<Reserva>, ReservaError> {
+        self.repository.buscar(&consulta).await
+    }
+
     async fn listar_reservas_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
         self.repository.listar_por_sala(sala_id).await
     }
@@ -148,9 +584,10 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
 
         // Solo se pueden cancelar reservas activas
         if !reserva.esta_activa() {
-            return Err(ReservaError::Validacion(vec![
-                "Solo se pueden cancelar reservas activas".to_string(),
-            ]));
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "estado",
+                "RESERVA_NO_ACTIVA",
+            )]));
         }
 
         reserva.cancelar();
@@ -168,9 +605,10 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
 
         // Solo se pueden completar reservas activas
         if !reserva.esta_activa() {
-            return Err(ReservaError::Validacion(vec![
-                "Solo se pueden completar reservas activas".to_string(),
-            ]));
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "estado",
+                "RESERVA_NO_ACTIVA",
+            )]));
         }
 
         reserva.completar();
@@ -179,17 +617,103 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
         Ok(reserva)
     }
 
+    async fn asignar_grupo(
+        &self,
+        id: &str,
+        grupo_id: Option<String>,
+    ) -> Result<Reserva, ReservaError> {
+        let mut reserva = self
+            .repository
+            .obtener(id)
+            .await?
+            .ok_or(ReservaError::NoEncontrada)?;
+
+        if !reserva.esta_activa() {
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "estado",
+                "RESERVA_NO_ACTIVA",
+            )]));
+        }
+
+        reserva.asignar_grupo(grupo_id);
+        self.repository.actualizar(&reserva).await?;
+
+        Ok(reserva)
+    }
+
+    async fn crear_reserva_con_recursos(
+        &self,
+        sala_id: Option<String>,
+        usuario_id: String,
+        creado_por: String,
+        recursos: Vec<String>,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Reserva, ReservaError> {
+        let reserva = match sala_id.filter(|sala_id| !sala_id.trim().is_empty()) {
+            Some(sala_id) => {
+                self.validar_sala_y_usuario(&sala_id, &usuario_id, &creado_por)
+                    .await?;
+                let reserva = Reserva::new(sala_id, usuario_id, fecha_inicio, fecha_fin)?
+                    .con_recursos(recursos);
+
+                if !self
+                    .verificar_disponibilidad(reserva.sala_id(), fecha_inicio, fecha_fin)
+                    .await?
+                {
+                    return Err(ReservaError::SalaNoDisponible);
+                }
+                reserva
+            }
+            None => {
+                self.validar_usuario(&usuario_id, &creado_por).await?;
+                Reserva::de_recursos(usuario_id, recursos, fecha_inicio, fecha_fin)?
+            }
+        }
+        .con_creador(creado_por);
+
+        self.validar_recursos(reserva.recursos()).await?;
+        self.exigir_recursos_libres(&reserva, &[]).await?;
+
+        self.repository.guardar(&reserva).await?;
+
+        Ok(reserva)
+    }
+
+    async fn cambiar_recursos(
+        &self,
+        id: &str,
+        recursos: Vec<String>,
+    ) -> Result<Reserva, ReservaError> {
+        let mut reserva = self
+            .repository
+            .obtener(id)
+            .await?
+            .ok_or(ReservaError::NoEncontrada)?;
+
+        if !reserva.esta_activa() {
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "estado",
+                "RESERVA_NO_ACTIVA",
+            )]));
+        }
+
+        reserva.cambiar_recursos(recursos)?;
+        self.validar_recursos(reserva.recursos()).await?;
+        self.exigir_recursos_libres(&reserva, &[]).await?;
+
+        self.repository.actualizar(&reserva).await?;
+
+        Ok(reserva)
+    }
+
     async fn verificar_disponibilidad(
         &self,
         sala_id: &str,
         fecha_inicio: DateTime<Utc>,
         fecha_fin: DateTime<Utc>,
     ) -> Result<bool, ReservaError> {
-        // Obtener todas las reservas activas de la sala en el rango de fechas
-        let reservas = self
-            .repository
-            .listar_por_sala_y_rango(sala_id, fecha_inicio, fecha_fin)
-            .await?;
+        let (margen_antes, margen_despues) = self.margenes_de_sala(sala_id).await?;
 
         // Crear una reserva temporal para verificar solapamientos
         // Usamos from_existing para evitar validaciones de fecha pasada
@@ -201,19 +725,49 @@ impl<R: ReservaRepository, S: SalaRepository, U: UsuarioRepository> ReservaServi
             fecha_fin,
             EstadoReserva::Activa,
             Utc::now(),
-        );
+        )
+        .con_margenes(margen_antes, margen_despues);
+
+        // Reservas activas de la sala que pueden chocar, con los márgenes
+        // actuales de la sala aunque se hicieran antes de configurarlos
+        let reservas = self.reservas_cercanas(&reserva_temporal).await?;
 
         // Verificar si hay solapamiento con alguna reserva existente
-        let hay_conflicto = reservas.iter().any(|r| reserva_temporal.se_solapa_con(r));
+        let hay_conflicto = reservas
+            .into_iter()
+            .any(|r| reserva_temporal.se_solapa_con(&r.con_margenes(margen_antes, margen_despues)));
 
         Ok(!hay_conflicto)
     }
+
+    async fn verificar_disponibilidad_recurso(
+        &self,
+        recurso_id: &str,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<bool, ReservaError> {
+        let ocupado = self
+            .repository
+            .listar_por_recurso_y_rango(recurso_id, fecha_inicio, fecha_fin)
+            .await?;
+        Ok(ocupado.is_empty())
+    }
+
+    async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError> {
+        let salas =
+            self.sala_repository.listar().await.map_err(|e| {
+                ReservaError::ErrorRepositorio(format!("Error al listar salas: {}", e))
+            })?;
+
+        Ok(salas.into_iter().map(|s| (s.id, s.zona_horaria)).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Duration;
+    use shared_errores::ErrorDominio;
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
 
@@ -241,12 +795,16 @@ mod tests {
 
         async fn obtener(
             &self,
-            _id: &str,
+            id: &str,
         ) -> Result<Option<salas_domain::Sala>, salas_domain::SalaError> {
-            // Siempre devuelve una sala válida para los tests
-            Ok(Some(
-                salas_domain::Sala::new("sala1".to_string(), "Sala Test".to_string(), 10).unwrap(),
-            ))
+            // Siempre devuelve una sala válida para los tests;
+            // "sala-con-margenes" pide 10 min antes y 15 después
+            let mut sala =
+                salas_domain::Sala::new(id.to_string(), "Sala Test".to_string(), 10).unwrap();
+            if id == "sala-con-margenes" {
+                sala.cambiar_margenes(10, 15).unwrap();
+            }
+            Ok(Some(sala))
         }
 
         async fn listar(&self) -> Result<Vec<salas_domain::Sala>, salas_domain::SalaError> {
@@ -259,6 +817,10 @@ mod tests {
         ) -> Result<(), salas_domain::SalaError> {
             Ok(())
         }
+
+        async fn eliminar(&self, _id: &str) -> Result<(), salas_domain::SalaError> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -272,13 +834,14 @@ mod tests {
 
         async fn obtener(
             &self,
-            _id: &str,
+            id: &str,
         ) -> Result<Option<usuarios_domain::Usuario>, usuarios_domain::UsuarioError> {
-            // Siempre devuelve un usuario válido para los tests
+            // Siempre devuelve un usuario válido para los tests; "directora"
+            // ha autorizado a "asistente" a reservar en su nombre
             use usuarios_domain::{Rol, Usuario};
             let now = chrono::Utc::now();
-            Ok(Some(Usuario::with_id(
-                "usuario1".to_string(),
+            let mut usuario = Usuario::with_id(
+                id.to_string(),
                 "Test User".to_string(),
                 "test@example.com".to_string(),
                 "hashed_password".to_string(),
@@ -286,7 +849,11 @@ mod tests {
                 now,
                 now,
                 true,
-            )?))
+            )?;
+            if id == "directora" {
+                usuario.autorizar_delegado("asistente")?;
+            }
+            Ok(Some(usuario))
         }
 
         async fn obtener_por_email(
@@ -403,7 +970,13 @@ mod tests {
         let fin = inicio + Duration::hours(2);
 
         let resultado = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio, fin)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+            )
             .await;
 
         assert!(resultado.is_ok());
@@ -414,39 +987,288 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_crear_reserva_con_conflicto() {
+    async fn test_exigir_email_verificado() {
         let repo = MockReservaRepository::new();
-        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
-
-        let ahora = Utc::now();
-        let inicio1 = ahora + Duration::hours(1);
-        let fin1 = inicio1 + Duration::hours(2);
-
-        // Crear primera reserva
-        let _ = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio1, fin1)
-            .await
-            .unwrap();
-
-        // Intentar crear segunda reserva que se solapa
-        let inicio2 = ahora + Duration::hours(2);
-        let fin2 = inicio2 + Duration::hours(2);
+        // El usuario del mock no ha verificado su email
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository)
+            .with_verificacion_email(true);
 
+        let inicio = Utc::now() + Duration::hours(1);
         let resultado = service
-            .crear_reserva("sala1".into(), "usuario2".into(), inicio2, fin2)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                inicio + Duration::hours(1),
+            )
             .await;
 
-        assert!(resultado.is_err());
-        match resultado.unwrap_err() {
-            ReservaError::Validacion(msgs) => {
-                assert!(msgs.iter().any(|m| m.contains("no está disponible")));
-            }
-            _ => panic!("Se esperaba error de validación"),
-        }
+        assert_eq!(resultado.unwrap_err(), ReservaError::EmailNoVerificado);
     }
 
     #[tokio::test]
-    async fn test_crear_reservas_sin_conflicto() {
+    async fn test_reservar_en_nombre_de_otro_exige_delegacion() {
+        use reservas_domain::Frecuencia;
+
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let inicio = Utc::now() + Duration::hours(1);
+        let reserva = service
+            .crear_reserva(
+                "sala1".into(),
+                "directora".into(),
+                "asistente".into(),
+                inicio,
+                inicio + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(reserva.usuario_id(), "directora");
+        assert_eq!(reserva.creado_por(), "asistente");
+
+        let resultado = service
+            .crear_reservas_recurrentes(
+                "sala1".into(),
+                "directora".into(),
+                "usuario1".into(),
+                inicio + Duration::hours(2),
+                inicio + Duration::hours(3),
+                Recurrencia::new(Frecuencia::Diaria, 2).unwrap(),
+            )
+            .await;
+        assert_eq!(resultado.unwrap_err(), ReservaError::SinDelegacion);
+    }
+
+    #[tokio::test]
+    async fn test_crear_reserva_con_conflicto() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let ahora = Utc::now();
+        let inicio1 = ahora + Duration::hours(1);
+        let fin1 = inicio1 + Duration::hours(2);
+
+        // Crear primera reserva
+        let _ = service
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio1,
+                fin1,
+            )
+            .await
+            .unwrap();
+
+        // Intentar crear segunda reserva que se solapa
+        let inicio2 = ahora + Duration::hours(2);
+        let fin2 = inicio2 + Duration::hours(2);
+
+        let resultado = service
+            .crear_reserva(
+                "sala1".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                inicio2,
+                fin2,
+            )
+            .await;
+
+        assert!(resultado.is_err());
+        match resultado.unwrap_err() {
+            ReservaError::SalaNoDisponible => {}
+            e => panic!("Se esperaba SalaNoDisponible, se obtuvo {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crear_reservas_recurrentes() {
+        use reservas_domain::Frecuencia;
+
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(1);
+        let recurrencia = Recurrencia::new(Frecuencia::Semanal, 3).unwrap();
+
+        let reservas = service
+            .crear_reservas_recurrentes(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+                recurrencia,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(reservas.len(), 3);
+        assert_eq!(service.listar_reservas().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reservas_recurrentes_con_conflicto_no_guarda_ninguna() {
+        use reservas_domain::Frecuencia;
+
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(1);
+
+        // Ocupa el hueco de la tercera repetición diaria
+        service
+            .crear_reserva(
+                "sala1".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                inicio + Duration::days(2),
+                fin + Duration::days(2),
+            )
+            .await
+            .unwrap();
+
+        let resultado = service
+            .crear_reservas_recurrentes(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+                Recurrencia::new(Frecuencia::Diaria, 5).unwrap(),
+            )
+            .await;
+
+        assert!(matches!(
+            resultado,
+            Err(ReservaError::RecurrenciaNoDisponible(_))
+        ));
+        assert_eq!(service.listar_reservas().await.unwrap().len(), 1);
+    }
+
+    fn parte(sala_id: &str, inicio: DateTime<Utc>, horas: i64) -> ParteConjunto {
+        ParteConjunto {
+            sala_id: sala_id.to_string(),
+            fecha_inicio: inicio,
+            fecha_fin: inicio + Duration::hours(horas),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reserva_conjunta_todas_o_ninguna() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+        let inicio = Utc::now() + Duration::hours(1);
+
+        service
+            .crear_reserva(
+                "grupos-b".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                inicio + Duration::hours(2),
+                inicio + Duration::hours(3),
+            )
+            .await
+            .unwrap();
+
+        // La segunda sala de grupos está ocupada: no se crea ninguna
+        let partes = vec![
+            parte("principal", inicio, 4),
+            parte("grupos-a", inicio + Duration::hours(2), 2),
+            parte("grupos-b", inicio + Duration::hours(2), 2),
+        ];
+        assert!(matches!(
+            service
+                .crear_reserva_conjunta("usuario1".into(), "usuario1".into(), partes.clone())
+                .await,
+            Err(ReservaError::ConjuntoNoDisponible(_))
+        ));
+        assert_eq!(service.listar_reservas().await.unwrap().len(), 1);
+
+        // Dos partes que se solapan en la misma sala tampoco
+        let repetida = vec![parte("principal", inicio, 2), parte("principal", inicio, 1)];
+        assert!(service
+            .crear_reserva_conjunta("usuario1".into(), "usuario1".into(), repetida)
+            .await
+            .is_err());
+
+        let mut partes = partes;
+        partes[2].sala_id = "grupos-c".to_string();
+        let reservas = service
+            .crear_reserva_conjunta("usuario1".into(), "usuario1".into(), partes)
+            .await
+            .unwrap();
+        assert_eq!(reservas.len(), 3);
+        let conjunto_id = reservas[0].conjunto_id().unwrap().to_string();
+        assert!(reservas
+            .iter()
+            .all(|r| r.conjunto_id() == Some(conjunto_id.as_str())));
+        assert_eq!(
+            service.listar_conjunto(&conjunto_id).await.unwrap().len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reprogramar_y_cancelar_conjunto() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+        let inicio = Utc::now() + Duration::hours(1);
+
+        let reservas = service
+            .crear_reserva_conjunta(
+                "usuario1".into(),
+                "usuario1".into(),
+                vec![
+                    parte("principal", inicio, 4),
+                    parte("grupos-a", inicio + Duration::hours(2), 2),
+                ],
+            )
+            .await
+            .unwrap();
+        let conjunto_id = reservas[0].conjunto_id().unwrap().to_string();
+
+        // Mañana la sala de grupos está ocupada a la hora de la sesión
+        let manana = inicio + Duration::days(1);
+        service
+            .crear_reserva(
+                "grupos-a".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                manana + Duration::hours(2),
+                manana + Duration::hours(3),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            service.reprogramar_conjunto(&conjunto_id, manana).await,
+            Err(ReservaError::ConjuntoNoDisponible(_))
+        ));
+
+        // Una hora más tarde cabe: se mueven las dos, incluso sobre su propio hueco
+        let movidas = service
+            .reprogramar_conjunto(&conjunto_id, inicio + Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(movidas[0].fecha_inicio(), inicio + Duration::hours(1));
+        assert_eq!(movidas[1].fecha_inicio(), inicio + Duration::hours(3));
+        assert_eq!(movidas[1].duracion_minutos(), 120);
+
+        let canceladas = service.cancelar_conjunto(&conjunto_id).await.unwrap();
+        assert!(canceladas.iter().all(|r| !r.esta_activa()));
+        assert!(service.cancelar_conjunto(&conjunto_id).await.is_err());
+        assert_eq!(
+            service.listar_conjunto("no-existe").await.unwrap_err(),
+            ReservaError::NoEncontrada
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crear_reservas_sin_conflicto() {
         let repo = MockReservaRepository::new();
         let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
 
@@ -456,7 +1278,13 @@ mod tests {
 
         // Crear primera reserva
         let _ = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio1, fin1)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio1,
+                fin1,
+            )
             .await
             .unwrap();
 
@@ -465,7 +1293,13 @@ mod tests {
         let fin2 = inicio2 + Duration::hours(1);
 
         let resultado = service
-            .crear_reserva("sala1".into(), "usuario2".into(), inicio2, fin2)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                inicio2,
+                fin2,
+            )
             .await;
 
         assert!(resultado.is_ok());
@@ -481,7 +1315,13 @@ mod tests {
         let fin = inicio + Duration::hours(2);
 
         let reserva = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio, fin)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+            )
             .await
             .unwrap();
 
@@ -505,7 +1345,13 @@ mod tests {
         let fin = inicio + Duration::hours(2);
 
         let reserva = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio, fin)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+            )
             .await
             .unwrap();
 
@@ -519,6 +1365,35 @@ mod tests {
         assert_eq!(reserva_completada.estado(), &EstadoReserva::Completada);
     }
 
+    #[tokio::test]
+    async fn test_asignar_grupo_solo_a_reservas_activas() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let inicio = Utc::now() + Duration::hours(1);
+        let reserva = service
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                inicio + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+
+        let asignada = service
+            .asignar_grupo(reserva.id(), Some("equipo".into()))
+            .await
+            .unwrap();
+        assert_eq!(asignada.grupo_id(), Some("equipo"));
+        let guardada = service.obtener_reserva(reserva.id()).await.unwrap();
+        assert_eq!(guardada.unwrap().grupo_id(), Some("equipo"));
+
+        service.cancelar_reserva(reserva.id()).await.unwrap();
+        assert!(service.asignar_grupo(reserva.id(), None).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_no_cancelar_reserva_ya_cancelada() {
         let repo = MockReservaRepository::new();
@@ -529,7 +1404,13 @@ mod tests {
         let fin = inicio + Duration::hours(2);
 
         let reserva = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio, fin)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+            )
             .await
             .unwrap();
 
@@ -555,6 +1436,7 @@ mod tests {
             .crear_reserva(
                 "sala1".into(),
                 "usuario1".into(),
+                "usuario1".into(),
                 ahora + Duration::hours(1),
                 ahora + Duration::hours(2),
             )
@@ -565,6 +1447,7 @@ mod tests {
             .crear_reserva(
                 "sala1".into(),
                 "usuario2".into(),
+                "usuario2".into(),
                 ahora + Duration::hours(3),
                 ahora + Duration::hours(4),
             )
@@ -576,6 +1459,7 @@ mod tests {
             .crear_reserva(
                 "sala2".into(),
                 "usuario1".into(),
+                "usuario1".into(),
                 ahora + Duration::hours(1),
                 ahora + Duration::hours(2),
             )
@@ -601,6 +1485,7 @@ mod tests {
             .crear_reserva(
                 "sala1".into(),
                 "usuario1".into(),
+                "usuario1".into(),
                 ahora + Duration::hours(1),
                 ahora + Duration::hours(2),
             )
@@ -611,6 +1496,7 @@ mod tests {
             .crear_reserva(
                 "sala2".into(),
                 "usuario1".into(),
+                "usuario1".into(),
                 ahora + Duration::hours(3),
                 ahora + Duration::hours(4),
             )
@@ -622,6 +1508,7 @@ mod tests {
             .crear_reserva(
                 "sala1".into(),
                 "usuario2".into(),
+                "usuario2".into(),
                 ahora + Duration::hours(5),
                 ahora + Duration::hours(6),
             )
@@ -652,7 +1539,13 @@ mod tests {
 
         // Crear reserva
         let _ = service
-            .crear_reserva("sala1".into(), "usuario1".into(), inicio1, fin1)
+            .crear_reserva(
+                "sala1".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio1,
+                fin1,
+            )
             .await
             .unwrap();
 
@@ -678,4 +1571,250 @@ mod tests {
             .unwrap();
         assert!(disponible);
     }
+
+    #[tokio::test]
+    async fn test_margenes_de_sala_entre_reservas() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(1);
+        let reserva = service
+            .crear_reserva(
+                "sala-con-margenes".into(),
+                "usuario1".into(),
+                "usuario1".into(),
+                inicio,
+                fin,
+            )
+            .await
+            .unwrap();
+        // Se guardan las horas pedidas, no las ampliadas
+        assert_eq!((reserva.fecha_inicio(), reserva.fecha_fin()), (inicio, fin));
+
+        // Justo a continuación: la limpieza (15) y la preparación (10) chocan
+        let resultado = service
+            .crear_reserva(
+                "sala-con-margenes".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                fin,
+                fin + Duration::hours(1),
+            )
+            .await;
+        assert!(matches!(resultado, Err(ReservaError::SalaNoDisponible)));
+
+        let libre_desde = fin + Duration::minutes(25);
+        assert!(!service
+            .verificar_disponibilidad(
+                "sala-con-margenes",
+                libre_desde - Duration::minutes(1),
+                libre_desde + Duration::hours(1),
+            )
+            .await
+            .unwrap());
+        assert!(service
+            .verificar_disponibilidad(
+                "sala-con-margenes",
+                libre_desde,
+                libre_desde + Duration::hours(1),
+            )
+            .await
+            .unwrap());
+
+        // Antes de la reserva pasa lo mismo con la preparación
+        assert!(!service
+            .verificar_disponibilidad(
+                "sala-con-margenes",
+                inicio - Duration::hours(1),
+                inicio - Duration::minutes(20),
+            )
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_margenes_configurados_despues_de_una_reserva() {
+        let repo = MockReservaRepository::new();
+        let service = ReservaServiceImpl::new(repo, MockSalaRepository, MockUsuarioRepository);
+
+        // Reserva hecha cuando la sala aún no tenía márgenes
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(1);
+        let anterior =
+            Reserva::new("sala-con-margenes".into(), "usuario1".into(), inicio, fin).unwrap();
+        service.repository.guardar(&anterior).await.unwrap();
+
+        // Los márgenes actuales (10 y 15) también la cubren a ella
+        let resultado = service
+            .crear_reserva(
+                "sala-con-margenes".into(),
+                "usuario2".into(),
+                "usuario2".into(),
+                fin + Duration::minutes(20),
+                fin + Duration::hours(1),
+            )
+            .await;
+        assert!(matches!(resultado, Err(ReservaError::SalaNoDisponible)));
+
+        let resultado = service
+            .crear_reserva_conjunta(
+                "usuario2".into(),
+                "usuario2".into(),
+                vec![parte("sala-con-margenes", fin + Duration::minutes(20), 1)],
+            )
+            .await;
+        assert!(matches!(
+            resultado,
+            Err(ReservaError::ConjuntoNoDisponible(_))
+        ));
+
+        let libre_desde = fin + Duration::minutes(25);
+        assert!(!service
+            .verificar_disponibilidad(
+                "sala-con-margenes",
+                libre_desde - Duration::minutes(1),
+                libre_desde + Duration::hours(1),
+            )
+            .await
+            .unwrap());
+        assert!(service
+            .verificar_disponibilidad(
+                "sala-con-margenes",
+                libre_desde,
+                libre_desde + Duration::hours(1),
+            )
+            .await
+            .unwrap());
+    }
+
+    /// "proyector" y "plaza-3" están activos; "portatil" no
+    struct MockRecursoRepository;
+
+    #[async_trait]
+    impl RecursoRepository for MockRecursoRepository {
+        async fn guardar(
+            &self,
+            _recurso: &recursos_domain::Recurso,
+        ) -> Result<(), recursos_domain::RecursoError> {
+            Ok(())
+        }
+
+        async fn obtener(
+            &self,
+            id: &str,
+        ) -> Result<Option<recursos_domain::Recurso>, recursos_domain::RecursoError> {
+            use recursos_domain::{Recurso, TipoRecurso};
+            let recurso = match id {
+                "proyector" | "portatil" => Recurso::new(
+                    id.to_string(),
+                    format!("Recurso {}", id),
+                    TipoRecurso::Equipo,
+                )?,
+                "plaza-3" => Recurso::new(
+                    id.to_string(),
+                    "Plaza 3".to_string(),
+                    TipoRecurso::Aparcamiento,
+                )?,
+                _ => return Ok(None),
+            };
+            let mut recurso = recurso;
+            if id == "portatil" {
+                recurso.desactivar();
+            }
+            Ok(Some(recurso))
+        }
+
+        async fn listar(
+            &self,
+        ) -> Result<Vec<recursos_domain::Recurso>, recursos_domain::RecursoError> {
+            Ok(vec![])
+        }
+
+        async fn actualizar(
+            &self,
+            _recurso: &recursos_domain::Recurso,
+        ) -> Result<(), recursos_domain::RecursoError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reservar_recursos_con_y_sin_sala() {
+        let service = ReservaServiceImpl::new(
+            MockReservaRepository::new(),
+            MockSalaRepository,
+            MockUsuarioRepository,
+        )
+        .with_recursos(Arc::new(MockRecursoRepository));
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(2);
+
+        let con_sala = service
+            .crear_reserva_con_recursos(
+                Some("sala1".into()),
+                "usuario1".into(),
+                "usuario1".into(),
+                vec!["proyector".into()],
+                inicio,
+                fin,
+            )
+            .await
+            .unwrap();
+        assert_eq!(con_sala.recursos(), ["proyector"]);
+
+        // El proyector ya está ocupado aunque la otra reserva sea sin sala
+        let error = service
+            .crear_reserva_con_recursos(
+                None,
+                "usuario2".into(),
+                "usuario2".into(),
+                vec!["plaza-3".into(), "proyector".into()],
+                inicio + Duration::hours(1),
+                fin + Duration::hours(1),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ReservaError::RecursoNoDisponible("Recurso proyector".into())
+        );
+        assert!(!service
+            .verificar_disponibilidad_recurso("proyector", inicio, fin)
+            .await
+            .unwrap());
+
+        let solo_plaza = service
+            .crear_reserva_con_recursos(
+                None,
+                "usuario2".into(),
+                "usuario2".into(),
+                vec!["plaza-3".into()],
+                inicio,
+                fin,
+            )
+            .await
+            .unwrap();
+        assert!(!solo_plaza.tiene_sala());
+
+        // Inexistentes e inactivos se rechazan por campo
+        for recurso in ["no-existe", "portatil"] {
+            let error = service
+                .cambiar_recursos(con_sala.id(), vec![recurso.into()])
+                .await
+                .unwrap_err();
+            assert!(error.campos().iter().any(|c| c.campo == "recursos"));
+        }
+
+        // Quitar el proyector lo deja libre para otros
+        let sin_recursos = service
+            .cambiar_recursos(con_sala.id(), vec![])
+            .await
+            .unwrap();
+        assert!(sin_recursos.recursos().is_empty());
+        assert!(service
+            .verificar_disponibilidad_recurso("proyector", inicio, fin)
+            .await
+            .unwrap());
+    }
 }
@@ -0,0 +1,52 @@
+use crate::error::ReservaError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Máximo de salas en una reserva conjunta
+pub const MAX_PARTES_CONJUNTO: usize = 10;
+
+/// Una de las salas de una reserva conjunta, con su propio horario
+///
+/// Una formación puede reservar la sala principal y dos salas de grupos a la
+/// vez o en horarios distintos; se reservan todas o ninguna.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParteConjunto {
+    pub sala_id: String,
+    pub fecha_inicio: DateTime<Utc>,
+    pub fecha_fin: DateTime<Utc>,
+}
+
+/// Comprueba que el conjunto tiene entre 1 y `MAX_PARTES_CONJUNTO` salas
+pub fn validar_partes(partes: &[ParteConjunto]) -> Result<(), ReservaError> {
+    if partes.is_empty() || partes.len() > MAX_PARTES_CONJUNTO {
+        return Err(ReservaError::ConjuntoInvalido {
+            max: MAX_PARTES_CONJUNTO,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn entre_una_y_el_maximo_de_salas() {
+        let inicio = Utc::now() + Duration::hours(1);
+        let parte = ParteConjunto {
+            sala_id: "sala1".to_string(),
+            fecha_inicio: inicio,
+            fecha_fin: inicio + Duration::hours(1),
+        };
+
+        assert!(validar_partes(std::slice::from_ref(&parte)).is_ok());
+        assert_eq!(
+            validar_partes(&[]),
+            Err(ReservaError::ConjuntoInvalido {
+                max: MAX_PARTES_CONJUNTO
+            })
+        );
+        assert!(validar_partes(&vec![parte; MAX_PARTES_CONJUNTO + 1]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use shared_errores::{Categoria, ErrorCampo, ErrorDominio, Idioma};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -9,37 +10,103 @@ pub enum ReservaError {
     FechaFinAnteriorAInicio,
     DuracionInvalida,
     NoEncontrada,
+    /// El horario se solapa con otra reserva activa de la sala
+    SalaNoDisponible,
+    /// Número de repeticiones fuera de 1..=max
+    RecurrenciaInvalida {
+        max: u32,
+    },
+    /// Una de las repeticiones choca con otra reserva (fecha local de la sala)
+    RecurrenciaNoDisponible(String),
+    /// Número de salas de una reserva conjunta fuera de 1..=max
+    ConjuntoInvalido {
+        max: usize,
+    },
+    /// Una de las salas de la reserva conjunta no está libre (nombre de la sala)
+    ConjuntoNoDisponible(String),
+    /// Uno de los recursos está ocupado en ese horario (nombre del recurso)
+    RecursoNoDisponible(String),
+    /// El usuario debe verificar su email antes de reservar
+    EmailNoVerificado,
+    /// Quien reserva no es el organizador ni está autorizado por él
+    SinDelegacion,
     ErrorRepositorio(String),
-    Validacion(Vec<String>),
+    Validacion(Vec<ErrorCampo>),
 }
 
+/// El texto sale del catálogo de mensajes en español
 impl fmt::Display for ReservaError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje_en(Idioma::Es))
+    }
+}
+
+impl std::error::Error for ReservaError {}
+
+impl ErrorDominio for ReservaError {
+    fn codigo(&self) -> &'static str {
         match self {
-            ReservaError::SalaIdVacio => write!(f, "El ID de sala no puede estar vacío"),
-            ReservaError::UsuarioIdVacio => write!(f, "El ID de usuario no puede estar vacío"),
-            ReservaError::FechaInicioInvalida => {
-                write!(f, "La fecha de inicio no puede ser en el pasado")
-            }
-            ReservaError::FechaFinInvalida => {
-                write!(f, "La fecha de fin no puede ser en el pasado")
-            }
-            ReservaError::FechaFinAnteriorAInicio => {
-                write!(f, "La fecha de fin debe ser posterior a la fecha de inicio")
-            }
-            ReservaError::DuracionInvalida => {
-                write!(
-                    f,
-                    "La duración de la reserva debe ser entre 15 minutos y 8 horas"
-                )
-            }
-            ReservaError::NoEncontrada => write!(f, "Reserva no encontrada"),
-            ReservaError::ErrorRepositorio(msg) => write!(f, "Error en repositorio: {}", msg),
-            ReservaError::Validacion(msgs) => {
-                write!(f, "Errores de validación: {}", msgs.join("; "))
+            ReservaError::SalaIdVacio => "RESERVA_SALA_ID_VACIO",
+            ReservaError::UsuarioIdVacio => "RESERVA_USUARIO_ID_VACIO",
+            ReservaError::FechaInicioInvalida => "RESERVA_FECHA_INICIO_PASADA",
+            ReservaError::FechaFinInvalida => "RESERVA_FECHA_FIN_PASADA",
+            ReservaError::FechaFinAnteriorAInicio => "RESERVA_FECHA_FIN_ANTERIOR_A_INICIO",
+            ReservaError::DuracionInvalida => "RESERVA_DURACION_INVALIDA",
+            ReservaError::NoEncontrada => "RESERVA_NO_ENCONTRADA",
+            ReservaError::SalaNoDisponible => "RESERVA_SALA_NO_DISPONIBLE",
+            ReservaError::RecurrenciaInvalida { .. } => "RESERVA_RECURRENCIA_INVALIDA",
+            ReservaError::RecurrenciaNoDisponible(_) => "RESERVA_RECURRENCIA_NO_DISPONIBLE",
+            ReservaError::ConjuntoInvalido { .. } => "RESERVA_CONJUNTO_INVALIDO",
+            ReservaError::ConjuntoNoDisponible(_) => "RESERVA_CONJUNTO_NO_DISPONIBLE",
+            ReservaError::RecursoNoDisponible(_) => "RESERVA_RECURSO_NO_DISPONIBLE",
+            ReservaError::EmailNoVerificado => "RESERVA_EMAIL_NO_VERIFICADO",
+            ReservaError::SinDelegacion => "RESERVA_SIN_DELEGACION",
+            ReservaError::ErrorRepositorio(_) => "RESERVA_ERROR_REPOSITORIO",
+            ReservaError::Validacion(_) => "RESERVA_VALIDACION",
+        }
+    }
+
+    fn categoria(&self) -> Categoria {
+        match self {
+            ReservaError::NoEncontrada => Categoria::NoEncontrado,
+            ReservaError::SalaNoDisponible
+            | ReservaError::RecurrenciaNoDisponible(_)
+            | ReservaError::ConjuntoNoDisponible(_)
+            | ReservaError::RecursoNoDisponible(_) => Categoria::Conflicto,
+            ReservaError::EmailNoVerificado | ReservaError::SinDelegacion => {
+                Categoria::PermisoDenegado
             }
+            ReservaError::ErrorRepositorio(_) => Categoria::Interno,
+            _ => Categoria::Validacion,
         }
     }
-}
 
-impl std::error::Error for ReservaError {}
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ReservaError::ErrorRepositorio(msg) => vec![("detalle", msg.clone())],
+            ReservaError::RecurrenciaInvalida { max } => vec![("max", max.to_string())],
+            ReservaError::RecurrenciaNoDisponible(fecha) => vec![("fecha", fecha.clone())],
+            ReservaError::ConjuntoInvalido { max } => vec![("max", max.to_string())],
+            ReservaError::ConjuntoNoDisponible(sala) => vec![("sala", sala.clone())],
+            ReservaError::RecursoNoDisponible(recurso) => vec![("recurso", recurso.clone())],
+            _ => Vec::new(),
+        }
+    }
+
+    fn campos(&self) -> Vec<ErrorCampo> {
+        let campo = match self {
+            ReservaError::SalaIdVacio => "sala_id",
+            ReservaError::UsuarioIdVacio | ReservaError::SinDelegacion => "usuario_id",
+            ReservaError::FechaInicioInvalida => "fecha_inicio",
+            ReservaError::FechaFinInvalida
+            | ReservaError::FechaFinAnteriorAInicio
+            | ReservaError::DuracionInvalida => "fecha_fin",
+            ReservaError::RecurrenciaInvalida { .. } => "repeticiones",
+            ReservaError::ConjuntoInvalido { .. } => "partes",
+            ReservaError::Validacion(errores) => return errores.clone(),
+            _ => return Vec::new(),
+        };
+
+        vec![ErrorCampo::new(campo, self.codigo(), self.to_string())]
+    }
+}
@@ -0,0 +1,754 @@
+use crate::reserva::{EstadoReserva, Reserva};
+use crate::zona::local_a_utc;
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Número de franjas que se devuelven como horas pico
+pub const NUM_HORAS_PICO: usize = 5;
+
+/// Número de usuarios que se devuelven en el ranking de reservadores
+pub const NUM_TOP_USUARIOS: usize = 10;
+
+const DIAS_SEMANA: [&str; 7] = [
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+    "domingo",
+];
+
+/// Horario de apertura usado como base para calcular la ocupación
+///
+/// Las horas son locales: cada sala abre y cierra según su zona horaria.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HorarioApertura {
+    /// Hora de apertura (0-23)
+    pub apertura: u32,
+    /// Hora de cierre (1-24)
+    pub cierre: u32,
+}
+
+impl Default for HorarioApertura {
+    fn default() -> Self {
+        Self {
+            apertura: 8,
+            cierre: 20,
+        }
+    }
+}
+
+impl HorarioApertura {
+    /// Horas abiertas dentro del rango [desde, hasta), con el horario en UTC
+    pub fn horas_disponibles(&self, desde: DateTime<Utc>, hasta: DateTime<Utc>) -> f64 {
+        self.horas_disponibles_en(desde, hasta, &chrono_tz::UTC)
+    }
+
+    /// Horas abiertas dentro del rango [desde, hasta) para una sala en `zona`
+    ///
+    /// Los días se recorren en hora local, así que un cambio de horario dentro
+    /// del horario de apertura suma o resta la hora correspondiente.
+    pub fn horas_disponibles_en(
+        &self,
+        desde: DateTime<Utc>,
+        hasta: DateTime<Utc>,
+        zona: &Tz,
+    ) -> f64 {
+        let mut total = Duration::zero();
+        let mut dia = desde.with_timezone(zona).date_naive();
+        let ultimo = hasta.with_timezone(zona).date_naive();
+
+        while dia <= ultimo {
+            let (apertura, cierre) = self.en_dia(dia, zona);
+
+            let inicio = apertura.max(desde);
+            let fin = cierre.min(hasta);
+            if fin > inicio {
+                total += fin - inicio;
+            }
+
+            dia += Duration::days(1);
+        }
+
+        horas(total)
+    }
+
+    /// Apertura y cierre (en UTC) de un día local de la sala
+    pub fn en_dia(&self, dia: NaiveDate, zona: &Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+        let apertura = local_a_utc(zona, dia.and_time(hora(self.apertura)));
+        let cierre = if self.cierre >= 24 {
+            local_a_utc(zona, (dia + Duration::days(1)).and_time(NaiveTime::MIN))
+        } else {
+            local_a_utc(zona, dia.and_time(hora(self.cierre)))
+        };
+
+        (apertura, cierre)
+    }
+}
+
+fn hora(h: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h.min(23), 0, 0).unwrap_or(NaiveTime::MIN)
+}
+
+fn horas(duracion: Duration) -> f64 {
+    duracion.num_minutes() as f64 / 60.0
+}
+
+/// Porcentaje redondeado a dos decimales (0 si el total es 0)
+fn porcentaje(parte: f64, total: f64) -> f64 {
+    if total <= 0.0 {
+        return 0.0;
+    }
+    (parte / total * 10_000.0).round() / 100.0
+}
+
+fn redondear(valor: f64) -> f64 {
+    (valor * 100.0).round() / 100.0
+}
+
+/// Métricas de uso de una sala
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricasSala {
+    pub sala_id: String,
+    pub sala_nombre: Option<String>,
+    pub total_reservas: usize,
+    pub canceladas: usize,
+    pub no_shows: usize,
+    pub horas_reservadas: f64,
+    /// Horas reservadas dentro del horario de apertura sobre las horas de
+    /// apertura del rango (%); nunca supera el 100%
+    pub tasa_ocupacion: f64,
+}
+
+/// Métricas de uso de un usuario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricasUsuario {
+    pub usuario_id: String,
+    pub total_reservas: usize,
+    pub canceladas: usize,
+    pub no_shows: usize,
+    pub horas_reservadas: f64,
+}
+
+/// Número de reservas que ocupan una franja (día de la semana + hora)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricasFranja {
+    pub dia_semana: String,
+    /// Hora de inicio de la franja (0-23, hora local de la sala)
+    pub hora: u32,
+    pub reservas: usize,
+}
+
+/// Informe de uso de las salas en un rango de fechas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InformeUso {
+    pub desde: DateTime<Utc>,
+    pub hasta: DateTime<Utc>,
+    pub total_reservas: usize,
+    pub canceladas: usize,
+    /// Reservas ya finalizadas que siguen activas (nadie las completó)
+    pub no_shows: usize,
+    pub tasa_cancelacion: f64,
+    pub tasa_no_show: f64,
+    /// Ocupación media de todas las salas (%)
+    pub tasa_ocupacion: f64,
+    pub por_sala: Vec<MetricasSala>,
+    pub por_usuario: Vec<MetricasUsuario>,
+    pub por_franja: Vec<MetricasFranja>,
+    pub horas_pico: Vec<MetricasFranja>,
+    pub top_usuarios: Vec<MetricasUsuario>,
+}
+
+/// Agrupación usada al exportar el informe a CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgrupacionInforme {
+    Sala,
+    Usuario,
+    Franja,
+}
+
+impl AgrupacionInforme {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "sala" => Some(AgrupacionInforme::Sala),
+            "usuario" => Some(AgrupacionInforme::Usuario),
+            "franja" => Some(AgrupacionInforme::Franja),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Acumulado {
+    total: usize,
+    canceladas: usize,
+    no_shows: usize,
+    horas: f64,
+    /// Parte de `horas` que cae dentro del horario de apertura
+    horas_en_horario: f64,
+}
+
+impl Acumulado {
+    fn sumar(&mut self, reserva: &Reserva, horas: f64, horas_en_horario: f64, no_show: bool) {
+        self.total += 1;
+        if reserva.estado == EstadoReserva::Cancelada {
+            self.canceladas += 1;
+        } else {
+            self.horas += horas;
+            self.horas_en_horario += horas_en_horario;
+        }
+        if no_show {
+            self.no_shows += 1;
+        }
+    }
+}
+
+impl InformeUso {
+    /// Calcula el informe a partir de las reservas y las salas existentes
+    /// (id, nombre y zona horaria)
+    ///
+    /// Solo se tienen en cuenta las reservas que se solapan con [desde, hasta);
+    /// las horas reservadas se recortan al rango. La ocupación solo cuenta las
+    /// horas que caen dentro del horario de apertura. Franjas y ocupación usan
+    /// la hora local de cada sala (UTC si la sala ya no existe). Las reservas
+    /// solo de recursos cuentan en los totales y por usuario, pero no ocupan
+    /// ninguna sala ni franja.
+    pub fn calcular(
+        desde: DateTime<Utc>,
+        hasta: DateTime<Utc>,
+        reservas: &[Reserva],
+        salas: &[(String, String, Tz)],
+        horario: HorarioApertura,
+        ahora: DateTime<Utc>,
+    ) -> Self {
+        let mut global = Acumulado::default();
+        let mut por_sala: HashMap<&str, Acumulado> = HashMap::new();
+        let mut por_usuario: HashMap<&str, Acumulado> = HashMap::new();
+        let mut por_franja: HashMap<(u32, u32), usize> = HashMap::new();
+
+        let zonas: HashMap<&str, Tz> = salas
+            .iter()
+            .map(|(id, _, zona)| (id.as_str(), *zona))
+            .collect();
+        let zona_de = |sala_id: &str| zonas.get(sala_id).copied().unwrap_or(chrono_tz::UTC);
+
+        for (sala_id, _, _) in salas {
+            por_sala.entry(sala_id.as_str()).or_default();
+        }
+
+        let en_rango = reservas
+            .iter()
+            .filter(|r| r.fecha_inicio < hasta && desde < r.fecha_fin);
+
+        for reserva in en_rango {
+            let inicio = reserva.fecha_inicio.max(desde);
+            let fin = reserva.fecha_fin.min(hasta);
+            let horas_reserva = horas(fin - inicio);
+            let en_horario = if reserva.tiene_sala() {
+                horario.horas_disponibles_en(inicio, fin, &zona_de(&reserva.sala_id))
+            } else {
+                0.0
+            };
+            let no_show = reserva.estado == EstadoReserva::Activa && reserva.fecha_fin < ahora;
+
+            global.sumar(reserva, horas_reserva, en_horario, no_show);
+            por_usuario
+                .entry(reserva.usuario_id.as_str())
+                .or_default()
+                .sumar(reserva, horas_reserva, en_horario, no_show);
+
+            if !reserva.tiene_sala() {
+                continue;
+            }
+
+            por_sala.entry(reserva.sala_id.as_str()).or_default().sumar(
+                reserva,
+                horas_reserva,
+                en_horario,
+                no_show,
+            );
+
+            if reserva.estado != EstadoReserva::Cancelada {
+                let zona = zona_de(&reserva.sala_id);
+                let mut franja = inicio.duration_trunc(Duration::hours(1)).unwrap_or(inicio);
+                while franja < fin {
+                    let local = franja.with_timezone(&zona);
+                    let clave = (local.weekday().num_days_from_monday(), local.hour());
+                    *por_franja.entry(clave).or_default() += 1;
+                    franja += Duration::hours(1);
+                }
+            }
+        }
+
+        let nombres: HashMap<&str, &str> = salas
+            .iter()
+            .map(|(id, nombre, _)| (id.as_str(), nombre.as_str()))
+            .collect();
+
+        let mut horas_disponibles_total = 0.0;
+        let mut metricas_sala: Vec<MetricasSala> = por_sala
+            .into_iter()
+            .map(|(sala_id, a)| {
+                let horas_disponibles =
+                    horario.horas_disponibles_en(desde, hasta, &zona_de(sala_id));
+                horas_disponibles_total += horas_disponibles;
+
+                MetricasSala {
+                    sala_id: sala_id.to_string(),
+                    sala_nombre: nombres.get(sala_id).map(|n| n.to_string()),
+                    total_reservas: a.total,
+                    canceladas: a.canceladas,
+                    no_shows: a.no_shows,
+                    horas_reservadas: redondear(a.horas),
+                    tasa_ocupacion: porcentaje(a.horas_en_horario, horas_disponibles),
+                }
+            })
+            .collect();
+        metricas_sala.sort_by(|a, b| {
+            b.tasa_ocupacion
+                .total_cmp(&a.tasa_ocupacion)
+                .then_with(|| a.sala_id.cmp(&b.sala_id))
+        });
+
+        let mut metricas_usuario: Vec<MetricasUsuario> = por_usuario
+            .into_iter()
+            .map(|(usuario_id, a)| MetricasUsuario {
+                usuario_id: usuario_id.to_string(),
+                total_reservas: a.total,
+                canceladas: a.canceladas,
+                no_shows: a.no_shows,
+                horas_reservadas: redondear(a.horas),
+            })
+            .collect();
+        metricas_usuario.sort_by(|a, b| {
+            b.total_reservas
+                .cmp(&a.total_reservas)
+                .then_with(|| b.horas_reservadas.total_cmp(&a.horas_reservadas))
+                .then_with(|| a.usuario_id.cmp(&b.usuario_id))
+        });
+
+        let mut franjas: Vec<((u32, u32), usize)> = por_franja.into_iter().collect();
+        franjas.sort_by_key(|(clave, _)| *clave);
+        let metricas_franja: Vec<MetricasFranja> = franjas
+            .into_iter()
+            .map(|((dia, hora), reservas)| MetricasFranja {
+                dia_semana: DIAS_SEMANA[dia as usize].to_string(),
+                hora,
+                reservas,
+            })
+            .collect();
+
+        let mut horas_pico = metricas_franja.clone();
+        // sort estable: en caso de empate se mantiene el orden cronológico
+        horas_pico.sort_by_key(|f| Reverse(f.reservas));
+        horas_pico.truncate(NUM_HORAS_PICO);
+
+        let top_usuarios = metricas_usuario
+            .iter()
+            .take(NUM_TOP_USUARIOS)
+            .cloned()
+            .collect();
+
+        Self {
+            desde,
+            hasta,
+            total_reservas: global.total,
+            canceladas: global.canceladas,
+            no_shows: global.no_shows,
+            tasa_cancelacion: porcentaje(global.canceladas as f64, global.total as f64),
+            tasa_no_show: porcentaje(global.no_shows as f64, global.total as f64),
+            tasa_ocupacion: porcentaje(global.horas_en_horario, horas_disponibles_total),
+            por_sala: metricas_sala,
+            por_usuario: metricas_usuario,
+            por_franja: metricas_franja,
+            horas_pico,
+            top_usuarios,
+        }
+    }
+
+    /// Exporta una de las agrupaciones del informe en formato CSV
+    pub fn to_csv(&self, agrupacion: AgrupacionInforme) -> String {
+        let mut csv = String::new();
+
+        match agrupacion {
+            AgrupacionInforme::Sala => {
+                csv.push_str("sala_id,sala_nombre,total_reservas,canceladas,no_shows,horas_reservadas,tasa_ocupacion\n");
+                for m in &self.por_sala {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{},{},{}\n",
+                        campo_csv(&m.sala_id),
+                        campo_csv(m.sala_nombre.as_deref().unwrap_or("")),
+                        m.total_reservas,
+                        m.canceladas,
+                        m.no_shows,
+                        m.horas_reservadas,
+                        m.tasa_ocupacion
+                    ));
+                }
+            }
+            AgrupacionInforme::Usuario => {
+                csv.push_str("usuario_id,total_reservas,canceladas,no_shows,horas_reservadas\n");
+                for m in &self.por_usuario {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        campo_csv(&m.usuario_id),
+                        m.total_reservas,
+                        m.canceladas,
+                        m.no_shows,
+                        m.horas_reservadas
+                    ));
+                }
+            }
+            AgrupacionInforme::Franja => {
+                csv.push_str("dia_semana,hora,reservas\n");
+                for m in &self.por_franja {
+                    csv.push_str(&format!("{},{},{}\n", m.dia_semana, m.hora, m.reservas));
+                }
+            }
+        }
+
+        csv
+    }
+}
+
+/// Escapa un campo CSV (RFC 4180) si contiene separadores, comillas o saltos de línea
+fn campo_csv(valor: &str) -> String {
+    if valor.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", valor.replace('"', "\"\""))
+    } else {
+        valor.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fecha(dia: u32, hora: u32) -> DateTime<Utc> {
+        // Enero de 2024: el día 1 es lunes
+        Utc.with_ymd_and_hms(2024, 1, dia, hora, 0, 0).unwrap()
+    }
+
+    fn reserva(
+        sala: &str,
+        usuario: &str,
+        inicio: DateTime<Utc>,
+        fin: DateTime<Utc>,
+        estado: EstadoReserva,
+    ) -> Reserva {
+        Reserva::from_existing(
+            format!("{}-{}-{}", sala, usuario, inicio.timestamp()),
+            sala.into(),
+            usuario.into(),
+            inicio,
+            fin,
+            estado,
+            inicio,
+        )
+    }
+
+    fn salas() -> Vec<(String, String, Tz)> {
+        vec![
+            ("s1".into(), "Sala Grande".into(), chrono_tz::UTC),
+            ("s2".into(), "Sala, Pequeña".into(), chrono_tz::UTC),
+        ]
+    }
+
+    #[test]
+    fn horas_disponibles_respeta_horario() {
+        let horario = HorarioApertura::default();
+
+        // Un día completo: 12 horas de apertura (8-20)
+        assert_eq!(horario.horas_disponibles(fecha(1, 0), fecha(2, 0)), 12.0);
+        // Rango parcial 18:00 - 10:00 del día siguiente: 2 + 2 horas
+        assert_eq!(horario.horas_disponibles(fecha(1, 18), fecha(2, 10)), 4.0);
+    }
+
+    #[test]
+    fn horas_disponibles_en_la_zona_de_la_sala() {
+        let horario = HorarioApertura::default();
+        let madrid = chrono_tz::Europe::Madrid;
+
+        // 8-20 en Madrid son 7-19 UTC en invierno
+        assert_eq!(
+            horario.horas_disponibles_en(fecha(1, 0), fecha(1, 8), &madrid),
+            1.0
+        );
+
+        // Día del cambio de horario con apertura 0-24: 23 horas
+        let horario = HorarioApertura {
+            apertura: 0,
+            cierre: 24,
+        };
+        let desde = Utc.with_ymd_and_hms(2024, 3, 30, 23, 0, 0).unwrap();
+        let hasta = Utc.with_ymd_and_hms(2024, 3, 31, 22, 0, 0).unwrap();
+        assert_eq!(horario.horas_disponibles_en(desde, hasta, &madrid), 23.0);
+    }
+
+    #[test]
+    fn franjas_en_hora_local_de_cada_sala() {
+        // Misma hora UTC (15:00): 16:00 en Madrid y 09:00 en Ciudad de México
+        let salas = vec![
+            ("mad".into(), "Madrid".into(), chrono_tz::Europe::Madrid),
+            ("mex".into(), "CDMX".into(), chrono_tz::America::Mexico_City),
+        ];
+        let reservas = vec![
+            reserva(
+                "mad",
+                "u1",
+                fecha(1, 15),
+                fecha(1, 16),
+                EstadoReserva::Completada,
+            ),
+            reserva(
+                "mex",
+                "u2",
+                fecha(1, 15),
+                fecha(1, 16),
+                EstadoReserva::Completada,
+            ),
+        ];
+
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(2, 0),
+            &reservas,
+            &salas,
+            HorarioApertura::default(),
+            fecha(3, 0),
+        );
+
+        let horas: Vec<u32> = informe.por_franja.iter().map(|f| f.hora).collect();
+        assert_eq!(horas, vec![9, 16]);
+    }
+
+    #[test]
+    fn calcula_metricas_globales_y_por_sala() {
+        let reservas = vec![
+            reserva(
+                "s1",
+                "u1",
+                fecha(1, 9),
+                fecha(1, 12),
+                EstadoReserva::Completada,
+            ),
+            reserva(
+                "s1",
+                "u2",
+                fecha(1, 13),
+                fecha(1, 16),
+                EstadoReserva::Activa,
+            ),
+            reserva(
+                "s1",
+                "u1",
+                fecha(1, 16),
+                fecha(1, 17),
+                EstadoReserva::Cancelada,
+            ),
+            reserva(
+                "s2",
+                "u1",
+                fecha(1, 9),
+                fecha(1, 10),
+                EstadoReserva::Completada,
+            ),
+            // Fuera de rango
+            reserva(
+                "s2",
+                "u3",
+                fecha(5, 9),
+                fecha(5, 10),
+                EstadoReserva::Completada,
+            ),
+        ];
+
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(2, 0),
+            &reservas,
+            &salas(),
+            HorarioApertura::default(),
+            fecha(3, 0),
+        );
+
+        assert_eq!(informe.total_reservas, 4);
+        assert_eq!(informe.canceladas, 1);
+        assert_eq!(informe.no_shows, 1);
+        assert_eq!(informe.tasa_cancelacion, 25.0);
+        assert_eq!(informe.tasa_no_show, 25.0);
+
+        let s1 = &informe.por_sala[0];
+        assert_eq!(s1.sala_id, "s1");
+        assert_eq!(s1.sala_nombre.as_deref(), Some("Sala Grande"));
+        assert_eq!(s1.horas_reservadas, 6.0);
+        assert_eq!(s1.tasa_ocupacion, 50.0);
+        // 7 horas reservadas sobre 24 horas disponibles (2 salas x 12)
+        assert_eq!(informe.tasa_ocupacion, 29.17);
+    }
+
+    #[test]
+    fn la_ocupacion_solo_cuenta_el_horario_de_apertura() {
+        let reservas = vec![
+            // De 8 a 20 abierta: solo cuentan 2 de las 5 horas
+            reserva(
+                "s1",
+                "u1",
+                fecha(1, 18),
+                fecha(1, 23),
+                EstadoReserva::Completada,
+            ),
+            // Toda la noche cerrada: no ocupa nada
+            reserva("s2", "u2", fecha(1, 0), fecha(1, 8), EstadoReserva::Activa),
+            reserva(
+                "s2",
+                "u2",
+                fecha(1, 8),
+                fecha(1, 20),
+                EstadoReserva::Completada,
+            ),
+        ];
+
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(2, 0),
+            &reservas,
+            &salas(),
+            HorarioApertura::default(),
+            fecha(3, 0),
+        );
+
+        let s1 = informe.por_sala.iter().find(|m| m.sala_id == "s1").unwrap();
+        assert_eq!(s1.horas_reservadas, 5.0);
+        assert_eq!(s1.tasa_ocupacion, 16.67);
+        let s2 = informe.por_sala.iter().find(|m| m.sala_id == "s2").unwrap();
+        assert_eq!(s2.horas_reservadas, 20.0);
+        assert_eq!(s2.tasa_ocupacion, 100.0);
+        // 14 horas en horario sobre 24 disponibles
+        assert_eq!(informe.tasa_ocupacion, 58.33);
+    }
+
+    #[test]
+    fn las_reservas_solo_de_recursos_no_ocupan_salas() {
+        // Reserva solo de recursos tal y como se carga del repositorio
+        let solo_proyector = reserva(
+            "",
+            "u2",
+            fecha(1, 10),
+            fecha(1, 12),
+            EstadoReserva::Completada,
+        )
+        .con_recursos(vec!["proyector".into()]);
+        assert!(!solo_proyector.tiene_sala());
+        let reservas = vec![
+            reserva(
+                "s1",
+                "u1",
+                fecha(1, 8),
+                fecha(1, 14),
+                EstadoReserva::Completada,
+            ),
+            solo_proyector,
+        ];
+
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(2, 0),
+            &reservas,
+            &salas(),
+            HorarioApertura::default(),
+            fecha(3, 0),
+        );
+
+        assert_eq!(informe.total_reservas, 2);
+        let salas: Vec<&str> = informe
+            .por_sala
+            .iter()
+            .map(|m| m.sala_id.as_str())
+            .collect();
+        assert_eq!(salas, vec!["s1", "s2"]);
+        assert_eq!(informe.por_sala[0].total_reservas, 1);
+        // 6 horas de s1 sobre 24 disponibles; el proyector no suma
+        assert_eq!(informe.tasa_ocupacion, 25.0);
+        let u2 = informe
+            .por_usuario
+            .iter()
+            .find(|m| m.usuario_id == "u2")
+            .unwrap();
+        assert_eq!(u2.total_reservas, 1);
+        assert_eq!(u2.horas_reservadas, 2.0);
+        let franjas: Vec<u32> = informe.por_franja.iter().map(|f| f.hora).collect();
+        assert_eq!(franjas, vec![8, 9, 10, 11, 12, 13]);
+        assert!(informe.por_franja.iter().all(|f| f.reservas == 1));
+    }
+
+    #[test]
+    fn agrupa_por_usuario_y_franja() {
+        let reservas = vec![
+            reserva(
+                "s1",
+                "u1",
+                fecha(1, 9),
+                fecha(1, 11),
+                EstadoReserva::Completada,
+            ),
+            reserva(
+                "s2",
+                "u1",
+                fecha(1, 10),
+                fecha(1, 11),
+                EstadoReserva::Completada,
+            ),
+            reserva(
+                "s2",
+                "u2",
+                fecha(2, 10),
+                fecha(2, 11),
+                EstadoReserva::Completada,
+            ),
+        ];
+
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(8, 0),
+            &reservas,
+            &salas(),
+            HorarioApertura::default(),
+            fecha(9, 0),
+        );
+
+        assert_eq!(informe.top_usuarios[0].usuario_id, "u1");
+        assert_eq!(informe.top_usuarios[0].total_reservas, 2);
+
+        let pico = &informe.horas_pico[0];
+        assert_eq!(
+            (pico.dia_semana.as_str(), pico.hora, pico.reservas),
+            ("lunes", 10, 2)
+        );
+        assert_eq!(informe.por_franja.len(), 3);
+    }
+
+    #[test]
+    fn exporta_csv_escapando_campos() {
+        let informe = InformeUso::calcular(
+            fecha(1, 0),
+            fecha(2, 0),
+            &[],
+            &salas(),
+            HorarioApertura::default(),
+            fecha(3, 0),
+        );
+
+        let csv = informe.to_csv(AgrupacionInforme::Sala);
+        let lineas: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lineas.len(), 3);
+        assert!(lineas[0].starts_with("sala_id,sala_nombre"));
+        assert!(csv.contains("s2,\"Sala, Pequeña\",0,0,0,0,0"));
+    }
+}
@@ -1,5 +1,15 @@
+pub mod conjunto;
 pub mod error;
+pub mod informe;
+pub mod recurrencia;
 pub mod reserva;
+pub mod zona;
 
+pub use conjunto::{validar_partes, ParteConjunto, MAX_PARTES_CONJUNTO};
 pub use error::ReservaError;
+pub use informe::{
+    AgrupacionInforme, HorarioApertura, InformeUso, MetricasFranja, MetricasSala, MetricasUsuario,
+};
+pub use recurrencia::{Frecuencia, Recurrencia, MAX_REPETICIONES};
 pub use reserva::{EstadoReserva, Reserva};
+pub use zona::local_a_utc;
@@ -0,0 +1,134 @@
+use crate::error::ReservaError;
+use crate::zona::local_a_utc;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Máximo de repeticiones de una reserva recurrente (un año de semanales)
+pub const MAX_REPETICIONES: u32 = 52;
+
+/// Cada cuánto se repite una reserva
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Frecuencia {
+    Diaria,
+    Semanal,
+}
+
+impl Frecuencia {
+    /// Crea la frecuencia desde un string ("diaria" o "semanal")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "diaria" => Some(Frecuencia::Diaria),
+            "semanal" => Some(Frecuencia::Semanal),
+            _ => None,
+        }
+    }
+
+    fn paso(&self) -> Duration {
+        match self {
+            Frecuencia::Diaria => Duration::days(1),
+            Frecuencia::Semanal => Duration::weeks(1),
+        }
+    }
+}
+
+/// Regla de repetición de una reserva
+///
+/// Las repeticiones conservan la hora local de la sala: una reunión semanal
+/// a las 09:00 en Madrid sigue a las 09:00 después del cambio de horario,
+/// aunque en UTC se mueva una hora.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recurrencia {
+    pub frecuencia: Frecuencia,
+    /// Número total de reservas, contando la primera
+    pub repeticiones: u32,
+}
+
+impl Recurrencia {
+    pub fn new(frecuencia: Frecuencia, repeticiones: u32) -> Result<Self, ReservaError> {
+        if !(1..=MAX_REPETICIONES).contains(&repeticiones) {
+            return Err(ReservaError::RecurrenciaInvalida {
+                max: MAX_REPETICIONES,
+            });
+        }
+
+        Ok(Self {
+            frecuencia,
+            repeticiones,
+        })
+    }
+
+    /// Inicio y fin (en UTC) de cada repetición, empezando por la original
+    pub fn ocurrencias(
+        &self,
+        inicio: DateTime<Utc>,
+        fin: DateTime<Utc>,
+        zona: &Tz,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let inicio_local = inicio.with_timezone(zona).naive_local();
+        let fin_local = fin.with_timezone(zona).naive_local();
+
+        (0..self.repeticiones)
+            .map(|i| {
+                let desplazamiento = self.frecuencia.paso() * i as i32;
+                (
+                    local_a_utc(zona, inicio_local + desplazamiento),
+                    local_a_utc(zona, fin_local + desplazamiento),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::{America, Europe};
+
+    #[test]
+    fn semanal_mantiene_la_hora_local_al_cambiar_de_horario() {
+        // Lunes 21/10/2024 09:00-10:00 en Madrid (CEST, UTC+2)
+        let inicio = Utc.with_ymd_and_hms(2024, 10, 21, 7, 0, 0).unwrap();
+        let fin = Utc.with_ymd_and_hms(2024, 10, 21, 8, 0, 0).unwrap();
+        let recurrencia = Recurrencia::new(Frecuencia::Semanal, 2).unwrap();
+
+        let ocurrencias = recurrencia.ocurrencias(inicio, fin, &Europe::Madrid);
+
+        // El 27/10 termina el horario de verano: la segunda es a las 08:00 UTC
+        assert_eq!(
+            ocurrencias[1],
+            (
+                Utc.with_ymd_and_hms(2024, 10, 28, 8, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 10, 28, 9, 0, 0).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn diaria_en_zona_sin_cambio_de_horario() {
+        let inicio = Utc.with_ymd_and_hms(2024, 10, 25, 15, 0, 0).unwrap();
+        let fin = Utc.with_ymd_and_hms(2024, 10, 25, 16, 0, 0).unwrap();
+        let recurrencia = Recurrencia::new(Frecuencia::Diaria, 5).unwrap();
+
+        let ocurrencias = recurrencia.ocurrencias(inicio, fin, &America::Mexico_City);
+
+        assert_eq!(ocurrencias.len(), 5);
+        assert_eq!(ocurrencias[4].0, inicio + Duration::days(4));
+    }
+
+    #[test]
+    fn repeticiones_fuera_de_rango() {
+        assert_eq!(
+            Recurrencia::new(Frecuencia::Diaria, 0),
+            Err(ReservaError::RecurrenciaInvalida {
+                max: MAX_REPETICIONES
+            })
+        );
+        assert!(Recurrencia::new(Frecuencia::Semanal, MAX_REPETICIONES + 1).is_err());
+        assert_eq!(
+            Frecuencia::from_str_opt(" Semanal "),
+            Some(Frecuencia::Semanal)
+        );
+    }
+}
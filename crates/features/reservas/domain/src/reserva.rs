@@ -1,6 +1,7 @@
 use crate::error::ReservaError;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use shared_errores::{ErrorCampo, ErrorDominio};
 use uuid::Uuid;
 
 /// Estado de una reserva
@@ -11,16 +12,54 @@ pub enum EstadoReserva {
     Completada,
 }
 
+impl EstadoReserva {
+    /// Crea el estado desde un string ("activa", "cancelada" o "completada")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "activa" => Some(EstadoReserva::Activa),
+            "cancelada" => Some(EstadoReserva::Cancelada),
+            "completada" => Some(EstadoReserva::Completada),
+            _ => None,
+        }
+    }
+}
+
 /// Entidad Reserva: representa la reserva de una sala por un usuario en un período de tiempo
+///
+/// Puede llevar recursos (proyectores, plazas de aparcamiento...) junto con
+/// la sala, o reservar solo recursos: entonces `sala_id` va vacío.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Reserva {
     pub id: String,
     pub sala_id: String,
+    /// Organizador: a nombre de quien está la reserva
     pub usuario_id: String,
     pub fecha_inicio: DateTime<Utc>,
     pub fecha_fin: DateTime<Utc>,
     pub estado: EstadoReserva,
     pub created_at: DateTime<Utc>,
+    /// Grupo al que pertenece la reserva; sus miembros también pueden gestionarla
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grupo_id: Option<String>,
+    /// Quien la hizo en nombre del organizador (p. ej. su asistente); sin
+    /// valor, la hizo el propio organizador
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creado_por: Option<String>,
+    /// Reserva conjunta de la que forma parte: sus salas se reservaron juntas
+    /// y se cancelan y reprograman juntas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conjunto_id: Option<String>,
+    /// Recursos reservados en el mismo horario, sin repetir
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recursos: Vec<String>,
+    /// Minutos de preparación que la sala queda ocupada antes del inicio.
+    /// No se guarda: se aplica el margen actual de la sala al comprobar
+    #[serde(skip)]
+    pub margen_antes: u32,
+    /// Minutos de limpieza que la sala queda ocupada después del fin.
+    /// No se guarda, igual que `margen_antes`
+    #[serde(skip)]
+    pub margen_despues: u32,
 }
 
 impl Reserva {
@@ -31,55 +70,82 @@ impl Reserva {
         fecha_inicio: DateTime<Utc>,
         fecha_fin: DateTime<Utc>,
     ) -> Result<Self, ReservaError> {
-        let mut errores: Vec<String> = Vec::new();
+        let mut errores: Vec<ErrorCampo> = Vec::new();
 
         // Validar que los IDs no estén vacíos
         if sala_id.trim().is_empty() {
-            errores.push("El ID de sala no puede estar vacío".to_string());
+            errores.extend(ReservaError::SalaIdVacio.campos());
         }
 
         if usuario_id.trim().is_empty() {
-            errores.push("El ID de usuario no puede estar vacío".to_string());
+            errores.extend(ReservaError::UsuarioIdVacio.campos());
         }
 
-        // Validar fechas
-        let ahora = Utc::now();
+        errores.extend(errores_de_fechas(fecha_inicio, fecha_fin));
 
-        if fecha_inicio < ahora {
-            errores.push("La fecha de inicio no puede ser en el pasado".to_string());
+        // Si hay errores, devolver todos
+        if !errores.is_empty() {
+            return Err(ReservaError::Validacion(errores));
         }
 
-        if fecha_fin < ahora {
-            errores.push("La fecha de fin no puede ser en el pasado".to_string());
-        }
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            sala_id,
+            usuario_id,
+            fecha_inicio,
+            fecha_fin,
+            estado: EstadoReserva::Activa,
+            created_at: Utc::now(),
+            grupo_id: None,
+            creado_por: None,
+            conjunto_id: None,
+            recursos: Vec::new(),
+            margen_antes: 0,
+            margen_despues: 0,
+        })
+    }
 
-        if fecha_fin <= fecha_inicio {
-            errores.push("La fecha de fin debe ser posterior a la fecha de inicio".to_string());
+    /// Crea una reserva solo de recursos, sin sala
+    pub fn de_recursos(
+        usuario_id: String,
+        recursos: Vec<String>,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Self, ReservaError> {
+        let recursos = normalizar_recursos(recursos);
+        let mut errores: Vec<ErrorCampo> = Vec::new();
+
+        if recursos.is_empty() {
+            errores.push(ErrorCampo::con_codigo(
+                "recursos",
+                "RESERVA_SIN_SALA_NI_RECURSOS",
+            ));
         }
 
-        // Validar duración (mínimo 15 minutos, máximo 8 horas)
-        let duracion = fecha_fin - fecha_inicio;
-        let min_duracion = Duration::minutes(15);
-        let max_duracion = Duration::hours(8);
-
-        if duracion < min_duracion || duracion > max_duracion {
-            errores
-                .push("La duración de la reserva debe ser entre 15 minutos y 8 horas".to_string());
+        if usuario_id.trim().is_empty() {
+            errores.extend(ReservaError::UsuarioIdVacio.campos());
         }
 
-        // Si hay errores, devolver todos
+        errores.extend(errores_de_fechas(fecha_inicio, fecha_fin));
+
         if !errores.is_empty() {
             return Err(ReservaError::Validacion(errores));
         }
 
         Ok(Self {
             id: Uuid::new_v4().to_string(),
-            sala_id,
+            sala_id: String::new(),
             usuario_id,
             fecha_inicio,
             fecha_fin,
             estado: EstadoReserva::Activa,
             created_at: Utc::now(),
+            grupo_id: None,
+            creado_por: None,
+            conjunto_id: None,
+            recursos,
+            margen_antes: 0,
+            margen_despues: 0,
         })
     }
 
@@ -101,6 +167,12 @@ impl Reserva {
             fecha_fin,
             estado,
             created_at,
+            grupo_id: None,
+            creado_por: None,
+            conjunto_id: None,
+            recursos: Vec::new(),
+            margen_antes: 0,
+            margen_despues: 0,
         }
     }
 
@@ -133,6 +205,79 @@ impl Reserva {
         self.created_at
     }
 
+    pub fn grupo_id(&self) -> Option<&str> {
+        self.grupo_id.as_deref()
+    }
+
+    /// Quien hizo la reserva: el organizador o alguien en su nombre
+    pub fn creado_por(&self) -> &str {
+        self.creado_por.as_deref().unwrap_or(&self.usuario_id)
+    }
+
+    /// Si reserva una sala; si no, es solo de recursos
+    pub fn tiene_sala(&self) -> bool {
+        !self.sala_id.is_empty()
+    }
+
+    pub fn recursos(&self) -> &[String] {
+        &self.recursos
+    }
+
+    /// Añade recursos a la reserva de una sala
+    pub fn con_recursos(mut self, recursos: Vec<String>) -> Self {
+        self.recursos = normalizar_recursos(recursos);
+        self
+    }
+
+    /// Sustituye los recursos de la reserva; una reserva sin sala debe
+    /// conservar al menos uno
+    pub fn cambiar_recursos(&mut self, recursos: Vec<String>) -> Result<(), ReservaError> {
+        let recursos = normalizar_recursos(recursos);
+        if recursos.is_empty() && !self.tiene_sala() {
+            return Err(ReservaError::Validacion(vec![ErrorCampo::con_codigo(
+                "recursos",
+                "RESERVA_SIN_SALA_NI_RECURSOS",
+            )]));
+        }
+
+        self.recursos = recursos;
+        Ok(())
+    }
+
+    /// Aplica los márgenes de preparación y limpieza de la sala, en minutos.
+    /// Las horas de la reserva no cambian
+    pub fn con_margenes(mut self, antes: u32, despues: u32) -> Self {
+        self.margen_antes = antes;
+        self.margen_despues = despues;
+        self
+    }
+
+    /// Inicio del tiempo que la sala queda ocupada, con la preparación
+    pub fn inicio_ocupado(&self) -> DateTime<Utc> {
+        self.fecha_inicio - Duration::minutes(self.margen_antes.into())
+    }
+
+    /// Fin del tiempo que la sala queda ocupada, con la limpieza
+    pub fn fin_ocupado(&self) -> DateTime<Utc> {
+        self.fecha_fin + Duration::minutes(self.margen_despues.into())
+    }
+
+    pub fn conjunto_id(&self) -> Option<&str> {
+        self.conjunto_id.as_deref()
+    }
+
+    /// Anota la reserva conjunta de la que forma parte
+    pub fn en_conjunto(mut self, conjunto_id: String) -> Self {
+        self.conjunto_id = Some(conjunto_id);
+        self
+    }
+
+    /// Anota quién hace la reserva si no es el propio organizador
+    pub fn con_creador(mut self, creado_por: String) -> Self {
+        self.creado_por = Some(creado_por).filter(|c| *c != self.usuario_id);
+        self
+    }
+
     // Métodos de negocio
     pub fn esta_activa(&self) -> bool {
         matches!(self.estado, EstadoReserva::Activa)
@@ -146,20 +291,81 @@ impl Reserva {
         self.estado = EstadoReserva::Completada;
     }
 
-    /// Verifica si esta reserva se solapa con otra
-    pub fn se_solapa_con(&self, otra: &Reserva) -> bool {
-        // Solo verificar solapamiento si ambas reservas son para la misma sala
-        if self.sala_id != otra.sala_id {
-            return false;
+    /// Cambia el horario con las mismas validaciones que al crearla
+    pub fn reprogramar(
+        &mut self,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<(), ReservaError> {
+        let errores = errores_de_fechas(fecha_inicio, fecha_fin);
+        if !errores.is_empty() {
+            return Err(ReservaError::Validacion(errores));
         }
 
-        // Solo verificar solapamiento si ambas reservas están activas
-        if !self.esta_activa() || !otra.esta_activa() {
-            return false;
+        self.fecha_inicio = fecha_inicio;
+        self.fecha_fin = fecha_fin;
+        Ok(())
+    }
+
+    /// Pone la reserva a nombre de un grupo, o la devuelve solo a su usuario
+    pub fn asignar_grupo(&mut self, grupo_id: Option<String>) {
+        self.grupo_id = grupo_id
+            .map(|grupo| grupo.trim().to_string())
+            .filter(|grupo| !grupo.is_empty());
+    }
+
+    /// Sustituye al usuario por un seudónimo al eliminar su cuenta, tanto si
+    /// es el organizador como si la hizo en nombre de otro
+    ///
+    /// La reserva se conserva para que los informes de uso no cambien.
+    pub fn seudonimizar(&mut self, usuario_id: &str, seudonimo: &str) {
+        if self.usuario_id == usuario_id {
+            self.usuario_id = seudonimo.to_string();
+        }
+        if self.creado_por.as_deref() == Some(usuario_id) {
+            self.creado_por = Some(seudonimo.to_string());
         }
+    }
+
+    /// Verifica si esta reserva se solapa con otra: ambas activas y con la
+    /// misma sala ocupada a la vez (márgenes incluidos) o algún recurso en
+    /// común en horarios que se cruzan
+    pub fn se_solapa_con(&self, otra: &Reserva) -> bool {
+        let misma_sala = self.tiene_sala() && self.sala_id == otra.sala_id;
+        if misma_sala && self.coincide_con_margenes(otra) {
+            return true;
+        }
+        self.recurso_en_conflicto(otra).is_some()
+    }
+
+    /// Primer recurso de esta reserva que la otra ocupa a la vez, si lo hay
+    pub fn recurso_en_conflicto(&self, otra: &Reserva) -> Option<&str> {
+        self.recurso_en_comun(otra)
+            .filter(|_| self.coincide_en_horario(otra))
+    }
+
+    fn recurso_en_comun(&self, otra: &Reserva) -> Option<&str> {
+        self.recursos
+            .iter()
+            .find(|r| otra.recursos.contains(r))
+            .map(String::as_str)
+    }
 
-        // Verificar solapamiento de fechas
-        self.fecha_inicio < otra.fecha_fin && otra.fecha_inicio < self.fecha_fin
+    /// Ambas activas y con horarios que se cruzan
+    fn coincide_en_horario(&self, otra: &Reserva) -> bool {
+        self.esta_activa()
+            && otra.esta_activa()
+            && self.fecha_inicio < otra.fecha_fin
+            && otra.fecha_inicio < self.fecha_fin
+    }
+
+    /// Como `coincide_en_horario`, pero con el tiempo de preparación y
+    /// limpieza de cada una
+    fn coincide_con_margenes(&self, otra: &Reserva) -> bool {
+        self.esta_activa()
+            && otra.esta_activa()
+            && self.inicio_ocupado() < otra.fin_ocupado()
+            && otra.inicio_ocupado() < self.fin_ocupado()
     }
 
     /// Obtiene la duración de la reserva en minutos
@@ -168,6 +374,47 @@ impl Reserva {
     }
 }
 
+/// Sin espacios sobrantes, vacíos ni repetidos, en el orden recibido
+fn normalizar_recursos(recursos: Vec<String>) -> Vec<String> {
+    let mut normalizados: Vec<String> = Vec::with_capacity(recursos.len());
+    for recurso in recursos {
+        let recurso = recurso.trim();
+        if !recurso.is_empty() && !normalizados.iter().any(|r| r == recurso) {
+            normalizados.push(recurso.to_string());
+        }
+    }
+    normalizados
+}
+
+/// Fechas futuras, en orden y con una duración de 15 minutos a 8 horas
+fn errores_de_fechas(fecha_inicio: DateTime<Utc>, fecha_fin: DateTime<Utc>) -> Vec<ErrorCampo> {
+    let mut errores = Vec::new();
+    let ahora = Utc::now();
+
+    if fecha_inicio < ahora {
+        errores.extend(ReservaError::FechaInicioInvalida.campos());
+    }
+
+    if fecha_fin < ahora {
+        errores.extend(ReservaError::FechaFinInvalida.campos());
+    }
+
+    if fecha_fin <= fecha_inicio {
+        errores.extend(ReservaError::FechaFinAnteriorAInicio.campos());
+    }
+
+    // Validar duración (mínimo 15 minutos, máximo 8 horas)
+    let duracion = fecha_fin - fecha_inicio;
+    let min_duracion = Duration::minutes(15);
+    let max_duracion = Duration::hours(8);
+
+    if duracion < min_duracion || duracion > max_duracion {
+        errores.extend(ReservaError::DuracionInvalida.campos());
+    }
+
+    errores
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,7 +422,9 @@ mod tests {
 
     fn extraer_errores(result: Result<Reserva, ReservaError>) -> Result<Vec<String>, String> {
         match result {
-            Err(ReservaError::Validacion(errores)) => Ok(errores),
+            Err(ReservaError::Validacion(errores)) => {
+                Ok(errores.into_iter().map(|e| e.mensaje).collect())
+            }
             Err(e) => Err(format!("Se esperaba Validacion, pero se obtuvo: {:?}", e)),
             Ok(r) => Err(format!("Se esperaba error, pero se obtuvo Ok({:?})", r)),
         }
@@ -257,6 +506,27 @@ mod tests {
         assert_contiene_error(&errores, &["fin", "posterior"])
     }
 
+    #[test]
+    fn errores_de_validacion_llevan_campo_y_codigo() {
+        let ahora = Utc::now();
+        let error = Reserva::new(
+            "".into(),
+            "usuario1".into(),
+            ahora + Duration::hours(2),
+            ahora,
+        )
+        .unwrap_err();
+        let campos = error.campos();
+
+        assert_eq!(error.codigo(), "RESERVA_VALIDACION");
+        assert!(campos
+            .iter()
+            .any(|c| c.campo == "sala_id" && c.codigo == "RESERVA_SALA_ID_VACIO"));
+        assert!(campos
+            .iter()
+            .any(|c| c.campo == "fecha_fin" && c.codigo == "RESERVA_FECHA_FIN_ANTERIOR_A_INICIO"));
+    }
+
     #[test]
     fn crear_reserva_con_duracion_muy_corta() -> Result<(), String> {
         let ahora = Utc::now();
@@ -313,6 +583,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn asignar_grupo_ignora_los_vacios() -> Result<(), String> {
+        let inicio = Utc::now() + Duration::hours(1);
+        let mut reserva = Reserva::new(
+            "sala1".into(),
+            "usuario1".into(),
+            inicio,
+            inicio + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert_eq!(reserva.grupo_id(), None);
+
+        reserva.asignar_grupo(Some(" equipo ".into()));
+        assert_eq!(reserva.grupo_id(), Some("equipo"));
+
+        reserva.asignar_grupo(Some("  ".into()));
+        assert_eq!(reserva.grupo_id(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reprogramar_valida_las_fechas() -> Result<(), String> {
+        let inicio = Utc::now() + Duration::hours(1);
+        let mut reserva = Reserva::new(
+            "sala1".into(),
+            "usuario1".into(),
+            inicio,
+            inicio + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+
+        let nuevo = inicio + Duration::days(1);
+        reserva
+            .reprogramar(nuevo, nuevo + Duration::hours(2))
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert_eq!(reserva.fecha_inicio(), nuevo);
+        assert_eq!(reserva.duracion_minutos(), 120);
+
+        let pasado = Utc::now() - Duration::hours(3);
+        assert!(reserva
+            .reprogramar(pasado, pasado + Duration::hours(1))
+            .is_err());
+        assert_eq!(reserva.fecha_inicio(), nuevo);
+
+        Ok(())
+    }
+
+    #[test]
+    fn creado_por_y_seudonimo() -> Result<(), String> {
+        let inicio = Utc::now() + Duration::hours(1);
+        let reserva = Reserva::new(
+            "sala1".into(),
+            "directora".into(),
+            inicio,
+            inicio + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+
+        let propia = reserva.clone().con_creador("directora".into());
+        assert_eq!(propia.creado_por, None);
+        assert_eq!(propia.creado_por(), "directora");
+
+        let mut delegada = reserva.con_creador("asistente".into());
+        assert_eq!(delegada.creado_por(), "asistente");
+
+        delegada.seudonimizar("asistente", "eliminado-1");
+        assert_eq!(delegada.usuario_id(), "directora");
+        assert_eq!(delegada.creado_por(), "eliminado-1");
+
+        Ok(())
+    }
+
     #[test]
     fn detectar_solapamiento_entre_reservas() -> Result<(), String> {
         let ahora = Utc::now();
@@ -334,6 +677,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn margenes_de_sala_cuentan_en_el_solapamiento() -> Result<(), String> {
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(1);
+
+        // Una detrás de otra: sin márgenes no chocan
+        let primera = Reserva::new("sala1".into(), "usuario1".into(), inicio, fin)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        let seguida = Reserva::new(
+            "sala1".into(),
+            "usuario2".into(),
+            fin,
+            fin + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert!(!primera.se_solapa_con(&seguida));
+
+        // 10 min de limpieza tras la primera pisan el inicio de la siguiente
+        let primera = primera.con_margenes(5, 10);
+        assert!(primera.se_solapa_con(&seguida));
+        assert!(seguida.se_solapa_con(&primera));
+        assert_eq!(primera.fecha_fin(), fin);
+        assert_eq!(primera.fin_ocupado(), fin + Duration::minutes(10));
+        assert_eq!(primera.inicio_ocupado(), inicio - Duration::minutes(5));
+
+        // Dejando el hueco de limpieza más la preparación ya no chocan
+        let holgada = Reserva::new(
+            "sala1".into(),
+            "usuario2".into(),
+            fin + Duration::minutes(15),
+            fin + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?
+        .con_margenes(5, 10);
+        assert!(!primera.se_solapa_con(&holgada));
+
+        // Los márgenes son de la sala: no afectan a otras salas
+        let otra_sala = Reserva::new(
+            "sala2".into(),
+            "usuario2".into(),
+            fin,
+            fin + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert!(!primera.se_solapa_con(&otra_sala));
+
+        Ok(())
+    }
+
     #[test]
     fn no_detectar_solapamiento_sin_conflicto() -> Result<(), String> {
         let ahora = Utc::now();
@@ -398,4 +790,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn solapamiento_por_recurso_compartido() -> Result<(), String> {
+        let inicio = Utc::now() + Duration::hours(1);
+        let fin = inicio + Duration::hours(2);
+
+        let con_proyector = Reserva::new("sala1".into(), "usuario1".into(), inicio, fin)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?
+            .con_recursos(vec![
+                "proyector".into(),
+                " plaza-3 ".into(),
+                "proyector".into(),
+            ]);
+        assert_eq!(con_proyector.recursos(), ["proyector", "plaza-3"]);
+
+        let solo_proyector = Reserva::de_recursos(
+            "usuario2".into(),
+            vec!["proyector".into()],
+            inicio + Duration::hours(1),
+            fin + Duration::hours(1),
+        )
+        .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert!(!solo_proyector.tiene_sala());
+
+        assert!(con_proyector.se_solapa_con(&solo_proyector));
+        assert_eq!(
+            solo_proyector.recurso_en_conflicto(&con_proyector),
+            Some("proyector")
+        );
+
+        // Dos reservas sin sala solo chocan si comparten recurso
+        let solo_plaza =
+            Reserva::de_recursos("usuario3".into(), vec!["plaza-4".into()], inicio, fin)
+                .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert!(!solo_plaza.se_solapa_con(&solo_proyector));
+        Ok(())
+    }
+
+    #[test]
+    fn reserva_sin_sala_necesita_recursos() {
+        let inicio = Utc::now() + Duration::hours(1);
+        let error = Reserva::de_recursos(
+            "usuario1".into(),
+            vec!["  ".into()],
+            inicio,
+            inicio + Duration::hours(1),
+        )
+        .unwrap_err();
+        assert!(error
+            .campos()
+            .iter()
+            .any(|c| c.campo == "recursos" && c.codigo == "RESERVA_SIN_SALA_NI_RECURSOS"));
+
+        let mut reserva = Reserva::de_recursos(
+            "usuario1".into(),
+            vec!["proyector".into()],
+            inicio,
+            inicio + Duration::hours(1),
+        )
+        .unwrap();
+        assert!(reserva.cambiar_recursos(vec![]).is_err());
+        assert_eq!(reserva.recursos(), ["proyector"]);
+    }
 }
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Convierte una fecha y hora local de `zona` a UTC
+///
+/// Los cambios de horario se resuelven como en los calendarios habituales:
+/// - En otoño la hora se repite: se toma la primera (horario de verano).
+/// - En primavera hay horas que no existen: se desplazan hacia delante lo que
+///   dure el salto (las 02:30 del cambio en Madrid pasan a las 03:30).
+pub fn local_a_utc(zona: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match zona.from_local_datetime(&local) {
+        LocalResult::Single(fecha) => fecha.with_timezone(&Utc),
+        LocalResult::Ambiguous(primera, _) => primera.with_timezone(&Utc),
+        LocalResult::None => {
+            // Se aplica el desfase que había justo antes del salto
+            let desfase = zona
+                .offset_from_utc_datetime(&(local - Duration::days(1)))
+                .fix()
+                .local_minus_utc();
+            Utc.from_utc_datetime(&(local - Duration::seconds(desfase as i64)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use chrono_tz::{America, Europe};
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn convierte_segun_el_horario_de_cada_temporada() {
+        // Madrid: UTC+1 en invierno, UTC+2 en verano
+        assert_eq!(
+            local_a_utc(&Europe::Madrid, local(2024, 1, 15, 9, 0)),
+            Utc.with_ymd_and_hms(2024, 1, 15, 8, 0, 0).unwrap()
+        );
+        assert_eq!(
+            local_a_utc(&Europe::Madrid, local(2024, 7, 15, 9, 0)),
+            Utc.with_ymd_and_hms(2024, 7, 15, 7, 0, 0).unwrap()
+        );
+        // Ciudad de México no cambia de horario desde 2022 (UTC-6)
+        assert_eq!(
+            local_a_utc(&America::Mexico_City, local(2024, 7, 15, 9, 0)),
+            Utc.with_ymd_and_hms(2024, 7, 15, 15, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn hora_inexistente_de_primavera_se_desplaza() {
+        // 31/03/2024: en Madrid se pasa de las 02:00 a las 03:00
+        assert_eq!(
+            local_a_utc(&Europe::Madrid, local(2024, 3, 31, 2, 30)),
+            Utc.with_ymd_and_hms(2024, 3, 31, 1, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn hora_repetida_de_otono_toma_la_primera() {
+        // 27/10/2024: en Madrid las 02:00-03:00 ocurren dos veces
+        assert_eq!(
+            local_a_utc(&Europe::Madrid, local(2024, 10, 27, 2, 30)),
+            Utc.with_ymd_and_hms(2024, 10, 27, 0, 30, 0).unwrap()
+        );
+    }
+}
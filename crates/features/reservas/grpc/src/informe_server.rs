@@ -0,0 +1,215 @@
+use crate::proto::informe_service_server::{
+    InformeService as InformeServiceTrait, InformeServiceServer,
+};
+use crate::proto::{
+    ExportarInformeCsvRequest, ExportarInformeCsvResponse, GenerarInformeRequest, InformeResponse,
+    MetricasFranja as ProtoMetricasFranja, MetricasSala as ProtoMetricasSala,
+    MetricasUsuario as ProtoMetricasUsuario,
+};
+use crate::server::{parse_datetime, reserva_error_to_status};
+use reservas_application::InformeService;
+use reservas_domain::{
+    AgrupacionInforme, InformeUso, MetricasFranja, MetricasSala, MetricasUsuario,
+};
+use shared_errores::Idioma;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use usuarios_auth::grpc::RequestPrincipalExt;
+use usuarios_domain::Permiso;
+
+/// Servidor gRPC para los informes de uso
+pub struct InformeGrpcServer {
+    service: Arc<dyn InformeService>,
+}
+
+impl InformeGrpcServer {
+    pub fn new(service: Arc<dyn InformeService>) -> Self {
+        Self { service }
+    }
+
+    pub fn into_service(self) -> InformeServiceServer<Self> {
+        InformeServiceServer::new(self)
+    }
+
+    async fn informe(
+        &self,
+        desde: &str,
+        hasta: &str,
+        idioma: Idioma,
+    ) -> Result<InformeUso, Status> {
+        let desde = parse_datetime(desde)?;
+        let hasta = parse_datetime(hasta)?;
+
+        self.service
+            .generar_informe(desde, hasta)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))
+    }
+}
+
+#[tonic::async_trait]
+impl InformeServiceTrait for InformeGrpcServer {
+    async fn generar_informe(
+        &self,
+        request: Request<GenerarInformeRequest>,
+    ) -> Result<Response<InformeResponse>, Status> {
+        // Requiere informe:read
+        request.principal_con_permiso(Permiso::VerInformes)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let informe = self.informe(&req.desde, &req.hasta, idioma).await?;
+
+        Ok(Response::new(informe_to_proto(&informe)))
+    }
+
+    async fn exportar_informe_csv(
+        &self,
+        request: Request<ExportarInformeCsvRequest>,
+    ) -> Result<Response<ExportarInformeCsvResponse>, Status> {
+        // Requiere informe:read
+        request.principal_con_permiso(Permiso::VerInformes)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let agrupacion = AgrupacionInforme::from_str_opt(&req.agrupacion).ok_or_else(|| {
+            Status::invalid_argument("Agrupación inválida. Use: sala, usuario o franja")
+        })?;
+        let informe = self.informe(&req.desde, &req.hasta, idioma).await?;
+
+        Ok(Response::new(ExportarInformeCsvResponse {
+            csv: informe.to_csv(agrupacion),
+        }))
+    }
+}
+
+fn informe_to_proto(informe: &InformeUso) -> InformeResponse {
+    InformeResponse {
+        desde: informe.desde.to_rfc3339(),
+        hasta: informe.hasta.to_rfc3339(),
+        total_reservas: informe.total_reservas as u32,
+        canceladas: informe.canceladas as u32,
+        no_shows: informe.no_shows as u32,
+        tasa_cancelacion: informe.tasa_cancelacion,
+        tasa_no_show: informe.tasa_no_show,
+        tasa_ocupacion: informe.tasa_ocupacion,
+        por_sala: informe.por_sala.iter().map(sala_to_proto).collect(),
+        por_usuario: informe.por_usuario.iter().map(usuario_to_proto).collect(),
+        por_franja: informe.por_franja.iter().map(franja_to_proto).collect(),
+        horas_pico: informe.horas_pico.iter().map(franja_to_proto).collect(),
+        top_usuarios: informe.top_usuarios.iter().map(usuario_to_proto).collect(),
+    }
+}
+
+fn sala_to_proto(m: &MetricasSala) -> ProtoMetricasSala {
+    ProtoMetricasSala {
+        sala_id: m.sala_id.clone(),
+        sala_nombre: m.sala_nombre.clone(),
+        total_reservas: m.total_reservas as u32,
+        canceladas: m.canceladas as u32,
+        no_shows: m.no_shows as u32,
+        horas_reservadas: m.horas_reservadas,
+        tasa_ocupacion: m.tasa_ocupacion,
+    }
+}
+
+fn usuario_to_proto(m: &MetricasUsuario) -> ProtoMetricasUsuario {
+    ProtoMetricasUsuario {
+        usuario_id: m.usuario_id.clone(),
+        total_reservas: m.total_reservas as u32,
+        canceladas: m.canceladas as u32,
+        no_shows: m.no_shows as u32,
+        horas_reservadas: m.horas_reservadas,
+    }
+}
+
+fn franja_to_proto(m: &MetricasFranja) -> ProtoMetricasFranja {
+    ProtoMetricasFranja {
+        dia_semana: m.dia_semana.clone(),
+        hora: m.hora,
+        reservas: m.reservas as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use reservas_domain::HorarioApertura;
+    use reservas_domain::ReservaError;
+    use usuarios_auth::Principal;
+    use usuarios_domain::Rol;
+
+    struct MockInformeService;
+
+    #[async_trait]
+    impl InformeService for MockInformeService {
+        async fn generar_informe(
+            &self,
+            desde: DateTime<Utc>,
+            hasta: DateTime<Utc>,
+        ) -> Result<InformeUso, ReservaError> {
+            Ok(InformeUso::calcular(
+                desde,
+                hasta,
+                &[],
+                &[(
+                    "sala1".to_string(),
+                    "Sala Test".to_string(),
+                    chrono_tz::Europe::Madrid,
+                )],
+                HorarioApertura::default(),
+                Utc::now(),
+            ))
+        }
+    }
+
+    fn request_con_rol<T>(mensaje: T, rol: Rol) -> Request<T> {
+        let mut req = Request::new(mensaje);
+        req.extensions_mut()
+            .insert(Principal::new("user-1", "user@example.com", rol));
+        req
+    }
+
+    fn rango() -> GenerarInformeRequest {
+        GenerarInformeRequest {
+            desde: "2024-01-01T00:00:00Z".to_string(),
+            hasta: "2024-01-08T00:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn generar_informe_devuelve_mensajes_tipados() {
+        let server = InformeGrpcServer::new(Arc::new(MockInformeService));
+
+        let resp = server
+            .generar_informe(request_con_rol(rango(), Rol::Admin))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.desde, "2024-01-01T00:00:00+00:00");
+        assert_eq!(resp.total_reservas, 0);
+        assert_eq!(resp.por_sala.len(), 1);
+        assert_eq!(resp.por_sala[0].sala_id, "sala1");
+        assert_eq!(resp.por_sala[0].sala_nombre.as_deref(), Some("Sala Test"));
+        assert_eq!(resp.por_sala[0].tasa_ocupacion, 0.0);
+    }
+
+    #[tokio::test]
+    async fn generar_informe_requiere_permiso_de_informes() {
+        let server = InformeGrpcServer::new(Arc::new(MockInformeService));
+
+        let resultado = server
+            .generar_informe(request_con_rol(rango(), Rol::Usuario))
+            .await;
+        assert_eq!(resultado.unwrap_err().code(), tonic::Code::PermissionDenied);
+
+        // El rol de solo lectura puede consultar informes
+        assert!(server
+            .generar_informe(request_con_rol(rango(), Rol::Lectura))
+            .await
+            .is_ok());
+    }
+}
@@ -1,7 +1,7 @@
-pub mod auth;
+pub mod informe_server;
 pub mod server;
 
-pub use auth::{AuthUser, RequestAuthExt};
+pub use informe_server::InformeGrpcServer;
 pub use server::ReservaGrpcServer;
 
 // Re-exportar los tipos generados por tonic
@@ -1,20 +1,30 @@
-use crate::auth::RequestAuthExt;
 use crate::proto::reserva_service_server::ReservaService as ReservaServiceTrait;
 use crate::proto::{
-    CancelarReservaRequest, CompletarReservaRequest, CrearReservaRequest,
-    EstadoReserva as ProtoEstadoReserva, ListarReservasPorSalaRequest,
-    ListarReservasPorUsuarioRequest, ListarReservasRequest, ListarReservasResponse,
-    ObtenerReservaRequest, Reserva as ProtoReserva, ReservaResponse,
-    VerificarDisponibilidadRequest, VerificarDisponibilidadResponse,
+    AsignarGrupoReservaRequest, CambiarRecursosReservaRequest, CancelarReservaRequest,
+    CompletarReservaRequest, ConjuntoRequest, CrearReservaConjuntaRequest, CrearReservaRequest,
+    CrearReservasRecurrentesRequest, EstadoReserva as ProtoEstadoReserva,
+    ListarReservasPorSalaRequest, ListarReservasPorUsuarioRequest, ListarReservasRequest,
+    ListarReservasResponse, ObtenerReservaRequest, ReprogramarConjuntoRequest,
+    Reserva as ProtoReserva, ReservaResponse, VerificarDisponibilidadRequest,
+    VerificarDisponibilidadResponse,
 };
 use chrono::{DateTime, Utc};
-use reservas_application::ReservaService;
-use reservas_domain::{EstadoReserva, Reserva};
+use chrono_tz::Tz;
+use reservas_application::{ConsultaReservas, ReservaService};
+use reservas_domain::{
+    EstadoReserva, Frecuencia, ParteConjunto, Recurrencia, Reserva, ReservaError,
+};
+use shared_errores::{ErrorCampo, Idioma};
+use shared_paginacion::Paginacion;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
+use usuarios_auth::grpc::RequestPrincipalExt;
+use usuarios_auth::{Autorizador, Principal};
+use usuarios_domain::Permiso;
 
 /// Servidor gRPC para el servicio de Reservas
-pub struct ReservaGrpcServer<S: ReservaService> {
+pub struct ReservaGrpcServer<S: ReservaService + ?Sized> {
     service: Arc<S>,
 }
 
@@ -26,17 +36,143 @@ impl<S: ReservaService> ReservaGrpcServer<S> {
     }
 }
 
+impl<S: ReservaService + ?Sized> ReservaGrpcServer<S> {
+    /// Crea el servidor a partir de un servicio compartido con otros adaptadores
+    pub fn from_arc(service: Arc<S>) -> Self {
+        Self { service }
+    }
+
+    /// Zona horaria de cada sala para devolver las fechas en hora local
+    #[allow(clippy::result_large_err)]
+    async fn zonas(&self, idioma: Idioma) -> Result<HashMap<String, Tz>, Status> {
+        self.service
+            .zonas_horarias_salas()
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))
+    }
+
+    /// Comprueba que el usuario pueda cancelar o completar la reserva: la
+    /// suya, la de uno de sus grupos o, con `reserva:manage_any`, la de
+    /// cualquiera en esa sala
+    async fn exigir_gestion(
+        &self,
+        principal: &Principal,
+        id: &str,
+        idioma: Idioma,
+    ) -> Result<(), Status> {
+        let reserva = self
+            .service
+            .obtener_reserva(id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?
+            .ok_or_else(|| reserva_error_to_status(ReservaError::NoEncontrada, idioma))?;
+
+        Autorizador::exigir_gestion_reserva(
+            principal,
+            reserva.usuario_id(),
+            reserva.grupo_id(),
+            reserva.sala_id(),
+        )
+        .map_err(|e| shared_errores::grpc::a_status_en(&e, idioma))
+    }
+
+    /// Reservas de la reserva conjunta, comprobando que el usuario pueda
+    /// gestionarlas todas
+    async fn exigir_gestion_conjunto(
+        &self,
+        principal: &Principal,
+        conjunto_id: &str,
+        idioma: Idioma,
+    ) -> Result<(), Status> {
+        let reservas = self
+            .service
+            .listar_conjunto(conjunto_id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        for reserva in &reservas {
+            Autorizador::exigir_gestion_reserva(
+                principal,
+                reserva.usuario_id(),
+                reserva.grupo_id(),
+                reserva.sala_id(),
+            )
+            .map_err(|e| shared_errores::grpc::a_status_en(&e, idioma))?;
+        }
+        Ok(())
+    }
+
+    /// Reserva que el usuario puede ver: la suya, la de uno de sus grupos o,
+    /// con `reserva:read_any`, la de cualquiera en esa sala
+    async fn obtener_visible(
+        &self,
+        principal: &Principal,
+        id: &str,
+        idioma: Idioma,
+    ) -> Result<Reserva, Status> {
+        let reserva = self
+            .service
+            .obtener_reserva(id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?
+            .ok_or_else(|| reserva_error_to_status(ReservaError::NoEncontrada, idioma))?;
+
+        Autorizador::exigir_ver_reserva(
+            principal,
+            reserva.usuario_id(),
+            reserva.grupo_id(),
+            reserva.sala_id(),
+        )
+        .map_err(|e| shared_errores::grpc::a_status_en(&e, idioma))?;
+        Ok(reserva)
+    }
+
+    async fn lista_con_zonas(
+        &self,
+        reservas: &[Reserva],
+        idioma: Idioma,
+    ) -> Result<ListarReservasResponse, Status> {
+        let zonas = self.zonas(idioma).await?;
+        Ok(lista_completa(
+            reservas
+                .iter()
+                .map(|r| reserva_to_proto(r, &zonas))
+                .collect(),
+        ))
+    }
+}
+
 // Funciones de conversión entre tipos de dominio y proto
 
-fn reserva_to_proto(reserva: &Reserva) -> ProtoReserva {
+/// Organizador de una reserva nueva: el indicado o, si no, quien la hace
+fn organizador(usuario_id: String, principal: &Principal) -> String {
+    if usuario_id.trim().is_empty() {
+        principal.user_id.clone()
+    } else {
+        usuario_id
+    }
+}
+
+/// Las fechas salen con el desfase de la sala (UTC si la sala ya no existe)
+fn reserva_to_proto(reserva: &Reserva, zonas: &HashMap<String, Tz>) -> ProtoReserva {
+    let zona = zonas
+        .get(reserva.sala_id())
+        .copied()
+        .unwrap_or(chrono_tz::UTC);
+
     ProtoReserva {
         id: reserva.id().to_string(),
         sala_id: reserva.sala_id().to_string(),
         usuario_id: reserva.usuario_id().to_string(),
-        fecha_inicio: reserva.fecha_inicio().to_rfc3339(),
-        fecha_fin: reserva.fecha_fin().to_rfc3339(),
+        fecha_inicio: reserva.fecha_inicio().with_timezone(&zona).to_rfc3339(),
+        fecha_fin: reserva.fecha_fin().with_timezone(&zona).to_rfc3339(),
         estado: estado_to_proto(reserva.estado()),
         created_at: reserva.created_at().to_rfc3339(),
+        zona_horaria: zona.name().to_string(),
+        grupo_id: reserva.grupo_id().map(str::to_string),
+        creado_por: reserva.creado_por().to_string(),
+        conjunto_id: reserva.conjunto_id().map(str::to_string),
+        recursos: reserva.recursos().to_vec(),
     }
 }
 
@@ -48,56 +184,147 @@ fn estado_to_proto(estado: &EstadoReserva) -> i32 {
     }
 }
 
+fn estado_from_proto(estado: ProtoEstadoReserva) -> EstadoReserva {
+    match estado {
+        ProtoEstadoReserva::Activa => EstadoReserva::Activa,
+        ProtoEstadoReserva::Cancelada => EstadoReserva::Cancelada,
+        ProtoEstadoReserva::Completada => EstadoReserva::Completada,
+    }
+}
+
+/// Solo las reservas que el usuario puede ver
+fn visibles(principal: &Principal, reservas: Vec<Reserva>) -> Vec<Reserva> {
+    reservas
+        .into_iter()
+        .filter(|r| {
+            Autorizador::puede_ver_reserva(principal, r.usuario_id(), r.grupo_id(), r.sala_id())
+        })
+        .collect()
+}
+
+/// Respuesta de los listados que no se paginan (por sala, por usuario...)
+fn lista_completa(reservas: Vec<ProtoReserva>) -> ListarReservasResponse {
+    ListarReservasResponse {
+        total: reservas.len() as u32,
+        siguiente_offset: None,
+        reservas,
+    }
+}
+
 #[allow(clippy::result_large_err)]
-fn parse_datetime(s: &str) -> Result<DateTime<Utc>, Status> {
+pub(crate) fn parse_datetime(s: &str) -> Result<DateTime<Utc>, Status> {
     DateTime::parse_from_rfc3339(s)
         .map(|dt| dt.with_timezone(&Utc))
         .map_err(|e| Status::invalid_argument(format!("Fecha inválida: {}", e)))
 }
 
+/// Convierte un `ReservaError` en `Status` con su código y campos en los detalles
+pub(crate) fn reserva_error_to_status(error: ReservaError, idioma: Idioma) -> Status {
+    shared_errores::grpc::a_status_en(&error, idioma)
+}
+
 #[tonic::async_trait]
-impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
+impl<S: ReservaService + ?Sized + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
     async fn crear_reserva(
         &self,
         request: Request<CrearReservaRequest>,
     ) -> Result<Response<ReservaResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Requiere reserva:create; en nombre de otro, que este lo haya autorizado
+        let principal = request.principal_con_permiso(Permiso::CrearReservas)?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
+        let organizador = organizador(req.usuario_id, &principal);
 
         let fecha_inicio = parse_datetime(&req.fecha_inicio)?;
         let fecha_fin = parse_datetime(&req.fecha_fin)?;
 
-        let reserva = self
-            .service
-            .crear_reserva(req.sala_id, req.usuario_id, fecha_inicio, fecha_fin)
-            .await
-            .map_err(|e| Status::internal(format!("Error al crear reserva: {}", e)))?;
+        // Con recursos, la sala es opcional
+        let reserva = if req.recursos.is_empty() {
+            self.service
+                .crear_reserva(
+                    req.sala_id,
+                    organizador,
+                    principal.user_id,
+                    fecha_inicio,
+                    fecha_fin,
+                )
+                .await
+        } else {
+            self.service
+                .crear_reserva_con_recursos(
+                    Some(req.sala_id),
+                    organizador,
+                    principal.user_id,
+                    req.recursos,
+                    fecha_inicio,
+                    fecha_fin,
+                )
+                .await
+        }
+        .map_err(|e| reserva_error_to_status(e, idioma))?;
 
+        let zonas = self.zonas(idioma).await?;
         Ok(Response::new(ReservaResponse {
-            reserva: Some(reserva_to_proto(&reserva)),
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
         }))
     }
 
-    async fn obtener_reserva(
+    async fn crear_reservas_recurrentes(
         &self,
-        request: Request<ObtenerReservaRequest>,
-    ) -> Result<Response<ReservaResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        request: Request<CrearReservasRecurrentesRequest>,
+    ) -> Result<Response<ListarReservasResponse>, Status> {
+        // Requiere reserva:create; en nombre de otro, que este lo haya autorizado
+        let principal = request.principal_con_permiso(Permiso::CrearReservas)?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
+        let organizador = organizador(req.usuario_id, &principal);
 
-        let reserva = self
+        let fecha_inicio = parse_datetime(&req.fecha_inicio)?;
+        let fecha_fin = parse_datetime(&req.fecha_fin)?;
+        let frecuencia = Frecuencia::from_str_opt(&req.frecuencia).ok_or_else(|| {
+            Status::invalid_argument("Frecuencia inválida. Use: diaria o semanal")
+        })?;
+        let recurrencia = Recurrencia::new(frecuencia, req.repeticiones)
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        let reservas = self
             .service
-            .obtener_reserva(&req.id)
+            .crear_reservas_recurrentes(
+                req.sala_id,
+                organizador,
+                principal.user_id,
+                fecha_inicio,
+                fecha_fin,
+                recurrencia,
+            )
             .await
-            .map_err(|e| Status::internal(format!("Error al obtener reserva: {}", e)))?
-            .ok_or_else(|| Status::not_found("Reserva no encontrada"))?;
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        let zonas = self.zonas(idioma).await?;
+        let proto_reservas = reservas
+            .iter()
+            .map(|r| reserva_to_proto(r, &zonas))
+            .collect();
+
+        Ok(Response::new(lista_completa(proto_reservas)))
+    }
+
+    async fn obtener_reserva(
+        &self,
+        request: Request<ObtenerReservaRequest>,
+    ) -> Result<Response<ReservaResponse>, Status> {
+        // La propia, la de uno de sus grupos o con `reserva:read_any` en la sala
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
+        let req = request.into_inner();
+        let reserva = self.obtener_visible(&principal, &req.id, idioma).await?;
+
+        let zonas = self.zonas(idioma).await?;
         Ok(Response::new(ReservaResponse {
-            reserva: Some(reserva_to_proto(&reserva)),
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
         }))
     }
 
@@ -105,19 +332,53 @@ impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
         &self,
         request: Request<ListarReservasRequest>,
     ) -> Result<Response<ListarReservasResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Sin `reserva:read_any` solo las propias y las de sus grupos
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
-        let reservas = self
+        let req = request.into_inner();
+        let ve_todas = Autorizador::ve_todas_las_reservas(&principal, req.sala_id.as_deref());
+        let estado = match req.estado {
+            Some(e) => Some(
+                ProtoEstadoReserva::try_from(e)
+                    .map(estado_from_proto)
+                    .map_err(|_| {
+                        let campo = ErrorCampo::con_codigo("estado", "RESERVA_ESTADO_INVALIDO");
+                        reserva_error_to_status(ReservaError::Validacion(vec![campo]), idioma)
+                    })?,
+            ),
+            None => None,
+        };
+
+        let consulta = ConsultaReservas {
+            estado,
+            sala_id: req.sala_id,
+            usuario_id: req.usuario_id,
+            grupo_id: req.grupo_id,
+            desde: req.desde.as_deref().map(parse_datetime).transpose()?,
+            hasta: req.hasta.as_deref().map(parse_datetime).transpose()?,
+            paginacion: Paginacion::new(req.offset, req.limite),
+            ..Default::default()
+        }
+        .ordenada_por(&req.orden, &req.direccion)
+        .map_err(|e| reserva_error_to_status(e, idioma))?
+        .visible_para(ve_todas, &principal.user_id, &principal.grupos);
+
+        let pagina = self
             .service
-            .listar_reservas()
+            .buscar_reservas(consulta)
             .await
-            .map_err(|e| Status::internal(format!("Error al listar reservas: {}", e)))?;
-
-        let proto_reservas = reservas.iter().map(reserva_to_proto).collect();
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
 
+        let zonas = self.zonas(idioma).await?;
         Ok(Response::new(ListarReservasResponse {
-            reservas: proto_reservas,
+            reservas: pagina
+                .elementos
+                .iter()
+                .map(|r| reserva_to_proto(r, &zonas))
+                .collect(),
+            total: pagina.total as u32,
+            siguiente_offset: pagina.siguiente_offset(),
         }))
     }
 
@@ -125,8 +386,10 @@ impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
         &self,
         request: Request<ListarReservasPorSalaRequest>,
     ) -> Result<Response<ListarReservasResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Solo las que puede ver: sin `reserva:read_any`, las propias y las
+        // de sus grupos
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -134,21 +397,26 @@ impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
             .service
             .listar_reservas_por_sala(&req.sala_id)
             .await
-            .map_err(|e| Status::internal(format!("Error al listar reservas: {}", e)))?;
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+        let reservas = visibles(&principal, reservas);
 
-        let proto_reservas = reservas.iter().map(reserva_to_proto).collect();
+        let zonas = self.zonas(idioma).await?;
+        let proto_reservas = reservas
+            .iter()
+            .map(|r| reserva_to_proto(r, &zonas))
+            .collect();
 
-        Ok(Response::new(ListarReservasResponse {
-            reservas: proto_reservas,
-        }))
+        Ok(Response::new(lista_completa(proto_reservas)))
     }
 
     async fn listar_reservas_por_usuario(
         &self,
         request: Request<ListarReservasPorUsuarioRequest>,
     ) -> Result<Response<ListarReservasResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Solo las que puede ver: sin `reserva:read_any`, las propias y las
+        // de sus grupos
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -156,32 +424,38 @@ impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
             .service
             .listar_reservas_por_usuario(&req.usuario_id)
             .await
-            .map_err(|e| Status::internal(format!("Error al listar reservas: {}", e)))?;
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+        let reservas = visibles(&principal, reservas);
 
-        let proto_reservas = reservas.iter().map(reserva_to_proto).collect();
+        let zonas = self.zonas(idioma).await?;
+        let proto_reservas = reservas
+            .iter()
+            .map(|r| reserva_to_proto(r, &zonas))
+            .collect();
 
-        Ok(Response::new(ListarReservasResponse {
-            reservas: proto_reservas,
-        }))
+        Ok(Response::new(lista_completa(proto_reservas)))
     }
 
     async fn cancelar_reserva(
         &self,
         request: Request<CancelarReservaRequest>,
     ) -> Result<Response<ReservaResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Solo el dueño o quien gestione las reservas de la sala
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
+        self.exigir_gestion(&principal, &req.id, idioma).await?;
 
         let reserva = self
             .service
             .cancelar_reserva(&req.id)
             .await
-            .map_err(|e| Status::internal(format!("Error al cancelar reserva: {}", e)))?;
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
 
+        let zonas = self.zonas(idioma).await?;
         Ok(Response::new(ReservaResponse {
-            reserva: Some(reserva_to_proto(&reserva)),
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
         }))
     }
 
@@ -189,44 +463,223 @@ impl<S: ReservaService + 'static> ReservaServiceTrait for ReservaGrpcServer<S> {
         &self,
         request: Request<CompletarReservaRequest>,
     ) -> Result<Response<ReservaResponse>, Status> {
-        // Validar autenticación
-        request.require_auth_user()?;
+        // Solo el dueño o quien gestione las reservas de la sala
+        let principal = request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
+        self.exigir_gestion(&principal, &req.id, idioma).await?;
 
         let reserva = self
             .service
             .completar_reserva(&req.id)
             .await
-            .map_err(|e| Status::internal(format!("Error al completar reserva: {}", e)))?;
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        let zonas = self.zonas(idioma).await?;
+        Ok(Response::new(ReservaResponse {
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
+        }))
+    }
+
+    async fn asignar_grupo_reserva(
+        &self,
+        request: Request<AsignarGrupoReservaRequest>,
+    ) -> Result<Response<ReservaResponse>, Status> {
+        // Quien puede gestionar la reserva, y solo a nombre de uno de sus grupos
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        self.exigir_gestion(&principal, &req.id, idioma).await?;
+        let grupo_id = req.grupo_id.filter(|g| !g.trim().is_empty());
+        if let Some(grupo_id) = &grupo_id {
+            if !principal.grupos.contains(grupo_id) {
+                let campo = ErrorCampo::con_codigo("grupo_id", "RESERVA_GRUPO_AJENO");
+                return Err(reserva_error_to_status(
+                    ReservaError::Validacion(vec![campo]),
+                    idioma,
+                ));
+            }
+        }
+
+        let reserva = self
+            .service
+            .asignar_grupo(&req.id, grupo_id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
 
+        let zonas = self.zonas(idioma).await?;
         Ok(Response::new(ReservaResponse {
-            reserva: Some(reserva_to_proto(&reserva)),
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
         }))
     }
 
+    async fn cambiar_recursos_reserva(
+        &self,
+        request: Request<CambiarRecursosReservaRequest>,
+    ) -> Result<Response<ReservaResponse>, Status> {
+        // Quien puede gestionar la reserva
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        self.exigir_gestion(&principal, &req.reserva_id, idioma)
+            .await?;
+
+        let reserva = self
+            .service
+            .cambiar_recursos(&req.reserva_id, req.recursos)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        let zonas = self.zonas(idioma).await?;
+        Ok(Response::new(ReservaResponse {
+            reserva: Some(reserva_to_proto(&reserva, &zonas)),
+        }))
+    }
+
+    async fn crear_reserva_conjunta(
+        &self,
+        request: Request<CrearReservaConjuntaRequest>,
+    ) -> Result<Response<ListarReservasResponse>, Status> {
+        // Requiere reserva:create; en nombre de otro, que este lo haya autorizado
+        let principal = request.principal_con_permiso(Permiso::CrearReservas)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let organizador = organizador(req.usuario_id, &principal);
+        let partes = req
+            .partes
+            .into_iter()
+            .map(|p| {
+                Ok(ParteConjunto {
+                    sala_id: p.sala_id,
+                    fecha_inicio: parse_datetime(&p.fecha_inicio)?,
+                    fecha_fin: parse_datetime(&p.fecha_fin)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        let reservas = self
+            .service
+            .crear_reserva_conjunta(organizador, principal.user_id, partes)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        Ok(Response::new(
+            self.lista_con_zonas(&reservas, idioma).await?,
+        ))
+    }
+
+    async fn obtener_conjunto(
+        &self,
+        request: Request<ConjuntoRequest>,
+    ) -> Result<Response<ListarReservasResponse>, Status> {
+        // Hay que poder ver cada una de sus reservas
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let reservas = self
+            .service
+            .listar_conjunto(&req.conjunto_id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+        for reserva in &reservas {
+            Autorizador::exigir_ver_reserva(
+                &principal,
+                reserva.usuario_id(),
+                reserva.grupo_id(),
+                reserva.sala_id(),
+            )
+            .map_err(|e| shared_errores::grpc::a_status_en(&e, idioma))?;
+        }
+
+        Ok(Response::new(
+            self.lista_con_zonas(&reservas, idioma).await?,
+        ))
+    }
+
+    async fn cancelar_conjunto(
+        &self,
+        request: Request<ConjuntoRequest>,
+    ) -> Result<Response<ListarReservasResponse>, Status> {
+        // Solo quien pueda gestionar todas sus reservas
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        self.exigir_gestion_conjunto(&principal, &req.conjunto_id, idioma)
+            .await?;
+
+        let reservas = self
+            .service
+            .cancelar_conjunto(&req.conjunto_id)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        Ok(Response::new(
+            self.lista_con_zonas(&reservas, idioma).await?,
+        ))
+    }
+
+    async fn reprogramar_conjunto(
+        &self,
+        request: Request<ReprogramarConjuntoRequest>,
+    ) -> Result<Response<ListarReservasResponse>, Status> {
+        // Solo quien pueda gestionar todas sus reservas
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+        let fecha_inicio = parse_datetime(&req.fecha_inicio)?;
+        self.exigir_gestion_conjunto(&principal, &req.conjunto_id, idioma)
+            .await?;
+
+        let reservas = self
+            .service
+            .reprogramar_conjunto(&req.conjunto_id, fecha_inicio)
+            .await
+            .map_err(|e| reserva_error_to_status(e, idioma))?;
+
+        Ok(Response::new(
+            self.lista_con_zonas(&reservas, idioma).await?,
+        ))
+    }
+
     async fn verificar_disponibilidad(
         &self,
         request: Request<VerificarDisponibilidadRequest>,
     ) -> Result<Response<VerificarDisponibilidadResponse>, Status> {
         // Validar autenticación
-        request.require_auth_user()?;
+        request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
         let fecha_inicio = parse_datetime(&req.fecha_inicio)?;
         let fecha_fin = parse_datetime(&req.fecha_fin)?;
 
-        let disponible = self
-            .service
-            .verificar_disponibilidad(&req.sala_id, fecha_inicio, fecha_fin)
-            .await
-            .map_err(|e| Status::internal(format!("Error al verificar disponibilidad: {}", e)))?;
+        let (disponible, que) = if req.recurso_id.trim().is_empty() {
+            let disponible = self
+                .service
+                .verificar_disponibilidad(&req.sala_id, fecha_inicio, fecha_fin)
+                .await;
+            (disponible, "La sala")
+        } else {
+            let disponible = self
+                .service
+                .verificar_disponibilidad_recurso(req.recurso_id.trim(), fecha_inicio, fecha_fin)
+                .await;
+            (disponible, "El recurso")
+        };
+        let disponible = disponible.map_err(|e| reserva_error_to_status(e, idioma))?;
 
         let mensaje = if disponible {
-            "La sala está disponible en el horario solicitado".to_string()
+            format!("{} está disponible en el horario solicitado", que)
         } else {
-            "La sala no está disponible en el horario solicitado".to_string()
+            format!("{} no está disponible en el horario solicitado", que)
         };
 
         Ok(Response::new(VerificarDisponibilidadResponse {
@@ -242,6 +695,7 @@ mod grpc_unit_tests {
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
     use reservas_domain::{EstadoReserva, Reserva as DomainReserva, ReservaError};
+    use shared_paginacion::Pagina;
 
     /// Mock mínimo del `ReservaService` para tests unitarios del servidor gRPC.
     struct MockReservaService {
@@ -270,6 +724,7 @@ mod grpc_unit_tests {
             &self,
             _sala_id: String,
             _usuario_id: String,
+            _creado_por: String,
             _fecha_inicio: DateTime<Utc>,
             _fecha_fin: DateTime<Utc>,
         ) -> Result<DomainReserva, ReservaError> {
@@ -277,6 +732,52 @@ mod grpc_unit_tests {
             Ok(self.reservas[0].clone())
         }
 
+        async fn crear_reservas_recurrentes(
+            &self,
+            _sala_id: String,
+            _usuario_id: String,
+            _creado_por: String,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+            recurrencia: Recurrencia,
+        ) -> Result<Vec<DomainReserva>, ReservaError> {
+            Ok(vec![
+                self.reservas[0].clone();
+                recurrencia.repeticiones as usize
+            ])
+        }
+
+        async fn crear_reserva_conjunta(
+            &self,
+            _usuario_id: String,
+            _creado_por: String,
+            _partes: Vec<ParteConjunto>,
+        ) -> Result<Vec<DomainReserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn listar_conjunto(
+            &self,
+            _conjunto_id: &str,
+        ) -> Result<Vec<DomainReserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn cancelar_conjunto(
+            &self,
+            _conjunto_id: &str,
+        ) -> Result<Vec<DomainReserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
+        async fn reprogramar_conjunto(
+            &self,
+            _conjunto_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+        ) -> Result<Vec<DomainReserva>, ReservaError> {
+            Ok(Vec::new())
+        }
+
         async fn obtener_reserva(&self, id: &str) -> Result<Option<DomainReserva>, ReservaError> {
             Ok(self.reservas.iter().find(|&r| r.id() == id).cloned())
         }
@@ -285,6 +786,13 @@ mod grpc_unit_tests {
             Ok(self.reservas.clone())
         }
 
+        async fn buscar_reservas(
+            &self,
+            consulta: ConsultaReservas,
+        ) -> Result<Pagina<DomainReserva>, ReservaError> {
+            Ok(consulta.aplicar(&self.reservas))
+        }
+
         async fn listar_reservas_por_sala(
             &self,
             sala_id: &str,
@@ -321,6 +829,39 @@ mod grpc_unit_tests {
                 .ok_or(ReservaError::NoEncontrada)
         }
 
+        async fn asignar_grupo(
+            &self,
+            id: &str,
+            grupo_id: Option<String>,
+        ) -> Result<DomainReserva, ReservaError> {
+            let mut reserva = self
+                .obtener_reserva(id)
+                .await?
+                .ok_or(ReservaError::NoEncontrada)?;
+            reserva.asignar_grupo(grupo_id);
+            Ok(reserva)
+        }
+
+        async fn crear_reserva_con_recursos(
+            &self,
+            _sala_id: Option<String>,
+            _usuario_id: String,
+            _creado_por: String,
+            _recursos: Vec<String>,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<DomainReserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
+        async fn cambiar_recursos(
+            &self,
+            _id: &str,
+            _recursos: Vec<String>,
+        ) -> Result<DomainReserva, ReservaError> {
+            Err(ReservaError::NoEncontrada)
+        }
+
         async fn verificar_disponibilidad(
             &self,
             _sala_id: &str,
@@ -329,6 +870,22 @@ mod grpc_unit_tests {
         ) -> Result<bool, ReservaError> {
             Ok(true)
         }
+
+        async fn verificar_disponibilidad_recurso(
+            &self,
+            _recurso_id: &str,
+            _fecha_inicio: DateTime<Utc>,
+            _fecha_fin: DateTime<Utc>,
+        ) -> Result<bool, ReservaError> {
+            Ok(true)
+        }
+
+        async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError> {
+            Ok(HashMap::from([(
+                "sala1".to_string(),
+                chrono_tz::America::Mexico_City,
+            )]))
+        }
     }
 
     #[tokio::test]
@@ -336,18 +893,15 @@ mod grpc_unit_tests {
         let service = MockReservaService::new();
         let server = ReservaGrpcServer::new(service);
 
-        // Generar token válido y añadirlo al metadata para pasar la autenticación
-        let token = usuarios_auth::jwt::JwtService::generate_token(
+        // Usuario autenticado con `reserva:read_any`, como lo dejaría la capa
+        // de autenticación
+        let principal = Principal::new(
             "test-user",
             "test@example.com",
-            usuarios_domain::Rol::Usuario,
-        )
-        .expect("failed to generate token");
-        let mut req = tonic::Request::new(ListarReservasRequest {});
-        req.metadata_mut().insert(
-            "authorization",
-            tonic::metadata::MetadataValue::try_from(format!("Bearer {}", token)).unwrap(),
+            usuarios_domain::Rol::Lectura,
         );
+        let mut req = tonic::Request::new(ListarReservasRequest::default());
+        req.extensions_mut().insert(principal);
 
         let resp = server
             .listar_reservas(req)
@@ -360,6 +914,219 @@ mod grpc_unit_tests {
         assert_eq!(proto.sala_id, "sala1");
         assert_eq!(proto.usuario_id, "usuario1");
         assert_eq!(proto.estado, ProtoEstadoReserva::Activa as i32);
+        // Fechas en la hora local de la sala (Ciudad de México, UTC-6)
+        assert_eq!(proto.zona_horaria, "America/Mexico_City");
+        assert!(proto.fecha_inicio.ends_with("-06:00"));
+        assert_eq!(inner.total, 1);
+        assert_eq!(inner.siguiente_offset, None);
+    }
+
+    #[tokio::test]
+    async fn servidor_listar_reservas_filtra_por_estado() {
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+
+        let principal = Principal::new(
+            "test-user",
+            "test@example.com",
+            usuarios_domain::Rol::Usuario,
+        );
+        let mut req = tonic::Request::new(ListarReservasRequest {
+            estado: Some(ProtoEstadoReserva::Cancelada as i32),
+            ..Default::default()
+        });
+        req.extensions_mut().insert(principal);
+
+        let inner = server
+            .listar_reservas(req)
+            .await
+            .expect("listar_reservas falló")
+            .into_inner();
+
+        assert!(inner.reservas.is_empty());
+        assert_eq!(inner.total, 0);
+    }
+
+    fn con_principal<T>(mensaje: T, principal: Principal) -> Request<T> {
+        let mut req = tonic::Request::new(mensaje);
+        req.extensions_mut().insert(principal);
+        req
+    }
+
+    #[tokio::test]
+    async fn servidor_sin_permiso_de_lectura_solo_ve_las_propias() {
+        use usuarios_domain::Rol;
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+        let otro = || Principal::new("otro", "otro@example.com", Rol::Usuario);
+        let dueno = || Principal::new("usuario1", "u1@example.com", Rol::Usuario);
+
+        let lista = server
+            .listar_reservas(con_principal(ListarReservasRequest::default(), otro()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+        assert_eq!(lista.total, 0);
+
+        // Pedir las de otro usuario no salta el filtro
+        let lista = server
+            .listar_reservas(con_principal(
+                ListarReservasRequest {
+                    usuario_id: Some("usuario1".to_string()),
+                    ..Default::default()
+                },
+                otro(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+
+        let por_sala = ListarReservasPorSalaRequest {
+            sala_id: "sala1".to_string(),
+        };
+        let lista = server
+            .listar_reservas_por_sala(con_principal(por_sala.clone(), otro()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+        let lista = server
+            .listar_reservas_por_sala(con_principal(por_sala, dueno()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lista.reservas.len(), 1);
+
+        let lista = server
+            .listar_reservas_por_usuario(con_principal(
+                ListarReservasPorUsuarioRequest {
+                    usuario_id: "usuario1".to_string(),
+                },
+                otro(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+
+        let obtener = ObtenerReservaRequest {
+            id: "r1".to_string(),
+        };
+        let status = server
+            .obtener_reserva(con_principal(obtener.clone(), otro()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(server
+            .obtener_reserva(con_principal(obtener, dueno()))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn servidor_los_miembros_ven_las_reservas_del_grupo() {
+        use usuarios_domain::Rol;
+        let mut service = MockReservaService::new();
+        service.reservas[0].asignar_grupo(Some("equipo".to_string()));
+        let server = ReservaGrpcServer::new(service);
+        let miembro = || {
+            Principal::new("ana", "ana@example.com", Rol::Usuario)
+                .with_grupos(vec!["equipo".into()])
+        };
+        let ajeno = || Principal::new("eva", "eva@example.com", Rol::Usuario);
+
+        let lista = server
+            .listar_reservas(con_principal(ListarReservasRequest::default(), miembro()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lista.reservas.len(), 1);
+        assert_eq!(lista.reservas[0].grupo_id.as_deref(), Some("equipo"));
+
+        let por_usuario = ListarReservasPorUsuarioRequest {
+            usuario_id: "usuario1".to_string(),
+        };
+        let lista = server
+            .listar_reservas_por_usuario(con_principal(por_usuario.clone(), miembro()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lista.reservas.len(), 1);
+        let lista = server
+            .listar_reservas_por_usuario(con_principal(por_usuario, ajeno()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+
+        assert!(server
+            .obtener_reserva(con_principal(
+                ObtenerReservaRequest {
+                    id: "r1".to_string(),
+                },
+                miembro(),
+            ))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn servidor_clave_de_api_sin_permiso_de_lectura_no_ve_reservas() {
+        use usuarios_domain::{ApiKey, Permiso};
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+        let clave = |permisos: Vec<Permiso>| {
+            let clave = ApiKey::new(
+                "Pantalla".to_string(),
+                "rsk_prueba",
+                "hash".to_string(),
+                permisos,
+                "admin".to_string(),
+                Utc::now(),
+            )
+            .unwrap();
+            Principal::desde_api_key(&clave)
+        };
+
+        // Su rol `Lectura` no cuenta: manda el alcance de la clave
+        let quiosco = clave(vec![Permiso::CrearReservas]);
+        let lista = server
+            .listar_reservas(con_principal(
+                ListarReservasRequest::default(),
+                quiosco.clone(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+        let lista = server
+            .listar_reservas_por_sala(con_principal(
+                ListarReservasPorSalaRequest {
+                    sala_id: "sala1".to_string(),
+                },
+                quiosco.clone(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(lista.reservas.is_empty());
+        let status = server
+            .obtener_reserva(con_principal(
+                ObtenerReservaRequest {
+                    id: "r1".to_string(),
+                },
+                quiosco,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let pantalla = clave(vec![Permiso::VerCualquierReserva]);
+        let lista = server
+            .listar_reservas(con_principal(ListarReservasRequest::default(), pantalla))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lista.reservas.len(), 1);
     }
 
     #[tokio::test]
@@ -373,18 +1140,15 @@ mod grpc_unit_tests {
             usuario_id: "usuario1".to_string(),
             fecha_inicio: ahora.to_rfc3339(),
             fecha_fin: (ahora + chrono::Duration::hours(1)).to_rfc3339(),
+            recursos: Vec::new(),
         });
 
-        let token = usuarios_auth::jwt::JwtService::generate_token(
+        let principal = Principal::new(
             "test-user",
             "test@example.com",
             usuarios_domain::Rol::Usuario,
-        )
-        .expect("failed to generate token");
-        req.metadata_mut().insert(
-            "authorization",
-            tonic::metadata::MetadataValue::try_from(format!("Bearer {}", token)).unwrap(),
         );
+        req.extensions_mut().insert(principal);
 
         let resp = server
             .crear_reserva(req)
@@ -397,4 +1161,110 @@ mod grpc_unit_tests {
         assert_eq!(proto.usuario_id, "usuario1");
         assert_eq!(proto.estado, ProtoEstadoReserva::Activa as i32);
     }
+
+    #[tokio::test]
+    async fn servidor_cancelar_reserva_inexistente_devuelve_not_found_con_codigo() {
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+
+        let principal = Principal::new(
+            "test-user",
+            "test@example.com",
+            usuarios_domain::Rol::Usuario,
+        );
+        let mut req = tonic::Request::new(CancelarReservaRequest {
+            id: "no-existe".to_string(),
+        });
+        req.extensions_mut().insert(principal);
+
+        let status = server.cancelar_reserva(req).await.unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(
+            shared_errores::grpc::detalles(&status).codigo.as_deref(),
+            Some("RESERVA_NO_ENCONTRADA")
+        );
+    }
+
+    #[tokio::test]
+    async fn servidor_traduce_el_error_segun_accept_language() {
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+
+        let principal = Principal::new(
+            "test-user",
+            "test@example.com",
+            usuarios_domain::Rol::Usuario,
+        );
+        let mut req = tonic::Request::new(CancelarReservaRequest {
+            id: "no-existe".to_string(),
+        });
+        req.extensions_mut().insert(principal);
+        req.metadata_mut().insert(
+            "accept-language",
+            tonic::metadata::MetadataValue::from_static("en-GB,en;q=0.9"),
+        );
+
+        let status = server.cancelar_reserva(req).await.unwrap_err();
+
+        assert_eq!(status.message(), "Booking not found");
+    }
+
+    fn cancelar_como(
+        usuario_id: &str,
+        rol: usuarios_domain::Rol,
+    ) -> Request<CancelarReservaRequest> {
+        let principal = Principal::new(usuario_id, "test@example.com", rol);
+        let mut req = tonic::Request::new(CancelarReservaRequest {
+            id: "r1".to_string(),
+        });
+        req.extensions_mut().insert(principal);
+        req
+    }
+
+    #[tokio::test]
+    async fn servidor_cancelar_reserva_ajena_requiere_permiso() {
+        use usuarios_domain::Rol;
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+
+        let status = server
+            .cancelar_reserva(cancelar_como("otro", Rol::Usuario))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(
+            shared_errores::grpc::detalles(&status).codigo.as_deref(),
+            Some("AUTH_PERMISO_DENEGADO")
+        );
+
+        assert!(server
+            .cancelar_reserva(cancelar_como("usuario1", Rol::Usuario))
+            .await
+            .is_ok());
+        assert!(server
+            .cancelar_reserva(cancelar_como("otro", Rol::Recepcionista))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn servidor_crear_reserva_requiere_permiso() {
+        let server = ReservaGrpcServer::new(MockReservaService::new());
+
+        let ahora = Utc::now();
+        let mut req = tonic::Request::new(CrearReservaRequest {
+            sala_id: "sala1".to_string(),
+            usuario_id: "lector".to_string(),
+            fecha_inicio: ahora.to_rfc3339(),
+            fecha_fin: (ahora + chrono::Duration::hours(1)).to_rfc3339(),
+            recursos: Vec::new(),
+        });
+        let principal = Principal::new(
+            "lector",
+            "lector@example.com",
+            usuarios_domain::Rol::Lectura,
+        );
+        req.extensions_mut().insert(principal);
+
+        let status = server.crear_reserva(req).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+    }
 }
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reservas_application::ReservaRepository;
+use reservas_application::{ConsultaReservas, ReservaRepository};
 use reservas_domain::{Reserva, ReservaError};
 use serde::{Deserialize, Serialize};
+use shared_paginacion::Pagina;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -130,6 +131,12 @@ impl ReservaRepository for FileReservaRepository {
         Ok(cache.values().cloned().collect())
     }
 
+    async fn buscar(&self, consulta: &ConsultaReservas) -> Result<Pagina<Reserva>, ReservaError> {
+        // Se filtra sobre la cache sin clonar las reservas que no entran en la página
+        let cache = self.cache.read().await;
+        Ok(consulta.aplicar(cache.values()))
+    }
+
     async fn listar_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
         let cache = self.cache.read().await;
         Ok(cache
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reservas_application::ReservaRepository;
+use reservas_application::{ConsultaReservas, ReservaRepository};
 use reservas_domain::{Reserva, ReservaError};
+use shared_paginacion::Pagina;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -70,6 +71,11 @@ impl ReservaRepository for InMemoryReservaRepository {
         Ok(reservas.values().cloned().collect())
     }
 
+    async fn buscar(&self, consulta: &ConsultaReservas) -> Result<Pagina<Reserva>, ReservaError> {
+        let reservas = self.reservas.read().await;
+        Ok(consulta.aplicar(reservas.values()))
+    }
+
     async fn listar_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
         let reservas = self.reservas.read().await;
         Ok(reservas
@@ -134,6 +140,7 @@ mod tests {
     use super::*;
     use chrono::Duration;
     use reservas_domain::EstadoReserva;
+    use shared_paginacion::{Direccion, Paginacion};
 
     #[tokio::test]
     async fn test_crear_y_obtener_reserva() {
@@ -182,6 +189,43 @@ mod tests {
         assert_eq!(todas.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_buscar_por_usuario_paginado() {
+        let ahora = Utc::now();
+        let reservas: Vec<Reserva> = (1..=3)
+            .map(|h| {
+                Reserva::new(
+                    "sala1".into(),
+                    "usuario1".into(),
+                    ahora + Duration::hours(h),
+                    ahora + Duration::hours(h) + Duration::minutes(30),
+                )
+                .unwrap()
+            })
+            .collect();
+        let repo = InMemoryReservaRepository::with_reservas(reservas.clone());
+
+        let consulta = ConsultaReservas {
+            usuario_id: Some("usuario1".into()),
+            direccion: Direccion::Desc,
+            paginacion: Paginacion::new(0, 2),
+            ..Default::default()
+        };
+        let pagina = repo.buscar(&consulta).await.unwrap();
+
+        assert_eq!(pagina.total, 3);
+        assert_eq!(
+            pagina.elementos,
+            vec![reservas[2].clone(), reservas[1].clone()]
+        );
+
+        let otro = ConsultaReservas {
+            usuario_id: Some("usuario2".into()),
+            ..Default::default()
+        };
+        assert_eq!(repo.buscar(&otro).await.unwrap().total, 0);
+    }
+
     #[tokio::test]
     async fn test_listar_por_sala() {
         let repo = InMemoryReservaRepository::new();
@@ -7,6 +7,68 @@ pub struct CrearSalaRequest {
     pub nombre: String,
     #[schema(example = 50, minimum = 1, maximum = 1000)]
     pub capacidad: u32,
+    /// Zona horaria IANA de la sala (por defecto `Europe/Madrid`)
+    #[serde(default)]
+    #[schema(example = "America/Mexico_City")]
+    pub zona_horaria: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActualizarSalaRequest {
+    #[schema(example = "Sala de Conferencias B")]
+    pub nombre: String,
+    #[schema(example = 30, minimum = 1, maximum = 1000)]
+    pub capacidad: u32,
+    /// Nueva zona horaria IANA; si se omite se mantiene la actual
+    #[serde(default)]
+    #[schema(example = "Europe/Madrid")]
+    pub zona_horaria: Option<String>,
+}
+
+/// Minutos bloqueados antes y después de cada reserva de la sala
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MargenesSalaRequest {
+    /// Preparación (montaje, AV) antes de cada reserva
+    #[serde(default)]
+    #[schema(example = 10, maximum = 240)]
+    pub margen_antes: u32,
+    /// Limpieza después de cada reserva
+    #[serde(default)]
+    #[schema(example = 15, maximum = 240)]
+    pub margen_despues: u32,
+}
+
+/// Query de filtros, orden y paginación del listado de salas
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct ListarSalasQuery {
+    /// Solo salas activas (`true`) o inactivas (`false`)
+    pub activa: Option<bool>,
+    /// Texto que debe contener el nombre
+    pub nombre: Option<String>,
+    /// `nombre` (por defecto) o `capacidad`
+    pub orden: Option<String>,
+    /// `asc` (por defecto) o `desc`
+    pub direccion: Option<String>,
+    /// Salas a saltar desde el principio
+    #[serde(default)]
+    pub offset: u32,
+    /// Salas por página (por defecto 50, máximo 200)
+    #[serde(default)]
+    pub limite: u32,
+}
+
+/// Query para indicar qué hacer con las reservas futuras afectadas
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AccionReservasQuery {
+    /// `rechazar` (por defecto) o `cancelar`
+    pub reservas: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CambioSalaResponse {
+    pub sala: SalaResponse,
+    /// IDs de las reservas canceladas por el cambio
+    pub reservas_canceladas: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -19,15 +81,12 @@ pub struct SalaResponse {
     pub capacidad: u32,
     #[schema(example = true)]
     pub activa: bool,
-}
-
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
-pub struct ErrorResponse {
-    #[schema(example = "El nombre no puede estar vacío")]
-    pub error: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
-pub struct ValidationErrorResponse {
-    pub errors: Vec<String>,
+    #[schema(example = "Europe/Madrid")]
+    pub zona_horaria: String,
+    /// Minutos de preparación bloqueados antes de cada reserva
+    #[schema(example = 10)]
+    pub margen_antes: u32,
+    /// Minutos de limpieza bloqueados después de cada reserva
+    #[schema(example = 15)]
+    pub margen_despues: u32,
 }
@@ -1,10 +1,16 @@
-use crate::dtos::{CrearSalaRequest, ErrorResponse, SalaResponse, ValidationErrorResponse};
-use axum::extract::{Path, State};
+use crate::dtos::{
+    AccionReservasQuery, ActualizarSalaRequest, CambioSalaResponse, CrearSalaRequest,
+    ListarSalasQuery, MargenesSalaRequest, SalaResponse,
+};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
-use salas_application::SalaService;
+use salas_application::{AccionReservasFuturas, ConsultaSalas, SalaService};
 use salas_domain::SalaError;
+use shared_errores::rest::respuesta_problema;
+use shared_errores::{ErrorCampo, Problema};
+use shared_paginacion::{Paginacion, TOTAL_HEADER};
 use std::sync::Arc;
 use tracing::log::{debug, error, info};
 
@@ -17,7 +23,7 @@ pub type SharedSalaService = Arc<dyn SalaService + Send + Sync>;
     request_body = CrearSalaRequest,
     responses(
         (status = 201, description = "Sala creada exitosamente", body = SalaResponse),
-        (status = 400, description = "Errores de validación", body = ValidationErrorResponse)
+        (status = 400, description = "Errores de validación", body = Problema, content_type = "application/problem+json")
     ),
     tag = "salas"
 )]
@@ -31,7 +37,7 @@ pub async fn crear_sala(
     );
 
     let sala = service
-        .crear_sala(request.nombre, request.capacidad)
+        .crear_sala(request.nombre, request.capacidad, request.zona_horaria)
         .await
         .map_err(|e| {
             error!("Error al crear sala: {}", e);
@@ -42,22 +48,39 @@ pub async fn crear_sala(
     Ok((StatusCode::CREATED, Json(sala.into())))
 }
 
-/// Listar todas las salas
+/// Listar salas con filtros, orden y paginación
+///
+/// El cuerpo es la página pedida; el total sin paginar va en `X-Total-Count`.
 #[utoipa::path(
     get,
     path = "/salas",
+    params(ListarSalasQuery),
     responses(
-        (status = 200, description = "Lista de salas", body = Vec<SalaResponse>)
+        (status = 200, description = "Página de salas", body = Vec<SalaResponse>,
+            headers(("X-Total-Count" = u32, description = "Salas que cumplen los filtros"))),
+        (status = 400, description = "Orden o dirección inválidos", body = Problema, content_type = "application/problem+json")
     ),
     tag = "salas"
 )]
 pub async fn listar_salas(
     State(service): State<SharedSalaService>,
-) -> Result<Json<Vec<SalaResponse>>, AppError> {
-    info!("Listamos salas");
-    let salas = service.listar_salas().await?;
-    let response: Vec<SalaResponse> = salas.iter().map(Into::into).collect();
-    Ok(Json(response))
+    Query(query): Query<ListarSalasQuery>,
+) -> Result<Response, AppError> {
+    info!("Listamos salas: {:?}", query);
+    let consulta = ConsultaSalas {
+        activa: query.activa,
+        nombre: query.nombre,
+        paginacion: Paginacion::new(query.offset, query.limite),
+        ..Default::default()
+    }
+    .ordenada_por(
+        query.orden.as_deref().unwrap_or_default(),
+        query.direccion.as_deref().unwrap_or_default(),
+    )?;
+
+    let pagina = service.buscar_salas(consulta).await?;
+    let response: Vec<SalaResponse> = pagina.elementos.iter().map(Into::into).collect();
+    Ok(([(TOTAL_HEADER, pagina.total.to_string())], Json(response)).into_response())
 }
 
 /// Obtener una sala por ID
@@ -69,7 +92,7 @@ pub async fn listar_salas(
     ),
     responses(
         (status = 200, description = "Sala encontrada", body = SalaResponse),
-        (status = 404, description = "Sala no encontrada", body = ErrorResponse)
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json")
     ),
     tag = "salas"
 )]
@@ -94,7 +117,7 @@ pub async fn obtener_sala(
     ),
     responses(
         (status = 200, description = "Sala activada", body = SalaResponse),
-        (status = 404, description = "Sala no encontrada", body = ErrorResponse)
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json")
     ),
     tag = "salas"
 )]
@@ -116,7 +139,7 @@ pub async fn activar_sala(
     ),
     responses(
         (status = 200, description = "Sala desactivada", body = SalaResponse),
-        (status = 404, description = "Sala no encontrada", body = ErrorResponse)
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json")
     ),
     tag = "salas"
 )]
@@ -128,6 +151,134 @@ pub async fn desactivar_sala(
     let response: SalaResponse = sala.into();
     Ok(Json(response))
 }
+
+/// Configurar los márgenes de preparación y limpieza de una sala
+///
+/// Las reservas nuevas o movidas no podrán ocupar esos minutos alrededor de
+/// otra reserva de la sala; las horas de las reservas no cambian.
+#[utoipa::path(
+    put,
+    path = "/salas/{id}/margenes",
+    params(
+        ("id" = String, Path, description = "ID de la sala")
+    ),
+    request_body = MargenesSalaRequest,
+    responses(
+        (status = 200, description = "Márgenes actualizados", body = SalaResponse),
+        (status = 400, description = "Errores de validación", body = Problema, content_type = "application/problem+json"),
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json")
+    ),
+    tag = "salas"
+)]
+pub async fn configurar_margenes(
+    State(service): State<SharedSalaService>,
+    Path(id): Path<String>,
+    Json(request): Json<MargenesSalaRequest>,
+) -> Result<Json<SalaResponse>, AppError> {
+    info!(
+        "Configurando márgenes de sala {}: antes={}, despues={}",
+        id, request.margen_antes, request.margen_despues
+    );
+    let sala = service
+        .configurar_margenes(&id, request.margen_antes, request.margen_despues)
+        .await?;
+    Ok(Json(sala.into()))
+}
+fn accion_desde_query(query: &AccionReservasQuery) -> Result<AccionReservasFuturas, SalaError> {
+    match query.reservas.as_deref() {
+        None => Ok(AccionReservasFuturas::default()),
+        Some(valor) => AccionReservasFuturas::from_str_opt(valor).ok_or_else(|| {
+            SalaError::Validacion(vec![ErrorCampo::con_codigo(
+                "reservas",
+                "SALA_ACCION_RESERVAS_INVALIDA",
+            )])
+        }),
+    }
+}
+
+/// Actualizar nombre y capacidad de una sala
+///
+/// Reducir la capacidad con reservas futuras activas devuelve 409, salvo que
+/// se pida cancelarlas con `?reservas=cancelar`.
+#[utoipa::path(
+    put,
+    path = "/salas/{id}",
+    params(
+        ("id" = String, Path, description = "ID de la sala"),
+        AccionReservasQuery
+    ),
+    request_body = ActualizarSalaRequest,
+    responses(
+        (status = 200, description = "Sala actualizada", body = CambioSalaResponse),
+        (status = 400, description = "Errores de validación", body = Problema, content_type = "application/problem+json"),
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json"),
+        (status = 409, description = "La sala tiene reservas futuras activas", body = Problema, content_type = "application/problem+json")
+    ),
+    tag = "salas"
+)]
+pub async fn actualizar_sala(
+    State(service): State<SharedSalaService>,
+    Path(id): Path<String>,
+    Query(query): Query<AccionReservasQuery>,
+    Json(request): Json<ActualizarSalaRequest>,
+) -> Result<Json<CambioSalaResponse>, AppError> {
+    let accion = accion_desde_query(&query)?;
+    info!(
+        "Actualizando sala {}: nombre={}, capacidad={}",
+        id, request.nombre, request.capacidad
+    );
+
+    let cambio = service
+        .actualizar_sala(
+            &id,
+            request.nombre,
+            request.capacidad,
+            request.zona_horaria,
+            accion,
+        )
+        .await
+        .map_err(|e| {
+            error!("Error al actualizar sala: {}", e);
+            AppError(e)
+        })?;
+
+    Ok(Json(cambio.into()))
+}
+
+/// Eliminar una sala
+///
+/// Con reservas futuras activas devuelve 409, salvo que se pida cancelarlas
+/// con `?reservas=cancelar`.
+#[utoipa::path(
+    delete,
+    path = "/salas/{id}",
+    params(
+        ("id" = String, Path, description = "ID de la sala"),
+        AccionReservasQuery
+    ),
+    responses(
+        (status = 200, description = "Sala eliminada", body = CambioSalaResponse),
+        (status = 404, description = "Sala no encontrada", body = Problema, content_type = "application/problem+json"),
+        (status = 409, description = "La sala tiene reservas futuras activas", body = Problema, content_type = "application/problem+json")
+    ),
+    tag = "salas"
+)]
+pub async fn eliminar_sala(
+    State(service): State<SharedSalaService>,
+    Path(id): Path<String>,
+    Query(query): Query<AccionReservasQuery>,
+) -> Result<Json<CambioSalaResponse>, AppError> {
+    let accion = accion_desde_query(&query)?;
+    info!("Eliminando sala {}", id);
+
+    let cambio = service.eliminar_sala(&id, accion).await.map_err(|e| {
+        error!("Error al eliminar sala: {}", e);
+        AppError(e)
+    })?;
+
+    Ok(Json(cambio.into()))
+}
+
 pub struct AppError(pub SalaError);
 
 impl From<SalaError> for AppError {
@@ -137,26 +288,6 @@ impl From<SalaError> for AppError {
 }
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        match self.0 {
-            SalaError::Validacion(msgs) => (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({ "errors": msgs })),
-            )
-                .into_response(),
-            _ => {
-                let (status, message) = match self.0 {
-                    SalaError::NombreVacio
-                    | SalaError::NombreDemasiadoLargo
-                    | SalaError::CapacidadInvalida => (StatusCode::BAD_REQUEST, self.0.to_string()),
-                    SalaError::NoEncontrada => (StatusCode::NOT_FOUND, self.0.to_string()),
-                    SalaError::ErrorRepositorio(_) => {
-                        (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_string())
-                    }
-                    _ => unreachable!(),
-                };
-
-                (status, Json(serde_json::json!({ "error": message }))).into_response()
-            }
-        }
+        respuesta_problema(self.0)
     }
 }
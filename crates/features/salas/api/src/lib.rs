@@ -3,10 +3,8 @@ pub mod mapper;
 pub use dtos::{CrearSalaRequest, SalaResponse};
 pub use mapper::SalaMapper;
 
-pub mod auth;
 pub mod handlers;
 pub mod openapi;
 pub mod routes;
 
-pub use auth::{AuthUser, RequestExt};
 pub use openapi::ApiDoc;
@@ -1,4 +1,5 @@
-use crate::dtos::SalaResponse;
+use crate::dtos::{CambioSalaResponse, SalaResponse};
+use salas_application::CambioSala;
 use salas_domain::Sala;
 
 pub struct SalaMapper;
@@ -10,6 +11,9 @@ impl From<&Sala> for SalaResponse {
             nombre: sala.nombre.clone(),
             capacidad: sala.capacidad,
             activa: sala.activa,
+            zona_horaria: sala.zona_horaria.name().to_string(),
+            margen_antes: sala.margen_antes,
+            margen_despues: sala.margen_despues,
         }
     }
 }
@@ -21,6 +25,18 @@ impl From<Sala> for SalaResponse {
             nombre: sala.nombre,
             capacidad: sala.capacidad,
             activa: sala.activa,
+            zona_horaria: sala.zona_horaria.name().to_string(),
+            margen_antes: sala.margen_antes,
+            margen_despues: sala.margen_despues,
+        }
+    }
+}
+
+impl From<CambioSala> for CambioSalaResponse {
+    fn from(cambio: CambioSala) -> Self {
+        CambioSalaResponse {
+            sala: cambio.sala.into(),
+            reservas_canceladas: cambio.reservas_canceladas,
         }
     }
 }
@@ -1,5 +1,8 @@
-use crate::dtos::{CrearSalaRequest, ErrorResponse, SalaResponse, ValidationErrorResponse};
+use crate::dtos::{
+    ActualizarSalaRequest, CambioSalaResponse, CrearSalaRequest, MargenesSalaRequest, SalaResponse,
+};
 use crate::handlers;
+use shared_errores::{ErrorCampo, Problema};
 use utoipa::OpenApi;
 
 #[derive(OpenApi)]
@@ -10,9 +13,20 @@ use utoipa::OpenApi;
         handlers::obtener_sala,
         handlers::activar_sala,
         handlers::desactivar_sala,
+        handlers::configurar_margenes,
+        handlers::actualizar_sala,
+        handlers::eliminar_sala,
     ),
     components(
-        schemas(CrearSalaRequest, SalaResponse, ErrorResponse, ValidationErrorResponse)
+        schemas(
+            CrearSalaRequest,
+            ActualizarSalaRequest,
+            MargenesSalaRequest,
+            SalaResponse,
+            CambioSalaResponse,
+            Problema,
+            ErrorCampo
+        )
     ),
     tags(
         (name = "salas", description = "Gestión de salas de reuniones")
@@ -1,11 +1,13 @@
-use crate::auth::{admin_middleware, auth_middleware};
 use crate::handlers::{
-    activar_sala, crear_sala, desactivar_sala, listar_salas, obtener_sala, SharedSalaService,
+    activar_sala, actualizar_sala, configurar_margenes, crear_sala, desactivar_sala, eliminar_sala,
+    listar_salas, obtener_sala, SharedSalaService,
 };
 use crate::openapi::ApiDoc;
 use axum::middleware;
 use axum::routing::{post, put};
 use axum::{routing::get, Router};
+use usuarios_auth::rest::{requiere_autenticacion, requiere_permiso, requiere_permiso_en_sala};
+use usuarios_domain::Permiso;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -21,15 +23,21 @@ pub fn salas_routes(service: SharedSalaService) -> Router {
             get(|| async move { axum::Json(openapi) }),
         )
         .route("/salas", post(crear_sala).get(listar_salas))
-        .route("/salas/{id}", get(obtener_sala))
+        .route(
+            "/salas/{id}",
+            get(obtener_sala).put(actualizar_sala).delete(eliminar_sala),
+        )
         .route("/salas/{id}/activar", put(activar_sala))
         .route("/salas/{id}/desactivar", put(desactivar_sala))
+        .route("/salas/{id}/margenes", put(configurar_margenes))
         .with_state(service)
 }
 
 /// Rutas de salas CON autenticación
 /// - GET: Requiere autenticación
-/// - POST/PUT: Requieren rol de administrador
+/// - POST/PUT/DELETE: Requieren `sala:manage` (los gestores, solo sobre sus salas)
+///
+/// El usuario lo autentica la `AutenticacionLayer` montada sobre la app.
 pub fn salas_routes_with_auth(service: SharedSalaService) -> Router {
     let openapi = ApiDoc::openapi();
 
@@ -43,24 +51,50 @@ pub fn salas_routes_with_auth(service: SharedSalaService) -> Router {
         // Rutas de lectura: requieren autenticación
         .route(
             "/salas",
-            get(listar_salas).layer(middleware::from_fn(auth_middleware)),
+            get(listar_salas).layer(middleware::from_fn(requiere_autenticacion)),
         )
         .route(
             "/salas/{id}",
-            get(obtener_sala).layer(middleware::from_fn(auth_middleware)),
+            get(obtener_sala).layer(middleware::from_fn(requiere_autenticacion)),
         )
-        // Rutas de escritura: requieren rol de admin
+        // Rutas de escritura: requieren sala:manage (en todo el sistema para
+        // crear; sobre la propia sala para el resto)
         .route(
             "/salas",
-            post(crear_sala).layer(middleware::from_fn(admin_middleware)),
+            post(crear_sala).layer(middleware::from_fn_with_state(
+                Permiso::GestionarSalas,
+                requiere_permiso,
+            )),
+        )
+        .route(
+            "/salas/{id}",
+            put(actualizar_sala)
+                .delete(eliminar_sala)
+                .layer(middleware::from_fn_with_state(
+                    Permiso::GestionarSalas,
+                    requiere_permiso_en_sala,
+                )),
         )
         .route(
             "/salas/{id}/activar",
-            put(activar_sala).layer(middleware::from_fn(admin_middleware)),
+            put(activar_sala).layer(middleware::from_fn_with_state(
+                Permiso::GestionarSalas,
+                requiere_permiso_en_sala,
+            )),
         )
         .route(
             "/salas/{id}/desactivar",
-            put(desactivar_sala).layer(middleware::from_fn(admin_middleware)),
+            put(desactivar_sala).layer(middleware::from_fn_with_state(
+                Permiso::GestionarSalas,
+                requiere_permiso_en_sala,
+            )),
+        )
+        .route(
+            "/salas/{id}/margenes",
+            put(configurar_margenes).layer(middleware::from_fn_with_state(
+                Permiso::GestionarSalas,
+                requiere_permiso_en_sala,
+            )),
         )
         .with_state(service)
 }
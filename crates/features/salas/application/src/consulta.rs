@@ -0,0 +1,155 @@
+use salas_domain::{Sala, SalaError};
+use shared_errores::ErrorCampo;
+use shared_paginacion::{Direccion, Pagina, Paginacion};
+use std::cmp::Ordering;
+
+/// Campo por el que se ordena el listado de salas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrdenSalas {
+    #[default]
+    Nombre,
+    Capacidad,
+}
+
+impl OrdenSalas {
+    /// Crea el orden desde un string ("nombre" o "capacidad")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "nombre" => Some(OrdenSalas::Nombre),
+            "capacidad" => Some(OrdenSalas::Capacidad),
+            _ => None,
+        }
+    }
+}
+
+/// Filtros, orden y página para listar salas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsultaSalas {
+    /// Solo salas activas (`true`) o inactivas (`false`)
+    pub activa: Option<bool>,
+    /// Texto que debe contener el nombre (sin distinguir mayúsculas)
+    pub nombre: Option<String>,
+    pub orden: OrdenSalas,
+    pub direccion: Direccion,
+    pub paginacion: Paginacion,
+}
+
+impl ConsultaSalas {
+    /// Fija orden y dirección recibidos como texto; vacío deja el de por defecto
+    pub fn ordenada_por(mut self, orden: &str, direccion: &str) -> Result<Self, SalaError> {
+        let mut errores = Vec::new();
+
+        if !orden.trim().is_empty() {
+            match OrdenSalas::from_str_opt(orden) {
+                Some(orden) => self.orden = orden,
+                None => errores.push(ErrorCampo::con_codigo("orden", "SALA_ORDEN_INVALIDO")),
+            }
+        }
+        if !direccion.trim().is_empty() {
+            match Direccion::from_str_opt(direccion) {
+                Some(direccion) => self.direccion = direccion,
+                None => errores.push(ErrorCampo::con_codigo(
+                    "direccion",
+                    "LISTADO_DIRECCION_INVALIDA",
+                )),
+            }
+        }
+
+        if errores.is_empty() {
+            Ok(self)
+        } else {
+            Err(SalaError::Validacion(errores))
+        }
+    }
+
+    /// Indica si la sala pasa los filtros
+    pub fn cumple(&self, sala: &Sala) -> bool {
+        self.activa.is_none_or(|activa| sala.activa == activa)
+            && self.nombre.as_deref().is_none_or(|texto| {
+                sala.nombre
+                    .to_lowercase()
+                    .contains(&texto.trim().to_lowercase())
+            })
+    }
+
+    /// Compara dos salas según el orden pedido; a igualdad decide el ID
+    /// para que las páginas sean estables
+    pub fn comparar(&self, a: &Sala, b: &Sala) -> Ordering {
+        let orden = match self.orden {
+            OrdenSalas::Nombre => a.nombre.to_lowercase().cmp(&b.nombre.to_lowercase()),
+            OrdenSalas::Capacidad => a.capacidad.cmp(&b.capacidad),
+        };
+        self.direccion.aplicar(orden.then_with(|| a.id.cmp(&b.id)))
+    }
+
+    /// Filtra, ordena y pagina; solo se clonan las salas de la página
+    pub fn aplicar<'a>(&self, salas: impl IntoIterator<Item = &'a Sala>) -> Pagina<Sala> {
+        let mut filtradas: Vec<&Sala> = salas.into_iter().filter(|s| self.cumple(s)).collect();
+        filtradas.sort_by(|a, b| self.comparar(a, b));
+        Pagina::desde_ordenados(filtradas, self.paginacion).map(Clone::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn salas() -> Vec<Sala> {
+        let mut inactiva = Sala::new("c".into(), "Cuarto".into(), 4).unwrap();
+        inactiva.desactivar();
+        vec![
+            Sala::new("a".into(), "Andrómeda".into(), 20).unwrap(),
+            Sala::new("b".into(), "Boreal".into(), 8).unwrap(),
+            inactiva,
+        ]
+    }
+
+    #[test]
+    fn filtra_por_activa_y_nombre() {
+        let salas = salas();
+
+        let consulta = ConsultaSalas {
+            activa: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(consulta.aplicar(&salas).total, 2);
+
+        let consulta = ConsultaSalas {
+            nombre: Some("BOR".into()),
+            ..Default::default()
+        };
+        let pagina = consulta.aplicar(&salas);
+        assert_eq!(pagina.elementos.len(), 1);
+        assert_eq!(pagina.elementos[0].id, "b");
+    }
+
+    #[test]
+    fn orden_desde_texto() {
+        let consulta = ConsultaSalas::default()
+            .ordenada_por("capacidad", "desc")
+            .unwrap();
+        assert_eq!(consulta.orden, OrdenSalas::Capacidad);
+        assert_eq!(consulta.direccion, Direccion::Desc);
+
+        let error = ConsultaSalas::default()
+            .ordenada_por("color", "")
+            .unwrap_err();
+        assert!(matches!(error, SalaError::Validacion(campos) if campos[0].campo == "orden"));
+    }
+
+    #[test]
+    fn ordena_y_pagina() {
+        let salas = salas();
+        let consulta = ConsultaSalas {
+            orden: OrdenSalas::Capacidad,
+            direccion: Direccion::Desc,
+            paginacion: Paginacion::new(1, 1),
+            ..Default::default()
+        };
+
+        let pagina = consulta.aplicar(&salas);
+        assert_eq!(pagina.total, 3);
+        assert_eq!(pagina.elementos[0].id, "b");
+        assert_eq!(pagina.siguiente_offset(), Some(2));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod consulta;
 pub mod ports;
 pub mod service;
 
-pub use ports::{SalaRepository, SalaService};
+pub use consulta::{ConsultaSalas, OrdenSalas};
+pub use ports::{AccionReservasFuturas, CambioSala, ReservasDeSala, SalaRepository, SalaService};
 pub use service::SalaServiceImpl;
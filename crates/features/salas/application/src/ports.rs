@@ -1,13 +1,76 @@
+use crate::consulta::ConsultaSalas;
 use async_trait::async_trait;
 use salas_domain::{Sala, SalaError};
+use shared_paginacion::Pagina;
+
+/// Qué hacer con las reservas futuras activas afectadas por un cambio en la sala
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccionReservasFuturas {
+    /// Rechaza la operación si hay reservas afectadas
+    #[default]
+    Rechazar,
+    /// Cancela las reservas afectadas (y notifica) antes de aplicar el cambio
+    Cancelar,
+}
+
+impl AccionReservasFuturas {
+    /// Crea la acción desde un string ("rechazar" o "cancelar")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "rechazar" => Some(AccionReservasFuturas::Rechazar),
+            "cancelar" => Some(AccionReservasFuturas::Cancelar),
+            _ => None,
+        }
+    }
+}
+
+/// Resultado de actualizar o eliminar una sala
+#[derive(Debug, Clone, PartialEq)]
+pub struct CambioSala {
+    pub sala: Sala,
+    /// IDs de las reservas canceladas por el cambio
+    pub reservas_canceladas: Vec<String>,
+}
 
 #[async_trait]
 pub trait SalaService: Send + Sync {
-    async fn crear_sala(&self, nombre: String, capacidad: u32) -> Result<Sala, SalaError>;
+    /// Crea la sala en la zona horaria IANA indicada (o en la de por defecto)
+    async fn crear_sala(
+        &self,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+    ) -> Result<Sala, SalaError>;
     async fn obtener_sala(&self, id: &str) -> Result<Option<Sala>, SalaError>;
     async fn listar_salas(&self) -> Result<Vec<Sala>, SalaError>;
+    /// Lista una página de salas aplicando filtros y orden
+    async fn buscar_salas(&self, consulta: ConsultaSalas) -> Result<Pagina<Sala>, SalaError>;
     async fn activar_sala(&self, id: &str) -> Result<Sala, SalaError>;
     async fn desactivar_sala(&self, id: &str) -> Result<Sala, SalaError>;
+    /// Cambia nombre, capacidad y, si se indica, la zona horaria.
+    /// Reducir la capacidad afecta a las reservas futuras
+    async fn actualizar_sala(
+        &self,
+        id: &str,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError>;
+    /// Cambia los minutos bloqueados antes y después de cada reserva.
+    /// Solo afecta a la detección de conflictos de reservas nuevas o movidas
+    async fn configurar_margenes(
+        &self,
+        id: &str,
+        margen_antes: u32,
+        margen_despues: u32,
+    ) -> Result<Sala, SalaError>;
+    /// Elimina la sala, comprobando antes sus reservas futuras
+    async fn eliminar_sala(
+        &self,
+        id: &str,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError>;
 }
 
 #[async_trait]
@@ -15,5 +78,25 @@ pub trait SalaRepository: Send + Sync {
     async fn guardar(&self, sala: &Sala) -> Result<(), SalaError>;
     async fn obtener(&self, id: &str) -> Result<Option<Sala>, SalaError>;
     async fn listar(&self) -> Result<Vec<Sala>, SalaError>;
+    /// Página de salas que cumplen la consulta.
+    ///
+    /// Por defecto filtra sobre `listar`; los adaptadores que puedan filtrar
+    /// en origen deberían sobrescribirlo.
+    async fn buscar(&self, consulta: &ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+        Ok(consulta.aplicar(&self.listar().await?))
+    }
     async fn actualizar(&self, sala: &Sala) -> Result<(), SalaError>;
+    async fn eliminar(&self, id: &str) -> Result<(), SalaError>;
+}
+
+/// Port hacia las reservas de una sala
+///
+/// Lo implementa la feature de reservas; así salas no depende de ella.
+#[async_trait]
+pub trait ReservasDeSala: Send + Sync {
+    /// IDs de las reservas activas de la sala que todavía no han terminado
+    async fn reservas_futuras(&self, sala_id: &str) -> Result<Vec<String>, SalaError>;
+
+    /// Cancela las reservas indicadas notificando a sus usuarios
+    async fn cancelar_reservas(&self, ids: &[String]) -> Result<(), SalaError>;
 }
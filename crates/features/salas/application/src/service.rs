@@ -1,24 +1,80 @@
 use async_trait::async_trait;
-use salas_domain::{Sala, SalaError};
+use salas_domain::{parsear_zona_horaria, Sala, SalaError};
+use shared_paginacion::Pagina;
+use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::ports::{SalaRepository, SalaService};
+use crate::consulta::ConsultaSalas;
+use crate::ports::{
+    AccionReservasFuturas, CambioSala, ReservasDeSala, SalaRepository, SalaService,
+};
 
 pub struct SalaServiceImpl<R: SalaRepository> {
     repository: R,
+    reservas: Option<Arc<dyn ReservasDeSala>>,
 }
 
 impl<R: SalaRepository> SalaServiceImpl<R> {
     pub fn new(repository: R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            reservas: None,
+        }
+    }
+
+    /// Conecta el servicio con las reservas para proteger editar/eliminar salas
+    pub fn with_reservas(mut self, reservas: Arc<dyn ReservasDeSala>) -> Self {
+        self.reservas = Some(reservas);
+        self
+    }
+
+    async fn obtener_existente(&self, id: &str) -> Result<Sala, SalaError> {
+        self.repository
+            .obtener(id)
+            .await?
+            .ok_or(SalaError::NoEncontrada)
+    }
+
+    /// Aplica la acción elegida sobre las reservas futuras de la sala
+    ///
+    /// Devuelve los IDs de las reservas canceladas.
+    async fn resolver_reservas_futuras(
+        &self,
+        sala_id: &str,
+        accion: AccionReservasFuturas,
+    ) -> Result<Vec<String>, SalaError> {
+        let Some(reservas) = &self.reservas else {
+            return Ok(vec![]);
+        };
+
+        let futuras = reservas.reservas_futuras(sala_id).await?;
+        if futuras.is_empty() {
+            return Ok(futuras);
+        }
+
+        match accion {
+            AccionReservasFuturas::Rechazar => Err(SalaError::ConReservasFuturas(futuras.len())),
+            AccionReservasFuturas::Cancelar => {
+                reservas.cancelar_reservas(&futuras).await?;
+                Ok(futuras)
+            }
+        }
     }
 }
 
 #[async_trait]
 impl<R: SalaRepository> SalaService for SalaServiceImpl<R> {
-    async fn crear_sala(&self, nombre: String, capacidad: u32) -> Result<Sala, SalaError> {
+    async fn crear_sala(
+        &self,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+    ) -> Result<Sala, SalaError> {
         let id = Uuid::new_v4().to_string();
-        let sala = Sala::new(id, nombre, capacidad)?;
+        let mut sala = Sala::new(id, nombre, capacidad)?;
+        if let Some(zona) = zona_horaria {
+            sala = sala.with_zona_horaria(parsear_zona_horaria(&zona)?);
+        }
         self.repository.guardar(&sala).await?;
         Ok(sala)
     }
@@ -31,6 +87,10 @@ impl<R: SalaRepository> SalaService for SalaServiceImpl<R> {
         self.repository.listar().await
     }
 
+    async fn buscar_salas(&self, consulta: ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+        self.repository.buscar(&consulta).await
+    }
+
     async fn activar_sala(&self, id: &str) -> Result<Sala, SalaError> {
         let mut sala = self
             .repository
@@ -52,4 +112,268 @@ impl<R: SalaRepository> SalaService for SalaServiceImpl<R> {
         self.repository.actualizar(&sala).await?;
         Ok(sala)
     }
+
+    async fn actualizar_sala(
+        &self,
+        id: &str,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError> {
+        let mut sala = self.obtener_existente(id).await?;
+        let capacidad_anterior = sala.capacidad();
+
+        // Validar antes de tocar ninguna reserva
+        let zona = zona_horaria
+            .as_deref()
+            .map(parsear_zona_horaria)
+            .transpose()?;
+        sala.actualizar(nombre, capacidad)?;
+        // Las reservas se guardan en UTC: cambiar la zona no las mueve
+        if let Some(zona) = zona {
+            sala.cambiar_zona_horaria(zona);
+        }
+
+        // Reducir la capacidad puede dejar reservas existentes sin sitio
+        let reservas_canceladas = if capacidad < capacidad_anterior {
+            self.resolver_reservas_futuras(id, accion).await?
+        } else {
+            vec![]
+        };
+
+        self.repository.actualizar(&sala).await?;
+
+        Ok(CambioSala {
+            sala,
+            reservas_canceladas,
+        })
+    }
+
+    async fn configurar_margenes(
+        &self,
+        id: &str,
+        margen_antes: u32,
+        margen_despues: u32,
+    ) -> Result<Sala, SalaError> {
+        let mut sala = self.obtener_existente(id).await?;
+        sala.cambiar_margenes(margen_antes, margen_despues)?;
+        self.repository.actualizar(&sala).await?;
+        Ok(sala)
+    }
+
+    async fn eliminar_sala(
+        &self,
+        id: &str,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError> {
+        let sala = self.obtener_existente(id).await?;
+        let reservas_canceladas = self.resolver_reservas_futuras(id, accion).await?;
+
+        self.repository.eliminar(id).await?;
+
+        Ok(CambioSala {
+            sala,
+            reservas_canceladas,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockSalaRepository {
+        salas: Mutex<HashMap<String, Sala>>,
+    }
+
+    #[async_trait]
+    impl SalaRepository for MockSalaRepository {
+        async fn guardar(&self, sala: &Sala) -> Result<(), SalaError> {
+            self.salas
+                .lock()
+                .unwrap()
+                .insert(sala.id.clone(), sala.clone());
+            Ok(())
+        }
+
+        async fn obtener(&self, id: &str) -> Result<Option<Sala>, SalaError> {
+            Ok(self.salas.lock().unwrap().get(id).cloned())
+        }
+
+        async fn listar(&self) -> Result<Vec<Sala>, SalaError> {
+            Ok(self.salas.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn actualizar(&self, sala: &Sala) -> Result<(), SalaError> {
+            self.guardar(sala).await
+        }
+
+        async fn eliminar(&self, id: &str) -> Result<(), SalaError> {
+            self.salas
+                .lock()
+                .unwrap()
+                .remove(id)
+                .map(|_| ())
+                .ok_or(SalaError::NoEncontrada)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockReservasDeSala {
+        futuras: Vec<String>,
+        canceladas: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ReservasDeSala for MockReservasDeSala {
+        async fn reservas_futuras(&self, _sala_id: &str) -> Result<Vec<String>, SalaError> {
+            Ok(self.futuras.clone())
+        }
+
+        async fn cancelar_reservas(&self, ids: &[String]) -> Result<(), SalaError> {
+            self.canceladas.lock().unwrap().extend_from_slice(ids);
+            Ok(())
+        }
+    }
+
+    async fn servicio_con_sala(
+        futuras: Vec<String>,
+    ) -> (
+        SalaServiceImpl<MockSalaRepository>,
+        Arc<MockReservasDeSala>,
+        String,
+    ) {
+        let reservas = Arc::new(MockReservasDeSala {
+            futuras,
+            ..Default::default()
+        });
+        let service =
+            SalaServiceImpl::new(MockSalaRepository::default()).with_reservas(reservas.clone());
+        let sala = service.crear_sala("Sala A".into(), 10, None).await.unwrap();
+        (service, reservas, sala.id)
+    }
+
+    #[tokio::test]
+    async fn actualizar_sala_sin_reducir_capacidad_no_mira_reservas() {
+        let (service, reservas, id) = servicio_con_sala(vec!["r1".into()]).await;
+
+        let cambio = service
+            .actualizar_sala(
+                &id,
+                "Sala B".into(),
+                20,
+                None,
+                AccionReservasFuturas::Rechazar,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(cambio.sala.nombre(), "Sala B");
+        assert_eq!(cambio.sala.capacidad(), 20);
+        assert!(cambio.reservas_canceladas.is_empty());
+        assert!(reservas.canceladas.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reducir_capacidad_con_reservas_futuras_se_rechaza() {
+        let (service, _, id) = servicio_con_sala(vec!["r1".into(), "r2".into()]).await;
+
+        let resultado = service
+            .actualizar_sala(
+                &id,
+                "Sala A".into(),
+                5,
+                None,
+                AccionReservasFuturas::Rechazar,
+            )
+            .await;
+
+        assert_eq!(resultado, Err(SalaError::ConReservasFuturas(2)));
+        let sala = service.obtener_sala(&id).await.unwrap().unwrap();
+        assert_eq!(sala.capacidad(), 10);
+    }
+
+    #[tokio::test]
+    async fn reducir_capacidad_cancelando_reservas() {
+        let (service, reservas, id) = servicio_con_sala(vec!["r1".into()]).await;
+
+        let cambio = service
+            .actualizar_sala(
+                &id,
+                "Sala A".into(),
+                5,
+                None,
+                AccionReservasFuturas::Cancelar,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(cambio.reservas_canceladas, vec!["r1".to_string()]);
+        assert_eq!(*reservas.canceladas.lock().unwrap(), vec!["r1".to_string()]);
+        assert_eq!(cambio.sala.capacidad(), 5);
+    }
+
+    #[tokio::test]
+    async fn eliminar_sala_con_reservas_futuras() {
+        let (service, reservas, id) = servicio_con_sala(vec!["r1".into()]).await;
+
+        let resultado = service
+            .eliminar_sala(&id, AccionReservasFuturas::Rechazar)
+            .await;
+        assert_eq!(resultado, Err(SalaError::ConReservasFuturas(1)));
+        assert!(service.obtener_sala(&id).await.unwrap().is_some());
+
+        service
+            .eliminar_sala(&id, AccionReservasFuturas::Cancelar)
+            .await
+            .unwrap();
+        assert!(service.obtener_sala(&id).await.unwrap().is_none());
+        assert_eq!(reservas.canceladas.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn crear_y_cambiar_zona_horaria() {
+        let service = SalaServiceImpl::new(MockSalaRepository::default());
+
+        let sala = service
+            .crear_sala("Sala CDMX".into(), 8, Some("America/Mexico_City".into()))
+            .await
+            .unwrap();
+        assert_eq!(sala.zona_horaria().name(), "America/Mexico_City");
+
+        let cambio = service
+            .actualizar_sala(
+                &sala.id,
+                "Sala CDMX".into(),
+                8,
+                Some("Europe/Madrid".into()),
+                AccionReservasFuturas::Rechazar,
+            )
+            .await
+            .unwrap();
+        assert_eq!(cambio.sala.zona_horaria().name(), "Europe/Madrid");
+
+        let resultado = service
+            .crear_sala("Sala X".into(), 8, Some("Marte/Olympus".into()))
+            .await;
+        assert_eq!(
+            resultado,
+            Err(SalaError::ZonaHorariaInvalida("Marte/Olympus".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn eliminar_sala_inexistente() {
+        let service = SalaServiceImpl::new(MockSalaRepository::default());
+
+        let resultado = service
+            .eliminar_sala("no-existe", AccionReservasFuturas::Cancelar)
+            .await;
+
+        assert_eq!(resultado, Err(SalaError::NoEncontrada));
+    }
 }
@@ -1,3 +1,4 @@
+use shared_errores::{i18n, Categoria, ErrorCampo, ErrorDominio, Idioma};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -6,40 +7,114 @@ pub enum SalaError {
     NombreDemasiadoLargo,
     CapacidadInvalida,
     NoEncontrada,
+    /// La operación afecta a reservas futuras activas (número de reservas)
+    ConReservasFuturas(usize),
+    /// El nombre no corresponde a ninguna zona horaria IANA
+    ZonaHorariaInvalida(String),
     ErrorRepositorio(String),
-    Validacion(Vec<String>),
+    Validacion(Vec<ErrorCampo>),
 }
 
+/// El texto sale del catálogo de mensajes en español
 impl fmt::Display for SalaError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje_en(Idioma::Es))
+    }
+}
+
+impl std::error::Error for SalaError {}
+
+impl ErrorDominio for SalaError {
+    fn codigo(&self) -> &'static str {
+        match self {
+            SalaError::NombreVacio => "SALA_NOMBRE_VACIO",
+            SalaError::NombreDemasiadoLargo => "SALA_NOMBRE_DEMASIADO_LARGO",
+            SalaError::CapacidadInvalida => "SALA_CAPACIDAD_INVALIDA",
+            SalaError::NoEncontrada => "SALA_NO_ENCONTRADA",
+            SalaError::ConReservasFuturas(_) => "SALA_CON_RESERVAS_FUTURAS",
+            SalaError::ZonaHorariaInvalida(_) => "SALA_ZONA_HORARIA_INVALIDA",
+            SalaError::ErrorRepositorio(_) => "SALA_ERROR_REPOSITORIO",
+            SalaError::Validacion(_) => "SALA_VALIDACION",
+        }
+    }
+
+    fn categoria(&self) -> Categoria {
+        match self {
+            SalaError::NombreVacio
+            | SalaError::NombreDemasiadoLargo
+            | SalaError::CapacidadInvalida
+            | SalaError::ZonaHorariaInvalida(_)
+            | SalaError::Validacion(_) => Categoria::Validacion,
+            SalaError::NoEncontrada => Categoria::NoEncontrado,
+            SalaError::ConReservasFuturas(_) => Categoria::Conflicto,
+            SalaError::ErrorRepositorio(_) => Categoria::Interno,
+        }
+    }
+
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        match self {
+            SalaError::ConReservasFuturas(n) => vec![("n", n.to_string())],
+            SalaError::ZonaHorariaInvalida(zona) => vec![("zona", zona.clone())],
+            SalaError::ErrorRepositorio(detalle) => vec![("detalle", detalle.clone())],
+            _ => Vec::new(),
+        }
+    }
+
+    fn campos(&self) -> Vec<ErrorCampo> {
         match self {
-            SalaError::NombreVacio => write!(f, "El nombre no puede estar vacío"),
-            SalaError::NombreDemasiadoLargo => {
-                write!(f, "El nombre no puede exceder 100 caracteres")
+            SalaError::NombreVacio | SalaError::NombreDemasiadoLargo => {
+                vec![ErrorCampo::new("nombre", self.codigo(), self.to_string())]
             }
-            SalaError::CapacidadInvalida => write!(f, "La capacidad debe ser entre 1 y 1000"),
-            SalaError::NoEncontrada => write!(f, "Sala no encontrada"),
-            SalaError::ErrorRepositorio(msg) => write!(f, "Error en repositorio: {}", msg),
-            SalaError::Validacion(msgs) => write!(f, "Errores de validación: {}", msgs.join("; ")),
+            SalaError::CapacidadInvalida => {
+                vec![ErrorCampo::new(
+                    "capacidad",
+                    self.codigo(),
+                    self.to_string(),
+                )]
+            }
+            SalaError::ZonaHorariaInvalida(_) => {
+                vec![ErrorCampo::new(
+                    "zona_horaria",
+                    self.codigo(),
+                    self.to_string(),
+                )]
+            }
+            SalaError::Validacion(errores) => errores.clone(),
+            _ => Vec::new(),
         }
     }
 }
 
-impl std::error::Error for SalaError {}
-
-pub fn convertir_errores_validacion(e: validator::ValidationErrors) -> Vec<String> {
+/// Convierte los errores de `validator` en errores por campo
+///
+/// El código se forma con el campo y la regla incumplida, p. ej.
+/// `SALA_NOMBRE_LONGITUD` o `SALA_CAPACIDAD_RANGO`.
+pub fn convertir_errores_validacion(e: validator::ValidationErrors) -> Vec<ErrorCampo> {
     use std::borrow::Cow;
     let mut errores = Vec::new();
 
     for (campo, errs) in e.field_errors() {
         for err in errs {
-            // Si en el atributo del struct pusiste `message = "..."`, la tienes aquí
-            let msg: Cow<'static, str> = err
-                .message
-                .clone()
-                .unwrap_or_else(|| Cow::from(format!("Error en {} ({})", campo, err.code)));
+            let regla = match err.code.as_ref() {
+                "length" => "LONGITUD".to_string(),
+                "range" => "RANGO".to_string(),
+                otra => otra.to_uppercase(),
+            };
+            let codigo = format!("SALA_{}_{}", campo.to_uppercase(), regla);
+
+            // El texto sale del catálogo; si la regla no está catalogada se usa
+            // el mensaje del atributo (`message = "..."`) o uno genérico
+            let error = if i18n::existe(&codigo) {
+                ErrorCampo::con_codigo(campo.to_string(), &codigo)
+            } else {
+                let msg: Cow<'static, str> = err
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| Cow::from(format!("Error en {} ({})", campo, err.code)));
+                ErrorCampo::new(campo.to_string(), codigo, msg)
+            };
 
-            errores.push(msg.to_string());
+            errores.push(error);
         }
     }
 
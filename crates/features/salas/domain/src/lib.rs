@@ -2,4 +2,4 @@ pub mod error;
 pub mod sala;
 
 pub use error::SalaError;
-pub use sala::Sala;
+pub use sala::{parsear_zona_horaria, Sala, MAX_MARGEN_MINUTOS, ZONA_HORARIA_POR_DEFECTO};
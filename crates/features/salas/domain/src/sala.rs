@@ -1,8 +1,29 @@
 use crate::error::{convertir_errores_validacion, SalaError};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use shared_errores::ErrorCampo;
 use utoipa::ToSchema;
 use validator::Validate;
 
+/// Zona horaria de las salas que no indican otra (y de las guardadas antes
+/// de que existiera el campo)
+pub const ZONA_HORARIA_POR_DEFECTO: Tz = chrono_tz::Europe::Madrid;
+
+/// Máximo de minutos de preparación o limpieza alrededor de cada reserva
+pub const MAX_MARGEN_MINUTOS: u32 = 240;
+
+fn zona_horaria_por_defecto() -> Tz {
+    ZONA_HORARIA_POR_DEFECTO
+}
+
+/// Interpreta un nombre IANA (`Europe/Madrid`, `America/Mexico_City`...)
+pub fn parsear_zona_horaria(nombre: &str) -> Result<Tz, SalaError> {
+    nombre
+        .trim()
+        .parse::<Tz>()
+        .map_err(|_| SalaError::ZonaHorariaInvalida(nombre.trim().to_string()))
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
 pub struct Sala {
     #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
@@ -10,16 +31,30 @@ pub struct Sala {
     #[validate(length(min = 1, max = 100))]
     #[schema(example = "Sala de Conferencias", min_length = 1, max_length = 100)]
     pub nombre: String,
-    #[validate(range(min = 1, max = 1000, message = "Sobrepasa la capacidad"))]
+    #[validate(range(min = 1, max = 1000))]
     #[schema(example = 50, minimum = 1, maximum = 1000)]
     pub capacidad: u32,
     #[schema(example = true)]
     pub activa: bool,
+    /// Zona horaria IANA en la que se interpretan horarios y recurrencias
+    #[serde(default = "zona_horaria_por_defecto")]
+    #[schema(value_type = String, example = "Europe/Madrid")]
+    pub zona_horaria: Tz,
+    /// Minutos que se bloquean antes de cada reserva (preparación, montaje AV)
+    #[serde(default)]
+    #[validate(range(max = 240))]
+    #[schema(example = 10, maximum = 240)]
+    pub margen_antes: u32,
+    /// Minutos que se bloquean después de cada reserva (limpieza)
+    #[serde(default)]
+    #[validate(range(max = 240))]
+    #[schema(example = 15, maximum = 240)]
+    pub margen_despues: u32,
 }
 
 impl Sala {
     pub fn new(id: String, nombre: String, capacidad: u32) -> Result<Self, SalaError> {
-        let mut errores: Vec<String> = Vec::new();
+        let mut errores: Vec<ErrorCampo> = Vec::new();
 
         let nombre_trim = nombre.trim().to_string();
 
@@ -29,6 +64,9 @@ impl Sala {
             nombre: nombre_trim,
             capacidad,
             activa: true,
+            zona_horaria: ZONA_HORARIA_POR_DEFECTO,
+            margen_antes: 0,
+            margen_despues: 0,
         };
 
         // Validaciones de `validator`
@@ -44,6 +82,11 @@ impl Sala {
         Ok(sala)
     }
 
+    pub fn with_zona_horaria(mut self, zona_horaria: Tz) -> Self {
+        self.zona_horaria = zona_horaria;
+        self
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -60,6 +103,36 @@ impl Sala {
         self.activa
     }
 
+    pub fn zona_horaria(&self) -> Tz {
+        self.zona_horaria
+    }
+
+    pub fn margen_antes(&self) -> u32 {
+        self.margen_antes
+    }
+
+    pub fn margen_despues(&self) -> u32 {
+        self.margen_despues
+    }
+
+    /// Cambia los minutos de preparación y limpieza; como mucho
+    /// `MAX_MARGEN_MINUTOS` cada uno
+    pub fn cambiar_margenes(&mut self, antes: u32, despues: u32) -> Result<(), SalaError> {
+        let mut cambiada = self.clone();
+        cambiada.margen_antes = antes;
+        cambiada.margen_despues = despues;
+        cambiada
+            .validate()
+            .map_err(|e| SalaError::Validacion(convertir_errores_validacion(e)))?;
+
+        *self = cambiada;
+        Ok(())
+    }
+
+    pub fn cambiar_zona_horaria(&mut self, zona_horaria: Tz) {
+        self.zona_horaria = zona_horaria;
+    }
+
     pub fn activar(&mut self) {
         self.activa = true;
     }
@@ -67,6 +140,14 @@ impl Sala {
     pub fn desactivar(&mut self) {
         self.activa = false;
     }
+
+    /// Cambia nombre y capacidad aplicando las mismas validaciones que `new`
+    pub fn actualizar(&mut self, nombre: String, capacidad: u32) -> Result<(), SalaError> {
+        let actualizada = Sala::new(self.id.clone(), nombre, capacidad)?;
+        self.nombre = actualizada.nombre;
+        self.capacidad = actualizada.capacidad;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -76,7 +157,7 @@ mod tests {
 
     fn extraer_errores(result: Result<Sala, SalaError>) -> Result<Vec<String>, String> {
         match result {
-            Err(Validacion(errores)) => Ok(errores),
+            Err(Validacion(errores)) => Ok(errores.into_iter().map(|e| e.mensaje).collect()),
             Err(e) => Err(format!("Se esperaba Validacion, pero se obtuvo: {:?}", e)),
             Ok(s) => Err(format!("Se esperaba error, pero se obtuvo Ok({:?})", s)),
         }
@@ -141,6 +222,38 @@ mod tests {
         assert_contiene_error(&errores, &["capacidad", "range", "Sobrepasa"])
     }
 
+    #[test]
+    fn errores_de_validacion_llevan_campo_y_codigo() {
+        use shared_errores::ErrorDominio;
+
+        let error = Sala::new("123".into(), "".into(), 0).unwrap_err();
+        let campos = error.campos();
+
+        assert_eq!(error.codigo(), "SALA_VALIDACION");
+        assert!(campos
+            .iter()
+            .any(|c| c.campo == "nombre" && c.codigo == "SALA_NOMBRE_LONGITUD"));
+        assert!(campos
+            .iter()
+            .any(|c| c.campo == "capacidad" && c.codigo == "SALA_CAPACIDAD_RANGO"));
+    }
+
+    #[test]
+    fn errores_de_validacion_traducidos_al_ingles() {
+        use shared_errores::{ErrorDominio, Idioma};
+
+        let error = Sala::new("123".into(), "Sala".into(), 0).unwrap_err();
+
+        assert_eq!(
+            error.mensaje_en(Idioma::En),
+            "Validation errors: Capacity must be between 1 and 1000"
+        );
+        assert_eq!(
+            error.to_string(),
+            "Errores de validación: La capacidad debe ser entre 1 y 1000"
+        );
+    }
+
     #[test]
     fn activar_sala() -> Result<(), String> {
         let mut sala = Sala::new("123".into(), "Sala 1".into(), 10)
@@ -168,6 +281,93 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn actualizar_sala_mantiene_id_y_estado() -> Result<(), String> {
+        let mut sala = Sala::new("123".into(), "Sala 1".into(), 10)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        sala.desactivar();
+
+        sala.actualizar("  Sala renombrada ".into(), 20)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+
+        assert_eq!(sala.id(), "123");
+        assert_eq!(sala.nombre(), "Sala renombrada");
+        assert_eq!(sala.capacidad(), 20);
+        assert!(!sala.esta_activa());
+        Ok(())
+    }
+
+    #[test]
+    fn actualizar_sala_invalida_no_modifica() -> Result<(), String> {
+        let mut sala = Sala::new("123".into(), "Sala 1".into(), 10)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+
+        assert!(sala.actualizar("".into(), 0).is_err());
+        assert_eq!(sala.nombre(), "Sala 1");
+        assert_eq!(sala.capacidad(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn zona_horaria_por_defecto_y_personalizada() -> Result<(), String> {
+        let sala = Sala::new("123".into(), "Sala 1".into(), 10)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert_eq!(sala.zona_horaria(), ZONA_HORARIA_POR_DEFECTO);
+
+        let zona = parsear_zona_horaria(" America/Mexico_City ")
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        let sala = sala.with_zona_horaria(zona);
+        assert_eq!(sala.zona_horaria().name(), "America/Mexico_City");
+        Ok(())
+    }
+
+    #[test]
+    fn zona_horaria_desconocida_es_error_de_campo() {
+        use shared_errores::{ErrorDominio, Idioma};
+
+        let error = parsear_zona_horaria("Madrid").unwrap_err();
+
+        assert_eq!(error, SalaError::ZonaHorariaInvalida("Madrid".into()));
+        assert_eq!(error.campos()[0].campo, "zona_horaria");
+        assert_eq!(
+            error.mensaje_en(Idioma::En),
+            "The time zone 'Madrid' is not valid. Use an IANA name, e.g. Europe/Madrid"
+        );
+    }
+
+    #[test]
+    fn sala_guardada_sin_zona_usa_la_de_por_defecto() {
+        let json = r#"{"id":"1","nombre":"Sala","capacidad":5,"activa":true}"#;
+        let sala: Sala = serde_json::from_str(json).unwrap();
+        assert_eq!(sala.zona_horaria(), ZONA_HORARIA_POR_DEFECTO);
+
+        let ida_y_vuelta = serde_json::to_string(&sala.with_zona_horaria(chrono_tz::UTC)).unwrap();
+        assert!(ida_y_vuelta.contains(r#""zona_horaria":"UTC""#));
+    }
+
+    #[test]
+    fn margenes_con_maximo() -> Result<(), String> {
+        use shared_errores::ErrorDominio;
+
+        let mut sala = Sala::new("123".into(), "Sala 1".into(), 10)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert_eq!((sala.margen_antes(), sala.margen_despues()), (0, 0));
+
+        sala.cambiar_margenes(10, 15)
+            .map_err(|e| format!("No debería fallar: {:?}", e))?;
+        assert_eq!((sala.margen_antes(), sala.margen_despues()), (10, 15));
+
+        let error = sala
+            .cambiar_margenes(MAX_MARGEN_MINUTOS + 1, 0)
+            .unwrap_err();
+        assert!(error
+            .campos()
+            .iter()
+            .any(|c| c.campo == "margen_antes" && c.codigo == "SALA_MARGEN_ANTES_RANGO"));
+        assert_eq!(sala.margen_antes(), 10);
+        Ok(())
+    }
+
     #[test]
     fn nombre_trimea_espacios() -> Result<(), String> {
         let sala = Sala::new("123".into(), "  Sala con espacios  ".into(), 10)
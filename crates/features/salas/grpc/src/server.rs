@@ -1,15 +1,19 @@
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
-use salas_application::SalaService;
-use salas_domain::SalaError;
+use salas_application::{AccionReservasFuturas, CambioSala, ConsultaSalas, SalaService};
+use salas_domain::{Sala, SalaError};
+use shared_errores::Idioma;
+use shared_paginacion::Paginacion;
 
-use crate::auth::extract_auth_user;
+use usuarios_auth::grpc::RequestPrincipalExt;
+use usuarios_domain::Permiso;
 
 use crate::proto::{
     sala_service_server::{SalaService as SalaServiceTrait, SalaServiceServer},
-    ActivarSalaRequest, CrearSalaRequest, DesactivarSalaRequest, ListarSalasRequest,
-    ListarSalasResponse, ObtenerSalaRequest, SalaResponse,
+    AccionReservasFuturas as ProtoAccion, ActivarSalaRequest, ActualizarSalaRequest,
+    CambioSalaResponse, ConfigurarMargenesSalaRequest, CrearSalaRequest, DesactivarSalaRequest,
+    EliminarSalaRequest, ListarSalasRequest, ListarSalasResponse, ObtenerSalaRequest, SalaResponse,
 };
 
 pub struct SalaGrpcServer {
@@ -32,23 +36,19 @@ impl SalaServiceTrait for SalaGrpcServer {
         &self,
         request: Request<CrearSalaRequest>,
     ) -> Result<Response<SalaResponse>, Status> {
-        // Requiere autenticación
-        extract_auth_user(&request)?;
+        // Requiere sala:manage en todo el sistema
+        request.principal_con_permiso(Permiso::GestionarSalas)?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
         let sala = self
             .service
-            .crear_sala(req.nombre, req.capacidad)
+            .crear_sala(req.nombre, req.capacidad, req.zona_horaria)
             .await
-            .map_err(sala_error_to_status)?;
+            .map_err(|e| sala_error_to_status(e, idioma))?;
 
-        Ok(Response::new(SalaResponse {
-            id: sala.id().to_string(),
-            nombre: sala.nombre().to_string(),
-            capacidad: sala.capacidad(),
-            activa: sala.activa,
-        }))
+        Ok(Response::new(sala_to_proto(&sala)))
     }
 
     async fn obtener_sala(
@@ -56,7 +56,8 @@ impl SalaServiceTrait for SalaGrpcServer {
         request: Request<ObtenerSalaRequest>,
     ) -> Result<Response<SalaResponse>, Status> {
         // Requiere autenticación
-        extract_auth_user(&request)?;
+        request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -64,15 +65,10 @@ impl SalaServiceTrait for SalaGrpcServer {
             .service
             .obtener_sala(&req.id)
             .await
-            .map_err(sala_error_to_status)?
-            .ok_or_else(|| Status::not_found("Sala no encontrada"))?;
-
-        Ok(Response::new(SalaResponse {
-            id: sala.id().to_string(),
-            nombre: sala.nombre().to_string(),
-            capacidad: sala.capacidad(),
-            activa: sala.activa,
-        }))
+            .map_err(|e| sala_error_to_status(e, idioma))?
+            .ok_or_else(|| sala_error_to_status(SalaError::NoEncontrada, idioma))?;
+
+        Ok(Response::new(sala_to_proto(&sala)))
     }
 
     async fn listar_salas(
@@ -80,26 +76,29 @@ impl SalaServiceTrait for SalaGrpcServer {
         request: Request<ListarSalasRequest>,
     ) -> Result<Response<ListarSalasResponse>, Status> {
         // Requiere autenticación
-        extract_auth_user(&request)?;
+        request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let consulta = ConsultaSalas {
+            activa: req.activa,
+            nombre: req.nombre,
+            paginacion: Paginacion::new(req.offset, req.limite),
+            ..Default::default()
+        }
+        .ordenada_por(&req.orden, &req.direccion)
+        .map_err(|e| sala_error_to_status(e, idioma))?;
 
-        let salas = self
+        let pagina = self
             .service
-            .listar_salas()
+            .buscar_salas(consulta)
             .await
-            .map_err(sala_error_to_status)?;
-
-        let salas_response: Vec<SalaResponse> = salas
-            .into_iter()
-            .map(|s| SalaResponse {
-                id: s.id().to_string(),
-                nombre: s.nombre().to_string(),
-                capacidad: s.capacidad(),
-                activa: s.activa,
-            })
-            .collect();
+            .map_err(|e| sala_error_to_status(e, idioma))?;
 
         Ok(Response::new(ListarSalasResponse {
-            salas: salas_response,
+            salas: pagina.elementos.iter().map(sala_to_proto).collect(),
+            total: pagina.total as u32,
+            siguiente_offset: pagina.siguiente_offset(),
         }))
     }
 
@@ -107,8 +106,9 @@ impl SalaServiceTrait for SalaGrpcServer {
         &self,
         request: Request<ActivarSalaRequest>,
     ) -> Result<Response<SalaResponse>, Status> {
-        // Requiere autenticación
-        extract_auth_user(&request)?;
+        // Requiere sala:manage sobre la sala
+        request.principal_con_permiso_en_sala(Permiso::GestionarSalas, &request.get_ref().id)?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -116,22 +116,18 @@ impl SalaServiceTrait for SalaGrpcServer {
             .service
             .activar_sala(&req.id)
             .await
-            .map_err(sala_error_to_status)?;
+            .map_err(|e| sala_error_to_status(e, idioma))?;
 
-        Ok(Response::new(SalaResponse {
-            id: sala.id().to_string(),
-            nombre: sala.nombre().to_string(),
-            capacidad: sala.capacidad(),
-            activa: sala.activa,
-        }))
+        Ok(Response::new(sala_to_proto(&sala)))
     }
 
     async fn desactivar_sala(
         &self,
         request: Request<DesactivarSalaRequest>,
     ) -> Result<Response<SalaResponse>, Status> {
-        // Requiere autenticación
-        extract_auth_user(&request)?;
+        // Requiere sala:manage sobre la sala
+        request.principal_con_permiso_en_sala(Permiso::GestionarSalas, &request.get_ref().id)?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -139,25 +135,102 @@ impl SalaServiceTrait for SalaGrpcServer {
             .service
             .desactivar_sala(&req.id)
             .await
-            .map_err(sala_error_to_status)?;
+            .map_err(|e| sala_error_to_status(e, idioma))?;
 
-        Ok(Response::new(SalaResponse {
-            id: sala.id().to_string(),
-            nombre: sala.nombre().to_string(),
-            capacidad: sala.capacidad(),
-            activa: sala.activa,
-        }))
+        Ok(Response::new(sala_to_proto(&sala)))
+    }
+
+    async fn actualizar_sala(
+        &self,
+        request: Request<ActualizarSalaRequest>,
+    ) -> Result<Response<CambioSalaResponse>, Status> {
+        // Requiere sala:manage sobre la sala
+        request.principal_con_permiso_en_sala(Permiso::GestionarSalas, &request.get_ref().id)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let cambio = self
+            .service
+            .actualizar_sala(
+                &req.id,
+                req.nombre,
+                req.capacidad,
+                req.zona_horaria,
+                accion_from_proto(req.accion),
+            )
+            .await
+            .map_err(|e| sala_error_to_status(e, idioma))?;
+
+        Ok(Response::new(cambio_to_proto(cambio)))
+    }
+
+    async fn eliminar_sala(
+        &self,
+        request: Request<EliminarSalaRequest>,
+    ) -> Result<Response<CambioSalaResponse>, Status> {
+        // Requiere sala:manage sobre la sala
+        request.principal_con_permiso_en_sala(Permiso::GestionarSalas, &request.get_ref().id)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let cambio = self
+            .service
+            .eliminar_sala(&req.id, accion_from_proto(req.accion))
+            .await
+            .map_err(|e| sala_error_to_status(e, idioma))?;
+
+        Ok(Response::new(cambio_to_proto(cambio)))
+    }
+
+    async fn configurar_margenes_sala(
+        &self,
+        request: Request<ConfigurarMargenesSalaRequest>,
+    ) -> Result<Response<SalaResponse>, Status> {
+        // Requiere sala:manage sobre la sala
+        request.principal_con_permiso_en_sala(Permiso::GestionarSalas, &request.get_ref().id)?;
+        let idioma = request.idioma();
+
+        let req = request.into_inner();
+
+        let sala = self
+            .service
+            .configurar_margenes(&req.id, req.margen_antes, req.margen_despues)
+            .await
+            .map_err(|e| sala_error_to_status(e, idioma))?;
+
+        Ok(Response::new(sala_to_proto(&sala)))
     }
 }
 
-// Helper para convertir SalaError a Status de gRPC
-fn sala_error_to_status(error: SalaError) -> Status {
-    match error {
-        SalaError::NombreVacio | SalaError::NombreDemasiadoLargo | SalaError::CapacidadInvalida => {
-            Status::invalid_argument(error.to_string())
-        }
-        SalaError::NoEncontrada => Status::not_found(error.to_string()),
-        SalaError::ErrorRepositorio(_) => Status::internal(error.to_string()),
-        SalaError::Validacion(msgs) => Status::invalid_argument(msgs.join("; ")),
+fn sala_to_proto(sala: &Sala) -> SalaResponse {
+    SalaResponse {
+        id: sala.id().to_string(),
+        nombre: sala.nombre().to_string(),
+        capacidad: sala.capacidad(),
+        activa: sala.activa,
+        zona_horaria: sala.zona_horaria().name().to_string(),
+        margen_antes: sala.margen_antes(),
+        margen_despues: sala.margen_despues(),
+    }
+}
+
+fn cambio_to_proto(cambio: CambioSala) -> CambioSalaResponse {
+    CambioSalaResponse {
+        sala: Some(sala_to_proto(&cambio.sala)),
+        reservas_canceladas: cambio.reservas_canceladas,
+    }
+}
+
+fn accion_from_proto(accion: i32) -> AccionReservasFuturas {
+    match ProtoAccion::try_from(accion) {
+        Ok(ProtoAccion::Cancelar) => AccionReservasFuturas::Cancelar,
+        _ => AccionReservasFuturas::Rechazar,
     }
 }
+
+// Helper para convertir SalaError a Status de gRPC (con código y campos en los detalles)
+fn sala_error_to_status(error: SalaError, idioma: Idioma) -> Status {
+    shared_errores::grpc::a_status_en(&error, idioma)
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
-use salas_application::SalaRepository;
+use salas_application::{ConsultaSalas, SalaRepository};
 use salas_domain::{Sala, SalaError};
 use serde::{Deserialize, Serialize};
+use shared_paginacion::Pagina;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -134,6 +135,12 @@ impl SalaRepository for FileSalaRepository {
         Ok(cache.values().cloned().collect())
     }
 
+    async fn buscar(&self, consulta: &ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+        // Se filtra sobre la cache sin clonar las salas que no entran en la página
+        let cache = self.cache.read().await;
+        Ok(consulta.aplicar(cache.values()))
+    }
+
     async fn actualizar(&self, sala: &Sala) -> Result<(), SalaError> {
         // Actualizar en cache
         {
@@ -146,11 +153,26 @@ impl SalaRepository for FileSalaRepository {
 
         Ok(())
     }
+
+    async fn eliminar(&self, id: &str) -> Result<(), SalaError> {
+        // Eliminar de cache
+        {
+            let mut cache = self.cache.write().await;
+            cache.remove(id).ok_or(SalaError::NoEncontrada)?;
+        }
+
+        // Persistir a disco
+        self.save_to_file().await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use salas_application::OrdenSalas;
+    use shared_paginacion::{Direccion, Paginacion};
     use std::fs;
     use tempfile::TempDir;
 
@@ -191,6 +213,28 @@ mod tests {
         assert_eq!(salas.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_buscar_salas_paginadas() {
+        let (repo, _temp) = crear_repo_temporal().await;
+
+        for (id, capacidad) in [("1", 10), ("2", 30), ("3", 20)] {
+            let sala = Sala::new(id.to_string(), format!("Sala {}", id), capacidad).unwrap();
+            repo.guardar(&sala).await.unwrap();
+        }
+
+        let consulta = ConsultaSalas {
+            orden: OrdenSalas::Capacidad,
+            direccion: Direccion::Desc,
+            paginacion: Paginacion::new(0, 2),
+            ..Default::default()
+        };
+        let pagina = repo.buscar(&consulta).await.unwrap();
+
+        assert_eq!(pagina.total, 3);
+        let ids: Vec<&str> = pagina.elementos.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+    }
+
     #[tokio::test]
     async fn test_actualizar_sala() {
         let (repo, _temp) = crear_repo_temporal().await;
@@ -207,6 +251,22 @@ mod tests {
         assert!(!resultado.esta_activa());
     }
 
+    #[tokio::test]
+    async fn test_eliminar_sala() {
+        let (repo, _temp) = crear_repo_temporal().await;
+
+        let sala = Sala::new("123".to_string(), "Sala a borrar".to_string(), 10).unwrap();
+        repo.guardar(&sala).await.unwrap();
+
+        repo.eliminar("123").await.unwrap();
+
+        assert!(repo.obtener("123").await.unwrap().is_none());
+        assert_eq!(
+            repo.eliminar("123").await.unwrap_err(),
+            SalaError::NoEncontrada
+        );
+    }
+
     #[tokio::test]
     async fn test_persistencia_en_archivo() {
         let temp_dir = TempDir::new().unwrap();
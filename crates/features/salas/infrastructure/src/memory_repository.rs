@@ -1,6 +1,7 @@
 use async_trait::async_trait;
-use salas_application::SalaRepository;
+use salas_application::{ConsultaSalas, SalaRepository};
 use salas_domain::{Sala, SalaError};
+use shared_paginacion::Pagina;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
@@ -50,6 +51,14 @@ impl SalaRepository for InMemorySalaRepository {
         Ok(store.values().cloned().collect())
     }
 
+    async fn buscar(&self, consulta: &ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|e| SalaError::ErrorRepositorio(format!("Error al leer: {}", e)))?;
+        Ok(consulta.aplicar(store.values()))
+    }
+
     async fn actualizar(&self, sala: &Sala) -> Result<(), SalaError> {
         let mut store = self
             .store
@@ -58,4 +67,12 @@ impl SalaRepository for InMemorySalaRepository {
         store.insert(sala.id.clone(), sala.clone());
         Ok(())
     }
+
+    async fn eliminar(&self, id: &str) -> Result<(), SalaError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|e| SalaError::ErrorRepositorio(format!("Error al escribir: {}", e)))?;
+        store.remove(id).map(|_| ()).ok_or(SalaError::NoEncontrada)
+    }
 }
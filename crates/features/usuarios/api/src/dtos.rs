@@ -0,0 +1,433 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use usuarios_application::{
+    ApiKeyCreada, CuentaEliminada, ExportacionDatos, InscripcionTotp, LoginResponse, Perfil,
+    ReservaExportada, ResultadoLogin, SegundoFactorActivado,
+};
+use usuarios_domain::{nombre_dia, ApiKey, Grupo, HorarioLaboral, Notificaciones, UsuarioPublico};
+
+/// Body de `POST /auth/login`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+/// Respuesta del login
+///
+/// Si `requiere_segundo_factor`, no hay token ni usuario: se envía el código
+/// con el `desafio` a `POST /auth/login/segundo-factor` (o, si
+/// `inscripcion_requerida`, se inscribe el segundo factor con él).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LoginRespuesta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usuario: Option<UsuarioResponse>,
+    pub requiere_segundo_factor: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desafio: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub inscripcion_requerida: bool,
+}
+
+/// Body de `POST /auth/login/segundo-factor`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificarSegundoFactorRequest {
+    pub desafio: String,
+    /// Código de la app o uno de recuperación
+    pub codigo: String,
+}
+
+/// Body de `POST /auth/segundo-factor` y de su versión con el desafío del login
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IniciarSegundoFactorRequest {
+    /// Solo al inscribirse durante el login
+    #[serde(default)]
+    pub desafio: Option<String>,
+}
+
+/// Secreto para dar de alta la cuenta en la app de autenticación
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InscripcionTotpResponse {
+    /// En base32, para quien no pueda leer el QR
+    pub secreto: String,
+    /// Contenido del código QR
+    pub url_otpauth: String,
+}
+
+/// Body de `POST /auth/segundo-factor/activar`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivarSegundoFactorRequest {
+    #[serde(default)]
+    pub desafio: Option<String>,
+    pub codigo: String,
+}
+
+/// Segundo factor activado: única vez en la que se devuelven los códigos de
+/// recuperación
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SegundoFactorActivadoResponse {
+    pub codigos_recuperacion: Vec<String>,
+    /// Sesión abierta, si se ha activado con el desafío del login
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login: Option<LoginRespuesta>,
+}
+
+/// Body de `POST /auth/segundo-factor/desactivar`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DesactivarSegundoFactorRequest {
+    pub codigo: String,
+}
+
+/// Body de `POST /auth/password/olvidada`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SolicitarRestablecimientoRequest {
+    pub email: String,
+}
+
+/// Body de `POST /auth/password/restablecer`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestablecerPasswordRequest {
+    /// Token recibido en el enlace del correo
+    pub token: String,
+    pub nueva_password: String,
+}
+
+/// Body de `POST /auth/email/verificar`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificarEmailRequest {
+    pub token: String,
+}
+
+/// Body de `POST /me/email`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CambiarEmailRequest {
+    pub nuevo_email: String,
+    /// Contraseña actual, para que una sesión robada no baste
+    pub password: String,
+}
+
+/// Body de `POST /auth/email/cambio/confirmar`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmarCambioEmailRequest {
+    pub token: String,
+}
+
+/// Body de `PATCH /me`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActualizarPerfilRequest {
+    pub nombre: String,
+}
+
+/// Preferencias del perfil; es el body de `PUT /me/preferencias`, que las
+/// sustituye todas (lo que no se envía vuelve al valor por defecto)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PreferenciasDto {
+    /// `es` o `en`; sin él se negocia con `Accept-Language`
+    #[serde(default)]
+    pub idioma: Option<String>,
+    /// Nombre IANA; sin ella cada reserva se muestra en la zona de su sala
+    #[serde(default)]
+    pub zona_horaria: Option<String>,
+    #[serde(default)]
+    pub notificaciones: Notificaciones,
+    #[serde(default)]
+    pub sala_predeterminada: Option<String>,
+    #[serde(default)]
+    pub horario_laboral: Option<HorarioLaboralDto>,
+}
+
+/// Horario laboral con horas `HH:MM` y días como `lunes` o `mon`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HorarioLaboralDto {
+    pub inicio: String,
+    pub fin: String,
+    pub dias: Vec<String>,
+}
+
+/// Respuesta de `GET /me`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PerfilResponse {
+    #[serde(flatten)]
+    pub usuario: UsuarioResponse,
+    /// Email nuevo a la espera de que se confirme desde esa dirección
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_pendiente: Option<String>,
+    pub preferencias: PreferenciasDto,
+    /// Usuarios autorizados a reservar en su nombre
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegados: Vec<String>,
+}
+
+/// Respuesta de `GET /me/datos`: copia de todos los datos del usuario
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportacionDatosResponse {
+    pub perfil: PerfilResponse,
+    pub reservas: Vec<ReservaExportadaDto>,
+    pub generada: DateTime<Utc>,
+}
+
+/// Reserva dentro de la exportación de datos
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReservaExportadaDto {
+    pub id: String,
+    pub sala_id: String,
+    pub fecha_inicio: DateTime<Utc>,
+    pub fecha_fin: DateTime<Utc>,
+    pub estado: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body de `DELETE /me`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EliminarCuentaRequest {
+    /// Contraseña actual; las cuentas del SSO o del directorio no la envían
+    #[serde(default)]
+    pub password: String,
+}
+
+/// Respuesta de eliminar una cuenta
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CuentaEliminadaResponse {
+    pub reservas_canceladas: usize,
+    pub reservas_seudonimizadas: usize,
+}
+
+/// Usuario devuelto por la API (sin contraseña)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsuarioResponse {
+    pub id: String,
+    pub nombre: String,
+    pub email: String,
+    pub rol: String,
+    pub activo: bool,
+    pub email_verificado: bool,
+    #[serde(default)]
+    pub segundo_factor: bool,
+}
+
+impl From<UsuarioPublico> for UsuarioResponse {
+    fn from(usuario: UsuarioPublico) -> Self {
+        Self {
+            id: usuario.id,
+            nombre: usuario.nombre,
+            email: usuario.email,
+            rol: usuario.rol.as_str().to_string(),
+            activo: usuario.activo,
+            email_verificado: usuario.email_verificado,
+            segundo_factor: usuario.segundo_factor,
+        }
+    }
+}
+
+/// Body de `POST /api-keys`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrearApiKeyRequest {
+    /// Nombre de la integración, p. ej. "Pantalla sala 1"
+    pub nombre: String,
+    /// Permisos de la clave, p. ej. `["reserva:read_any"]`; sin ninguno
+    /// solo puede hacer lo que cualquier usuario autenticado (ver salas)
+    #[serde(default)]
+    pub permisos: Vec<String>,
+}
+
+/// Clave de API devuelta por la API (sin la clave ni su hash)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub id: String,
+    pub nombre: String,
+    pub prefijo: String,
+    pub permisos: Vec<String>,
+    pub creada_por: String,
+    pub creada: DateTime<Utc>,
+    pub ultimo_uso: Option<DateTime<Utc>>,
+    pub revocada: Option<DateTime<Utc>>,
+}
+
+/// Respuesta de creación: única vez en la que se devuelve la clave
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyCreadaResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    /// Se envía como `Authorization: ApiKey <clave>`
+    pub clave: String,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(clave: ApiKey) -> Self {
+        Self {
+            id: clave.id,
+            nombre: clave.nombre,
+            prefijo: clave.prefijo,
+            permisos: clave
+                .permisos
+                .iter()
+                .map(|p| p.as_str().to_string())
+                .collect(),
+            creada_por: clave.creada_por,
+            creada: clave.creada,
+            ultimo_uso: clave.ultimo_uso,
+            revocada: clave.revocada,
+        }
+    }
+}
+
+impl From<ApiKeyCreada> for ApiKeyCreadaResponse {
+    fn from(creada: ApiKeyCreada) -> Self {
+        Self {
+            api_key: creada.api_key.into(),
+            clave: creada.clave,
+        }
+    }
+}
+
+impl From<LoginResponse> for LoginRespuesta {
+    fn from(login: LoginResponse) -> Self {
+        Self {
+            token: Some(login.token),
+            usuario: Some(login.usuario.into()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ResultadoLogin> for LoginRespuesta {
+    fn from(resultado: ResultadoLogin) -> Self {
+        match resultado {
+            ResultadoLogin::Completado(login) => login.into(),
+            ResultadoLogin::SegundoFactor {
+                desafio,
+                inscripcion_requerida,
+            } => Self {
+                requiere_segundo_factor: true,
+                desafio: Some(desafio),
+                inscripcion_requerida,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<InscripcionTotp> for InscripcionTotpResponse {
+    fn from(inscripcion: InscripcionTotp) -> Self {
+        Self {
+            secreto: inscripcion.secreto,
+            url_otpauth: inscripcion.url,
+        }
+    }
+}
+
+impl From<SegundoFactorActivado> for SegundoFactorActivadoResponse {
+    fn from(activado: SegundoFactorActivado) -> Self {
+        Self {
+            codigos_recuperacion: activado.codigos_recuperacion,
+            login: activado.login.map(Into::into),
+        }
+    }
+}
+
+impl From<&HorarioLaboral> for HorarioLaboralDto {
+    fn from(horario: &HorarioLaboral) -> Self {
+        Self {
+            inicio: horario.inicio.format("%H:%M").to_string(),
+            fin: horario.fin.format("%H:%M").to_string(),
+            dias: horario
+                .dias
+                .iter()
+                .map(|dia| nombre_dia(*dia).to_string())
+                .collect(),
+        }
+    }
+}
+
+impl From<Perfil> for PerfilResponse {
+    fn from(perfil: Perfil) -> Self {
+        let preferencias = PreferenciasDto {
+            idioma: perfil.usuario.idioma.map(|i| i.codigo().to_string()),
+            zona_horaria: perfil.usuario.zona_horaria.map(|z| z.name().to_string()),
+            notificaciones: perfil.preferencias.notificaciones,
+            sala_predeterminada: perfil.preferencias.sala_predeterminada,
+            horario_laboral: perfil.preferencias.horario_laboral.as_ref().map(Into::into),
+        };
+        Self {
+            usuario: perfil.usuario.into(),
+            email_pendiente: perfil.email_pendiente,
+            preferencias,
+            delegados: perfil.delegados,
+        }
+    }
+}
+
+impl From<ReservaExportada> for ReservaExportadaDto {
+    fn from(reserva: ReservaExportada) -> Self {
+        Self {
+            id: reserva.id,
+            sala_id: reserva.sala_id,
+            fecha_inicio: reserva.fecha_inicio,
+            fecha_fin: reserva.fecha_fin,
+            estado: reserva.estado,
+            created_at: reserva.created_at,
+        }
+    }
+}
+
+impl From<ExportacionDatos> for ExportacionDatosResponse {
+    fn from(exportacion: ExportacionDatos) -> Self {
+        Self {
+            perfil: exportacion.perfil.into(),
+            reservas: exportacion.reservas.into_iter().map(Into::into).collect(),
+            generada: exportacion.generada,
+        }
+    }
+}
+
+impl From<CuentaEliminada> for CuentaEliminadaResponse {
+    fn from(eliminada: CuentaEliminada) -> Self {
+        Self {
+            reservas_canceladas: eliminada.reservas_canceladas,
+            reservas_seudonimizadas: eliminada.reservas_seudonimizadas,
+        }
+    }
+}
+
+/// Body de `POST /grupos`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrearGrupoRequest {
+    pub nombre: String,
+}
+
+/// Body de `POST /me/delegados`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutorizarDelegadoRequest {
+    pub usuario_id: String,
+}
+
+/// Body de `POST /grupos/{id}/miembros`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgregarMiembroRequest {
+    pub usuario_id: String,
+}
+
+/// Grupo devuelto por la API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrupoResponse {
+    pub id: String,
+    pub nombre: String,
+    /// IDs de los usuarios miembros
+    pub miembros: Vec<String>,
+    pub creado_por: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Grupo> for GrupoResponse {
+    fn from(grupo: Grupo) -> Self {
+        Self {
+            id: grupo.id,
+            nombre: grupo.nombre,
+            miembros: grupo.miembros,
+            creado_por: grupo.creado_por,
+            created_at: grupo.created_at,
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use crate::dtos::{
+    ActivarSegundoFactorRequest, ActualizarPerfilRequest, AgregarMiembroRequest,
+    ApiKeyCreadaResponse, ApiKeyResponse, AutorizarDelegadoRequest, CambiarEmailRequest,
+    ConfirmarCambioEmailRequest, CrearApiKeyRequest, CrearGrupoRequest, CuentaEliminadaResponse,
+    DesactivarSegundoFactorRequest, EliminarCuentaRequest, ExportacionDatosResponse, GrupoResponse,
+    IniciarSegundoFactorRequest, InscripcionTotpResponse, LoginRequest, LoginRespuesta,
+    PerfilResponse, PreferenciasDto, RestablecerPasswordRequest, SegundoFactorActivadoResponse,
+    SolicitarRestablecimientoRequest, UsuarioResponse, VerificarEmailRequest,
+    VerificarSegundoFactorRequest,
+};
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use shared_errores::rest::respuesta_problema;
+use shared_errores::{ErrorCampo, Idioma};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use usuarios_application::{
+    AccesoSegundoFactor, ApiKeyService, AuthService, CuentaService, DatosPersonalesService,
+    GrupoService, PerfilService,
+};
+use usuarios_auth::{Autorizador, ErrorAutenticacion, Principal};
+use usuarios_domain::{
+    parsear_zona_horaria, Grupo, HorarioLaboral, Permiso, Preferencias, UsuarioError,
+};
+
+pub type SharedAuthService = Arc<dyn AuthService + Send + Sync>;
+pub type SharedCuentaService = Arc<dyn CuentaService + Send + Sync>;
+pub type SharedApiKeyService = Arc<dyn ApiKeyService + Send + Sync>;
+pub type SharedPerfilService = Arc<dyn PerfilService + Send + Sync>;
+pub type SharedDatosPersonalesService = Arc<dyn DatosPersonalesService + Send + Sync>;
+pub type SharedGrupoService = Arc<dyn GrupoService + Send + Sync>;
+
+type Conexion = Option<Extension<ConnectInfo<SocketAddr>>>;
+
+/// Login con email y contraseña
+///
+/// Si la cuenta tiene (o debe tener) segundo factor devuelve un desafío en
+/// lugar del token.
+pub async fn login(
+    State(service): State<SharedAuthService>,
+    conexion: Conexion,
+    Json(body): Json<LoginRequest>,
+) -> Result<Json<LoginRespuesta>, AppError> {
+    let origen = conexion.map(|Extension(ConnectInfo(addr))| addr.ip());
+    let resultado = service.login(body.email, body.password, origen).await?;
+    Ok(Json(resultado.into()))
+}
+
+/// Completa el login con el código de la app o uno de recuperación
+pub async fn verificar_segundo_factor(
+    State(service): State<SharedAuthService>,
+    conexion: Conexion,
+    Json(body): Json<VerificarSegundoFactorRequest>,
+) -> Result<Json<LoginRespuesta>, AppError> {
+    let origen = conexion.map(|Extension(ConnectInfo(addr))| addr.ip());
+    let login = service
+        .verificar_segundo_factor(body.desafio, body.codigo, origen)
+        .await?;
+    Ok(Json(login.into()))
+}
+
+/// Empieza a inscribir el segundo factor
+///
+/// Con el `desafio` del login si la política obliga a inscribirse; sin él,
+/// como el usuario autenticado.
+pub async fn iniciar_segundo_factor(
+    State(service): State<SharedAuthService>,
+    principal: Result<Principal, ErrorAutenticacion>,
+    body: Option<Json<IniciarSegundoFactorRequest>>,
+) -> Result<Json<InscripcionTotpResponse>, Response> {
+    let desafio = body.and_then(|Json(body)| body.desafio);
+    let acceso = acceso_segundo_factor(principal, desafio).map_err(|e| e.into_response())?;
+    let inscripcion = service
+        .iniciar_segundo_factor(acceso)
+        .await
+        .map_err(|e| AppError(e).into_response())?;
+    Ok(Json(inscripcion.into()))
+}
+
+/// Activa el segundo factor con un primer código de la app
+pub async fn activar_segundo_factor(
+    State(service): State<SharedAuthService>,
+    principal: Result<Principal, ErrorAutenticacion>,
+    Json(body): Json<ActivarSegundoFactorRequest>,
+) -> Result<Json<SegundoFactorActivadoResponse>, Response> {
+    let acceso = acceso_segundo_factor(principal, body.desafio).map_err(|e| e.into_response())?;
+    let activado = service
+        .activar_segundo_factor(acceso, body.codigo)
+        .await
+        .map_err(|e| AppError(e).into_response())?;
+    Ok(Json(activado.into()))
+}
+
+/// Quita el segundo factor del usuario autenticado
+pub async fn desactivar_segundo_factor(
+    State(service): State<SharedAuthService>,
+    usuario: Principal,
+    Json(body): Json<DesactivarSegundoFactorRequest>,
+) -> Result<StatusCode, AppError> {
+    service
+        .desactivar_segundo_factor(usuario.user_id, body.codigo)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn acceso_segundo_factor(
+    principal: Result<Principal, ErrorAutenticacion>,
+    desafio: Option<String>,
+) -> Result<AccesoSegundoFactor, ErrorAutenticacion> {
+    match desafio.filter(|d| !d.is_empty()) {
+        Some(desafio) => Ok(AccesoSegundoFactor::Desafio(desafio)),
+        None => principal.map(|p| AccesoSegundoFactor::Sesion(p.user_id)),
+    }
+}
+
+/// Envía un enlace para restablecer la contraseña
+///
+/// Responde `202` exista o no el email, para no revelar qué cuentas hay.
+pub async fn solicitar_restablecimiento(
+    State(service): State<SharedCuentaService>,
+    Json(body): Json<SolicitarRestablecimientoRequest>,
+) -> Result<StatusCode, AppError> {
+    service.solicitar_restablecimiento(body.email).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Fija una contraseña nueva con el token del correo
+pub async fn restablecer_password(
+    State(service): State<SharedCuentaService>,
+    Json(body): Json<RestablecerPasswordRequest>,
+) -> Result<StatusCode, AppError> {
+    service
+        .restablecer_password(body.token, body.nueva_password)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reenvía el correo de verificación al usuario autenticado
+pub async fn solicitar_verificacion(
+    State(service): State<SharedCuentaService>,
+    usuario: Principal,
+) -> Result<StatusCode, AppError> {
+    service.solicitar_verificacion(usuario.user_id).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Confirma el email con el token del correo
+pub async fn verificar_email(
+    State(service): State<SharedCuentaService>,
+    Json(body): Json<VerificarEmailRequest>,
+) -> Result<Json<UsuarioResponse>, AppError> {
+    let usuario = service.verificar_email(body.token).await?;
+    Ok(Json(usuario.into()))
+}
+
+/// Pide cambiar el email del usuario autenticado
+///
+/// El cambio no se aplica hasta que se confirma con el enlace enviado a la
+/// dirección nueva.
+pub async fn solicitar_cambio_email(
+    State(service): State<SharedCuentaService>,
+    usuario: Principal,
+    Json(body): Json<CambiarEmailRequest>,
+) -> Result<StatusCode, AppError> {
+    service
+        .solicitar_cambio_email(usuario.user_id, body.nuevo_email, body.password)
+        .await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Aplica el cambio de email con el token del correo
+pub async fn confirmar_cambio_email(
+    State(service): State<SharedCuentaService>,
+    Json(body): Json<ConfirmarCambioEmailRequest>,
+) -> Result<Json<UsuarioResponse>, AppError> {
+    let usuario = service.confirmar_cambio_email(body.token).await?;
+    Ok(Json(usuario.into()))
+}
+
+/// Perfil y preferencias del usuario autenticado
+pub async fn obtener_perfil(
+    State(service): State<SharedPerfilService>,
+    usuario: Principal,
+) -> Result<Json<PerfilResponse>, AppError> {
+    let perfil = service.obtener_perfil(usuario.user_id).await?;
+    Ok(Json(perfil.into()))
+}
+
+/// Cambia el nombre del usuario autenticado
+pub async fn actualizar_perfil(
+    State(service): State<SharedPerfilService>,
+    usuario: Principal,
+    Json(body): Json<ActualizarPerfilRequest>,
+) -> Result<Json<PerfilResponse>, AppError> {
+    let perfil = service
+        .actualizar_nombre(usuario.user_id, body.nombre)
+        .await?;
+    Ok(Json(perfil.into()))
+}
+
+/// Sustituye las preferencias del usuario autenticado
+pub async fn actualizar_preferencias(
+    State(service): State<SharedPerfilService>,
+    usuario: Principal,
+    Json(body): Json<PreferenciasDto>,
+) -> Result<Json<PerfilResponse>, AppError> {
+    let idioma = match body.idioma.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(codigo) => Some(Idioma::from_str_opt(codigo).ok_or_else(|| {
+            UsuarioError::Validacion(vec![ErrorCampo::con_codigo(
+                "idioma",
+                "USUARIO_IDIOMA_INVALIDO",
+            )])
+        })?),
+    };
+    let zona_horaria = match body.zona_horaria.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(nombre) => Some(parsear_zona_horaria(nombre)?),
+    };
+    let horario_laboral = body
+        .horario_laboral
+        .map(|h| HorarioLaboral::parsear(&h.inicio, &h.fin, &h.dias))
+        .transpose()?;
+    let preferencias = Preferencias::new(
+        body.notificaciones,
+        body.sala_predeterminada,
+        horario_laboral,
+    );
+
+    let perfil = service
+        .actualizar_preferencias(usuario.user_id, idioma, zona_horaria, preferencias)
+        .await?;
+    Ok(Json(perfil.into()))
+}
+
+/// Autoriza a otro usuario a reservar en nombre del usuario autenticado
+pub async fn autorizar_delegado(
+    State(service): State<SharedPerfilService>,
+    usuario: Principal,
+    Json(body): Json<AutorizarDelegadoRequest>,
+) -> Result<Json<PerfilResponse>, AppError> {
+    let perfil = service
+        .autorizar_delegado(usuario.user_id, body.usuario_id)
+        .await?;
+    Ok(Json(perfil.into()))
+}
+
+/// Retira a un usuario la autorización a reservar en su nombre
+pub async fn revocar_delegado(
+    State(service): State<SharedPerfilService>,
+    usuario: Principal,
+    Path(delegado_id): Path<String>,
+) -> Result<Json<PerfilResponse>, AppError> {
+    let perfil = service
+        .revocar_delegado(usuario.user_id, delegado_id)
+        .await?;
+    Ok(Json(perfil.into()))
+}
+
+/// Descarga el perfil y todas las reservas del usuario autenticado en JSON
+pub async fn exportar_datos(
+    State(service): State<SharedDatosPersonalesService>,
+    usuario: Principal,
+) -> Result<impl IntoResponse, AppError> {
+    let exportacion = service.exportar_datos(usuario.user_id).await?;
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"mis-datos.json\"",
+        )],
+        Json(ExportacionDatosResponse::from(exportacion)),
+    ))
+}
+
+/// Elimina la cuenta del usuario autenticado
+///
+/// Cancela sus reservas futuras y conserva las demás con un seudónimo.
+pub async fn eliminar_cuenta(
+    State(service): State<SharedDatosPersonalesService>,
+    usuario: Principal,
+    body: Option<Json<EliminarCuentaRequest>>,
+) -> Result<Json<CuentaEliminadaResponse>, AppError> {
+    let password = body.map(|Json(body)| body.password).unwrap_or_default();
+    let eliminada = service.eliminar_cuenta(usuario.user_id, password).await?;
+    Ok(Json(eliminada.into()))
+}
+
+/// Elimina la cuenta de otro usuario, igual que si lo hiciera él mismo
+pub async fn eliminar_usuario(
+    State(service): State<SharedDatosPersonalesService>,
+    admin: Principal,
+    Path(id): Path<String>,
+) -> Result<Json<CuentaEliminadaResponse>, AppError> {
+    let eliminada = service.eliminar_usuario(admin.user_id, id).await?;
+    Ok(Json(eliminada.into()))
+}
+
+/// Crea una clave de API (devuelve la clave una única vez)
+pub async fn crear_api_key(
+    State(service): State<SharedApiKeyService>,
+    admin: Principal,
+    Json(body): Json<CrearApiKeyRequest>,
+) -> Result<(StatusCode, Json<ApiKeyCreadaResponse>), AppError> {
+    let permisos = parsear_permisos(&body.permisos)?;
+    let creada = service
+        .crear_api_key(body.nombre, permisos, admin.user_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(creada.into())))
+}
+
+pub async fn listar_api_keys(
+    State(service): State<SharedApiKeyService>,
+) -> Result<Json<Vec<ApiKeyResponse>>, AppError> {
+    let claves = service.listar_api_keys().await?;
+    Ok(Json(claves.into_iter().map(Into::into).collect()))
+}
+
+/// Revoca una clave; se conserva en el listado con la fecha de revocación
+pub async fn revocar_api_key(
+    State(service): State<SharedApiKeyService>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiKeyResponse>, AppError> {
+    let clave = service.revocar_api_key(&id).await?;
+    Ok(Json(clave.into()))
+}
+
+/// Crea un grupo con el usuario autenticado como primer miembro
+pub async fn crear_grupo(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+    Json(body): Json<CrearGrupoRequest>,
+) -> Result<(StatusCode, Json<GrupoResponse>), AppError> {
+    let grupo = service.crear_grupo(body.nombre, usuario.user_id).await?;
+    Ok((StatusCode::CREATED, Json(grupo.into())))
+}
+
+/// Lista los grupos del usuario autenticado; con `usuario:manage`, todos
+pub async fn listar_grupos(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+) -> Result<Json<Vec<GrupoResponse>>, AppError> {
+    let grupos = if Autorizador::puede(&usuario, Permiso::GestionarUsuarios) {
+        service.listar_grupos().await?
+    } else {
+        service.grupos_de_usuario(&usuario.user_id).await?
+    };
+    Ok(Json(grupos.into_iter().map(Into::into).collect()))
+}
+
+/// Grupo que el usuario puede gestionar: es miembro o tiene `usuario:manage`
+async fn grupo_gestionable(
+    service: &SharedGrupoService,
+    usuario: &Principal,
+    id: &str,
+) -> Result<Grupo, AppError> {
+    let grupo = service.obtener_grupo(id).await?;
+    if !Autorizador::puede_gestionar_grupo(usuario, &grupo) {
+        return Err(UsuarioError::PermisosDenegados.into());
+    }
+    Ok(grupo)
+}
+
+pub async fn obtener_grupo(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+    Path(id): Path<String>,
+) -> Result<Json<GrupoResponse>, AppError> {
+    let grupo = grupo_gestionable(&service, &usuario, &id).await?;
+    Ok(Json(grupo.into()))
+}
+
+/// Elimina el grupo; sus reservas quedan solo a nombre de quien las hizo
+pub async fn eliminar_grupo(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    grupo_gestionable(&service, &usuario, &id).await?;
+    service.eliminar_grupo(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn agregar_miembro(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+    Path(id): Path<String>,
+    Json(body): Json<AgregarMiembroRequest>,
+) -> Result<Json<GrupoResponse>, AppError> {
+    grupo_gestionable(&service, &usuario, &id).await?;
+    let grupo = service.agregar_miembro(&id, &body.usuario_id).await?;
+    Ok(Json(grupo.into()))
+}
+
+/// Quita un miembro; también sirve para salir del grupo
+pub async fn quitar_miembro(
+    State(service): State<SharedGrupoService>,
+    usuario: Principal,
+    Path((id, usuario_id)): Path<(String, String)>,
+) -> Result<Json<GrupoResponse>, AppError> {
+    grupo_gestionable(&service, &usuario, &id).await?;
+    let grupo = service.quitar_miembro(&id, &usuario_id).await?;
+    Ok(Json(grupo.into()))
+}
+
+fn parsear_permisos(nombres: &[String]) -> Result<Vec<Permiso>, UsuarioError> {
+    let mut permisos = Vec::with_capacity(nombres.len());
+    let mut errores = Vec::new();
+    for nombre in nombres {
+        match Permiso::from_str_opt(nombre) {
+            Some(permiso) => permisos.push(permiso),
+            None => errores.push(ErrorCampo::con_codigo(
+                "permisos",
+                "USUARIO_PERMISO_INVALIDO",
+            )),
+        }
+    }
+
+    if errores.is_empty() {
+        Ok(permisos)
+    } else {
+        Err(UsuarioError::Validacion(errores))
+    }
+}
+
+pub struct AppError(pub UsuarioError);
+
+impl From<UsuarioError> for AppError {
+    fn from(err: UsuarioError) -> Self {
+        AppError(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        respuesta_problema(self.0)
+    }
+}
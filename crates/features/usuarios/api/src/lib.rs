@@ -0,0 +1,22 @@
+pub mod dtos;
+pub mod handlers;
+pub mod routes;
+
+pub use dtos::{
+    ActivarSegundoFactorRequest, ActualizarPerfilRequest, AgregarMiembroRequest,
+    ApiKeyCreadaResponse, ApiKeyResponse, AutorizarDelegadoRequest, CambiarEmailRequest,
+    ConfirmarCambioEmailRequest, CrearApiKeyRequest, CrearGrupoRequest, CuentaEliminadaResponse,
+    DesactivarSegundoFactorRequest, EliminarCuentaRequest, ExportacionDatosResponse, GrupoResponse,
+    HorarioLaboralDto, IniciarSegundoFactorRequest, InscripcionTotpResponse, LoginRequest,
+    LoginRespuesta, PerfilResponse, PreferenciasDto, ReservaExportadaDto,
+    RestablecerPasswordRequest, SegundoFactorActivadoResponse, SolicitarRestablecimientoRequest,
+    UsuarioResponse, VerificarEmailRequest, VerificarSegundoFactorRequest,
+};
+pub use handlers::{
+    SharedApiKeyService, SharedAuthService, SharedCuentaService, SharedDatosPersonalesService,
+    SharedGrupoService, SharedPerfilService,
+};
+pub use routes::{
+    api_keys_routes_with_auth, auth_routes, cuenta_routes, datos_personales_routes, grupos_routes,
+    perfil_routes,
+};
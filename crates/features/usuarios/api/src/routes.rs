@@ -0,0 +1,112 @@
+use crate::handlers::{
+    activar_segundo_factor, actualizar_perfil, actualizar_preferencias, agregar_miembro,
+    autorizar_delegado, confirmar_cambio_email, crear_api_key, crear_grupo,
+    desactivar_segundo_factor, eliminar_cuenta, eliminar_grupo, eliminar_usuario, exportar_datos,
+    iniciar_segundo_factor, listar_api_keys, listar_grupos, login, obtener_grupo, obtener_perfil,
+    quitar_miembro, restablecer_password, revocar_api_key, revocar_delegado,
+    solicitar_cambio_email, solicitar_restablecimiento, solicitar_verificacion, verificar_email,
+    verificar_segundo_factor, SharedApiKeyService, SharedAuthService, SharedCuentaService,
+    SharedDatosPersonalesService, SharedGrupoService, SharedPerfilService,
+};
+use axum::middleware;
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+use usuarios_auth::rest::{requiere_autenticacion, requiere_permiso};
+use usuarios_domain::Permiso;
+
+/// Rutas de login y segundo factor
+/// - El login y su segundo paso son públicos
+/// - Inscribir el segundo factor admite el desafío del login o un usuario
+///   autenticado; quitarlo requiere autenticación
+pub fn auth_routes(service: SharedAuthService) -> Router {
+    let publicas = Router::new()
+        .route("/auth/login", post(login))
+        .route("/auth/login/segundo-factor", post(verificar_segundo_factor))
+        .route("/auth/segundo-factor", post(iniciar_segundo_factor))
+        .route("/auth/segundo-factor/activar", post(activar_segundo_factor));
+
+    let autenticadas = Router::new()
+        .route(
+            "/auth/segundo-factor/desactivar",
+            post(desactivar_segundo_factor),
+        )
+        .layer(middleware::from_fn(requiere_autenticacion));
+
+    publicas.merge(autenticadas).with_state(service)
+}
+
+/// Rutas de recuperación de contraseña, verificación y cambio de email
+/// - Las que llevan un token del correo son públicas
+/// - Reenviar la verificación y pedir el cambio de email requieren autenticación
+pub fn cuenta_routes(service: SharedCuentaService) -> Router {
+    let publicas = Router::new()
+        .route("/auth/password/olvidada", post(solicitar_restablecimiento))
+        .route("/auth/password/restablecer", post(restablecer_password))
+        .route("/auth/email/verificar", post(verificar_email))
+        .route("/auth/email/cambio/confirmar", post(confirmar_cambio_email));
+
+    let autenticadas = Router::new()
+        .route("/auth/email/verificacion", post(solicitar_verificacion))
+        .route("/me/email", post(solicitar_cambio_email))
+        .layer(middleware::from_fn(requiere_autenticacion));
+
+    publicas.merge(autenticadas).with_state(service)
+}
+
+/// Rutas del perfil propio
+/// - Todas requieren autenticación y actúan sobre el usuario autenticado
+pub fn perfil_routes(service: SharedPerfilService) -> Router {
+    Router::new()
+        .route("/me", get(obtener_perfil).patch(actualizar_perfil))
+        .route("/me/preferencias", put(actualizar_preferencias))
+        .route("/me/delegados", post(autorizar_delegado))
+        .route("/me/delegados/{usuario_id}", delete(revocar_delegado))
+        .layer(middleware::from_fn(requiere_autenticacion))
+        .with_state(service)
+}
+
+/// Rutas de exportación y eliminación de cuentas
+/// - Descargar los datos y eliminar la propia cuenta requieren autenticación
+/// - Eliminar la de otro usuario requiere `usuario:manage`
+pub fn datos_personales_routes(service: SharedDatosPersonalesService) -> Router {
+    let propias = Router::new()
+        .route("/me/datos", get(exportar_datos))
+        .route("/me", delete(eliminar_cuenta))
+        .layer(middleware::from_fn(requiere_autenticacion));
+
+    let admin = Router::new()
+        .route("/usuarios/{id}", delete(eliminar_usuario))
+        .layer(middleware::from_fn_with_state(
+            Permiso::GestionarUsuarios,
+            requiere_permiso,
+        ));
+
+    propias.merge(admin).with_state(service)
+}
+
+/// Rutas de grupos de usuarios
+/// - Todas requieren autenticación; cualquiera puede crear un grupo
+/// - Ver, cambiar los miembros o eliminar un grupo es para sus miembros y
+///   quien tenga `usuario:manage`
+pub fn grupos_routes(service: SharedGrupoService) -> Router {
+    Router::new()
+        .route("/grupos", post(crear_grupo).get(listar_grupos))
+        .route("/grupos/{id}", get(obtener_grupo).delete(eliminar_grupo))
+        .route("/grupos/{id}/miembros", post(agregar_miembro))
+        .route("/grupos/{id}/miembros/{usuario_id}", delete(quitar_miembro))
+        .layer(middleware::from_fn(requiere_autenticacion))
+        .with_state(service)
+}
+
+/// Rutas de administración de claves de API
+/// - Todas requieren `usuario:manage`, que nunca se concede a una clave
+pub fn api_keys_routes_with_auth(service: SharedApiKeyService) -> Router {
+    Router::new()
+        .route("/api-keys", post(crear_api_key).get(listar_api_keys))
+        .route("/api-keys/{id}", delete(revocar_api_key))
+        .layer(middleware::from_fn_with_state(
+            Permiso::GestionarUsuarios,
+            requiere_permiso,
+        ))
+        .with_state(service)
+}
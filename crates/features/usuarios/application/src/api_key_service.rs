@@ -0,0 +1,189 @@
+use crate::repository::ApiKeyRepository;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use usuarios_auth::{EstadoApiKeys, TokenService};
+use usuarios_domain::{ApiKey, Permiso, UsuarioError, PREFIJO_API_KEY};
+
+/// Clave recién creada
+///
+/// La clave en claro solo se devuelve aquí: después únicamente queda su hash.
+#[derive(Debug, Clone)]
+pub struct ApiKeyCreada {
+    pub clave: String,
+    pub api_key: ApiKey,
+}
+
+/// Port (interfaz) de la gestión de claves de API de las cuentas de servicio
+#[async_trait]
+pub trait ApiKeyService: Send + Sync {
+    /// Crea una clave con los permisos indicados
+    async fn crear_api_key(
+        &self,
+        nombre: String,
+        permisos: Vec<Permiso>,
+        creada_por: String,
+    ) -> Result<ApiKeyCreada, UsuarioError>;
+
+    /// Lista todas las claves, también las revocadas
+    async fn listar_api_keys(&self) -> Result<Vec<ApiKey>, UsuarioError>;
+
+    /// Revoca una clave; deja de autenticar en la siguiente petición
+    async fn revocar_api_key(&self, id: &str) -> Result<ApiKey, UsuarioError>;
+}
+
+/// Implementación del servicio de claves de API
+pub struct ApiKeyServiceImpl<R: ApiKeyRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: ApiKeyRepository> ApiKeyServiceImpl<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl<R: ApiKeyRepository> ApiKeyService for ApiKeyServiceImpl<R> {
+    async fn crear_api_key(
+        &self,
+        nombre: String,
+        permisos: Vec<Permiso>,
+        creada_por: String,
+    ) -> Result<ApiKeyCreada, UsuarioError> {
+        let (secreto, _) = TokenService::generar();
+        let clave = format!("{}{}", PREFIJO_API_KEY, secreto);
+
+        let api_key = ApiKey::new(
+            nombre,
+            &clave,
+            TokenService::hash(&clave),
+            permisos,
+            creada_por,
+            Utc::now(),
+        )?;
+        self.repository.guardar(&api_key).await?;
+
+        tracing::info!(
+            "Clave de API {} ({}) creada con permisos {:?}",
+            api_key.prefijo,
+            api_key.nombre,
+            api_key.permisos
+        );
+        Ok(ApiKeyCreada { clave, api_key })
+    }
+
+    async fn listar_api_keys(&self) -> Result<Vec<ApiKey>, UsuarioError> {
+        let mut claves = self.repository.listar().await?;
+        claves.sort_by_key(|c| c.creada);
+        Ok(claves)
+    }
+
+    async fn revocar_api_key(&self, id: &str) -> Result<ApiKey, UsuarioError> {
+        let mut api_key = self
+            .repository
+            .obtener(id)
+            .await?
+            .ok_or_else(|| UsuarioError::ApiKeyNoEncontrada(id.to_string()))?;
+
+        api_key.revocar(Utc::now());
+        self.repository.actualizar(&api_key).await?;
+
+        tracing::info!("Clave de API {} revocada", api_key.prefijo);
+        Ok(api_key)
+    }
+}
+
+/// Claves de API para el `Autenticador` de REST y gRPC
+#[async_trait]
+impl<R: ApiKeyRepository> EstadoApiKeys for ApiKeyServiceImpl<R> {
+    async fn usar_api_key(&self, hash: &str) -> Result<Option<ApiKey>, String> {
+        let Some(mut api_key) = self
+            .repository
+            .obtener_por_hash(hash)
+            .await
+            .map_err(|e| e.to_string())?
+            .filter(ApiKey::es_valida)
+        else {
+            return Ok(None);
+        };
+
+        // No guardar el último uso no impide autenticar
+        if api_key.registrar_uso(Utc::now()) {
+            if let Err(e) = self.repository.actualizar(&api_key).await {
+                tracing::warn!(
+                    "No se pudo guardar el uso de la clave {}: {}",
+                    api_key.prefijo,
+                    e
+                );
+            }
+        }
+        Ok(Some(api_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::MockApiKeyRepository;
+
+    fn servicio() -> (
+        ApiKeyServiceImpl<MockApiKeyRepository>,
+        Arc<MockApiKeyRepository>,
+    ) {
+        let repo = Arc::new(MockApiKeyRepository::default());
+        (ApiKeyServiceImpl::new(repo.clone()), repo)
+    }
+
+    #[tokio::test]
+    async fn test_solo_guarda_el_hash_y_anota_el_uso() {
+        let (servicio, repo) = servicio();
+
+        let creada = servicio
+            .crear_api_key(
+                "Pantallas".to_string(),
+                vec![Permiso::VerCualquierReserva],
+                "admin".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(creada.clave.starts_with(PREFIJO_API_KEY));
+        assert!(creada.clave.starts_with(&creada.api_key.prefijo));
+
+        let guardada = repo.obtener(&creada.api_key.id).await.unwrap().unwrap();
+        assert_ne!(guardada.hash, creada.clave);
+        assert_eq!(guardada.ultimo_uso, None);
+
+        let usada = servicio
+            .usar_api_key(&TokenService::hash(&creada.clave))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(usada.permisos, vec![Permiso::VerCualquierReserva]);
+
+        let guardada = repo.obtener(&creada.api_key.id).await.unwrap().unwrap();
+        assert!(guardada.ultimo_uso.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_una_clave_revocada_no_autentica() {
+        let (servicio, _) = servicio();
+        let creada = servicio
+            .crear_api_key(
+                "Informes".to_string(),
+                vec![Permiso::VerInformes],
+                "admin".to_string(),
+            )
+            .await
+            .unwrap();
+
+        servicio.revocar_api_key(&creada.api_key.id).await.unwrap();
+
+        let hash = TokenService::hash(&creada.clave);
+        assert_eq!(servicio.usar_api_key(&hash).await.unwrap(), None);
+        assert!(matches!(
+            servicio.revocar_api_key("no-existe").await,
+            Err(UsuarioError::ApiKeyNoEncontrada(_))
+        ));
+    }
+}
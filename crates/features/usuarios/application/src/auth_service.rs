@@ -1,8 +1,19 @@
+use crate::bloqueo::{LimitadorIntentosIp, PoliticaBloqueo};
+use crate::directorio::VerificadorCredenciales;
+use crate::identidad_externa::{aprovisionar, MapeoRoles};
 use crate::repository::UsuarioRepository;
+use crate::segundo_factor::{
+    gastar_codigo, resultado_login, AccesoSegundoFactor, InscripcionTotp, ResultadoLogin,
+    SegundoFactorActivado, EMISOR_TOTP,
+};
 use async_trait::async_trait;
+use chrono::Utc;
+use std::net::IpAddr;
 use std::sync::Arc;
-use usuarios_auth::{JwtService, PasswordService};
-use usuarios_domain::{validar_password, Rol, Usuario, UsuarioError, UsuarioPublico};
+use usuarios_auth::{EstadoUsuarios, JwtService, PasswordService, TotpService};
+use usuarios_domain::{
+    PoliticaPassword, PoliticaSegundoFactor, Rol, Usuario, UsuarioError, UsuarioPublico,
+};
 
 /// Respuesta del login
 #[derive(Debug, Clone)]
@@ -31,7 +42,48 @@ pub trait AuthService: Send + Sync {
     ) -> Result<RegisterResponse, UsuarioError>;
 
     /// Autentica un usuario y genera un token JWT
-    async fn login(&self, email: String, password: String) -> Result<LoginResponse, UsuarioError>;
+    ///
+    /// `origen` es la IP del cliente, si se conoce; los fallos repetidos
+    /// bloquean temporalmente tanto la cuenta como la IP. Si la cuenta tiene
+    /// (o debe tener) segundo factor, en lugar del token devuelve un desafío.
+    async fn login(
+        &self,
+        email: String,
+        password: String,
+        origen: Option<IpAddr>,
+    ) -> Result<ResultadoLogin, UsuarioError>;
+
+    /// Completa el login con el código de la app o uno de recuperación
+    ///
+    /// Los códigos incorrectos cuentan como logins fallidos.
+    async fn verificar_segundo_factor(
+        &self,
+        desafio: String,
+        codigo: String,
+        origen: Option<IpAddr>,
+    ) -> Result<LoginResponse, UsuarioError>;
+
+    /// Genera el secreto que se da de alta en la app de autenticación
+    ///
+    /// Mientras no se active, repetirlo devuelve el mismo secreto.
+    async fn iniciar_segundo_factor(
+        &self,
+        acceso: AccesoSegundoFactor,
+    ) -> Result<InscripcionTotp, UsuarioError>;
+
+    /// Activa el segundo factor con un primer código de la app
+    async fn activar_segundo_factor(
+        &self,
+        acceso: AccesoSegundoFactor,
+        codigo: String,
+    ) -> Result<SegundoFactorActivado, UsuarioError>;
+
+    /// Quita el segundo factor, salvo que la política lo exija a su rol
+    async fn desactivar_segundo_factor(
+        &self,
+        user_id: String,
+        codigo: String,
+    ) -> Result<(), UsuarioError>;
 
     /// Valida un token JWT y retorna el usuario
     async fn validate_token(&self, token: String) -> Result<UsuarioPublico, UsuarioError>;
@@ -48,11 +100,132 @@ pub trait AuthService: Send + Sync {
 /// Implementación del servicio de autenticación
 pub struct AuthServiceImpl<R: UsuarioRepository> {
     repository: Arc<R>,
+    politica: PoliticaBloqueo,
+    intentos_ip: LimitadorIntentosIp,
+    politica_password: PoliticaPassword,
+    passwords: PasswordService,
+    directorio: Option<(Arc<dyn VerificadorCredenciales>, MapeoRoles)>,
+    segundo_factor: PoliticaSegundoFactor,
 }
 
 impl<R: UsuarioRepository> AuthServiceImpl<R> {
     pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            politica: PoliticaBloqueo::default(),
+            intentos_ip: LimitadorIntentosIp::new(),
+            politica_password: PoliticaPassword::default(),
+            passwords: PasswordService::default(),
+            directorio: None,
+            segundo_factor: PoliticaSegundoFactor::default(),
+        }
+    }
+
+    /// Cambia los límites de intentos de login
+    pub fn with_politica_bloqueo(mut self, politica: PoliticaBloqueo) -> Self {
+        self.politica = politica;
+        self
+    }
+
+    /// Cambia los requisitos de las contraseñas nuevas
+    pub fn with_politica_password(mut self, politica: PoliticaPassword) -> Self {
+        self.politica_password = politica;
+        self
+    }
+
+    /// Cambia los parámetros de Argon2; los hashes más débiles se recalculan
+    /// en el siguiente login correcto
+    pub fn with_password_service(mut self, passwords: PasswordService) -> Self {
+        self.passwords = passwords;
+        self
+    }
+
+    /// Verifica también las contraseñas contra un directorio externo (LDAP)
+    ///
+    /// Quien no tiene cuenta pero está en el directorio se da de alta al
+    /// entrar, con el rol que le corresponda por sus grupos.
+    pub fn with_verificador_externo(
+        mut self,
+        verificador: Arc<dyn VerificadorCredenciales>,
+        roles: MapeoRoles,
+    ) -> Self {
+        self.directorio = Some((verificador, roles));
+        self
+    }
+
+    /// Roles que deben entrar con segundo factor
+    pub fn with_politica_segundo_factor(mut self, politica: PoliticaSegundoFactor) -> Self {
+        self.segundo_factor = politica;
+        self
+    }
+
+    /// Usuario que inscribe el segundo factor
+    async fn usuario_de(&self, acceso: &AccesoSegundoFactor) -> Result<Usuario, UsuarioError> {
+        match acceso {
+            AccesoSegundoFactor::Sesion(id) => self
+                .repository
+                .obtener(id)
+                .await?
+                .ok_or_else(|| UsuarioError::UsuarioNoEncontrado(id.clone())),
+            AccesoSegundoFactor::Desafio(desafio) => self.usuario_del_desafio(desafio).await,
+        }
+    }
+
+    /// Usuario activo que ha dado la contraseña en el login del desafío
+    async fn usuario_del_desafio(&self, desafio: &str) -> Result<Usuario, UsuarioError> {
+        let id = JwtService::validate_desafio(desafio)
+            .map_err(|_| UsuarioError::DesafioSegundoFactorInvalido)?;
+        match self.repository.obtener(&id).await? {
+            Some(usuario) if usuario.activo => Ok(usuario),
+            Some(_) => Err(UsuarioError::CredencialesInvalidas),
+            None => Err(UsuarioError::DesafioSegundoFactorInvalido),
+        }
+    }
+
+    /// Verificador de la cuenta si su contraseña está en el directorio
+    fn verificador_de(&self, usuario: &Usuario) -> Option<&dyn VerificadorCredenciales> {
+        let (verificador, _) = self.directorio.as_ref()?;
+        let prefijo = format!("{}|", verificador.emisor());
+        usuario
+            .identidad_externa
+            .as_deref()
+            .filter(|identidad| identidad.starts_with(&prefijo))
+            .map(|_| verificador.as_ref())
+    }
+
+    /// Da de alta (o vincula) a quien aún no tiene cuenta pero sí está en el directorio
+    async fn alta_desde_directorio(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Option<Usuario>, UsuarioError> {
+        let Some((verificador, roles)) = self.directorio.as_ref() else {
+            return Ok(None);
+        };
+        let Some(identidad) = verificador.verificar(email, password).await? else {
+            return Ok(None);
+        };
+
+        aprovisionar(self.repository.as_ref(), &identidad, roles)
+            .await
+            .map(Some)
+    }
+
+    /// Anota el fallo de la IP; si queda bloqueada lo indica en el error
+    fn fallo_desde(&self, origen: Option<IpAddr>) -> UsuarioError {
+        if let Some(ip) = origen {
+            let ahora = Utc::now();
+            if self.intentos_ip.registrar_fallo(ip, ahora, &self.politica) {
+                tracing::warn!("IP {} bloqueada por intentos de login fallidos", ip);
+                return UsuarioError::DemasiadosIntentos {
+                    minutos: self
+                        .intentos_ip
+                        .minutos_bloqueada(ip, ahora)
+                        .unwrap_or_default(),
+                };
+            }
+        }
+        UsuarioError::CredencialesInvalidas
     }
 }
 
@@ -66,7 +239,7 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         rol: Option<Rol>,
     ) -> Result<RegisterResponse, UsuarioError> {
         // Validar contraseña
-        validar_password(&password)?;
+        self.politica_password.validar(&password)?;
 
         // Verificar que el email no esté registrado
         if self.repository.existe_email(&email).await? {
@@ -74,8 +247,10 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         }
 
         // Hashear contraseña
-        let password_hash =
-            PasswordService::hash_password(&password).map_err(UsuarioError::ErrorRepositorio)?;
+        let password_hash = self
+            .passwords
+            .hashear(&password)
+            .map_err(UsuarioError::ErrorRepositorio)?;
 
         // Crear usuario
         let rol = rol.unwrap_or(Rol::Usuario);
@@ -85,8 +260,8 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         self.repository.guardar(&usuario).await?;
 
         // Generar token JWT
-        let token = JwtService::generate_token(&usuario.id, &usuario.email, usuario.rol.clone())
-            .map_err(UsuarioError::ErrorRepositorio)?;
+        let token =
+            JwtService::generate_token_para(&usuario).map_err(UsuarioError::ErrorRepositorio)?;
 
         Ok(RegisterResponse {
             token,
@@ -94,37 +269,228 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         })
     }
 
-    async fn login(&self, email: String, password: String) -> Result<LoginResponse, UsuarioError> {
-        // Buscar usuario por email
-        let usuario = self
-            .repository
-            .obtener_por_email(&email)
-            .await?
-            .ok_or(UsuarioError::CredencialesInvalidas)?;
+    async fn login(
+        &self,
+        email: String,
+        password: String,
+        origen: Option<IpAddr>,
+    ) -> Result<ResultadoLogin, UsuarioError> {
+        let ahora = Utc::now();
+
+        // El bloqueo de la IP se comprueba antes de verificar la contraseña:
+        // Argon2 es caro y no debe poder forzarse a voluntad
+        if let Some(minutos) = origen.and_then(|ip| self.intentos_ip.minutos_bloqueada(ip, ahora)) {
+            return Err(UsuarioError::DemasiadosIntentos { minutos });
+        }
+
+        // Buscar usuario por email; si no hay, puede estar en el directorio
+        let Some(mut usuario) = self.repository.obtener_por_email(&email).await? else {
+            return match self.alta_desde_directorio(&email, &password).await? {
+                Some(usuario) if usuario.activo => resultado_login(&usuario, &self.segundo_factor),
+                _ => Err(self.fallo_desde(origen)),
+            };
+        };
 
         // Verificar que el usuario esté activo
         if !usuario.activo {
-            return Err(UsuarioError::CredencialesInvalidas);
+            return Err(self.fallo_desde(origen));
         }
 
-        // Verificar contraseña
-        let password_valida = PasswordService::verify_password(&password, &usuario.password_hash)
-            .map_err(UsuarioError::ErrorRepositorio)?;
+        // Verificar contraseña (las cuentas del directorio, contra él)
+        let directorio = self.verificador_de(&usuario);
+        let password_valida = match directorio {
+            Some(verificador) => verificador.verificar(&email, &password).await?.is_some(),
+            None => PasswordService::verify_password(&password, &usuario.password_hash)
+                .map_err(UsuarioError::ErrorRepositorio)?,
+        };
 
+        // Sin la contraseña correcta la respuesta es siempre la genérica: ni
+        // el bloqueo de la cuenta debe delatar que el email existe
         if !password_valida {
-            return Err(UsuarioError::CredencialesInvalidas);
+            if usuario.minutos_bloqueado(ahora).is_none() {
+                if usuario.registrar_login_fallido(
+                    ahora,
+                    self.politica.max_intentos_cuenta,
+                    self.politica.bloqueo_cuenta,
+                ) {
+                    tracing::warn!("Cuenta {} bloqueada por intentos fallidos", usuario.id);
+                }
+                self.repository.actualizar(&usuario).await?;
+            }
+            return Err(self.fallo_desde(origen));
         }
 
-        // Generar token JWT
-        let token = JwtService::generate_token(&usuario.id, &usuario.email, usuario.rol.clone())
-            .map_err(UsuarioError::ErrorRepositorio)?;
+        if let Some(minutos) = usuario.minutos_bloqueado(ahora) {
+            return Err(UsuarioError::CuentaBloqueada { minutos });
+        }
+
+        let mut cambios = usuario.registrar_login_correcto();
+
+        // Es el único momento en que se tiene la contraseña en claro para
+        // subir el coste de un hash antiguo; si falla, el login sigue adelante.
+        // La contraseña del directorio nunca se guarda aquí.
+        if directorio.is_none() && self.passwords.necesita_rehash(&usuario.password_hash) {
+            match self.passwords.hashear(&password) {
+                Ok(hash) => {
+                    usuario.actualizar_password(hash);
+                    cambios = true;
+                    tracing::info!("Hash de la contraseña de {} actualizado", usuario.id);
+                }
+                Err(e) => tracing::warn!("No se pudo recalcular el hash: {}", e),
+            }
+        }
+
+        if cambios {
+            self.repository.actualizar(&usuario).await?;
+        }
+
+        // Generar token JWT (o el desafío del segundo factor)
+        resultado_login(&usuario, &self.segundo_factor)
+    }
+
+    async fn verificar_segundo_factor(
+        &self,
+        desafio: String,
+        codigo: String,
+        origen: Option<IpAddr>,
+    ) -> Result<LoginResponse, UsuarioError> {
+        let ahora = Utc::now();
+        if let Some(minutos) = origen.and_then(|ip| self.intentos_ip.minutos_bloqueada(ip, ahora)) {
+            return Err(UsuarioError::DemasiadosIntentos { minutos });
+        }
+
+        let mut usuario = self.usuario_del_desafio(&desafio).await?;
+        if let Some(minutos) = usuario.minutos_bloqueado(ahora) {
+            return Err(UsuarioError::CuentaBloqueada { minutos });
+        }
+        if !usuario.tiene_segundo_factor() {
+            return Err(UsuarioError::SegundoFactorNoIniciado);
+        }
+
+        // Un código adivinado cuenta igual que una contraseña adivinada
+        if !gastar_codigo(&mut usuario, &codigo, ahora) {
+            let bloqueada = usuario.registrar_login_fallido(
+                ahora,
+                self.politica.max_intentos_cuenta,
+                self.politica.bloqueo_cuenta,
+            );
+            self.repository.actualizar(&usuario).await?;
+            let error = self.fallo_desde(origen);
+
+            if bloqueada {
+                tracing::warn!("Cuenta {} bloqueada por códigos incorrectos", usuario.id);
+                return Err(UsuarioError::CuentaBloqueada {
+                    minutos: self.politica.bloqueo_cuenta.num_minutes(),
+                });
+            }
+            return Err(match error {
+                UsuarioError::DemasiadosIntentos { .. } => error,
+                _ => UsuarioError::CodigoSegundoFactorInvalido,
+            });
+        }
+
+        usuario.registrar_login_correcto();
+        self.repository.actualizar(&usuario).await?;
 
+        let token = JwtService::generate_token_con_segundo_factor(&usuario)
+            .map_err(UsuarioError::ErrorRepositorio)?;
         Ok(LoginResponse {
             token,
             usuario: usuario.sin_password(),
         })
     }
 
+    async fn iniciar_segundo_factor(
+        &self,
+        acceso: AccesoSegundoFactor,
+    ) -> Result<InscripcionTotp, UsuarioError> {
+        let mut usuario = self.usuario_de(&acceso).await?;
+        if usuario.tiene_segundo_factor() {
+            return Err(UsuarioError::SegundoFactorYaActivo);
+        }
+
+        let secreto = match &usuario.segundo_factor {
+            Some(pendiente) => pendiente.secreto.clone(),
+            None => {
+                let secreto = TotpService::generar_secreto();
+                usuario.iniciar_segundo_factor(secreto.clone());
+                self.repository.actualizar(&usuario).await?;
+                secreto
+            }
+        };
+
+        Ok(InscripcionTotp {
+            url: TotpService::url_otpauth(&secreto, EMISOR_TOTP, &usuario.email),
+            secreto,
+        })
+    }
+
+    async fn activar_segundo_factor(
+        &self,
+        acceso: AccesoSegundoFactor,
+        codigo: String,
+    ) -> Result<SegundoFactorActivado, UsuarioError> {
+        let mut usuario = self.usuario_de(&acceso).await?;
+        if usuario.tiene_segundo_factor() {
+            return Err(UsuarioError::SegundoFactorYaActivo);
+        }
+        let factor = usuario
+            .segundo_factor
+            .as_mut()
+            .ok_or(UsuarioError::SegundoFactorNoIniciado)?;
+
+        // El primer código demuestra que la app tiene bien el secreto
+        let paso = TotpService::verificar(&factor.secreto, &codigo, Utc::now())
+            .ok_or(UsuarioError::CodigoSegundoFactorInvalido)?;
+        factor.registrar_paso(paso);
+
+        let (codigos_recuperacion, hashes) = TotpService::generar_codigos_recuperacion();
+        usuario.activar_segundo_factor(hashes);
+        self.repository.actualizar(&usuario).await?;
+        tracing::info!("Segundo factor activado para {}", usuario.id);
+
+        let login = match acceso {
+            AccesoSegundoFactor::Desafio(_) => Some(LoginResponse {
+                token: JwtService::generate_token_con_segundo_factor(&usuario)
+                    .map_err(UsuarioError::ErrorRepositorio)?,
+                usuario: usuario.sin_password(),
+            }),
+            AccesoSegundoFactor::Sesion(_) => None,
+        };
+
+        Ok(SegundoFactorActivado {
+            codigos_recuperacion,
+            login,
+        })
+    }
+
+    async fn desactivar_segundo_factor(
+        &self,
+        user_id: String,
+        codigo: String,
+    ) -> Result<(), UsuarioError> {
+        let mut usuario = self
+            .repository
+            .obtener(&user_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(user_id))?;
+
+        if !usuario.tiene_segundo_factor() {
+            return Err(UsuarioError::SegundoFactorNoIniciado);
+        }
+        if self.segundo_factor.exige(&usuario.rol) {
+            return Err(UsuarioError::SegundoFactorObligatorio);
+        }
+        if !gastar_codigo(&mut usuario, &codigo, Utc::now()) {
+            return Err(UsuarioError::CodigoSegundoFactorInvalido);
+        }
+
+        usuario.quitar_segundo_factor();
+        self.repository.actualizar(&usuario).await?;
+        tracing::info!("Segundo factor desactivado para {}", usuario.id);
+        Ok(())
+    }
+
     async fn validate_token(&self, token: String) -> Result<UsuarioPublico, UsuarioError> {
         // Validar token JWT
         let claims =
@@ -135,13 +501,17 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
             .repository
             .obtener(&claims.sub)
             .await?
-            .ok_or(UsuarioError::UsuarioNoEncontrado(claims.sub))?;
+            .ok_or_else(|| UsuarioError::UsuarioNoEncontrado(claims.sub.clone()))?;
 
         // Verificar que el usuario esté activo
         if !usuario.activo {
             return Err(UsuarioError::CredencialesInvalidas);
         }
 
+        if !claims.segundo_factor && self.segundo_factor.exige(&usuario.rol) {
+            return Err(UsuarioError::SegundoFactorRequerido);
+        }
+
         Ok(usuario.sin_password())
     }
 
@@ -152,7 +522,7 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         new_password: String,
     ) -> Result<(), UsuarioError> {
         // Validar nueva contraseña
-        validar_password(&new_password)?;
+        self.politica_password.validar(&new_password)?;
 
         // Obtener usuario
         let mut usuario = self
@@ -161,6 +531,10 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
             .await?
             .ok_or(UsuarioError::UsuarioNoEncontrado(user_id))?;
 
+        if self.verificador_de(&usuario).is_some() {
+            return Err(UsuarioError::PasswordGestionadaExternamente);
+        }
+
         // Verificar contraseña actual
         let password_valida =
             PasswordService::verify_password(&old_password, &usuario.password_hash)
@@ -171,7 +545,9 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
         }
 
         // Hashear nueva contraseña
-        let new_password_hash = PasswordService::hash_password(&new_password)
+        let new_password_hash = self
+            .passwords
+            .hashear(&new_password)
             .map_err(UsuarioError::ErrorRepositorio)?;
 
         // Actualizar usuario
@@ -182,69 +558,31 @@ impl<R: UsuarioRepository> AuthService for AuthServiceImpl<R> {
     }
 }
 
+/// Estado actual de los usuarios para el `Autenticador` de REST y gRPC
+#[async_trait]
+impl<R: UsuarioRepository> EstadoUsuarios for AuthServiceImpl<R> {
+    async fn usuario_actual(&self, id: &str) -> Result<Option<Usuario>, String> {
+        self.repository.obtener(id).await.map_err(|e| e.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
-    use tokio::sync::RwLock;
-
-    // Mock repository para tests
-    struct MockUsuarioRepository {
-        usuarios: Arc<RwLock<HashMap<String, Usuario>>>,
-    }
-
-    impl MockUsuarioRepository {
-        fn new() -> Self {
-            Self {
-                usuarios: Arc::new(RwLock::new(HashMap::new())),
-            }
-        }
-    }
-
-    #[async_trait]
-    impl UsuarioRepository for MockUsuarioRepository {
-        async fn guardar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.insert(usuario.id.clone(), usuario.clone());
-            Ok(())
-        }
-
-        async fn obtener(&self, id: &str) -> Result<Option<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.get(id).cloned())
-        }
-
-        async fn obtener_por_email(&self, email: &str) -> Result<Option<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().find(|u| u.email == email).cloned())
-        }
-
-        async fn listar(&self) -> Result<Vec<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().cloned().collect())
-        }
-
-        async fn actualizar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.insert(usuario.id.clone(), usuario.clone());
-            Ok(())
-        }
-
-        async fn eliminar(&self, id: &str) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.remove(id);
-            Ok(())
-        }
-
-        async fn existe_email(&self, email: &str) -> Result<bool, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().any(|u| u.email == email))
+    use crate::identidad_externa::IdentidadExterna;
+    use crate::test_util::MockUsuarioRepository;
+
+    /// Login que no ha necesitado segundo factor
+    fn completado(resultado: ResultadoLogin) -> LoginResponse {
+        match resultado {
+            ResultadoLogin::Completado(login) => login,
+            otro => panic!("login sin completar: {:?}", otro),
         }
     }
 
     #[tokio::test]
     async fn test_register_usuario() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         let result = service
@@ -265,7 +603,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_register_email_duplicado() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         // Registrar primer usuario
@@ -294,7 +632,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_login_exitoso() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         // Registrar usuario
@@ -310,18 +648,21 @@ mod tests {
 
         // Hacer login
         let result = service
-            .login("login@example.com".to_string(), "mypassword".to_string())
+            .login(
+                "login@example.com".to_string(),
+                "mypassword".to_string(),
+                None,
+            )
             .await;
 
-        assert!(result.is_ok());
-        let response = result.unwrap();
+        let response = completado(result.unwrap());
         assert!(!response.token.is_empty());
         assert_eq!(response.usuario.email, "login@example.com");
     }
 
     #[tokio::test]
     async fn test_login_password_incorrecta() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         // Registrar usuario
@@ -337,15 +678,94 @@ mod tests {
 
         // Intentar login con password incorrecta
         let result = service
-            .login("user@example.com".to_string(), "wrongpassword".to_string())
+            .login(
+                "user@example.com".to_string(),
+                "wrongpassword".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(UsuarioError::CredencialesInvalidas)));
+    }
+
+    #[tokio::test]
+    async fn test_login_bloquea_cuenta_tras_fallos() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = AuthServiceImpl::new(repo.clone()).with_politica_bloqueo(PoliticaBloqueo {
+            max_intentos_cuenta: 2,
+            ..Default::default()
+        });
+
+        service
+            .register(
+                "User".to_string(),
+                "lock@example.com".to_string(),
+                "correctpassword".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let login = |password: &str| {
+            service.login("lock@example.com".to_string(), password.to_string(), None)
+        };
+
+        // El fallo que la bloquea tampoco lo dice: sin la contraseña no se
+        // distingue de un email que no existe
+        for _ in 0..3 {
+            assert!(matches!(
+                login("wrong").await,
+                Err(UsuarioError::CredencialesInvalidas)
+            ));
+        }
+
+        // Bloqueada: ni siquiera la contraseña correcta entra, pero solo con
+        // ella se sabe que está bloqueada
+        assert!(matches!(
+            login("correctpassword").await,
+            Err(UsuarioError::CuentaBloqueada { minutos: 15 })
+        ));
+        let usuario = repo
+            .obtener_por_email("lock@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(usuario.bloqueado_hasta.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_login_bloquea_ip_con_emails_distintos() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = AuthServiceImpl::new(repo.clone()).with_politica_bloqueo(PoliticaBloqueo {
+            max_intentos_ip: 3,
+            ..Default::default()
+        });
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+
+        for i in 0..2 {
+            let result = service
+                .login(format!("nadie{}@example.com", i), "x".to_string(), Some(ip))
+                .await;
+            assert!(matches!(result, Err(UsuarioError::CredencialesInvalidas)));
+        }
+        let result = service
+            .login("otro@example.com".to_string(), "x".to_string(), Some(ip))
             .await;
+        assert!(matches!(
+            result,
+            Err(UsuarioError::DemasiadosIntentos { .. })
+        ));
 
+        // Sin IP conocida solo cuenta el bloqueo por cuenta
+        let result = service
+            .login("otro@example.com".to_string(), "x".to_string(), None)
+            .await;
         assert!(matches!(result, Err(UsuarioError::CredencialesInvalidas)));
     }
 
     #[tokio::test]
     async fn test_validate_token() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         // Registrar y obtener token
@@ -367,9 +787,37 @@ mod tests {
         assert_eq!(usuario.email, "token@example.com");
     }
 
+    #[tokio::test]
+    async fn test_autenticador_rechaza_usuarios_desactivados() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = Arc::new(AuthServiceImpl::new(repo.clone()));
+        let autenticador = usuarios_auth::Autenticador::new().with_estado(service.clone());
+
+        let registro = service
+            .register(
+                "Baja".to_string(),
+                "baja@example.com".to_string(),
+                "password123".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        let cabecera = format!("Bearer {}", registro.token);
+        assert!(autenticador.autenticar(Some(&cabecera)).await.is_ok());
+
+        let mut usuario = repo.obtener(&registro.usuario.id).await.unwrap().unwrap();
+        usuario.desactivar();
+        repo.actualizar(&usuario).await.unwrap();
+
+        assert_eq!(
+            autenticador.autenticar(Some(&cabecera)).await,
+            Err(usuarios_auth::ErrorAutenticacion::UsuarioInactivo)
+        );
+    }
+
     #[tokio::test]
     async fn test_change_password() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = AuthServiceImpl::new(repo.clone());
 
         // Registrar usuario
@@ -399,6 +847,7 @@ mod tests {
             .login(
                 "change@example.com".to_string(),
                 "newpassword123".to_string(),
+                None,
             )
             .await;
 
@@ -406,7 +855,11 @@ mod tests {
 
         // Verificar que el login con la vieja contraseña falla
         let old_login_result = service
-            .login("change@example.com".to_string(), "oldpassword".to_string())
+            .login(
+                "change@example.com".to_string(),
+                "oldpassword".to_string(),
+                None,
+            )
             .await;
 
         assert!(matches!(
@@ -414,4 +867,252 @@ mod tests {
             Err(UsuarioError::CredencialesInvalidas)
         ));
     }
+
+    #[tokio::test]
+    async fn test_register_aplica_la_politica_de_password() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = AuthServiceImpl::new(repo.clone())
+            .with_politica_password(PoliticaPassword::default().con_comunes());
+
+        let result = service
+            .register(
+                "User".to_string(),
+                "user@example.com".to_string(),
+                "password123".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(UsuarioError::Validacion(_))));
+        assert!(repo.listar().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_login_recalcula_hashes_debiles() {
+        use usuarios_auth::ParametrosArgon2;
+
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let debil = AuthServiceImpl::new(repo.clone()).with_password_service(PasswordService::new(
+            ParametrosArgon2::new(8 * 1024, 1, 1).unwrap(),
+        ));
+        let registro = debil
+            .register(
+                "User".to_string(),
+                "user@example.com".to_string(),
+                "mypassword".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        let hash_antiguo = repo
+            .obtener(&registro.usuario.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .password_hash;
+
+        let parametros = ParametrosArgon2::new(16 * 1024, 2, 1).unwrap();
+        let actual = AuthServiceImpl::new(repo.clone())
+            .with_password_service(PasswordService::new(parametros));
+        actual
+            .login(
+                "user@example.com".to_string(),
+                "mypassword".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let hash_nuevo = repo
+            .obtener(&registro.usuario.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .password_hash;
+        assert_ne!(hash_nuevo, hash_antiguo);
+        assert!(!PasswordService::new(parametros).necesita_rehash(&hash_nuevo));
+        assert!(PasswordService::verify_password("mypassword", &hash_nuevo).unwrap());
+    }
+
+    /// Directorio con una sola persona: ana@empresa.com / secreta
+    struct MockDirectorio;
+
+    #[async_trait]
+    impl VerificadorCredenciales for MockDirectorio {
+        fn emisor(&self) -> &str {
+            "ldap://directorio.test"
+        }
+
+        async fn verificar(
+            &self,
+            email: &str,
+            password: &str,
+        ) -> Result<Option<IdentidadExterna>, UsuarioError> {
+            if email != "ana@empresa.com" || password != "secreta" {
+                return Ok(None);
+            }
+            Ok(Some(IdentidadExterna {
+                emisor: self.emisor().to_string(),
+                sujeto: "uid=ana".to_string(),
+                email: Some(email.to_string()),
+                email_verificado: true,
+                nombre: Some("Ana García".to_string()),
+                grupos: vec!["recepcion".to_string()],
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_con_el_directorio_da_de_alta_y_no_guarda_su_password() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = AuthServiceImpl::new(repo.clone()).with_verificador_externo(
+            Arc::new(MockDirectorio),
+            MapeoRoles::parsear("recepcion=recepcionista").unwrap(),
+        );
+
+        let login = completado(
+            service
+                .login("ana@empresa.com".to_string(), "secreta".to_string(), None)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(login.usuario.rol, Rol::Recepcionista);
+
+        let usuario = repo.obtener(&login.usuario.id).await.unwrap().unwrap();
+        assert!(!PasswordService::verify_password("secreta", &usuario.password_hash).unwrap());
+
+        // Los siguientes logins también van contra el directorio
+        let otra = completado(
+            service
+                .login("ana@empresa.com".to_string(), "secreta".to_string(), None)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(otra.usuario.id, login.usuario.id);
+        assert!(matches!(
+            service
+                .login("ana@empresa.com".to_string(), "otra".to_string(), None)
+                .await,
+            Err(UsuarioError::CredencialesInvalidas)
+        ));
+        assert!(matches!(
+            service
+                .login("nadie@empresa.com".to_string(), "secreta".to_string(), None)
+                .await,
+            Err(UsuarioError::CredencialesInvalidas)
+        ));
+        assert!(matches!(
+            service
+                .change_password(
+                    login.usuario.id.clone(),
+                    "secreta".to_string(),
+                    "nueva-password".to_string()
+                )
+                .await,
+            Err(UsuarioError::PasswordGestionadaExternamente)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_la_politica_obliga_a_los_admins_a_inscribir_el_segundo_factor() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = AuthServiceImpl::new(repo.clone())
+            .with_politica_segundo_factor(PoliticaSegundoFactor::para_admins());
+        let registro = service
+            .register(
+                "Admin".to_string(),
+                "admin@example.com".to_string(),
+                "password123".to_string(),
+                Some(Rol::Admin),
+            )
+            .await
+            .unwrap();
+        let login = || {
+            service.login(
+                "admin@example.com".to_string(),
+                "password123".to_string(),
+                None,
+            )
+        };
+
+        // Su token sin segundo factor no abre sesión
+        assert!(matches!(
+            service.validate_token(registro.token).await,
+            Err(UsuarioError::SegundoFactorRequerido)
+        ));
+
+        let ResultadoLogin::SegundoFactor {
+            desafio,
+            inscripcion_requerida: true,
+        } = login().await.unwrap()
+        else {
+            panic!("el admin debería tener que inscribirse");
+        };
+        let acceso = AccesoSegundoFactor::Desafio(desafio);
+        let inscripcion = service
+            .iniciar_segundo_factor(acceso.clone())
+            .await
+            .unwrap();
+        assert!(inscripcion.url.contains(&inscripcion.secreto));
+
+        let paso = TotpService::paso(Utc::now());
+        let codigo = |paso| TotpService::codigo(&inscripcion.secreto, paso).unwrap();
+        assert!(matches!(
+            service
+                .activar_segundo_factor(acceso.clone(), "000000".to_string())
+                .await,
+            Err(UsuarioError::CodigoSegundoFactorInvalido)
+        ));
+        let activado = service
+            .activar_segundo_factor(acceso, codigo(paso))
+            .await
+            .unwrap();
+        let sesion = activado.login.unwrap();
+        assert_eq!(activado.codigos_recuperacion.len(), 10);
+        assert!(sesion.usuario.segundo_factor);
+        assert!(service.validate_token(sesion.token).await.is_ok());
+
+        // Los siguientes logins piden el código, que no vale dos veces
+        let ResultadoLogin::SegundoFactor {
+            desafio,
+            inscripcion_requerida: false,
+        } = login().await.unwrap()
+        else {
+            panic!("el login debería pedir el código");
+        };
+        assert!(matches!(
+            service
+                .verificar_segundo_factor(desafio.clone(), codigo(paso), None)
+                .await,
+            Err(UsuarioError::CodigoSegundoFactorInvalido)
+        ));
+        service
+            .verificar_segundo_factor(desafio.clone(), codigo(paso + 1), None)
+            .await
+            .unwrap();
+        service
+            .verificar_segundo_factor(desafio, activado.codigos_recuperacion[0].clone(), None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service
+                .desactivar_segundo_factor(
+                    registro.usuario.id.clone(),
+                    activado.codigos_recuperacion[1].clone()
+                )
+                .await,
+            Err(UsuarioError::SegundoFactorObligatorio)
+        ));
+        // Un desafío no permite cambiar el secreto de quien ya lo tiene
+        let ResultadoLogin::SegundoFactor { desafio, .. } = login().await.unwrap() else {
+            panic!("el login debería pedir el código");
+        };
+        assert!(matches!(
+            service
+                .iniciar_segundo_factor(AccesoSegundoFactor::Desafio(desafio))
+                .await,
+            Err(UsuarioError::SegundoFactorYaActivo)
+        ));
+    }
 }
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+/// A partir de cuántas IPs registradas se purgan las que ya no cuentan
+const PURGA_A_PARTIR_DE: usize = 1024;
+
+/// Límites contra la fuerza bruta en el login
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoliticaBloqueo {
+    /// Fallos seguidos que bloquean una cuenta
+    pub max_intentos_cuenta: u32,
+    /// Duración del bloqueo de la cuenta
+    pub bloqueo_cuenta: Duration,
+    /// Fallos desde una misma IP dentro de `ventana_ip` que la bloquean
+    pub max_intentos_ip: u32,
+    pub ventana_ip: Duration,
+    /// Duración del primer bloqueo de la IP
+    pub bloqueo_ip: Duration,
+    /// Tope de los bloqueos de una IP que sigue fallando: cada bloqueo que
+    /// llega en la ventana siguiente al anterior dura el doble
+    pub bloqueo_ip_maximo: Duration,
+}
+
+impl Default for PoliticaBloqueo {
+    fn default() -> Self {
+        Self {
+            max_intentos_cuenta: 5,
+            bloqueo_cuenta: Duration::minutes(15),
+            max_intentos_ip: 20,
+            ventana_ip: Duration::minutes(15),
+            bloqueo_ip: Duration::minutes(15),
+            bloqueo_ip_maximo: Duration::hours(4),
+        }
+    }
+}
+
+impl PoliticaBloqueo {
+    /// Duración del bloqueo de una IP que ya se ha bloqueado `previos` veces
+    fn duracion_bloqueo_ip(&self, previos: u32) -> Duration {
+        (0..previos).fold(self.bloqueo_ip, |duracion, _| {
+            (duracion * 2).min(self.bloqueo_ip_maximo)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RegistroIp {
+    fallos: u32,
+    ventana_desde: DateTime<Utc>,
+    bloqueada_hasta: Option<DateTime<Utc>>,
+    /// Bloqueos seguidos, para alargar el siguiente
+    bloqueos: u32,
+}
+
+impl RegistroIp {
+    fn nuevo(ahora: DateTime<Utc>) -> Self {
+        Self {
+            fallos: 0,
+            ventana_desde: ahora,
+            bloqueada_hasta: None,
+            bloqueos: 0,
+        }
+    }
+}
+
+/// Cuenta los logins fallidos por IP en memoria
+///
+/// Cubre el caso que el bloqueo por cuenta no ve: muchos emails distintos
+/// (o inexistentes) probados desde la misma dirección. Una IP que vuelve a
+/// fallar justo después de un bloqueo recibe otro del doble de duración.
+#[derive(Debug, Default)]
+pub struct LimitadorIntentosIp {
+    registros: Mutex<HashMap<IpAddr, RegistroIp>>,
+}
+
+impl LimitadorIntentosIp {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minutos que quedan de bloqueo de la IP (redondeando hacia arriba), si lo hay
+    pub fn minutos_bloqueada(&self, ip: IpAddr, ahora: DateTime<Utc>) -> Option<i64> {
+        let registros = self.registros.lock().unwrap();
+        registros
+            .get(&ip)
+            .and_then(|r| r.bloqueada_hasta)
+            .filter(|hasta| *hasta > ahora)
+            .map(|hasta| ((hasta - ahora).num_seconds() + 59) / 60)
+    }
+
+    /// Anota un login fallido desde `ip`. Devuelve si la IP queda bloqueada.
+    pub fn registrar_fallo(
+        &self,
+        ip: IpAddr,
+        ahora: DateTime<Utc>,
+        politica: &PoliticaBloqueo,
+    ) -> bool {
+        let mut registros = self.registros.lock().unwrap();
+        if registros.len() >= PURGA_A_PARTIR_DE {
+            registros.retain(|_, r| !Self::caducado(r, ahora, politica));
+        }
+
+        let registro = registros
+            .entry(ip)
+            .or_insert_with(|| RegistroIp::nuevo(ahora));
+        if Self::caducado(registro, ahora, politica) {
+            *registro = RegistroIp::nuevo(ahora);
+        }
+
+        registro.fallos += 1;
+        if registro.fallos < politica.max_intentos_ip {
+            return false;
+        }
+
+        // La ventana siguiente empieza al acabar el bloqueo: si en ella se
+        // vuelve a bloquear, el bloqueo dura más
+        let hasta = ahora + politica.duracion_bloqueo_ip(registro.bloqueos);
+        registro.fallos = 0;
+        registro.ventana_desde = hasta;
+        registro.bloqueada_hasta = Some(hasta);
+        registro.bloqueos += 1;
+        true
+    }
+
+    /// Un registro deja de contar cuando pasa su ventana y no está bloqueado
+    fn caducado(registro: &RegistroIp, ahora: DateTime<Utc>, politica: &PoliticaBloqueo) -> bool {
+        registro.bloqueada_hasta.is_none_or(|hasta| hasta <= ahora)
+            && registro.ventana_desde + politica.ventana_ip <= ahora
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloquea_la_ip_al_superar_el_limite() {
+        let limitador = LimitadorIntentosIp::new();
+        let politica = PoliticaBloqueo {
+            max_intentos_ip: 3,
+            ..Default::default()
+        };
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let otra: IpAddr = "10.0.0.2".parse().unwrap();
+        let ahora = Utc::now();
+
+        assert!(!limitador.registrar_fallo(ip, ahora, &politica));
+        assert!(!limitador.registrar_fallo(ip, ahora, &politica));
+        assert!(limitador.registrar_fallo(ip, ahora, &politica));
+
+        assert_eq!(limitador.minutos_bloqueada(ip, ahora), Some(15));
+        assert_eq!(limitador.minutos_bloqueada(otra, ahora), None);
+        assert_eq!(
+            limitador.minutos_bloqueada(ip, ahora + Duration::minutes(15)),
+            None
+        );
+    }
+
+    #[test]
+    fn los_fallos_caducan_con_la_ventana() {
+        let limitador = LimitadorIntentosIp::new();
+        let politica = PoliticaBloqueo {
+            max_intentos_ip: 2,
+            ..Default::default()
+        };
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let ahora = Utc::now();
+
+        assert!(!limitador.registrar_fallo(ip, ahora, &politica));
+        let despues = ahora + politica.ventana_ip;
+        assert!(!limitador.registrar_fallo(ip, despues, &politica));
+        assert_eq!(limitador.minutos_bloqueada(ip, despues), None);
+    }
+
+    #[test]
+    fn los_bloqueos_seguidos_se_alargan_hasta_el_maximo() {
+        let limitador = LimitadorIntentosIp::new();
+        let politica = PoliticaBloqueo {
+            max_intentos_ip: 1,
+            bloqueo_ip_maximo: Duration::minutes(40),
+            ..Default::default()
+        };
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let mut ahora = Utc::now();
+
+        for minutos in [15, 30, 40, 40] {
+            assert!(limitador.registrar_fallo(ip, ahora, &politica));
+            assert_eq!(limitador.minutos_bloqueada(ip, ahora), Some(minutos));
+            ahora += Duration::minutes(minutos);
+        }
+
+        // Pasada una ventana entera sin fallos se vuelve a empezar
+        ahora += politica.ventana_ip;
+        assert!(limitador.registrar_fallo(ip, ahora, &politica));
+        assert_eq!(limitador.minutos_bloqueada(ip, ahora), Some(15));
+    }
+}
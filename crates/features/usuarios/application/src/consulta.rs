@@ -0,0 +1,169 @@
+use shared_errores::ErrorCampo;
+use shared_paginacion::{Direccion, Pagina, Paginacion};
+use std::cmp::Ordering;
+use usuarios_domain::{Rol, Usuario, UsuarioError};
+
+/// Campo por el que se ordena el listado de usuarios
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrdenUsuarios {
+    #[default]
+    Nombre,
+    Email,
+    Creado,
+}
+
+impl OrdenUsuarios {
+    /// Crea el orden desde un string ("nombre", "email" o "creado")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "nombre" => Some(OrdenUsuarios::Nombre),
+            "email" => Some(OrdenUsuarios::Email),
+            "creado" => Some(OrdenUsuarios::Creado),
+            _ => None,
+        }
+    }
+}
+
+/// Filtros, orden y página para listar usuarios
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsultaUsuarios {
+    /// Solo usuarios activos (`true`) o desactivados (`false`)
+    pub activo: Option<bool>,
+    pub rol: Option<Rol>,
+    /// Texto que debe aparecer en el nombre o el email (sin distinguir mayúsculas)
+    pub texto: Option<String>,
+    pub orden: OrdenUsuarios,
+    pub direccion: Direccion,
+    pub paginacion: Paginacion,
+}
+
+impl ConsultaUsuarios {
+    /// Fija orden y dirección recibidos como texto; vacío deja el de por defecto
+    pub fn ordenada_por(mut self, orden: &str, direccion: &str) -> Result<Self, UsuarioError> {
+        let mut errores = Vec::new();
+
+        if !orden.trim().is_empty() {
+            match OrdenUsuarios::from_str_opt(orden) {
+                Some(orden) => self.orden = orden,
+                None => errores.push(ErrorCampo::con_codigo("orden", "USUARIO_ORDEN_INVALIDO")),
+            }
+        }
+        if !direccion.trim().is_empty() {
+            match Direccion::from_str_opt(direccion) {
+                Some(direccion) => self.direccion = direccion,
+                None => errores.push(ErrorCampo::con_codigo(
+                    "direccion",
+                    "LISTADO_DIRECCION_INVALIDA",
+                )),
+            }
+        }
+
+        if errores.is_empty() {
+            Ok(self)
+        } else {
+            Err(UsuarioError::Validacion(errores))
+        }
+    }
+
+    /// Indica si el usuario pasa los filtros
+    pub fn cumple(&self, usuario: &Usuario) -> bool {
+        self.activo.is_none_or(|activo| usuario.activo == activo)
+            && self.rol.as_ref().is_none_or(|rol| &usuario.rol == rol)
+            && self.texto.as_deref().is_none_or(|texto| {
+                let texto = texto.trim().to_lowercase();
+                usuario.nombre.to_lowercase().contains(&texto)
+                    || usuario.email.to_lowercase().contains(&texto)
+            })
+    }
+
+    /// Compara dos usuarios según el orden pedido; a igualdad decide el ID
+    pub fn comparar(&self, a: &Usuario, b: &Usuario) -> Ordering {
+        let orden = match self.orden {
+            OrdenUsuarios::Nombre => a.nombre.to_lowercase().cmp(&b.nombre.to_lowercase()),
+            OrdenUsuarios::Email => a.email.to_lowercase().cmp(&b.email.to_lowercase()),
+            OrdenUsuarios::Creado => a.created_at.cmp(&b.created_at),
+        };
+        self.direccion.aplicar(orden.then_with(|| a.id.cmp(&b.id)))
+    }
+
+    /// Filtra, ordena y pagina; solo se clonan los usuarios de la página
+    pub fn aplicar<'a>(&self, usuarios: impl IntoIterator<Item = &'a Usuario>) -> Pagina<Usuario> {
+        let mut filtrados: Vec<&Usuario> =
+            usuarios.into_iter().filter(|u| self.cumple(u)).collect();
+        filtrados.sort_by(|a, b| self.comparar(a, b));
+        Pagina::desde_ordenados(filtrados, self.paginacion).map(Clone::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usuarios() -> Vec<Usuario> {
+        let mut baja = Usuario::new(
+            "Carla".into(),
+            "carla@test.com".into(),
+            "hash".into(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        baja.desactivar();
+        vec![
+            Usuario::new(
+                "Ana".into(),
+                "ana@test.com".into(),
+                "hash".into(),
+                Rol::Admin,
+            )
+            .unwrap(),
+            Usuario::new(
+                "Bruno".into(),
+                "bruno@empresa.com".into(),
+                "hash".into(),
+                Rol::Usuario,
+            )
+            .unwrap(),
+            baja,
+        ]
+    }
+
+    #[test]
+    fn filtra_por_activo_rol_y_texto() {
+        let usuarios = usuarios();
+
+        let consulta = ConsultaUsuarios {
+            activo: Some(true),
+            rol: Some(Rol::Usuario),
+            ..Default::default()
+        };
+        let pagina = consulta.aplicar(&usuarios);
+        assert_eq!(pagina.total, 1);
+        assert_eq!(pagina.elementos[0].nombre, "Bruno");
+
+        let consulta = ConsultaUsuarios {
+            texto: Some("EMPRESA".into()),
+            ..Default::default()
+        };
+        assert_eq!(consulta.aplicar(&usuarios).total, 1);
+    }
+
+    #[test]
+    fn ordena_y_rechaza_orden_desconocido() {
+        let usuarios = usuarios();
+        let consulta = ConsultaUsuarios {
+            paginacion: Paginacion::new(0, 2),
+            ..Default::default()
+        }
+        .ordenada_por("email", "desc")
+        .unwrap();
+
+        let pagina = consulta.aplicar(&usuarios);
+        assert_eq!(pagina.elementos[0].nombre, "Carla");
+        assert_eq!(pagina.siguiente_offset(), Some(2));
+
+        let error = ConsultaUsuarios::default()
+            .ordenada_por("edad", "abajo")
+            .unwrap_err();
+        assert!(matches!(error, UsuarioError::Validacion(campos) if campos.len() == 2));
+    }
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use usuarios_domain::UsuarioError;
+
+/// Correo listo para enviar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correo {
+    pub para: String,
+    pub asunto: String,
+    pub cuerpo: String,
+}
+
+/// Port (interfaz) para enviar correos
+///
+/// Los adaptadores deciden el transporte (SMTP, un proveedor HTTP o, en
+/// local, un fichero).
+#[async_trait]
+pub trait EnvioCorreo: Send + Sync {
+    async fn enviar(&self, correo: &Correo) -> Result<(), UsuarioError>;
+}
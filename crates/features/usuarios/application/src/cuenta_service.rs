@@ -0,0 +1,588 @@
+use crate::correo::{Correo, EnvioCorreo};
+use crate::repository::{TokenUsuarioRepository, UsuarioRepository};
+use async_trait::async_trait;
+use chrono::Utc;
+use shared_errores::Idioma;
+use std::sync::Arc;
+use usuarios_auth::{PasswordService, TokenService};
+use usuarios_domain::{
+    PoliticaPassword, PropositoToken, TokenUsuario, Usuario, UsuarioError, UsuarioPublico,
+};
+
+/// Base de los enlaces de los correos si no se configura otra
+pub const URL_BASE_POR_DEFECTO: &str = "http://localhost:3000";
+
+/// Port (interfaz) de la recuperación de contraseña y verificación de email
+#[async_trait]
+pub trait CuentaService: Send + Sync {
+    /// Envía un enlace para elegir contraseña nueva
+    ///
+    /// Si el email no existe o la cuenta está desactivada no se envía nada,
+    /// pero el resultado es el mismo: no debe servir para averiguar qué
+    /// emails están registrados.
+    async fn solicitar_restablecimiento(&self, email: String) -> Result<(), UsuarioError>;
+
+    /// Canjea un token de restablecimiento y fija la contraseña nueva
+    ///
+    /// También levanta el bloqueo por intentos fallidos de la cuenta.
+    async fn restablecer_password(
+        &self,
+        token: String,
+        nueva_password: String,
+    ) -> Result<(), UsuarioError>;
+
+    /// Envía al usuario un enlace para confirmar su email
+    ///
+    /// Si el email ya está verificado no hace nada.
+    async fn solicitar_verificacion(&self, usuario_id: String) -> Result<(), UsuarioError>;
+
+    /// Canjea un token de verificación y marca el email como verificado
+    async fn verificar_email(&self, token: String) -> Result<UsuarioPublico, UsuarioError>;
+
+    /// Envía a la dirección nueva un enlace para confirmar el cambio de email
+    ///
+    /// Pide la contraseña actual. El email no cambia hasta que se confirma, y
+    /// no se puede cambiar en las cuentas del proveedor de identidad.
+    async fn solicitar_cambio_email(
+        &self,
+        usuario_id: String,
+        nuevo_email: String,
+        password: String,
+    ) -> Result<(), UsuarioError>;
+
+    /// Canjea el token del cambio de email: aplica el nuevo, ya verificado,
+    /// y avisa a la dirección anterior
+    async fn confirmar_cambio_email(&self, token: String) -> Result<UsuarioPublico, UsuarioError>;
+}
+
+/// Implementación del servicio de cuenta
+pub struct CuentaServiceImpl<R, T, C>
+where
+    R: UsuarioRepository,
+    T: TokenUsuarioRepository,
+    C: EnvioCorreo,
+{
+    repository: Arc<R>,
+    tokens: Arc<T>,
+    correo: Arc<C>,
+    url_base: String,
+    politica_password: PoliticaPassword,
+    passwords: PasswordService,
+}
+
+impl<R, T, C> CuentaServiceImpl<R, T, C>
+where
+    R: UsuarioRepository,
+    T: TokenUsuarioRepository,
+    C: EnvioCorreo,
+{
+    pub fn new(repository: Arc<R>, tokens: Arc<T>, correo: Arc<C>) -> Self {
+        Self {
+            repository,
+            tokens,
+            correo,
+            url_base: URL_BASE_POR_DEFECTO.to_string(),
+            politica_password: PoliticaPassword::default(),
+            passwords: PasswordService::default(),
+        }
+    }
+
+    /// Cambia la URL de la aplicación con la que se montan los enlaces
+    pub fn with_url_base(mut self, url_base: impl Into<String>) -> Self {
+        self.url_base = url_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Cambia los requisitos de las contraseñas nuevas
+    pub fn with_politica_password(mut self, politica: PoliticaPassword) -> Self {
+        self.politica_password = politica;
+        self
+    }
+
+    /// Cambia los parámetros de Argon2 con los que se hashea la contraseña nueva
+    pub fn with_password_service(mut self, passwords: PasswordService) -> Self {
+        self.passwords = passwords;
+        self
+    }
+
+    /// Emite un token nuevo (invalidando los pendientes) y lo envía por correo
+    async fn emitir(
+        &self,
+        usuario: &Usuario,
+        proposito: PropositoToken,
+    ) -> Result<(), UsuarioError> {
+        let (token, hash) = TokenService::generar();
+
+        self.tokens.invalidar(&usuario.id, proposito).await?;
+        self.tokens
+            .guardar(&TokenUsuario::new(
+                hash,
+                usuario.id.clone(),
+                proposito,
+                Utc::now(),
+            ))
+            .await?;
+
+        let correo = self.redactar(usuario, proposito, &token);
+        self.correo.enviar(&correo).await
+    }
+
+    /// Busca un token canjeable para ese propósito y lo marca como usado
+    async fn canjear(
+        &self,
+        token: &str,
+        proposito: PropositoToken,
+    ) -> Result<Usuario, UsuarioError> {
+        let mut guardado = self
+            .tokens
+            .obtener(&TokenService::hash(token))
+            .await?
+            .filter(|t| t.proposito == proposito && t.es_valido(Utc::now()))
+            .ok_or(UsuarioError::TokenInvalido)?;
+
+        let usuario = self
+            .repository
+            .obtener(&guardado.usuario_id)
+            .await?
+            .filter(|u| u.activo)
+            .ok_or(UsuarioError::TokenInvalido)?;
+
+        guardado.marcar_usado();
+        self.tokens.actualizar(&guardado).await?;
+
+        Ok(usuario)
+    }
+
+    fn redactar(&self, usuario: &Usuario, proposito: PropositoToken, token: &str) -> Correo {
+        let idioma = usuario.idioma.unwrap_or_default();
+        let (ruta, asunto, texto) = match (proposito, idioma) {
+            (PropositoToken::CambiarEmail, Idioma::Es) => (
+                "confirmar-email",
+                "Confirma tu nuevo email",
+                "Para usar esta dirección en tu cuenta abre este enlace (caduca en 24 horas):",
+            ),
+            (PropositoToken::CambiarEmail, Idioma::En) => (
+                "confirmar-email",
+                "Confirm your new email",
+                "To use this address for your account open this link (it expires in 24 hours):",
+            ),
+            (PropositoToken::RestablecerPassword, Idioma::Es) => (
+                "restablecer-password",
+                "Restablece tu contraseña",
+                "Para elegir una contraseña nueva abre este enlace (caduca en 1 hora):",
+            ),
+            (PropositoToken::RestablecerPassword, Idioma::En) => (
+                "restablecer-password",
+                "Reset your password",
+                "To choose a new password open this link (it expires in 1 hour):",
+            ),
+            (PropositoToken::VerificarEmail, Idioma::Es) => (
+                "verificar-email",
+                "Verifica tu email",
+                "Confirma tu dirección de email abriendo este enlace (caduca en 24 horas):",
+            ),
+            (PropositoToken::VerificarEmail, Idioma::En) => (
+                "verificar-email",
+                "Verify your email",
+                "Confirm your email address by opening this link (it expires in 24 hours):",
+            ),
+        };
+        let (saludo, ignorar) = match idioma {
+            Idioma::Es => ("Hola", "Si no lo has pedido tú, ignora este correo."),
+            Idioma::En => ("Hello", "If you did not request it, ignore this email."),
+        };
+
+        // El cambio de email se confirma desde la dirección nueva
+        let para = match proposito {
+            PropositoToken::CambiarEmail => usuario.email_pendiente.as_ref(),
+            _ => None,
+        };
+
+        Correo {
+            para: para.unwrap_or(&usuario.email).clone(),
+            asunto: asunto.to_string(),
+            cuerpo: format!(
+                "{} {}:\n\n{}\n{}/{}?token={}\n\n{}\n",
+                saludo, usuario.nombre, texto, self.url_base, ruta, token, ignorar
+            ),
+        }
+    }
+
+    /// Aviso a la dirección anterior de que el email de la cuenta ha cambiado
+    fn aviso_cambio_email(&self, usuario: &Usuario, anterior: String) -> Correo {
+        let (asunto, texto) = match usuario.idioma.unwrap_or_default() {
+            Idioma::Es => (
+                "Tu email ha cambiado",
+                format!(
+                    "Hola {}:\n\nEl email de tu cuenta ahora es {}. Si no has sido tú, avisa a un administrador.\n",
+                    usuario.nombre, usuario.email
+                ),
+            ),
+            Idioma::En => (
+                "Your email has changed",
+                format!(
+                    "Hello {}:\n\nYour account's email is now {}. If it was not you, contact an administrator.\n",
+                    usuario.nombre, usuario.email
+                ),
+            ),
+        };
+
+        Correo {
+            para: anterior,
+            asunto: asunto.to_string(),
+            cuerpo: texto,
+        }
+    }
+}
+
+#[async_trait]
+impl<R, T, C> CuentaService for CuentaServiceImpl<R, T, C>
+where
+    R: UsuarioRepository,
+    T: TokenUsuarioRepository,
+    C: EnvioCorreo,
+{
+    async fn solicitar_restablecimiento(&self, email: String) -> Result<(), UsuarioError> {
+        match self.repository.obtener_por_email(email.trim()).await? {
+            Some(usuario) if usuario.activo => {
+                self.emitir(&usuario, PropositoToken::RestablecerPassword)
+                    .await
+            }
+            _ => {
+                tracing::info!("Restablecimiento pedido para un email sin cuenta activa");
+                Ok(())
+            }
+        }
+    }
+
+    async fn restablecer_password(
+        &self,
+        token: String,
+        nueva_password: String,
+    ) -> Result<(), UsuarioError> {
+        // Se valida antes de canjear para no gastar el token en un intento fallido
+        self.politica_password.validar(&nueva_password)?;
+
+        let mut usuario = self
+            .canjear(&token, PropositoToken::RestablecerPassword)
+            .await?;
+
+        let hash = self
+            .passwords
+            .hashear(&nueva_password)
+            .map_err(UsuarioError::ErrorRepositorio)?;
+        usuario.actualizar_password(hash);
+        usuario.registrar_login_correcto();
+        self.repository.actualizar(&usuario).await
+    }
+
+    async fn solicitar_verificacion(&self, usuario_id: String) -> Result<(), UsuarioError> {
+        let usuario = self
+            .repository
+            .obtener(&usuario_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(usuario_id))?;
+
+        if usuario.email_verificado {
+            return Ok(());
+        }
+        self.emitir(&usuario, PropositoToken::VerificarEmail).await
+    }
+
+    async fn verificar_email(&self, token: String) -> Result<UsuarioPublico, UsuarioError> {
+        let mut usuario = self.canjear(&token, PropositoToken::VerificarEmail).await?;
+
+        usuario.marcar_email_verificado();
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(usuario.sin_password())
+    }
+
+    async fn solicitar_cambio_email(
+        &self,
+        usuario_id: String,
+        nuevo_email: String,
+        password: String,
+    ) -> Result<(), UsuarioError> {
+        let mut usuario = self
+            .repository
+            .obtener(&usuario_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(usuario_id))?;
+
+        // Las cuentas del SSO o del directorio reciben el email de allí
+        if usuario.identidad_externa.is_some() {
+            return Err(UsuarioError::EmailGestionadoExternamente);
+        }
+        if !PasswordService::verify_password(&password, &usuario.password_hash)
+            .map_err(UsuarioError::ErrorRepositorio)?
+        {
+            return Err(UsuarioError::CredencialesInvalidas);
+        }
+
+        usuario.solicitar_cambio_email(nuevo_email)?;
+        let nuevo_email = usuario.email_pendiente.clone().unwrap_or_default();
+        if nuevo_email == usuario.email {
+            return Ok(());
+        }
+        if self.repository.existe_email(&nuevo_email).await? {
+            return Err(UsuarioError::EmailDuplicado(nuevo_email));
+        }
+
+        self.repository.actualizar(&usuario).await?;
+        self.emitir(&usuario, PropositoToken::CambiarEmail).await
+    }
+
+    async fn confirmar_cambio_email(&self, token: String) -> Result<UsuarioPublico, UsuarioError> {
+        let mut usuario = self.canjear(&token, PropositoToken::CambiarEmail).await?;
+
+        // Otra cuenta ha podido quedarse con la dirección mientras tanto
+        let nuevo_email = usuario
+            .email_pendiente
+            .clone()
+            .ok_or(UsuarioError::TokenInvalido)?;
+        if self.repository.existe_email(&nuevo_email).await? {
+            return Err(UsuarioError::EmailDuplicado(nuevo_email));
+        }
+
+        let anterior = usuario
+            .confirmar_cambio_email()
+            .ok_or(UsuarioError::TokenInvalido)?;
+        self.repository.actualizar(&usuario).await?;
+
+        // El cambio ya está hecho aunque el aviso no salga
+        if let Err(e) = self
+            .correo
+            .enviar(&self.aviso_cambio_email(&usuario, anterior))
+            .await
+        {
+            tracing::warn!("No se pudo avisar del cambio de email: {}", e);
+        }
+
+        Ok(usuario.sin_password())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{MockTokenRepository, MockUsuarioRepository};
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+    use usuarios_domain::Rol;
+
+    #[derive(Default)]
+    struct MockCorreo {
+        enviados: RwLock<Vec<Correo>>,
+    }
+
+    impl MockCorreo {
+        /// Token del último correo enviado (lo que sigue a `token=`)
+        async fn ultimo_token(&self) -> String {
+            let enviados = self.enviados.read().await;
+            let cuerpo = &enviados
+                .last()
+                .expect("no se ha enviado ningún correo")
+                .cuerpo;
+            let inicio = cuerpo.find("token=").unwrap() + "token=".len();
+            cuerpo[inicio..inicio + 64].to_string()
+        }
+    }
+
+    #[async_trait]
+    impl EnvioCorreo for MockCorreo {
+        async fn enviar(&self, correo: &Correo) -> Result<(), UsuarioError> {
+            self.enviados.write().await.push(correo.clone());
+            Ok(())
+        }
+    }
+
+    type Servicio = CuentaServiceImpl<MockUsuarioRepository, MockTokenRepository, MockCorreo>;
+
+    fn servicio(usuario: Usuario) -> (Servicio, Arc<MockUsuarioRepository>, Arc<MockCorreo>) {
+        let repo = Arc::new(MockUsuarioRepository {
+            usuarios: RwLock::new(HashMap::from([(usuario.id.clone(), usuario)])),
+        });
+        let correo = Arc::new(MockCorreo::default());
+        let servicio = CuentaServiceImpl::new(
+            repo.clone(),
+            Arc::new(MockTokenRepository::default()),
+            correo.clone(),
+        )
+        .with_url_base("https://reservas.example.com/");
+        (servicio, repo, correo)
+    }
+
+    fn usuario() -> Usuario {
+        Usuario::new(
+            "Ana".to_string(),
+            "ana@example.com".to_string(),
+            PasswordService::hash_password("antigua123").unwrap(),
+            Rol::Usuario,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_restablecer_password_con_token_de_un_solo_uso() {
+        let mut usuario = usuario();
+        usuario.bloqueado_hasta = Some(Utc::now() + chrono::Duration::minutes(10));
+        let id = usuario.id.clone();
+        let (servicio, repo, correo) = servicio(usuario);
+
+        servicio
+            .solicitar_restablecimiento("ana@example.com".to_string())
+            .await
+            .unwrap();
+        let token = correo.ultimo_token().await;
+        assert!(correo.enviados.read().await[0]
+            .cuerpo
+            .contains("https://reservas.example.com/restablecer-password?token="));
+
+        servicio
+            .restablecer_password(token.clone(), "nueva12345".to_string())
+            .await
+            .unwrap();
+
+        let guardado = repo.obtener(&id).await.unwrap().unwrap();
+        assert!(PasswordService::verify_password("nueva12345", &guardado.password_hash).unwrap());
+        assert_eq!(guardado.bloqueado_hasta, None);
+
+        let repetido = servicio
+            .restablecer_password(token, "otra123456".to_string())
+            .await;
+        assert!(matches!(repetido, Err(UsuarioError::TokenInvalido)));
+    }
+
+    #[tokio::test]
+    async fn test_pedir_otro_token_invalida_el_anterior() {
+        let (servicio, _, correo) = servicio(usuario());
+
+        servicio
+            .solicitar_restablecimiento("ana@example.com".to_string())
+            .await
+            .unwrap();
+        let primero = correo.ultimo_token().await;
+        servicio
+            .solicitar_restablecimiento("ana@example.com".to_string())
+            .await
+            .unwrap();
+
+        let resultado = servicio
+            .restablecer_password(primero, "nueva12345".to_string())
+            .await;
+        assert!(matches!(resultado, Err(UsuarioError::TokenInvalido)));
+    }
+
+    #[tokio::test]
+    async fn test_email_desconocido_no_envia_nada_ni_falla() {
+        let (servicio, _, correo) = servicio(usuario());
+
+        servicio
+            .solicitar_restablecimiento("nadie@example.com".to_string())
+            .await
+            .unwrap();
+        assert!(correo.enviados.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verificar_email() {
+        let usuario = usuario();
+        let id = usuario.id.clone();
+        let (servicio, _, correo) = servicio(usuario);
+
+        servicio.solicitar_verificacion(id.clone()).await.unwrap();
+        let token = correo.ultimo_token().await;
+
+        // Un token de verificación no sirve para cambiar la contraseña
+        let cruzado = servicio
+            .restablecer_password(token.clone(), "nueva12345".to_string())
+            .await;
+        assert!(matches!(cruzado, Err(UsuarioError::TokenInvalido)));
+
+        let publico = servicio.verificar_email(token).await.unwrap();
+        assert!(publico.email_verificado);
+
+        servicio.solicitar_verificacion(id).await.unwrap();
+        assert_eq!(correo.enviados.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cambio_de_email_confirmado_desde_la_direccion_nueva() {
+        let mut usuario = usuario();
+        usuario.marcar_email_verificado();
+        let id = usuario.id.clone();
+        let (servicio, repo, correo) = servicio(usuario);
+
+        let sin_password = servicio
+            .solicitar_cambio_email(id.clone(), "ana@nueva.com".to_string(), "mala".to_string())
+            .await;
+        assert!(matches!(
+            sin_password,
+            Err(UsuarioError::CredencialesInvalidas)
+        ));
+
+        servicio
+            .solicitar_cambio_email(
+                id.clone(),
+                "ana@nueva.com".to_string(),
+                "antigua123".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(correo.enviados.read().await[0].para, "ana@nueva.com");
+        assert_eq!(
+            repo.obtener(&id).await.unwrap().unwrap().email,
+            "ana@example.com"
+        );
+
+        let token = correo.ultimo_token().await;
+        let publico = servicio
+            .confirmar_cambio_email(token.clone())
+            .await
+            .unwrap();
+        assert_eq!(publico.email, "ana@nueva.com");
+        assert!(publico.email_verificado);
+
+        // Se avisa a la dirección anterior y el enlace no vale otra vez
+        assert_eq!(correo.enviados.read().await[1].para, "ana@example.com");
+        assert!(matches!(
+            servicio.confirmar_cambio_email(token).await,
+            Err(UsuarioError::TokenInvalido)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cambio_de_email_rechazado_si_ya_existe_o_es_externo() {
+        let mut usuario = usuario();
+        let id = usuario.id.clone();
+        let (servicio, repo, _) = servicio(usuario.clone());
+        let otra = Usuario::new(
+            "Otra".to_string(),
+            "otra@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        repo.guardar(&otra).await.unwrap();
+
+        let duplicado = servicio
+            .solicitar_cambio_email(
+                id.clone(),
+                "otra@example.com".to_string(),
+                "antigua123".to_string(),
+            )
+            .await;
+        assert!(matches!(duplicado, Err(UsuarioError::EmailDuplicado(_))));
+
+        usuario.vincular_identidad_externa("https://sso.example.com|ana".to_string());
+        repo.actualizar(&usuario).await.unwrap();
+        let externo = servicio
+            .solicitar_cambio_email(id, "ana@nueva.com".to_string(), "antigua123".to_string())
+            .await;
+        assert!(matches!(
+            externo,
+            Err(UsuarioError::EmailGestionadoExternamente)
+        ));
+    }
+}
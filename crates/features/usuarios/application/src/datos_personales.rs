@@ -0,0 +1,504 @@
+use crate::perfil_service::Perfil;
+use crate::repository::{
+    ApiKeyRepository, GrupoRepository, TokenUsuarioRepository, UsuarioRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use usuarios_auth::{Autorizador, PasswordService};
+use usuarios_domain::{Permiso, Rol, Usuario, UsuarioError};
+use uuid::Uuid;
+
+/// Prefijo de los seudónimos que sustituyen al usuario en sus reservas
+pub const PREFIJO_SEUDONIMO: &str = "eliminado-";
+
+/// Reserva tal y como se entrega al exportar los datos de su usuario
+#[derive(Debug, Clone)]
+pub struct ReservaExportada {
+    pub id: String,
+    pub sala_id: String,
+    pub fecha_inicio: DateTime<Utc>,
+    pub fecha_fin: DateTime<Utc>,
+    /// `activa`, `cancelada` o `completada`
+    pub estado: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Port hacia las reservas de un usuario
+///
+/// Lo implementa la feature de reservas; así usuarios no depende de ella.
+#[async_trait]
+pub trait ReservasDeUsuario: Send + Sync {
+    /// Todas las reservas del usuario, en cualquier estado
+    async fn reservas(&self, usuario_id: &str) -> Result<Vec<ReservaExportada>, UsuarioError>;
+
+    /// Cancela las reservas activas que aún no han terminado notificando la
+    /// cancelación; devuelve cuántas
+    async fn cancelar_futuras(&self, usuario_id: &str) -> Result<usize, UsuarioError>;
+
+    /// Sustituye el usuario por el seudónimo en todas sus reservas, también en
+    /// las que hizo en nombre de otros; devuelve cuántas
+    async fn seudonimizar(&self, usuario_id: &str, seudonimo: &str) -> Result<usize, UsuarioError>;
+}
+
+/// Copia de los datos personales de un usuario ("descargar mis datos")
+#[derive(Debug, Clone)]
+pub struct ExportacionDatos {
+    pub perfil: Perfil,
+    pub reservas: Vec<ReservaExportada>,
+    pub generada: DateTime<Utc>,
+}
+
+/// Resultado de eliminar una cuenta
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuentaEliminada {
+    /// Reservas futuras que se han cancelado
+    pub reservas_canceladas: usize,
+    /// Reservas que se conservan con el usuario sustituido por un seudónimo
+    pub reservas_seudonimizadas: usize,
+}
+
+/// Port (interfaz) de los derechos sobre los datos personales (RGPD)
+#[async_trait]
+pub trait DatosPersonalesService: Send + Sync {
+    /// Exporta el perfil y todas las reservas del usuario
+    async fn exportar_datos(&self, usuario_id: String) -> Result<ExportacionDatos, UsuarioError>;
+
+    /// Elimina la propia cuenta
+    ///
+    /// Las cuentas locales confirman con la contraseña; las del SSO o del
+    /// directorio no tienen una propia y basta con la sesión.
+    async fn eliminar_cuenta(
+        &self,
+        usuario_id: String,
+        password: String,
+    ) -> Result<CuentaEliminada, UsuarioError>;
+
+    /// Elimina la cuenta de otro usuario (requiere `usuario:manage`)
+    async fn eliminar_usuario(
+        &self,
+        admin_id: String,
+        usuario_id: String,
+    ) -> Result<CuentaEliminada, UsuarioError>;
+}
+
+/// Implementación de la exportación y eliminación de cuentas
+///
+/// Al eliminar una cuenta se cancelan sus reservas futuras y el resto se
+/// conservan con un seudónimo en lugar del usuario, para que los informes de
+/// uso no cambien y ninguna reserva apunte a un usuario inexistente. Tampoco
+/// queda el usuario en grupos, delegaciones, tokens ni claves de API.
+pub struct DatosPersonalesServiceImpl<R: UsuarioRepository> {
+    repository: Arc<R>,
+    reservas: Arc<dyn ReservasDeUsuario>,
+    grupos: Arc<dyn GrupoRepository>,
+    tokens: Arc<dyn TokenUsuarioRepository>,
+    api_keys: Arc<dyn ApiKeyRepository>,
+}
+
+impl<R: UsuarioRepository> DatosPersonalesServiceImpl<R> {
+    pub fn new(
+        repository: Arc<R>,
+        reservas: Arc<dyn ReservasDeUsuario>,
+        grupos: Arc<dyn GrupoRepository>,
+        tokens: Arc<dyn TokenUsuarioRepository>,
+        api_keys: Arc<dyn ApiKeyRepository>,
+    ) -> Self {
+        Self {
+            repository,
+            reservas,
+            grupos,
+            tokens,
+            api_keys,
+        }
+    }
+
+    async fn usuario(&self, usuario_id: &str) -> Result<Usuario, UsuarioError> {
+        self.repository
+            .obtener(usuario_id)
+            .await?
+            .ok_or_else(|| UsuarioError::UsuarioNoEncontrado(usuario_id.to_string()))
+    }
+
+    /// Cancela, seudonimiza y borra; el usuario ya está comprobado
+    async fn eliminar(&self, usuario: &Usuario) -> Result<CuentaEliminada, UsuarioError> {
+        if usuario.rol == Rol::Admin && usuario.activo {
+            let otros_admins = self
+                .repository
+                .listar()
+                .await?
+                .iter()
+                .filter(|u| u.id != usuario.id && u.rol == Rol::Admin && u.activo)
+                .count();
+            if otros_admins == 0 {
+                return Err(UsuarioError::UltimoAdministrador);
+            }
+        }
+
+        // Primero las reservas: si algo falla la cuenta sigue existiendo y se
+        // puede volver a intentar
+        let reservas_canceladas = self.reservas.cancelar_futuras(&usuario.id).await?;
+        let seudonimo = format!("{}{}", PREFIJO_SEUDONIMO, Uuid::new_v4());
+        let reservas_seudonimizadas = self.reservas.seudonimizar(&usuario.id, &seudonimo).await?;
+
+        // Ninguna referencia al usuario debe sobrevivir a la cuenta
+        let grupos = self.grupos.retirar_miembro(&usuario.id).await?;
+        let delegaciones = self.repository.retirar_delegado(&usuario.id).await?;
+        let tokens = self.tokens.eliminar_de_usuario(&usuario.id).await?;
+        let api_keys = self
+            .api_keys
+            .seudonimizar_creador(&usuario.id, &seudonimo)
+            .await?;
+
+        self.repository.eliminar(&usuario.id).await?;
+        tracing::info!(
+            usuario_id = %usuario.id,
+            reservas_canceladas,
+            reservas_seudonimizadas,
+            grupos,
+            delegaciones,
+            tokens,
+            api_keys,
+            "Cuenta eliminada"
+        );
+
+        Ok(CuentaEliminada {
+            reservas_canceladas,
+            reservas_seudonimizadas,
+        })
+    }
+}
+
+#[async_trait]
+impl<R: UsuarioRepository> DatosPersonalesService for DatosPersonalesServiceImpl<R> {
+    async fn exportar_datos(&self, usuario_id: String) -> Result<ExportacionDatos, UsuarioError> {
+        let usuario = self.usuario(&usuario_id).await?;
+        let reservas = self.reservas.reservas(&usuario_id).await?;
+
+        Ok(ExportacionDatos {
+            perfil: Perfil::from(&usuario),
+            reservas,
+            generada: Utc::now(),
+        })
+    }
+
+    async fn eliminar_cuenta(
+        &self,
+        usuario_id: String,
+        password: String,
+    ) -> Result<CuentaEliminada, UsuarioError> {
+        let usuario = self.usuario(&usuario_id).await?;
+
+        if usuario.identidad_externa.is_none()
+            && !PasswordService::verify_password(&password, &usuario.password_hash)
+                .map_err(UsuarioError::ErrorRepositorio)?
+        {
+            return Err(UsuarioError::CredencialesInvalidas);
+        }
+
+        self.eliminar(&usuario).await
+    }
+
+    async fn eliminar_usuario(
+        &self,
+        admin_id: String,
+        usuario_id: String,
+    ) -> Result<CuentaEliminada, UsuarioError> {
+        let admin = self.usuario(&admin_id).await?;
+        if !Autorizador::puede(&admin, Permiso::GestionarUsuarios) {
+            return Err(UsuarioError::PermisosDenegados);
+        }
+
+        let usuario = self.usuario(&usuario_id).await?;
+        self.eliminar(&usuario).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{
+        MockApiKeyRepository, MockGrupoRepository, MockTokenRepository, MockUsuarioRepository,
+    };
+    use std::sync::Mutex;
+    use usuarios_domain::{ApiKey, Grupo, PropositoToken, TokenUsuario};
+
+    /// Reservas en memoria: `(usuario_id, futura)`
+    #[derive(Default)]
+    struct MockReservasDeUsuario {
+        reservas: Mutex<Vec<(String, bool)>>,
+        canceladas: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl ReservasDeUsuario for MockReservasDeUsuario {
+        async fn reservas(&self, usuario_id: &str) -> Result<Vec<ReservaExportada>, UsuarioError> {
+            let reservas = self.reservas.lock().unwrap();
+            Ok(reservas
+                .iter()
+                .enumerate()
+                .filter(|(_, (usuario, _))| usuario == usuario_id)
+                .map(|(i, _)| ReservaExportada {
+                    id: format!("r{}", i),
+                    sala_id: "sala1".to_string(),
+                    fecha_inicio: Utc::now(),
+                    fecha_fin: Utc::now(),
+                    estado: "activa".to_string(),
+                    created_at: Utc::now(),
+                })
+                .collect())
+        }
+
+        async fn cancelar_futuras(&self, usuario_id: &str) -> Result<usize, UsuarioError> {
+            let reservas = self.reservas.lock().unwrap();
+            let futuras = reservas
+                .iter()
+                .filter(|(usuario, futura)| usuario == usuario_id && *futura)
+                .count();
+            *self.canceladas.lock().unwrap() += futuras;
+            Ok(futuras)
+        }
+
+        async fn seudonimizar(
+            &self,
+            usuario_id: &str,
+            seudonimo: &str,
+        ) -> Result<usize, UsuarioError> {
+            let mut reservas = self.reservas.lock().unwrap();
+            let mut total = 0;
+            for (usuario, _) in reservas.iter_mut().filter(|(u, _)| u == usuario_id) {
+                *usuario = seudonimo.to_string();
+                total += 1;
+            }
+            Ok(total)
+        }
+    }
+
+    /// Almacenes que la eliminación de una cuenta debe limpiar
+    #[derive(Default)]
+    struct Almacenes {
+        grupos: Arc<MockGrupoRepository>,
+        tokens: Arc<MockTokenRepository>,
+        api_keys: Arc<MockApiKeyRepository>,
+    }
+
+    async fn crear(repo: &MockUsuarioRepository, email: &str, rol: Rol) -> Usuario {
+        let hash = PasswordService::hash_password("password123").unwrap();
+        let usuario = Usuario::new("Ana".to_string(), email.to_string(), hash, rol).unwrap();
+        repo.guardar(&usuario).await.unwrap();
+        usuario
+    }
+
+    fn servicio(
+        repo: Arc<MockUsuarioRepository>,
+        reservas: Arc<MockReservasDeUsuario>,
+    ) -> DatosPersonalesServiceImpl<MockUsuarioRepository> {
+        servicio_con(repo, reservas, &Almacenes::default())
+    }
+
+    fn servicio_con(
+        repo: Arc<MockUsuarioRepository>,
+        reservas: Arc<MockReservasDeUsuario>,
+        almacenes: &Almacenes,
+    ) -> DatosPersonalesServiceImpl<MockUsuarioRepository> {
+        DatosPersonalesServiceImpl::new(
+            repo,
+            reservas,
+            almacenes.grupos.clone(),
+            almacenes.tokens.clone(),
+            almacenes.api_keys.clone(),
+        )
+    }
+
+    /// Elimina la cuenta de Ana (usuaria normal) con su contraseña
+    async fn eliminar_ana(repo: Arc<MockUsuarioRepository>, ana: &Usuario, almacenes: &Almacenes) {
+        servicio_con(repo, Arc::new(MockReservasDeUsuario::default()), almacenes)
+            .eliminar_cuenta(ana.id.clone(), "password123".to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exportar_incluye_perfil_y_reservas() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let reservas = Arc::new(MockReservasDeUsuario::default());
+        reservas.reservas.lock().unwrap().extend([
+            (ana.id.clone(), true),
+            ("otro".to_string(), true),
+            (ana.id.clone(), false),
+        ]);
+
+        let exportacion = servicio(repo, reservas)
+            .exportar_datos(ana.id.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(exportacion.perfil.usuario.email, "ana@example.com");
+        assert_eq!(exportacion.reservas.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_cuenta_cancela_seudonimiza_y_borra() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let reservas = Arc::new(MockReservasDeUsuario::default());
+        reservas
+            .reservas
+            .lock()
+            .unwrap()
+            .extend([(ana.id.clone(), true), (ana.id.clone(), false)]);
+        let servicio = servicio(repo.clone(), reservas.clone());
+
+        assert!(matches!(
+            servicio
+                .eliminar_cuenta(ana.id.clone(), "incorrecta".to_string())
+                .await,
+            Err(UsuarioError::CredencialesInvalidas)
+        ));
+
+        let eliminada = servicio
+            .eliminar_cuenta(ana.id.clone(), "password123".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            eliminada,
+            CuentaEliminada {
+                reservas_canceladas: 1,
+                reservas_seudonimizadas: 2,
+            }
+        );
+        assert!(repo.obtener(&ana.id).await.unwrap().is_none());
+
+        // Ambas reservas conservan el mismo seudónimo, que no es el id original
+        let reservas = reservas.reservas.lock().unwrap();
+        assert!(reservas[0].0.starts_with(PREFIJO_SEUDONIMO));
+        assert_eq!(reservas[0].0, reservas[1].0);
+    }
+
+    #[tokio::test]
+    async fn test_no_se_elimina_al_unico_admin() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let admin = crear(&repo, "admin@example.com", Rol::Admin).await;
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let servicio = servicio(repo.clone(), Arc::new(MockReservasDeUsuario::default()));
+
+        assert!(matches!(
+            servicio
+                .eliminar_cuenta(admin.id.clone(), "password123".to_string())
+                .await,
+            Err(UsuarioError::UltimoAdministrador)
+        ));
+        assert!(matches!(
+            servicio
+                .eliminar_usuario(ana.id.clone(), admin.id.clone())
+                .await,
+            Err(UsuarioError::PermisosDenegados)
+        ));
+
+        servicio
+            .eliminar_usuario(admin.id.clone(), ana.id.clone())
+            .await
+            .unwrap();
+        assert!(repo.obtener(&ana.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_cuenta_la_quita_de_sus_grupos() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let almacenes = Almacenes::default();
+        let mut equipo = Grupo::new("Equipo".to_string(), "luis".to_string(), Utc::now()).unwrap();
+        equipo.agregar_miembro(&ana.id);
+        let solo_ana = Grupo::new("Solo Ana".to_string(), ana.id.clone(), Utc::now()).unwrap();
+        almacenes.grupos.guardar(&equipo).await.unwrap();
+        almacenes.grupos.guardar(&solo_ana).await.unwrap();
+
+        eliminar_ana(repo, &ana, &almacenes).await;
+
+        // También del grupo en el que era la única miembro
+        assert!(almacenes
+            .grupos
+            .listar_por_miembro(&ana.id)
+            .await
+            .unwrap()
+            .is_empty());
+        let equipo = almacenes.grupos.obtener(&equipo.id).await.unwrap().unwrap();
+        assert_eq!(equipo.miembros, vec!["luis".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_cuenta_la_quita_de_los_delegados() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let mut directora = crear(&repo, "directora@example.com", Rol::Usuario).await;
+        directora.autorizar_delegado(&ana.id).unwrap();
+        directora.autorizar_delegado("asistente").unwrap();
+        repo.actualizar(&directora).await.unwrap();
+
+        eliminar_ana(repo.clone(), &ana, &Almacenes::default()).await;
+
+        let directora = repo.obtener(&directora.id).await.unwrap().unwrap();
+        assert_eq!(directora.delegados, vec!["asistente".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_cuenta_borra_sus_tokens() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = crear(&repo, "ana@example.com", Rol::Usuario).await;
+        let almacenes = Almacenes::default();
+        for (hash, usuario_id) in [("h1", ana.id.as_str()), ("h2", "otro")] {
+            let token = TokenUsuario::new(
+                hash.to_string(),
+                usuario_id.to_string(),
+                PropositoToken::RestablecerPassword,
+                Utc::now(),
+            );
+            almacenes.tokens.guardar(&token).await.unwrap();
+        }
+
+        eliminar_ana(repo, &ana, &almacenes).await;
+
+        assert!(almacenes.tokens.obtener("h1").await.unwrap().is_none());
+        assert!(almacenes.tokens.obtener("h2").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_cuenta_conserva_las_claves_que_creo_seudonimizadas() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let admin = crear(&repo, "admin@example.com", Rol::Admin).await;
+        let ana = crear(&repo, "ana@example.com", Rol::Admin).await;
+        let almacenes = Almacenes::default();
+        for (hash, creada_por) in [("h1", ana.id.as_str()), ("h2", admin.id.as_str())] {
+            let clave = ApiKey::new(
+                "Pantallas".to_string(),
+                "rsk_0123456789",
+                hash.to_string(),
+                Vec::new(),
+                creada_por.to_string(),
+                Utc::now(),
+            )
+            .unwrap();
+            almacenes.api_keys.guardar(&clave).await.unwrap();
+        }
+
+        eliminar_ana(repo, &ana, &almacenes).await;
+
+        // Las pantallas y scripts siguen autenticando con la clave
+        let de_ana = almacenes
+            .api_keys
+            .obtener_por_hash("h1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(de_ana.es_valida());
+        assert!(de_ana.creada_por.starts_with(PREFIJO_SEUDONIMO));
+        let del_admin = almacenes
+            .api_keys
+            .obtener_por_hash("h2")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(del_admin.creada_por, admin.id);
+    }
+}
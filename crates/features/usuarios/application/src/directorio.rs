@@ -0,0 +1,297 @@
+use crate::identidad_externa::{IdentidadExterna, MapeoRoles};
+use crate::repository::UsuarioRepository;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use usuarios_domain::{Usuario, UsuarioError};
+
+/// Port (interfaz) de un directorio externo que verifica contraseñas
+///
+/// Las cuentas vinculadas a su emisor ya no usan la contraseña local: cada
+/// login se comprueba contra el directorio.
+#[async_trait]
+pub trait VerificadorCredenciales: Send + Sync {
+    /// Emisor de las identidades que devuelve (p. ej. la URL del servidor LDAP)
+    fn emisor(&self) -> &str;
+
+    /// Comprueba email y contraseña
+    ///
+    /// Devuelve `None` si el usuario no existe o la contraseña no es correcta;
+    /// un error solo si no se ha podido consultar el directorio.
+    async fn verificar(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Option<IdentidadExterna>, UsuarioError>;
+}
+
+/// Port (interfaz) de un directorio del que se sincronizan los usuarios
+#[async_trait]
+pub trait DirectorioUsuarios: VerificadorCredenciales {
+    /// Todos los usuarios que pueden entrar en el sistema
+    async fn listar_usuarios(&self) -> Result<Vec<IdentidadExterna>, UsuarioError>;
+}
+
+/// Resultado de una sincronización con el directorio
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResumenSincronizacion {
+    /// Cuentas con nombre, email o rol cambiados
+    pub actualizados: usize,
+    /// Cuentas desactivadas por haber desaparecido del directorio
+    pub desactivados: usize,
+}
+
+/// Sincroniza con el directorio las cuentas vinculadas a él
+///
+/// Solo toca las cuentas que ya entraron alguna vez con el directorio (las
+/// demás se dan de alta en su primer login). El directorio manda sobre su
+/// nombre, email y rol; quien deja de aparecer en él se desactiva. Una cuenta
+/// desactivada no se reactiva sola aunque vuelva a aparecer.
+pub struct SincronizacionDirectorio<R: UsuarioRepository, D: DirectorioUsuarios> {
+    repository: Arc<R>,
+    directorio: Arc<D>,
+    roles: MapeoRoles,
+}
+
+impl<R: UsuarioRepository, D: DirectorioUsuarios> SincronizacionDirectorio<R, D> {
+    pub fn new(repository: Arc<R>, directorio: Arc<D>, roles: MapeoRoles) -> Self {
+        Self {
+            repository,
+            directorio,
+            roles,
+        }
+    }
+
+    pub async fn sincronizar(&self) -> Result<ResumenSincronizacion, UsuarioError> {
+        let prefijo = format!("{}|", self.directorio.emisor());
+        let vinculados: Vec<Usuario> = self
+            .repository
+            .listar()
+            .await?
+            .into_iter()
+            .filter(|u| {
+                u.identidad_externa
+                    .as_deref()
+                    .is_some_and(|i| i.starts_with(&prefijo))
+            })
+            .collect();
+
+        let identidades: HashMap<String, IdentidadExterna> = self
+            .directorio
+            .listar_usuarios()
+            .await?
+            .into_iter()
+            .map(|identidad| (identidad.clave(), identidad))
+            .collect();
+
+        // Un directorio vacío es casi seguro un filtro mal configurado, no
+        // que se haya ido toda la plantilla: mejor no desactivar a nadie
+        if identidades.is_empty() && !vinculados.is_empty() {
+            return Err(UsuarioError::ProveedorIdentidadNoDisponible(
+                "el directorio no ha devuelto ningún usuario".to_string(),
+            ));
+        }
+
+        let mut resumen = ResumenSincronizacion::default();
+        for mut usuario in vinculados {
+            let clave = usuario.identidad_externa.clone().unwrap_or_default();
+            let cambiado = match identidades.get(&clave) {
+                Some(identidad) => self.actualizar(&mut usuario, identidad).await?,
+                None if usuario.activo => {
+                    usuario.desactivar();
+                    resumen.desactivados += 1;
+                    tracing::info!(
+                        "Usuario {} desactivado: ya no está en el directorio",
+                        usuario.id
+                    );
+                    true
+                }
+                None => false,
+            };
+
+            if cambiado {
+                self.repository.actualizar(&usuario).await?;
+                if usuario.activo {
+                    resumen.actualizados += 1;
+                }
+            }
+        }
+
+        Ok(resumen)
+    }
+
+    /// Copia en la cuenta los datos del directorio; devuelve si ha cambiado algo
+    async fn actualizar(
+        &self,
+        usuario: &mut Usuario,
+        identidad: &IdentidadExterna,
+    ) -> Result<bool, UsuarioError> {
+        let mut cambiado = false;
+
+        if let Some(nombre) = identidad.nombre.as_deref().map(str::trim) {
+            if nombre != usuario.nombre && usuario.actualizar_nombre(nombre.to_string()).is_ok() {
+                cambiado = true;
+            }
+        }
+
+        if let Some(email) = identidad.email.as_deref().map(str::trim) {
+            if email != usuario.email {
+                // Si el email ya es de otra cuenta se deja como estaba
+                let libre = self
+                    .repository
+                    .obtener_por_email(email)
+                    .await?
+                    .is_none_or(|otro| otro.id == usuario.id);
+                if libre && usuario.actualizar_email(email.to_string()).is_ok() {
+                    usuario.marcar_email_verificado();
+                    cambiado = true;
+                } else {
+                    tracing::warn!("No se pudo cambiar el email de {} a {}", usuario.id, email);
+                }
+            }
+        }
+
+        let rol = self.roles.rol_para(&identidad.grupos);
+        if rol != usuario.rol {
+            usuario.actualizar_rol(rol);
+            cambiado = true;
+        }
+
+        Ok(cambiado)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::MockUsuarioRepository;
+    use usuarios_domain::Rol;
+
+    const EMISOR: &str = "ldap://directorio.test";
+
+    /// Directorio en memoria
+    struct MockDirectorio {
+        usuarios: Vec<IdentidadExterna>,
+    }
+
+    #[async_trait]
+    impl VerificadorCredenciales for MockDirectorio {
+        fn emisor(&self) -> &str {
+            EMISOR
+        }
+
+        async fn verificar(
+            &self,
+            _email: &str,
+            _password: &str,
+        ) -> Result<Option<IdentidadExterna>, UsuarioError> {
+            Ok(None)
+        }
+    }
+
+    #[async_trait]
+    impl DirectorioUsuarios for MockDirectorio {
+        async fn listar_usuarios(&self) -> Result<Vec<IdentidadExterna>, UsuarioError> {
+            Ok(self.usuarios.clone())
+        }
+    }
+
+    fn identidad(sujeto: &str, email: &str, grupos: &[&str]) -> IdentidadExterna {
+        IdentidadExterna {
+            emisor: EMISOR.to_string(),
+            sujeto: sujeto.to_string(),
+            email: Some(email.to_string()),
+            email_verificado: true,
+            nombre: Some(format!("Empleado {}", sujeto)),
+            grupos: grupos.iter().map(|g| g.to_string()).collect(),
+        }
+    }
+
+    async fn vinculado(repo: &MockUsuarioRepository, sujeto: &str, email: &str) -> Usuario {
+        let mut usuario = Usuario::new(
+            format!("Empleado {}", sujeto),
+            email.to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        usuario.vincular_identidad_externa(format!("{}|{}", EMISOR, sujeto));
+        repo.guardar(&usuario).await.unwrap();
+        usuario
+    }
+
+    fn sincronizacion(
+        repo: Arc<MockUsuarioRepository>,
+        usuarios: Vec<IdentidadExterna>,
+    ) -> SincronizacionDirectorio<MockUsuarioRepository, MockDirectorio> {
+        SincronizacionDirectorio::new(
+            repo,
+            Arc::new(MockDirectorio { usuarios }),
+            MapeoRoles::parsear("recepcion=recepcionista").unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_actualiza_datos_y_desactiva_a_los_que_ya_no_estan() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = vinculado(&repo, "ana", "ana@empresa.com").await;
+        let luis = vinculado(&repo, "luis", "luis@empresa.com").await;
+        let sin_cambios = vinculado(&repo, "eva", "eva@empresa.com").await;
+        let local = Usuario::new(
+            "Local".to_string(),
+            "local@empresa.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        repo.guardar(&local).await.unwrap();
+
+        let sincronizacion = sincronizacion(
+            repo.clone(),
+            vec![
+                identidad("ana", "ana.garcia@empresa.com", &["recepcion"]),
+                identidad("eva", "eva@empresa.com", &[]),
+            ],
+        );
+        let resumen = sincronizacion.sincronizar().await.unwrap();
+
+        assert_eq!(
+            resumen,
+            ResumenSincronizacion {
+                actualizados: 1,
+                desactivados: 1
+            }
+        );
+        let ana = repo.obtener(&ana.id).await.unwrap().unwrap();
+        assert_eq!(ana.email, "ana.garcia@empresa.com");
+        assert!(ana.email_verificado);
+        assert_eq!(ana.rol, Rol::Recepcionista);
+        assert!(!repo.obtener(&luis.id).await.unwrap().unwrap().activo);
+        assert_eq!(
+            repo.obtener(&sin_cambios.id).await.unwrap().unwrap(),
+            sin_cambios
+        );
+        // Las cuentas locales no son cosa del directorio
+        assert!(repo.obtener(&local.id).await.unwrap().unwrap().activo);
+
+        // Una segunda pasada no cambia nada
+        assert_eq!(
+            sincronizacion.sincronizar().await.unwrap(),
+            ResumenSincronizacion::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_desactiva_a_nadie_si_el_directorio_viene_vacio() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let ana = vinculado(&repo, "ana", "ana@empresa.com").await;
+
+        let resultado = sincronizacion(repo.clone(), Vec::new()).sincronizar().await;
+
+        assert!(matches!(
+            resultado,
+            Err(UsuarioError::ProveedorIdentidadNoDisponible(_))
+        ));
+        assert!(repo.obtener(&ana.id).await.unwrap().unwrap().activo);
+    }
+}
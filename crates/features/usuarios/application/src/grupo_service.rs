@@ -0,0 +1,202 @@
+use crate::repository::{GrupoRepository, UsuarioRepository};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use usuarios_auth::GruposUsuarios;
+use usuarios_domain::{Grupo, UsuarioError};
+
+/// Port (interfaz) de la gestión de grupos de usuarios
+///
+/// Quién puede cambiar cada grupo lo decide `Autorizador::puede_gestionar_grupo`
+/// en los adaptadores, como con las reservas.
+#[async_trait]
+pub trait GrupoService: Send + Sync {
+    /// Crea un grupo con quien lo crea como primer miembro
+    async fn crear_grupo(&self, nombre: String, creado_por: String) -> Result<Grupo, UsuarioError>;
+
+    /// Obtiene un grupo por su ID
+    async fn obtener_grupo(&self, id: &str) -> Result<Grupo, UsuarioError>;
+
+    /// Lista todos los grupos por nombre
+    async fn listar_grupos(&self) -> Result<Vec<Grupo>, UsuarioError>;
+
+    /// Lista por nombre los grupos de los que es miembro el usuario
+    async fn grupos_de_usuario(&self, usuario_id: &str) -> Result<Vec<Grupo>, UsuarioError>;
+
+    /// Añade un usuario existente al grupo
+    async fn agregar_miembro(
+        &self,
+        grupo_id: &str,
+        usuario_id: &str,
+    ) -> Result<Grupo, UsuarioError>;
+
+    /// Quita un miembro del grupo; no puede quedarse vacío
+    async fn quitar_miembro(&self, grupo_id: &str, usuario_id: &str)
+        -> Result<Grupo, UsuarioError>;
+
+    /// Elimina el grupo; sus reservas quedan solo a nombre de quien las hizo
+    async fn eliminar_grupo(&self, id: &str) -> Result<(), UsuarioError>;
+}
+
+/// Implementación del servicio de grupos
+pub struct GrupoServiceImpl<G: GrupoRepository, U: UsuarioRepository> {
+    repository: Arc<G>,
+    usuarios: Arc<U>,
+}
+
+impl<G: GrupoRepository, U: UsuarioRepository> GrupoServiceImpl<G, U> {
+    pub fn new(repository: Arc<G>, usuarios: Arc<U>) -> Self {
+        Self {
+            repository,
+            usuarios,
+        }
+    }
+}
+
+fn por_nombre(mut grupos: Vec<Grupo>) -> Vec<Grupo> {
+    grupos.sort_by_key(|g| g.nombre.to_lowercase());
+    grupos
+}
+
+#[async_trait]
+impl<G: GrupoRepository, U: UsuarioRepository> GrupoService for GrupoServiceImpl<G, U> {
+    async fn crear_grupo(&self, nombre: String, creado_por: String) -> Result<Grupo, UsuarioError> {
+        let grupo = Grupo::new(nombre, creado_por, Utc::now())?;
+        self.repository.guardar(&grupo).await?;
+
+        tracing::info!("Grupo {} ({}) creado", grupo.id, grupo.nombre);
+        Ok(grupo)
+    }
+
+    async fn obtener_grupo(&self, id: &str) -> Result<Grupo, UsuarioError> {
+        self.repository
+            .obtener(id)
+            .await?
+            .ok_or_else(|| UsuarioError::GrupoNoEncontrado(id.to_string()))
+    }
+
+    async fn listar_grupos(&self) -> Result<Vec<Grupo>, UsuarioError> {
+        Ok(por_nombre(self.repository.listar().await?))
+    }
+
+    async fn grupos_de_usuario(&self, usuario_id: &str) -> Result<Vec<Grupo>, UsuarioError> {
+        Ok(por_nombre(
+            self.repository.listar_por_miembro(usuario_id).await?,
+        ))
+    }
+
+    async fn agregar_miembro(
+        &self,
+        grupo_id: &str,
+        usuario_id: &str,
+    ) -> Result<Grupo, UsuarioError> {
+        let mut grupo = self.obtener_grupo(grupo_id).await?;
+        if self.usuarios.obtener(usuario_id).await?.is_none() {
+            return Err(UsuarioError::UsuarioNoEncontrado(usuario_id.to_string()));
+        }
+
+        if grupo.agregar_miembro(usuario_id) {
+            self.repository.actualizar(&grupo).await?;
+        }
+        Ok(grupo)
+    }
+
+    async fn quitar_miembro(
+        &self,
+        grupo_id: &str,
+        usuario_id: &str,
+    ) -> Result<Grupo, UsuarioError> {
+        let mut grupo = self.obtener_grupo(grupo_id).await?;
+        grupo.quitar_miembro(usuario_id)?;
+        self.repository.actualizar(&grupo).await?;
+        Ok(grupo)
+    }
+
+    async fn eliminar_grupo(&self, id: &str) -> Result<(), UsuarioError> {
+        let grupo = self.obtener_grupo(id).await?;
+        self.repository.eliminar(&grupo.id).await?;
+
+        tracing::info!("Grupo {} ({}) eliminado", grupo.id, grupo.nombre);
+        Ok(())
+    }
+}
+
+/// Grupos del usuario para el `Autenticador` de REST y gRPC
+#[async_trait]
+impl<G: GrupoRepository, U: UsuarioRepository> GruposUsuarios for GrupoServiceImpl<G, U> {
+    async fn grupos_de(&self, usuario_id: &str) -> Result<Vec<String>, String> {
+        let grupos = self
+            .repository
+            .listar_por_miembro(usuario_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(grupos.into_iter().map(|g| g.id).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{MockGrupoRepository, MockUsuarioRepository};
+    use usuarios_domain::{Rol, Usuario};
+
+    async fn usuario(repo: &MockUsuarioRepository, nombre: &str) -> String {
+        let usuario = Usuario::new(
+            nombre.to_string(),
+            format!("{}@example.com", nombre.to_lowercase()),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        repo.guardar(&usuario).await.unwrap();
+        usuario.id
+    }
+
+    #[tokio::test]
+    async fn test_miembros_y_grupos_del_usuario() {
+        let usuarios = Arc::new(MockUsuarioRepository::default());
+        let ana = usuario(&usuarios, "Ana").await;
+        let luis = usuario(&usuarios, "Luis").await;
+        let servicio =
+            GrupoServiceImpl::new(Arc::new(MockGrupoRepository::default()), usuarios.clone());
+
+        let ventas = servicio
+            .crear_grupo("Ventas".to_string(), ana.clone())
+            .await
+            .unwrap();
+        let marketing = servicio
+            .crear_grupo("marketing".to_string(), ana.clone())
+            .await
+            .unwrap();
+        servicio.agregar_miembro(&ventas.id, &luis).await.unwrap();
+
+        let de_ana: Vec<_> = servicio
+            .grupos_de_usuario(&ana)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.nombre)
+            .collect();
+        assert_eq!(de_ana, ["marketing", "Ventas"]);
+        assert_eq!(
+            servicio.grupos_de(&luis).await.unwrap(),
+            vec![ventas.id.clone()]
+        );
+
+        assert!(matches!(
+            servicio.agregar_miembro(&ventas.id, "no-existe").await,
+            Err(UsuarioError::UsuarioNoEncontrado(_))
+        ));
+        assert!(matches!(
+            servicio.quitar_miembro(&marketing.id, &ana).await,
+            Err(UsuarioError::UltimoMiembroGrupo)
+        ));
+
+        servicio.eliminar_grupo(&ventas.id).await.unwrap();
+        assert!(servicio.grupos_de(&luis).await.unwrap().is_empty());
+        assert!(matches!(
+            servicio.obtener_grupo(&ventas.id).await,
+            Err(UsuarioError::GrupoNoEncontrado(_))
+        ));
+    }
+}
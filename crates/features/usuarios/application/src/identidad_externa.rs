@@ -0,0 +1,178 @@
+use crate::repository::UsuarioRepository;
+use usuarios_auth::{PasswordService, TokenService};
+use usuarios_domain::{validar_nombre, Rol, Usuario, UsuarioError};
+
+/// Identidad de un usuario tal como la devuelve un proveedor externo
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentidadExterna {
+    /// Quién la emite (el `issuer` de OIDC)
+    pub emisor: String,
+    /// Identificador estable del usuario en el proveedor
+    pub sujeto: String,
+    pub email: Option<String>,
+    /// Si el proveedor garantiza que el email es del usuario
+    pub email_verificado: bool,
+    pub nombre: Option<String>,
+    /// Grupos del usuario en el proveedor, para asignarle un rol
+    pub grupos: Vec<String>,
+}
+
+impl IdentidadExterna {
+    /// Clave con la que se vincula la cuenta local (`emisor|sujeto`)
+    pub fn clave(&self) -> String {
+        format!("{}|{}", self.emisor, self.sujeto)
+    }
+}
+
+/// Asignación de roles a partir de los grupos del proveedor
+///
+/// Las reglas se evalúan en orden y gana la primera cuyo grupo tenga el
+/// usuario; si no tiene ninguno, recibe el rol por defecto.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MapeoRoles {
+    reglas: Vec<(String, Rol)>,
+    por_defecto: Rol,
+}
+
+impl MapeoRoles {
+    pub fn new(reglas: Vec<(String, Rol)>) -> Self {
+        Self {
+            reglas,
+            por_defecto: Rol::default(),
+        }
+    }
+
+    /// Lee reglas con la forma `grupo=rol,grupo=rol`
+    ///
+    /// # Errores
+    /// Devuelve la regla que no se entiende
+    pub fn parsear(texto: &str) -> Result<Self, String> {
+        let reglas = texto
+            .split(',')
+            .map(str::trim)
+            .filter(|regla| !regla.is_empty())
+            .map(|regla| {
+                regla
+                    .rsplit_once('=')
+                    .and_then(|(grupo, rol)| {
+                        let rol = Rol::from_str_opt(rol.trim())?;
+                        Some((grupo.trim().to_string(), rol))
+                    })
+                    .filter(|(grupo, _)| !grupo.is_empty())
+                    .ok_or_else(|| regla.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(reglas))
+    }
+
+    /// Rol que corresponde a los grupos indicados
+    pub fn rol_para(&self, grupos: &[String]) -> Rol {
+        self.reglas
+            .iter()
+            .find(|(grupo, _)| grupos.contains(grupo))
+            .map(|(_, rol)| rol.clone())
+            .unwrap_or_else(|| self.por_defecto.clone())
+    }
+}
+
+/// Busca o da de alta el usuario local de una identidad externa
+///
+/// Primero se busca la cuenta ya vinculada; si no hay, una con el mismo
+/// email, que solo se vincula si el proveedor ha verificado ese email (si
+/// no, cualquiera podría apropiarse de una cuenta registrada con contraseña).
+/// Las cuentas nuevas reciben el rol de sus grupos y una contraseña
+/// aleatoria que nadie conoce. El rol solo se asigna en el alta: después lo
+/// gestiona un administrador como el de cualquier otra cuenta.
+pub(crate) async fn aprovisionar<R: UsuarioRepository>(
+    repository: &R,
+    identidad: &IdentidadExterna,
+    roles: &MapeoRoles,
+) -> Result<Usuario, UsuarioError> {
+    let clave = identidad.clave();
+    if let Some(usuario) = repository.obtener_por_identidad_externa(&clave).await? {
+        return Ok(usuario);
+    }
+
+    let email = identidad
+        .email
+        .as_deref()
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .ok_or_else(|| {
+            UsuarioError::IdentidadExternaRechazada("la identidad no incluye email".to_string())
+        })?;
+
+    if let Some(mut usuario) = repository.obtener_por_email(&email).await? {
+        if !identidad.email_verificado {
+            return Err(UsuarioError::EmailExternoNoVerificado(email));
+        }
+        usuario.vincular_identidad_externa(clave);
+        repository.actualizar(&usuario).await?;
+        tracing::info!("Usuario {} vinculado a {}", usuario.id, identidad.emisor);
+        return Ok(usuario);
+    }
+
+    let (password, _) = TokenService::generar();
+    let password_hash =
+        PasswordService::hash_password(&password).map_err(UsuarioError::ErrorRepositorio)?;
+
+    let mut usuario = Usuario::new(
+        nombre_para(identidad, &email),
+        email,
+        password_hash,
+        roles.rol_para(&identidad.grupos),
+    )?;
+    if identidad.email_verificado {
+        usuario.marcar_email_verificado();
+    }
+    usuario.vincular_identidad_externa(clave);
+    repository.guardar(&usuario).await?;
+
+    tracing::info!(
+        "Usuario {} dado de alta desde {} con rol {}",
+        usuario.id,
+        identidad.emisor,
+        usuario.rol.as_str()
+    );
+    Ok(usuario)
+}
+
+/// Nombre de la cuenta nueva: el del proveedor o, si no es válido, el email
+fn nombre_para(identidad: &IdentidadExterna, email: &str) -> String {
+    identidad
+        .nombre
+        .as_deref()
+        .map(str::trim)
+        .filter(|nombre| validar_nombre(nombre).is_ok())
+        .unwrap_or(email)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn el_mapeo_usa_la_primera_regla_que_coincide() {
+        let roles = MapeoRoles::parsear("it-admins=admin, recepcion=recepcionista").unwrap();
+
+        let grupos = vec!["recepcion".to_string(), "it-admins".to_string()];
+        assert_eq!(roles.rol_para(&grupos), Rol::Admin);
+        assert_eq!(
+            roles.rol_para(&["recepcion".to_string()]),
+            Rol::Recepcionista
+        );
+        assert_eq!(roles.rol_para(&[]), Rol::Usuario);
+    }
+
+    #[test]
+    fn el_mapeo_rechaza_reglas_mal_formadas() {
+        assert_eq!(MapeoRoles::parsear(""), Ok(MapeoRoles::default()));
+        assert_eq!(
+            MapeoRoles::parsear("it=admin,ventas"),
+            Err("ventas".to_string())
+        );
+        assert_eq!(MapeoRoles::parsear("it=jefe"), Err("it=jefe".to_string()));
+    }
+}
@@ -1,7 +1,45 @@
+pub mod api_key_service;
 pub mod auth_service;
+pub mod bloqueo;
+pub mod consulta;
+pub mod correo;
+pub mod cuenta_service;
+pub mod datos_personales;
+pub mod directorio;
+pub mod grupo_service;
+pub mod identidad_externa;
+pub mod oidc_service;
+pub mod perfil_service;
 pub mod repository;
+pub mod segundo_factor;
 pub mod usuario_service;
 
+#[cfg(test)]
+mod test_util;
+
+pub use api_key_service::{ApiKeyCreada, ApiKeyService, ApiKeyServiceImpl};
 pub use auth_service::{AuthService, AuthServiceImpl, LoginResponse, RegisterResponse};
-pub use repository::UsuarioRepository;
+pub use bloqueo::{LimitadorIntentosIp, PoliticaBloqueo};
+pub use consulta::{ConsultaUsuarios, OrdenUsuarios};
+pub use correo::{Correo, EnvioCorreo};
+pub use cuenta_service::{CuentaService, CuentaServiceImpl};
+pub use datos_personales::{
+    CuentaEliminada, DatosPersonalesService, DatosPersonalesServiceImpl, ExportacionDatos,
+    ReservaExportada, ReservasDeUsuario, PREFIJO_SEUDONIMO,
+};
+pub use directorio::{
+    DirectorioUsuarios, ResumenSincronizacion, SincronizacionDirectorio, VerificadorCredenciales,
+};
+pub use grupo_service::{GrupoService, GrupoServiceImpl};
+pub use identidad_externa::{IdentidadExterna, MapeoRoles};
+pub use oidc_service::{
+    InicioOidc, OidcService, OidcServiceImpl, ProveedorOidc, VALIDEZ_INICIO_OIDC,
+};
+pub use perfil_service::{Perfil, PerfilService, PerfilServiceImpl};
+pub use repository::{
+    ApiKeyRepository, GrupoRepository, TokenUsuarioRepository, UsuarioRepository,
+};
+pub use segundo_factor::{
+    AccesoSegundoFactor, InscripcionTotp, ResultadoLogin, SegundoFactorActivado, EMISOR_TOTP,
+};
 pub use usuario_service::{UsuarioService, UsuarioServiceImpl};
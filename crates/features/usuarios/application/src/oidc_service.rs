@@ -0,0 +1,386 @@
+use crate::identidad_externa::{aprovisionar, IdentidadExterna, MapeoRoles};
+use crate::repository::UsuarioRepository;
+use crate::segundo_factor::{resultado_login, ResultadoLogin};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use usuarios_auth::TokenService;
+use usuarios_domain::{PoliticaSegundoFactor, UsuarioError};
+
+/// Tiempo que tiene el usuario para volver del proveedor de identidad
+pub const VALIDEZ_INICIO_OIDC: Duration = Duration::minutes(10);
+
+/// A partir de cuántos inicios pendientes se purgan los caducados
+const PURGA_A_PARTIR_DE: usize = 1024;
+
+/// Máximo de inicios pendientes a la vez
+///
+/// Iniciar el login no requiere autenticación: sin límite cualquiera podría
+/// llenar la memoria durante la validez de los inicios. Al llegar al máximo
+/// se descarta el más antiguo; ese usuario tendrá que volver a empezar.
+const MAXIMO_PENDIENTES: usize = 4096;
+
+/// Port (interfaz) de un proveedor OpenID Connect
+#[async_trait]
+pub trait ProveedorOidc: Send + Sync {
+    /// URL del proveedor a la que se redirige al usuario para que se identifique
+    fn url_autorizacion(&self, state: &str, nonce: &str) -> String;
+
+    /// Canjea el código de autorización y valida el ID token recibido
+    ///
+    /// El ID token debe llevar el `nonce` con el que se inició el login.
+    async fn canjear_codigo(
+        &self,
+        codigo: &str,
+        nonce: &str,
+    ) -> Result<IdentidadExterna, UsuarioError>;
+}
+
+/// Inicio de un login con SSO
+#[derive(Debug, Clone)]
+pub struct InicioOidc {
+    /// URL del proveedor a la que redirigir al usuario
+    pub url: String,
+    /// Valor que el proveedor devuelve en la vuelta; el cliente debe
+    /// guardarlo (p. ej. en una cookie) para comprobar que es el mismo
+    pub state: String,
+}
+
+/// Port (interfaz) del login con un proveedor OpenID Connect
+#[async_trait]
+pub trait OidcService: Send + Sync {
+    /// Prepara la redirección al proveedor
+    fn iniciar_login(&self) -> InicioOidc;
+
+    /// Completa el login con el código que devuelve el proveedor
+    ///
+    /// Da de alta al usuario la primera vez que entra y emite el mismo JWT
+    /// que el login con contraseña, incluido el paso del segundo factor.
+    async fn completar_login(
+        &self,
+        codigo: &str,
+        state: &str,
+    ) -> Result<ResultadoLogin, UsuarioError>;
+}
+
+struct InicioPendiente {
+    nonce: String,
+    caduca: DateTime<Utc>,
+}
+
+/// Implementación del login con el flujo *authorization code* de OIDC
+pub struct OidcServiceImpl<R: UsuarioRepository, P: ProveedorOidc> {
+    repository: Arc<R>,
+    proveedor: Arc<P>,
+    roles: MapeoRoles,
+    segundo_factor: PoliticaSegundoFactor,
+    pendientes: Mutex<HashMap<String, InicioPendiente>>,
+}
+
+impl<R: UsuarioRepository, P: ProveedorOidc> OidcServiceImpl<R, P> {
+    pub fn new(repository: Arc<R>, proveedor: Arc<P>) -> Self {
+        Self {
+            repository,
+            proveedor,
+            roles: MapeoRoles::default(),
+            segundo_factor: PoliticaSegundoFactor::default(),
+            pendientes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cambia la asignación de roles a los usuarios nuevos
+    pub fn with_mapeo_roles(mut self, roles: MapeoRoles) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Roles que deben entrar con segundo factor (como en el login con contraseña)
+    pub fn with_politica_segundo_factor(mut self, politica: PoliticaSegundoFactor) -> Self {
+        self.segundo_factor = politica;
+        self
+    }
+
+    /// Recupera (y consume) el nonce de un inicio pendiente
+    fn consumir_inicio(&self, state: &str, ahora: DateTime<Utc>) -> Option<String> {
+        let mut pendientes = self.pendientes.lock().unwrap_or_else(|e| e.into_inner());
+        pendientes
+            .remove(state)
+            .filter(|inicio| inicio.caduca > ahora)
+            .map(|inicio| inicio.nonce)
+    }
+}
+
+#[async_trait]
+impl<R: UsuarioRepository, P: ProveedorOidc> OidcService for OidcServiceImpl<R, P> {
+    fn iniciar_login(&self) -> InicioOidc {
+        let ahora = Utc::now();
+        let (state, _) = TokenService::generar();
+        let (nonce, _) = TokenService::generar();
+        let url = self.proveedor.url_autorizacion(&state, &nonce);
+
+        let mut pendientes = self.pendientes.lock().unwrap_or_else(|e| e.into_inner());
+        if pendientes.len() >= PURGA_A_PARTIR_DE {
+            pendientes.retain(|_, inicio| inicio.caduca > ahora);
+        }
+        if pendientes.len() >= MAXIMO_PENDIENTES {
+            let mas_antiguo = pendientes
+                .iter()
+                .min_by_key(|(_, inicio)| inicio.caduca)
+                .map(|(state, _)| state.clone());
+            if let Some(state) = mas_antiguo {
+                pendientes.remove(&state);
+            }
+        }
+        pendientes.insert(
+            state.clone(),
+            InicioPendiente {
+                nonce,
+                caduca: ahora + VALIDEZ_INICIO_OIDC,
+            },
+        );
+
+        InicioOidc { url, state }
+    }
+
+    async fn completar_login(
+        &self,
+        codigo: &str,
+        state: &str,
+    ) -> Result<ResultadoLogin, UsuarioError> {
+        let nonce = self
+            .consumir_inicio(state, Utc::now())
+            .ok_or(UsuarioError::OidcEstadoInvalido)?;
+
+        let identidad = self.proveedor.canjear_codigo(codigo, &nonce).await?;
+        let usuario = aprovisionar(self.repository.as_ref(), &identidad, &self.roles).await?;
+
+        if !usuario.activo {
+            return Err(UsuarioError::CredencialesInvalidas);
+        }
+
+        resultado_login(&usuario, &self.segundo_factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth_service::LoginResponse;
+    use crate::test_util::MockUsuarioRepository;
+    use usuarios_auth::JwtService;
+    use usuarios_domain::{Rol, Usuario};
+
+    /// Proveedor que acepta el código `valido` y devuelve siempre la misma identidad
+    struct MockProveedor {
+        identidad: IdentidadExterna,
+        ultimo_nonce: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl ProveedorOidc for MockProveedor {
+        fn url_autorizacion(&self, state: &str, nonce: &str) -> String {
+            *self.ultimo_nonce.lock().unwrap() = Some(nonce.to_string());
+            format!("https://idp.test/authorize?state={}", state)
+        }
+
+        async fn canjear_codigo(
+            &self,
+            codigo: &str,
+            nonce: &str,
+        ) -> Result<IdentidadExterna, UsuarioError> {
+            let esperado = self.ultimo_nonce.lock().unwrap().clone();
+            if codigo != "valido" || esperado.as_deref() != Some(nonce) {
+                return Err(UsuarioError::IdentidadExternaRechazada(
+                    "código no válido".to_string(),
+                ));
+            }
+            Ok(self.identidad.clone())
+        }
+    }
+
+    fn identidad(email_verificado: bool) -> IdentidadExterna {
+        IdentidadExterna {
+            emisor: "https://idp.test".to_string(),
+            sujeto: "1234".to_string(),
+            email: Some("ana@empresa.com".to_string()),
+            email_verificado,
+            nombre: Some("Ana García".to_string()),
+            grupos: vec!["recepcion".to_string()],
+        }
+    }
+
+    fn servicio_con(
+        identidad: IdentidadExterna,
+    ) -> (
+        OidcServiceImpl<MockUsuarioRepository, MockProveedor>,
+        Arc<MockUsuarioRepository>,
+    ) {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let proveedor = Arc::new(MockProveedor {
+            identidad,
+            ultimo_nonce: Mutex::new(None),
+        });
+        let servicio = OidcServiceImpl::new(repo.clone(), proveedor)
+            .with_mapeo_roles(MapeoRoles::parsear("recepcion=recepcionista").unwrap());
+        (servicio, repo)
+    }
+
+    /// Login que no ha necesitado segundo factor
+    fn completado(resultado: ResultadoLogin) -> LoginResponse {
+        match resultado {
+            ResultadoLogin::Completado(login) => login,
+            otro => panic!("login sin completar: {:?}", otro),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_da_de_alta_al_usuario_la_primera_vez() {
+        let (servicio, repo) = servicio_con(identidad(true));
+
+        let inicio = servicio.iniciar_login();
+        assert!(inicio.url.contains(&inicio.state));
+        let login = completado(
+            servicio
+                .completar_login("valido", &inicio.state)
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(login.usuario.email, "ana@empresa.com");
+        assert_eq!(login.usuario.nombre, "Ana García");
+        assert_eq!(login.usuario.rol, Rol::Recepcionista);
+        assert!(login.usuario.email_verificado);
+        let claims = JwtService::validate_token(&login.token).unwrap();
+        assert_eq!(claims.sub, login.usuario.id);
+
+        // La segunda vez entra con la misma cuenta
+        let inicio = servicio.iniciar_login();
+        let otra = completado(
+            servicio
+                .completar_login("valido", &inicio.state)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(otra.usuario.id, login.usuario.id);
+        assert_eq!(repo.listar().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_el_state_solo_sirve_una_vez() {
+        let (servicio, _) = servicio_con(identidad(true));
+
+        let inicio = servicio.iniciar_login();
+        servicio
+            .completar_login("valido", &inicio.state)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            servicio.completar_login("valido", &inicio.state).await,
+            Err(UsuarioError::OidcEstadoInvalido)
+        ));
+        assert!(matches!(
+            servicio.completar_login("valido", "inventado").await,
+            Err(UsuarioError::OidcEstadoInvalido)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_los_inicios_pendientes_tienen_un_maximo() {
+        let (servicio, _) = servicio_con(identidad(true));
+
+        let primero = servicio.iniciar_login();
+        for _ in 0..MAXIMO_PENDIENTES {
+            servicio.iniciar_login();
+        }
+        let ultimo = servicio.iniciar_login();
+
+        assert_eq!(servicio.pendientes.lock().unwrap().len(), MAXIMO_PENDIENTES);
+        // El más antiguo se ha descartado; los recientes siguen valiendo
+        assert!(matches!(
+            servicio.completar_login("valido", &primero.state).await,
+            Err(UsuarioError::OidcEstadoInvalido)
+        ));
+        assert!(servicio
+            .completar_login("valido", &ultimo.state)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_vincula_cuentas_existentes_solo_con_email_verificado() {
+        let existente = Usuario::new(
+            "Ana".to_string(),
+            "ana@empresa.com".to_string(),
+            "hash".to_string(),
+            Rol::Admin,
+        )
+        .unwrap();
+
+        let (servicio, repo) = servicio_con(identidad(false));
+        repo.guardar(&existente).await.unwrap();
+        let inicio = servicio.iniciar_login();
+        assert!(matches!(
+            servicio.completar_login("valido", &inicio.state).await,
+            Err(UsuarioError::EmailExternoNoVerificado(_))
+        ));
+
+        let (servicio, repo) = servicio_con(identidad(true));
+        repo.guardar(&existente).await.unwrap();
+        let inicio = servicio.iniciar_login();
+        let login = completado(
+            servicio
+                .completar_login("valido", &inicio.state)
+                .await
+                .unwrap(),
+        );
+
+        // Conserva su cuenta y su rol
+        assert_eq!(login.usuario.id, existente.id);
+        assert_eq!(login.usuario.rol, Rol::Admin);
+        let vinculado = repo.obtener(&existente.id).await.unwrap().unwrap();
+        assert_eq!(
+            vinculado.identidad_externa.as_deref(),
+            Some("https://idp.test|1234")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rechaza_usuarios_desactivados() {
+        let (servicio, repo) = servicio_con(identidad(true));
+        let inicio = servicio.iniciar_login();
+        let login = completado(
+            servicio
+                .completar_login("valido", &inicio.state)
+                .await
+                .unwrap(),
+        );
+
+        let mut usuario = repo.obtener(&login.usuario.id).await.unwrap().unwrap();
+        usuario.desactivar();
+        repo.actualizar(&usuario).await.unwrap();
+
+        let inicio = servicio.iniciar_login();
+        assert!(matches!(
+            servicio.completar_login("valido", &inicio.state).await,
+            Err(UsuarioError::CredencialesInvalidas)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_la_politica_pide_el_segundo_factor_tambien_con_sso() {
+        let (servicio, _) = servicio_con(identidad(true));
+        let servicio = servicio
+            .with_politica_segundo_factor(PoliticaSegundoFactor::new(vec![Rol::Recepcionista]));
+
+        let inicio = servicio.iniciar_login();
+        assert!(matches!(
+            servicio.completar_login("valido", &inicio.state).await,
+            Ok(ResultadoLogin::SegundoFactor {
+                inscripcion_requerida: true,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,254 @@
+use crate::repository::UsuarioRepository;
+use async_trait::async_trait;
+use chrono_tz::Tz;
+use shared_errores::Idioma;
+use std::sync::Arc;
+use usuarios_domain::{Preferencias, Usuario, UsuarioError, UsuarioPublico};
+
+/// Perfil que ve y edita el propio usuario
+#[derive(Debug, Clone)]
+pub struct Perfil {
+    pub usuario: UsuarioPublico,
+    pub preferencias: Preferencias,
+    /// Email nuevo a la espera de que se confirme desde esa dirección
+    pub email_pendiente: Option<String>,
+    /// Usuarios autorizados a reservar en su nombre
+    pub delegados: Vec<String>,
+}
+
+impl From<&Usuario> for Perfil {
+    fn from(usuario: &Usuario) -> Self {
+        Self {
+            usuario: usuario.sin_password(),
+            preferencias: usuario.preferencias.clone(),
+            email_pendiente: usuario.email_pendiente.clone(),
+            delegados: usuario.delegados.clone(),
+        }
+    }
+}
+
+/// Port (interfaz) del autoservicio del perfil
+///
+/// Todas las operaciones son sobre el propio usuario; el cambio de email, que
+/// necesita enviar un correo, está en `CuentaService`.
+#[async_trait]
+pub trait PerfilService: Send + Sync {
+    /// Obtiene el perfil del usuario
+    async fn obtener_perfil(&self, usuario_id: String) -> Result<Perfil, UsuarioError>;
+
+    /// Cambia el nombre del usuario
+    async fn actualizar_nombre(
+        &self,
+        usuario_id: String,
+        nombre: String,
+    ) -> Result<Perfil, UsuarioError>;
+
+    /// Sustituye todas las preferencias: idioma, zona horaria y las del perfil
+    async fn actualizar_preferencias(
+        &self,
+        usuario_id: String,
+        idioma: Option<Idioma>,
+        zona_horaria: Option<Tz>,
+        preferencias: Preferencias,
+    ) -> Result<Perfil, UsuarioError>;
+
+    /// Autoriza a otro usuario existente a reservar en su nombre
+    async fn autorizar_delegado(
+        &self,
+        usuario_id: String,
+        delegado_id: String,
+    ) -> Result<Perfil, UsuarioError>;
+
+    /// Retira la autorización a reservar en su nombre
+    async fn revocar_delegado(
+        &self,
+        usuario_id: String,
+        delegado_id: String,
+    ) -> Result<Perfil, UsuarioError>;
+}
+
+/// Implementación del autoservicio del perfil
+pub struct PerfilServiceImpl<R: UsuarioRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: UsuarioRepository> PerfilServiceImpl<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    async fn usuario(&self, usuario_id: String) -> Result<Usuario, UsuarioError> {
+        self.repository
+            .obtener(&usuario_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(usuario_id))
+    }
+}
+
+#[async_trait]
+impl<R: UsuarioRepository> PerfilService for PerfilServiceImpl<R> {
+    async fn obtener_perfil(&self, usuario_id: String) -> Result<Perfil, UsuarioError> {
+        let usuario = self.usuario(usuario_id).await?;
+        Ok(Perfil::from(&usuario))
+    }
+
+    async fn actualizar_nombre(
+        &self,
+        usuario_id: String,
+        nombre: String,
+    ) -> Result<Perfil, UsuarioError> {
+        let mut usuario = self.usuario(usuario_id).await?;
+
+        usuario.actualizar_nombre(nombre)?;
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(Perfil::from(&usuario))
+    }
+
+    async fn actualizar_preferencias(
+        &self,
+        usuario_id: String,
+        idioma: Option<Idioma>,
+        zona_horaria: Option<Tz>,
+        preferencias: Preferencias,
+    ) -> Result<Perfil, UsuarioError> {
+        let mut usuario = self.usuario(usuario_id).await?;
+
+        usuario.actualizar_idioma(idioma);
+        usuario.actualizar_zona_horaria(zona_horaria);
+        usuario.actualizar_preferencias(preferencias);
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(Perfil::from(&usuario))
+    }
+
+    async fn autorizar_delegado(
+        &self,
+        usuario_id: String,
+        delegado_id: String,
+    ) -> Result<Perfil, UsuarioError> {
+        let mut usuario = self.usuario(usuario_id).await?;
+        let delegado_id = delegado_id.trim().to_string();
+        self.usuario(delegado_id.clone()).await?;
+
+        if usuario.autorizar_delegado(&delegado_id)? {
+            self.repository.actualizar(&usuario).await?;
+            tracing::info!(
+                "{} autoriza a {} a reservar en su nombre",
+                usuario.id,
+                delegado_id
+            );
+        }
+        Ok(Perfil::from(&usuario))
+    }
+
+    async fn revocar_delegado(
+        &self,
+        usuario_id: String,
+        delegado_id: String,
+    ) -> Result<Perfil, UsuarioError> {
+        let mut usuario = self.usuario(usuario_id).await?;
+
+        if usuario.revocar_delegado(&delegado_id) {
+            self.repository.actualizar(&usuario).await?;
+        }
+        Ok(Perfil::from(&usuario))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::MockUsuarioRepository;
+    use usuarios_domain::{Notificaciones, Rol};
+
+    #[tokio::test]
+    async fn test_actualizar_preferencias_sustituye_todas() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let usuario = Usuario::new(
+            "Ana".to_string(),
+            "ana@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        let id = usuario.id.clone();
+        repo.guardar(&usuario).await.unwrap();
+        let servicio = PerfilServiceImpl::new(repo.clone());
+
+        let preferencias = Preferencias::new(
+            Notificaciones {
+                cancelaciones: false,
+                ..Notificaciones::default()
+            },
+            Some("sala-1".to_string()),
+            None,
+        );
+        let perfil = servicio
+            .actualizar_preferencias(
+                id.clone(),
+                Some(Idioma::En),
+                Some(chrono_tz::Europe::Lisbon),
+                preferencias.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(perfil.usuario.idioma, Some(Idioma::En));
+        assert_eq!(perfil.preferencias, preferencias);
+
+        // Lo que no se envía vuelve al valor por defecto
+        let perfil = servicio
+            .actualizar_preferencias(id.clone(), None, None, Preferencias::default())
+            .await
+            .unwrap();
+        assert_eq!(perfil.usuario.zona_horaria, None);
+        assert_eq!(perfil.preferencias.sala_predeterminada, None);
+
+        let guardado = servicio.obtener_perfil(id).await.unwrap();
+        assert!(guardado.preferencias.notificaciones.cancelaciones);
+    }
+
+    #[tokio::test]
+    async fn test_autorizar_y_revocar_delegados() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let mut ids = Vec::new();
+        for nombre in ["Directora", "Asistente"] {
+            let usuario = Usuario::new(
+                nombre.to_string(),
+                format!("{}@example.com", nombre.to_lowercase()),
+                "hash".to_string(),
+                Rol::Usuario,
+            )
+            .unwrap();
+            ids.push(usuario.id.clone());
+            repo.guardar(&usuario).await.unwrap();
+        }
+        let (directora, asistente) = (ids[0].clone(), ids[1].clone());
+        let servicio = PerfilServiceImpl::new(repo.clone());
+
+        let perfil = servicio
+            .autorizar_delegado(directora.clone(), asistente.clone())
+            .await
+            .unwrap();
+        assert_eq!(perfil.delegados, vec![asistente.clone()]);
+
+        assert!(matches!(
+            servicio
+                .autorizar_delegado(directora.clone(), "no-existe".to_string())
+                .await,
+            Err(UsuarioError::UsuarioNoEncontrado(_))
+        ));
+        assert!(matches!(
+            servicio
+                .autorizar_delegado(directora.clone(), directora.clone())
+                .await,
+            Err(UsuarioError::DelegadoPropio)
+        ));
+
+        let perfil = servicio
+            .revocar_delegado(directora, asistente)
+            .await
+            .unwrap();
+        assert!(perfil.delegados.is_empty());
+    }
+}
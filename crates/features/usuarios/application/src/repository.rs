@@ -1,5 +1,7 @@
+use crate::consulta::ConsultaUsuarios;
 use async_trait::async_trait;
-use usuarios_domain::{Usuario, UsuarioError};
+use shared_paginacion::Pagina;
+use usuarios_domain::{ApiKey, Grupo, PropositoToken, TokenUsuario, Usuario, UsuarioError};
 
 /// Port (interfaz) del repositorio de usuarios
 #[async_trait]
@@ -16,6 +18,29 @@ pub trait UsuarioRepository: Send + Sync {
     /// Lista todos los usuarios
     async fn listar(&self) -> Result<Vec<Usuario>, UsuarioError>;
 
+    /// Obtiene el usuario vinculado a una identidad externa (`emisor|sujeto`)
+    ///
+    /// Por defecto busca sobre `listar`; solo se usa en los inicios de sesión
+    /// con SSO, así que los adaptadores pueden quedarse con esta versión.
+    async fn obtener_por_identidad_externa(
+        &self,
+        identidad: &str,
+    ) -> Result<Option<Usuario>, UsuarioError> {
+        Ok(self
+            .listar()
+            .await?
+            .into_iter()
+            .find(|u| u.identidad_externa.as_deref() == Some(identidad)))
+    }
+
+    /// Lista usuarios filtrados, ordenados y paginados
+    ///
+    /// Por defecto filtra en memoria sobre `listar`; los adaptadores que
+    /// puedan hacerlo sin cargar todos los usuarios deberían sobrescribirlo.
+    async fn buscar(&self, consulta: &ConsultaUsuarios) -> Result<Pagina<Usuario>, UsuarioError> {
+        Ok(consulta.aplicar(&self.listar().await?))
+    }
+
     /// Actualiza un usuario existente
     async fn actualizar(&self, usuario: &Usuario) -> Result<(), UsuarioError>;
 
@@ -24,4 +49,128 @@ pub trait UsuarioRepository: Send + Sync {
 
     /// Verifica si existe un usuario con el email dado
     async fn existe_email(&self, email: &str) -> Result<bool, UsuarioError>;
+
+    /// Quita al usuario de los delegados de todos los demás; devuelve de
+    /// cuántos lo era
+    ///
+    /// Por defecto recorre `listar`; solo se usa al eliminar una cuenta.
+    async fn retirar_delegado(&self, delegado_id: &str) -> Result<usize, UsuarioError> {
+        let mut total = 0;
+        for mut usuario in self.listar().await? {
+            if usuario.revocar_delegado(delegado_id) {
+                self.actualizar(&usuario).await?;
+                total += 1;
+            }
+        }
+        Ok(total)
+    }
+}
+
+/// Port (interfaz) del almacén de tokens de un solo uso
+#[async_trait]
+pub trait TokenUsuarioRepository: Send + Sync {
+    /// Guarda un token recién emitido
+    async fn guardar(&self, token: &TokenUsuario) -> Result<(), UsuarioError>;
+
+    /// Obtiene un token por su hash
+    async fn obtener(&self, hash: &str) -> Result<Option<TokenUsuario>, UsuarioError>;
+
+    /// Actualiza un token existente (p. ej. al canjearlo)
+    async fn actualizar(&self, token: &TokenUsuario) -> Result<(), UsuarioError>;
+
+    /// Marca como usados los tokens pendientes del usuario para ese propósito
+    async fn invalidar(
+        &self,
+        usuario_id: &str,
+        proposito: PropositoToken,
+    ) -> Result<(), UsuarioError>;
+
+    /// Borra todos los tokens del usuario, usados o no; devuelve cuántos
+    async fn eliminar_de_usuario(&self, usuario_id: &str) -> Result<usize, UsuarioError>;
+}
+
+/// Port (interfaz) del almacén de claves de API
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Guarda una clave recién creada
+    async fn guardar(&self, clave: &ApiKey) -> Result<(), UsuarioError>;
+
+    /// Obtiene una clave por su ID
+    async fn obtener(&self, id: &str) -> Result<Option<ApiKey>, UsuarioError>;
+
+    /// Obtiene una clave por el hash de la clave en claro
+    async fn obtener_por_hash(&self, hash: &str) -> Result<Option<ApiKey>, UsuarioError>;
+
+    /// Lista todas las claves, también las revocadas
+    async fn listar(&self) -> Result<Vec<ApiKey>, UsuarioError>;
+
+    /// Actualiza una clave existente (último uso, revocación)
+    async fn actualizar(&self, clave: &ApiKey) -> Result<(), UsuarioError>;
+
+    /// Sustituye al usuario por el seudónimo como creador de sus claves;
+    /// devuelve cuántas
+    ///
+    /// Las claves siguen funcionando: las integraciones no dependen de la
+    /// cuenta que las creó. Por defecto recorre `listar`; solo se usa al
+    /// eliminar una cuenta.
+    async fn seudonimizar_creador(
+        &self,
+        usuario_id: &str,
+        seudonimo: &str,
+    ) -> Result<usize, UsuarioError> {
+        let mut total = 0;
+        for mut clave in self.listar().await? {
+            if clave.seudonimizar(usuario_id, seudonimo) {
+                self.actualizar(&clave).await?;
+                total += 1;
+            }
+        }
+        Ok(total)
+    }
+}
+
+/// Port (interfaz) del almacén de grupos de usuarios
+#[async_trait]
+pub trait GrupoRepository: Send + Sync {
+    /// Guarda un grupo nuevo
+    async fn guardar(&self, grupo: &Grupo) -> Result<(), UsuarioError>;
+
+    /// Obtiene un grupo por su ID
+    async fn obtener(&self, id: &str) -> Result<Option<Grupo>, UsuarioError>;
+
+    /// Lista todos los grupos
+    async fn listar(&self) -> Result<Vec<Grupo>, UsuarioError>;
+
+    /// Lista los grupos de los que es miembro el usuario
+    ///
+    /// Por defecto filtra sobre `listar`; se consulta en cada petición
+    /// autenticada, así que conviene que los adaptadores lo hagan barato.
+    async fn listar_por_miembro(&self, usuario_id: &str) -> Result<Vec<Grupo>, UsuarioError> {
+        Ok(self
+            .listar()
+            .await?
+            .into_iter()
+            .filter(|g| g.es_miembro(usuario_id))
+            .collect())
+    }
+
+    /// Actualiza un grupo existente (miembros)
+    async fn actualizar(&self, grupo: &Grupo) -> Result<(), UsuarioError>;
+
+    /// Elimina un grupo por su ID
+    async fn eliminar(&self, id: &str) -> Result<(), UsuarioError>;
+
+    /// Quita al usuario de todos sus grupos; devuelve de cuántos
+    ///
+    /// A diferencia de `Grupo::quitar_miembro` también lo quita si es el
+    /// último: el grupo queda vacío y solo un administrador puede gestionar
+    /// sus reservas. Se usa al eliminar una cuenta.
+    async fn retirar_miembro(&self, usuario_id: &str) -> Result<usize, UsuarioError> {
+        let grupos = self.listar_por_miembro(usuario_id).await?;
+        for mut grupo in grupos.iter().cloned() {
+            grupo.miembros.retain(|m| m != usuario_id);
+            self.actualizar(&grupo).await?;
+        }
+        Ok(grupos.len())
+    }
 }
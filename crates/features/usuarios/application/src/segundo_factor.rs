@@ -0,0 +1,147 @@
+use crate::auth_service::LoginResponse;
+use chrono::{DateTime, Utc};
+use usuarios_auth::{JwtService, TotpService};
+use usuarios_domain::{PoliticaSegundoFactor, Usuario, UsuarioError};
+
+/// Nombre con el que aparece la cuenta en la app de autenticación
+pub const EMISOR_TOTP: &str = "Reservas";
+
+/// Resultado del login con contraseña (o con SSO)
+#[derive(Debug, Clone)]
+pub enum ResultadoLogin {
+    /// Sesión abierta
+    Completado(LoginResponse),
+    /// Falta el segundo factor
+    ///
+    /// El desafío se canjea con `verificar_segundo_factor` o, si
+    /// `inscripcion_requerida` (la política lo exige y la cuenta aún no lo
+    /// tiene), inscribiendo el segundo factor con él.
+    SegundoFactor {
+        desafio: String,
+        inscripcion_requerida: bool,
+    },
+}
+
+/// Quién inscribe el segundo factor
+#[derive(Debug, Clone)]
+pub enum AccesoSegundoFactor {
+    /// Un usuario con la sesión abierta (su id)
+    Sesion(String),
+    /// Un login al que la política obliga a inscribirse (su desafío)
+    Desafio(String),
+}
+
+/// Datos para dar de alta la cuenta en la app de autenticación
+#[derive(Debug, Clone)]
+pub struct InscripcionTotp {
+    /// Secreto en base32, para quien no pueda leer el QR
+    pub secreto: String,
+    /// URL `otpauth://` que se muestra como código QR
+    pub url: String,
+}
+
+/// Segundo factor recién activado
+#[derive(Debug, Clone)]
+pub struct SegundoFactorActivado {
+    /// Códigos de recuperación; es la única vez que se muestran
+    pub codigos_recuperacion: Vec<String>,
+    /// Sesión abierta, si se ha activado durante el login
+    pub login: Option<LoginResponse>,
+}
+
+/// Cierra un login ya verificado: abre la sesión o pide el segundo factor
+pub(crate) fn resultado_login(
+    usuario: &Usuario,
+    politica: &PoliticaSegundoFactor,
+) -> Result<ResultadoLogin, UsuarioError> {
+    if usuario.tiene_segundo_factor() || politica.exige(&usuario.rol) {
+        let desafio =
+            JwtService::generate_desafio(&usuario.id).map_err(UsuarioError::ErrorRepositorio)?;
+        return Ok(ResultadoLogin::SegundoFactor {
+            desafio,
+            inscripcion_requerida: !usuario.tiene_segundo_factor(),
+        });
+    }
+
+    let token = JwtService::generate_token_para(usuario).map_err(UsuarioError::ErrorRepositorio)?;
+    Ok(ResultadoLogin::Completado(LoginResponse {
+        token,
+        usuario: usuario.sin_password(),
+    }))
+}
+
+/// Comprueba un código de la app o uno de recuperación y lo gasta
+///
+/// El usuario cambia aunque el código no valga de nada más (último paso
+/// usado, códigos de recuperación que quedan): hay que guardarlo.
+pub(crate) fn gastar_codigo(usuario: &mut Usuario, codigo: &str, ahora: DateTime<Utc>) -> bool {
+    let Some(factor) = usuario.segundo_factor.as_mut().filter(|f| f.activo) else {
+        return false;
+    };
+
+    match TotpService::verificar(&factor.secreto, codigo, ahora) {
+        Some(paso) => factor.registrar_paso(paso),
+        None => factor.usar_codigo_recuperacion(&TotpService::hash_codigo_recuperacion(codigo)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use usuarios_domain::Rol;
+
+    fn con_segundo_factor(ahora: DateTime<Utc>) -> (Usuario, String) {
+        let mut usuario = Usuario::new(
+            "Admin".to_string(),
+            "admin@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Admin,
+        )
+        .unwrap();
+        let secreto = TotpService::generar_secreto();
+        usuario.iniciar_segundo_factor(secreto.clone());
+        usuario.activar_segundo_factor(vec![TotpService::hash_codigo_recuperacion("abcde-12345")]);
+        let codigo = TotpService::codigo(&secreto, TotpService::paso(ahora)).unwrap();
+        (usuario, codigo)
+    }
+
+    #[test]
+    fn test_cada_codigo_vale_una_sola_vez() {
+        let ahora = Utc::now();
+        let (mut usuario, codigo) = con_segundo_factor(ahora);
+
+        assert!(gastar_codigo(&mut usuario, &codigo, ahora));
+        assert!(!gastar_codigo(&mut usuario, &codigo, ahora));
+
+        assert!(gastar_codigo(&mut usuario, "ABCDE 12345", ahora));
+        assert!(!gastar_codigo(&mut usuario, "abcde-12345", ahora));
+    }
+
+    #[test]
+    fn test_pide_el_segundo_factor_o_la_inscripcion() {
+        let ahora = Utc::now();
+        let (mut usuario, _) = con_segundo_factor(ahora);
+        let politica = PoliticaSegundoFactor::para_admins();
+
+        assert!(matches!(
+            resultado_login(&usuario, &PoliticaSegundoFactor::default()),
+            Ok(ResultadoLogin::SegundoFactor {
+                inscripcion_requerida: false,
+                ..
+            })
+        ));
+
+        usuario.quitar_segundo_factor();
+        assert!(matches!(
+            resultado_login(&usuario, &politica),
+            Ok(ResultadoLogin::SegundoFactor {
+                inscripcion_requerida: true,
+                ..
+            })
+        ));
+        assert!(matches!(
+            resultado_login(&usuario, &PoliticaSegundoFactor::default()),
+            Ok(ResultadoLogin::Completado(_))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Repositorios en memoria compartidos por los tests de los servicios
+
+use crate::repository::{
+    ApiKeyRepository, GrupoRepository, TokenUsuarioRepository, UsuarioRepository,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use usuarios_domain::{ApiKey, Grupo, PropositoToken, TokenUsuario, Usuario, UsuarioError};
+
+#[derive(Default)]
+pub(crate) struct MockUsuarioRepository {
+    pub(crate) usuarios: RwLock<HashMap<String, Usuario>>,
+}
+
+#[async_trait]
+impl UsuarioRepository for MockUsuarioRepository {
+    async fn guardar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
+        let mut usuarios = self.usuarios.write().await;
+        usuarios.insert(usuario.id.clone(), usuario.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Usuario>, UsuarioError> {
+        Ok(self.usuarios.read().await.get(id).cloned())
+    }
+
+    async fn obtener_por_email(&self, email: &str) -> Result<Option<Usuario>, UsuarioError> {
+        let usuarios = self.usuarios.read().await;
+        Ok(usuarios.values().find(|u| u.email == email).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Usuario>, UsuarioError> {
+        Ok(self.usuarios.read().await.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
+        self.guardar(usuario).await
+    }
+
+    async fn eliminar(&self, id: &str) -> Result<(), UsuarioError> {
+        self.usuarios.write().await.remove(id);
+        Ok(())
+    }
+
+    async fn existe_email(&self, email: &str) -> Result<bool, UsuarioError> {
+        Ok(self.obtener_por_email(email).await?.is_some())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MockTokenRepository {
+    tokens: RwLock<HashMap<String, TokenUsuario>>,
+}
+
+#[async_trait]
+impl TokenUsuarioRepository for MockTokenRepository {
+    async fn guardar(&self, token: &TokenUsuario) -> Result<(), UsuarioError> {
+        let mut tokens = self.tokens.write().await;
+        tokens.insert(token.hash.clone(), token.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, hash: &str) -> Result<Option<TokenUsuario>, UsuarioError> {
+        Ok(self.tokens.read().await.get(hash).cloned())
+    }
+
+    async fn actualizar(&self, token: &TokenUsuario) -> Result<(), UsuarioError> {
+        self.guardar(token).await
+    }
+
+    async fn invalidar(
+        &self,
+        usuario_id: &str,
+        proposito: PropositoToken,
+    ) -> Result<(), UsuarioError> {
+        let mut tokens = self.tokens.write().await;
+        tokens
+            .values_mut()
+            .filter(|t| t.usuario_id == usuario_id && t.proposito == proposito)
+            .for_each(TokenUsuario::marcar_usado);
+        Ok(())
+    }
+
+    async fn eliminar_de_usuario(&self, usuario_id: &str) -> Result<usize, UsuarioError> {
+        let mut tokens = self.tokens.write().await;
+        let antes = tokens.len();
+        tokens.retain(|_, t| t.usuario_id != usuario_id);
+        Ok(antes - tokens.len())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MockApiKeyRepository {
+    claves: RwLock<HashMap<String, ApiKey>>,
+}
+
+#[async_trait]
+impl ApiKeyRepository for MockApiKeyRepository {
+    async fn guardar(&self, clave: &ApiKey) -> Result<(), UsuarioError> {
+        let mut claves = self.claves.write().await;
+        claves.insert(clave.id.clone(), clave.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<ApiKey>, UsuarioError> {
+        Ok(self.claves.read().await.get(id).cloned())
+    }
+
+    async fn obtener_por_hash(&self, hash: &str) -> Result<Option<ApiKey>, UsuarioError> {
+        let claves = self.claves.read().await;
+        Ok(claves.values().find(|c| c.hash == hash).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<ApiKey>, UsuarioError> {
+        Ok(self.claves.read().await.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, clave: &ApiKey) -> Result<(), UsuarioError> {
+        self.guardar(clave).await
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MockGrupoRepository {
+    grupos: RwLock<HashMap<String, Grupo>>,
+}
+
+#[async_trait]
+impl GrupoRepository for MockGrupoRepository {
+    async fn guardar(&self, grupo: &Grupo) -> Result<(), UsuarioError> {
+        let mut grupos = self.grupos.write().await;
+        grupos.insert(grupo.id.clone(), grupo.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Grupo>, UsuarioError> {
+        Ok(self.grupos.read().await.get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Grupo>, UsuarioError> {
+        Ok(self.grupos.read().await.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, grupo: &Grupo) -> Result<(), UsuarioError> {
+        self.guardar(grupo).await
+    }
+
+    async fn eliminar(&self, id: &str) -> Result<(), UsuarioError> {
+        self.grupos.write().await.remove(id);
+        Ok(())
+    }
+}
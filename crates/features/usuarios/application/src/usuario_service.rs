@@ -1,7 +1,12 @@
+use crate::consulta::ConsultaUsuarios;
 use crate::repository::UsuarioRepository;
 use async_trait::async_trait;
+use chrono_tz::Tz;
+use shared_errores::Idioma;
+use shared_paginacion::Pagina;
 use std::sync::Arc;
-use usuarios_domain::{Rol, UsuarioError, UsuarioPublico};
+use usuarios_auth::Autorizador;
+use usuarios_domain::{Permiso, Rol, UsuarioError, UsuarioPublico};
 
 /// Port (interfaz) del servicio de gestión de usuarios
 #[async_trait]
@@ -12,6 +17,12 @@ pub trait UsuarioService: Send + Sync {
     /// Lista todos los usuarios
     async fn listar_usuarios(&self) -> Result<Vec<UsuarioPublico>, UsuarioError>;
 
+    /// Lista usuarios con filtros, orden y paginación
+    async fn buscar_usuarios(
+        &self,
+        consulta: ConsultaUsuarios,
+    ) -> Result<Pagina<UsuarioPublico>, UsuarioError>;
+
     /// Actualiza el nombre de un usuario
     async fn actualizar_nombre(
         &self,
@@ -19,7 +30,21 @@ pub trait UsuarioService: Send + Sync {
         nuevo_nombre: String,
     ) -> Result<UsuarioPublico, UsuarioError>;
 
-    /// Actualiza el rol de un usuario (solo admins)
+    /// Cambia el idioma preferido del propio usuario
+    async fn actualizar_idioma(
+        &self,
+        user_id: String,
+        idioma: Option<Idioma>,
+    ) -> Result<UsuarioPublico, UsuarioError>;
+
+    /// Cambia la zona horaria del perfil del propio usuario
+    async fn actualizar_zona_horaria(
+        &self,
+        user_id: String,
+        zona_horaria: Option<Tz>,
+    ) -> Result<UsuarioPublico, UsuarioError>;
+
+    /// Actualiza el rol de un usuario (requiere `usuario:manage`)
     async fn actualizar_rol(
         &self,
         admin_id: String,
@@ -27,14 +52,22 @@ pub trait UsuarioService: Send + Sync {
         nuevo_rol: Rol,
     ) -> Result<UsuarioPublico, UsuarioError>;
 
-    /// Desactiva un usuario (solo admins)
+    /// Sustituye las salas que gestiona un usuario (requiere `usuario:manage`)
+    async fn asignar_salas_gestionadas(
+        &self,
+        admin_id: String,
+        user_id: String,
+        salas: Vec<String>,
+    ) -> Result<UsuarioPublico, UsuarioError>;
+
+    /// Desactiva un usuario (requiere `usuario:manage`)
     async fn desactivar_usuario(
         &self,
         admin_id: String,
         user_id: String,
     ) -> Result<(), UsuarioError>;
 
-    /// Activa un usuario (solo admins)
+    /// Activa un usuario (requiere `usuario:manage`)
     async fn activar_usuario(&self, admin_id: String, user_id: String) -> Result<(), UsuarioError>;
 }
 
@@ -48,7 +81,7 @@ impl<R: UsuarioRepository> UsuarioServiceImpl<R> {
         Self { repository }
     }
 
-    /// Verifica que un usuario sea admin
+    /// Verifica que quien llama pueda administrar usuarios
     async fn verificar_admin(&self, admin_id: &str) -> Result<(), UsuarioError> {
         let admin = self
             .repository
@@ -56,7 +89,7 @@ impl<R: UsuarioRepository> UsuarioServiceImpl<R> {
             .await?
             .ok_or(UsuarioError::UsuarioNoEncontrado(admin_id.to_string()))?;
 
-        if !admin.es_admin() {
+        if !Autorizador::puede(&admin, Permiso::GestionarUsuarios) {
             return Err(UsuarioError::PermisosDenegados);
         }
 
@@ -81,6 +114,14 @@ impl<R: UsuarioRepository> UsuarioService for UsuarioServiceImpl<R> {
         Ok(usuarios.iter().map(|u| u.sin_password()).collect())
     }
 
+    async fn buscar_usuarios(
+        &self,
+        consulta: ConsultaUsuarios,
+    ) -> Result<Pagina<UsuarioPublico>, UsuarioError> {
+        let pagina = self.repository.buscar(&consulta).await?;
+        Ok(pagina.map(|u| u.sin_password()))
+    }
+
     async fn actualizar_nombre(
         &self,
         user_id: String,
@@ -98,13 +139,47 @@ impl<R: UsuarioRepository> UsuarioService for UsuarioServiceImpl<R> {
         Ok(usuario.sin_password())
     }
 
+    async fn actualizar_idioma(
+        &self,
+        user_id: String,
+        idioma: Option<Idioma>,
+    ) -> Result<UsuarioPublico, UsuarioError> {
+        let mut usuario = self
+            .repository
+            .obtener(&user_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(user_id))?;
+
+        usuario.actualizar_idioma(idioma);
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(usuario.sin_password())
+    }
+
+    async fn actualizar_zona_horaria(
+        &self,
+        user_id: String,
+        zona_horaria: Option<Tz>,
+    ) -> Result<UsuarioPublico, UsuarioError> {
+        let mut usuario = self
+            .repository
+            .obtener(&user_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(user_id))?;
+
+        usuario.actualizar_zona_horaria(zona_horaria);
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(usuario.sin_password())
+    }
+
     async fn actualizar_rol(
         &self,
         admin_id: String,
         user_id: String,
         nuevo_rol: Rol,
     ) -> Result<UsuarioPublico, UsuarioError> {
-        // Verificar que quien llama sea admin
+        // Verificar que quien llama pueda administrar usuarios
         self.verificar_admin(&admin_id).await?;
 
         // Obtener usuario a actualizar
@@ -121,12 +196,32 @@ impl<R: UsuarioRepository> UsuarioService for UsuarioServiceImpl<R> {
         Ok(usuario.sin_password())
     }
 
+    async fn asignar_salas_gestionadas(
+        &self,
+        admin_id: String,
+        user_id: String,
+        salas: Vec<String>,
+    ) -> Result<UsuarioPublico, UsuarioError> {
+        self.verificar_admin(&admin_id).await?;
+
+        let mut usuario = self
+            .repository
+            .obtener(&user_id)
+            .await?
+            .ok_or(UsuarioError::UsuarioNoEncontrado(user_id))?;
+
+        usuario.asignar_salas_gestionadas(salas);
+        self.repository.actualizar(&usuario).await?;
+
+        Ok(usuario.sin_password())
+    }
+
     async fn desactivar_usuario(
         &self,
         admin_id: String,
         user_id: String,
     ) -> Result<(), UsuarioError> {
-        // Verificar que quien llama sea admin
+        // Verificar que quien llama pueda administrar usuarios
         self.verificar_admin(&admin_id).await?;
 
         // No permitir que un admin se desactive a sí mismo
@@ -150,7 +245,7 @@ impl<R: UsuarioRepository> UsuarioService for UsuarioServiceImpl<R> {
     }
 
     async fn activar_usuario(&self, admin_id: String, user_id: String) -> Result<(), UsuarioError> {
-        // Verificar que quien llama sea admin
+        // Verificar que quien llama pueda administrar usuarios
         self.verificar_admin(&admin_id).await?;
 
         // Obtener y activar usuario
@@ -170,65 +265,11 @@ impl<R: UsuarioRepository> UsuarioService for UsuarioServiceImpl<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
-    use tokio::sync::RwLock;
+    use crate::test_util::MockUsuarioRepository;
+    use shared_paginacion::Paginacion;
     use usuarios_auth::PasswordService;
     use usuarios_domain::Usuario;
 
-    // Mock repository
-    struct MockUsuarioRepository {
-        usuarios: Arc<RwLock<HashMap<String, Usuario>>>,
-    }
-
-    impl MockUsuarioRepository {
-        fn new() -> Self {
-            Self {
-                usuarios: Arc::new(RwLock::new(HashMap::new())),
-            }
-        }
-    }
-
-    #[async_trait]
-    impl UsuarioRepository for MockUsuarioRepository {
-        async fn guardar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.insert(usuario.id.clone(), usuario.clone());
-            Ok(())
-        }
-
-        async fn obtener(&self, id: &str) -> Result<Option<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.get(id).cloned())
-        }
-
-        async fn obtener_por_email(&self, email: &str) -> Result<Option<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().find(|u| u.email == email).cloned())
-        }
-
-        async fn listar(&self) -> Result<Vec<Usuario>, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().cloned().collect())
-        }
-
-        async fn actualizar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.insert(usuario.id.clone(), usuario.clone());
-            Ok(())
-        }
-
-        async fn eliminar(&self, id: &str) -> Result<(), UsuarioError> {
-            let mut usuarios = self.usuarios.write().await;
-            usuarios.remove(id);
-            Ok(())
-        }
-
-        async fn existe_email(&self, email: &str) -> Result<bool, UsuarioError> {
-            let usuarios = self.usuarios.read().await;
-            Ok(usuarios.values().any(|u| u.email == email))
-        }
-    }
-
     async fn crear_usuario_test(
         repo: &Arc<MockUsuarioRepository>,
         nombre: &str,
@@ -243,7 +284,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_listar_usuarios() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = UsuarioServiceImpl::new(repo.clone());
 
         // Crear usuarios
@@ -254,9 +295,66 @@ mod tests {
         assert_eq!(usuarios.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_buscar_usuarios_paginados() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = UsuarioServiceImpl::new(repo.clone());
+
+        crear_usuario_test(&repo, "User 1", "user1@test.com", Rol::Usuario).await;
+        crear_usuario_test(&repo, "User 2", "user2@test.com", Rol::Usuario).await;
+        crear_usuario_test(&repo, "Admin", "admin@test.com", Rol::Admin).await;
+
+        let consulta = ConsultaUsuarios {
+            rol: Some(Rol::Usuario),
+            paginacion: Paginacion::new(0, 1),
+            ..Default::default()
+        };
+        let pagina = service.buscar_usuarios(consulta).await.unwrap();
+        assert_eq!(pagina.total, 2);
+        assert_eq!(pagina.elementos[0].nombre, "User 1");
+        assert_eq!(pagina.siguiente_offset(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_actualizar_idioma() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = UsuarioServiceImpl::new(repo.clone());
+
+        let usuario = crear_usuario_test(&repo, "User", "user@test.com", Rol::Usuario).await;
+
+        let actualizado = service
+            .actualizar_idioma(usuario.id.clone(), Some(Idioma::En))
+            .await
+            .unwrap();
+        assert_eq!(actualizado.idioma, Some(Idioma::En));
+
+        let guardado = repo.obtener(&usuario.id).await.unwrap().unwrap();
+        assert_eq!(guardado.idioma, Some(Idioma::En));
+    }
+
+    #[tokio::test]
+    async fn test_actualizar_zona_horaria() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = UsuarioServiceImpl::new(repo.clone());
+
+        let usuario = crear_usuario_test(&repo, "User", "user@test.com", Rol::Usuario).await;
+
+        let actualizado = service
+            .actualizar_zona_horaria(usuario.id.clone(), Some(chrono_tz::America::Mexico_City))
+            .await
+            .unwrap();
+        assert_eq!(
+            actualizado.zona_horaria,
+            Some(chrono_tz::America::Mexico_City)
+        );
+
+        let guardado = repo.obtener(&usuario.id).await.unwrap().unwrap();
+        assert_eq!(guardado.zona_horaria, Some(chrono_tz::America::Mexico_City));
+    }
+
     #[tokio::test]
     async fn test_actualizar_rol_como_admin() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = UsuarioServiceImpl::new(repo.clone());
 
         let admin = crear_usuario_test(&repo, "Admin", "admin@test.com", Rol::Admin).await;
@@ -274,7 +372,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_actualizar_rol_sin_permisos() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = UsuarioServiceImpl::new(repo.clone());
 
         let user1 = crear_usuario_test(&repo, "User 1", "user1@test.com", Rol::Usuario).await;
@@ -286,9 +384,32 @@ mod tests {
         assert!(matches!(result, Err(UsuarioError::PermisosDenegados)));
     }
 
+    #[tokio::test]
+    async fn test_asignar_salas_gestionadas() {
+        let repo = Arc::new(MockUsuarioRepository::default());
+        let service = UsuarioServiceImpl::new(repo.clone());
+
+        let admin = crear_usuario_test(&repo, "Admin", "admin@test.com", Rol::Admin).await;
+        let gestor = crear_usuario_test(&repo, "Gestor", "gestor@test.com", Rol::GestorSalas).await;
+        let recepcion =
+            crear_usuario_test(&repo, "Recepción", "recepcion@test.com", Rol::Recepcionista).await;
+
+        let actualizado = service
+            .asignar_salas_gestionadas(admin.id, gestor.id.clone(), vec!["sala-1".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(actualizado.salas_gestionadas, ["sala-1"]);
+
+        // Gestionar reservas no da permiso para administrar usuarios
+        let result = service
+            .asignar_salas_gestionadas(recepcion.id, gestor.id, Vec::new())
+            .await;
+        assert!(matches!(result, Err(UsuarioError::PermisosDenegados)));
+    }
+
     #[tokio::test]
     async fn test_desactivar_usuario() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = UsuarioServiceImpl::new(repo.clone());
 
         let admin = crear_usuario_test(&repo, "Admin", "admin@test.com", Rol::Admin).await;
@@ -308,7 +429,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_admin_no_puede_desactivarse_a_si_mismo() {
-        let repo = Arc::new(MockUsuarioRepository::new());
+        let repo = Arc::new(MockUsuarioRepository::default());
         let service = UsuarioServiceImpl::new(repo.clone());
 
         let admin = crear_usuario_test(&repo, "Admin", "admin@test.com", Rol::Admin).await;
@@ -0,0 +1,456 @@
+use crate::autorizacion::PermisoDenegado;
+use crate::jwt::JwtService;
+use crate::principal::Principal;
+use crate::token::TokenService;
+use async_trait::async_trait;
+use shared_errores::{Categoria, ErrorCampo, ErrorDominio, Idioma};
+use std::fmt;
+use std::sync::Arc;
+use usuarios_domain::{ApiKey, PoliticaSegundoFactor, Usuario};
+
+/// Consulta de los datos actuales de un usuario
+///
+/// La implementa la capa de aplicación sobre el repositorio de usuarios.
+#[async_trait]
+pub trait EstadoUsuarios: Send + Sync {
+    /// Usuario con ese id, o `None` si ya no existe
+    async fn usuario_actual(&self, id: &str) -> Result<Option<Usuario>, String>;
+}
+
+/// Consulta de las claves de API de las cuentas de servicio
+///
+/// La implementa la capa de aplicación sobre el repositorio de claves.
+#[async_trait]
+pub trait EstadoApiKeys: Send + Sync {
+    /// Clave vigente con ese hash, anotando que se ha usado; `None` si no
+    /// existe o está revocada
+    async fn usar_api_key(&self, hash: &str) -> Result<Option<ApiKey>, String>;
+}
+
+/// Consulta de los grupos de cada usuario
+///
+/// La implementa la capa de aplicación sobre el repositorio de grupos.
+#[async_trait]
+pub trait GruposUsuarios: Send + Sync {
+    /// IDs de los grupos de los que es miembro
+    async fn grupos_de(&self, usuario_id: &str) -> Result<Vec<String>, String>;
+}
+
+/// Errores de autenticación
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorAutenticacion {
+    TokenRequerido,
+    FormatoInvalido,
+    TokenInvalido,
+    RolInvalido,
+    /// La cuenta del token se ha desactivado o eliminado
+    UsuarioInactivo,
+    /// No se pudo consultar el estado actual del usuario
+    EstadoNoDisponible,
+    /// La clave de API no existe o está revocada
+    ApiKeyInvalida,
+    /// El rol exige segundo factor y el token no pasó por él
+    SegundoFactorRequerido,
+    PermisoDenegado(PermisoDenegado),
+}
+
+impl From<PermisoDenegado> for ErrorAutenticacion {
+    fn from(error: PermisoDenegado) -> Self {
+        ErrorAutenticacion::PermisoDenegado(error)
+    }
+}
+
+impl fmt::Display for ErrorAutenticacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje_en(Idioma::Es))
+    }
+}
+
+impl std::error::Error for ErrorAutenticacion {}
+
+impl ErrorDominio for ErrorAutenticacion {
+    fn codigo(&self) -> &'static str {
+        match self {
+            ErrorAutenticacion::TokenRequerido => "AUTH_TOKEN_REQUERIDO",
+            ErrorAutenticacion::FormatoInvalido => "AUTH_FORMATO_TOKEN_INVALIDO",
+            ErrorAutenticacion::TokenInvalido => "AUTH_TOKEN_INVALIDO",
+            ErrorAutenticacion::RolInvalido => "AUTH_ROL_INVALIDO",
+            ErrorAutenticacion::UsuarioInactivo => "AUTH_USUARIO_INACTIVO",
+            ErrorAutenticacion::EstadoNoDisponible => "AUTH_ESTADO_NO_DISPONIBLE",
+            ErrorAutenticacion::ApiKeyInvalida => "AUTH_API_KEY_INVALIDA",
+            ErrorAutenticacion::SegundoFactorRequerido => "AUTH_SEGUNDO_FACTOR_REQUERIDO",
+            ErrorAutenticacion::PermisoDenegado(e) => e.codigo(),
+        }
+    }
+
+    fn categoria(&self) -> Categoria {
+        match self {
+            ErrorAutenticacion::FormatoInvalido => Categoria::Validacion,
+            ErrorAutenticacion::TokenRequerido
+            | ErrorAutenticacion::TokenInvalido
+            | ErrorAutenticacion::UsuarioInactivo
+            | ErrorAutenticacion::ApiKeyInvalida
+            | ErrorAutenticacion::SegundoFactorRequerido => Categoria::NoAutenticado,
+            ErrorAutenticacion::RolInvalido | ErrorAutenticacion::PermisoDenegado(_) => {
+                Categoria::PermisoDenegado
+            }
+            ErrorAutenticacion::EstadoNoDisponible => Categoria::Interno,
+        }
+    }
+
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ErrorAutenticacion::PermisoDenegado(e) => e.parametros(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn campos(&self) -> Vec<ErrorCampo> {
+        match self {
+            ErrorAutenticacion::FormatoInvalido => {
+                vec![ErrorCampo::con_codigo("authorization", self.codigo())]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Autentica las peticiones a partir de la cabecera `authorization`
+///
+/// Sin [`EstadoUsuarios`] solo se valida el token. Con él se comprueba además
+/// que la cuenta siga activa, y el rol y las salas se toman de los datos
+/// actuales del usuario en lugar de los del token.
+///
+/// Las claves de API (`ApiKey <clave>`) solo se admiten si se configura
+/// [`EstadoApiKeys`].
+///
+/// Los tokens de los roles a los que la [`PoliticaSegundoFactor`] exige
+/// segundo factor solo valen si el login pasó por él.
+///
+/// Con [`GruposUsuarios`] el principal lleva los grupos actuales del usuario;
+/// sin él no tiene ninguno.
+#[derive(Clone, Default)]
+pub struct Autenticador {
+    estado: Option<Arc<dyn EstadoUsuarios>>,
+    api_keys: Option<Arc<dyn EstadoApiKeys>>,
+    grupos: Option<Arc<dyn GruposUsuarios>>,
+    segundo_factor: PoliticaSegundoFactor,
+}
+
+impl Autenticador {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_estado(mut self, estado: Arc<dyn EstadoUsuarios>) -> Self {
+        self.estado = Some(estado);
+        self
+    }
+
+    pub fn with_api_keys(mut self, api_keys: Arc<dyn EstadoApiKeys>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    pub fn with_grupos(mut self, grupos: Arc<dyn GruposUsuarios>) -> Self {
+        self.grupos = Some(grupos);
+        self
+    }
+
+    pub fn with_politica_segundo_factor(mut self, politica: PoliticaSegundoFactor) -> Self {
+        self.segundo_factor = politica;
+        self
+    }
+
+    /// Autentica el valor de la cabecera `authorization` (`Bearer <token>` o
+    /// `ApiKey <clave>`)
+    pub async fn autenticar(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<Principal, ErrorAutenticacion> {
+        let cabecera = authorization.ok_or(ErrorAutenticacion::TokenRequerido)?;
+        if let Some(clave) = cabecera.strip_prefix("ApiKey ") {
+            return self.autenticar_api_key(clave).await;
+        }
+
+        let token = cabecera
+            .strip_prefix("Bearer ")
+            .ok_or(ErrorAutenticacion::FormatoInvalido)?;
+
+        let claims =
+            JwtService::validate_token(token).map_err(|_| ErrorAutenticacion::TokenInvalido)?;
+        let segundo_factor = claims.segundo_factor;
+        let mut principal =
+            Principal::desde_claims(claims).ok_or(ErrorAutenticacion::RolInvalido)?;
+
+        if let Some(estado) = &self.estado {
+            principal = match estado.usuario_actual(&principal.user_id).await {
+                Ok(Some(usuario)) if usuario.activo => Principal::desde_usuario(&usuario),
+                Ok(_) => return Err(ErrorAutenticacion::UsuarioInactivo),
+                Err(_) => return Err(ErrorAutenticacion::EstadoNoDisponible),
+            };
+        }
+
+        // Con el rol actual: a quien asciendan a admin le vale su token de
+        // antes solo si ya entró con segundo factor
+        if !segundo_factor && self.segundo_factor.exige(&principal.rol) {
+            return Err(ErrorAutenticacion::SegundoFactorRequerido);
+        }
+
+        if let Some(grupos) = &self.grupos {
+            principal.grupos = grupos
+                .grupos_de(&principal.user_id)
+                .await
+                .map_err(|_| ErrorAutenticacion::EstadoNoDisponible)?;
+        }
+        Ok(principal)
+    }
+
+    async fn autenticar_api_key(&self, clave: &str) -> Result<Principal, ErrorAutenticacion> {
+        let api_keys = self
+            .api_keys
+            .as_ref()
+            .ok_or(ErrorAutenticacion::ApiKeyInvalida)?;
+
+        match api_keys.usar_api_key(&TokenService::hash(clave)).await {
+            Ok(Some(clave)) => Ok(Principal::desde_api_key(&clave)),
+            Ok(None) => Err(ErrorAutenticacion::ApiKeyInvalida),
+            Err(_) => Err(ErrorAutenticacion::EstadoNoDisponible),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autorizacion::{Autorizador, Sujeto};
+    use std::collections::HashMap;
+    use usuarios_domain::{Permiso, Rol};
+
+    struct MockEstado {
+        usuarios: HashMap<String, Usuario>,
+    }
+
+    #[async_trait]
+    impl EstadoUsuarios for MockEstado {
+        async fn usuario_actual(&self, id: &str) -> Result<Option<Usuario>, String> {
+            Ok(self.usuarios.get(id).cloned())
+        }
+    }
+
+    struct MockApiKeys {
+        claves: HashMap<String, ApiKey>,
+    }
+
+    #[async_trait]
+    impl EstadoApiKeys for MockApiKeys {
+        async fn usar_api_key(&self, hash: &str) -> Result<Option<ApiKey>, String> {
+            Ok(self.claves.get(hash).filter(|c| c.es_valida()).cloned())
+        }
+    }
+
+    struct MockGrupos;
+
+    #[async_trait]
+    impl GruposUsuarios for MockGrupos {
+        async fn grupos_de(&self, _usuario_id: &str) -> Result<Vec<String>, String> {
+            Ok(vec!["equipo".to_string()])
+        }
+    }
+
+    fn usuario(rol: Rol) -> Usuario {
+        Usuario::new(
+            "Prueba".to_string(),
+            "prueba@example.com".to_string(),
+            "hash".to_string(),
+            rol,
+        )
+        .unwrap()
+    }
+
+    fn cabecera(usuario: &Usuario) -> String {
+        format!(
+            "Bearer {}",
+            JwtService::generate_token_para(usuario).unwrap()
+        )
+    }
+
+    fn con_estado(usuarios: &[&Usuario]) -> Autenticador {
+        let usuarios = usuarios
+            .iter()
+            .map(|u| (u.id.clone(), (*u).clone()))
+            .collect();
+        Autenticador::new().with_estado(Arc::new(MockEstado { usuarios }))
+    }
+
+    #[tokio::test]
+    async fn errores_de_cabecera() {
+        let autenticador = Autenticador::new();
+
+        assert_eq!(
+            autenticador.autenticar(None).await,
+            Err(ErrorAutenticacion::TokenRequerido)
+        );
+        assert_eq!(
+            autenticador.autenticar(Some("Token abc")).await,
+            Err(ErrorAutenticacion::FormatoInvalido)
+        );
+        assert_eq!(
+            autenticador.autenticar(Some("Bearer a.b.c")).await,
+            Err(ErrorAutenticacion::TokenInvalido)
+        );
+    }
+
+    #[tokio::test]
+    async fn sin_estado_usa_los_datos_del_token() {
+        let usuario = usuario(Rol::Recepcionista);
+
+        let principal = Autenticador::new()
+            .autenticar(Some(&cabecera(&usuario)))
+            .await
+            .unwrap();
+
+        assert_eq!(principal.id(), usuario.id);
+        assert_eq!(principal.rol, Rol::Recepcionista);
+    }
+
+    #[tokio::test]
+    async fn con_grupos_el_principal_lleva_los_del_usuario() {
+        let usuario = usuario(Rol::Usuario);
+        let cabecera = cabecera(&usuario);
+
+        let sin_grupos = Autenticador::new()
+            .autenticar(Some(&cabecera))
+            .await
+            .unwrap();
+        assert!(sin_grupos.grupos.is_empty());
+
+        let principal = Autenticador::new()
+            .with_grupos(Arc::new(MockGrupos))
+            .autenticar(Some(&cabecera))
+            .await
+            .unwrap();
+        assert_eq!(principal.grupos(), ["equipo"]);
+    }
+
+    #[test]
+    fn rechaza_roles_desconocidos() {
+        let usuario = usuario(Rol::Usuario);
+        let mut claims =
+            JwtService::validate_token(&JwtService::generate_token_para(&usuario).unwrap())
+                .unwrap();
+        claims.rol = "superusuario".to_string();
+
+        assert_eq!(Principal::desde_claims(claims), None);
+    }
+
+    #[tokio::test]
+    async fn con_estado_toma_el_rol_y_las_salas_actuales() {
+        let mut gestor = usuario(Rol::Usuario);
+        let token = cabecera(&gestor);
+        gestor.actualizar_rol(Rol::GestorSalas);
+        gestor.asignar_salas_gestionadas(vec!["sala-1".to_string()]);
+
+        let principal = con_estado(&[&gestor])
+            .autenticar(Some(&token))
+            .await
+            .unwrap();
+
+        assert_eq!(principal.rol, Rol::GestorSalas);
+        assert!(Autorizador::puede_en_sala(
+            &principal,
+            Permiso::GestionarSalas,
+            "sala-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn con_estado_rechaza_usuarios_desactivados_o_eliminados() {
+        let mut desactivado = usuario(Rol::Admin);
+        let token = cabecera(&desactivado);
+        desactivado.desactivar();
+
+        assert_eq!(
+            con_estado(&[&desactivado]).autenticar(Some(&token)).await,
+            Err(ErrorAutenticacion::UsuarioInactivo)
+        );
+        assert_eq!(
+            con_estado(&[]).autenticar(Some(&token)).await,
+            Err(ErrorAutenticacion::UsuarioInactivo)
+        );
+    }
+
+    #[tokio::test]
+    async fn autentica_claves_de_api_vigentes() {
+        let (secreto, _) = TokenService::generar();
+        let clave = format!("rsk_{}", secreto);
+        let mut api_key = ApiKey::new(
+            "Pantalla".to_string(),
+            &clave,
+            TokenService::hash(&clave),
+            vec![Permiso::VerCualquierReserva],
+            "admin".to_string(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let cabecera = format!("ApiKey {}", clave);
+
+        assert_eq!(
+            Autenticador::new().autenticar(Some(&cabecera)).await,
+            Err(ErrorAutenticacion::ApiKeyInvalida)
+        );
+
+        let con_claves = |api_key: &ApiKey| {
+            let claves = HashMap::from([(api_key.hash.clone(), api_key.clone())]);
+            Autenticador::new().with_api_keys(Arc::new(MockApiKeys { claves }))
+        };
+
+        let principal = con_claves(&api_key)
+            .autenticar(Some(&cabecera))
+            .await
+            .unwrap();
+        assert!(principal.es_cuenta_servicio());
+        assert_eq!(principal.id(), api_key.id);
+        assert!(Autorizador::puede(&principal, Permiso::VerCualquierReserva));
+
+        api_key.revocar(chrono::Utc::now());
+        assert_eq!(
+            con_claves(&api_key).autenticar(Some(&cabecera)).await,
+            Err(ErrorAutenticacion::ApiKeyInvalida)
+        );
+    }
+
+    #[tokio::test]
+    async fn la_politica_exige_tokens_con_segundo_factor() {
+        let mut admin = usuario(Rol::Usuario);
+        let token_previo = cabecera(&admin);
+        admin.actualizar_rol(Rol::Admin);
+        let con_segundo_factor = format!(
+            "Bearer {}",
+            JwtService::generate_token_con_segundo_factor(&admin).unwrap()
+        );
+        let autenticador = con_estado(&[&admin])
+            .with_politica_segundo_factor(PoliticaSegundoFactor::para_admins());
+
+        // El rol que cuenta es el actual, no el del token
+        assert_eq!(
+            autenticador.autenticar(Some(&token_previo)).await,
+            Err(ErrorAutenticacion::SegundoFactorRequerido)
+        );
+        assert_eq!(
+            autenticador
+                .autenticar(Some(&con_segundo_factor))
+                .await
+                .unwrap()
+                .rol,
+            Rol::Admin
+        );
+
+        let usuario = usuario(Rol::Usuario);
+        assert!(con_estado(&[&usuario])
+            .with_politica_segundo_factor(PoliticaSegundoFactor::para_admins())
+            .autenticar(Some(&cabecera(&usuario)))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,422 @@
+use shared_errores::{Categoria, ErrorDominio, Idioma};
+use std::fmt;
+use usuarios_domain::{Grupo, Permiso, Rol, Usuario, UsuarioPublico};
+
+/// Quien intenta hacer una acción
+///
+/// Lo implementan el usuario del dominio y los usuarios autenticados de las
+/// capas REST y gRPC, para que todas decidan con el mismo [`Autorizador`].
+pub trait Sujeto {
+    fn id(&self) -> &str;
+    fn rol(&self) -> &Rol;
+    /// Salas que gestiona (solo cuentan si el rol da permisos por sala)
+    fn salas_gestionadas(&self) -> &[String];
+
+    /// Permisos a los que está limitado en lugar de los del rol (cuentas de
+    /// servicio); `None` si mandan los del rol
+    fn alcance(&self) -> Option<&[Permiso]> {
+        None
+    }
+
+    /// Grupos de los que es miembro; solo los conoce el usuario autenticado
+    fn grupos(&self) -> &[String] {
+        &[]
+    }
+}
+
+impl Sujeto for Usuario {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn rol(&self) -> &Rol {
+        &self.rol
+    }
+
+    fn salas_gestionadas(&self) -> &[String] {
+        &self.salas_gestionadas
+    }
+}
+
+impl Sujeto for UsuarioPublico {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn rol(&self) -> &Rol {
+        &self.rol
+    }
+
+    fn salas_gestionadas(&self) -> &[String] {
+        &self.salas_gestionadas
+    }
+}
+
+/// Error cuando el sujeto no tiene el permiso necesario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermisoDenegado {
+    pub permiso: Permiso,
+}
+
+impl fmt::Display for PermisoDenegado {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje_en(Idioma::Es))
+    }
+}
+
+impl std::error::Error for PermisoDenegado {}
+
+impl ErrorDominio for PermisoDenegado {
+    fn codigo(&self) -> &'static str {
+        "AUTH_PERMISO_DENEGADO"
+    }
+
+    fn categoria(&self) -> Categoria {
+        Categoria::PermisoDenegado
+    }
+
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        vec![("permiso", self.permiso.as_str().to_string())]
+    }
+}
+
+/// Servicio único de autorización para REST, gRPC y la web
+///
+/// Un permiso se concede si lo da el rol en todo el sistema o, para los
+/// permisos de sala, si el rol lo da sobre las salas gestionadas y la sala
+/// está entre ellas. Los sujetos con alcance (claves de API) solo tienen los
+/// permisos de su alcance.
+pub struct Autorizador;
+
+impl Autorizador {
+    /// Si el sujeto tiene el permiso en todo el sistema
+    pub fn puede<S: Sujeto + ?Sized>(sujeto: &S, permiso: Permiso) -> bool {
+        match sujeto.alcance() {
+            Some(alcance) => alcance.contains(&permiso),
+            None => sujeto.rol().tiene_permiso(permiso),
+        }
+    }
+
+    /// Si el sujeto tiene el permiso sobre una sala concreta
+    pub fn puede_en_sala<S: Sujeto + ?Sized>(sujeto: &S, permiso: Permiso, sala_id: &str) -> bool {
+        Self::puede(sujeto, permiso)
+            || (sujeto
+                .rol()
+                .permisos_en_salas_gestionadas()
+                .contains(&permiso)
+                && sujeto.salas_gestionadas().iter().any(|s| s == sala_id))
+    }
+
+    /// Si el sujeto tiene el permiso en todo el sistema o en al menos una sala
+    /// (para decidir si mostrar las pantallas de gestión)
+    pub fn puede_en_alguna_sala<S: Sujeto + ?Sized>(sujeto: &S, permiso: Permiso) -> bool {
+        Self::puede(sujeto, permiso)
+            || (sujeto
+                .rol()
+                .permisos_en_salas_gestionadas()
+                .contains(&permiso)
+                && !sujeto.salas_gestionadas().is_empty())
+    }
+
+    /// Si el sujeto puede cancelar o completar una reserva: la suya, la de
+    /// uno de sus grupos o, con `reserva:manage_any`, la de cualquiera en esa sala
+    pub fn puede_gestionar_reserva<S: Sujeto + ?Sized>(
+        sujeto: &S,
+        usuario_id: &str,
+        grupo_id: Option<&str>,
+        sala_id: &str,
+    ) -> bool {
+        sujeto.id() == usuario_id
+            || grupo_id.is_some_and(|grupo| sujeto.grupos().iter().any(|g| g == grupo))
+            || Self::puede_en_sala(sujeto, Permiso::GestionarCualquierReserva, sala_id)
+    }
+
+    /// Si el sujeto puede ver una reserva: si puede gestionarla o, con
+    /// `reserva:read_any`, cualquiera de esa sala
+    pub fn puede_ver_reserva<S: Sujeto + ?Sized>(
+        sujeto: &S,
+        usuario_id: &str,
+        grupo_id: Option<&str>,
+        sala_id: &str,
+    ) -> bool {
+        Self::puede_gestionar_reserva(sujeto, usuario_id, grupo_id, sala_id)
+            || Self::puede_en_sala(sujeto, Permiso::VerCualquierReserva, sala_id)
+    }
+
+    /// Si en los listados el sujeto ve las reservas de todos: con
+    /// `reserva:read_any` en todo el sistema o sobre la sala filtrada. Si no,
+    /// solo ve las suyas y las de sus grupos
+    pub fn ve_todas_las_reservas<S: Sujeto + ?Sized>(sujeto: &S, sala_id: Option<&str>) -> bool {
+        match sala_id {
+            Some(sala_id) => Self::puede_en_sala(sujeto, Permiso::VerCualquierReserva, sala_id),
+            None => Self::puede(sujeto, Permiso::VerCualquierReserva),
+        }
+    }
+
+    /// Si el sujeto puede cambiar los miembros de un grupo o eliminarlo: sus
+    /// miembros y quien tenga `usuario:manage`
+    pub fn puede_gestionar_grupo<S: Sujeto + ?Sized>(sujeto: &S, grupo: &Grupo) -> bool {
+        grupo.es_miembro(sujeto.id()) || Self::puede(sujeto, Permiso::GestionarUsuarios)
+    }
+
+    /// Exige el permiso en todo el sistema
+    pub fn exigir<S: Sujeto + ?Sized>(sujeto: &S, permiso: Permiso) -> Result<(), PermisoDenegado> {
+        if Self::puede(sujeto, permiso) {
+            Ok(())
+        } else {
+            Err(PermisoDenegado { permiso })
+        }
+    }
+
+    /// Exige el permiso sobre una sala concreta
+    pub fn exigir_en_sala<S: Sujeto + ?Sized>(
+        sujeto: &S,
+        permiso: Permiso,
+        sala_id: &str,
+    ) -> Result<(), PermisoDenegado> {
+        if Self::puede_en_sala(sujeto, permiso, sala_id) {
+            Ok(())
+        } else {
+            Err(PermisoDenegado { permiso })
+        }
+    }
+
+    /// Exige poder ver la reserva de `usuario_id` (y `grupo_id`) en `sala_id`
+    pub fn exigir_ver_reserva<S: Sujeto + ?Sized>(
+        sujeto: &S,
+        usuario_id: &str,
+        grupo_id: Option<&str>,
+        sala_id: &str,
+    ) -> Result<(), PermisoDenegado> {
+        if Self::puede_ver_reserva(sujeto, usuario_id, grupo_id, sala_id) {
+            Ok(())
+        } else {
+            Err(PermisoDenegado {
+                permiso: Permiso::VerCualquierReserva,
+            })
+        }
+    }
+
+    /// Exige poder gestionar la reserva de `usuario_id` (y `grupo_id`) en `sala_id`
+    pub fn exigir_gestion_reserva<S: Sujeto + ?Sized>(
+        sujeto: &S,
+        usuario_id: &str,
+        grupo_id: Option<&str>,
+        sala_id: &str,
+    ) -> Result<(), PermisoDenegado> {
+        if Self::puede_gestionar_reserva(sujeto, usuario_id, grupo_id, sala_id) {
+            Ok(())
+        } else {
+            Err(PermisoDenegado {
+                permiso: Permiso::GestionarCualquierReserva,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usuario(id: &str, rol: Rol, salas: &[&str]) -> Usuario {
+        let mut usuario = Usuario::new(
+            "Prueba".to_string(),
+            format!("{}@example.com", id),
+            "hash".to_string(),
+            rol,
+        )
+        .unwrap();
+        usuario.id = id.to_string();
+        usuario.asignar_salas_gestionadas(salas.iter().map(|s| s.to_string()).collect());
+        usuario
+    }
+
+    #[test]
+    fn admin_puede_todo() {
+        let admin = usuario("admin", Rol::Admin, &[]);
+
+        assert!(Autorizador::puede(&admin, Permiso::GestionarUsuarios));
+        assert!(Autorizador::puede_en_sala(
+            &admin,
+            Permiso::GestionarSalas,
+            "cualquiera"
+        ));
+        assert!(Autorizador::puede_gestionar_reserva(
+            &admin, "otro", None, "sala"
+        ));
+    }
+
+    #[test]
+    fn gestor_solo_en_sus_salas() {
+        let gestor = usuario("g", Rol::GestorSalas, &["sala-1"]);
+
+        assert!(Autorizador::puede_en_sala(
+            &gestor,
+            Permiso::GestionarSalas,
+            "sala-1"
+        ));
+        assert!(!Autorizador::puede_en_sala(
+            &gestor,
+            Permiso::GestionarSalas,
+            "sala-2"
+        ));
+        assert!(!Autorizador::puede(&gestor, Permiso::GestionarSalas));
+        assert!(Autorizador::puede_en_alguna_sala(
+            &gestor,
+            Permiso::GestionarSalas
+        ));
+        assert!(Autorizador::puede_gestionar_reserva(
+            &gestor, "otro", None, "sala-1"
+        ));
+        assert!(!Autorizador::puede_gestionar_reserva(
+            &gestor, "otro", None, "sala-2"
+        ));
+        assert!(!Autorizador::puede_en_sala(
+            &gestor,
+            Permiso::VerInformes,
+            "sala-1"
+        ));
+    }
+
+    #[test]
+    fn ver_reservas_propias_o_con_permiso_de_lectura() {
+        let usuario_normal = usuario("u", Rol::Usuario, &[]);
+        let gestor = usuario("g", Rol::GestorSalas, &["sala-1"]);
+        let lectura = usuario("l", Rol::Lectura, &[]);
+
+        assert!(Autorizador::puede_ver_reserva(
+            &usuario_normal,
+            "u",
+            None,
+            "sala-1"
+        ));
+        assert!(!Autorizador::puede_ver_reserva(
+            &usuario_normal,
+            "otro",
+            None,
+            "sala-1"
+        ));
+        assert!(!Autorizador::ve_todas_las_reservas(&usuario_normal, None));
+
+        assert!(Autorizador::puede_ver_reserva(
+            &gestor, "otro", None, "sala-1"
+        ));
+        assert!(!Autorizador::puede_ver_reserva(
+            &gestor, "otro", None, "sala-2"
+        ));
+        assert!(Autorizador::ve_todas_las_reservas(&gestor, Some("sala-1")));
+        assert!(!Autorizador::ve_todas_las_reservas(&gestor, None));
+
+        assert!(Autorizador::ve_todas_las_reservas(&lectura, None));
+        assert_eq!(
+            Autorizador::exigir_ver_reserva(&usuario_normal, "otro", None, "sala-1")
+                .unwrap_err()
+                .permiso,
+            Permiso::VerCualquierReserva
+        );
+    }
+
+    #[test]
+    fn las_salas_asignadas_no_cuentan_sin_rol_de_gestor() {
+        let usuario = usuario("u", Rol::Usuario, &["sala-1"]);
+
+        assert!(!Autorizador::puede_en_sala(
+            &usuario,
+            Permiso::GestionarSalas,
+            "sala-1"
+        ));
+        assert!(Autorizador::puede_gestionar_reserva(
+            &usuario, "u", None, "sala-1"
+        ));
+        assert!(!Autorizador::puede_gestionar_reserva(
+            &usuario, "otro", None, "sala-1"
+        ));
+    }
+
+    #[test]
+    fn los_miembros_gestionan_las_reservas_y_el_grupo() {
+        let mut grupo = Grupo::new(
+            "Marketing".to_string(),
+            "ana".to_string(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let miembro = crate::Principal::new("ana", "ana@example.com", Rol::Usuario)
+            .with_grupos(vec![grupo.id.clone()]);
+        let ajeno = crate::Principal::new("eva", "eva@example.com", Rol::Usuario);
+
+        assert!(Autorizador::puede_gestionar_reserva(
+            &miembro,
+            "luis",
+            Some(&grupo.id),
+            "sala-1"
+        ));
+        assert!(!Autorizador::puede_gestionar_reserva(
+            &miembro, "luis", None, "sala-1"
+        ));
+        assert!(!Autorizador::puede_gestionar_reserva(
+            &ajeno,
+            "luis",
+            Some(&grupo.id),
+            "sala-1"
+        ));
+
+        assert!(Autorizador::puede_gestionar_grupo(&miembro, &grupo));
+        assert!(!Autorizador::puede_gestionar_grupo(&ajeno, &grupo));
+        grupo.agregar_miembro("eva");
+        assert!(Autorizador::puede_gestionar_grupo(&ajeno, &grupo));
+        let admin = usuario("admin", Rol::Admin, &[]);
+        assert!(Autorizador::puede_gestionar_grupo(&admin, &grupo));
+    }
+
+    #[test]
+    fn error_con_el_permiso_que_falta() {
+        let lectura = usuario("l", Rol::Lectura, &[]);
+
+        let error = Autorizador::exigir(&lectura, Permiso::CrearReservas).unwrap_err();
+        assert_eq!(error.codigo(), "AUTH_PERMISO_DENEGADO");
+        assert_eq!(error.categoria(), Categoria::PermisoDenegado);
+        assert!(error.mensaje_en(Idioma::En).contains("reserva:create"));
+        assert!(Autorizador::exigir(&lectura, Permiso::VerInformes).is_ok());
+    }
+
+    #[test]
+    fn las_cuentas_de_servicio_solo_tienen_su_alcance() {
+        let clave = usuarios_domain::ApiKey::new(
+            "Pantallas".to_string(),
+            "rsk_clave",
+            "hash".to_string(),
+            vec![Permiso::VerCualquierReserva],
+            "admin".to_string(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let servicio = crate::Principal::desde_api_key(&clave);
+
+        assert!(Autorizador::puede(&servicio, Permiso::VerCualquierReserva));
+        assert!(Autorizador::ve_todas_las_reservas(&servicio, None));
+        // El rol `Lectura` de la cuenta de servicio no añade permisos
+        assert!(!Autorizador::puede(&servicio, Permiso::VerInformes));
+        assert!(!Autorizador::puede_en_sala(
+            &servicio,
+            Permiso::GestionarCualquierReserva,
+            "sala-1"
+        ));
+
+        // Con otro alcance no ve las reservas ajenas aunque su rol sea `Lectura`
+        let clave = usuarios_domain::ApiKey::new(
+            "Quiosco".to_string(),
+            "rsk_otra",
+            "hash".to_string(),
+            vec![Permiso::CrearReservas],
+            "admin".to_string(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let quiosco = crate::Principal::desde_api_key(&clave);
+        assert!(!Autorizador::ve_todas_las_reservas(&quiosco, None));
+        assert!(!Autorizador::puede_ver_reserva(
+            &quiosco, "ana", None, "sala-1"
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! Capa de autenticación común a los servidores HTTP y gRPC
+//!
+//! [`AutenticacionLayer`] autentica cada petición una sola vez con el
+//! [`Autenticador`] y deja en sus extensiones el [`Principal`] o, si no se
+//! pudo autenticar, el [`ErrorAutenticacion`]. Las rutas y los servicios que
+//! exigen usuario lo leen con [`principal`]; las públicas lo ignoran.
+
+use crate::autenticacion::{Autenticador, ErrorAutenticacion};
+use crate::principal::Principal;
+use http::header::AUTHORIZATION;
+use http::{Extensions, Request};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Principal de la petición o el motivo por el que no lo hay
+///
+/// Sin la capa instalada nunca hay principal y se responde como si faltara
+/// el token.
+pub fn principal(extensiones: &Extensions) -> Result<&Principal, ErrorAutenticacion> {
+    extensiones.get::<Principal>().ok_or_else(|| {
+        extensiones
+            .get::<ErrorAutenticacion>()
+            .cloned()
+            .unwrap_or(ErrorAutenticacion::TokenRequerido)
+    })
+}
+
+/// Capa de tower que autentica las peticiones
+#[derive(Clone)]
+pub struct AutenticacionLayer {
+    autenticador: Autenticador,
+}
+
+impl AutenticacionLayer {
+    pub fn new(autenticador: Autenticador) -> Self {
+        Self { autenticador }
+    }
+}
+
+impl<S> Layer<S> for AutenticacionLayer {
+    type Service = Autenticacion<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Autenticacion {
+            inner,
+            autenticador: self.autenticador.clone(),
+        }
+    }
+}
+
+/// Servicio creado por [`AutenticacionLayer`]
+#[derive(Clone)]
+pub struct Autenticacion<S> {
+    inner: S,
+    autenticador: Autenticador,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Autenticacion<S>
+where
+    S: Service<Request<ReqBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // El servicio que ha pasado `poll_ready` es el que atiende la petición
+        let clon = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clon);
+        let autenticador = self.autenticador.clone();
+
+        Box::pin(async move {
+            let authorization = request
+                .headers()
+                .get(AUTHORIZATION)
+                .map(|valor| valor.to_str().unwrap_or_default());
+
+            match autenticador.autenticar(authorization).await {
+                Ok(principal) => {
+                    request.extensions_mut().insert(principal);
+                }
+                Err(error) => {
+                    request.extensions_mut().insert(error);
+                }
+            }
+
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::JwtService;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+    use usuarios_domain::Rol;
+
+    async fn autenticar(request: Request<()>) -> Result<Principal, ErrorAutenticacion> {
+        let servicio = AutenticacionLayer::new(Autenticador::new()).layer(service_fn(
+            |request: Request<()>| async move {
+                Ok::<_, Infallible>(principal(request.extensions()).cloned())
+            },
+        ));
+        servicio.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn deja_el_principal_en_las_extensiones() {
+        let token = JwtService::generate_token("user-1", "a@example.com", Rol::Admin).unwrap();
+        let request = Request::builder()
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .body(())
+            .unwrap();
+
+        let principal = autenticar(request).await.unwrap();
+
+        assert_eq!(principal.user_id, "user-1");
+        assert_eq!(principal.rol, Rol::Admin);
+    }
+
+    #[tokio::test]
+    async fn deja_el_error_si_no_se_puede_autenticar() {
+        let sin_token = Request::builder().body(()).unwrap();
+        let token_invalido = Request::builder()
+            .header(AUTHORIZATION, "Bearer a.b.c")
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            autenticar(sin_token).await,
+            Err(ErrorAutenticacion::TokenRequerido)
+        );
+        assert_eq!(
+            autenticar(token_invalido).await,
+            Err(ErrorAutenticacion::TokenInvalido)
+        );
+    }
+}
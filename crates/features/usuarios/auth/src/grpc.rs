@@ -0,0 +1,134 @@
+//! Acceso al usuario autenticado desde los servicios tonic
+//!
+//! La [`AutenticacionLayer`](crate::AutenticacionLayer) instalada en el
+//! `Server` deja el [`Principal`] en las extensiones, que tonic conserva en
+//! cada `Request`. Los errores se devuelven traducidos al idioma de la petición.
+
+#![allow(clippy::result_large_err)]
+
+use crate::autenticacion::ErrorAutenticacion;
+use crate::autorizacion::{Autorizador, PermisoDenegado};
+use crate::capa::principal;
+use crate::principal::Principal;
+use shared_errores::grpc::{a_status_en, idioma_de_metadata};
+use shared_errores::Idioma;
+use tonic::{Request, Status};
+use usuarios_domain::Permiso;
+
+/// Interceptor que rechaza las llamadas sin usuario autenticado
+///
+/// Para servicios en los que todas las llamadas lo exigen; se monta con
+/// `XServiceServer::with_interceptor(servicio, exigir_autenticacion)`.
+pub fn exigir_autenticacion(request: Request<()>) -> Result<Request<()>, Status> {
+    request.principal()?;
+    Ok(request)
+}
+
+/// Trait de extensión para leer el [`Principal`] de un `tonic::Request`
+pub trait RequestPrincipalExt {
+    /// Usuario autenticado (`UNAUTHENTICATED` si no lo hay)
+    fn principal(&self) -> Result<Principal, Status>;
+
+    /// Usuario autenticado con el permiso en todo el sistema
+    fn principal_con_permiso(&self, permiso: Permiso) -> Result<Principal, Status>;
+
+    /// Usuario autenticado con el permiso sobre la sala
+    fn principal_con_permiso_en_sala(
+        &self,
+        permiso: Permiso,
+        sala_id: &str,
+    ) -> Result<Principal, Status>;
+
+    /// Idioma de los mensajes de error
+    ///
+    /// Manda la preferencia del perfil; si no la hay se usa la metadata
+    /// `accept-language` y, en último caso, el idioma por defecto.
+    fn idioma(&self) -> Idioma;
+
+    /// Status `PERMISSION_DENIED` traducido al idioma de la petición
+    fn status_denegado(&self, error: PermisoDenegado) -> Status;
+}
+
+impl<T> RequestPrincipalExt for Request<T> {
+    fn principal(&self) -> Result<Principal, Status> {
+        principal(self.extensions())
+            .cloned()
+            .map_err(|e| a_status_en(&e, self.idioma()))
+    }
+
+    fn principal_con_permiso(&self, permiso: Permiso) -> Result<Principal, Status> {
+        let principal = self.principal()?;
+        Autorizador::exigir(&principal, permiso).map_err(|e| self.status_denegado(e))?;
+        Ok(principal)
+    }
+
+    fn principal_con_permiso_en_sala(
+        &self,
+        permiso: Permiso,
+        sala_id: &str,
+    ) -> Result<Principal, Status> {
+        let principal = self.principal()?;
+        Autorizador::exigir_en_sala(&principal, permiso, sala_id)
+            .map_err(|e| self.status_denegado(e))?;
+        Ok(principal)
+    }
+
+    fn idioma(&self) -> Idioma {
+        principal(self.extensions())
+            .ok()
+            .and_then(|p| p.idioma)
+            .or_else(|| idioma_de_metadata(self.metadata()))
+            .unwrap_or_default()
+    }
+
+    fn status_denegado(&self, error: PermisoDenegado) -> Status {
+        a_status_en(&ErrorAutenticacion::from(error), self.idioma())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+    use usuarios_domain::Rol;
+
+    fn request_con<E: Clone + Send + Sync + 'static>(extension: E) -> Request<()> {
+        let mut request = Request::new(());
+        request.extensions_mut().insert(extension);
+        request
+    }
+
+    #[test]
+    fn sin_principal_es_unauthenticated() {
+        let sin_capa = Request::new(());
+        let token_invalido = request_con(ErrorAutenticacion::TokenInvalido);
+
+        assert_eq!(
+            sin_capa.principal().unwrap_err().code(),
+            Code::Unauthenticated
+        );
+        assert!(exigir_autenticacion(sin_capa).is_err());
+        assert_eq!(
+            token_invalido.principal().unwrap_err().code(),
+            Code::Unauthenticated
+        );
+    }
+
+    #[test]
+    fn permisos_globales_y_de_sala() {
+        let gestor = Principal::new("g", "g@example.com", Rol::GestorSalas)
+            .with_salas_gestionadas(vec!["sala-1".to_string()])
+            .with_idioma(Idioma::En);
+        let request = request_con(gestor);
+
+        assert!(request
+            .principal_con_permiso_en_sala(Permiso::GestionarSalas, "sala-1")
+            .is_ok());
+
+        let status = request
+            .principal_con_permiso(Permiso::GestionarSalas)
+            .unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+        assert!(status.message().contains("permission"));
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use usuarios_domain::Rol;
+use usuarios_domain::{Rol, Usuario};
 
 /// Clave secreta para firmar JWT (EN PRODUCCIÓN: usar variable de entorno)
 const JWT_SECRET: &str = "tu_clave_secreta_super_segura_cambiar_en_produccion";
@@ -9,6 +9,12 @@ const JWT_SECRET: &str = "tu_clave_secreta_super_segura_cambiar_en_produccion";
 /// Tiempo de expiración del token (24 horas)
 const TOKEN_EXPIRATION_HOURS: i64 = 24;
 
+/// Tiempo para introducir el código del segundo factor tras la contraseña
+const DESAFIO_EXPIRATION_MINUTES: i64 = 5;
+
+/// Propósito de los desafíos del segundo factor (no sirven como token de acceso)
+const PROPOSITO_DESAFIO: &str = "segundo_factor";
+
 /// Claims (datos) incluidos en el JWT
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -22,6 +28,33 @@ pub struct Claims {
     pub exp: i64,
     /// Timestamp de emisión
     pub iat: i64,
+    /// Idioma preferido del usuario (`es`, `en`), si lo ha elegido
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idioma: Option<String>,
+    /// Salas que gestiona el usuario (rol `gestor_salas`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub salas: Vec<String>,
+    /// Si el login pasó por el segundo factor
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub segundo_factor: bool,
+}
+
+/// Claims del desafío que se entrega entre la contraseña y el segundo factor
+///
+/// Le faltan el email y el rol, así que no se puede usar como token de acceso.
+#[derive(Debug, Serialize, Deserialize)]
+struct ClaimsDesafio {
+    sub: String,
+    proposito: String,
+    exp: i64,
+    iat: i64,
+}
+
+impl Claims {
+    /// Rol del token, si es uno conocido
+    pub fn rol_usuario(&self) -> Option<Rol> {
+        Rol::from_str_opt(&self.rol)
+    }
 }
 
 /// Servicio para generar y validar tokens JWT
@@ -41,6 +74,16 @@ impl JwtService {
     /// # Errores
     /// Retorna error si la generación del token falla
     pub fn generate_token(user_id: &str, email: &str, rol: Rol) -> Result<String, String> {
+        Self::generate_token_con_idioma(user_id, email, rol, None)
+    }
+
+    /// Genera un token JWT que además lleva el idioma preferido del usuario
+    pub fn generate_token_con_idioma(
+        user_id: &str,
+        email: &str,
+        rol: Rol,
+        idioma: Option<&str>,
+    ) -> Result<String, String> {
         let now = Utc::now();
         let exp = (now + Duration::hours(TOKEN_EXPIRATION_HOURS)).timestamp();
 
@@ -50,6 +93,47 @@ impl JwtService {
             rol: rol.as_str().to_string(),
             exp,
             iat: now.timestamp(),
+            idioma: idioma.map(str::to_string),
+            salas: Vec::new(),
+            segundo_factor: false,
+        };
+
+        Self::firmar(&claims)
+    }
+
+    /// Genera el token de un usuario con su idioma y las salas que gestiona
+    pub fn generate_token_para(usuario: &Usuario) -> Result<String, String> {
+        Self::firmar(&Self::claims_para(usuario, false))
+    }
+
+    /// Genera el token de un usuario que ha entrado con segundo factor
+    pub fn generate_token_con_segundo_factor(usuario: &Usuario) -> Result<String, String> {
+        Self::firmar(&Self::claims_para(usuario, true))
+    }
+
+    fn claims_para(usuario: &Usuario, segundo_factor: bool) -> Claims {
+        let now = Utc::now();
+        Claims {
+            sub: usuario.id.clone(),
+            email: usuario.email.clone(),
+            rol: usuario.rol.as_str().to_string(),
+            exp: (now + Duration::hours(TOKEN_EXPIRATION_HOURS)).timestamp(),
+            iat: now.timestamp(),
+            idioma: usuario.idioma.map(|i| i.codigo().to_string()),
+            salas: usuario.salas_gestionadas.clone(),
+            segundo_factor,
+        }
+    }
+
+    /// Genera el desafío de un usuario que ya ha dado su contraseña y debe
+    /// completar el segundo factor
+    pub fn generate_desafio(user_id: &str) -> Result<String, String> {
+        let now = Utc::now();
+        let claims = ClaimsDesafio {
+            sub: user_id.to_string(),
+            proposito: PROPOSITO_DESAFIO.to_string(),
+            exp: (now + Duration::minutes(DESAFIO_EXPIRATION_MINUTES)).timestamp(),
+            iat: now.timestamp(),
         };
 
         encode(
@@ -57,6 +141,30 @@ impl JwtService {
             &claims,
             &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
         )
+        .map_err(|e| format!("Error al generar desafío: {}", e))
+    }
+
+    /// Valida un desafío y devuelve el id de su usuario
+    pub fn validate_desafio(desafio: &str) -> Result<String, String> {
+        let datos = decode::<ClaimsDesafio>(
+            desafio,
+            &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|e| format!("Desafío inválido: {}", e))?;
+
+        if datos.claims.proposito != PROPOSITO_DESAFIO {
+            return Err("Desafío inválido: propósito incorrecto".to_string());
+        }
+        Ok(datos.claims.sub)
+    }
+
+    fn firmar(claims: &Claims) -> Result<String, String> {
+        encode(
+            &Header::default(),
+            claims,
+            &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
+        )
         .map_err(|e| format!("Error al generar token: {}", e))
     }
 
@@ -117,6 +225,49 @@ mod tests {
         assert!(token.split('.').count() == 3); // JWT tiene 3 partes
     }
 
+    #[test]
+    fn test_token_con_idioma() {
+        let token = JwtService::generate_token_con_idioma(
+            "user-1",
+            "a@example.com",
+            Rol::Usuario,
+            Some("en"),
+        )
+        .unwrap();
+        let sin_idioma =
+            JwtService::generate_token("user-1", "a@example.com", Rol::Usuario).unwrap();
+
+        assert_eq!(
+            JwtService::validate_token(&token)
+                .unwrap()
+                .idioma
+                .as_deref(),
+            Some("en")
+        );
+        assert_eq!(
+            JwtService::validate_token(&sin_idioma).unwrap().idioma,
+            None
+        );
+    }
+
+    #[test]
+    fn test_token_para_gestor_lleva_sus_salas() {
+        let mut gestor = Usuario::new(
+            "Gestora".to_string(),
+            "gestora@example.com".to_string(),
+            "hash".to_string(),
+            Rol::GestorSalas,
+        )
+        .unwrap();
+        gestor.asignar_salas_gestionadas(vec!["sala-1".to_string()]);
+
+        let claims =
+            JwtService::validate_token(&JwtService::generate_token_para(&gestor).unwrap()).unwrap();
+
+        assert_eq!(claims.rol_usuario(), Some(Rol::GestorSalas));
+        assert_eq!(claims.salas, ["sala-1"]);
+    }
+
     #[test]
     fn test_validate_token_valido() {
         let token =
@@ -131,6 +282,29 @@ mod tests {
         assert_eq!(claims.rol, "admin");
     }
 
+    #[test]
+    fn test_desafio_y_token_no_son_intercambiables() {
+        let usuario = Usuario::new(
+            "Admin".to_string(),
+            "admin@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Admin,
+        )
+        .unwrap();
+        let desafio = JwtService::generate_desafio(&usuario.id).unwrap();
+        let token = JwtService::generate_token_con_segundo_factor(&usuario).unwrap();
+
+        assert_eq!(JwtService::validate_desafio(&desafio).unwrap(), usuario.id);
+        assert!(JwtService::validate_token(&desafio).is_err());
+        assert!(JwtService::validate_desafio(&token).is_err());
+        assert!(JwtService::validate_token(&token).unwrap().segundo_factor);
+        assert!(
+            !JwtService::validate_token(&JwtService::generate_token_para(&usuario).unwrap())
+                .unwrap()
+                .segundo_factor
+        );
+    }
+
     #[test]
     fn test_validate_token_invalido() {
         let result = JwtService::validate_token("invalid.token.here");
@@ -1,5 +1,27 @@
+pub mod autenticacion;
+pub mod autorizacion;
+pub mod capa;
 pub mod jwt;
 pub mod password;
+pub mod principal;
+pub mod token;
+pub mod totp;
 
+/// Extractor y middlewares de axum
+#[cfg(feature = "axum")]
+pub mod rest;
+
+/// Acceso al principal desde los servicios tonic
+#[cfg(feature = "tonic")]
+pub mod grpc;
+
+pub use autenticacion::{
+    Autenticador, ErrorAutenticacion, EstadoApiKeys, EstadoUsuarios, GruposUsuarios,
+};
+pub use autorizacion::{Autorizador, PermisoDenegado, Sujeto};
+pub use capa::{Autenticacion, AutenticacionLayer};
 pub use jwt::{Claims, JwtService};
-pub use password::PasswordService;
+pub use password::{ParametrosArgon2, PasswordService};
+pub use principal::Principal;
+pub use token::TokenService;
+pub use totp::{TotpService, PERIODO_TOTP, TOTAL_CODIGOS_RECUPERACION};
@@ -1,13 +1,69 @@
 use argon2::{
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 use rand_core::OsRng;
 
+/// Coste de Argon2id con el que se hashean las contraseñas nuevas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParametrosArgon2 {
+    /// Memoria en KiB
+    pub memoria_kib: u32,
+    /// Número de pasadas
+    pub iteraciones: u32,
+    /// Hilos (grado de paralelismo)
+    pub paralelismo: u32,
+}
+
+impl Default for ParametrosArgon2 {
+    /// Los valores por defecto de la crate `argon2` (19 MiB, 2 pasadas, 1 hilo)
+    fn default() -> Self {
+        Self {
+            memoria_kib: Params::DEFAULT_M_COST,
+            iteraciones: Params::DEFAULT_T_COST,
+            paralelismo: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl ParametrosArgon2 {
+    /// Crea los parámetros comprobando que Argon2 los admite
+    pub fn new(memoria_kib: u32, iteraciones: u32, paralelismo: u32) -> Result<Self, String> {
+        Params::new(memoria_kib, iteraciones, paralelismo, None)
+            .map_err(|e| format!("Parámetros de Argon2 inválidos: {}", e))?;
+        Ok(Self {
+            memoria_kib,
+            iteraciones,
+            paralelismo,
+        })
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>, String> {
+        let params = Params::new(self.memoria_kib, self.iteraciones, self.paralelismo, None)
+            .map_err(|e| format!("Parámetros de Argon2 inválidos: {}", e))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
 /// Servicio para hashear y verificar contraseñas usando Argon2
-pub struct PasswordService;
+///
+/// Las funciones asociadas (`hash_password`, `verify_password`) usan los
+/// parámetros por defecto; una instancia creada con [`PasswordService::new`]
+/// hashea con los suyos y sabe si un hash guardado se quedó por debajo.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PasswordService {
+    parametros: ParametrosArgon2,
+}
 
 impl PasswordService {
+    pub fn new(parametros: ParametrosArgon2) -> Self {
+        Self { parametros }
+    }
+
+    pub fn parametros(&self) -> ParametrosArgon2 {
+        self.parametros
+    }
+
     /// Hashea una contraseña usando Argon2
     ///
     /// # Argumentos
@@ -19,8 +75,13 @@ impl PasswordService {
     /// # Errores
     /// Retorna error si el hashing falla
     pub fn hash_password(password: &str) -> Result<String, String> {
+        Self::default().hashear(password)
+    }
+
+    /// Hashea una contraseña con los parámetros de este servicio
+    pub fn hashear(&self, password: &str) -> Result<String, String> {
         let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
+        let argon2 = self.parametros.argon2()?;
 
         let password_hash = argon2
             .hash_password(password.as_bytes(), &salt)
@@ -29,6 +90,29 @@ impl PasswordService {
         Ok(password_hash.to_string())
     }
 
+    /// Indica si un hash guardado es más débil que los parámetros actuales
+    /// (otro algoritmo o versión, o menos memoria, pasadas o hilos) y
+    /// conviene recalcularlo en el próximo login correcto
+    pub fn necesita_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+        if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        match Params::try_from(&parsed_hash) {
+            Ok(params) => {
+                params.m_cost() < self.parametros.memoria_kib
+                    || params.t_cost() < self.parametros.iteraciones
+                    || params.p_cost() < self.parametros.paralelismo
+            }
+            Err(_) => true,
+        }
+    }
+
     /// Verifica si una contraseña coincide con su hash
     ///
     /// # Argumentos
@@ -100,6 +184,26 @@ mod tests {
         assert!(PasswordService::verify_password(password, &hash2).unwrap());
     }
 
+    #[test]
+    fn test_parametros_propios_y_rehash() {
+        let debil = PasswordService::new(ParametrosArgon2::new(8 * 1024, 1, 1).unwrap());
+        let fuerte = PasswordService::new(ParametrosArgon2::new(16 * 1024, 2, 1).unwrap());
+
+        let hash = debil.hashear("mi_password").unwrap();
+        assert!(hash.contains("m=8192,t=1,p=1"));
+        assert!(PasswordService::verify_password("mi_password", &hash).unwrap());
+
+        assert!(!debil.necesita_rehash(&hash));
+        assert!(fuerte.necesita_rehash(&hash));
+        assert!(!debil.necesita_rehash(&fuerte.hashear("mi_password").unwrap()));
+        assert!(fuerte.necesita_rehash("$argon2i$v=19$m=65536,t=3,p=1$c2FsdHNhbHQ$aGFzaA"));
+    }
+
+    #[test]
+    fn test_parametros_invalidos() {
+        assert!(ParametrosArgon2::new(1, 0, 1).is_err());
+    }
+
     #[test]
     fn test_verify_hash_invalido() {
         let result = PasswordService::verify_password("password", "invalid_hash");
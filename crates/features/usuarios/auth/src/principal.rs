@@ -0,0 +1,123 @@
+use crate::autorizacion::Sujeto;
+use crate::jwt::Claims;
+use shared_errores::Idioma;
+use usuarios_domain::{ApiKey, Permiso, Rol, Usuario};
+
+/// Usuario o cuenta de servicio autenticado de una petición
+///
+/// Lo construye el [`Autenticador`](crate::Autenticador) una sola vez por
+/// petición y la [`AutenticacionLayer`](crate::AutenticacionLayer) lo deja en
+/// las extensiones, de donde lo leen los handlers REST y gRPC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub user_id: String,
+    pub email: String,
+    pub rol: Rol,
+    /// Salas que gestiona (rol `gestor_salas`)
+    pub salas_gestionadas: Vec<String>,
+    /// Idioma preferido del perfil, si el usuario lo ha elegido
+    pub idioma: Option<Idioma>,
+    /// Permisos de la clave de API si es una cuenta de servicio; `None` para
+    /// los usuarios, cuyos permisos salen del rol
+    pub alcance: Option<Vec<Permiso>>,
+    /// Grupos de los que es miembro (si el `Autenticador` los consulta)
+    pub grupos: Vec<String>,
+}
+
+impl Principal {
+    pub fn new(user_id: impl Into<String>, email: impl Into<String>, rol: Rol) -> Self {
+        Self {
+            user_id: user_id.into(),
+            email: email.into(),
+            rol,
+            salas_gestionadas: Vec::new(),
+            idioma: None,
+            alcance: None,
+            grupos: Vec::new(),
+        }
+    }
+
+    pub fn with_salas_gestionadas(mut self, salas: Vec<String>) -> Self {
+        self.salas_gestionadas = salas;
+        self
+    }
+
+    pub fn with_idioma(mut self, idioma: Idioma) -> Self {
+        self.idioma = Some(idioma);
+        self
+    }
+
+    pub fn with_grupos(mut self, grupos: Vec<String>) -> Self {
+        self.grupos = grupos;
+        self
+    }
+
+    /// Principal con los datos del token; `None` si el rol no es conocido
+    pub fn desde_claims(claims: Claims) -> Option<Self> {
+        Some(Self {
+            rol: claims.rol_usuario()?,
+            idioma: claims.idioma.as_deref().and_then(Idioma::from_str_opt),
+            user_id: claims.sub,
+            email: claims.email,
+            salas_gestionadas: claims.salas,
+            alcance: None,
+            grupos: Vec::new(),
+        })
+    }
+
+    /// Principal con los datos actuales del usuario
+    pub fn desde_usuario(usuario: &Usuario) -> Self {
+        Self {
+            user_id: usuario.id.clone(),
+            email: usuario.email.clone(),
+            rol: usuario.rol.clone(),
+            salas_gestionadas: usuario.salas_gestionadas.clone(),
+            idioma: usuario.idioma,
+            alcance: None,
+            grupos: Vec::new(),
+        }
+    }
+
+    /// Cuenta de servicio de una clave de API
+    ///
+    /// Su id es el de la clave y solo tiene los permisos de su alcance; el rol
+    /// `Lectura` es solo para quien mire el rol directamente.
+    pub fn desde_api_key(clave: &ApiKey) -> Self {
+        Self {
+            user_id: clave.id.clone(),
+            email: String::new(),
+            rol: Rol::Lectura,
+            salas_gestionadas: Vec::new(),
+            idioma: None,
+            alcance: Some(clave.permisos.clone()),
+            grupos: Vec::new(),
+        }
+    }
+
+    /// Si se ha autenticado con una clave de API
+    pub fn es_cuenta_servicio(&self) -> bool {
+        self.alcance.is_some()
+    }
+}
+
+impl Sujeto for Principal {
+    fn id(&self) -> &str {
+        &self.user_id
+    }
+
+    fn rol(&self) -> &Rol {
+        &self.rol
+    }
+
+    fn salas_gestionadas(&self) -> &[String] {
+        &self.salas_gestionadas
+    }
+
+    fn alcance(&self) -> Option<&[Permiso]> {
+        self.alcance.as_deref()
+    }
+
+    fn grupos(&self) -> &[String] {
+        &self.grupos
+    }
+}
@@ -0,0 +1,155 @@
+//! Autenticación y permisos en las rutas axum
+//!
+//! Los handlers reciben el [`Principal`] como extractor. Las rutas protegidas
+//! se montan con uno de los middlewares, que además marcan la respuesta con
+//! el idioma del perfil para que `localizar_problemas` lo prefiera a
+//! `Accept-Language`.
+
+use crate::autenticacion::ErrorAutenticacion;
+use crate::autorizacion::{Autorizador, PermisoDenegado};
+use crate::capa::principal;
+use crate::principal::Principal;
+use axum::extract::{FromRequestParts, Path, Request, State};
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use shared_errores::rest::respuesta_problema;
+use usuarios_domain::Permiso;
+
+impl IntoResponse for ErrorAutenticacion {
+    fn into_response(self) -> Response {
+        respuesta_problema(self)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Principal {
+    type Rejection = ErrorAutenticacion;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        principal(&parts.extensions).cloned()
+    }
+}
+
+/// Middleware que exige un usuario autenticado
+pub async fn requiere_autenticacion(req: Request, next: Next) -> Response {
+    continuar_si(req, next, |_| Ok(())).await
+}
+
+/// Middleware que exige un permiso en todo el sistema
+///
+/// Se monta con `middleware::from_fn_with_state(Permiso::..., requiere_permiso)`.
+pub async fn requiere_permiso(
+    State(permiso): State<Permiso>,
+    req: Request,
+    next: Next,
+) -> Response {
+    continuar_si(req, next, |p| Autorizador::exigir(p, permiso)).await
+}
+
+/// Middleware que exige un permiso sobre la sala `{id}` de la ruta
+///
+/// Los gestores de salas lo cumplen para las salas que tienen asignadas.
+pub async fn requiere_permiso_en_sala(
+    State(permiso): State<Permiso>,
+    Path(sala_id): Path<String>,
+    req: Request,
+    next: Next,
+) -> Response {
+    continuar_si(req, next, |p| {
+        Autorizador::exigir_en_sala(p, permiso, &sala_id)
+    })
+    .await
+}
+
+async fn continuar_si(
+    req: Request,
+    next: Next,
+    comprobar: impl FnOnce(&Principal) -> Result<(), PermisoDenegado>,
+) -> Response {
+    let principal = match principal(req.extensions()) {
+        Ok(principal) => principal,
+        Err(error) => return error.into_response(),
+    };
+    let idioma = principal.idioma;
+
+    let mut respuesta = match comprobar(principal) {
+        Ok(()) => next.run(req).await,
+        Err(error) => ErrorAutenticacion::from(error).into_response(),
+    };
+
+    if let Some(idioma) = idioma {
+        respuesta.extensions_mut().insert(idioma);
+    }
+    respuesta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AutenticacionLayer, Autenticador, JwtService};
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{middleware, Router};
+    use shared_errores::Idioma;
+    use tower::ServiceExt;
+    use usuarios_domain::{Rol, Usuario};
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/salas/{id}",
+                get(|principal: Principal| async move { principal.user_id }).layer(
+                    middleware::from_fn_with_state(
+                        Permiso::GestionarSalas,
+                        requiere_permiso_en_sala,
+                    ),
+                ),
+            )
+            .layer(AutenticacionLayer::new(Autenticador::new()))
+    }
+
+    fn gestor() -> Usuario {
+        let mut gestor = Usuario::new(
+            "Gestora".to_string(),
+            "gestora@example.com".to_string(),
+            "hash".to_string(),
+            Rol::GestorSalas,
+        )
+        .unwrap();
+        gestor.asignar_salas_gestionadas(vec!["sala-1".to_string()]);
+        gestor.actualizar_idioma(Some(Idioma::En));
+        gestor
+    }
+
+    async fn get_sala(sala: &str, usuario: Option<&Usuario>) -> Response {
+        let mut request = Request::builder().uri(format!("/salas/{}", sala));
+        if let Some(usuario) = usuario {
+            let token = JwtService::generate_token_para(usuario).unwrap();
+            request = request.header("authorization", format!("Bearer {}", token));
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sin_token_responde_401() {
+        let respuesta = get_sala("sala-1", None).await;
+
+        assert_eq!(respuesta.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn el_gestor_entra_solo_en_sus_salas() {
+        let gestor = gestor();
+
+        let propia = get_sala("sala-1", Some(&gestor)).await;
+        let ajena = get_sala("sala-2", Some(&gestor)).await;
+
+        assert_eq!(propia.status(), StatusCode::OK);
+        assert_eq!(ajena.status(), StatusCode::FORBIDDEN);
+        assert_eq!(ajena.extensions().get::<Idioma>(), Some(&Idioma::En));
+    }
+}
@@ -0,0 +1,43 @@
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// Bytes aleatorios de cada token (se codifican en hex: 64 caracteres)
+const TOKEN_BYTES: usize = 32;
+
+/// Servicio para generar tokens de un solo uso (enlaces enviados por correo)
+///
+/// El token en claro solo se entrega al usuario; lo que se guarda es su hash
+/// SHA-256, así que una copia de los datos no permite canjearlos.
+pub struct TokenService;
+
+impl TokenService {
+    /// Genera un token aleatorio y devuelve `(token, hash)`
+    pub fn generar() -> (String, String) {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+        let hash = Self::hash(&token);
+        (token, hash)
+    }
+
+    /// Hash con el que se guarda y se busca un token
+    pub fn hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.trim().as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generar_tokens_distintos_con_su_hash() {
+        let (token, hash) = TokenService::generar();
+        let (otro, _) = TokenService::generar();
+
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, otro);
+        assert_eq!(TokenService::hash(&token), hash);
+        assert_ne!(hash, token);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::token::TokenService;
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand_core::{OsRng, RngCore};
+use sha1::Sha1;
+
+/// Segundos que vale cada código
+pub const PERIODO_TOTP: u64 = 30;
+
+/// Cifras de cada código
+const DIGITOS: u32 = 6;
+
+/// Pasos de margen a cada lado por si el reloj del móvil va desfasado
+const MARGEN_PASOS: u64 = 1;
+
+/// Bytes del secreto (160 bits, lo que recomienda el RFC 4226 para SHA-1)
+const BYTES_SECRETO: usize = 20;
+
+/// Códigos de recuperación que se entregan al activar el segundo factor
+pub const TOTAL_CODIGOS_RECUPERACION: usize = 10;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Códigos de un solo uso basados en el tiempo (RFC 6238), compatibles con
+/// las apps de autenticación habituales
+pub struct TotpService;
+
+impl TotpService {
+    /// Genera un secreto aleatorio en base32
+    pub fn generar_secreto() -> String {
+        let mut bytes = [0u8; BYTES_SECRETO];
+        OsRng.fill_bytes(&mut bytes);
+        BASE32_NOPAD.encode(&bytes)
+    }
+
+    /// URL `otpauth://` que las apps leen del código QR
+    pub fn url_otpauth(secreto: &str, emisor: &str, cuenta: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            codificar(emisor),
+            codificar(cuenta),
+            secreto,
+            codificar(emisor),
+            DIGITOS,
+            PERIODO_TOTP
+        )
+    }
+
+    /// Paso de tiempo al que pertenece un instante
+    pub fn paso(instante: DateTime<Utc>) -> u64 {
+        instante.timestamp().max(0) as u64 / PERIODO_TOTP
+    }
+
+    /// Código de un paso; `None` si el secreto no es base32 válido
+    pub fn codigo(secreto: &str, paso: u64) -> Option<String> {
+        let clave = BASE32_NOPAD
+            .decode(secreto.trim_end_matches('=').as_bytes())
+            .ok()?;
+        let mut mac = HmacSha1::new_from_slice(&clave).ok()?;
+        mac.update(&paso.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Truncado dinámico (RFC 4226, sección 5.3)
+        let desplazamiento = (hash[hash.len() - 1] & 0x0f) as usize;
+        let valor = u32::from_be_bytes([
+            hash[desplazamiento] & 0x7f,
+            hash[desplazamiento + 1],
+            hash[desplazamiento + 2],
+            hash[desplazamiento + 3],
+        ]);
+
+        Some(format!(
+            "{:0ancho$}",
+            valor % 10u32.pow(DIGITOS),
+            ancho = DIGITOS as usize
+        ))
+    }
+
+    /// Paso cuyo código coincide con el introducido, dentro del margen
+    ///
+    /// Quien llama debe rechazar un paso ya usado para que el mismo código no
+    /// sirva dos veces.
+    pub fn verificar(secreto: &str, codigo: &str, ahora: DateTime<Utc>) -> Option<u64> {
+        let codigo: String = codigo.chars().filter(|c| !c.is_whitespace()).collect();
+        if codigo.len() != DIGITOS as usize {
+            return None;
+        }
+
+        let actual = Self::paso(ahora);
+        (actual.saturating_sub(MARGEN_PASOS)..=actual + MARGEN_PASOS).find(|paso| {
+            Self::codigo(secreto, *paso)
+                .is_some_and(|esperado| iguales(esperado.as_bytes(), codigo.as_bytes()))
+        })
+    }
+
+    /// Genera los códigos de recuperación y devuelve `(códigos, hashes)`
+    ///
+    /// Los códigos solo se muestran una vez; se guardan sus hashes.
+    pub fn generar_codigos_recuperacion() -> (Vec<String>, Vec<String>) {
+        (0..TOTAL_CODIGOS_RECUPERACION)
+            .map(|_| {
+                let mut bytes = [0u8; 5];
+                OsRng.fill_bytes(&mut bytes);
+                let hex = hex::encode(bytes);
+                let codigo = format!("{}-{}", &hex[..5], &hex[5..]);
+                let hash = Self::hash_codigo_recuperacion(&codigo);
+                (codigo, hash)
+            })
+            .unzip()
+    }
+
+    /// Hash con el que se guarda un código de recuperación (sin guiones ni
+    /// espacios y en minúsculas, como se haya tecleado)
+    pub fn hash_codigo_recuperacion(codigo: &str) -> String {
+        let normalizado: String = codigo
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        TokenService::hash(&normalizado)
+    }
+}
+
+/// Compara en tiempo constante
+fn iguales(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Codifica un texto para la URL `otpauth://` (todo salvo lo no reservado)
+fn codificar(texto: &str) -> String {
+    texto
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Secreto `12345678901234567890` de los vectores de prueba del RFC 6238
+    const SECRETO_RFC: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_vectores_del_rfc_6238() {
+        // El RFC da 8 cifras; con 6 son las 6 últimas
+        for (segundos, esperado) in [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+        ] {
+            let instante = Utc.timestamp_opt(segundos, 0).unwrap();
+            assert_eq!(
+                TotpService::codigo(SECRETO_RFC, TotpService::paso(instante)).unwrap(),
+                esperado
+            );
+        }
+    }
+
+    #[test]
+    fn test_verificar_admite_un_paso_de_desfase() {
+        let ahora = Utc.timestamp_opt(1_234_567_890, 0).unwrap();
+        let paso = TotpService::paso(ahora);
+        let anterior = TotpService::codigo(SECRETO_RFC, paso - 1).unwrap();
+        let lejano = TotpService::codigo(SECRETO_RFC, paso - 2).unwrap();
+
+        assert_eq!(
+            TotpService::verificar(SECRETO_RFC, "005 924", ahora),
+            Some(paso)
+        );
+        assert_eq!(
+            TotpService::verificar(SECRETO_RFC, &anterior, ahora),
+            Some(paso - 1)
+        );
+        assert_eq!(TotpService::verificar(SECRETO_RFC, &lejano, ahora), None);
+        assert_eq!(TotpService::verificar(SECRETO_RFC, "12345", ahora), None);
+    }
+
+    #[test]
+    fn test_secreto_y_url_otpauth() {
+        let secreto = TotpService::generar_secreto();
+        assert_eq!(secreto.len(), 32);
+        assert!(TotpService::codigo(&secreto, 1).is_some());
+
+        let url = TotpService::url_otpauth(&secreto, "Reservas", "ana@empresa.com");
+        assert!(url.starts_with("otpauth://totp/Reservas:ana%40empresa.com?secret="));
+        assert!(url.contains("&issuer=Reservas"));
+    }
+
+    #[test]
+    fn test_codigos_de_recuperacion() {
+        let (codigos, hashes) = TotpService::generar_codigos_recuperacion();
+
+        assert_eq!(codigos.len(), TOTAL_CODIGOS_RECUPERACION);
+        assert_eq!(codigos[0].len(), 11);
+        assert_eq!(
+            TotpService::hash_codigo_recuperacion(&codigos[0].to_uppercase().replace('-', " ")),
+            hashes[0]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::error::UsuarioError;
+use crate::permiso::Permiso;
+use crate::usuario::validar_nombre;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Prefijo de las claves en claro, para reconocerlas en la cabecera y en los logs
+pub const PREFIJO_API_KEY: &str = "rsk_";
+
+/// Caracteres de la clave en claro que se guardan para identificarla en los listados
+const LONGITUD_PREFIJO_VISIBLE: usize = 12;
+
+/// Cada cuánto se guarda como mucho el último uso de una clave
+///
+/// Las integraciones llaman con frecuencia; no hace falta escribir en cada
+/// petición para saber si una clave sigue en uso.
+pub const INTERVALO_REGISTRO_USO: Duration = Duration::minutes(1);
+
+/// Permisos que nunca se conceden a una clave: con ellos podría crear otras
+const PERMISOS_NO_DELEGABLES: &[Permiso] = &[Permiso::GestionarUsuarios];
+
+/// Clave de API de una cuenta de servicio
+///
+/// La crea un administrador para integraciones que no pueden hacer login
+/// (pantallas de sala, scripts de informes). Solo tiene los permisos de su
+/// alcance y, como el resto de tokens, solo se guarda el hash de la clave.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiKey {
+    pub id: String,
+    /// Nombre descriptivo de la integración
+    pub nombre: String,
+    /// Inicio de la clave en claro (p. ej. `rsk_1a2b3c4d`), para reconocerla
+    pub prefijo: String,
+    /// Hash SHA-256 (hex) de la clave
+    pub hash: String,
+    /// Permisos que concede la clave
+    pub permisos: Vec<Permiso>,
+    /// Administrador que la creó
+    pub creada_por: String,
+    pub creada: DateTime<Utc>,
+    #[serde(default)]
+    pub ultimo_uso: Option<DateTime<Utc>>,
+    /// Cuándo se revocó; una clave revocada ya no autentica
+    #[serde(default)]
+    pub revocada: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Crea una clave a partir de la clave en claro ya generada y su hash
+    ///
+    /// # Errores
+    /// Si el nombre no es válido o el alcance incluye permisos no delegables
+    pub fn new(
+        nombre: String,
+        clave: &str,
+        hash: String,
+        permisos: Vec<Permiso>,
+        creada_por: String,
+        ahora: DateTime<Utc>,
+    ) -> Result<Self, UsuarioError> {
+        let nombre = nombre.trim().to_string();
+        validar_nombre(&nombre)?;
+
+        if let Some(permiso) = permisos.iter().find(|p| PERMISOS_NO_DELEGABLES.contains(p)) {
+            return Err(UsuarioError::PermisoNoDelegable(
+                permiso.as_str().to_string(),
+            ));
+        }
+
+        let mut unicos: Vec<Permiso> = Vec::with_capacity(permisos.len());
+        for permiso in permisos {
+            if !unicos.contains(&permiso) {
+                unicos.push(permiso);
+            }
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            nombre,
+            prefijo: clave.chars().take(LONGITUD_PREFIJO_VISIBLE).collect(),
+            hash,
+            permisos: unicos,
+            creada_por,
+            creada: ahora,
+            ultimo_uso: None,
+            revocada: None,
+        })
+    }
+
+    /// Indica si todavía autentica
+    pub fn es_valida(&self) -> bool {
+        self.revocada.is_none()
+    }
+
+    /// Sustituye al creador por un seudónimo si la creó `usuario_id`;
+    /// devuelve si ha cambiado
+    pub fn seudonimizar(&mut self, usuario_id: &str, seudonimo: &str) -> bool {
+        if self.creada_por != usuario_id {
+            return false;
+        }
+        self.creada_por = seudonimo.to_string();
+        true
+    }
+
+    /// Revoca la clave; revocarla otra vez no cambia la fecha
+    pub fn revocar(&mut self, ahora: DateTime<Utc>) {
+        self.revocada.get_or_insert(ahora);
+    }
+
+    /// Anota un uso de la clave
+    ///
+    /// Devuelve `true` si ha cambiado y hay que guardarla, es decir, si el
+    /// último uso anotado es anterior a [`INTERVALO_REGISTRO_USO`].
+    pub fn registrar_uso(&mut self, ahora: DateTime<Utc>) -> bool {
+        match self.ultimo_uso {
+            Some(ultimo) if ahora - ultimo < INTERVALO_REGISTRO_USO => false,
+            _ => {
+                self.ultimo_uso = Some(ahora);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clave(permisos: Vec<Permiso>) -> Result<ApiKey, UsuarioError> {
+        ApiKey::new(
+            "Pantalla sala 1".to_string(),
+            "rsk_0123456789abcdef",
+            "hash".to_string(),
+            permisos,
+            "admin".to_string(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn seudonimiza_solo_al_creador() {
+        let mut clave = clave(Vec::new()).unwrap();
+
+        assert!(!clave.seudonimizar("otro", "eliminado-1"));
+        assert_eq!(clave.creada_por, "admin");
+        assert!(clave.seudonimizar("admin", "eliminado-1"));
+        assert_eq!(clave.creada_por, "eliminado-1");
+        assert!(clave.es_valida());
+    }
+
+    #[test]
+    fn guarda_el_prefijo_y_permisos_sin_repetir() {
+        let clave = clave(vec![
+            Permiso::VerCualquierReserva,
+            Permiso::VerCualquierReserva,
+            Permiso::VerInformes,
+        ])
+        .unwrap();
+
+        assert_eq!(clave.prefijo, "rsk_01234567");
+        assert_eq!(
+            clave.permisos,
+            vec![Permiso::VerCualquierReserva, Permiso::VerInformes]
+        );
+        assert!(clave.es_valida());
+    }
+
+    #[test]
+    fn no_delega_la_gestion_de_usuarios() {
+        let resultado = clave(vec![Permiso::GestionarUsuarios]);
+
+        assert!(matches!(
+            resultado,
+            Err(UsuarioError::PermisoNoDelegable(p)) if p == "usuario:manage"
+        ));
+    }
+
+    #[test]
+    fn registra_el_uso_como_mucho_una_vez_por_intervalo() {
+        let mut clave = clave(Vec::new()).unwrap();
+        let ahora = Utc::now();
+
+        assert!(clave.registrar_uso(ahora));
+        assert!(!clave.registrar_uso(ahora + Duration::seconds(30)));
+        assert!(clave.registrar_uso(ahora + INTERVALO_REGISTRO_USO));
+        assert_eq!(clave.ultimo_uso, Some(ahora + INTERVALO_REGISTRO_USO));
+    }
+
+    #[test]
+    fn revocar_conserva_la_primera_fecha() {
+        let mut clave = clave(Vec::new()).unwrap();
+        let ahora = Utc::now();
+
+        clave.revocar(ahora);
+        clave.revocar(ahora + Duration::hours(1));
+
+        assert!(!clave.es_valida());
+        assert_eq!(clave.revocada, Some(ahora));
+    }
+}
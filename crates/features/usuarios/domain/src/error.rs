@@ -1,3 +1,4 @@
+use shared_errores::{Categoria, ErrorCampo, ErrorDominio, Idioma};
 use thiserror::Error;
 
 /// Errores relacionados con el dominio de usuarios
@@ -36,56 +37,233 @@ pub enum UsuarioError {
 
     #[error("Error de validación: {0}")]
     ValidacionError(String),
+
+    #[error("La zona horaria '{0}' no es válida")]
+    ZonaHorariaInvalida(String),
+
+    #[error("Cuenta bloqueada temporalmente; inténtalo de nuevo en {minutos} minutos")]
+    CuentaBloqueada { minutos: i64 },
+
+    #[error("Demasiados intentos de login desde esta dirección; espera {minutos} minutos")]
+    DemasiadosIntentos { minutos: i64 },
+
+    #[error("El enlace no es válido o ha caducado")]
+    TokenInvalido,
+
+    #[error("No se ha podido enviar el correo: {0}")]
+    ErrorCorreo(String),
+
+    #[error("Clave de API no encontrada con ID: {0}")]
+    ApiKeyNoEncontrada(String),
+
+    #[error("El permiso '{0}' no se puede conceder a una clave de API")]
+    PermisoNoDelegable(String),
+
+    #[error("El inicio de sesión con el proveedor de identidad no es válido o ha caducado")]
+    OidcEstadoInvalido,
+
+    #[error("El proveedor de identidad ha rechazado el inicio de sesión: {0}")]
+    IdentidadExternaRechazada(String),
+
+    #[error("El email '{0}' ya tiene cuenta y el proveedor de identidad no lo ha verificado")]
+    EmailExternoNoVerificado(String),
+
+    #[error("No se ha podido contactar con el proveedor de identidad: {0}")]
+    ProveedorIdentidadNoDisponible(String),
+
+    #[error("La contraseña de esta cuenta se gestiona en el directorio de la empresa")]
+    PasswordGestionadaExternamente,
+
+    #[error("El código de verificación no es correcto")]
+    CodigoSegundoFactorInvalido,
+
+    #[error("El inicio de sesión ha caducado; vuelve a introducir la contraseña")]
+    DesafioSegundoFactorInvalido,
+
+    #[error("Esta cuenta debe entrar con verificación en dos pasos")]
+    SegundoFactorRequerido,
+
+    #[error("La verificación en dos pasos ya está activa")]
+    SegundoFactorYaActivo,
+
+    #[error("No hay ninguna verificación en dos pasos iniciada")]
+    SegundoFactorNoIniciado,
+
+    #[error("La verificación en dos pasos es obligatoria para este rol")]
+    SegundoFactorObligatorio,
+
+    #[error("El horario laboral debe terminar después de empezar e incluir algún día")]
+    HorarioLaboralInvalido,
+
+    #[error("El email de esta cuenta lo gestiona el proveedor de identidad de la empresa")]
+    EmailGestionadoExternamente,
+
+    #[error("No se puede eliminar al único administrador activo")]
+    UltimoAdministrador,
+
+    #[error("Grupo no encontrado con ID: {0}")]
+    GrupoNoEncontrado(String),
+
+    #[error("Un grupo no puede quedarse sin miembros")]
+    UltimoMiembroGrupo,
+
+    #[error("No puedes autorizarte a ti mismo a reservar en tu nombre")]
+    DelegadoPropio,
+
+    /// Errores en campos concretos de la petición (p. ej. parámetros de un listado)
+    #[error("Errores de validación en {} campos", .0.len())]
+    Validacion(Vec<ErrorCampo>),
 }
 
 impl UsuarioError {
-    /// Obtiene un mensaje amigable para mostrar al usuario
+    /// Obtiene un mensaje amigable para mostrar al usuario (en español)
     pub fn mensaje_usuario(&self) -> String {
+        self.mensaje_en(Idioma::Es)
+    }
+}
+
+impl ErrorDominio for UsuarioError {
+    fn codigo(&self) -> &'static str {
         match self {
-            UsuarioError::EmailInvalido(email) => {
-                format!(
-                    "El email '{}' no es válido. Por favor, ingresa un email correcto.",
-                    email
-                )
+            UsuarioError::EmailInvalido(_) => "USUARIO_EMAIL_INVALIDO",
+            UsuarioError::EmailDuplicado(_) => "USUARIO_EMAIL_DUPLICADO",
+            UsuarioError::NombreVacio => "USUARIO_NOMBRE_VACIO",
+            UsuarioError::NombreLongitudInvalida { .. } => "USUARIO_NOMBRE_LONGITUD",
+            UsuarioError::ContrasenaDemasiadoCorta { .. } => "USUARIO_PASSWORD_DEMASIADO_CORTA",
+            UsuarioError::UsuarioNoEncontrado(_) => "USUARIO_NO_ENCONTRADO",
+            UsuarioError::CredencialesInvalidas => "USUARIO_CREDENCIALES_INVALIDAS",
+            UsuarioError::PermisosDenegados => "USUARIO_PERMISOS_DENEGADOS",
+            UsuarioError::ErrorRepositorio(_) => "USUARIO_ERROR_REPOSITORIO",
+            UsuarioError::ValidacionError(_) => "USUARIO_VALIDACION",
+            UsuarioError::ZonaHorariaInvalida(_) => "USUARIO_ZONA_HORARIA_INVALIDA",
+            UsuarioError::Validacion(_) => "USUARIO_VALIDACION_CAMPOS",
+            UsuarioError::CuentaBloqueada { .. } => "USUARIO_CUENTA_BLOQUEADA",
+            UsuarioError::DemasiadosIntentos { .. } => "USUARIO_DEMASIADOS_INTENTOS",
+            UsuarioError::TokenInvalido => "USUARIO_TOKEN_INVALIDO",
+            UsuarioError::ErrorCorreo(_) => "USUARIO_ERROR_CORREO",
+            UsuarioError::ApiKeyNoEncontrada(_) => "USUARIO_API_KEY_NO_ENCONTRADA",
+            UsuarioError::PermisoNoDelegable(_) => "USUARIO_PERMISO_NO_DELEGABLE",
+            UsuarioError::OidcEstadoInvalido => "USUARIO_OIDC_ESTADO_INVALIDO",
+            UsuarioError::IdentidadExternaRechazada(_) => "USUARIO_IDENTIDAD_EXTERNA_RECHAZADA",
+            UsuarioError::EmailExternoNoVerificado(_) => "USUARIO_EMAIL_EXTERNO_NO_VERIFICADO",
+            UsuarioError::ProveedorIdentidadNoDisponible(_) => {
+                "USUARIO_PROVEEDOR_IDENTIDAD_NO_DISPONIBLE"
             }
-            UsuarioError::EmailDuplicado(email) => {
-                format!(
-                    "El email '{}' ya está registrado. Intenta con otro email o inicia sesión.",
-                    email
-                )
-            }
-            UsuarioError::NombreVacio => {
-                "El nombre no puede estar vacío. Por favor, ingresa tu nombre.".to_string()
+            UsuarioError::PasswordGestionadaExternamente => "USUARIO_PASSWORD_EXTERNA",
+            UsuarioError::CodigoSegundoFactorInvalido => "USUARIO_CODIGO_SEGUNDO_FACTOR_INVALIDO",
+            UsuarioError::DesafioSegundoFactorInvalido => "USUARIO_DESAFIO_SEGUNDO_FACTOR_INVALIDO",
+            UsuarioError::SegundoFactorRequerido => "USUARIO_SEGUNDO_FACTOR_REQUERIDO",
+            UsuarioError::SegundoFactorYaActivo => "USUARIO_SEGUNDO_FACTOR_YA_ACTIVO",
+            UsuarioError::SegundoFactorNoIniciado => "USUARIO_SEGUNDO_FACTOR_NO_INICIADO",
+            UsuarioError::SegundoFactorObligatorio => "USUARIO_SEGUNDO_FACTOR_OBLIGATORIO",
+            UsuarioError::HorarioLaboralInvalido => "USUARIO_HORARIO_LABORAL_INVALIDO",
+            UsuarioError::EmailGestionadoExternamente => "USUARIO_EMAIL_EXTERNO",
+            UsuarioError::UltimoAdministrador => "USUARIO_ULTIMO_ADMINISTRADOR",
+            UsuarioError::GrupoNoEncontrado(_) => "USUARIO_GRUPO_NO_ENCONTRADO",
+            UsuarioError::UltimoMiembroGrupo => "USUARIO_GRUPO_ULTIMO_MIEMBRO",
+            UsuarioError::DelegadoPropio => "USUARIO_DELEGADO_PROPIO",
+        }
+    }
+
+    fn categoria(&self) -> Categoria {
+        match self {
+            UsuarioError::EmailInvalido(_)
+            | UsuarioError::NombreVacio
+            | UsuarioError::NombreLongitudInvalida { .. }
+            | UsuarioError::ContrasenaDemasiadoCorta { .. }
+            | UsuarioError::ValidacionError(_)
+            | UsuarioError::ZonaHorariaInvalida(_)
+            | UsuarioError::TokenInvalido
+            | UsuarioError::PermisoNoDelegable(_)
+            | UsuarioError::HorarioLaboralInvalido
+            | UsuarioError::DelegadoPropio
+            | UsuarioError::Validacion(_) => Categoria::Validacion,
+            UsuarioError::EmailDuplicado(_) => Categoria::Duplicado,
+            UsuarioError::EmailExternoNoVerificado(_)
+            | UsuarioError::PasswordGestionadaExternamente
+            | UsuarioError::EmailGestionadoExternamente
+            | UsuarioError::UltimoAdministrador
+            | UsuarioError::UltimoMiembroGrupo
+            | UsuarioError::SegundoFactorYaActivo
+            | UsuarioError::SegundoFactorNoIniciado
+            | UsuarioError::SegundoFactorObligatorio => Categoria::Conflicto,
+            UsuarioError::UsuarioNoEncontrado(_)
+            | UsuarioError::ApiKeyNoEncontrada(_)
+            | UsuarioError::GrupoNoEncontrado(_) => Categoria::NoEncontrado,
+            UsuarioError::CredencialesInvalidas
+            | UsuarioError::OidcEstadoInvalido
+            | UsuarioError::IdentidadExternaRechazada(_)
+            | UsuarioError::CodigoSegundoFactorInvalido
+            | UsuarioError::DesafioSegundoFactorInvalido
+            | UsuarioError::SegundoFactorRequerido => Categoria::NoAutenticado,
+            UsuarioError::PermisosDenegados => Categoria::PermisoDenegado,
+            UsuarioError::CuentaBloqueada { .. } | UsuarioError::DemasiadosIntentos { .. } => {
+                Categoria::DemasiadasPeticiones
             }
-            UsuarioError::NombreLongitudInvalida { min, max, actual } => {
-                format!(
-                    "El nombre debe tener entre {} y {} caracteres (actualmente tiene {}).",
-                    min, max, actual
-                )
+            UsuarioError::ErrorRepositorio(_)
+            | UsuarioError::ErrorCorreo(_)
+            | UsuarioError::ProveedorIdentidadNoDisponible(_) => Categoria::Interno,
+        }
+    }
+
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        match self {
+            UsuarioError::EmailInvalido(email)
+            | UsuarioError::EmailDuplicado(email)
+            | UsuarioError::EmailExternoNoVerificado(email) => {
+                vec![("email", email.clone())]
             }
+            UsuarioError::NombreLongitudInvalida { min, max, actual } => vec![
+                ("min", min.to_string()),
+                ("max", max.to_string()),
+                ("actual", actual.to_string()),
+            ],
             UsuarioError::ContrasenaDemasiadoCorta { min, actual } => {
-                format!(
-                    "La contraseña debe tener al menos {} caracteres (actualmente tiene {}).",
-                    min, actual
-                )
-            }
-            UsuarioError::UsuarioNoEncontrado(id) => {
-                format!("No se encontró el usuario con ID: {}", id)
-            }
-            UsuarioError::CredencialesInvalidas => {
-                "Email o contraseña incorrectos. Por favor, verifica tus credenciales.".to_string()
+                vec![("min", min.to_string()), ("actual", actual.to_string())]
             }
-            UsuarioError::PermisosDenegados => {
-                "No tienes permisos para realizar esta acción.".to_string()
+            UsuarioError::UsuarioNoEncontrado(id)
+            | UsuarioError::ApiKeyNoEncontrada(id)
+            | UsuarioError::GrupoNoEncontrado(id) => vec![("id", id.clone())],
+            UsuarioError::PermisoNoDelegable(permiso) => vec![("permiso", permiso.clone())],
+            UsuarioError::ZonaHorariaInvalida(zona) => vec![("zona", zona.clone())],
+            UsuarioError::CuentaBloqueada { minutos }
+            | UsuarioError::DemasiadosIntentos { minutos } => {
+                vec![("minutos", minutos.to_string())]
             }
-            UsuarioError::ErrorRepositorio(msg) => {
-                format!("Error al acceder a los datos: {}", msg)
-            }
-            UsuarioError::ValidacionError(msg) => {
-                format!("Error de validación: {}", msg)
+            UsuarioError::ErrorRepositorio(msg)
+            | UsuarioError::ValidacionError(msg)
+            | UsuarioError::ErrorCorreo(msg)
+            | UsuarioError::IdentidadExternaRechazada(msg)
+            | UsuarioError::ProveedorIdentidadNoDisponible(msg) => {
+                vec![("detalle", msg.clone())]
             }
+            _ => Vec::new(),
         }
     }
+
+    fn campos(&self) -> Vec<ErrorCampo> {
+        let campo = match self {
+            UsuarioError::Validacion(campos) => return campos.clone(),
+            UsuarioError::EmailInvalido(_)
+            | UsuarioError::EmailDuplicado(_)
+            | UsuarioError::EmailGestionadoExternamente => "email",
+            UsuarioError::NombreVacio | UsuarioError::NombreLongitudInvalida { .. } => "nombre",
+            UsuarioError::ContrasenaDemasiadoCorta { .. } => "password",
+            UsuarioError::ZonaHorariaInvalida(_) => "zona_horaria",
+            UsuarioError::TokenInvalido => "token",
+            UsuarioError::PermisoNoDelegable(_) => "permisos",
+            UsuarioError::CodigoSegundoFactorInvalido => "codigo",
+            UsuarioError::HorarioLaboralInvalido => "horario_laboral",
+            UsuarioError::DelegadoPropio => "usuario_id",
+            _ => return Vec::new(),
+        };
+
+        vec![ErrorCampo::new(
+            campo,
+            self.codigo(),
+            self.mensaje_usuario(),
+        )]
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +282,35 @@ mod tests {
         let error = UsuarioError::CredencialesInvalidas;
         assert!(error.mensaje_usuario().contains("incorrectos"));
     }
+
+    #[test]
+    fn test_codigo_y_campo_email_duplicado() {
+        let error = UsuarioError::EmailDuplicado("ana@test.com".to_string());
+
+        assert_eq!(error.codigo(), "USUARIO_EMAIL_DUPLICADO");
+        assert_eq!(error.categoria(), Categoria::Duplicado);
+        assert_eq!(error.campos()[0].campo, "email");
+    }
+
+    #[test]
+    fn test_cuenta_bloqueada_es_demasiadas_peticiones() {
+        let error = UsuarioError::CuentaBloqueada { minutos: 15 };
+
+        assert_eq!(error.categoria(), Categoria::DemasiadasPeticiones);
+        assert!(error.mensaje_usuario().contains("15 minutos"));
+    }
+
+    #[test]
+    fn test_mensaje_en_ingles_con_parametros() {
+        let error = UsuarioError::ContrasenaDemasiadoCorta { min: 8, actual: 3 };
+
+        assert_eq!(
+            error.mensaje_en(Idioma::En),
+            "The password must be at least 8 characters long (currently 3)."
+        );
+        assert_eq!(
+            error.campos_en(Idioma::En)[0].mensaje,
+            error.mensaje_en(Idioma::En)
+        );
+    }
 }
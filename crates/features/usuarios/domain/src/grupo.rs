@@ -0,0 +1,103 @@
+use crate::error::UsuarioError;
+use crate::usuario::validar_nombre;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Grupo de usuarios (un equipo) que puede ser dueño de reservas
+///
+/// Cualquier miembro gestiona las reservas del grupo, así una reunión no
+/// depende de que esté disponible quien la reservó.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Grupo {
+    pub id: String,
+    pub nombre: String,
+    /// IDs de los usuarios miembros, sin repetidos
+    pub miembros: Vec<String>,
+    /// Usuario que lo creó
+    pub creado_por: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Grupo {
+    /// Crea el grupo con quien lo crea como único miembro
+    pub fn new(
+        nombre: String,
+        creado_por: String,
+        ahora: DateTime<Utc>,
+    ) -> Result<Self, UsuarioError> {
+        let nombre = nombre.trim().to_string();
+        validar_nombre(&nombre)?;
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            nombre,
+            miembros: vec![creado_por.clone()],
+            creado_por,
+            created_at: ahora,
+        })
+    }
+
+    pub fn es_miembro(&self, usuario_id: &str) -> bool {
+        self.miembros.iter().any(|m| m == usuario_id)
+    }
+
+    /// Añade un miembro; devuelve `false` si ya lo era
+    pub fn agregar_miembro(&mut self, usuario_id: &str) -> bool {
+        if self.es_miembro(usuario_id) {
+            return false;
+        }
+        self.miembros.push(usuario_id.to_string());
+        true
+    }
+
+    /// Quita un miembro
+    ///
+    /// # Errores
+    /// Si es el último: sin miembros nadie salvo un administrador podría
+    /// gestionar las reservas del grupo
+    pub fn quitar_miembro(&mut self, usuario_id: &str) -> Result<(), UsuarioError> {
+        if self.miembros.len() == 1 && self.es_miembro(usuario_id) {
+            return Err(UsuarioError::UltimoMiembroGrupo);
+        }
+        self.miembros.retain(|m| m != usuario_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn el_creador_es_el_primer_miembro() {
+        let mut grupo =
+            Grupo::new(" Marketing ".to_string(), "ana".to_string(), Utc::now()).unwrap();
+
+        assert_eq!(grupo.nombre, "Marketing");
+        assert!(grupo.es_miembro("ana"));
+        assert!(grupo.agregar_miembro("luis"));
+        assert!(!grupo.agregar_miembro("luis"));
+        assert_eq!(grupo.miembros, vec!["ana", "luis"]);
+
+        assert!(matches!(
+            Grupo::new("  ".to_string(), "ana".to_string(), Utc::now()),
+            Err(UsuarioError::NombreVacio)
+        ));
+    }
+
+    #[test]
+    fn no_se_queda_sin_miembros() {
+        let mut grupo = Grupo::new("Marketing".to_string(), "ana".to_string(), Utc::now()).unwrap();
+        grupo.agregar_miembro("luis");
+
+        grupo.quitar_miembro("ana").unwrap();
+        assert_eq!(grupo.miembros, vec!["luis"]);
+        assert!(matches!(
+            grupo.quitar_miembro("luis"),
+            Err(UsuarioError::UltimoMiembroGrupo)
+        ));
+        // Quitar a quien no es miembro no cambia nada
+        grupo.quitar_miembro("eva").unwrap();
+    }
+}
@@ -1,8 +1,24 @@
+pub mod api_key;
 pub mod error;
+pub mod grupo;
+pub mod permiso;
+pub mod politica_password;
+pub mod preferencias;
 pub mod rol;
+pub mod segundo_factor;
+pub mod token;
 pub mod usuario;
 
+pub use api_key::{ApiKey, INTERVALO_REGISTRO_USO, PREFIJO_API_KEY};
 pub use error::UsuarioError;
+pub use grupo::Grupo;
+pub use permiso::Permiso;
+pub use politica_password::{ClaseCaracter, PoliticaPassword, PASSWORDS_COMUNES};
+pub use preferencias::{nombre_dia, parsear_dia, HorarioLaboral, Notificaciones, Preferencias};
 pub use rol::Rol;
-pub use usuario::{validar_email, validar_nombre, validar_password, Usuario, UsuarioPublico};
+pub use segundo_factor::{PoliticaSegundoFactor, SegundoFactor};
+pub use token::{PropositoToken, TokenUsuario};
+pub use usuario::{
+    parsear_zona_horaria, validar_email, validar_nombre, validar_password, Usuario, UsuarioPublico,
+};
 pub use usuario::{NOMBRE_MAX_LENGTH, NOMBRE_MIN_LENGTH, PASSWORD_MIN_LENGTH};
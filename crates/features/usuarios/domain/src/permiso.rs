@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Acciones protegidas del sistema
+///
+/// Los roles se traducen a permisos con [`Rol::permisos`](crate::Rol::permisos);
+/// los gestores de salas obtienen además los permisos de sala
+/// ([`Permiso::es_de_sala`]) solo sobre las salas que gestionan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permiso {
+    /// Crear, modificar, activar y desactivar salas (`sala:manage`)
+    GestionarSalas,
+    /// Aprobar reservas de una sala (`reserva:approve`)
+    ///
+    /// Reservado: se concede y se puede comprobar, pero todavía no hay flujo
+    /// de aprobación que lo exija.
+    AprobarReservas,
+    /// Cancelar o completar reservas de otros usuarios (`reserva:manage_any`)
+    GestionarCualquierReserva,
+    /// Ver las reservas de todos los usuarios (`reserva:read_any`)
+    VerCualquierReserva,
+    /// Hacer reservas propias (`reserva:create`)
+    CrearReservas,
+    /// Administrar cuentas y roles de usuario (`usuario:manage`)
+    GestionarUsuarios,
+    /// Consultar los informes de uso (`informe:read`)
+    VerInformes,
+    /// Registrar y borrar webhooks (`webhook:manage`)
+    GestionarWebhooks,
+}
+
+impl Permiso {
+    /// Todos los permisos, en el orden en que se documentan
+    pub const TODOS: &'static [Permiso] = &[
+        Permiso::GestionarSalas,
+        Permiso::AprobarReservas,
+        Permiso::GestionarCualquierReserva,
+        Permiso::VerCualquierReserva,
+        Permiso::CrearReservas,
+        Permiso::GestionarUsuarios,
+        Permiso::VerInformes,
+        Permiso::GestionarWebhooks,
+    ];
+
+    /// Nombre del permiso en formato `recurso:accion`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permiso::GestionarSalas => "sala:manage",
+            Permiso::AprobarReservas => "reserva:approve",
+            Permiso::GestionarCualquierReserva => "reserva:manage_any",
+            Permiso::VerCualquierReserva => "reserva:read_any",
+            Permiso::CrearReservas => "reserva:create",
+            Permiso::GestionarUsuarios => "usuario:manage",
+            Permiso::VerInformes => "informe:read",
+            Permiso::GestionarWebhooks => "webhook:manage",
+        }
+    }
+
+    /// Crea el permiso desde su nombre `recurso:accion`
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        Permiso::TODOS.iter().copied().find(|p| p.as_str() == s)
+    }
+
+    /// Si el permiso se puede conceder solo sobre algunas salas
+    pub fn es_de_sala(&self) -> bool {
+        matches!(
+            self,
+            Permiso::GestionarSalas
+                | Permiso::AprobarReservas
+                | Permiso::GestionarCualquierReserva
+                | Permiso::VerCualquierReserva
+        )
+    }
+}
+
+impl fmt::Display for Permiso {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nombres_ida_y_vuelta() {
+        for permiso in Permiso::TODOS {
+            assert_eq!(Permiso::from_str_opt(permiso.as_str()), Some(*permiso));
+        }
+        assert_eq!(
+            Permiso::from_str_opt(" SALA:MANAGE "),
+            Some(Permiso::GestionarSalas)
+        );
+        assert_eq!(Permiso::from_str_opt("sala:delete"), None);
+    }
+
+    #[test]
+    fn permisos_de_sala() {
+        assert!(Permiso::GestionarSalas.es_de_sala());
+        assert!(Permiso::AprobarReservas.es_de_sala());
+        assert!(!Permiso::GestionarUsuarios.es_de_sala());
+        assert!(!Permiso::CrearReservas.es_de_sala());
+    }
+}
@@ -0,0 +1,246 @@
+use crate::error::UsuarioError;
+use crate::usuario::PASSWORD_MIN_LENGTH;
+use shared_errores::ErrorCampo;
+use std::collections::HashSet;
+
+/// Contraseñas demasiado comunes, incluidas con [`PoliticaPassword::con_comunes`]
+pub const PASSWORDS_COMUNES: &[&str] = &[
+    "123456789",
+    "12345678",
+    "1234567890",
+    "11111111",
+    "00000000",
+    "87654321",
+    "password",
+    "password1",
+    "password123",
+    "passw0rd",
+    "contraseña",
+    "contrasena",
+    "qwertyuiop",
+    "qwerty123",
+    "qwerty12",
+    "1q2w3e4r",
+    "1qaz2wsx",
+    "abc12345",
+    "abcd1234",
+    "iloveyou",
+    "sunshine",
+    "princess",
+    "football",
+    "baseball",
+    "superman",
+    "starwars",
+    "welcome1",
+    "letmein1",
+    "trustno1",
+    "admin123",
+    "administrador",
+    "changeme",
+    "teamoteamo",
+];
+
+/// Tipos de carácter que una política puede exigir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClaseCaracter {
+    Mayuscula,
+    Minuscula,
+    Digito,
+    /// Cualquier carácter que no sea letra ni dígito
+    Simbolo,
+}
+
+impl ClaseCaracter {
+    /// Crea la clase desde un string ("mayuscula", "minuscula", "digito" o "simbolo")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "mayuscula" | "mayúscula" => Some(ClaseCaracter::Mayuscula),
+            "minuscula" | "minúscula" => Some(ClaseCaracter::Minuscula),
+            "digito" | "dígito" => Some(ClaseCaracter::Digito),
+            "simbolo" | "símbolo" => Some(ClaseCaracter::Simbolo),
+            _ => None,
+        }
+    }
+
+    /// Código de error cuando falta un carácter de esta clase
+    pub fn codigo_error(&self) -> &'static str {
+        match self {
+            ClaseCaracter::Mayuscula => "USUARIO_PASSWORD_SIN_MAYUSCULA",
+            ClaseCaracter::Minuscula => "USUARIO_PASSWORD_SIN_MINUSCULA",
+            ClaseCaracter::Digito => "USUARIO_PASSWORD_SIN_DIGITO",
+            ClaseCaracter::Simbolo => "USUARIO_PASSWORD_SIN_SIMBOLO",
+        }
+    }
+
+    fn incluye(&self, c: char) -> bool {
+        match self {
+            ClaseCaracter::Mayuscula => c.is_uppercase(),
+            ClaseCaracter::Minuscula => c.is_lowercase(),
+            ClaseCaracter::Digito => c.is_numeric(),
+            ClaseCaracter::Simbolo => !c.is_alphanumeric(),
+        }
+    }
+}
+
+/// Requisitos de las contraseñas nuevas (registro, cambio y restablecimiento)
+///
+/// Por defecto solo exige [`PASSWORD_MIN_LENGTH`] caracteres, igual que
+/// [`validar_password`](crate::validar_password).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoliticaPassword {
+    /// Longitud mínima en caracteres
+    pub longitud_minima: usize,
+    /// Clases de las que debe haber al menos un carácter
+    pub clases: Vec<ClaseCaracter>,
+    /// Contraseñas prohibidas, en minúsculas
+    prohibidas: HashSet<String>,
+}
+
+impl Default for PoliticaPassword {
+    fn default() -> Self {
+        Self {
+            longitud_minima: PASSWORD_MIN_LENGTH,
+            clases: Vec::new(),
+            prohibidas: HashSet::new(),
+        }
+    }
+}
+
+impl PoliticaPassword {
+    /// Cambia la longitud mínima (nunca por debajo de 1)
+    pub fn with_longitud_minima(mut self, longitud_minima: usize) -> Self {
+        self.longitud_minima = longitud_minima.max(1);
+        self
+    }
+
+    /// Exige al menos un carácter de cada clase indicada
+    pub fn with_clases(mut self, clases: impl IntoIterator<Item = ClaseCaracter>) -> Self {
+        for clase in clases {
+            if !self.clases.contains(&clase) {
+                self.clases.push(clase);
+            }
+        }
+        self
+    }
+
+    /// Añade contraseñas a la lista prohibida (sin distinguir mayúsculas)
+    pub fn with_prohibidas<S: AsRef<str>>(
+        mut self,
+        prohibidas: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.prohibidas.extend(
+            prohibidas
+                .into_iter()
+                .map(|p| p.as_ref().trim().to_lowercase())
+                .filter(|p| !p.is_empty()),
+        );
+        self
+    }
+
+    /// Prohíbe las contraseñas de [`PASSWORDS_COMUNES`]
+    pub fn con_comunes(self) -> Self {
+        self.with_prohibidas(PASSWORDS_COMUNES.iter().copied())
+    }
+
+    /// Número de contraseñas prohibidas
+    pub fn total_prohibidas(&self) -> usize {
+        self.prohibidas.len()
+    }
+
+    /// Comprueba que la contraseña cumple la política
+    ///
+    /// Si es demasiado corta devuelve `ContrasenaDemasiadoCorta`; si no, junta
+    /// en un error de validación todas las clases que faltan y si está prohibida.
+    pub fn validar(&self, password: &str) -> Result<(), UsuarioError> {
+        let longitud = password.chars().count();
+        if longitud < self.longitud_minima {
+            return Err(UsuarioError::ContrasenaDemasiadoCorta {
+                min: self.longitud_minima,
+                actual: longitud,
+            });
+        }
+
+        let mut errores: Vec<ErrorCampo> = self
+            .clases
+            .iter()
+            .filter(|clase| !password.chars().any(|c| clase.incluye(c)))
+            .map(|clase| ErrorCampo::con_codigo("password", clase.codigo_error()))
+            .collect();
+
+        if self.prohibidas.contains(&password.trim().to_lowercase()) {
+            errores.push(ErrorCampo::con_codigo("password", "USUARIO_PASSWORD_COMUN"));
+        }
+
+        if errores.is_empty() {
+            Ok(())
+        } else {
+            Err(UsuarioError::Validacion(errores))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn por_defecto_solo_exige_la_longitud() {
+        let politica = PoliticaPassword::default();
+
+        assert!(politica.validar("password123").is_ok());
+        assert!(matches!(
+            politica.validar("corta"),
+            Err(UsuarioError::ContrasenaDemasiadoCorta { min: 8, actual: 5 })
+        ));
+    }
+
+    #[test]
+    fn exige_las_clases_configuradas() {
+        let politica = PoliticaPassword::default()
+            .with_longitud_minima(10)
+            .with_clases([
+                ClaseCaracter::Mayuscula,
+                ClaseCaracter::Digito,
+                ClaseCaracter::Simbolo,
+            ]);
+
+        let Err(UsuarioError::Validacion(campos)) = politica.validar("solominusculas") else {
+            panic!("se esperaba un error de validación");
+        };
+        let codigos: Vec<&str> = campos.iter().map(|c| c.codigo.as_str()).collect();
+        assert_eq!(
+            codigos,
+            [
+                "USUARIO_PASSWORD_SIN_MAYUSCULA",
+                "USUARIO_PASSWORD_SIN_DIGITO",
+                "USUARIO_PASSWORD_SIN_SIMBOLO"
+            ]
+        );
+
+        assert!(politica.validar("Ñandú-2024!").is_ok());
+    }
+
+    #[test]
+    fn rechaza_las_prohibidas() {
+        let politica = PoliticaPassword::default()
+            .con_comunes()
+            .with_prohibidas(["ReservasSalas2024"]);
+
+        for password in ["Password123", "reservassalas2024"] {
+            let Err(UsuarioError::Validacion(campos)) = politica.validar(password) else {
+                panic!("'{}' debería estar prohibida", password);
+            };
+            assert_eq!(campos[0].codigo, "USUARIO_PASSWORD_COMUN");
+        }
+        assert!(politica.validar("una frase larga").is_ok());
+    }
+
+    #[test]
+    fn clases_desde_texto() {
+        assert_eq!(
+            ClaseCaracter::from_str_opt(" Dígito "),
+            Some(ClaseCaracter::Digito)
+        );
+        assert_eq!(ClaseCaracter::from_str_opt("emoji"), None);
+    }
+}
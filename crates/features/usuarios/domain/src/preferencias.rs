@@ -0,0 +1,214 @@
+use crate::error::UsuarioError;
+use chrono::{NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use shared_errores::ErrorCampo;
+
+/// Preferencias del perfil que no afectan a la seguridad de la cuenta
+///
+/// El idioma y la zona horaria también son preferencias, pero se guardan
+/// directamente en el usuario porque los usan la autenticación y las reservas.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Preferencias {
+    /// Correos que quiere recibir
+    #[serde(default)]
+    pub notificaciones: Notificaciones,
+    /// Sala que se propone al crear una reserva
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sala_predeterminada: Option<String>,
+    /// Franja en la que suele reservar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horario_laboral: Option<HorarioLaboral>,
+}
+
+impl Preferencias {
+    /// Normaliza la sala predeterminada (vacía es ninguna)
+    pub fn new(
+        notificaciones: Notificaciones,
+        sala_predeterminada: Option<String>,
+        horario_laboral: Option<HorarioLaboral>,
+    ) -> Self {
+        Self {
+            notificaciones,
+            sala_predeterminada: sala_predeterminada
+                .map(|sala| sala.trim().to_string())
+                .filter(|sala| !sala.is_empty()),
+            horario_laboral,
+        }
+    }
+}
+
+/// Avisos por correo a los que el usuario está suscrito (todos por defecto)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Notificaciones {
+    /// Al crear o modificar una de sus reservas
+    #[serde(default = "activada")]
+    pub confirmaciones: bool,
+    /// Antes de que empiece una reserva
+    #[serde(default = "activada")]
+    pub recordatorios: bool,
+    /// Cuando otra persona cancela una de sus reservas
+    #[serde(default = "activada")]
+    pub cancelaciones: bool,
+}
+
+fn activada() -> bool {
+    true
+}
+
+impl Default for Notificaciones {
+    fn default() -> Self {
+        Self {
+            confirmaciones: true,
+            recordatorios: true,
+            cancelaciones: true,
+        }
+    }
+}
+
+/// Horario laboral del usuario, en su zona horaria
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HorarioLaboral {
+    pub inicio: NaiveTime,
+    pub fin: NaiveTime,
+    /// Días en que trabaja, de lunes a domingo y sin repetidos
+    pub dias: Vec<Weekday>,
+}
+
+impl HorarioLaboral {
+    /// Crea el horario; el fin debe ser posterior al inicio y debe haber algún día
+    pub fn new(
+        inicio: NaiveTime,
+        fin: NaiveTime,
+        dias: Vec<Weekday>,
+    ) -> Result<Self, UsuarioError> {
+        if fin <= inicio || dias.is_empty() {
+            return Err(UsuarioError::HorarioLaboralInvalido);
+        }
+
+        let mut dias = dias;
+        dias.sort_by_key(Weekday::num_days_from_monday);
+        dias.dedup();
+        Ok(Self { inicio, fin, dias })
+    }
+
+    /// Crea el horario a partir de horas `HH:MM` y nombres de días
+    pub fn parsear(inicio: &str, fin: &str, dias: &[String]) -> Result<Self, UsuarioError> {
+        let mut errores = Vec::new();
+        let mut hora = |campo: &str, texto: &str| {
+            let hora = NaiveTime::parse_from_str(texto.trim(), "%H:%M").ok();
+            if hora.is_none() {
+                errores.push(ErrorCampo::con_codigo(campo, "USUARIO_HORA_INVALIDA"));
+            }
+            hora
+        };
+        let inicio = hora("horario_laboral.inicio", inicio);
+        let fin = hora("horario_laboral.fin", fin);
+
+        let mut validos = Vec::with_capacity(dias.len());
+        for dia in dias {
+            match parsear_dia(dia) {
+                Some(dia) => validos.push(dia),
+                None => errores.push(ErrorCampo::con_codigo(
+                    "horario_laboral.dias",
+                    "USUARIO_DIA_INVALIDO",
+                )),
+            }
+        }
+
+        match (inicio, fin) {
+            (Some(inicio), Some(fin)) if errores.is_empty() => Self::new(inicio, fin, validos),
+            _ => Err(UsuarioError::Validacion(errores)),
+        }
+    }
+}
+
+/// Interpreta un día de la semana en español o en inglés (`lunes`, `mon`, `monday`)
+pub fn parsear_dia(dia: &str) -> Option<Weekday> {
+    match dia.trim().to_lowercase().as_str() {
+        "lunes" => Some(Weekday::Mon),
+        "martes" => Some(Weekday::Tue),
+        "miercoles" | "miércoles" => Some(Weekday::Wed),
+        "jueves" => Some(Weekday::Thu),
+        "viernes" => Some(Weekday::Fri),
+        "sabado" | "sábado" => Some(Weekday::Sat),
+        "domingo" => Some(Weekday::Sun),
+        otro => otro.parse().ok(),
+    }
+}
+
+/// Nombre del día con el que se devuelve en las APIs
+pub fn nombre_dia(dia: Weekday) -> &'static str {
+    match dia {
+        Weekday::Mon => "lunes",
+        Weekday::Tue => "martes",
+        Weekday::Wed => "miercoles",
+        Weekday::Thu => "jueves",
+        Weekday::Fri => "viernes",
+        Weekday::Sat => "sabado",
+        Weekday::Sun => "domingo",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hora(h: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_horario_laboral_ordena_los_dias_y_valida_la_franja() {
+        let horario = HorarioLaboral::new(
+            hora(9),
+            hora(17),
+            vec![Weekday::Fri, Weekday::Mon, Weekday::Fri],
+        )
+        .unwrap();
+        assert_eq!(horario.dias, [Weekday::Mon, Weekday::Fri]);
+
+        assert!(matches!(
+            HorarioLaboral::new(hora(17), hora(9), vec![Weekday::Mon]),
+            Err(UsuarioError::HorarioLaboralInvalido)
+        ));
+        assert!(HorarioLaboral::new(hora(9), hora(17), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_parsear_horario_laboral_señala_cada_campo() {
+        let horario = HorarioLaboral::parsear(
+            "08:30",
+            "15:00",
+            &["viernes".to_string(), "lunes".to_string()],
+        )
+        .unwrap();
+        assert_eq!(horario.inicio, NaiveTime::from_hms_opt(8, 30, 0).unwrap());
+        assert_eq!(horario.dias, [Weekday::Mon, Weekday::Fri]);
+
+        match HorarioLaboral::parsear("8h", "15:00", &["festivo".to_string()]) {
+            Err(UsuarioError::Validacion(errores)) => {
+                let campos: Vec<_> = errores.iter().map(|e| e.campo.as_str()).collect();
+                assert_eq!(campos, ["horario_laboral.inicio", "horario_laboral.dias"]);
+            }
+            otro => panic!("se esperaba un error de validación: {:?}", otro),
+        }
+    }
+
+    #[test]
+    fn test_parsear_dia() {
+        assert_eq!(parsear_dia("Miércoles"), Some(Weekday::Wed));
+        assert_eq!(parsear_dia("sat"), Some(Weekday::Sat));
+        assert_eq!(parsear_dia("festivo"), None);
+        assert_eq!(parsear_dia(nombre_dia(Weekday::Sun)), Some(Weekday::Sun));
+    }
+
+    #[test]
+    fn test_preferencias_antiguas_tienen_todas_las_notificaciones() {
+        let preferencias: Preferencias = serde_json::from_str("{}").unwrap();
+        assert_eq!(preferencias, Preferencias::default());
+        assert!(preferencias.notificaciones.recordatorios);
+
+        let sin_sala = Preferencias::new(Notificaciones::default(), Some("  ".to_string()), None);
+        assert_eq!(sin_sala.sala_predeterminada, None);
+    }
+}
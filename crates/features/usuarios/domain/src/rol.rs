@@ -1,3 +1,4 @@
+use crate::permiso::Permiso;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -10,19 +11,64 @@ pub enum Rol {
     /// Usuario normal - puede crear y gestionar sus propias reservas
     #[default]
     Usuario,
+    /// Gestor de salas - administra las salas que tiene asignadas y sus reservas
+    GestorSalas,
+    /// Recepcionista - gestiona las reservas de todas las salas
+    Recepcionista,
+    /// Solo lectura - consulta reservas e informes sin poder reservar
+    Lectura,
 }
 
+/// Permisos que un gestor tiene sobre cada una de sus salas
+const PERMISOS_GESTOR_EN_SALA: &[Permiso] = &[
+    Permiso::GestionarSalas,
+    Permiso::AprobarReservas,
+    Permiso::GestionarCualquierReserva,
+    Permiso::VerCualquierReserva,
+];
+
 impl Rol {
     /// Verifica si el rol tiene permisos de administrador
     pub fn es_admin(&self) -> bool {
         matches!(self, Rol::Admin)
     }
 
+    /// Permisos que el rol concede sobre todo el sistema
+    pub fn permisos(&self) -> &'static [Permiso] {
+        match self {
+            Rol::Admin => Permiso::TODOS,
+            Rol::Usuario | Rol::GestorSalas => &[Permiso::CrearReservas],
+            Rol::Recepcionista => &[
+                Permiso::AprobarReservas,
+                Permiso::GestionarCualquierReserva,
+                Permiso::VerCualquierReserva,
+                Permiso::CrearReservas,
+            ],
+            Rol::Lectura => &[Permiso::VerCualquierReserva, Permiso::VerInformes],
+        }
+    }
+
+    /// Permisos que el rol concede solo sobre las salas asignadas al usuario
+    pub fn permisos_en_salas_gestionadas(&self) -> &'static [Permiso] {
+        match self {
+            Rol::GestorSalas => PERMISOS_GESTOR_EN_SALA,
+            _ => &[],
+        }
+    }
+
+    /// Verifica si el rol concede el permiso sobre todo el sistema
+    pub fn tiene_permiso(&self, permiso: Permiso) -> bool {
+        self.permisos().contains(&permiso)
+    }
+
     /// Convierte el rol a string para almacenamiento
     pub fn as_str(&self) -> &str {
         match self {
             Rol::Admin => "admin",
             Rol::Usuario => "usuario",
+            Rol::GestorSalas => "gestor_salas",
+            Rol::Recepcionista => "recepcionista",
+            Rol::Lectura => "lectura",
         }
     }
 
@@ -31,6 +77,9 @@ impl Rol {
         match s.to_lowercase().as_str() {
             "admin" => Some(Rol::Admin),
             "usuario" => Some(Rol::Usuario),
+            "gestor_salas" | "gestor" => Some(Rol::GestorSalas),
+            "recepcionista" => Some(Rol::Recepcionista),
+            "lectura" | "solo_lectura" => Some(Rol::Lectura),
             _ => None,
         }
     }
@@ -67,6 +116,33 @@ mod tests {
         assert_eq!(Rol::from_str_opt("invalid"), None);
     }
 
+    #[test]
+    fn test_nuevos_roles_ida_y_vuelta() {
+        for rol in [Rol::GestorSalas, Rol::Recepcionista, Rol::Lectura] {
+            assert_eq!(Rol::from_str_opt(rol.as_str()), Some(rol.clone()));
+            assert!(!rol.es_admin());
+        }
+        assert_eq!(Rol::from_str_opt("Gestor"), Some(Rol::GestorSalas));
+    }
+
+    #[test]
+    fn test_permisos_por_rol() {
+        assert!(Permiso::TODOS.iter().all(|p| Rol::Admin.tiene_permiso(*p)));
+        assert!(Rol::Usuario.tiene_permiso(Permiso::CrearReservas));
+        assert!(!Rol::Usuario.tiene_permiso(Permiso::VerCualquierReserva));
+        assert!(Rol::Recepcionista.tiene_permiso(Permiso::GestionarCualquierReserva));
+        assert!(!Rol::Recepcionista.tiene_permiso(Permiso::GestionarSalas));
+        assert!(Rol::Lectura.tiene_permiso(Permiso::VerInformes));
+        assert!(!Rol::Lectura.tiene_permiso(Permiso::CrearReservas));
+
+        // Un gestor solo gestiona salas a través de las que tiene asignadas
+        assert!(!Rol::GestorSalas.tiene_permiso(Permiso::GestionarSalas));
+        assert!(Rol::GestorSalas
+            .permisos_en_salas_gestionadas()
+            .contains(&Permiso::GestionarSalas));
+        assert!(Rol::Admin.permisos_en_salas_gestionadas().is_empty());
+    }
+
     #[test]
     fn test_default() {
         assert_eq!(Rol::default(), Rol::Usuario);
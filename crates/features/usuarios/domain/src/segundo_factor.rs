@@ -0,0 +1,107 @@
+use crate::rol::Rol;
+use serde::{Deserialize, Serialize};
+
+/// Verificación en dos pasos con una app de códigos TOTP (RFC 6238)
+///
+/// Se crea al iniciar la inscripción, pero no se pide al entrar hasta que el
+/// usuario la confirma con un primer código (`activo`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SegundoFactor {
+    /// Secreto compartido con la app, en base32
+    pub secreto: String,
+    /// Si ya se ha confirmado con un código
+    pub activo: bool,
+    /// Último paso de 30 s cuyo código se ha aceptado: un código no vale dos veces
+    #[serde(default)]
+    pub ultimo_paso: u64,
+    /// Hashes de los códigos de recuperación que quedan sin usar
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codigos_recuperacion: Vec<String>,
+}
+
+impl SegundoFactor {
+    /// Inscripción pendiente de confirmar
+    pub fn new(secreto: String) -> Self {
+        Self {
+            secreto,
+            activo: false,
+            ultimo_paso: 0,
+            codigos_recuperacion: Vec::new(),
+        }
+    }
+
+    /// Anota el paso de un código aceptado; `false` si ya se aceptó uno de
+    /// ese paso o de uno posterior
+    pub fn registrar_paso(&mut self, paso: u64) -> bool {
+        if paso <= self.ultimo_paso {
+            return false;
+        }
+        self.ultimo_paso = paso;
+        true
+    }
+
+    /// Gasta el código de recuperación con ese hash, si queda
+    pub fn usar_codigo_recuperacion(&mut self, hash: &str) -> bool {
+        let antes = self.codigos_recuperacion.len();
+        self.codigos_recuperacion.retain(|codigo| codigo != hash);
+        self.codigos_recuperacion.len() < antes
+    }
+}
+
+/// Roles que no pueden entrar sin segundo factor
+///
+/// Por defecto no se exige a nadie; quien lo tenga activo lo usa igualmente.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoliticaSegundoFactor {
+    pub roles: Vec<Rol>,
+}
+
+impl PoliticaSegundoFactor {
+    pub fn new(roles: Vec<Rol>) -> Self {
+        Self { roles }
+    }
+
+    /// Exige el segundo factor a los administradores
+    pub fn para_admins() -> Self {
+        Self::new(vec![Rol::Admin])
+    }
+
+    /// Si el rol debe entrar con segundo factor
+    pub fn exige(&self, rol: &Rol) -> bool {
+        self.roles.contains(rol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_un_paso_solo_se_acepta_una_vez() {
+        let mut factor = SegundoFactor::new("SECRETO".to_string());
+
+        assert!(factor.registrar_paso(100));
+        assert!(!factor.registrar_paso(100));
+        assert!(!factor.registrar_paso(99));
+        assert!(factor.registrar_paso(101));
+    }
+
+    #[test]
+    fn test_codigos_de_recuperacion_de_un_solo_uso() {
+        let mut factor = SegundoFactor::new("SECRETO".to_string());
+        factor.codigos_recuperacion = vec!["hash-1".to_string(), "hash-2".to_string()];
+
+        assert!(factor.usar_codigo_recuperacion("hash-1"));
+        assert!(!factor.usar_codigo_recuperacion("hash-1"));
+        assert_eq!(factor.codigos_recuperacion, ["hash-2"]);
+    }
+
+    #[test]
+    fn test_politica_para_admins() {
+        let politica = PoliticaSegundoFactor::para_admins();
+
+        assert!(politica.exige(&Rol::Admin));
+        assert!(!politica.exige(&Rol::Recepcionista));
+        assert!(!PoliticaSegundoFactor::default().exige(&Rol::Admin));
+    }
+}
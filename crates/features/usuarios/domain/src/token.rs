@@ -0,0 +1,86 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Para qué sirve un token de un solo uso enviado por correo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PropositoToken {
+    RestablecerPassword,
+    VerificarEmail,
+    /// Confirmar desde la dirección nueva un cambio de email
+    CambiarEmail,
+}
+
+impl PropositoToken {
+    /// Tiempo que el token sigue siendo válido desde que se emite
+    pub fn vigencia(&self) -> Duration {
+        match self {
+            PropositoToken::RestablecerPassword => Duration::hours(1),
+            PropositoToken::VerificarEmail | PropositoToken::CambiarEmail => Duration::hours(24),
+        }
+    }
+}
+
+/// Token de un solo uso asociado a un usuario
+///
+/// Solo se guarda el hash: el token en claro viaja únicamente en el correo.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TokenUsuario {
+    /// Hash SHA-256 (hex) del token
+    pub hash: String,
+    pub usuario_id: String,
+    pub proposito: PropositoToken,
+    pub creado: DateTime<Utc>,
+    pub expira: DateTime<Utc>,
+    /// Si ya se ha canjeado (o se ha invalidado al emitir otro)
+    pub usado: bool,
+}
+
+impl TokenUsuario {
+    /// Crea un token que caduca según la vigencia de su propósito
+    pub fn new(
+        hash: String,
+        usuario_id: String,
+        proposito: PropositoToken,
+        ahora: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            hash,
+            usuario_id,
+            proposito,
+            creado: ahora,
+            expira: ahora + proposito.vigencia(),
+            usado: false,
+        }
+    }
+
+    /// Indica si todavía se puede canjear
+    pub fn es_valido(&self, ahora: DateTime<Utc>) -> bool {
+        !self.usado && self.expira > ahora
+    }
+
+    pub fn marcar_usado(&mut self) {
+        self.usado = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caduca_segun_el_proposito_y_es_de_un_solo_uso() {
+        let ahora = Utc::now();
+        let mut token = TokenUsuario::new(
+            "hash".into(),
+            "u1".into(),
+            PropositoToken::RestablecerPassword,
+            ahora,
+        );
+
+        assert!(token.es_valido(ahora + Duration::minutes(59)));
+        assert!(!token.es_valido(ahora + Duration::hours(1)));
+
+        token.marcar_usado();
+        assert!(!token.es_valido(ahora));
+    }
+}
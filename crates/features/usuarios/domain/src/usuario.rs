@@ -1,7 +1,11 @@
 use crate::error::UsuarioError;
+use crate::preferencias::Preferencias;
 use crate::rol::Rol;
-use chrono::{DateTime, Utc};
+use crate::segundo_factor::SegundoFactor;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use shared_errores::Idioma;
 use uuid::Uuid;
 
 /// Longitud mínima del nombre de usuario
@@ -30,6 +34,43 @@ pub struct Usuario {
     pub updated_at: DateTime<Utc>,
     /// Si el usuario está activo en el sistema
     pub activo: bool,
+    /// Idioma preferido para los mensajes (si no hay, se usa `Accept-Language`)
+    #[serde(default)]
+    pub idioma: Option<Idioma>,
+    /// Zona horaria IANA en la que ve las horas (si no hay, la de cada sala)
+    #[serde(default)]
+    pub zona_horaria: Option<Tz>,
+    /// Intentos de login fallidos seguidos desde el último correcto o bloqueo
+    #[serde(default)]
+    pub intentos_fallidos: u32,
+    /// Hasta cuándo se rechazan los logins tras demasiados intentos fallidos
+    #[serde(default)]
+    pub bloqueado_hasta: Option<DateTime<Utc>>,
+    /// Si el usuario ha confirmado su email (los usuarios anteriores cuentan como verificados)
+    #[serde(default = "verificado_por_defecto")]
+    pub email_verificado: bool,
+    /// Salas que gestiona (solo cuenta para el rol `GestorSalas`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub salas_gestionadas: Vec<String>,
+    /// Identidad en el proveedor externo (`emisor|sujeto`) si entra con SSO
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identidad_externa: Option<String>,
+    /// Verificación en dos pasos (TOTP), activa o pendiente de confirmar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segundo_factor: Option<SegundoFactor>,
+    /// Notificaciones, sala predeterminada y horario laboral
+    #[serde(default)]
+    pub preferencias: Preferencias,
+    /// Email nuevo pendiente de confirmar desde esa dirección
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_pendiente: Option<String>,
+    /// Usuarios autorizados a reservar en su nombre (p. ej. su asistente)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegados: Vec<String>,
+}
+
+fn verificado_por_defecto() -> bool {
+    true
 }
 
 impl Usuario {
@@ -66,6 +107,17 @@ impl Usuario {
             created_at: now,
             updated_at: now,
             activo: true,
+            idioma: None,
+            zona_horaria: None,
+            intentos_fallidos: 0,
+            bloqueado_hasta: None,
+            email_verificado: false,
+            salas_gestionadas: Vec::new(),
+            identidad_externa: None,
+            segundo_factor: None,
+            preferencias: Preferencias::default(),
+            email_pendiente: None,
+            delegados: Vec::new(),
         })
     }
 
@@ -94,6 +146,17 @@ impl Usuario {
             created_at,
             updated_at,
             activo,
+            idioma: None,
+            zona_horaria: None,
+            intentos_fallidos: 0,
+            bloqueado_hasta: None,
+            email_verificado: false,
+            salas_gestionadas: Vec::new(),
+            identidad_externa: None,
+            segundo_factor: None,
+            preferencias: Preferencias::default(),
+            email_pendiente: None,
+            delegados: Vec::new(),
         })
     }
 
@@ -105,14 +168,43 @@ impl Usuario {
         Ok(())
     }
 
-    /// Actualiza el email del usuario
+    /// Actualiza el email del usuario; si cambia, hay que volver a verificarlo
     pub fn actualizar_email(&mut self, nuevo_email: String) -> Result<(), UsuarioError> {
         validar_email(&nuevo_email)?;
+        if nuevo_email != self.email {
+            self.email_verificado = false;
+        }
         self.email = nuevo_email;
         self.updated_at = Utc::now();
         Ok(())
     }
 
+    /// Anota el email nuevo que el usuario quiere usar
+    ///
+    /// No cambia nada más hasta que lo confirme desde esa dirección.
+    pub fn solicitar_cambio_email(&mut self, nuevo_email: String) -> Result<(), UsuarioError> {
+        let nuevo_email = nuevo_email.trim().to_string();
+        validar_email(&nuevo_email)?;
+        self.email_pendiente = Some(nuevo_email);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Aplica el email pendiente, ya verificado; devuelve el anterior
+    pub fn confirmar_cambio_email(&mut self) -> Option<String> {
+        let nuevo_email = self.email_pendiente.take()?;
+        let anterior = std::mem::replace(&mut self.email, nuevo_email);
+        self.email_verificado = true;
+        self.updated_at = Utc::now();
+        Some(anterior)
+    }
+
+    /// Sustituye las preferencias del perfil
+    pub fn actualizar_preferencias(&mut self, preferencias: Preferencias) {
+        self.preferencias = preferencias;
+        self.updated_at = Utc::now();
+    }
+
     /// Actualiza el hash de la contraseña
     pub fn actualizar_password(&mut self, nuevo_hash: String) {
         self.password_hash = nuevo_hash;
@@ -125,6 +217,112 @@ impl Usuario {
         self.updated_at = Utc::now();
     }
 
+    /// Sustituye las salas que gestiona el usuario (sin repetidas)
+    pub fn asignar_salas_gestionadas(&mut self, salas: Vec<String>) {
+        let mut gestionadas: Vec<String> = Vec::with_capacity(salas.len());
+        for sala in salas {
+            let sala = sala.trim().to_string();
+            if !sala.is_empty() && !gestionadas.contains(&sala) {
+                gestionadas.push(sala);
+            }
+        }
+        self.salas_gestionadas = gestionadas;
+        self.updated_at = Utc::now();
+    }
+
+    /// Autoriza a otro usuario a reservar en su nombre
+    ///
+    /// Devuelve `false` si ya lo estaba.
+    ///
+    /// # Errores
+    /// Si es el propio usuario
+    pub fn autorizar_delegado(&mut self, delegado_id: &str) -> Result<bool, UsuarioError> {
+        if delegado_id == self.id {
+            return Err(UsuarioError::DelegadoPropio);
+        }
+        if self.admite_delegado(delegado_id) {
+            return Ok(false);
+        }
+        self.delegados.push(delegado_id.to_string());
+        self.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    /// Retira la autorización; devuelve `false` si no la tenía
+    pub fn revocar_delegado(&mut self, delegado_id: &str) -> bool {
+        let antes = self.delegados.len();
+        self.delegados.retain(|d| d != delegado_id);
+        if self.delegados.len() == antes {
+            return false;
+        }
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Si el usuario puede reservar en su nombre
+    pub fn admite_delegado(&self, delegado_id: &str) -> bool {
+        self.delegados.iter().any(|d| d == delegado_id)
+    }
+
+    /// Cambia el idioma preferido (`None` vuelve a la negociación por cabeceras)
+    pub fn actualizar_idioma(&mut self, idioma: Option<Idioma>) {
+        self.idioma = idioma;
+        self.updated_at = Utc::now();
+    }
+
+    /// Cambia la zona horaria del perfil (`None` usa la de cada sala)
+    pub fn actualizar_zona_horaria(&mut self, zona_horaria: Option<Tz>) {
+        self.zona_horaria = zona_horaria;
+        self.updated_at = Utc::now();
+    }
+
+    /// Marca el email como confirmado por el propio usuario
+    pub fn marcar_email_verificado(&mut self) {
+        self.email_verificado = true;
+        self.updated_at = Utc::now();
+    }
+
+    /// Vincula la cuenta con su identidad en el proveedor externo
+    ///
+    /// `identidad` tiene la forma `emisor|sujeto`; a partir de aquí los
+    /// inicios de sesión con SSO la buscan por ella y no por email.
+    pub fn vincular_identidad_externa(&mut self, identidad: String) {
+        self.identidad_externa = Some(identidad);
+        self.updated_at = Utc::now();
+    }
+
+    /// Empieza la inscripción del segundo factor con un secreto nuevo
+    ///
+    /// Sustituye a una inscripción anterior sin confirmar.
+    pub fn iniciar_segundo_factor(&mut self, secreto: String) {
+        self.segundo_factor = Some(SegundoFactor::new(secreto));
+        self.updated_at = Utc::now();
+    }
+
+    /// Confirma la inscripción con los hashes de los códigos de recuperación
+    ///
+    /// Devuelve `false` si no había ninguna inscripción iniciada.
+    pub fn activar_segundo_factor(&mut self, codigos_recuperacion: Vec<String>) -> bool {
+        let Some(factor) = self.segundo_factor.as_mut() else {
+            return false;
+        };
+        factor.activo = true;
+        factor.codigos_recuperacion = codigos_recuperacion;
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Quita el segundo factor (activo o pendiente)
+    pub fn quitar_segundo_factor(&mut self) {
+        self.segundo_factor = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Si al entrar hay que pedir el código del segundo factor
+    pub fn tiene_segundo_factor(&self) -> bool {
+        self.segundo_factor.as_ref().is_some_and(|f| f.activo)
+    }
+
     /// Desactiva el usuario
     pub fn desactivar(&mut self) {
         self.activo = false;
@@ -137,6 +335,40 @@ impl Usuario {
         self.updated_at = Utc::now();
     }
 
+    /// Minutos que quedan de bloqueo de login (redondeando hacia arriba), si lo hay
+    pub fn minutos_bloqueado(&self, ahora: DateTime<Utc>) -> Option<i64> {
+        self.bloqueado_hasta
+            .filter(|hasta| *hasta > ahora)
+            .map(|hasta| ((hasta - ahora).num_seconds() + 59) / 60)
+    }
+
+    /// Anota un login fallido; al llegar a `max_intentos` bloquea la cuenta
+    /// durante `duracion` y reinicia el contador. Devuelve si se ha bloqueado.
+    pub fn registrar_login_fallido(
+        &mut self,
+        ahora: DateTime<Utc>,
+        max_intentos: u32,
+        duracion: Duration,
+    ) -> bool {
+        self.intentos_fallidos += 1;
+        if self.intentos_fallidos < max_intentos {
+            return false;
+        }
+
+        self.intentos_fallidos = 0;
+        self.bloqueado_hasta = Some(ahora + duracion);
+        true
+    }
+
+    /// Olvida los intentos fallidos tras un login correcto. Devuelve si había
+    /// algo que limpiar (para no escribir en el repositorio en cada login).
+    pub fn registrar_login_correcto(&mut self) -> bool {
+        let habia_fallos = self.intentos_fallidos > 0 || self.bloqueado_hasta.is_some();
+        self.intentos_fallidos = 0;
+        self.bloqueado_hasta = None;
+        habia_fallos
+    }
+
     /// Verifica si el usuario es administrador
     pub fn es_admin(&self) -> bool {
         self.rol.es_admin()
@@ -151,6 +383,11 @@ impl Usuario {
             rol: self.rol.clone(),
             created_at: self.created_at,
             activo: self.activo,
+            idioma: self.idioma,
+            zona_horaria: self.zona_horaria,
+            email_verificado: self.email_verificado,
+            salas_gestionadas: self.salas_gestionadas.clone(),
+            segundo_factor: self.tiene_segundo_factor(),
         }
     }
 }
@@ -164,6 +401,17 @@ pub struct UsuarioPublico {
     pub rol: Rol,
     pub created_at: DateTime<Utc>,
     pub activo: bool,
+    #[serde(default)]
+    pub idioma: Option<Idioma>,
+    #[serde(default)]
+    pub zona_horaria: Option<Tz>,
+    #[serde(default)]
+    pub email_verificado: bool,
+    #[serde(default)]
+    pub salas_gestionadas: Vec<String>,
+    /// Si entra con verificación en dos pasos
+    #[serde(default)]
+    pub segundo_factor: bool,
 }
 
 /// Valida que el nombre cumpla con las reglas de negocio
@@ -211,6 +459,14 @@ pub fn validar_email(email: &str) -> Result<(), UsuarioError> {
     Ok(())
 }
 
+/// Interpreta el nombre IANA de una zona horaria (`America/Mexico_City`...)
+pub fn parsear_zona_horaria(nombre: &str) -> Result<Tz, UsuarioError> {
+    nombre
+        .trim()
+        .parse::<Tz>()
+        .map_err(|_| UsuarioError::ZonaHorariaInvalida(nombre.trim().to_string()))
+}
+
 /// Valida que la contraseña cumpla con los requisitos mínimos
 pub fn validar_password(password: &str) -> Result<(), UsuarioError> {
     let len = password.len();
@@ -332,6 +588,138 @@ mod tests {
         assert!(!usuario.activo);
     }
 
+    #[test]
+    fn test_actualizar_idioma() {
+        let mut usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+
+        assert_eq!(usuario.idioma, None);
+        usuario.actualizar_idioma(Some(Idioma::En));
+        assert_eq!(usuario.sin_password().idioma, Some(Idioma::En));
+    }
+
+    #[test]
+    fn test_actualizar_zona_horaria() {
+        let mut usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+
+        let zona = parsear_zona_horaria("America/Mexico_City").unwrap();
+        usuario.actualizar_zona_horaria(Some(zona));
+        assert_eq!(usuario.sin_password().zona_horaria, Some(zona));
+
+        assert!(matches!(
+            parsear_zona_horaria("Hora/Local"),
+            Err(UsuarioError::ZonaHorariaInvalida(_))
+        ));
+    }
+
+    #[test]
+    fn test_bloqueo_tras_intentos_fallidos() {
+        let mut usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        let ahora = Utc::now();
+
+        assert!(!usuario.registrar_login_fallido(ahora, 3, Duration::minutes(15)));
+        assert!(!usuario.registrar_login_fallido(ahora, 3, Duration::minutes(15)));
+        assert_eq!(usuario.minutos_bloqueado(ahora), None);
+
+        assert!(usuario.registrar_login_fallido(ahora, 3, Duration::minutes(15)));
+        assert_eq!(usuario.intentos_fallidos, 0);
+        assert_eq!(usuario.minutos_bloqueado(ahora), Some(15));
+        assert_eq!(
+            usuario.minutos_bloqueado(ahora + Duration::seconds(14 * 60 + 30)),
+            Some(1)
+        );
+        assert_eq!(
+            usuario.minutos_bloqueado(ahora + Duration::minutes(15)),
+            None
+        );
+
+        assert!(usuario.registrar_login_correcto());
+        assert!(!usuario.registrar_login_correcto());
+    }
+
+    #[test]
+    fn test_cambiar_email_exige_verificarlo_de_nuevo() {
+        let mut usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        assert!(!usuario.email_verificado);
+
+        usuario.marcar_email_verificado();
+        usuario
+            .actualizar_email("test@example.com".to_string())
+            .unwrap();
+        assert!(usuario.email_verificado);
+
+        usuario
+            .actualizar_email("otro@example.com".to_string())
+            .unwrap();
+        assert!(!usuario.email_verificado);
+    }
+
+    #[test]
+    fn test_cambio_de_email_pendiente_hasta_confirmarlo() {
+        let mut usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+
+        assert!(usuario
+            .solicitar_cambio_email("no-es-email".to_string())
+            .is_err());
+        usuario
+            .solicitar_cambio_email(" nuevo@example.com ".to_string())
+            .unwrap();
+        assert_eq!(usuario.email, "test@example.com");
+
+        assert_eq!(
+            usuario.confirmar_cambio_email().as_deref(),
+            Some("test@example.com")
+        );
+        assert_eq!(usuario.email, "nuevo@example.com");
+        assert!(usuario.email_verificado);
+        assert_eq!(usuario.confirmar_cambio_email(), None);
+    }
+
+    #[test]
+    fn test_usuarios_guardados_sin_campo_cuentan_como_verificados() {
+        let usuario = Usuario::new(
+            "Test".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        let mut json = serde_json::to_value(&usuario).unwrap();
+        json.as_object_mut().unwrap().remove("email_verificado");
+
+        let cargado: Usuario = serde_json::from_value(json).unwrap();
+        assert!(cargado.email_verificado);
+    }
+
     #[test]
     fn test_sin_password() {
         let usuario = Usuario::new(
@@ -346,4 +734,49 @@ mod tests {
         assert_eq!(publico.nombre, "Test");
         assert_eq!(publico.email, "test@example.com");
     }
+
+    #[test]
+    fn test_asignar_salas_gestionadas() {
+        let mut usuario = Usuario::new(
+            "Gestora".to_string(),
+            "gestora@example.com".to_string(),
+            "hashed".to_string(),
+            Rol::GestorSalas,
+        )
+        .unwrap();
+
+        usuario.asignar_salas_gestionadas(vec![
+            "sala-1".to_string(),
+            " sala-2 ".to_string(),
+            "sala-1".to_string(),
+            "".to_string(),
+        ]);
+
+        assert_eq!(usuario.salas_gestionadas, ["sala-1", "sala-2"]);
+        assert_eq!(usuario.sin_password().salas_gestionadas.len(), 2);
+    }
+
+    #[test]
+    fn test_delegados() {
+        let mut usuario = Usuario::new(
+            "Directora".to_string(),
+            "directora@example.com".to_string(),
+            "hashed".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        let id = usuario.id.clone();
+
+        assert!(usuario.autorizar_delegado("asistente").unwrap());
+        assert!(!usuario.autorizar_delegado("asistente").unwrap());
+        assert!(usuario.admite_delegado("asistente"));
+        assert!(matches!(
+            usuario.autorizar_delegado(&id),
+            Err(UsuarioError::DelegadoPropio)
+        ));
+
+        assert!(usuario.revocar_delegado("asistente"));
+        assert!(!usuario.revocar_delegado("asistente"));
+        assert!(usuario.delegados.is_empty());
+    }
 }
@@ -1,39 +1,94 @@
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
-use usuarios_application::{AuthService, UsuarioService};
-use usuarios_domain::{Rol, UsuarioError};
-
-use crate::auth::{extract_admin_user, extract_auth_user};
+use shared_errores::grpc::idioma_de_metadata;
+use shared_errores::{ErrorCampo, Idioma};
+use shared_paginacion::Paginacion;
+use usuarios_application::{
+    AccesoSegundoFactor, AuthService, ConsultaUsuarios, CuentaService, DatosPersonalesService,
+    GrupoService, PerfilService, ResultadoLogin, UsuarioService,
+};
+use usuarios_auth::grpc::RequestPrincipalExt;
+use usuarios_auth::{Autorizador, Principal};
+use usuarios_domain::{parsear_zona_horaria, Permiso, Rol, UsuarioError};
 
 use crate::proto::{
     usuario_service_server::{UsuarioService as UsuarioServiceTrait, UsuarioServiceServer},
-    ActivarUsuarioRequest, ActivarUsuarioResponse, ActualizarNombreRequest, ActualizarRolRequest,
-    ChangePasswordRequest, ChangePasswordResponse, DesactivarUsuarioRequest,
-    DesactivarUsuarioResponse, ListarUsuariosRequest, ListarUsuariosResponse, LoginRequest,
-    LoginResponse, ObtenerUsuarioRequest, RegisterRequest, RegisterResponse, UsuarioPublico,
-    UsuarioPublicoResponse, ValidateTokenRequest, ValidateTokenResponse,
+    ActivarSegundoFactorRequest, ActivarSegundoFactorResponse, ActivarUsuarioRequest,
+    ActivarUsuarioResponse, ActualizarIdiomaRequest, ActualizarNombreRequest,
+    ActualizarPreferenciasRequest, ActualizarRolRequest, ActualizarZonaHorariaRequest,
+    AsignarSalasGestionadasRequest, ChangePasswordRequest, ChangePasswordResponse,
+    ConfirmarCambioEmailRequest, CrearGrupoRequest, CuentaEliminadaResponse, DelegadoRequest,
+    DesactivarSegundoFactorRequest, DesactivarSegundoFactorResponse, DesactivarUsuarioRequest,
+    DesactivarUsuarioResponse, EliminarCuentaRequest, EliminarGrupoRequest, EliminarGrupoResponse,
+    EliminarUsuarioRequest, ExportarDatosRequest, ExportarDatosResponse, Grupo, GrupoResponse,
+    HorarioLaboral, IniciarSegundoFactorRequest, IniciarSegundoFactorResponse, ListarGruposRequest,
+    ListarGruposResponse, ListarUsuariosRequest, ListarUsuariosResponse, LoginRequest,
+    LoginResponse, MiembroGrupoRequest, Notificaciones, ObtenerPerfilRequest,
+    ObtenerUsuarioRequest, PerfilResponse, Preferencias, RegisterRequest, RegisterResponse,
+    ReservaExportada, RestablecerPasswordRequest, RestablecerPasswordResponse,
+    SolicitarCambioEmailRequest, SolicitarCambioEmailResponse, SolicitarRestablecerPasswordRequest,
+    SolicitarRestablecerPasswordResponse, SolicitarVerificacionEmailRequest,
+    SolicitarVerificacionEmailResponse, UsuarioPublico, UsuarioPublicoResponse,
+    ValidateTokenRequest, ValidateTokenResponse, VerificarEmailRequest,
+    VerificarSegundoFactorRequest,
 };
 
 pub struct UsuarioGrpcServer {
     auth_service: Arc<dyn AuthService + Send + Sync>,
     usuario_service: Arc<dyn UsuarioService + Send + Sync>,
+    cuenta_service: Arc<dyn CuentaService + Send + Sync>,
+    perfil_service: Arc<dyn PerfilService + Send + Sync>,
+    datos_service: Arc<dyn DatosPersonalesService + Send + Sync>,
+    grupo_service: Arc<dyn GrupoService + Send + Sync>,
 }
 
 impl UsuarioGrpcServer {
     pub fn new(
         auth_service: Arc<dyn AuthService + Send + Sync>,
         usuario_service: Arc<dyn UsuarioService + Send + Sync>,
+        cuenta_service: Arc<dyn CuentaService + Send + Sync>,
+        perfil_service: Arc<dyn PerfilService + Send + Sync>,
+        datos_service: Arc<dyn DatosPersonalesService + Send + Sync>,
+        grupo_service: Arc<dyn GrupoService + Send + Sync>,
     ) -> Self {
         Self {
             auth_service,
             usuario_service,
+            cuenta_service,
+            perfil_service,
+            datos_service,
+            grupo_service,
         }
     }
 
     pub fn into_service(self) -> UsuarioServiceServer<Self> {
         UsuarioServiceServer::new(self)
     }
+
+    /// Comprueba que el usuario pueda gestionar el grupo: es miembro o
+    /// tiene `usuario:manage`
+    async fn exigir_gestion_grupo(
+        &self,
+        principal: &Principal,
+        grupo_id: &str,
+        idioma: Idioma,
+    ) -> Result<(), Status> {
+        let grupo = self
+            .grupo_service
+            .obtener_grupo(grupo_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        if Autorizador::puede_gestionar_grupo(principal, &grupo) {
+            Ok(())
+        } else {
+            Err(usuario_error_to_status(
+                UsuarioError::PermisosDenegados,
+                idioma,
+            ))
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -42,24 +97,103 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<LoginRequest>,
     ) -> Result<Response<LoginResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let origen = request.remote_addr().map(|addr| addr.ip());
+        let req = request.into_inner();
+
+        let resultado = self
+            .auth_service
+            .login(req.email, req.password, origen)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(match resultado {
+            ResultadoLogin::Completado(login) => login_a_proto(login),
+            ResultadoLogin::SegundoFactor {
+                desafio,
+                inscripcion_requerida,
+            } => LoginResponse {
+                requiere_segundo_factor: true,
+                desafio,
+                inscripcion_requerida,
+                ..Default::default()
+            },
+        }))
+    }
+
+    async fn verificar_segundo_factor(
+        &self,
+        request: Request<VerificarSegundoFactorRequest>,
+    ) -> Result<Response<LoginResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let origen = request.remote_addr().map(|addr| addr.ip());
+        let req = request.into_inner();
+
+        let login = self
+            .auth_service
+            .verificar_segundo_factor(req.desafio, req.codigo, origen)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(login_a_proto(login)))
+    }
+
+    async fn iniciar_segundo_factor(
+        &self,
+        request: Request<IniciarSegundoFactorRequest>,
+    ) -> Result<Response<IniciarSegundoFactorResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let acceso = acceso_segundo_factor(&request, request.get_ref().desafio.clone())?;
+
+        let inscripcion = self
+            .auth_service
+            .iniciar_segundo_factor(acceso)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(IniciarSegundoFactorResponse {
+            secreto: inscripcion.secreto,
+            url_otpauth: inscripcion.url,
+        }))
+    }
+
+    async fn activar_segundo_factor(
+        &self,
+        request: Request<ActivarSegundoFactorRequest>,
+    ) -> Result<Response<ActivarSegundoFactorResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let acceso = acceso_segundo_factor(&request, request.get_ref().desafio.clone())?;
         let req = request.into_inner();
 
-        let login_response = self
+        let activado = self
             .auth_service
-            .login(req.email, req.password)
-            .await
-            .map_err(usuario_error_to_status)?;
-
-        Ok(Response::new(LoginResponse {
-            token: login_response.token,
-            usuario: Some(UsuarioPublico {
-                id: login_response.usuario.id,
-                nombre: login_response.usuario.nombre,
-                email: login_response.usuario.email,
-                rol: login_response.usuario.rol.as_str().to_string(),
-                created_at: login_response.usuario.created_at.to_rfc3339(),
-                activo: login_response.usuario.activo,
-            }),
+            .activar_segundo_factor(acceso, req.codigo)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(ActivarSegundoFactorResponse {
+            codigos_recuperacion: activado.codigos_recuperacion,
+            login: activado.login.map(login_a_proto),
+        }))
+    }
+
+    async fn desactivar_segundo_factor(
+        &self,
+        request: Request<DesactivarSegundoFactorRequest>,
+    ) -> Result<Response<DesactivarSegundoFactorResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        self.auth_service
+            .desactivar_segundo_factor(principal.user_id, req.codigo)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(DesactivarSegundoFactorResponse {
+            success: true,
+            message: "Segundo factor desactivado".to_string(),
         }))
     }
 
@@ -67,6 +201,7 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<RegisterRequest>,
     ) -> Result<Response<RegisterResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
         let req = request.into_inner();
 
         // Parsear rol si viene
@@ -76,18 +211,20 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
             .auth_service
             .register(req.nombre, req.email, req.password, rol)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        // El registro no falla si el correo de verificación no sale: se puede pedir otro
+        if let Err(e) = self
+            .cuenta_service
+            .solicitar_verificacion(register_response.usuario.id.clone())
+            .await
+        {
+            tracing::warn!("No se pudo enviar la verificación de email: {}", e);
+        }
 
         Ok(Response::new(RegisterResponse {
             token: register_response.token,
-            usuario: Some(UsuarioPublico {
-                id: register_response.usuario.id,
-                nombre: register_response.usuario.nombre,
-                email: register_response.usuario.email,
-                rol: register_response.usuario.rol.as_str().to_string(),
-                created_at: register_response.usuario.created_at.to_rfc3339(),
-                activo: register_response.usuario.activo,
-            }),
+            usuario: Some(usuario_a_proto(register_response.usuario)),
         }))
     }
 
@@ -95,23 +232,17 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<ValidateTokenRequest>,
     ) -> Result<Response<ValidateTokenResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
         let req = request.into_inner();
 
         let usuario = self
             .auth_service
             .validate_token(req.token)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(ValidateTokenResponse {
-            usuario: Some(UsuarioPublico {
-                id: usuario.id,
-                nombre: usuario.nombre,
-                email: usuario.email,
-                rol: usuario.rol.as_str().to_string(),
-                created_at: usuario.created_at.to_rfc3339(),
-                activo: usuario.activo,
-            }),
+            usuario: Some(usuario_a_proto(usuario)),
         }))
     }
 
@@ -120,11 +251,12 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         request: Request<ChangePasswordRequest>,
     ) -> Result<Response<ChangePasswordResponse>, Status> {
         // Validar autenticación
-        let auth_user = extract_auth_user(&request)?;
+        let principal = request.principal()?;
+        let idioma = request.idioma();
         let req = request.into_inner();
 
         // Solo se puede cambiar la propia contraseña
-        if auth_user.user_id != req.user_id {
+        if principal.user_id != req.user_id {
             return Err(Status::permission_denied(
                 "Solo puedes cambiar tu propia contraseña",
             ));
@@ -133,7 +265,7 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         self.auth_service
             .change_password(req.user_id, req.old_password, req.new_password)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(ChangePasswordResponse {
             success: true,
@@ -145,29 +277,48 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<ListarUsuariosRequest>,
     ) -> Result<Response<ListarUsuariosResponse>, Status> {
-        // Requiere rol de administrador
-        extract_admin_user(&request)?;
+        // Requiere usuario:manage
+        request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let rol = match req.rol.as_deref() {
+            Some(rol) => Some(Rol::from_str_opt(rol).ok_or_else(|| {
+                usuario_error_to_status(
+                    UsuarioError::Validacion(vec![ErrorCampo::con_codigo(
+                        "rol",
+                        "USUARIO_ROL_INVALIDO",
+                    )]),
+                    idioma,
+                )
+            })?),
+            None => None,
+        };
+        let consulta = ConsultaUsuarios {
+            activo: req.activo,
+            rol,
+            texto: req.texto,
+            paginacion: Paginacion::new(req.offset, req.limite),
+            ..Default::default()
+        }
+        .ordenada_por(&req.orden, &req.direccion)
+        .map_err(|e| usuario_error_to_status(e, idioma))?;
 
-        let usuarios = self
+        let pagina = self
             .usuario_service
-            .listar_usuarios()
-            .await
-            .map_err(usuario_error_to_status)?;
-
-        let usuarios_response: Vec<UsuarioPublico> = usuarios
-            .into_iter()
-            .map(|u| UsuarioPublico {
-                id: u.id,
-                nombre: u.nombre,
-                email: u.email,
-                rol: u.rol.as_str().to_string(),
-                created_at: u.created_at.to_rfc3339(),
-                activo: u.activo,
-            })
-            .collect();
+            .buscar_usuarios(consulta)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+        let total = pagina.total as u32;
+        let siguiente_offset = pagina.siguiente_offset();
+
+        let usuarios_response: Vec<UsuarioPublico> =
+            pagina.elementos.into_iter().map(usuario_a_proto).collect();
 
         Ok(Response::new(ListarUsuariosResponse {
             usuarios: usuarios_response,
+            total,
+            siguiente_offset,
         }))
     }
 
@@ -176,7 +327,8 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         request: Request<ObtenerUsuarioRequest>,
     ) -> Result<Response<UsuarioPublicoResponse>, Status> {
         // Requiere autenticación
-        extract_auth_user(&request)?;
+        request.principal()?;
+        let idioma = request.idioma();
 
         let req = request.into_inner();
 
@@ -184,17 +336,10 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
             .usuario_service
             .obtener_usuario(req.id)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(UsuarioPublicoResponse {
-            usuario: Some(UsuarioPublico {
-                id: usuario.id,
-                nombre: usuario.nombre,
-                email: usuario.email,
-                rol: usuario.rol.as_str().to_string(),
-                created_at: usuario.created_at.to_rfc3339(),
-                activo: usuario.activo,
-            }),
+            usuario: Some(usuario_a_proto(usuario)),
         }))
     }
 
@@ -203,11 +348,12 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         request: Request<ActualizarNombreRequest>,
     ) -> Result<Response<UsuarioPublicoResponse>, Status> {
         // Validar autenticación
-        let auth_user = extract_auth_user(&request)?;
+        let principal = request.principal()?;
+        let idioma = request.idioma();
         let req = request.into_inner();
 
         // Solo se puede actualizar el propio nombre
-        if auth_user.user_id != req.user_id {
+        if principal.user_id != req.user_id {
             return Err(Status::permission_denied(
                 "Solo puedes actualizar tu propio nombre",
             ));
@@ -217,17 +363,81 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
             .usuario_service
             .actualizar_nombre(req.user_id, req.nuevo_nombre)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(UsuarioPublicoResponse {
+            usuario: Some(usuario_a_proto(usuario)),
+        }))
+    }
+
+    async fn actualizar_idioma(
+        &self,
+        request: Request<ActualizarIdiomaRequest>,
+    ) -> Result<Response<UsuarioPublicoResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        // Solo se puede cambiar el propio idioma
+        if principal.user_id != req.user_id {
+            return Err(Status::permission_denied(
+                "Solo puedes cambiar tu propio idioma",
+            ));
+        }
+
+        // Vacío o ausente vuelve a la negociación por `accept-language`
+        let nuevo_idioma = match req.idioma.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(codigo) => Some(
+                Idioma::from_str_opt(codigo)
+                    .ok_or_else(|| Status::invalid_argument("Idioma inválido. Use: es o en"))?,
+            ),
+        };
+
+        let usuario = self
+            .usuario_service
+            .actualizar_idioma(req.user_id, nuevo_idioma)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(UsuarioPublicoResponse {
+            usuario: Some(usuario_a_proto(usuario)),
+        }))
+    }
+
+    async fn actualizar_zona_horaria(
+        &self,
+        request: Request<ActualizarZonaHorariaRequest>,
+    ) -> Result<Response<UsuarioPublicoResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        // Solo se puede cambiar la propia zona horaria
+        if principal.user_id != req.user_id {
+            return Err(Status::permission_denied(
+                "Solo puedes cambiar tu propia zona horaria",
+            ));
+        }
+
+        // Vacío o ausente vuelve a mostrar cada reserva en la zona de su sala
+        let zona = match req.zona_horaria.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(nombre) => {
+                Some(parsear_zona_horaria(nombre).map_err(|e| usuario_error_to_status(e, idioma))?)
+            }
+        };
+
+        let usuario = self
+            .usuario_service
+            .actualizar_zona_horaria(req.user_id, zona)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(UsuarioPublicoResponse {
-            usuario: Some(UsuarioPublico {
-                id: usuario.id,
-                nombre: usuario.nombre,
-                email: usuario.email,
-                rol: usuario.rol.as_str().to_string(),
-                created_at: usuario.created_at.to_rfc3339(),
-                activo: usuario.activo,
-            }),
+            usuario: Some(usuario_a_proto(usuario)),
         }))
     }
 
@@ -235,8 +445,9 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<ActualizarRolRequest>,
     ) -> Result<Response<UsuarioPublicoResponse>, Status> {
-        // Requiere rol de administrador
-        let auth_user = extract_admin_user(&request)?;
+        // Requiere usuario:manage
+        let principal = request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
         let req = request.into_inner();
 
         let nuevo_rol = Rol::from_str_opt(&req.nuevo_rol)
@@ -244,19 +455,32 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
 
         let usuario = self
             .usuario_service
-            .actualizar_rol(auth_user.user_id, req.user_id, nuevo_rol)
+            .actualizar_rol(principal.user_id, req.user_id, nuevo_rol)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(UsuarioPublicoResponse {
+            usuario: Some(usuario_a_proto(usuario)),
+        }))
+    }
+
+    async fn asignar_salas_gestionadas(
+        &self,
+        request: Request<AsignarSalasGestionadasRequest>,
+    ) -> Result<Response<UsuarioPublicoResponse>, Status> {
+        // Requiere usuario:manage
+        let principal = request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let usuario = self
+            .usuario_service
+            .asignar_salas_gestionadas(principal.user_id, req.user_id, req.salas)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(UsuarioPublicoResponse {
-            usuario: Some(UsuarioPublico {
-                id: usuario.id,
-                nombre: usuario.nombre,
-                email: usuario.email,
-                rol: usuario.rol.as_str().to_string(),
-                created_at: usuario.created_at.to_rfc3339(),
-                activo: usuario.activo,
-            }),
+            usuario: Some(usuario_a_proto(usuario)),
         }))
     }
 
@@ -264,14 +488,15 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<DesactivarUsuarioRequest>,
     ) -> Result<Response<DesactivarUsuarioResponse>, Status> {
-        // Requiere rol de administrador
-        let auth_user = extract_admin_user(&request)?;
+        // Requiere usuario:manage
+        let principal = request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
         let req = request.into_inner();
 
         self.usuario_service
-            .desactivar_usuario(auth_user.user_id, req.user_id)
+            .desactivar_usuario(principal.user_id, req.user_id)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(DesactivarUsuarioResponse {
             success: true,
@@ -283,38 +508,495 @@ impl UsuarioServiceTrait for UsuarioGrpcServer {
         &self,
         request: Request<ActivarUsuarioRequest>,
     ) -> Result<Response<ActivarUsuarioResponse>, Status> {
-        // Requiere rol de administrador
-        let auth_user = extract_admin_user(&request)?;
+        // Requiere usuario:manage
+        let principal = request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
         let req = request.into_inner();
 
         self.usuario_service
-            .activar_usuario(auth_user.user_id, req.user_id)
+            .activar_usuario(principal.user_id, req.user_id)
             .await
-            .map_err(usuario_error_to_status)?;
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
 
         Ok(Response::new(ActivarUsuarioResponse {
             success: true,
             message: "Usuario activado exitosamente".to_string(),
         }))
     }
-}
 
-// Convertir errores de dominio a errores de gRPC
-fn usuario_error_to_status(error: UsuarioError) -> Status {
-    match error {
-        UsuarioError::EmailInvalido(_) => Status::invalid_argument(error.mensaje_usuario()),
-        UsuarioError::EmailDuplicado(_) => Status::already_exists(error.mensaje_usuario()),
-        UsuarioError::NombreVacio => Status::invalid_argument(error.mensaje_usuario()),
-        UsuarioError::NombreLongitudInvalida { .. } => {
-            Status::invalid_argument(error.mensaje_usuario())
-        }
-        UsuarioError::ContrasenaDemasiadoCorta { .. } => {
-            Status::invalid_argument(error.mensaje_usuario())
+    async fn solicitar_restablecer_password(
+        &self,
+        request: Request<SolicitarRestablecerPasswordRequest>,
+    ) -> Result<Response<SolicitarRestablecerPasswordResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let req = request.into_inner();
+
+        self.cuenta_service
+            .solicitar_restablecimiento(req.email)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        // Misma respuesta exista o no el email
+        Ok(Response::new(SolicitarRestablecerPasswordResponse {
+            success: true,
+            message:
+                "Si el email está registrado, recibirás un enlace para restablecer la contraseña"
+                    .to_string(),
+        }))
+    }
+
+    async fn restablecer_password(
+        &self,
+        request: Request<RestablecerPasswordRequest>,
+    ) -> Result<Response<RestablecerPasswordResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let req = request.into_inner();
+
+        self.cuenta_service
+            .restablecer_password(req.token, req.new_password)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(RestablecerPasswordResponse {
+            success: true,
+            message: "Contraseña restablecida exitosamente".to_string(),
+        }))
+    }
+
+    async fn solicitar_verificacion_email(
+        &self,
+        request: Request<SolicitarVerificacionEmailRequest>,
+    ) -> Result<Response<SolicitarVerificacionEmailResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        self.cuenta_service
+            .solicitar_verificacion(principal.user_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(SolicitarVerificacionEmailResponse {
+            success: true,
+            message: "Correo de verificación enviado".to_string(),
+        }))
+    }
+
+    async fn verificar_email(
+        &self,
+        request: Request<VerificarEmailRequest>,
+    ) -> Result<Response<UsuarioPublicoResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let req = request.into_inner();
+
+        let usuario = self
+            .cuenta_service
+            .verificar_email(req.token)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(UsuarioPublicoResponse {
+            usuario: Some(usuario_a_proto(usuario)),
+        }))
+    }
+
+    async fn obtener_perfil(
+        &self,
+        request: Request<ObtenerPerfilRequest>,
+    ) -> Result<Response<PerfilResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let perfil = self
+            .perfil_service
+            .obtener_perfil(principal.user_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(perfil_a_proto(perfil)))
+    }
+
+    async fn actualizar_preferencias(
+        &self,
+        request: Request<ActualizarPreferenciasRequest>,
+    ) -> Result<Response<PerfilResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner().preferencias.unwrap_or_default();
+
+        let nuevo_idioma = match req.idioma.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(codigo) => Some(
+                Idioma::from_str_opt(codigo)
+                    .ok_or_else(|| Status::invalid_argument("Idioma inválido. Use: es o en"))?,
+            ),
+        };
+        let zona = match req.zona_horaria.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(nombre) => {
+                Some(parsear_zona_horaria(nombre).map_err(|e| usuario_error_to_status(e, idioma))?)
+            }
+        };
+        let horario = req
+            .horario_laboral
+            .map(|h| usuarios_domain::HorarioLaboral::parsear(&h.inicio, &h.fin, &h.dias))
+            .transpose()
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+        let notificaciones = req
+            .notificaciones
+            .map(|n| usuarios_domain::Notificaciones {
+                confirmaciones: n.confirmaciones,
+                recordatorios: n.recordatorios,
+                cancelaciones: n.cancelaciones,
+            })
+            .unwrap_or_default();
+        let preferencias =
+            usuarios_domain::Preferencias::new(notificaciones, req.sala_predeterminada, horario);
+
+        let perfil = self
+            .perfil_service
+            .actualizar_preferencias(principal.user_id, nuevo_idioma, zona, preferencias)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(perfil_a_proto(perfil)))
+    }
+
+    async fn autorizar_delegado(
+        &self,
+        request: Request<DelegadoRequest>,
+    ) -> Result<Response<PerfilResponse>, Status> {
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let perfil = self
+            .perfil_service
+            .autorizar_delegado(principal.user_id, req.usuario_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(perfil_a_proto(perfil)))
+    }
+
+    async fn revocar_delegado(
+        &self,
+        request: Request<DelegadoRequest>,
+    ) -> Result<Response<PerfilResponse>, Status> {
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let perfil = self
+            .perfil_service
+            .revocar_delegado(principal.user_id, req.usuario_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(perfil_a_proto(perfil)))
+    }
+
+    async fn solicitar_cambio_email(
+        &self,
+        request: Request<SolicitarCambioEmailRequest>,
+    ) -> Result<Response<SolicitarCambioEmailResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        self.cuenta_service
+            .solicitar_cambio_email(principal.user_id, req.nuevo_email, req.password)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(SolicitarCambioEmailResponse {
+            success: true,
+            message: "Correo de confirmación enviado a la dirección nueva".to_string(),
+        }))
+    }
+
+    async fn confirmar_cambio_email(
+        &self,
+        request: Request<ConfirmarCambioEmailRequest>,
+    ) -> Result<Response<UsuarioPublicoResponse>, Status> {
+        let idioma = idioma_de_metadata(request.metadata()).unwrap_or_default();
+        let req = request.into_inner();
+
+        let usuario = self
+            .cuenta_service
+            .confirmar_cambio_email(req.token)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(UsuarioPublicoResponse {
+            usuario: Some(usuario_a_proto(usuario)),
+        }))
+    }
+
+    async fn exportar_datos(
+        &self,
+        request: Request<ExportarDatosRequest>,
+    ) -> Result<Response<ExportarDatosResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let exportacion = self
+            .datos_service
+            .exportar_datos(principal.user_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(ExportarDatosResponse {
+            perfil: Some(perfil_a_proto(exportacion.perfil)),
+            reservas: exportacion
+                .reservas
+                .into_iter()
+                .map(|r| ReservaExportada {
+                    id: r.id,
+                    sala_id: r.sala_id,
+                    fecha_inicio: r.fecha_inicio.to_rfc3339(),
+                    fecha_fin: r.fecha_fin.to_rfc3339(),
+                    estado: r.estado,
+                    created_at: r.created_at.to_rfc3339(),
+                })
+                .collect(),
+            generada: exportacion.generada.to_rfc3339(),
+        }))
+    }
+
+    async fn eliminar_cuenta(
+        &self,
+        request: Request<EliminarCuentaRequest>,
+    ) -> Result<Response<CuentaEliminadaResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let eliminada = self
+            .datos_service
+            .eliminar_cuenta(principal.user_id, req.password)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(cuenta_eliminada_a_proto(eliminada)))
+    }
+
+    async fn eliminar_usuario(
+        &self,
+        request: Request<EliminarUsuarioRequest>,
+    ) -> Result<Response<CuentaEliminadaResponse>, Status> {
+        // Requiere usuario:manage
+        let principal = request.principal_con_permiso(Permiso::GestionarUsuarios)?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let eliminada = self
+            .datos_service
+            .eliminar_usuario(principal.user_id, req.user_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(cuenta_eliminada_a_proto(eliminada)))
+    }
+
+    async fn crear_grupo(
+        &self,
+        request: Request<CrearGrupoRequest>,
+    ) -> Result<Response<GrupoResponse>, Status> {
+        // Validar autenticación
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+
+        let grupo = self
+            .grupo_service
+            .crear_grupo(req.nombre, principal.user_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(GrupoResponse {
+            grupo: Some(grupo_a_proto(grupo)),
+        }))
+    }
+
+    async fn listar_grupos(
+        &self,
+        request: Request<ListarGruposRequest>,
+    ) -> Result<Response<ListarGruposResponse>, Status> {
+        // Con usuario:manage se ven todos; si no, los propios
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+
+        let grupos = if Autorizador::puede(&principal, Permiso::GestionarUsuarios) {
+            self.grupo_service.listar_grupos().await
+        } else {
+            self.grupo_service
+                .grupos_de_usuario(&principal.user_id)
+                .await
         }
-        UsuarioError::UsuarioNoEncontrado(_) => Status::not_found(error.mensaje_usuario()),
-        UsuarioError::CredencialesInvalidas => Status::unauthenticated(error.mensaje_usuario()),
-        UsuarioError::PermisosDenegados => Status::permission_denied(error.mensaje_usuario()),
-        UsuarioError::ErrorRepositorio(msg) => Status::internal(msg),
-        UsuarioError::ValidacionError(msg) => Status::invalid_argument(msg),
+        .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(ListarGruposResponse {
+            grupos: grupos.into_iter().map(grupo_a_proto).collect(),
+        }))
     }
+
+    async fn agregar_miembro_grupo(
+        &self,
+        request: Request<MiembroGrupoRequest>,
+    ) -> Result<Response<GrupoResponse>, Status> {
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+        self.exigir_gestion_grupo(&principal, &req.grupo_id, idioma)
+            .await?;
+
+        let grupo = self
+            .grupo_service
+            .agregar_miembro(&req.grupo_id, &req.usuario_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(GrupoResponse {
+            grupo: Some(grupo_a_proto(grupo)),
+        }))
+    }
+
+    async fn quitar_miembro_grupo(
+        &self,
+        request: Request<MiembroGrupoRequest>,
+    ) -> Result<Response<GrupoResponse>, Status> {
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+        self.exigir_gestion_grupo(&principal, &req.grupo_id, idioma)
+            .await?;
+
+        let grupo = self
+            .grupo_service
+            .quitar_miembro(&req.grupo_id, &req.usuario_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(GrupoResponse {
+            grupo: Some(grupo_a_proto(grupo)),
+        }))
+    }
+
+    async fn eliminar_grupo(
+        &self,
+        request: Request<EliminarGrupoRequest>,
+    ) -> Result<Response<EliminarGrupoResponse>, Status> {
+        let principal = request.principal()?;
+        let idioma = request.idioma();
+        let req = request.into_inner();
+        self.exigir_gestion_grupo(&principal, &req.grupo_id, idioma)
+            .await?;
+
+        self.grupo_service
+            .eliminar_grupo(&req.grupo_id)
+            .await
+            .map_err(|e| usuario_error_to_status(e, idioma))?;
+
+        Ok(Response::new(EliminarGrupoResponse {
+            success: true,
+            message: "Grupo eliminado exitosamente".to_string(),
+        }))
+    }
+}
+
+fn grupo_a_proto(grupo: usuarios_domain::Grupo) -> Grupo {
+    Grupo {
+        id: grupo.id,
+        nombre: grupo.nombre,
+        miembros: grupo.miembros,
+        creado_por: grupo.creado_por,
+        created_at: grupo.created_at.to_rfc3339(),
+    }
+}
+
+// Convertir el usuario de la aplicación al mensaje de gRPC
+fn usuario_a_proto(usuario: usuarios_domain::UsuarioPublico) -> UsuarioPublico {
+    UsuarioPublico {
+        id: usuario.id,
+        nombre: usuario.nombre,
+        email: usuario.email,
+        rol: usuario.rol.as_str().to_string(),
+        created_at: usuario.created_at.to_rfc3339(),
+        activo: usuario.activo,
+        idioma: usuario.idioma.map(|i| i.codigo().to_string()),
+        zona_horaria: usuario.zona_horaria.map(|z| z.name().to_string()),
+        email_verificado: usuario.email_verificado,
+        salas_gestionadas: usuario.salas_gestionadas,
+        segundo_factor: usuario.segundo_factor,
+    }
+}
+
+// Convertir el perfil (usuario y preferencias) al mensaje de gRPC
+fn perfil_a_proto(perfil: usuarios_application::Perfil) -> PerfilResponse {
+    let notificaciones = perfil.preferencias.notificaciones;
+    let preferencias = Preferencias {
+        idioma: perfil.usuario.idioma.map(|i| i.codigo().to_string()),
+        zona_horaria: perfil.usuario.zona_horaria.map(|z| z.name().to_string()),
+        notificaciones: Some(Notificaciones {
+            confirmaciones: notificaciones.confirmaciones,
+            recordatorios: notificaciones.recordatorios,
+            cancelaciones: notificaciones.cancelaciones,
+        }),
+        sala_predeterminada: perfil.preferencias.sala_predeterminada,
+        horario_laboral: perfil.preferencias.horario_laboral.map(|h| HorarioLaboral {
+            inicio: h.inicio.format("%H:%M").to_string(),
+            fin: h.fin.format("%H:%M").to_string(),
+            dias: h
+                .dias
+                .iter()
+                .map(|dia| usuarios_domain::nombre_dia(*dia).to_string())
+                .collect(),
+        }),
+    };
+
+    PerfilResponse {
+        usuario: Some(usuario_a_proto(perfil.usuario)),
+        preferencias: Some(preferencias),
+        email_pendiente: perfil.email_pendiente,
+        delegados: perfil.delegados,
+    }
+}
+
+fn cuenta_eliminada_a_proto(
+    eliminada: usuarios_application::CuentaEliminada,
+) -> CuentaEliminadaResponse {
+    CuentaEliminadaResponse {
+        reservas_canceladas: eliminada.reservas_canceladas as u32,
+        reservas_seudonimizadas: eliminada.reservas_seudonimizadas as u32,
+    }
+}
+
+fn login_a_proto(login: usuarios_application::LoginResponse) -> LoginResponse {
+    LoginResponse {
+        token: login.token,
+        usuario: Some(usuario_a_proto(login.usuario)),
+        ..Default::default()
+    }
+}
+
+// La inscripción se hace con el desafío del login o, sin él, como el usuario autenticado
+fn acceso_segundo_factor<T>(
+    request: &Request<T>,
+    desafio: Option<String>,
+) -> Result<AccesoSegundoFactor, Status> {
+    match desafio.filter(|d| !d.is_empty()) {
+        Some(desafio) => Ok(AccesoSegundoFactor::Desafio(desafio)),
+        None => Ok(AccesoSegundoFactor::Sesion(request.principal()?.user_id)),
+    }
+}
+
+// Convertir errores de dominio a errores de gRPC (con código y campos en los detalles)
+fn usuario_error_to_status(error: UsuarioError, idioma: Idioma) -> Status {
+    shared_errores::grpc::a_status_en(&error, idioma)
 }
@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+use usuarios_application::ApiKeyRepository;
+use usuarios_domain::{ApiKey, UsuarioError};
+
+/// Estructura para persistir las claves en JSON (indexadas por id)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApiKeysData {
+    claves: HashMap<String, ApiKey>,
+}
+
+/// Adaptador que guarda las claves de API en un archivo JSON
+///
+/// Las revocadas se conservan para que el listado muestre cuándo se
+/// revocaron y cuándo se usaron por última vez.
+#[derive(Clone)]
+pub struct FileApiKeyRepository {
+    file_path: PathBuf,
+    cache: Arc<RwLock<HashMap<String, ApiKey>>>,
+}
+
+impl FileApiKeyRepository {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/api_keys.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/api_keys.json"))
+    }
+
+    /// Carga las claves existentes del archivo
+    pub async fn init(&self) -> Result<(), UsuarioError> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al leer archivo: {}", e)))?;
+        let data: ApiKeysData = serde_json::from_str(&contents)
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al parsear JSON: {}", e)))?;
+
+        *self.cache.write().await = data.claves;
+        Ok(())
+    }
+
+    async fn save_to_file(&self) -> Result<(), UsuarioError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                UsuarioError::ErrorRepositorio(format!("Error al crear directorio: {}", e))
+            })?;
+        }
+
+        let claves = self.cache.read().await.clone();
+        let json = serde_json::to_string_pretty(&ApiKeysData { claves }).map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al serializar JSON: {}", e))
+        })?;
+        fs::write(&self.file_path, json).await.map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al escribir archivo: {}", e))
+        })
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for FileApiKeyRepository {
+    async fn guardar(&self, clave: &ApiKey) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(clave.id.clone(), clave.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<ApiKey>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.get(id).cloned())
+    }
+
+    async fn obtener_por_hash(&self, hash: &str) -> Result<Option<ApiKey>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.values().find(|c| c.hash == hash).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<ApiKey>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, clave: &ApiKey) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            if !cache.contains_key(&clave.id) {
+                return Err(UsuarioError::ApiKeyNoEncontrada(clave.id.clone()));
+            }
+            cache.insert(clave.id.clone(), clave.clone());
+        }
+        self.save_to_file().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use usuarios_domain::Permiso;
+
+    #[tokio::test]
+    async fn test_persiste_las_claves_y_las_busca_por_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("api_keys.json");
+        let repo = FileApiKeyRepository::new(file_path.clone());
+
+        let mut clave = ApiKey::new(
+            "Pantallas".to_string(),
+            "rsk_0123456789",
+            "h1".to_string(),
+            vec![Permiso::VerCualquierReserva],
+            "admin".to_string(),
+            Utc::now(),
+        )
+        .unwrap();
+        repo.guardar(&clave).await.unwrap();
+        clave.revocar(Utc::now());
+        repo.actualizar(&clave).await.unwrap();
+
+        let recargado = FileApiKeyRepository::new(file_path);
+        recargado.init().await.unwrap();
+        assert_eq!(recargado.obtener_por_hash("h1").await.unwrap(), Some(clave));
+        assert_eq!(recargado.obtener_por_hash("h2").await.unwrap(), None);
+    }
+}
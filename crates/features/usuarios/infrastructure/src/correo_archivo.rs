@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::PathBuf;
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use usuarios_application::{Correo, EnvioCorreo};
+use usuarios_domain::UsuarioError;
+
+/// Adaptador de correo para desarrollo: no envía nada, añade cada correo a
+/// un archivo de texto y lo anota en el log
+#[derive(Clone)]
+pub struct ArchivoEnvioCorreo {
+    file_path: PathBuf,
+}
+
+impl ArchivoEnvioCorreo {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self { file_path }
+    }
+
+    /// Crea el adaptador con ruta por defecto (./data/correos.log)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/correos.log"))
+    }
+}
+
+#[async_trait]
+impl EnvioCorreo for ArchivoEnvioCorreo {
+    async fn enviar(&self, correo: &Correo) -> Result<(), UsuarioError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| UsuarioError::ErrorCorreo(e.to_string()))?;
+        }
+
+        let entrada = format!(
+            "--- {}\nPara: {}\nAsunto: {}\n\n{}\n",
+            Utc::now().to_rfc3339(),
+            correo.para,
+            correo.asunto,
+            correo.cuerpo
+        );
+        let mut archivo = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .await
+            .map_err(|e| UsuarioError::ErrorCorreo(e.to_string()))?;
+        archivo
+            .write_all(entrada.as_bytes())
+            .await
+            .map_err(|e| UsuarioError::ErrorCorreo(e.to_string()))?;
+        // El `File` de tokio escribe en segundo plano: sin esperar a que
+        // termine, el siguiente correo podría adelantarse o perderse
+        archivo
+            .flush()
+            .await
+            .map_err(|e| UsuarioError::ErrorCorreo(e.to_string()))?;
+
+        tracing::info!(
+            "📧 Correo para {} ('{}') guardado en {}",
+            correo.para,
+            correo.asunto,
+            self.file_path.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_anade_los_correos_al_archivo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("correos.log");
+        let adaptador = ArchivoEnvioCorreo::new(file_path.clone());
+
+        for asunto in ["Uno", "Dos"] {
+            adaptador
+                .enviar(&Correo {
+                    para: "ana@example.com".into(),
+                    asunto: asunto.into(),
+                    cuerpo: "Hola".into(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let contenido = std::fs::read_to_string(file_path).unwrap();
+        assert!(contenido.contains("Asunto: Uno"));
+        assert!(contenido.contains("Asunto: Dos"));
+    }
+}
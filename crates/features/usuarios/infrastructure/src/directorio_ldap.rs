@@ -0,0 +1,550 @@
+use async_trait::async_trait;
+use ldap3::{drive, ldap_escape, Ldap, LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use std::time::Duration;
+use usuarios_application::{DirectorioUsuarios, IdentidadExterna, VerificadorCredenciales};
+use usuarios_domain::UsuarioError;
+
+/// Timeout de la conexión y de cada operación contra el directorio
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Código LDAP de credenciales incorrectas
+const RC_CREDENCIALES_INVALIDAS: u32 = 49;
+
+/// Conexión y esquema del directorio LDAP
+#[derive(Debug, Clone)]
+pub struct ConfigLdap {
+    /// `ldap://` o `ldaps://`
+    pub url: String,
+    /// Cuenta de servicio con la que se buscan los usuarios
+    pub bind_dn: String,
+    pub bind_password: String,
+    /// Rama en la que se buscan los usuarios
+    pub base_dn: String,
+    /// Filtro de los usuarios que pueden entrar
+    pub filtro: String,
+    /// Si se pide STARTTLS sobre `ldap://`
+    pub starttls: bool,
+    pub atributo_email: String,
+    pub atributo_nombre: String,
+    pub atributo_grupos: String,
+    /// Identificador estable; si una entrada no lo tiene se usa su DN
+    pub atributo_id: String,
+}
+
+impl ConfigLdap {
+    pub fn new(url: String, bind_dn: String, bind_password: String, base_dn: String) -> Self {
+        Self {
+            url,
+            bind_dn,
+            bind_password,
+            base_dn,
+            filtro: "(objectClass=person)".to_string(),
+            starttls: false,
+            atributo_email: "mail".to_string(),
+            atributo_nombre: "cn".to_string(),
+            atributo_grupos: "memberOf".to_string(),
+            atributo_id: "entryUUID".to_string(),
+        }
+    }
+}
+
+/// Adaptador de un directorio LDAP: verifica contraseñas y lista usuarios
+///
+/// Busca al usuario por email con la cuenta de servicio y comprueba su
+/// contraseña haciendo bind con su DN. Cada operación abre su propia conexión.
+pub struct LdapDirectorio {
+    config: ConfigLdap,
+}
+
+impl LdapDirectorio {
+    pub fn new(config: ConfigLdap) -> Self {
+        Self { config }
+    }
+
+    /// Abre una conexión autenticada con la cuenta de servicio
+    async fn conectar(&self) -> Result<Ldap, UsuarioError> {
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(TIMEOUT)
+            .set_starttls(self.config.starttls);
+        let (conexion, mut ldap) = LdapConnAsync::with_settings(settings, &self.config.url)
+            .await
+            .map_err(no_disponible)?;
+        drive!(conexion);
+        ldap.with_timeout(TIMEOUT);
+
+        ldap.simple_bind(&self.config.bind_dn, &self.config.bind_password)
+            .await
+            .and_then(|r| r.success())
+            .map_err(no_disponible)?;
+        Ok(ldap)
+    }
+
+    async fn buscar(
+        &self,
+        ldap: &mut Ldap,
+        filtro: &str,
+    ) -> Result<Vec<SearchEntry>, UsuarioError> {
+        let atributos = [
+            self.config.atributo_email.as_str(),
+            self.config.atributo_nombre.as_str(),
+            self.config.atributo_grupos.as_str(),
+            self.config.atributo_id.as_str(),
+        ];
+        let (entradas, _) = ldap
+            .search(&self.config.base_dn, Scope::Subtree, filtro, atributos)
+            .await
+            .and_then(|r| r.success())
+            .map_err(no_disponible)?;
+
+        Ok(entradas.into_iter().map(SearchEntry::construct).collect())
+    }
+
+    fn identidad(&self, entrada: SearchEntry) -> IdentidadExterna {
+        let primero = |atributo: &str| valores(&entrada, atributo).first().cloned();
+
+        // `memberOf` trae DNs completos; se puede mapear por el DN o por su CN
+        let mut grupos = Vec::new();
+        for grupo in valores(&entrada, &self.config.atributo_grupos) {
+            if let Some(cn) = cn_de(&grupo) {
+                grupos.push(cn);
+            }
+            grupos.push(grupo);
+        }
+
+        IdentidadExterna {
+            emisor: self.config.url.clone(),
+            sujeto: primero(&self.config.atributo_id).unwrap_or_else(|| entrada.dn.clone()),
+            email: primero(&self.config.atributo_email),
+            // El email lo mantiene la propia empresa
+            email_verificado: true,
+            nombre: primero(&self.config.atributo_nombre),
+            grupos,
+        }
+    }
+}
+
+#[async_trait]
+impl VerificadorCredenciales for LdapDirectorio {
+    fn emisor(&self) -> &str {
+        &self.config.url
+    }
+
+    async fn verificar(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Option<IdentidadExterna>, UsuarioError> {
+        // Un bind con contraseña vacía es anónimo y el servidor lo acepta
+        if password.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ldap = self.conectar().await?;
+        let filtro = format!(
+            "(&{}({}={}))",
+            self.config.filtro,
+            self.config.atributo_email,
+            ldap_escape(email)
+        );
+        let mut entradas = self.buscar(&mut ldap, &filtro).await?;
+
+        // Un email repetido en el directorio no identifica a nadie
+        if entradas.len() != 1 {
+            if entradas.len() > 1 {
+                tracing::warn!(
+                    "El email {} aparece {} veces en el directorio",
+                    email,
+                    entradas.len()
+                );
+            }
+            let _ = ldap.unbind().await;
+            return Ok(None);
+        }
+        let entrada = entradas.remove(0);
+
+        let resultado = ldap
+            .simple_bind(&entrada.dn, password)
+            .await
+            .map_err(no_disponible)?;
+        let _ = ldap.unbind().await;
+
+        match resultado.rc {
+            0 => Ok(Some(self.identidad(entrada))),
+            RC_CREDENCIALES_INVALIDAS => Ok(None),
+            _ => Err(no_disponible(resultado)),
+        }
+    }
+}
+
+#[async_trait]
+impl DirectorioUsuarios for LdapDirectorio {
+    async fn listar_usuarios(&self) -> Result<Vec<IdentidadExterna>, UsuarioError> {
+        let mut ldap = self.conectar().await?;
+        let entradas = self.buscar(&mut ldap, &self.config.filtro).await?;
+        let _ = ldap.unbind().await;
+
+        Ok(entradas.into_iter().map(|e| self.identidad(e)).collect())
+    }
+}
+
+/// Valores de un atributo, sin distinguir mayúsculas en el nombre
+fn valores(entrada: &SearchEntry, atributo: &str) -> Vec<String> {
+    entrada
+        .attrs
+        .iter()
+        .find(|(nombre, _)| nombre.eq_ignore_ascii_case(atributo))
+        .map(|(_, valores)| valores.clone())
+        .unwrap_or_default()
+}
+
+/// CN de un DN (`cn=recepcion,ou=grupos,...` → `recepcion`)
+fn cn_de(dn: &str) -> Option<String> {
+    let (tipo, valor) = dn.split(',').next()?.split_once('=')?;
+    tipo.trim()
+        .eq_ignore_ascii_case("cn")
+        .then(|| valor.trim().to_string())
+}
+
+fn no_disponible(error: impl std::fmt::Display) -> UsuarioError {
+    UsuarioError::ProveedorIdentidadNoDisponible(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileUsuarioRepository;
+    use bytes::BytesMut;
+    use ldap3::asn1::{parse_tag, write, StructureTag, TagClass, PL};
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use usuarios_application::{
+        AuthService, AuthServiceImpl, MapeoRoles, ResultadoLogin, ResumenSincronizacion,
+        SincronizacionDirectorio, UsuarioRepository,
+    };
+    use usuarios_domain::Rol;
+
+    const SERVICIO_DN: &str = "cn=servicio,dc=empresa,dc=test";
+    const SERVICIO_PASSWORD: &str = "clave-servicio";
+
+    /// Entrada del directorio de pruebas
+    #[derive(Clone)]
+    struct Entrada {
+        dn: String,
+        password: String,
+        atributos: Vec<(&'static str, Vec<String>)>,
+    }
+
+    fn entrada(uid: &str, email: &str, grupos: &[&str]) -> Entrada {
+        Entrada {
+            dn: format!("uid={},ou=personas,dc=empresa,dc=test", uid),
+            password: format!("{}-secreta", uid),
+            atributos: vec![
+                ("mail", vec![email.to_string()]),
+                ("cn", vec![format!("Empleado {}", uid)]),
+                ("entryUUID", vec![format!("uuid-{}", uid)]),
+                (
+                    "memberOf",
+                    grupos
+                        .iter()
+                        .map(|g| format!("cn={},ou=grupos,dc=empresa,dc=test", g))
+                        .collect(),
+                ),
+            ],
+        }
+    }
+
+    fn primitivo(class: TagClass, id: u64, valor: &[u8]) -> StructureTag {
+        StructureTag {
+            class,
+            id,
+            payload: PL::P(valor.to_vec()),
+        }
+    }
+
+    fn texto(valor: &str) -> StructureTag {
+        primitivo(TagClass::Universal, 4, valor.as_bytes())
+    }
+
+    fn secuencia(class: TagClass, id: u64, hijos: Vec<StructureTag>) -> StructureTag {
+        StructureTag {
+            class,
+            id,
+            payload: PL::C(hijos),
+        }
+    }
+
+    /// `LDAPResult` de una operación (bind = 1, fin de búsqueda = 5)
+    fn resultado(operacion: u64, rc: u8) -> StructureTag {
+        secuencia(
+            TagClass::Application,
+            operacion,
+            vec![
+                primitivo(TagClass::Universal, 10, &[rc]),
+                texto(""),
+                texto(""),
+            ],
+        )
+    }
+
+    fn resultado_entrada(entrada: &Entrada) -> StructureTag {
+        let atributos = entrada
+            .atributos
+            .iter()
+            .map(|(nombre, valores)| {
+                secuencia(
+                    TagClass::Universal,
+                    16,
+                    vec![
+                        texto(nombre),
+                        secuencia(
+                            TagClass::Universal,
+                            17,
+                            valores.iter().map(|v| texto(v)).collect(),
+                        ),
+                    ],
+                )
+            })
+            .collect();
+        secuencia(
+            TagClass::Application,
+            4,
+            vec![
+                texto(&entrada.dn),
+                secuencia(TagClass::Universal, 16, atributos),
+            ],
+        )
+    }
+
+    /// Pares `atributo=valor` de las igualdades del filtro (sin `objectClass`)
+    fn igualdades(filtro: StructureTag, pares: &mut Vec<(String, String)>) {
+        match (filtro.class, filtro.id, filtro.payload) {
+            (TagClass::Context, 0, PL::C(hijos)) => {
+                for hijo in hijos {
+                    igualdades(hijo, pares);
+                }
+            }
+            (TagClass::Context, 3, PL::C(mut par)) if par.len() == 2 => {
+                let valor = par
+                    .pop()
+                    .and_then(|t| t.expect_primitive())
+                    .unwrap_or_default();
+                let atributo = par
+                    .pop()
+                    .and_then(|t| t.expect_primitive())
+                    .unwrap_or_default();
+                let atributo = String::from_utf8_lossy(&atributo).to_string();
+                if !atributo.eq_ignore_ascii_case("objectClass") {
+                    pares.push((atributo, String::from_utf8_lossy(&valor).to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Responde a un mensaje; `None` si es un unbind y hay que cerrar
+    fn responder(mensaje: StructureTag, entradas: &[Entrada]) -> Option<Vec<StructureTag>> {
+        let mut partes = mensaje.expect_constructed()?.into_iter();
+        let id = partes.next()?;
+        let operacion = partes.next()?;
+        let con_id = |op: StructureTag| secuencia(TagClass::Universal, 16, vec![id.clone(), op]);
+
+        match operacion.id {
+            // Bind simple; con contraseña vacía es anónimo y se acepta, como en un servidor real
+            0 => {
+                let mut campos = operacion.expect_constructed()?.into_iter().skip(1);
+                let dn = String::from_utf8(campos.next()?.expect_primitive()?).ok()?;
+                let password = String::from_utf8(campos.next()?.expect_primitive()?).ok()?;
+                let valido = password.is_empty()
+                    || (dn == SERVICIO_DN && password == SERVICIO_PASSWORD)
+                    || entradas
+                        .iter()
+                        .any(|e| e.dn == dn && e.password == password);
+                Some(vec![con_id(resultado(1, if valido { 0 } else { 49 }))])
+            }
+            // Búsqueda: solo se tienen en cuenta las igualdades del filtro
+            3 => {
+                let filtro = operacion.expect_constructed()?.into_iter().nth(6)?;
+                let mut pares = Vec::new();
+                igualdades(filtro, &mut pares);
+
+                let mut respuesta: Vec<StructureTag> = entradas
+                    .iter()
+                    .filter(|e| {
+                        pares.iter().all(|(atributo, valor)| {
+                            e.atributos.iter().any(|(nombre, valores)| {
+                                nombre.eq_ignore_ascii_case(atributo) && valores.contains(valor)
+                            })
+                        })
+                    })
+                    .map(|e| con_id(resultado_entrada(e)))
+                    .collect();
+                respuesta.push(con_id(resultado(5, 0)));
+                Some(respuesta)
+            }
+            _ => None,
+        }
+    }
+
+    async fn atender(mut socket: TcpStream, entradas: Arc<Mutex<Vec<Entrada>>>) {
+        let mut pendiente = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            let Ok(leidos) = socket.read(&mut buffer).await else {
+                return;
+            };
+            if leidos == 0 {
+                return;
+            }
+            pendiente.extend_from_slice(&buffer[..leidos]);
+
+            while let Ok((resto, mensaje)) = parse_tag(&pendiente) {
+                let consumidos = pendiente.len() - resto.len();
+                let entradas = entradas.lock().unwrap().clone();
+                let Some(respuesta) = responder(mensaje, &entradas) else {
+                    return;
+                };
+                pendiente.drain(..consumidos);
+
+                let mut salida = BytesMut::new();
+                for tag in respuesta {
+                    write::encode_into(&mut salida, tag).unwrap();
+                }
+                if socket.write_all(&salida).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Arranca un servidor LDAP mínimo en un puerto libre
+    async fn arrancar_directorio(entradas: Arc<Mutex<Vec<Entrada>>>) -> ConfigLdap {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ldap://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(atender(socket, entradas.clone()));
+            }
+        });
+
+        ConfigLdap::new(
+            url,
+            SERVICIO_DN.to_string(),
+            SERVICIO_PASSWORD.to_string(),
+            "dc=empresa,dc=test".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_verifica_la_password_con_el_dn_del_usuario() {
+        let entradas = Arc::new(Mutex::new(vec![entrada(
+            "ana",
+            "ana@empresa.com",
+            &["recepcion"],
+        )]));
+        let directorio = LdapDirectorio::new(arrancar_directorio(entradas).await);
+
+        let identidad = directorio
+            .verificar("ana@empresa.com", "ana-secreta")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(identidad.sujeto, "uuid-ana");
+        assert_eq!(identidad.nombre.as_deref(), Some("Empleado ana"));
+        assert!(identidad.grupos.contains(&"recepcion".to_string()));
+
+        assert_eq!(
+            directorio
+                .verificar("ana@empresa.com", "otra")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            directorio.verificar("ana@empresa.com", "").await.unwrap(),
+            None
+        );
+        assert_eq!(
+            directorio
+                .verificar("nadie@empresa.com", "x")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_y_sincronizacion_contra_el_directorio_de_pruebas() {
+        let entradas = Arc::new(Mutex::new(vec![
+            entrada("ana", "ana@empresa.com", &["recepcion"]),
+            entrada("luis", "luis@empresa.com", &[]),
+        ]));
+        let directorio = Arc::new(LdapDirectorio::new(
+            arrancar_directorio(entradas.clone()).await,
+        ));
+        let roles = MapeoRoles::parsear("recepcion=recepcionista").unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Arc::new(FileUsuarioRepository::new(
+            temp_dir.path().join("usuarios.json"),
+        ));
+        let auth = AuthServiceImpl::new(repo.clone())
+            .with_verificador_externo(directorio.clone(), roles.clone());
+
+        let Ok(ResultadoLogin::Completado(ana)) = auth
+            .login(
+                "ana@empresa.com".to_string(),
+                "ana-secreta".to_string(),
+                None,
+            )
+            .await
+        else {
+            panic!("login de ana sin completar");
+        };
+        let ana = ana.usuario;
+        let Ok(ResultadoLogin::Completado(luis)) = auth
+            .login(
+                "luis@empresa.com".to_string(),
+                "luis-secreta".to_string(),
+                None,
+            )
+            .await
+        else {
+            panic!("login de luis sin completar");
+        };
+        let luis = luis.usuario;
+        assert_eq!(ana.rol, Rol::Recepcionista);
+        assert_eq!(luis.rol, Rol::Usuario);
+
+        // Luis deja la empresa y Ana cambia de grupo
+        entradas
+            .lock()
+            .unwrap()
+            .retain(|e| !e.dn.starts_with("uid=luis"));
+        entradas.lock().unwrap()[0] = entrada("ana", "ana@empresa.com", &[]);
+
+        let sincronizacion = SincronizacionDirectorio::new(repo.clone(), directorio, roles);
+        assert_eq!(
+            sincronizacion.sincronizar().await.unwrap(),
+            ResumenSincronizacion {
+                actualizados: 1,
+                desactivados: 1
+            }
+        );
+
+        assert_eq!(
+            repo.obtener(&ana.id).await.unwrap().unwrap().rol,
+            Rol::Usuario
+        );
+        assert!(!repo.obtener(&luis.id).await.unwrap().unwrap().activo);
+        assert!(auth
+            .login(
+                "luis@empresa.com".to_string(),
+                "luis-secreta".to_string(),
+                None
+            )
+            .await
+            .is_err());
+    }
+}
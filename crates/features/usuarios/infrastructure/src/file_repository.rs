@@ -1,11 +1,12 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use shared_paginacion::Pagina;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::RwLock;
-use usuarios_application::UsuarioRepository;
+use usuarios_application::{ConsultaUsuarios, UsuarioRepository};
 use usuarios_domain::{Usuario, UsuarioError};
 
 /// Estructura para persistir usuarios en JSON
@@ -132,6 +133,12 @@ impl UsuarioRepository for FileUsuarioRepository {
         Ok(cache.values().cloned().collect())
     }
 
+    async fn buscar(&self, consulta: &ConsultaUsuarios) -> Result<Pagina<Usuario>, UsuarioError> {
+        // Se filtra sobre la cache sin clonar los usuarios que no entran en la página
+        let cache = self.cache.read().await;
+        Ok(consulta.aplicar(cache.values()))
+    }
+
     async fn actualizar(&self, usuario: &Usuario) -> Result<(), UsuarioError> {
         // Actualizar en cache
         {
@@ -167,9 +174,10 @@ impl UsuarioRepository for FileUsuarioRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{NaiveTime, Weekday};
     use tempfile::TempDir;
     use usuarios_auth::PasswordService;
-    use usuarios_domain::Rol;
+    use usuarios_domain::{HorarioLaboral, Notificaciones, Preferencias, Rol};
 
     async fn crear_repo_temporal() -> (FileUsuarioRepository, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -250,6 +258,14 @@ mod tests {
 
         let usuarios = repo.listar().await.unwrap();
         assert_eq!(usuarios.len(), 2);
+
+        let consulta = ConsultaUsuarios {
+            rol: Some(Rol::Admin),
+            ..Default::default()
+        };
+        let pagina = repo.buscar(&consulta).await.unwrap();
+        assert_eq!(pagina.total, 1);
+        assert_eq!(pagina.elementos[0].email, "user2@test.com");
     }
 
     #[tokio::test]
@@ -392,4 +408,41 @@ mod tests {
         assert_eq!(json["usuarios"][&user_id]["email"], "json@test.com");
         assert_eq!(json["usuarios"][&user_id]["rol"], "Admin");
     }
+
+    #[tokio::test]
+    async fn test_preferencias_persisten_en_archivo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("preferencias.json");
+
+        let repo = FileUsuarioRepository::new(file_path.clone());
+        repo.init().await.unwrap();
+        let mut usuario = Usuario::new(
+            "Ana".to_string(),
+            "ana@test.com".to_string(),
+            "hash".to_string(),
+            Rol::Usuario,
+        )
+        .unwrap();
+        let horario = HorarioLaboral::new(
+            NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+            NaiveTime::from_hms_opt(15, 0, 0).unwrap(),
+            vec![Weekday::Mon, Weekday::Thu],
+        )
+        .unwrap();
+        let preferencias = Preferencias::new(
+            Notificaciones {
+                recordatorios: false,
+                ..Notificaciones::default()
+            },
+            Some("sala-1".to_string()),
+            Some(horario),
+        );
+        usuario.actualizar_preferencias(preferencias.clone());
+        repo.guardar(&usuario).await.unwrap();
+
+        let recargado = FileUsuarioRepository::new(file_path);
+        recargado.init().await.unwrap();
+        let guardado = recargado.obtener(&usuario.id).await.unwrap().unwrap();
+        assert_eq!(guardado.preferencias, preferencias);
+    }
 }
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+use usuarios_application::GrupoRepository;
+use usuarios_domain::{Grupo, UsuarioError};
+
+/// Estructura para persistir los grupos en JSON (indexados por id)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GruposData {
+    grupos: HashMap<String, Grupo>,
+}
+
+/// Adaptador que guarda los grupos de usuarios en un archivo JSON
+///
+/// Los grupos de cada usuario se consultan en cada petición autenticada; se
+/// resuelven sobre la cache, sin leer el archivo.
+#[derive(Clone)]
+pub struct FileGrupoRepository {
+    file_path: PathBuf,
+    cache: Arc<RwLock<HashMap<String, Grupo>>>,
+}
+
+impl FileGrupoRepository {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/grupos.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/grupos.json"))
+    }
+
+    /// Carga los grupos existentes del archivo
+    pub async fn init(&self) -> Result<(), UsuarioError> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al leer archivo: {}", e)))?;
+        let data: GruposData = serde_json::from_str(&contents)
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al parsear JSON: {}", e)))?;
+
+        *self.cache.write().await = data.grupos;
+        Ok(())
+    }
+
+    async fn save_to_file(&self) -> Result<(), UsuarioError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                UsuarioError::ErrorRepositorio(format!("Error al crear directorio: {}", e))
+            })?;
+        }
+
+        let grupos = self.cache.read().await.clone();
+        let json = serde_json::to_string_pretty(&GruposData { grupos }).map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al serializar JSON: {}", e))
+        })?;
+        fs::write(&self.file_path, json).await.map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al escribir archivo: {}", e))
+        })
+    }
+}
+
+#[async_trait]
+impl GrupoRepository for FileGrupoRepository {
+    async fn guardar(&self, grupo: &Grupo) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(grupo.id.clone(), grupo.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Grupo>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Grupo>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.values().cloned().collect())
+    }
+
+    async fn listar_por_miembro(&self, usuario_id: &str) -> Result<Vec<Grupo>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache
+            .values()
+            .filter(|g| g.es_miembro(usuario_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn actualizar(&self, grupo: &Grupo) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            if !cache.contains_key(&grupo.id) {
+                return Err(UsuarioError::GrupoNoEncontrado(grupo.id.clone()));
+            }
+            cache.insert(grupo.id.clone(), grupo.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn eliminar(&self, id: &str) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            if cache.remove(id).is_none() {
+                return Err(UsuarioError::GrupoNoEncontrado(id.to_string()));
+            }
+        }
+        self.save_to_file().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_persiste_los_grupos_y_los_busca_por_miembro() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("grupos.json");
+        let repo = FileGrupoRepository::new(file_path.clone());
+
+        let mut grupo = Grupo::new("Ventas".to_string(), "ana".to_string(), Utc::now()).unwrap();
+        repo.guardar(&grupo).await.unwrap();
+        grupo.agregar_miembro("luis");
+        repo.actualizar(&grupo).await.unwrap();
+
+        let recargado = FileGrupoRepository::new(file_path);
+        recargado.init().await.unwrap();
+        assert_eq!(
+            recargado.listar_por_miembro("luis").await.unwrap(),
+            vec![grupo.clone()]
+        );
+        assert!(recargado
+            .listar_por_miembro("eva")
+            .await
+            .unwrap()
+            .is_empty());
+
+        recargado.eliminar(&grupo.id).await.unwrap();
+        assert!(matches!(
+            recargado.eliminar(&grupo.id).await,
+            Err(UsuarioError::GrupoNoEncontrado(_))
+        ));
+    }
+}
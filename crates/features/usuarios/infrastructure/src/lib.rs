@@ -1,3 +1,15 @@
+pub mod api_key_repository;
+pub mod correo_archivo;
+pub mod directorio_ldap;
 pub mod file_repository;
+pub mod grupo_repository;
+pub mod proveedor_oidc;
+pub mod token_repository;
 
+pub use api_key_repository::FileApiKeyRepository;
+pub use correo_archivo::ArchivoEnvioCorreo;
+pub use directorio_ldap::{ConfigLdap, LdapDirectorio};
 pub use file_repository::FileUsuarioRepository;
+pub use grupo_repository::FileGrupoRepository;
+pub use proveedor_oidc::{ConfigOidc, HttpProveedorOidc};
+pub use token_repository::FileTokenRepository;
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+use usuarios_application::TokenUsuarioRepository;
+use usuarios_domain::{PropositoToken, TokenUsuario, UsuarioError};
+
+/// Estructura para persistir los tokens en JSON (indexados por hash)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TokensData {
+    tokens: HashMap<String, TokenUsuario>,
+}
+
+/// Adaptador que guarda los tokens de un solo uso en un archivo JSON
+///
+/// Al escribir se descartan los tokens usados o caducados: ya no se pueden
+/// canjear y no hace falta conservarlos.
+#[derive(Clone)]
+pub struct FileTokenRepository {
+    file_path: PathBuf,
+    cache: Arc<RwLock<HashMap<String, TokenUsuario>>>,
+}
+
+impl FileTokenRepository {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/tokens.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/tokens.json"))
+    }
+
+    /// Carga los tokens existentes del archivo
+    pub async fn init(&self) -> Result<(), UsuarioError> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)
+            .await
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al leer archivo: {}", e)))?;
+        let data: TokensData = serde_json::from_str(&contents)
+            .map_err(|e| UsuarioError::ErrorRepositorio(format!("Error al parsear JSON: {}", e)))?;
+
+        *self.cache.write().await = data.tokens;
+        Ok(())
+    }
+
+    /// Purga los tokens que ya no sirven y escribe el resto
+    async fn save_to_file(&self) -> Result<(), UsuarioError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                UsuarioError::ErrorRepositorio(format!("Error al crear directorio: {}", e))
+            })?;
+        }
+
+        let tokens = {
+            let mut cache = self.cache.write().await;
+            let ahora = Utc::now();
+            cache.retain(|_, token| token.es_valido(ahora));
+            cache.clone()
+        };
+
+        let json = serde_json::to_string_pretty(&TokensData { tokens }).map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al serializar JSON: {}", e))
+        })?;
+        fs::write(&self.file_path, json).await.map_err(|e| {
+            UsuarioError::ErrorRepositorio(format!("Error al escribir archivo: {}", e))
+        })
+    }
+}
+
+#[async_trait]
+impl TokenUsuarioRepository for FileTokenRepository {
+    async fn guardar(&self, token: &TokenUsuario) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(token.hash.clone(), token.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn obtener(&self, hash: &str) -> Result<Option<TokenUsuario>, UsuarioError> {
+        let cache = self.cache.read().await;
+        Ok(cache.get(hash).cloned())
+    }
+
+    async fn actualizar(&self, token: &TokenUsuario) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            if !cache.contains_key(&token.hash) {
+                return Err(UsuarioError::TokenInvalido);
+            }
+            cache.insert(token.hash.clone(), token.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn invalidar(
+        &self,
+        usuario_id: &str,
+        proposito: PropositoToken,
+    ) -> Result<(), UsuarioError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache
+                .values_mut()
+                .filter(|t| t.usuario_id == usuario_id && t.proposito == proposito)
+                .for_each(TokenUsuario::marcar_usado);
+        }
+        self.save_to_file().await
+    }
+
+    async fn eliminar_de_usuario(&self, usuario_id: &str) -> Result<usize, UsuarioError> {
+        let eliminados = {
+            let mut cache = self.cache.write().await;
+            let antes = cache.len();
+            cache.retain(|_, t| t.usuario_id != usuario_id);
+            antes - cache.len()
+        };
+        self.save_to_file().await?;
+        Ok(eliminados)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_persiste_y_purga_los_tokens_usados() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("tokens.json");
+        let repo = FileTokenRepository::new(file_path.clone());
+        let ahora = Utc::now();
+
+        let reset = TokenUsuario::new(
+            "h1".into(),
+            "u1".into(),
+            PropositoToken::RestablecerPassword,
+            ahora,
+        );
+        let verificacion = TokenUsuario::new(
+            "h2".into(),
+            "u1".into(),
+            PropositoToken::VerificarEmail,
+            ahora,
+        );
+        repo.guardar(&reset).await.unwrap();
+        repo.guardar(&verificacion).await.unwrap();
+
+        repo.invalidar("u1", PropositoToken::RestablecerPassword)
+            .await
+            .unwrap();
+
+        let recargado = FileTokenRepository::new(file_path);
+        recargado.init().await.unwrap();
+        assert_eq!(recargado.obtener("h1").await.unwrap(), None);
+        assert_eq!(recargado.obtener("h2").await.unwrap(), Some(verificacion));
+
+        assert_eq!(recargado.eliminar_de_usuario("u1").await.unwrap(), 1);
+        assert_eq!(recargado.obtener("h2").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use webhooks_domain::{EntregaWebhook, Webhook};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrearWebhookRequest {
+    pub url: String,
+    /// Nombres de evento, p.ej. `["reserva.creada", "sala.desactivada"]`
+    pub eventos: Vec<String>,
+    /// Secreto para firmar los payloads; si se omite se genera uno
+    pub secret: Option<String>,
+}
+
+/// Respuesta de un webhook (sin el secreto)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub eventos: Vec<String>,
+    pub activo: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Respuesta de creación: única vez en la que se devuelve el secreto
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookCreadoResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntregaResponse {
+    pub id: String,
+    pub evento_id: String,
+    pub tipo_evento: String,
+    pub intento: u32,
+    pub exito: bool,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub fecha: DateTime<Utc>,
+}
+
+impl From<&Webhook> for WebhookResponse {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id.clone(),
+            url: webhook.url.clone(),
+            eventos: webhook
+                .eventos
+                .iter()
+                .map(|e| e.as_str().to_string())
+                .collect(),
+            activo: webhook.activo,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+impl From<Webhook> for WebhookCreadoResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            webhook: (&webhook).into(),
+            secret: webhook.secret,
+        }
+    }
+}
+
+impl From<EntregaWebhook> for EntregaResponse {
+    fn from(entrega: EntregaWebhook) -> Self {
+        Self {
+            id: entrega.id,
+            evento_id: entrega.evento_id,
+            tipo_evento: entrega.tipo_evento.as_str().to_string(),
+            intento: entrega.intento,
+            exito: entrega.exito,
+            status_code: entrega.status_code,
+            error: entrega.error,
+            fecha: entrega.fecha,
+        }
+    }
+}
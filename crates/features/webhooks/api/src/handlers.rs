@@ -0,0 +1,141 @@
+use crate::dtos::{CrearWebhookRequest, EntregaResponse, WebhookCreadoResponse, WebhookResponse};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+use tracing::log::{error, info};
+use webhooks_application::WebhookService;
+use webhooks_domain::{TipoEvento, WebhookError};
+
+pub type SharedWebhookService = Arc<dyn WebhookService>;
+
+/// Crear una suscripción (devuelve el secreto una única vez)
+pub async fn crear_webhook(
+    State(service): State<SharedWebhookService>,
+    Json(request): Json<CrearWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookCreadoResponse>), AppError> {
+    info!("Creando webhook: url={}", request.url);
+
+    let eventos = parsear_eventos(&request.eventos)?;
+    let webhook = service
+        .crear_webhook(request.url, eventos, request.secret)
+        .await
+        .map_err(|e| {
+            error!("Error al crear webhook: {}", e);
+            AppError(e)
+        })?;
+
+    Ok((StatusCode::CREATED, Json(webhook.into())))
+}
+
+pub async fn listar_webhooks(
+    State(service): State<SharedWebhookService>,
+) -> Result<Json<Vec<WebhookResponse>>, AppError> {
+    let webhooks = service.listar_webhooks().await?;
+    Ok(Json(webhooks.iter().map(Into::into).collect()))
+}
+
+pub async fn obtener_webhook(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    let webhook = service
+        .obtener_webhook(&id)
+        .await?
+        .ok_or(WebhookError::NoEncontrado)?;
+    Ok(Json((&webhook).into()))
+}
+
+pub async fn activar_webhook(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    let webhook = service.activar_webhook(&id).await?;
+    Ok(Json((&webhook).into()))
+}
+
+pub async fn desactivar_webhook(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    let webhook = service.desactivar_webhook(&id).await?;
+    Ok(Json((&webhook).into()))
+}
+
+pub async fn eliminar_webhook(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    service.eliminar_webhook(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Log de entregas (todos los intentos) de un webhook
+pub async fn listar_entregas(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<EntregaResponse>>, AppError> {
+    let entregas = service.listar_entregas(&id).await?;
+    Ok(Json(entregas.into_iter().map(Into::into).collect()))
+}
+
+/// Envía un evento de prueba y devuelve el resultado del intento
+pub async fn probar_webhook(
+    State(service): State<SharedWebhookService>,
+    Path(id): Path<String>,
+) -> Result<Json<EntregaResponse>, AppError> {
+    info!("Probando webhook: id={}", id);
+    let entrega = service.probar_webhook(&id).await?;
+    Ok(Json(entrega.into()))
+}
+
+fn parsear_eventos(nombres: &[String]) -> Result<Vec<TipoEvento>, WebhookError> {
+    let desconocidos: Vec<String> = nombres
+        .iter()
+        .filter(|n| TipoEvento::from_str_opt(n).is_none())
+        .map(|n| format!("Evento desconocido: {}", n))
+        .collect();
+
+    if !desconocidos.is_empty() {
+        return Err(WebhookError::Validacion(desconocidos));
+    }
+
+    Ok(nombres
+        .iter()
+        .filter_map(|n| TipoEvento::from_str_opt(n))
+        .collect())
+}
+
+pub struct AppError(pub WebhookError);
+
+impl From<WebhookError> for AppError {
+    fn from(err: WebhookError) -> Self {
+        AppError(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self.0 {
+            WebhookError::Validacion(msgs) => (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "errors": msgs })),
+            )
+                .into_response(),
+            _ => {
+                let status = match self.0 {
+                    WebhookError::NoEncontrado => StatusCode::NOT_FOUND,
+                    WebhookError::ErrorEntrega(_) => StatusCode::BAD_GATEWAY,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+
+                (
+                    status,
+                    Json(serde_json::json!({ "error": self.0.to_string() })),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
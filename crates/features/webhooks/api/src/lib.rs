@@ -0,0 +1,7 @@
+pub mod dtos;
+pub mod handlers;
+pub mod routes;
+
+pub use dtos::{CrearWebhookRequest, EntregaResponse, WebhookCreadoResponse, WebhookResponse};
+pub use handlers::SharedWebhookService;
+pub use routes::webhooks_routes_with_auth;
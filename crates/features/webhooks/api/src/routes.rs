@@ -0,0 +1,29 @@
+use crate::handlers::{
+    activar_webhook, crear_webhook, desactivar_webhook, eliminar_webhook, listar_entregas,
+    listar_webhooks, obtener_webhook, probar_webhook, SharedWebhookService,
+};
+use axum::middleware;
+use axum::routing::{get, post, put};
+use axum::Router;
+use usuarios_auth::rest::requiere_permiso;
+use usuarios_domain::Permiso;
+
+/// Rutas de administración de webhooks
+/// - Todas requieren `webhook:manage`
+pub fn webhooks_routes_with_auth(service: SharedWebhookService) -> Router {
+    Router::new()
+        .route("/webhooks", post(crear_webhook).get(listar_webhooks))
+        .route(
+            "/webhooks/{id}",
+            get(obtener_webhook).delete(eliminar_webhook),
+        )
+        .route("/webhooks/{id}/activar", put(activar_webhook))
+        .route("/webhooks/{id}/desactivar", put(desactivar_webhook))
+        .route("/webhooks/{id}/entregas", get(listar_entregas))
+        .route("/webhooks/{id}/probar", post(probar_webhook))
+        .layer(middleware::from_fn_with_state(
+            Permiso::GestionarWebhooks,
+            requiere_permiso,
+        ))
+        .with_state(service)
+}
@@ -0,0 +1,371 @@
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use webhooks_domain::{EntregaWebhook, Evento, Webhook, WebhookError};
+
+use crate::eventos::PublicadorEventos;
+use crate::firma::{firmar, HEADER_EVENTO, HEADER_FIRMA, HEADER_ID, HEADER_TIMESTAMP};
+use crate::ports::{EntregaRepository, WebhookRepository, WebhookSender};
+
+/// Política de reintentos con backoff exponencial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoliticaReintentos {
+    /// Número máximo de intentos (incluido el primero)
+    pub max_intentos: u32,
+    /// Espera antes del segundo intento; se multiplica por `factor` en cada reintento
+    pub retardo_inicial: Duration,
+    pub factor: u32,
+}
+
+impl PoliticaReintentos {
+    /// Espera antes del intento `intento` (1 = primer intento, sin espera)
+    pub fn retardo_para(&self, intento: u32) -> Duration {
+        if intento <= 1 {
+            return Duration::ZERO;
+        }
+        self.retardo_inicial * self.factor.saturating_pow(intento - 2)
+    }
+}
+
+impl Default for PoliticaReintentos {
+    fn default() -> Self {
+        Self {
+            max_intentos: 5,
+            retardo_inicial: Duration::from_secs(1),
+            factor: 2,
+        }
+    }
+}
+
+/// Entrega eventos firmados a los webhooks suscritos y registra cada intento
+///
+/// Los reintentos pendientes solo viven en memoria: si el proceso se detiene
+/// durante el backoff, esa entrega se pierde. El log de entregas conserva los
+/// intentos fallidos, así que las entregas perdidas quedan a la vista.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    repository: Arc<dyn WebhookRepository>,
+    entregas: Arc<dyn EntregaRepository>,
+    sender: Arc<dyn WebhookSender>,
+    politica: PoliticaReintentos,
+}
+
+impl WebhookDispatcher {
+    pub fn new(
+        repository: Arc<dyn WebhookRepository>,
+        entregas: Arc<dyn EntregaRepository>,
+        sender: Arc<dyn WebhookSender>,
+    ) -> Self {
+        Self {
+            repository,
+            entregas,
+            sender,
+            politica: PoliticaReintentos::default(),
+        }
+    }
+
+    pub fn with_politica(mut self, politica: PoliticaReintentos) -> Self {
+        self.politica = politica;
+        self
+    }
+
+    /// Entrega el evento a todos los webhooks activos suscritos a su tipo
+    ///
+    /// Cada webhook se entrega por separado: uno caído no retrasa a los demás
+    /// con sus reintentos, y un error al entregar a uno se registra en el log
+    /// sin cortar el resto. Devuelve las entregas que llegaron a completarse.
+    pub async fn despachar(&self, evento: &Evento) -> Result<Vec<EntregaWebhook>, WebhookError> {
+        let webhooks = self.repository.listar().await?;
+
+        let tareas: Vec<_> = webhooks
+            .into_iter()
+            .filter(|w| w.escucha(evento.tipo))
+            .map(|webhook| {
+                let dispatcher = self.clone();
+                let evento = evento.clone();
+                let webhook_id = webhook.id.clone();
+                let tarea =
+                    tokio::spawn(async move { dispatcher.entregar(&webhook, &evento).await });
+                (webhook_id, tarea)
+            })
+            .collect();
+
+        let mut resultados = Vec::with_capacity(tareas.len());
+        for (webhook_id, tarea) in tareas {
+            match tarea.await {
+                Ok(Ok(entrega)) => resultados.push(entrega),
+                Ok(Err(e)) => tracing::error!(
+                    "Error al entregar evento {} al webhook {}: {}",
+                    evento.tipo.as_str(),
+                    webhook_id,
+                    e
+                ),
+                Err(e) => tracing::error!(
+                    "Entrega de evento {} al webhook {} interrumpida: {}",
+                    evento.tipo.as_str(),
+                    webhook_id,
+                    e
+                ),
+            }
+        }
+
+        Ok(resultados)
+    }
+
+    /// Entrega un evento a un webhook aplicando la política de reintentos
+    ///
+    /// Devuelve el último intento realizado (exitoso o no).
+    pub async fn entregar(
+        &self,
+        webhook: &Webhook,
+        evento: &Evento,
+    ) -> Result<EntregaWebhook, WebhookError> {
+        let max_intentos = self.politica.max_intentos.max(1);
+        let mut intento = 1;
+
+        loop {
+            let entrega = self.intentar(webhook, evento, intento).await?;
+
+            if entrega.exito || intento >= max_intentos {
+                return Ok(entrega);
+            }
+
+            intento += 1;
+            tokio::time::sleep(self.politica.retardo_para(intento)).await;
+        }
+    }
+
+    /// Realiza un único intento de entrega y lo registra en el log
+    pub async fn intentar(
+        &self,
+        webhook: &Webhook,
+        evento: &Evento,
+        intento: u32,
+    ) -> Result<EntregaWebhook, WebhookError> {
+        let cuerpo = serde_json::to_string(evento)
+            .map_err(|e| WebhookError::ErrorEntrega(format!("Error al serializar: {}", e)))?;
+        let timestamp = Utc::now().timestamp();
+
+        let cabeceras = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            (HEADER_ID.to_string(), evento.id.clone()),
+            (HEADER_EVENTO.to_string(), evento.tipo.as_str().to_string()),
+            (HEADER_TIMESTAMP.to_string(), timestamp.to_string()),
+            (
+                HEADER_FIRMA.to_string(),
+                firmar(&webhook.secret, timestamp, &cuerpo),
+            ),
+        ];
+
+        let (status_code, error) = match self.sender.enviar(&webhook.url, &cuerpo, &cabeceras).await
+        {
+            Ok(code) if (200..300).contains(&code) => (Some(code), None),
+            Ok(code) => (Some(code), Some(format!("Respuesta HTTP {}", code))),
+            Err(e) => (None, Some(e)),
+        };
+
+        let entrega = EntregaWebhook::new(
+            webhook.id.clone(),
+            evento.id.clone(),
+            evento.tipo,
+            intento,
+            status_code,
+            error,
+        );
+
+        self.entregas.registrar(&entrega).await?;
+
+        Ok(entrega)
+    }
+}
+
+impl PublicadorEventos for WebhookDispatcher {
+    fn publicar(&self, evento: Evento) {
+        let dispatcher = self.clone();
+
+        // La entrega (con sus reintentos) no debe bloquear el caso de uso que generó el evento
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.despachar(&evento).await {
+                tracing::error!("Error al despachar evento {}: {}", evento.tipo.as_str(), e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::firma::verificar_firma;
+    use crate::test_util::{MockEntregaRepository, MockSender, MockWebhookRepository};
+    use webhooks_domain::TipoEvento;
+
+    fn politica_sin_espera(max_intentos: u32) -> PoliticaReintentos {
+        PoliticaReintentos {
+            max_intentos,
+            retardo_inicial: Duration::ZERO,
+            factor: 2,
+        }
+    }
+
+    fn crear_dispatcher(
+        fallos: u32,
+        max_intentos: u32,
+    ) -> (
+        WebhookDispatcher,
+        Webhook,
+        Arc<MockEntregaRepository>,
+        Arc<MockSender>,
+    ) {
+        let webhook = Webhook::new(
+            "http://localhost:9000/hook".into(),
+            vec![TipoEvento::ReservaCreada],
+            "secreto-de-pruebas-123".into(),
+        )
+        .unwrap();
+
+        let entregas = Arc::new(MockEntregaRepository::default());
+        let sender = Arc::new(MockSender::new(fallos));
+        let dispatcher = WebhookDispatcher::new(
+            Arc::new(MockWebhookRepository::con(std::slice::from_ref(&webhook))),
+            entregas.clone(),
+            sender.clone(),
+        )
+        .with_politica(politica_sin_espera(max_intentos));
+
+        (dispatcher, webhook, entregas, sender)
+    }
+
+    #[test]
+    fn retardo_crece_exponencialmente() {
+        let politica = PoliticaReintentos::default();
+
+        assert_eq!(politica.retardo_para(1), Duration::ZERO);
+        assert_eq!(politica.retardo_para(2), Duration::from_secs(1));
+        assert_eq!(politica.retardo_para(3), Duration::from_secs(2));
+        assert_eq!(politica.retardo_para(4), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn entrega_firmada_al_primer_intento() {
+        let (dispatcher, webhook, entregas, sender) = crear_dispatcher(0, 3);
+        let evento = Evento::new(TipoEvento::ReservaCreada, serde_json::json!({"id": "r1"}));
+
+        let resultado = dispatcher.despachar(&evento).await.unwrap();
+
+        assert_eq!(resultado.len(), 1);
+        assert!(resultado[0].exito);
+
+        let recibidos = sender.recibidos.lock().unwrap();
+        let (_url, cuerpo, cabeceras) = &recibidos[0];
+        let cabecera = |nombre: &str| {
+            cabeceras
+                .iter()
+                .find(|(k, _)| k == nombre)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+        let timestamp: i64 = cabecera(HEADER_TIMESTAMP).parse().unwrap();
+
+        assert_eq!(cabecera(HEADER_EVENTO), "reserva.creada");
+        assert!(verificar_firma(
+            &webhook.secret,
+            timestamp,
+            cuerpo,
+            &cabecera(HEADER_FIRMA)
+        ));
+        assert_eq!(entregas.entregas.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reintenta_hasta_tener_exito_y_registra_cada_intento() {
+        let (dispatcher, webhook, entregas, _sender) = crear_dispatcher(2, 5);
+        let evento = Evento::new(TipoEvento::ReservaCreada, serde_json::json!({}));
+
+        let entrega = dispatcher.entregar(&webhook, &evento).await.unwrap();
+
+        assert!(entrega.exito);
+        assert_eq!(entrega.intento, 3);
+
+        let log = entregas.listar_por_webhook(&webhook.id).await.unwrap();
+        assert_eq!(log.len(), 3);
+        assert!(!log[0].exito);
+        assert_eq!(log[0].status_code, Some(503));
+    }
+
+    #[tokio::test]
+    async fn se_rinde_tras_el_maximo_de_intentos() {
+        let (dispatcher, webhook, entregas, _sender) = crear_dispatcher(10, 3);
+        let evento = Evento::new(TipoEvento::ReservaCreada, serde_json::json!({}));
+
+        let entrega = dispatcher.entregar(&webhook, &evento).await.unwrap();
+
+        assert!(!entrega.exito);
+        assert_eq!(entrega.intento, 3);
+        assert_eq!(entregas.entregas.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn un_webhook_que_falla_no_frena_a_los_demas() {
+        let suscrito = |url: &str| {
+            Webhook::new(
+                url.into(),
+                vec![TipoEvento::ReservaCreada],
+                "secreto-de-pruebas-123".into(),
+            )
+            .unwrap()
+        };
+        let caido = suscrito("http://caido.example.com/hook");
+        let sin_log = suscrito("http://sin-log.example.com/hook");
+        let sano = suscrito("http://sano.example.com/hook");
+
+        // Uno agota sus reintentos y el otro no puede escribir en el log
+        let entregas = Arc::new(MockEntregaRepository::fallando_para(&sin_log.id));
+        let sender = Arc::new(MockSender::con_caida(&caido.url));
+        let dispatcher = WebhookDispatcher::new(
+            Arc::new(MockWebhookRepository::con(&[
+                caido.clone(),
+                sin_log.clone(),
+                sano.clone(),
+            ])),
+            entregas.clone(),
+            sender.clone(),
+        )
+        .with_politica(PoliticaReintentos {
+            max_intentos: 3,
+            retardo_inicial: Duration::from_millis(20),
+            factor: 2,
+        });
+        let evento = Evento::new(TipoEvento::ReservaCreada, serde_json::json!({}));
+
+        let resultado = dispatcher.despachar(&evento).await.unwrap();
+
+        assert_eq!(resultado.len(), 2);
+        let del_sano = resultado.iter().find(|e| e.webhook_id == sano.id).unwrap();
+        assert!(del_sano.exito);
+        let del_caido = resultado.iter().find(|e| e.webhook_id == caido.id).unwrap();
+        assert_eq!(del_caido.intento, 3);
+
+        // El sano recibe el evento sin esperar a los reintentos del caído
+        let urls: Vec<String> = sender
+            .recibidos
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(url, _, _)| url.clone())
+            .collect();
+        let posicion_sano = urls.iter().position(|u| *u == sano.url).unwrap();
+        let ultimo_caido = urls.iter().rposition(|u| *u == caido.url).unwrap();
+        assert!(posicion_sano < ultimo_caido);
+    }
+
+    #[tokio::test]
+    async fn no_entrega_eventos_no_suscritos() {
+        let (dispatcher, _webhook, entregas, _sender) = crear_dispatcher(0, 3);
+        let evento = Evento::new(TipoEvento::SalaCreada, serde_json::json!({}));
+
+        let resultado = dispatcher.despachar(&evento).await.unwrap();
+
+        assert!(resultado.is_empty());
+        assert!(entregas.entregas.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,437 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use reservas_application::{ConsultaReservas, ReservaService};
+use reservas_domain::{ParteConjunto, Recurrencia, Reserva, ReservaError};
+use salas_application::{AccionReservasFuturas, CambioSala, ConsultaSalas, SalaService};
+use salas_domain::{Sala, SalaError};
+use shared_paginacion::Pagina;
+use std::collections::HashMap;
+use std::sync::Arc;
+use webhooks_domain::{Evento, TipoEvento};
+
+/// Port para publicar eventos de dominio sin bloquear el caso de uso
+pub trait PublicadorEventos: Send + Sync {
+    fn publicar(&self, evento: Evento);
+}
+
+fn evento_de<T: serde::Serialize>(tipo: TipoEvento, entidad: &T) -> Evento {
+    Evento::new(
+        tipo,
+        serde_json::to_value(entidad).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+/// Decorador de `SalaService` que publica un evento tras cada operación exitosa
+pub struct SalaServiceConEventos<S: SalaService> {
+    inner: S,
+    publicador: Arc<dyn PublicadorEventos>,
+}
+
+impl<S: SalaService> SalaServiceConEventos<S> {
+    pub fn new(inner: S, publicador: Arc<dyn PublicadorEventos>) -> Self {
+        Self { inner, publicador }
+    }
+}
+
+#[async_trait]
+impl<S: SalaService> SalaService for SalaServiceConEventos<S> {
+    async fn crear_sala(
+        &self,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+    ) -> Result<Sala, SalaError> {
+        let sala = self
+            .inner
+            .crear_sala(nombre, capacidad, zona_horaria)
+            .await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaCreada, &sala));
+        Ok(sala)
+    }
+
+    async fn obtener_sala(&self, id: &str) -> Result<Option<Sala>, SalaError> {
+        self.inner.obtener_sala(id).await
+    }
+
+    async fn listar_salas(&self) -> Result<Vec<Sala>, SalaError> {
+        self.inner.listar_salas().await
+    }
+
+    async fn buscar_salas(&self, consulta: ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+        self.inner.buscar_salas(consulta).await
+    }
+
+    async fn activar_sala(&self, id: &str) -> Result<Sala, SalaError> {
+        let sala = self.inner.activar_sala(id).await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaActivada, &sala));
+        Ok(sala)
+    }
+
+    async fn desactivar_sala(&self, id: &str) -> Result<Sala, SalaError> {
+        let sala = self.inner.desactivar_sala(id).await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaDesactivada, &sala));
+        Ok(sala)
+    }
+
+    async fn actualizar_sala(
+        &self,
+        id: &str,
+        nombre: String,
+        capacidad: u32,
+        zona_horaria: Option<String>,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError> {
+        let cambio = self
+            .inner
+            .actualizar_sala(id, nombre, capacidad, zona_horaria, accion)
+            .await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaActualizada, &cambio.sala));
+        Ok(cambio)
+    }
+
+    async fn configurar_margenes(
+        &self,
+        id: &str,
+        margen_antes: u32,
+        margen_despues: u32,
+    ) -> Result<Sala, SalaError> {
+        let sala = self
+            .inner
+            .configurar_margenes(id, margen_antes, margen_despues)
+            .await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaActualizada, &sala));
+        Ok(sala)
+    }
+
+    async fn eliminar_sala(
+        &self,
+        id: &str,
+        accion: AccionReservasFuturas,
+    ) -> Result<CambioSala, SalaError> {
+        let cambio = self.inner.eliminar_sala(id, accion).await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::SalaEliminada, &cambio.sala));
+        Ok(cambio)
+    }
+}
+
+/// Decorador de `ReservaService` que publica un evento tras cada operación exitosa
+pub struct ReservaServiceConEventos<S: ReservaService> {
+    inner: S,
+    publicador: Arc<dyn PublicadorEventos>,
+}
+
+impl<S: ReservaService> ReservaServiceConEventos<S> {
+    pub fn new(inner: S, publicador: Arc<dyn PublicadorEventos>) -> Self {
+        Self { inner, publicador }
+    }
+}
+
+#[async_trait]
+impl<S: ReservaService> ReservaService for ReservaServiceConEventos<S> {
+    async fn crear_reserva(
+        &self,
+        sala_id: String,
+        usuario_id: String,
+        creado_por: String,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Reserva, ReservaError> {
+        let reserva = self
+            .inner
+            .crear_reserva(sala_id, usuario_id, creado_por, fecha_inicio, fecha_fin)
+            .await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::ReservaCreada, &reserva));
+        Ok(reserva)
+    }
+
+    async fn crear_reservas_recurrentes(
+        &self,
+        sala_id: String,
+        usuario_id: String,
+        creado_por: String,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+        recurrencia: Recurrencia,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        let reservas = self
+            .inner
+            .crear_reservas_recurrentes(
+                sala_id,
+                usuario_id,
+                creado_por,
+                fecha_inicio,
+                fecha_fin,
+                recurrencia,
+            )
+            .await?;
+        for reserva in &reservas {
+            self.publicador
+                .publicar(evento_de(TipoEvento::ReservaCreada, reserva));
+        }
+        Ok(reservas)
+    }
+
+    async fn crear_reserva_conjunta(
+        &self,
+        usuario_id: String,
+        creado_por: String,
+        partes: Vec<ParteConjunto>,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        let reservas = self
+            .inner
+            .crear_reserva_conjunta(usuario_id, creado_por, partes)
+            .await?;
+        for reserva in &reservas {
+            self.publicador
+                .publicar(evento_de(TipoEvento::ReservaCreada, reserva));
+        }
+        Ok(reservas)
+    }
+
+    async fn listar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        self.inner.listar_conjunto(conjunto_id).await
+    }
+
+    async fn cancelar_conjunto(&self, conjunto_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        let reservas = self.inner.cancelar_conjunto(conjunto_id).await?;
+        for reserva in &reservas {
+            self.publicador
+                .publicar(evento_de(TipoEvento::ReservaCancelada, reserva));
+        }
+        Ok(reservas)
+    }
+
+    async fn reprogramar_conjunto(
+        &self,
+        conjunto_id: &str,
+        fecha_inicio: DateTime<Utc>,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        let reservas = self
+            .inner
+            .reprogramar_conjunto(conjunto_id, fecha_inicio)
+            .await?;
+        for reserva in &reservas {
+            self.publicador
+                .publicar(evento_de(TipoEvento::ReservaReprogramada, reserva));
+        }
+        Ok(reservas)
+    }
+
+    async fn obtener_reserva(&self, id: &str) -> Result<Option<Reserva>, ReservaError> {
+        self.inner.obtener_reserva(id).await
+    }
+
+    async fn listar_reservas(&self) -> Result<Vec<Reserva>, ReservaError> {
+        self.inner.listar_reservas().await
+    }
+
+    async fn buscar_reservas(
+        &self,
+        consulta: ConsultaReservas,
+    ) -> Result<Pagina<Reserva>, ReservaError> {
+        self.inner.buscar_reservas(consulta).await
+    }
+
+    async fn listar_reservas_por_sala(&self, sala_id: &str) -> Result<Vec<Reserva>, ReservaError> {
+        self.inner.listar_reservas_por_sala(sala_id).await
+    }
+
+    async fn listar_reservas_por_usuario(
+        &self,
+        usuario_id: &str,
+    ) -> Result<Vec<Reserva>, ReservaError> {
+        self.inner.listar_reservas_por_usuario(usuario_id).await
+    }
+
+    async fn cancelar_reserva(&self, id: &str) -> Result<Reserva, ReservaError> {
+        let reserva = self.inner.cancelar_reserva(id).await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::ReservaCancelada, &reserva));
+        Ok(reserva)
+    }
+
+    async fn completar_reserva(&self, id: &str) -> Result<Reserva, ReservaError> {
+        let reserva = self.inner.completar_reserva(id).await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::ReservaCompletada, &reserva));
+        Ok(reserva)
+    }
+
+    async fn asignar_grupo(
+        &self,
+        id: &str,
+        grupo_id: Option<String>,
+    ) -> Result<Reserva, ReservaError> {
+        self.inner.asignar_grupo(id, grupo_id).await
+    }
+
+    async fn crear_reserva_con_recursos(
+        &self,
+        sala_id: Option<String>,
+        usuario_id: String,
+        creado_por: String,
+        recursos: Vec<String>,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<Reserva, ReservaError> {
+        let reserva = self
+            .inner
+            .crear_reserva_con_recursos(
+                sala_id,
+                usuario_id,
+                creado_por,
+                recursos,
+                fecha_inicio,
+                fecha_fin,
+            )
+            .await?;
+        self.publicador
+            .publicar(evento_de(TipoEvento::ReservaCreada, &reserva));
+        Ok(reserva)
+    }
+
+    async fn cambiar_recursos(
+        &self,
+        id: &str,
+        recursos: Vec<String>,
+    ) -> Result<Reserva, ReservaError> {
+        self.inner.cambiar_recursos(id, recursos).await
+    }
+
+    async fn verificar_disponibilidad(
+        &self,
+        sala_id: &str,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<bool, ReservaError> {
+        self.inner
+            .verificar_disponibilidad(sala_id, fecha_inicio, fecha_fin)
+            .await
+    }
+
+    async fn verificar_disponibilidad_recurso(
+        &self,
+        recurso_id: &str,
+        fecha_inicio: DateTime<Utc>,
+        fecha_fin: DateTime<Utc>,
+    ) -> Result<bool, ReservaError> {
+        self.inner
+            .verificar_disponibilidad_recurso(recurso_id, fecha_inicio, fecha_fin)
+            .await
+    }
+
+    async fn zonas_horarias_salas(&self) -> Result<HashMap<String, Tz>, ReservaError> {
+        self.inner.zonas_horarias_salas().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct PublicadorCaptura {
+        eventos: Mutex<Vec<Evento>>,
+    }
+
+    impl PublicadorEventos for PublicadorCaptura {
+        fn publicar(&self, evento: Evento) {
+            self.eventos.lock().unwrap().push(evento);
+        }
+    }
+
+    struct MockSalaService;
+
+    #[async_trait]
+    impl SalaService for MockSalaService {
+        async fn crear_sala(
+            &self,
+            nombre: String,
+            capacidad: u32,
+            _zona_horaria: Option<String>,
+        ) -> Result<Sala, SalaError> {
+            Sala::new("sala-1".into(), nombre, capacidad)
+        }
+
+        async fn obtener_sala(&self, _id: &str) -> Result<Option<Sala>, SalaError> {
+            Ok(None)
+        }
+
+        async fn listar_salas(&self) -> Result<Vec<Sala>, SalaError> {
+            Ok(vec![])
+        }
+
+        async fn buscar_salas(&self, consulta: ConsultaSalas) -> Result<Pagina<Sala>, SalaError> {
+            Ok(consulta.aplicar(&[]))
+        }
+
+        async fn activar_sala(&self, _id: &str) -> Result<Sala, SalaError> {
+            Err(SalaError::NoEncontrada)
+        }
+
+        async fn desactivar_sala(&self, _id: &str) -> Result<Sala, SalaError> {
+            Err(SalaError::NoEncontrada)
+        }
+
+        async fn actualizar_sala(
+            &self,
+            _id: &str,
+            _nombre: String,
+            _capacidad: u32,
+            _zona_horaria: Option<String>,
+            _accion: AccionReservasFuturas,
+        ) -> Result<CambioSala, SalaError> {
+            Err(SalaError::NoEncontrada)
+        }
+
+        async fn configurar_margenes(
+            &self,
+            _id: &str,
+            _margen_antes: u32,
+            _margen_despues: u32,
+        ) -> Result<Sala, SalaError> {
+            Err(SalaError::NoEncontrada)
+        }
+
+        async fn eliminar_sala(
+            &self,
+            _id: &str,
+            _accion: AccionReservasFuturas,
+        ) -> Result<CambioSala, SalaError> {
+            Err(SalaError::NoEncontrada)
+        }
+    }
+
+    #[tokio::test]
+    async fn publica_evento_al_crear_sala() {
+        let publicador = Arc::new(PublicadorCaptura::default());
+        let service = SalaServiceConEventos::new(MockSalaService, publicador.clone());
+
+        service.crear_sala("Sala A".into(), 10, None).await.unwrap();
+
+        let eventos = publicador.eventos.lock().unwrap();
+        assert_eq!(eventos.len(), 1);
+        assert_eq!(eventos[0].tipo, TipoEvento::SalaCreada);
+        assert_eq!(eventos[0].datos["nombre"], "Sala A");
+    }
+
+    #[tokio::test]
+    async fn no_publica_si_la_operacion_falla() {
+        let publicador = Arc::new(PublicadorCaptura::default());
+        let service = SalaServiceConEventos::new(MockSalaService, publicador.clone());
+
+        assert!(service.crear_sala(String::new(), 10, None).await.is_err());
+        assert!(service.activar_sala("x").await.is_err());
+
+        assert!(publicador.eventos.lock().unwrap().is_empty());
+    }
+}
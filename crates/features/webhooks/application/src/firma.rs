@@ -0,0 +1,80 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Cabecera con el identificador del evento
+pub const HEADER_ID: &str = "X-Webhook-Id";
+/// Cabecera con el tipo de evento
+pub const HEADER_EVENTO: &str = "X-Webhook-Event";
+/// Cabecera con el timestamp (segundos UNIX) incluido en la firma
+pub const HEADER_TIMESTAMP: &str = "X-Webhook-Timestamp";
+/// Cabecera con la firma `sha256=<hex>`
+pub const HEADER_FIRMA: &str = "X-Webhook-Signature";
+
+/// Firma un payload con HMAC-SHA256 sobre `"{timestamp}.{cuerpo}"`
+///
+/// Incluir el timestamp permite al receptor rechazar reenvíos antiguos.
+pub fn firmar(secret: &str, timestamp: i64, cuerpo: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(format!("{}.{}", timestamp, cuerpo).as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verifica una firma en tiempo constante (útil para los receptores y los tests)
+pub fn verificar_firma(secret: &str, timestamp: i64, cuerpo: &str, firma: &str) -> bool {
+    let Some(hex_firma) = firma.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(bytes) = hex::decode(hex_firma) else {
+        return false;
+    };
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(format!("{}.{}", timestamp, cuerpo).as_bytes());
+    mac.verify_slice(&bytes).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firma_verificable() {
+        let firma = firmar("secreto-compartido", 1_700_000_000, "{\"a\":1}");
+
+        assert!(firma.starts_with("sha256="));
+        assert!(verificar_firma(
+            "secreto-compartido",
+            1_700_000_000,
+            "{\"a\":1}",
+            &firma
+        ));
+    }
+
+    #[test]
+    fn firma_cambia_con_el_cuerpo_o_el_secreto() {
+        let firma = firmar("secreto-compartido", 1_700_000_000, "{\"a\":1}");
+
+        assert!(!verificar_firma(
+            "secreto-compartido",
+            1_700_000_000,
+            "{\"a\":2}",
+            &firma
+        ));
+        assert!(!verificar_firma(
+            "otro-secreto",
+            1_700_000_000,
+            "{\"a\":1}",
+            &firma
+        ));
+        assert!(!verificar_firma(
+            "secreto-compartido",
+            1_700_000_001,
+            "{\"a\":1}",
+            &firma
+        ));
+    }
+}
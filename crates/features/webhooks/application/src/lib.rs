@@ -0,0 +1,13 @@
+pub mod dispatcher;
+pub mod eventos;
+pub mod firma;
+pub mod ports;
+pub mod service;
+
+#[cfg(test)]
+mod test_util;
+
+pub use dispatcher::{PoliticaReintentos, WebhookDispatcher};
+pub use eventos::{PublicadorEventos, ReservaServiceConEventos, SalaServiceConEventos};
+pub use ports::{EntregaRepository, WebhookRepository, WebhookSender, WebhookService};
+pub use service::WebhookServiceImpl;
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use webhooks_domain::{EntregaWebhook, TipoEvento, Webhook, WebhookError};
+
+/// Casos de uso de administración de webhooks
+#[async_trait]
+pub trait WebhookService: Send + Sync {
+    /// Crea una suscripción. Si no se indica secreto se genera uno aleatorio
+    async fn crear_webhook(
+        &self,
+        url: String,
+        eventos: Vec<TipoEvento>,
+        secret: Option<String>,
+    ) -> Result<Webhook, WebhookError>;
+    async fn obtener_webhook(&self, id: &str) -> Result<Option<Webhook>, WebhookError>;
+    async fn listar_webhooks(&self) -> Result<Vec<Webhook>, WebhookError>;
+    async fn activar_webhook(&self, id: &str) -> Result<Webhook, WebhookError>;
+    async fn desactivar_webhook(&self, id: &str) -> Result<Webhook, WebhookError>;
+    async fn eliminar_webhook(&self, id: &str) -> Result<(), WebhookError>;
+    /// Historial de entregas (todos los intentos) de un webhook
+    async fn listar_entregas(&self, webhook_id: &str) -> Result<Vec<EntregaWebhook>, WebhookError>;
+    /// Envía un evento de ejemplo al webhook y devuelve el resultado del intento
+    async fn probar_webhook(&self, id: &str) -> Result<EntregaWebhook, WebhookError>;
+}
+
+/// Port (interfaz) del repositorio de suscripciones
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn guardar(&self, webhook: &Webhook) -> Result<(), WebhookError>;
+    async fn obtener(&self, id: &str) -> Result<Option<Webhook>, WebhookError>;
+    async fn listar(&self) -> Result<Vec<Webhook>, WebhookError>;
+    async fn actualizar(&self, webhook: &Webhook) -> Result<(), WebhookError>;
+    async fn eliminar(&self, id: &str) -> Result<(), WebhookError>;
+}
+
+/// Port (interfaz) del registro de entregas
+#[async_trait]
+pub trait EntregaRepository: Send + Sync {
+    async fn registrar(&self, entrega: &EntregaWebhook) -> Result<(), WebhookError>;
+    async fn listar_por_webhook(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<EntregaWebhook>, WebhookError>;
+}
+
+/// Port (interfaz) del cliente HTTP que hace el POST al destino
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Envía el cuerpo JSON y devuelve el código HTTP de la respuesta
+    async fn enviar(
+        &self,
+        url: &str,
+        cuerpo: &str,
+        cabeceras: &[(String, String)],
+    ) -> Result<u16, String>;
+}
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use webhooks_domain::{EntregaWebhook, Evento, TipoEvento, Webhook, WebhookError};
+
+use crate::dispatcher::WebhookDispatcher;
+use crate::ports::{EntregaRepository, WebhookRepository, WebhookService};
+
+/// Implementación del servicio de administración de webhooks
+pub struct WebhookServiceImpl<R: WebhookRepository, E: EntregaRepository> {
+    repository: R,
+    entregas: E,
+    dispatcher: WebhookDispatcher,
+}
+
+impl<R: WebhookRepository, E: EntregaRepository> WebhookServiceImpl<R, E> {
+    pub fn new(repository: R, entregas: E, dispatcher: WebhookDispatcher) -> Self {
+        Self {
+            repository,
+            entregas,
+            dispatcher,
+        }
+    }
+
+    async fn obtener_existente(&self, id: &str) -> Result<Webhook, WebhookError> {
+        self.repository
+            .obtener(id)
+            .await?
+            .ok_or(WebhookError::NoEncontrado)
+    }
+}
+
+/// Genera un secreto aleatorio para firmar los payloads
+fn generar_secret() -> String {
+    format!("whsec_{}", Uuid::new_v4().simple())
+}
+
+#[async_trait]
+impl<R: WebhookRepository, E: EntregaRepository> WebhookService for WebhookServiceImpl<R, E> {
+    async fn crear_webhook(
+        &self,
+        url: String,
+        eventos: Vec<TipoEvento>,
+        secret: Option<String>,
+    ) -> Result<Webhook, WebhookError> {
+        let secret = secret.unwrap_or_else(generar_secret);
+        let webhook = Webhook::new(url, eventos, secret)?;
+        self.repository.guardar(&webhook).await?;
+        Ok(webhook)
+    }
+
+    async fn obtener_webhook(&self, id: &str) -> Result<Option<Webhook>, WebhookError> {
+        self.repository.obtener(id).await
+    }
+
+    async fn listar_webhooks(&self) -> Result<Vec<Webhook>, WebhookError> {
+        self.repository.listar().await
+    }
+
+    async fn activar_webhook(&self, id: &str) -> Result<Webhook, WebhookError> {
+        let mut webhook = self.obtener_existente(id).await?;
+        webhook.activar();
+        self.repository.actualizar(&webhook).await?;
+        Ok(webhook)
+    }
+
+    async fn desactivar_webhook(&self, id: &str) -> Result<Webhook, WebhookError> {
+        let mut webhook = self.obtener_existente(id).await?;
+        webhook.desactivar();
+        self.repository.actualizar(&webhook).await?;
+        Ok(webhook)
+    }
+
+    async fn eliminar_webhook(&self, id: &str) -> Result<(), WebhookError> {
+        self.obtener_existente(id).await?;
+        self.repository.eliminar(id).await
+    }
+
+    async fn listar_entregas(&self, webhook_id: &str) -> Result<Vec<EntregaWebhook>, WebhookError> {
+        self.obtener_existente(webhook_id).await?;
+        self.entregas.listar_por_webhook(webhook_id).await
+    }
+
+    async fn probar_webhook(&self, id: &str) -> Result<EntregaWebhook, WebhookError> {
+        let webhook = self.obtener_existente(id).await?;
+
+        // Un único intento: el administrador quiere ver el resultado en el momento
+        self.dispatcher
+            .intentar(&webhook, &Evento::prueba(), 1)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{MockEntregaRepository, MockSender, MockWebhookRepository};
+    use std::sync::Arc;
+
+    fn crear_service(
+        fallos: u32,
+    ) -> WebhookServiceImpl<MockWebhookRepository, MockEntregaRepository> {
+        let repository = MockWebhookRepository::default();
+        let entregas = MockEntregaRepository::default();
+        let dispatcher = WebhookDispatcher::new(
+            Arc::new(repository.clone()),
+            Arc::new(entregas.clone()),
+            Arc::new(MockSender::new(fallos)),
+        );
+
+        WebhookServiceImpl::new(repository, entregas, dispatcher)
+    }
+
+    #[tokio::test]
+    async fn crear_webhook_genera_secret_si_no_se_indica() {
+        let service = crear_service(0);
+
+        let webhook = service
+            .crear_webhook(
+                "https://example.com/hook".into(),
+                vec![TipoEvento::ReservaCreada],
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(webhook.secret.starts_with("whsec_"));
+        assert_eq!(service.listar_webhooks().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn desactivar_webhook_inexistente_falla() {
+        let service = crear_service(0);
+
+        let resultado = service.desactivar_webhook("no-existe").await;
+
+        assert_eq!(resultado, Err(WebhookError::NoEncontrado));
+    }
+
+    #[tokio::test]
+    async fn probar_webhook_registra_la_entrega() {
+        let service = crear_service(u32::MAX);
+        let webhook = service
+            .crear_webhook(
+                "https://example.com/hook".into(),
+                vec![TipoEvento::SalaCreada],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let entrega = service.probar_webhook(&webhook.id).await.unwrap();
+
+        assert!(!entrega.exito);
+        assert_eq!(entrega.tipo_evento, TipoEvento::Prueba);
+        assert_eq!(entrega.status_code, Some(503));
+        assert_eq!(service.listar_entregas(&webhook.id).await.unwrap().len(), 1);
+    }
+}
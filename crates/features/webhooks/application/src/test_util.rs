@@ -0,0 +1,141 @@
+//! Repositorios y sender en memoria compartidos por los tests del dispatcher y
+//! del servicio
+
+use crate::ports::{EntregaRepository, WebhookRepository, WebhookSender};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use webhooks_domain::{EntregaWebhook, Webhook, WebhookError};
+
+#[derive(Clone, Default)]
+pub(crate) struct MockWebhookRepository {
+    webhooks: Arc<Mutex<HashMap<String, Webhook>>>,
+}
+
+impl MockWebhookRepository {
+    pub(crate) fn con(webhooks: &[Webhook]) -> Self {
+        let repository = Self::default();
+        repository
+            .webhooks
+            .lock()
+            .unwrap()
+            .extend(webhooks.iter().map(|w| (w.id.clone(), w.clone())));
+        repository
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for MockWebhookRepository {
+    async fn guardar(&self, webhook: &Webhook) -> Result<(), WebhookError> {
+        self.webhooks
+            .lock()
+            .unwrap()
+            .insert(webhook.id.clone(), webhook.clone());
+        Ok(())
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Webhook>, WebhookError> {
+        Ok(self.webhooks.lock().unwrap().get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Webhook>, WebhookError> {
+        Ok(self.webhooks.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn actualizar(&self, webhook: &Webhook) -> Result<(), WebhookError> {
+        self.guardar(webhook).await
+    }
+
+    async fn eliminar(&self, id: &str) -> Result<(), WebhookError> {
+        self.webhooks.lock().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+/// Log de entregas en memoria; puede fallar al registrar las de un webhook
+#[derive(Clone, Default)]
+pub(crate) struct MockEntregaRepository {
+    pub(crate) entregas: Arc<Mutex<Vec<EntregaWebhook>>>,
+    falla_para: Option<String>,
+}
+
+impl MockEntregaRepository {
+    pub(crate) fn fallando_para(webhook_id: &str) -> Self {
+        Self {
+            falla_para: Some(webhook_id.to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl EntregaRepository for MockEntregaRepository {
+    async fn registrar(&self, entrega: &EntregaWebhook) -> Result<(), WebhookError> {
+        if self.falla_para.as_deref() == Some(entrega.webhook_id.as_str()) {
+            return Err(WebhookError::ErrorRepositorio("Disco lleno".to_string()));
+        }
+        self.entregas.lock().unwrap().push(entrega.clone());
+        Ok(())
+    }
+
+    async fn listar_por_webhook(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<EntregaWebhook>, WebhookError> {
+        Ok(self
+            .entregas
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.webhook_id == webhook_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// URL, cuerpo y cabeceras de una petición enviada
+pub(crate) type Peticion = (String, String, Vec<(String, String)>);
+
+/// Sender que responde 503 a las primeras `fallos` peticiones y siempre a las
+/// URLs caídas; guarda todo lo recibido
+#[derive(Default)]
+pub(crate) struct MockSender {
+    fallos: u32,
+    caidas: Vec<String>,
+    pub(crate) recibidos: Mutex<Vec<Peticion>>,
+}
+
+impl MockSender {
+    pub(crate) fn new(fallos: u32) -> Self {
+        Self {
+            fallos,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn con_caida(url: &str) -> Self {
+        Self {
+            caidas: vec![url.to_string()],
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for MockSender {
+    async fn enviar(
+        &self,
+        url: &str,
+        cuerpo: &str,
+        cabeceras: &[(String, String)],
+    ) -> Result<u16, String> {
+        let mut recibidos = self.recibidos.lock().unwrap();
+        recibidos.push((url.to_string(), cuerpo.to_string(), cabeceras.to_vec()));
+
+        if recibidos.len() as u32 <= self.fallos || self.caidas.iter().any(|c| c == url) {
+            Ok(503)
+        } else {
+            Ok(200)
+        }
+    }
+}
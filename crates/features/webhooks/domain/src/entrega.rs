@@ -0,0 +1,45 @@
+use crate::evento::TipoEvento;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Registro de un intento de entrega de un evento a un webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntregaWebhook {
+    pub id: String,
+    pub webhook_id: String,
+    pub evento_id: String,
+    pub tipo_evento: TipoEvento,
+    /// Número de intento (empezando en 1)
+    pub intento: u32,
+    pub exito: bool,
+    /// Código HTTP devuelto por el destino, si llegó a responder
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub fecha: DateTime<Utc>,
+}
+
+impl EntregaWebhook {
+    pub fn new(
+        webhook_id: String,
+        evento_id: String,
+        tipo_evento: TipoEvento,
+        intento: u32,
+        status_code: Option<u16>,
+        error: Option<String>,
+    ) -> Self {
+        let exito = error.is_none() && status_code.is_some_and(|c| (200..300).contains(&c));
+
+        Self {
+            id: Uuid::new_v4().to_string(),
+            webhook_id,
+            evento_id,
+            tipo_evento,
+            intento,
+            exito,
+            status_code,
+            error,
+            fecha: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookError {
+    NoEncontrado,
+    ErrorRepositorio(String),
+    ErrorEntrega(String),
+    Validacion(Vec<String>),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::NoEncontrado => write!(f, "Webhook no encontrado"),
+            WebhookError::ErrorRepositorio(msg) => write!(f, "Error en repositorio: {}", msg),
+            WebhookError::ErrorEntrega(msg) => write!(f, "Error al entregar el evento: {}", msg),
+            WebhookError::Validacion(msgs) => {
+                write!(f, "Errores de validación: {}", msgs.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
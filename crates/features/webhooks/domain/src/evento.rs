@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Tipos de evento a los que se puede suscribir un webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TipoEvento {
+    #[serde(rename = "reserva.creada")]
+    ReservaCreada,
+    #[serde(rename = "reserva.cancelada")]
+    ReservaCancelada,
+    #[serde(rename = "reserva.completada")]
+    ReservaCompletada,
+    /// Una reserva ha cambiado de horario
+    #[serde(rename = "reserva.reprogramada")]
+    ReservaReprogramada,
+    #[serde(rename = "sala.creada")]
+    SalaCreada,
+    #[serde(rename = "sala.activada")]
+    SalaActivada,
+    #[serde(rename = "sala.desactivada")]
+    SalaDesactivada,
+    #[serde(rename = "sala.actualizada")]
+    SalaActualizada,
+    #[serde(rename = "sala.eliminada")]
+    SalaEliminada,
+    /// Evento de ejemplo enviado desde el endpoint de prueba
+    #[serde(rename = "webhook.prueba")]
+    Prueba,
+}
+
+impl TipoEvento {
+    /// Todos los tipos de evento publicados por el sistema
+    pub const TODOS: [TipoEvento; 9] = [
+        TipoEvento::ReservaCreada,
+        TipoEvento::ReservaCancelada,
+        TipoEvento::ReservaCompletada,
+        TipoEvento::ReservaReprogramada,
+        TipoEvento::SalaCreada,
+        TipoEvento::SalaActivada,
+        TipoEvento::SalaDesactivada,
+        TipoEvento::SalaActualizada,
+        TipoEvento::SalaEliminada,
+    ];
+
+    /// Nombre del evento tal y como viaja en el payload y en las cabeceras
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TipoEvento::ReservaCreada => "reserva.creada",
+            TipoEvento::ReservaCancelada => "reserva.cancelada",
+            TipoEvento::ReservaCompletada => "reserva.completada",
+            TipoEvento::ReservaReprogramada => "reserva.reprogramada",
+            TipoEvento::SalaCreada => "sala.creada",
+            TipoEvento::SalaActivada => "sala.activada",
+            TipoEvento::SalaDesactivada => "sala.desactivada",
+            TipoEvento::SalaActualizada => "sala.actualizada",
+            TipoEvento::SalaEliminada => "sala.eliminada",
+            TipoEvento::Prueba => "webhook.prueba",
+        }
+    }
+
+    /// Crea un TipoEvento desde su nombre
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "reserva.creada" => Some(TipoEvento::ReservaCreada),
+            "reserva.cancelada" => Some(TipoEvento::ReservaCancelada),
+            "reserva.completada" => Some(TipoEvento::ReservaCompletada),
+            "reserva.reprogramada" => Some(TipoEvento::ReservaReprogramada),
+            "sala.creada" => Some(TipoEvento::SalaCreada),
+            "sala.activada" => Some(TipoEvento::SalaActivada),
+            "sala.desactivada" => Some(TipoEvento::SalaDesactivada),
+            "sala.actualizada" => Some(TipoEvento::SalaActualizada),
+            "sala.eliminada" => Some(TipoEvento::SalaEliminada),
+            "webhook.prueba" => Some(TipoEvento::Prueba),
+            _ => None,
+        }
+    }
+}
+
+/// Evento de dominio que se entrega a los webhooks suscritos
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evento {
+    pub id: String,
+    pub tipo: TipoEvento,
+    pub ocurrido_en: DateTime<Utc>,
+    /// Representación JSON de la entidad afectada (sala o reserva)
+    pub datos: serde_json::Value,
+}
+
+impl Evento {
+    pub fn new(tipo: TipoEvento, datos: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tipo,
+            ocurrido_en: Utc::now(),
+            datos,
+        }
+    }
+
+    /// Evento de ejemplo para probar la integración de un webhook
+    pub fn prueba() -> Self {
+        Self::new(
+            TipoEvento::Prueba,
+            serde_json::json!({
+                "mensaje": "Evento de prueba enviado desde el sistema de reservas"
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tipo_evento_ida_y_vuelta() {
+        for tipo in TipoEvento::TODOS {
+            assert_eq!(TipoEvento::from_str_opt(tipo.as_str()), Some(tipo));
+        }
+        assert_eq!(TipoEvento::from_str_opt("otro"), None);
+    }
+
+    #[test]
+    fn tipo_evento_se_serializa_con_su_nombre() {
+        let json = serde_json::to_string(&TipoEvento::ReservaCreada).unwrap();
+        assert_eq!(json, "\"reserva.creada\"");
+    }
+}
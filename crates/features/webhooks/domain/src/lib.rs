@@ -0,0 +1,9 @@
+pub mod entrega;
+pub mod error;
+pub mod evento;
+pub mod webhook;
+
+pub use entrega::EntregaWebhook;
+pub use error::WebhookError;
+pub use evento::{Evento, TipoEvento};
+pub use webhook::{Webhook, SECRET_MIN_LENGTH};
@@ -0,0 +1,148 @@
+use crate::error::WebhookError;
+use crate::evento::TipoEvento;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Longitud mínima del secreto usado para firmar los payloads
+pub const SECRET_MIN_LENGTH: usize = 16;
+
+/// Suscripción de un sistema externo a eventos de reservas y salas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    /// URL a la que se hace POST con cada evento
+    pub url: String,
+    /// Filtro de eventos: solo se entregan los tipos incluidos
+    pub eventos: Vec<TipoEvento>,
+    /// Secreto compartido para firmar los payloads (HMAC-SHA256)
+    pub secret: String,
+    pub activo: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Crea una nueva suscripción con validaciones
+    pub fn new(
+        url: String,
+        eventos: Vec<TipoEvento>,
+        secret: String,
+    ) -> Result<Self, WebhookError> {
+        let mut errores: Vec<String> = Vec::new();
+        let url = url.trim().to_string();
+
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            errores.push("La URL debe empezar por http:// o https://".to_string());
+        }
+
+        if eventos.is_empty() {
+            errores.push("Debe suscribirse al menos a un evento".to_string());
+        }
+
+        if secret.len() < SECRET_MIN_LENGTH {
+            errores.push(format!(
+                "El secreto debe tener al menos {} caracteres",
+                SECRET_MIN_LENGTH
+            ));
+        }
+
+        if !errores.is_empty() {
+            return Err(WebhookError::Validacion(errores));
+        }
+
+        // Quita repetidos aunque no estén seguidos, conservando el orden
+        let mut vistos = HashSet::new();
+        let mut eventos = eventos;
+        eventos.retain(|tipo| vistos.insert(*tipo));
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            url,
+            eventos,
+            secret,
+            activo: true,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Indica si el webhook debe recibir un evento del tipo dado
+    pub fn escucha(&self, tipo: TipoEvento) -> bool {
+        self.activo && (tipo == TipoEvento::Prueba || self.eventos.contains(&tipo))
+    }
+
+    pub fn activar(&mut self) {
+        self.activo = true;
+    }
+
+    pub fn desactivar(&mut self) {
+        self.activo = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secreto() -> String {
+        "s".repeat(SECRET_MIN_LENGTH)
+    }
+
+    #[test]
+    fn crear_webhook_valido() {
+        let webhook = Webhook::new(
+            "https://chat.example.com/hooks/reservas".into(),
+            vec![TipoEvento::ReservaCreada],
+            secreto(),
+        )
+        .unwrap();
+
+        assert!(webhook.activo);
+        assert!(webhook.escucha(TipoEvento::ReservaCreada));
+        assert!(!webhook.escucha(TipoEvento::SalaCreada));
+    }
+
+    #[test]
+    fn crear_webhook_quita_eventos_repetidos() {
+        let webhook = Webhook::new(
+            "https://chat.example.com/hooks/reservas".into(),
+            vec![
+                TipoEvento::ReservaCreada,
+                TipoEvento::SalaCreada,
+                TipoEvento::ReservaCreada,
+                TipoEvento::SalaCreada,
+            ],
+            secreto(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            webhook.eventos,
+            vec![TipoEvento::ReservaCreada, TipoEvento::SalaCreada]
+        );
+    }
+
+    #[test]
+    fn crear_webhook_acumula_errores() {
+        let resultado = Webhook::new("ftp://example.com".into(), vec![], "corto".into());
+
+        match resultado {
+            Err(WebhookError::Validacion(errores)) => assert_eq!(errores.len(), 3),
+            otro => panic!("Se esperaba error de validación: {:?}", otro),
+        }
+    }
+
+    #[test]
+    fn webhook_inactivo_no_escucha() {
+        let mut webhook = Webhook::new(
+            "http://localhost:9000".into(),
+            vec![TipoEvento::SalaCreada],
+            secreto(),
+        )
+        .unwrap();
+
+        webhook.desactivar();
+        assert!(!webhook.escucha(TipoEvento::SalaCreada));
+        assert!(!webhook.escucha(TipoEvento::Prueba));
+    }
+}
@@ -0,0 +1,261 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+use webhooks_application::{EntregaRepository, WebhookRepository};
+use webhooks_domain::{EntregaWebhook, Webhook, WebhookError};
+
+/// Estructura para persistir las suscripciones en JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WebhooksData {
+    webhooks: HashMap<String, Webhook>,
+}
+
+/// Estructura para persistir el log de entregas en JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EntregasData {
+    entregas: Vec<EntregaWebhook>,
+}
+
+/// Escribe el JSON en disco creando el directorio si no existe
+async fn escribir_json<T: Serialize>(file_path: &Path, data: &T) -> Result<(), WebhookError> {
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).await.map_err(|e| {
+            WebhookError::ErrorRepositorio(format!("Error al crear directorio: {}", e))
+        })?;
+    }
+
+    let json = serde_json::to_string_pretty(data)
+        .map_err(|e| WebhookError::ErrorRepositorio(format!("Error al serializar JSON: {}", e)))?;
+
+    fs::write(file_path, json)
+        .await
+        .map_err(|e| WebhookError::ErrorRepositorio(format!("Error al escribir archivo: {}", e)))
+}
+
+/// Lee el JSON de disco; `None` si el archivo todavía no existe
+async fn leer_json<T: for<'de> Deserialize<'de>>(
+    file_path: &Path,
+) -> Result<Option<T>, WebhookError> {
+    if !file_path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(file_path)
+        .await
+        .map_err(|e| WebhookError::ErrorRepositorio(format!("Error al leer archivo: {}", e)))?;
+
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| WebhookError::ErrorRepositorio(format!("Error al parsear JSON: {}", e)))
+}
+
+/// Adaptador de repositorio que guarda las suscripciones en un archivo JSON
+#[derive(Clone)]
+pub struct FileWebhookRepository {
+    file_path: PathBuf,
+    cache: Arc<RwLock<HashMap<String, Webhook>>>,
+}
+
+impl FileWebhookRepository {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/webhooks.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/webhooks.json"))
+    }
+
+    /// Inicializa el repositorio cargando datos del archivo
+    pub async fn init(&self) -> Result<(), WebhookError> {
+        if let Some(data) = leer_json::<WebhooksData>(&self.file_path).await? {
+            *self.cache.write().await = data.webhooks;
+        }
+        Ok(())
+    }
+
+    async fn save_to_file(&self) -> Result<(), WebhookError> {
+        let webhooks = self.cache.read().await.clone();
+        escribir_json(&self.file_path, &WebhooksData { webhooks }).await
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for FileWebhookRepository {
+    async fn guardar(&self, webhook: &Webhook) -> Result<(), WebhookError> {
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(webhook.id.clone(), webhook.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn obtener(&self, id: &str) -> Result<Option<Webhook>, WebhookError> {
+        let cache = self.cache.read().await;
+        Ok(cache.get(id).cloned())
+    }
+
+    async fn listar(&self) -> Result<Vec<Webhook>, WebhookError> {
+        let cache = self.cache.read().await;
+        Ok(cache.values().cloned().collect())
+    }
+
+    async fn actualizar(&self, webhook: &Webhook) -> Result<(), WebhookError> {
+        {
+            let mut cache = self.cache.write().await;
+            if !cache.contains_key(&webhook.id) {
+                return Err(WebhookError::NoEncontrado);
+            }
+            cache.insert(webhook.id.clone(), webhook.clone());
+        }
+        self.save_to_file().await
+    }
+
+    async fn eliminar(&self, id: &str) -> Result<(), WebhookError> {
+        {
+            let mut cache = self.cache.write().await;
+            if cache.remove(id).is_none() {
+                return Err(WebhookError::NoEncontrado);
+            }
+        }
+        self.save_to_file().await
+    }
+}
+
+/// Adaptador que guarda el log de entregas en un archivo JSON
+#[derive(Clone)]
+pub struct FileEntregaRepository {
+    file_path: PathBuf,
+    cache: Arc<RwLock<Vec<EntregaWebhook>>>,
+}
+
+impl FileEntregaRepository {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cache: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Crea un repositorio con ruta por defecto (./data/webhook_entregas.json)
+    pub fn default_path() -> Self {
+        Self::new(PathBuf::from("./data/webhook_entregas.json"))
+    }
+
+    /// Inicializa el repositorio cargando datos del archivo
+    pub async fn init(&self) -> Result<(), WebhookError> {
+        if let Some(data) = leer_json::<EntregasData>(&self.file_path).await? {
+            *self.cache.write().await = data.entregas;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EntregaRepository for FileEntregaRepository {
+    async fn registrar(&self, entrega: &EntregaWebhook) -> Result<(), WebhookError> {
+        let entregas = {
+            let mut cache = self.cache.write().await;
+            cache.push(entrega.clone());
+            cache.clone()
+        };
+        escribir_json(&self.file_path, &EntregasData { entregas }).await
+    }
+
+    async fn listar_por_webhook(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<EntregaWebhook>, WebhookError> {
+        let cache = self.cache.read().await;
+        Ok(cache
+            .iter()
+            .filter(|e| e.webhook_id == webhook_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use webhooks_domain::TipoEvento;
+
+    fn webhook() -> Webhook {
+        Webhook::new(
+            "https://example.com/hook".to_string(),
+            vec![TipoEvento::ReservaCreada],
+            "secreto-de-pruebas-123".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_persistencia_de_webhooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("webhooks.json");
+        let webhook = webhook();
+
+        {
+            let repo = FileWebhookRepository::new(file_path.clone());
+            repo.init().await.unwrap();
+            repo.guardar(&webhook).await.unwrap();
+        }
+
+        let repo = FileWebhookRepository::new(file_path);
+        repo.init().await.unwrap();
+
+        let cargado = repo.obtener(&webhook.id).await.unwrap().unwrap();
+        assert_eq!(cargado, webhook);
+    }
+
+    #[tokio::test]
+    async fn test_eliminar_webhook_inexistente() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = FileWebhookRepository::new(temp_dir.path().join("webhooks.json"));
+        repo.init().await.unwrap();
+
+        assert_eq!(
+            repo.eliminar("no-existe").await,
+            Err(WebhookError::NoEncontrado)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_log_de_entregas_por_webhook() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("entregas.json");
+
+        {
+            let repo = FileEntregaRepository::new(file_path.clone());
+            repo.init().await.unwrap();
+
+            for (webhook_id, status) in [("w1", 500), ("w1", 200), ("w2", 200)] {
+                let entrega = EntregaWebhook::new(
+                    webhook_id.to_string(),
+                    "e1".to_string(),
+                    TipoEvento::ReservaCreada,
+                    1,
+                    Some(status),
+                    None,
+                );
+                repo.registrar(&entrega).await.unwrap();
+            }
+        }
+
+        let repo = FileEntregaRepository::new(file_path);
+        repo.init().await.unwrap();
+
+        let entregas = repo.listar_por_webhook("w1").await.unwrap();
+        assert_eq!(entregas.len(), 2);
+        assert!(!entregas[0].exito);
+        assert!(entregas[1].exito);
+    }
+}
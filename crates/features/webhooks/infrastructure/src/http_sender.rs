@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use std::time::Duration;
+use webhooks_application::WebhookSender;
+
+/// Timeout por defecto de cada petición a un webhook
+const TIMEOUT_POR_DEFECTO: Duration = Duration::from_secs(10);
+
+/// Adaptador HTTP que entrega los eventos con reqwest
+#[derive(Clone)]
+pub struct ReqwestWebhookSender {
+    client: reqwest::Client,
+}
+
+impl ReqwestWebhookSender {
+    pub fn new() -> Self {
+        Self::with_timeout(TIMEOUT_POR_DEFECTO)
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("No se pudo crear el cliente HTTP");
+
+        Self { client }
+    }
+}
+
+impl Default for ReqwestWebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WebhookSender for ReqwestWebhookSender {
+    async fn enviar(
+        &self,
+        url: &str,
+        cuerpo: &str,
+        cabeceras: &[(String, String)],
+    ) -> Result<u16, String> {
+        let mut request = self.client.post(url).body(cuerpo.to_string());
+
+        for (nombre, valor) in cabeceras {
+            request = request.header(nombre, valor);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Error de conexión: {}", e))?;
+
+        Ok(response.status().as_u16())
+    }
+}
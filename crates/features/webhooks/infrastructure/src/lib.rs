@@ -0,0 +1,5 @@
+pub mod file_repository;
+pub mod http_sender;
+
+pub use file_repository::{FileEntregaRepository, FileWebhookRepository};
+pub use http_sender::ReqwestWebhookSender;
@@ -0,0 +1,232 @@
+//! Conversión a `tonic::Status` con detalles estándar de Google
+//!
+//! `tonic-types` no está disponible para nuestra versión de tonic, así que
+//! los mensajes `google.rpc.*` se definen aquí con prost. Los clientes que
+//! entienden "rich error details" (grpcurl, tonic-types, grpc-gateway...)
+//! los decodifican sin configuración adicional.
+
+use crate::{Categoria, ErrorCampo, ErrorDominio, Idioma, DOMINIO};
+use prost::Message;
+use std::collections::HashMap;
+use tonic::metadata::MetadataMap;
+use tonic::{Code, Status};
+
+const TIPO_ERROR_INFO: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+const TIPO_BAD_REQUEST: &str = "type.googleapis.com/google.rpc.BadRequest";
+
+/// `google.rpc.Status`
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+/// `google.protobuf.Any`
+#[derive(Clone, PartialEq, Message)]
+struct Any {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+/// `google.rpc.ErrorInfo`
+#[derive(Clone, PartialEq, Message)]
+struct ErrorInfo {
+    #[prost(string, tag = "1")]
+    reason: String,
+    #[prost(string, tag = "2")]
+    domain: String,
+    #[prost(map = "string, string", tag = "3")]
+    metadata: HashMap<String, String>,
+}
+
+/// `google.rpc.BadRequest`
+#[derive(Clone, PartialEq, Message)]
+struct BadRequest {
+    #[prost(message, repeated, tag = "1")]
+    field_violations: Vec<FieldViolation>,
+}
+
+/// `google.rpc.BadRequest.FieldViolation`
+#[derive(Clone, PartialEq, Message)]
+struct FieldViolation {
+    #[prost(string, tag = "1")]
+    field: String,
+    #[prost(string, tag = "2")]
+    description: String,
+    #[prost(string, tag = "3")]
+    reason: String,
+}
+
+pub fn codigo_grpc(categoria: Categoria) -> Code {
+    match categoria {
+        Categoria::Validacion => Code::InvalidArgument,
+        Categoria::NoEncontrado => Code::NotFound,
+        Categoria::Duplicado => Code::AlreadyExists,
+        Categoria::Conflicto => Code::FailedPrecondition,
+        Categoria::NoAutenticado => Code::Unauthenticated,
+        Categoria::PermisoDenegado => Code::PermissionDenied,
+        Categoria::DemasiadasPeticiones => Code::ResourceExhausted,
+        Categoria::Interno => Code::Internal,
+    }
+}
+
+/// Convierte un error de dominio en `Status` con `ErrorInfo` y, si hay
+/// campos, `BadRequest` (mensajes en español)
+pub fn a_status<E: ErrorDominio + ?Sized>(error: &E) -> Status {
+    a_status_en(error, Idioma::default())
+}
+
+/// Igual que [`a_status`] con los mensajes en el idioma pedido
+pub fn a_status_en<E: ErrorDominio + ?Sized>(error: &E, idioma: Idioma) -> Status {
+    let code = codigo_grpc(error.categoria());
+    let message = error.mensaje_en(idioma);
+
+    let mut details = vec![Any {
+        type_url: TIPO_ERROR_INFO.to_string(),
+        value: ErrorInfo {
+            reason: error.codigo().to_string(),
+            domain: DOMINIO.to_string(),
+            metadata: HashMap::new(),
+        }
+        .encode_to_vec(),
+    }];
+
+    let campos = error.campos_en(idioma);
+    if !campos.is_empty() {
+        details.push(Any {
+            type_url: TIPO_BAD_REQUEST.to_string(),
+            value: BadRequest {
+                field_violations: campos
+                    .into_iter()
+                    .map(|c| FieldViolation {
+                        field: c.campo,
+                        description: c.mensaje,
+                        reason: c.codigo,
+                    })
+                    .collect(),
+            }
+            .encode_to_vec(),
+        });
+    }
+
+    let estado = RpcStatus {
+        code: code as i32,
+        message: message.clone(),
+        details,
+    };
+
+    Status::with_details(code, message, estado.encode_to_vec().into())
+}
+
+/// Idioma pedido en la metadata `accept-language` de una llamada gRPC
+pub fn idioma_de_metadata(metadata: &MetadataMap) -> Option<Idioma> {
+    metadata
+        .get("accept-language")
+        .and_then(|v| v.to_str().ok())
+        .and_then(Idioma::desde_accept_language)
+}
+
+/// Detalles estructurados recuperados de un `Status`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DetallesError {
+    pub codigo: Option<String>,
+    pub campos: Vec<ErrorCampo>,
+}
+
+/// Lee el código y los errores por campo de un `Status` generado con
+/// [`a_status`] (útil en clientes gRPC)
+pub fn detalles(status: &Status) -> DetallesError {
+    let mut resultado = DetallesError::default();
+
+    let Ok(estado) = RpcStatus::decode(status.details()) else {
+        return resultado;
+    };
+
+    for detalle in estado.details {
+        match detalle.type_url.as_str() {
+            TIPO_ERROR_INFO => {
+                if let Ok(info) = ErrorInfo::decode(detalle.value.as_slice()) {
+                    resultado.codigo = Some(info.reason);
+                }
+            }
+            TIPO_BAD_REQUEST => {
+                if let Ok(bad) = BadRequest::decode(detalle.value.as_slice()) {
+                    resultado.campos.extend(
+                        bad.field_violations
+                            .into_iter()
+                            .map(|v| ErrorCampo::new(v.field, v.reason, v.description)),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    resultado
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SinCampos;
+
+    impl ErrorDominio for SinCampos {
+        fn codigo(&self) -> &'static str {
+            "RESERVA_NO_ENCONTRADA"
+        }
+
+        fn categoria(&self) -> Categoria {
+            Categoria::NoEncontrado
+        }
+    }
+
+    struct ConCampos;
+
+    impl ErrorDominio for ConCampos {
+        fn codigo(&self) -> &'static str {
+            "SALA_VALIDACION"
+        }
+
+        fn categoria(&self) -> Categoria {
+            Categoria::Validacion
+        }
+
+        fn campos(&self) -> Vec<ErrorCampo> {
+            vec![ErrorCampo::con_codigo("nombre", "SALA_NOMBRE_VACIO")]
+        }
+    }
+
+    #[test]
+    fn status_sin_campos_lleva_error_info() {
+        let status = a_status(&SinCampos);
+
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Reserva no encontrada");
+
+        let detalles = detalles(&status);
+        assert_eq!(detalles.codigo.as_deref(), Some("RESERVA_NO_ENCONTRADA"));
+        assert!(detalles.campos.is_empty());
+    }
+
+    #[test]
+    fn status_con_campos_lleva_bad_request() {
+        let status = a_status_en(&ConCampos, Idioma::En);
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(
+            detalles(&status).campos,
+            vec![ErrorCampo::new(
+                "nombre",
+                "SALA_NOMBRE_VACIO",
+                "The name cannot be empty"
+            )]
+        );
+    }
+}
@@ -0,0 +1,620 @@
+//! Catálogo de mensajes localizados (es/en) indexado por código de error
+//!
+//! Los textos admiten parámetros con la sintaxis `{nombre}`. Si falta la
+//! traducción en un idioma se usa la española, y si tampoco existe se
+//! devuelve el propio código para que el fallo sea visible.
+
+use serde::{Deserialize, Serialize};
+
+/// Idiomas soportados en los mensajes de cara al usuario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Idioma {
+    #[default]
+    Es,
+    En,
+}
+
+impl Idioma {
+    pub const TODOS: [Idioma; 2] = [Idioma::Es, Idioma::En];
+
+    /// Código ISO 639-1 (`es`, `en`)
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            Idioma::Es => "es",
+            Idioma::En => "en",
+        }
+    }
+
+    /// Acepta `es`, `EN`, `en-US`, `es_MX.UTF-8`...
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        let base = s
+            .trim()
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match base.as_str() {
+            "es" => Some(Idioma::Es),
+            "en" => Some(Idioma::En),
+            _ => None,
+        }
+    }
+
+    /// Elige el idioma soportado con mayor peso en una cabecera `Accept-Language`
+    ///
+    /// `"en-US,en;q=0.9,es;q=0.8"` → `En`. Devuelve `None` si ninguno encaja.
+    pub fn desde_accept_language(cabecera: &str) -> Option<Self> {
+        let mut candidatos: Vec<(Idioma, f32)> = cabecera
+            .split(',')
+            .filter_map(|parte| {
+                let mut trozos = parte.split(';');
+                let idioma = Idioma::from_str_opt(trozos.next()?)?;
+                let peso = trozos
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (peso > 0.0).then_some((idioma, peso))
+            })
+            .collect();
+
+        // Orden estable: a igual peso gana el que aparece antes
+        candidatos.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidatos.first().map(|(idioma, _)| *idioma)
+    }
+
+    /// Idioma del sistema operativo según `LC_ALL`, `LC_MESSAGES` o `LANG`
+    pub fn del_sistema() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|valor| Idioma::from_str_opt(&valor))
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for Idioma {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.codigo())
+    }
+}
+
+/// Mensaje para `codigo` en `idioma` sustituyendo los parámetros
+pub fn traducir(codigo: &str, idioma: Idioma, parametros: &[(&str, String)]) -> String {
+    let plantilla = plantilla(codigo, idioma)
+        .or_else(|| plantilla(codigo, Idioma::Es))
+        .unwrap_or(codigo);
+
+    parametros
+        .iter()
+        .fold(plantilla.to_string(), |texto, (nombre, valor)| {
+            texto.replace(&format!("{{{}}}", nombre), valor)
+        })
+}
+
+/// Indica si el mensaje de `codigo` usa el parámetro `{nombre}`
+pub fn usa_parametro(codigo: &str, idioma: Idioma, nombre: &str) -> bool {
+    plantilla(codigo, idioma)
+        .or_else(|| plantilla(codigo, Idioma::Es))
+        .is_some_and(|p| p.contains(&format!("{{{}}}", nombre)))
+}
+
+/// Indica si el catálogo tiene texto para un código
+pub fn existe(codigo: &str) -> bool {
+    plantilla(codigo, Idioma::Es).is_some()
+}
+
+fn plantilla(codigo: &str, idioma: Idioma) -> Option<&'static str> {
+    match idioma {
+        Idioma::Es => es(codigo),
+        Idioma::En => en(codigo),
+    }
+}
+
+fn es(codigo: &str) -> Option<&'static str> {
+    let texto = match codigo {
+        // Salas
+        "SALA_NOMBRE_VACIO" => "El nombre no puede estar vacío",
+        "SALA_NOMBRE_DEMASIADO_LARGO" => "El nombre no puede exceder 100 caracteres",
+        "SALA_NOMBRE_LONGITUD" => "El nombre debe tener entre 1 y 100 caracteres",
+        "SALA_CAPACIDAD_INVALIDA" | "SALA_CAPACIDAD_RANGO" => {
+            "La capacidad debe ser entre 1 y 1000"
+        }
+        "SALA_NO_ENCONTRADA" => "Sala no encontrada",
+        "SALA_CON_RESERVAS_FUTURAS" => {
+            "La sala tiene {n} reservas futuras activas; cancélalas o usa la opción de cancelarlas"
+        }
+        "SALA_ERROR_REPOSITORIO" => "Error en repositorio: {detalle}",
+        "SALA_VALIDACION" => "Errores de validación: {errores}",
+        "SALA_ACCION_RESERVAS_INVALIDA" => "Valor de 'reservas' inválido. Use: rechazar o cancelar",
+        "SALA_ZONA_HORARIA_INVALIDA" => {
+            "La zona horaria '{zona}' no es válida. Usa un nombre IANA, p. ej. Europe/Madrid"
+        }
+        "SALA_ORDEN_INVALIDO" => "Orden inválido. Use: nombre o capacidad",
+        "SALA_MARGEN_ANTES_RANGO" => "El margen de preparación debe ser de 0 a 240 minutos",
+        "SALA_MARGEN_DESPUES_RANGO" => "El margen de limpieza debe ser de 0 a 240 minutos",
+
+        // Recursos
+        "RECURSO_NOMBRE_VACIO" => "El nombre no puede estar vacío",
+        "RECURSO_NOMBRE_DEMASIADO_LARGO" => "El nombre no puede exceder 100 caracteres",
+        "RECURSO_TIPO_INVALIDO" => {
+            "Tipo de recurso '{tipo}' inválido. Use: equipo, aparcamiento u otro"
+        }
+        "RECURSO_NO_ENCONTRADO" => "Recurso no encontrado",
+        "RECURSO_ERROR_REPOSITORIO" => "Error en repositorio: {detalle}",
+        "LISTADO_DIRECCION_INVALIDA" => "Dirección de orden inválida. Use: asc o desc",
+
+        // Reservas
+        "RESERVA_SALA_ID_VACIO" => "El ID de sala no puede estar vacío",
+        "RESERVA_USUARIO_ID_VACIO" => "El ID de usuario no puede estar vacío",
+        "RESERVA_FECHA_INICIO_PASADA" => "La fecha de inicio no puede ser en el pasado",
+        "RESERVA_FECHA_FIN_PASADA" => "La fecha de fin no puede ser en el pasado",
+        "RESERVA_FECHA_FIN_ANTERIOR_A_INICIO" => {
+            "La fecha de fin debe ser posterior a la fecha de inicio"
+        }
+        "RESERVA_DURACION_INVALIDA" => {
+            "La duración de la reserva debe ser entre 15 minutos y 8 horas"
+        }
+        "RESERVA_NO_ENCONTRADA" => "Reserva no encontrada",
+        "RESERVA_SALA_NO_DISPONIBLE" => "La sala no está disponible en el horario solicitado",
+        "RESERVA_ERROR_REPOSITORIO" => "Error en repositorio: {detalle}",
+        "RESERVA_VALIDACION" => "Errores de validación: {errores}",
+        "RESERVA_SALA_NO_EXISTE" => "La sala no existe",
+        "RESERVA_SALA_INACTIVA" => "La sala no está activa",
+        "RESERVA_USUARIO_NO_EXISTE" => "El usuario no existe",
+        "RESERVA_EMAIL_NO_VERIFICADO" => "Verifica tu email antes de hacer reservas",
+        "RESERVA_NO_ACTIVA" => "Solo se pueden modificar reservas activas",
+        "RESERVA_GRUPO_AJENO" => "Solo puedes poner la reserva a nombre de uno de tus grupos",
+        "RESERVA_SIN_DELEGACION" => "El organizador no te ha autorizado a reservar en su nombre",
+        "RESERVA_RECURRENCIA_INVALIDA" => "La recurrencia debe tener entre 1 y {max} repeticiones",
+        "RESERVA_RECURRENCIA_NO_DISPONIBLE" => {
+            "La sala no está disponible en la repetición del {fecha}"
+        }
+        "RESERVA_CONJUNTO_INVALIDO" => "Una reserva conjunta debe tener entre 1 y {max} salas",
+        "RESERVA_CONJUNTO_NO_DISPONIBLE" => {
+            "La sala {sala} no está disponible; no se ha reservado ninguna"
+        }
+        "RESERVA_SIN_SALA_NI_RECURSOS" => "Indica una sala o al menos un recurso",
+        "RESERVA_RECURSO_NO_EXISTE" => "Alguno de los recursos no existe",
+        "RESERVA_RECURSO_INACTIVO" => "Alguno de los recursos no está activo",
+        "RESERVA_RECURSO_NO_DISPONIBLE" => {
+            "El recurso {recurso} no está disponible en el horario solicitado"
+        }
+        "INFORME_RANGO_INVALIDO" => "La fecha 'hasta' debe ser posterior a 'desde'",
+        "INFORME_AGRUPACION_INVALIDA" => "Agrupación inválida. Use: sala, usuario o franja",
+        "RESERVA_ORDEN_INVALIDO" => "Orden inválido. Use: fecha_inicio o creada",
+        "RESERVA_ESTADO_INVALIDO" => "Estado inválido. Use: activa, cancelada o completada",
+
+        // Usuarios
+        "USUARIO_EMAIL_INVALIDO" => {
+            "El email '{email}' no es válido. Por favor, ingresa un email correcto."
+        }
+        "USUARIO_EMAIL_DUPLICADO" => {
+            "El email '{email}' ya está registrado. Intenta con otro email o inicia sesión."
+        }
+        "USUARIO_NOMBRE_VACIO" => "El nombre no puede estar vacío. Por favor, ingresa tu nombre.",
+        "USUARIO_NOMBRE_LONGITUD" => {
+            "El nombre debe tener entre {min} y {max} caracteres (actualmente tiene {actual})."
+        }
+        "USUARIO_PASSWORD_DEMASIADO_CORTA" => {
+            "La contraseña debe tener al menos {min} caracteres (actualmente tiene {actual})."
+        }
+        "USUARIO_NO_ENCONTRADO" => "No se encontró el usuario con ID: {id}",
+        "USUARIO_CREDENCIALES_INVALIDAS" => {
+            "Email o contraseña incorrectos. Por favor, verifica tus credenciales."
+        }
+        "USUARIO_PERMISOS_DENEGADOS" => "No tienes permisos para realizar esta acción.",
+        "USUARIO_ERROR_REPOSITORIO" => "Error al acceder a los datos: {detalle}",
+        "USUARIO_VALIDACION" => "Error de validación: {detalle}",
+        "USUARIO_ZONA_HORARIA_INVALIDA" => {
+            "La zona horaria '{zona}' no es válida. Usa un nombre IANA, p. ej. America/Mexico_City"
+        }
+        "USUARIO_VALIDACION_CAMPOS" => "Errores de validación: {errores}",
+        "USUARIO_ORDEN_INVALIDO" => "Orden inválido. Use: nombre, email o creado",
+        "USUARIO_ROL_INVALIDO" => "Rol inválido. Use: admin o usuario",
+        "USUARIO_CUENTA_BLOQUEADA" => {
+            "Cuenta bloqueada temporalmente por demasiados intentos fallidos. Inténtalo de nuevo en {minutos} minutos."
+        }
+        "USUARIO_DEMASIADOS_INTENTOS" => {
+            "Demasiados intentos de inicio de sesión desde tu conexión. Inténtalo de nuevo en {minutos} minutos."
+        }
+        "USUARIO_TOKEN_INVALIDO" => "El enlace no es válido o ha caducado. Solicita uno nuevo.",
+        "USUARIO_PASSWORD_SIN_MAYUSCULA" => "La contraseña debe incluir al menos una mayúscula",
+        "USUARIO_PASSWORD_SIN_MINUSCULA" => "La contraseña debe incluir al menos una minúscula",
+        "USUARIO_PASSWORD_SIN_DIGITO" => "La contraseña debe incluir al menos un número",
+        "USUARIO_PASSWORD_SIN_SIMBOLO" => "La contraseña debe incluir al menos un símbolo",
+        "USUARIO_PASSWORD_COMUN" => "Esa contraseña es demasiado común. Elige otra.",
+        "USUARIO_ERROR_CORREO" => "No se ha podido enviar el correo. Inténtalo más tarde.",
+        "USUARIO_API_KEY_NO_ENCONTRADA" => "No se encontró la clave de API con ID: {id}",
+        "USUARIO_PERMISO_NO_DELEGABLE" => {
+            "El permiso '{permiso}' no se puede conceder a una clave de API"
+        }
+        "USUARIO_PERMISO_INVALIDO" => "Permiso desconocido. Use nombres como reserva:read_any",
+        "USUARIO_OIDC_ESTADO_INVALIDO" => {
+            "El inicio de sesión único no es válido o ha caducado. Vuelve a intentarlo."
+        }
+        "USUARIO_IDENTIDAD_EXTERNA_RECHAZADA" => {
+            "El proveedor de identidad no ha permitido el inicio de sesión"
+        }
+        "USUARIO_EMAIL_EXTERNO_NO_VERIFICADO" => {
+            "Ya existe una cuenta con el email {email}, pero tu proveedor de identidad no lo ha verificado"
+        }
+        "USUARIO_PROVEEDOR_IDENTIDAD_NO_DISPONIBLE" => {
+            "No se ha podido contactar con el proveedor de identidad. Inténtalo más tarde."
+        }
+        "USUARIO_PASSWORD_EXTERNA" => {
+            "La contraseña de esta cuenta se gestiona en el directorio de la empresa"
+        }
+        "USUARIO_CODIGO_SEGUNDO_FACTOR_INVALIDO" => "El código de verificación no es correcto",
+        "USUARIO_DESAFIO_SEGUNDO_FACTOR_INVALIDO" => {
+            "El inicio de sesión ha caducado; vuelve a introducir la contraseña"
+        }
+        "USUARIO_SEGUNDO_FACTOR_REQUERIDO" => {
+            "Esta cuenta debe entrar con verificación en dos pasos"
+        }
+        "USUARIO_SEGUNDO_FACTOR_YA_ACTIVO" => "La verificación en dos pasos ya está activa",
+        "USUARIO_SEGUNDO_FACTOR_NO_INICIADO" => {
+            "No hay ninguna verificación en dos pasos iniciada"
+        }
+        "USUARIO_SEGUNDO_FACTOR_OBLIGATORIO" => {
+            "La verificación en dos pasos es obligatoria para tu rol"
+        }
+        "USUARIO_HORARIO_LABORAL_INVALIDO" => {
+            "El horario laboral debe terminar después de empezar e incluir algún día"
+        }
+        "USUARIO_EMAIL_EXTERNO" => {
+            "El email de esta cuenta lo gestiona el proveedor de identidad de la empresa"
+        }
+        "USUARIO_DIA_INVALIDO" => "Día de la semana desconocido. Use lunes, martes, … o mon, tue, …",
+        "USUARIO_HORA_INVALIDA" => "Hora no válida. Use el formato HH:MM",
+        "USUARIO_IDIOMA_INVALIDO" => "Idioma no soportado. Use: es o en",
+        "USUARIO_ULTIMO_ADMINISTRADOR" => {
+            "No se puede eliminar al único administrador activo"
+        }
+        "USUARIO_GRUPO_NO_ENCONTRADO" => "Grupo no encontrado con ID: {id}",
+        "USUARIO_GRUPO_ULTIMO_MIEMBRO" => "Un grupo no puede quedarse sin miembros",
+        "USUARIO_DELEGADO_PROPIO" => "No puedes autorizarte a ti mismo a reservar en tu nombre",
+
+        // Autenticación y autorización
+        "AUTH_TOKEN_REQUERIDO" => "Token de autenticación requerido",
+        "AUTH_FORMATO_TOKEN_INVALIDO" => "Formato de token inválido. Use: Bearer <token> o ApiKey <clave>",
+        "AUTH_TOKEN_INVALIDO" => "Token inválido o expirado",
+        "AUTH_ROL_INVALIDO" => "Rol inválido en el token",
+        "AUTH_USUARIO_INACTIVO" => "La cuenta está desactivada o ya no existe",
+        "AUTH_ESTADO_NO_DISPONIBLE" => "No se pudo comprobar el estado de la cuenta",
+        "AUTH_API_KEY_INVALIDA" => "Clave de API inválida o revocada",
+        "AUTH_SEGUNDO_FACTOR_REQUERIDO" => {
+            "Vuelve a iniciar sesión con la verificación en dos pasos"
+        }
+        "AUTH_PERMISO_DENEGADO" => "No tienes permiso para esta acción ({permiso})",
+
+        // Formularios web
+        "WEB_SALA_NO_SELECCIONADA" => "Selecciona una sala",
+        "WEB_FECHA_HORA_INVALIDA" => "Fecha u hora inválida",
+        _ => return None,
+    };
+
+    Some(texto)
+}
+
+fn en(codigo: &str) -> Option<&'static str> {
+    let texto = match codigo {
+        // Salas
+        "SALA_NOMBRE_VACIO" => "The name cannot be empty",
+        "SALA_NOMBRE_DEMASIADO_LARGO" => "The name cannot exceed 100 characters",
+        "SALA_NOMBRE_LONGITUD" => "The name must be between 1 and 100 characters",
+        "SALA_CAPACIDAD_INVALIDA" | "SALA_CAPACIDAD_RANGO" => {
+            "Capacity must be between 1 and 1000"
+        }
+        "SALA_NO_ENCONTRADA" => "Room not found",
+        "SALA_CON_RESERVAS_FUTURAS" => {
+            "The room has {n} upcoming active bookings; cancel them or use the option to cancel them"
+        }
+        "SALA_ERROR_REPOSITORIO" => "Repository error: {detalle}",
+        "SALA_VALIDACION" => "Validation errors: {errores}",
+        "SALA_ACCION_RESERVAS_INVALIDA" => "Invalid 'reservas' value. Use: rechazar or cancelar",
+        "SALA_ZONA_HORARIA_INVALIDA" => {
+            "The time zone '{zona}' is not valid. Use an IANA name, e.g. Europe/Madrid"
+        }
+        "SALA_ORDEN_INVALIDO" => "Invalid sort field. Use: nombre or capacidad",
+        "SALA_MARGEN_ANTES_RANGO" => "The setup buffer must be between 0 and 240 minutes",
+        "SALA_MARGEN_DESPUES_RANGO" => "The cleanup buffer must be between 0 and 240 minutes",
+
+        // Recursos
+        "RECURSO_NOMBRE_VACIO" => "The name cannot be empty",
+        "RECURSO_NOMBRE_DEMASIADO_LARGO" => "The name cannot exceed 100 characters",
+        "RECURSO_TIPO_INVALIDO" => {
+            "Invalid resource type '{tipo}'. Use: equipo, aparcamiento or otro"
+        }
+        "RECURSO_NO_ENCONTRADO" => "Resource not found",
+        "RECURSO_ERROR_REPOSITORIO" => "Repository error: {detalle}",
+        "LISTADO_DIRECCION_INVALIDA" => "Invalid sort direction. Use: asc or desc",
+
+        // Reservas
+        "RESERVA_SALA_ID_VACIO" => "The room ID cannot be empty",
+        "RESERVA_USUARIO_ID_VACIO" => "The user ID cannot be empty",
+        "RESERVA_FECHA_INICIO_PASADA" => "The start date cannot be in the past",
+        "RESERVA_FECHA_FIN_PASADA" => "The end date cannot be in the past",
+        "RESERVA_FECHA_FIN_ANTERIOR_A_INICIO" => "The end date must be after the start date",
+        "RESERVA_DURACION_INVALIDA" => "A booking must last between 15 minutes and 8 hours",
+        "RESERVA_NO_ENCONTRADA" => "Booking not found",
+        "RESERVA_SALA_NO_DISPONIBLE" => "The room is not available at the requested time",
+        "RESERVA_ERROR_REPOSITORIO" => "Repository error: {detalle}",
+        "RESERVA_VALIDACION" => "Validation errors: {errores}",
+        "RESERVA_SALA_NO_EXISTE" => "The room does not exist",
+        "RESERVA_SALA_INACTIVA" => "The room is not active",
+        "RESERVA_USUARIO_NO_EXISTE" => "The user does not exist",
+        "RESERVA_EMAIL_NO_VERIFICADO" => "Verify your email before making bookings",
+        "RESERVA_NO_ACTIVA" => "Only active bookings can be modified",
+        "RESERVA_GRUPO_AJENO" => "You can only assign the booking to one of your groups",
+        "RESERVA_SIN_DELEGACION" => "The organizer has not authorized you to book on their behalf",
+        "RESERVA_RECURRENCIA_INVALIDA" => "A recurrence must have between 1 and {max} repetitions",
+        "RESERVA_RECURRENCIA_NO_DISPONIBLE" => {
+            "The room is not available for the repetition on {fecha}"
+        }
+        "RESERVA_CONJUNTO_INVALIDO" => "A combined booking must have between 1 and {max} rooms",
+        "RESERVA_CONJUNTO_NO_DISPONIBLE" => {
+            "Room {sala} is not available; none of the rooms were booked"
+        }
+        "RESERVA_SIN_SALA_NI_RECURSOS" => "Specify a room or at least one resource",
+        "RESERVA_RECURSO_NO_EXISTE" => "One of the resources does not exist",
+        "RESERVA_RECURSO_INACTIVO" => "One of the resources is not active",
+        "RESERVA_RECURSO_NO_DISPONIBLE" => {
+            "Resource {recurso} is not available at the requested time"
+        }
+        "INFORME_RANGO_INVALIDO" => "The 'hasta' date must be after 'desde'",
+        "INFORME_AGRUPACION_INVALIDA" => "Invalid grouping. Use: sala, usuario or franja",
+        "RESERVA_ORDEN_INVALIDO" => "Invalid sort field. Use: fecha_inicio or creada",
+        "RESERVA_ESTADO_INVALIDO" => "Invalid status. Use: activa, cancelada or completada",
+
+        // Usuarios
+        "USUARIO_EMAIL_INVALIDO" => "The email '{email}' is not valid. Please enter a valid email.",
+        "USUARIO_EMAIL_DUPLICADO" => {
+            "The email '{email}' is already registered. Try another email or sign in."
+        }
+        "USUARIO_NOMBRE_VACIO" => "The name cannot be empty. Please enter your name.",
+        "USUARIO_NOMBRE_LONGITUD" => {
+            "The name must be between {min} and {max} characters (currently {actual})."
+        }
+        "USUARIO_PASSWORD_DEMASIADO_CORTA" => {
+            "The password must be at least {min} characters long (currently {actual})."
+        }
+        "USUARIO_NO_ENCONTRADO" => "No user found with ID: {id}",
+        "USUARIO_CREDENCIALES_INVALIDAS" => {
+            "Incorrect email or password. Please check your credentials."
+        }
+        "USUARIO_PERMISOS_DENEGADOS" => "You are not allowed to perform this action.",
+        "USUARIO_ERROR_REPOSITORIO" => "Error accessing data: {detalle}",
+        "USUARIO_VALIDACION" => "Validation error: {detalle}",
+        "USUARIO_ZONA_HORARIA_INVALIDA" => {
+            "The time zone '{zona}' is not valid. Use an IANA name, e.g. America/Mexico_City"
+        }
+        "USUARIO_VALIDACION_CAMPOS" => "Validation errors: {errores}",
+        "USUARIO_ORDEN_INVALIDO" => "Invalid sort field. Use: nombre, email or creado",
+        "USUARIO_ROL_INVALIDO" => "Invalid role. Use: admin or usuario",
+        "USUARIO_CUENTA_BLOQUEADA" => {
+            "Account temporarily locked after too many failed attempts. Try again in {minutos} minutes."
+        }
+        "USUARIO_DEMASIADOS_INTENTOS" => {
+            "Too many sign-in attempts from your connection. Try again in {minutos} minutes."
+        }
+        "USUARIO_TOKEN_INVALIDO" => "The link is invalid or has expired. Request a new one.",
+        "USUARIO_PASSWORD_SIN_MAYUSCULA" => "The password must include at least one uppercase letter",
+        "USUARIO_PASSWORD_SIN_MINUSCULA" => "The password must include at least one lowercase letter",
+        "USUARIO_PASSWORD_SIN_DIGITO" => "The password must include at least one number",
+        "USUARIO_PASSWORD_SIN_SIMBOLO" => "The password must include at least one symbol",
+        "USUARIO_PASSWORD_COMUN" => "That password is too common. Choose another one.",
+        "USUARIO_ERROR_CORREO" => "The email could not be sent. Try again later.",
+        "USUARIO_API_KEY_NO_ENCONTRADA" => "No API key found with ID: {id}",
+        "USUARIO_PERMISO_NO_DELEGABLE" => "The permission '{permiso}' cannot be granted to an API key",
+        "USUARIO_PERMISO_INVALIDO" => "Unknown permission. Use names such as reserva:read_any",
+        "USUARIO_OIDC_ESTADO_INVALIDO" => "The single sign-on attempt is invalid or has expired. Try again.",
+        "USUARIO_IDENTIDAD_EXTERNA_RECHAZADA" => "The identity provider did not allow the sign-in",
+        "USUARIO_EMAIL_EXTERNO_NO_VERIFICADO" => {
+            "An account with the email {email} already exists, but your identity provider has not verified it"
+        }
+        "USUARIO_PROVEEDOR_IDENTIDAD_NO_DISPONIBLE" => {
+            "The identity provider could not be reached. Try again later."
+        }
+        "USUARIO_PASSWORD_EXTERNA" => "This account's password is managed in the company directory",
+        "USUARIO_CODIGO_SEGUNDO_FACTOR_INVALIDO" => "The verification code is not correct",
+        "USUARIO_DESAFIO_SEGUNDO_FACTOR_INVALIDO" => {
+            "The sign-in has expired; enter your password again"
+        }
+        "USUARIO_SEGUNDO_FACTOR_REQUERIDO" => "This account must sign in with two-step verification",
+        "USUARIO_SEGUNDO_FACTOR_YA_ACTIVO" => "Two-step verification is already enabled",
+        "USUARIO_SEGUNDO_FACTOR_NO_INICIADO" => "No two-step verification setup has been started",
+        "USUARIO_SEGUNDO_FACTOR_OBLIGATORIO" => "Two-step verification is required for your role",
+        "USUARIO_HORARIO_LABORAL_INVALIDO" => {
+            "Working hours must end after they start and include at least one day"
+        }
+        "USUARIO_EMAIL_EXTERNO" => "This account's email is managed by the company identity provider",
+        "USUARIO_DIA_INVALIDO" => "Unknown day of the week. Use lunes, martes, … or mon, tue, …",
+        "USUARIO_HORA_INVALIDA" => "Invalid time. Use the HH:MM format",
+        "USUARIO_IDIOMA_INVALIDO" => "Unsupported language. Use: es or en",
+        "USUARIO_ULTIMO_ADMINISTRADOR" => "The only active administrator cannot be deleted",
+        "USUARIO_GRUPO_NO_ENCONTRADO" => "Group not found with ID: {id}",
+        "USUARIO_GRUPO_ULTIMO_MIEMBRO" => "A group cannot be left without members",
+        "USUARIO_DELEGADO_PROPIO" => "You cannot authorize yourself to book on your own behalf",
+
+        // Autenticación y autorización
+        "AUTH_TOKEN_REQUERIDO" => "Authentication token required",
+        "AUTH_FORMATO_TOKEN_INVALIDO" => "Invalid token format. Use: Bearer <token> or ApiKey <key>",
+        "AUTH_TOKEN_INVALIDO" => "Invalid or expired token",
+        "AUTH_ROL_INVALIDO" => "Invalid role in token",
+        "AUTH_USUARIO_INACTIVO" => "The account is deactivated or no longer exists",
+        "AUTH_ESTADO_NO_DISPONIBLE" => "Could not check the account status",
+        "AUTH_API_KEY_INVALIDA" => "Invalid or revoked API key",
+        "AUTH_SEGUNDO_FACTOR_REQUERIDO" => "Sign in again with two-step verification",
+        "AUTH_PERMISO_DENEGADO" => "You do not have permission for this action ({permiso})",
+
+        // Formularios web
+        "WEB_SALA_NO_SELECCIONADA" => "Select a room",
+        "WEB_FECHA_HORA_INVALIDA" => "Invalid date or time",
+        _ => return None,
+    };
+
+    Some(texto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_language_respeta_pesos() {
+        assert_eq!(
+            Idioma::desde_accept_language("en-US,en;q=0.9,es;q=0.8"),
+            Some(Idioma::En)
+        );
+        assert_eq!(
+            Idioma::desde_accept_language("fr-FR, en;q=0.5, es;q=0.7"),
+            Some(Idioma::Es)
+        );
+        assert_eq!(Idioma::desde_accept_language("fr, de;q=0.5"), None);
+        assert_eq!(
+            Idioma::desde_accept_language("en;q=0, es"),
+            Some(Idioma::Es)
+        );
+    }
+
+    #[test]
+    fn traduce_con_parametros_y_cae_a_espanol() {
+        assert_eq!(
+            traducir(
+                "SALA_CON_RESERVAS_FUTURAS",
+                Idioma::En,
+                &[("n", "3".into())]
+            ),
+            "The room has 3 upcoming active bookings; cancel them or use the option to cancel them"
+        );
+        assert_eq!(
+            traducir("CODIGO_DESCONOCIDO", Idioma::En, &[]),
+            "CODIGO_DESCONOCIDO"
+        );
+    }
+
+    #[test]
+    fn todos_los_codigos_estan_traducidos() {
+        let codigos = [
+            "SALA_NOMBRE_VACIO",
+            "SALA_NOMBRE_DEMASIADO_LARGO",
+            "SALA_NOMBRE_LONGITUD",
+            "SALA_CAPACIDAD_INVALIDA",
+            "SALA_CAPACIDAD_RANGO",
+            "SALA_NO_ENCONTRADA",
+            "SALA_CON_RESERVAS_FUTURAS",
+            "SALA_ERROR_REPOSITORIO",
+            "SALA_VALIDACION",
+            "SALA_ACCION_RESERVAS_INVALIDA",
+            "SALA_ZONA_HORARIA_INVALIDA",
+            "SALA_ORDEN_INVALIDO",
+            "SALA_MARGEN_ANTES_RANGO",
+            "SALA_MARGEN_DESPUES_RANGO",
+            "RECURSO_NOMBRE_VACIO",
+            "RECURSO_NOMBRE_DEMASIADO_LARGO",
+            "RECURSO_TIPO_INVALIDO",
+            "RECURSO_NO_ENCONTRADO",
+            "RECURSO_ERROR_REPOSITORIO",
+            "LISTADO_DIRECCION_INVALIDA",
+            "RESERVA_SALA_ID_VACIO",
+            "RESERVA_USUARIO_ID_VACIO",
+            "RESERVA_FECHA_INICIO_PASADA",
+            "RESERVA_FECHA_FIN_PASADA",
+            "RESERVA_FECHA_FIN_ANTERIOR_A_INICIO",
+            "RESERVA_DURACION_INVALIDA",
+            "RESERVA_NO_ENCONTRADA",
+            "RESERVA_SALA_NO_DISPONIBLE",
+            "RESERVA_ERROR_REPOSITORIO",
+            "RESERVA_VALIDACION",
+            "RESERVA_SALA_NO_EXISTE",
+            "RESERVA_SALA_INACTIVA",
+            "RESERVA_USUARIO_NO_EXISTE",
+            "RESERVA_EMAIL_NO_VERIFICADO",
+            "RESERVA_NO_ACTIVA",
+            "RESERVA_GRUPO_AJENO",
+            "RESERVA_SIN_DELEGACION",
+            "RESERVA_RECURRENCIA_INVALIDA",
+            "RESERVA_RECURRENCIA_NO_DISPONIBLE",
+            "RESERVA_CONJUNTO_INVALIDO",
+            "RESERVA_CONJUNTO_NO_DISPONIBLE",
+            "RESERVA_SIN_SALA_NI_RECURSOS",
+            "RESERVA_RECURSO_NO_EXISTE",
+            "RESERVA_RECURSO_INACTIVO",
+            "RESERVA_RECURSO_NO_DISPONIBLE",
+            "INFORME_RANGO_INVALIDO",
+            "INFORME_AGRUPACION_INVALIDA",
+            "RESERVA_ORDEN_INVALIDO",
+            "RESERVA_ESTADO_INVALIDO",
+            "USUARIO_EMAIL_INVALIDO",
+            "USUARIO_EMAIL_DUPLICADO",
+            "USUARIO_NOMBRE_VACIO",
+            "USUARIO_NOMBRE_LONGITUD",
+            "USUARIO_PASSWORD_DEMASIADO_CORTA",
+            "USUARIO_NO_ENCONTRADO",
+            "USUARIO_CREDENCIALES_INVALIDAS",
+            "USUARIO_PERMISOS_DENEGADOS",
+            "USUARIO_ERROR_REPOSITORIO",
+            "USUARIO_VALIDACION",
+            "USUARIO_ZONA_HORARIA_INVALIDA",
+            "USUARIO_VALIDACION_CAMPOS",
+            "USUARIO_ORDEN_INVALIDO",
+            "USUARIO_ROL_INVALIDO",
+            "USUARIO_CUENTA_BLOQUEADA",
+            "USUARIO_DEMASIADOS_INTENTOS",
+            "USUARIO_TOKEN_INVALIDO",
+            "USUARIO_PASSWORD_SIN_MAYUSCULA",
+            "USUARIO_PASSWORD_SIN_MINUSCULA",
+            "USUARIO_PASSWORD_SIN_DIGITO",
+            "USUARIO_PASSWORD_SIN_SIMBOLO",
+            "USUARIO_PASSWORD_COMUN",
+            "USUARIO_ERROR_CORREO",
+            "USUARIO_API_KEY_NO_ENCONTRADA",
+            "USUARIO_PERMISO_NO_DELEGABLE",
+            "USUARIO_PERMISO_INVALIDO",
+            "USUARIO_OIDC_ESTADO_INVALIDO",
+            "USUARIO_IDENTIDAD_EXTERNA_RECHAZADA",
+            "USUARIO_EMAIL_EXTERNO_NO_VERIFICADO",
+            "USUARIO_PROVEEDOR_IDENTIDAD_NO_DISPONIBLE",
+            "USUARIO_PASSWORD_EXTERNA",
+            "USUARIO_CODIGO_SEGUNDO_FACTOR_INVALIDO",
+            "USUARIO_DESAFIO_SEGUNDO_FACTOR_INVALIDO",
+            "USUARIO_SEGUNDO_FACTOR_REQUERIDO",
+            "USUARIO_SEGUNDO_FACTOR_YA_ACTIVO",
+            "USUARIO_SEGUNDO_FACTOR_NO_INICIADO",
+            "USUARIO_SEGUNDO_FACTOR_OBLIGATORIO",
+            "USUARIO_HORARIO_LABORAL_INVALIDO",
+            "USUARIO_EMAIL_EXTERNO",
+            "USUARIO_DIA_INVALIDO",
+            "USUARIO_HORA_INVALIDA",
+            "USUARIO_IDIOMA_INVALIDO",
+            "USUARIO_ULTIMO_ADMINISTRADOR",
+            "USUARIO_GRUPO_NO_ENCONTRADO",
+            "USUARIO_GRUPO_ULTIMO_MIEMBRO",
+            "USUARIO_DELEGADO_PROPIO",
+            "AUTH_TOKEN_REQUERIDO",
+            "AUTH_FORMATO_TOKEN_INVALIDO",
+            "AUTH_TOKEN_INVALIDO",
+            "AUTH_ROL_INVALIDO",
+            "AUTH_USUARIO_INACTIVO",
+            "AUTH_ESTADO_NO_DISPONIBLE",
+            "AUTH_API_KEY_INVALIDA",
+            "AUTH_SEGUNDO_FACTOR_REQUERIDO",
+            "AUTH_PERMISO_DENEGADO",
+            "WEB_SALA_NO_SELECCIONADA",
+            "WEB_FECHA_HORA_INVALIDA",
+        ];
+
+        for codigo in codigos {
+            for idioma in Idioma::TODOS {
+                assert!(
+                    plantilla(codigo, idioma).is_some(),
+                    "Falta {} en {}",
+                    codigo,
+                    idioma
+                );
+            }
+        }
+    }
+}
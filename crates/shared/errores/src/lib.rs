@@ -0,0 +1,186 @@
+//! Modelo de errores compartido por las features
+//!
+//! Cada error de dominio expone un código estable (`SALA_NO_ENCONTRADA`), una
+//! categoría que decide el status HTTP / gRPC y, si aplica, los campos que lo
+//! provocaron. Los mensajes pueden cambiar; los códigos no.
+//!
+//! Los textos de cara al usuario viven en el catálogo de [`i18n`].
+
+#[cfg(feature = "tonic")]
+pub mod grpc;
+pub mod i18n;
+mod problema;
+#[cfg(feature = "axum")]
+pub mod rest;
+
+pub use i18n::Idioma;
+pub use problema::{Problema, CONTENT_TYPE_PROBLEMA};
+
+use serde::Serialize;
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Dominio que se publica en `google.rpc.ErrorInfo`
+pub const DOMINIO: &str = "reservas-salas";
+
+/// Familia de un error; determina el status HTTP y el código gRPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Categoria {
+    Validacion,
+    NoEncontrado,
+    /// Ya existe un recurso con esos datos (p. ej. email repetido)
+    Duplicado,
+    Conflicto,
+    NoAutenticado,
+    PermisoDenegado,
+    /// Demasiados intentos; el cliente debe esperar antes de reintentar
+    DemasiadasPeticiones,
+    Interno,
+}
+
+impl Categoria {
+    pub fn status_http(&self) -> u16 {
+        match self {
+            Categoria::Validacion => 400,
+            Categoria::NoAutenticado => 401,
+            Categoria::PermisoDenegado => 403,
+            Categoria::NoEncontrado => 404,
+            Categoria::Duplicado | Categoria::Conflicto => 409,
+            Categoria::DemasiadasPeticiones => 429,
+            Categoria::Interno => 500,
+        }
+    }
+
+    /// Título corto para el campo `title` del problem+json
+    pub fn titulo(&self) -> &'static str {
+        self.titulo_en(Idioma::Es)
+    }
+
+    pub fn titulo_en(&self, idioma: Idioma) -> &'static str {
+        match (idioma, self) {
+            (Idioma::Es, Categoria::Validacion) => "Datos inválidos",
+            (Idioma::Es, Categoria::NoEncontrado) => "Recurso no encontrado",
+            (Idioma::Es, Categoria::Duplicado) => "Recurso duplicado",
+            (Idioma::Es, Categoria::Conflicto) => "Conflicto con el estado actual",
+            (Idioma::Es, Categoria::NoAutenticado) => "No autenticado",
+            (Idioma::Es, Categoria::PermisoDenegado) => "Permiso denegado",
+            (Idioma::Es, Categoria::DemasiadasPeticiones) => "Demasiadas peticiones",
+            (Idioma::Es, Categoria::Interno) => "Error interno",
+            (Idioma::En, Categoria::Validacion) => "Invalid data",
+            (Idioma::En, Categoria::NoEncontrado) => "Resource not found",
+            (Idioma::En, Categoria::Duplicado) => "Duplicate resource",
+            (Idioma::En, Categoria::Conflicto) => "Conflict with the current state",
+            (Idioma::En, Categoria::NoAutenticado) => "Not authenticated",
+            (Idioma::En, Categoria::PermisoDenegado) => "Permission denied",
+            (Idioma::En, Categoria::DemasiadasPeticiones) => "Too many requests",
+            (Idioma::En, Categoria::Interno) => "Internal error",
+        }
+    }
+}
+
+/// Error asociado a un campo concreto de la petición
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ErrorCampo {
+    /// Nombre del campo tal y como aparece en la API
+    #[serde(rename = "field")]
+    #[schema(example = "capacidad")]
+    pub campo: String,
+    #[serde(rename = "code")]
+    #[schema(example = "SALA_CAPACIDAD_RANGO")]
+    pub codigo: String,
+    #[serde(rename = "message")]
+    #[schema(example = "La capacidad debe ser entre 1 y 1000")]
+    pub mensaje: String,
+}
+
+impl ErrorCampo {
+    pub fn new(
+        campo: impl Into<String>,
+        codigo: impl Into<String>,
+        mensaje: impl Into<String>,
+    ) -> Self {
+        Self {
+            campo: campo.into(),
+            codigo: codigo.into(),
+            mensaje: mensaje.into(),
+        }
+    }
+
+    /// Error de campo con el mensaje del catálogo en español
+    pub fn con_codigo(campo: impl Into<String>, codigo: &str) -> Self {
+        Self::new(campo, codigo, i18n::traducir(codigo, Idioma::Es, &[]))
+    }
+}
+
+impl fmt::Display for ErrorCampo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje)
+    }
+}
+
+/// Error de dominio con código estable
+pub trait ErrorDominio {
+    /// Código estable en MAYÚSCULAS (`RESERVA_SALA_NO_DISPONIBLE`)
+    fn codigo(&self) -> &'static str;
+
+    fn categoria(&self) -> Categoria;
+
+    /// Valores para los parámetros del mensaje del catálogo (`{n}`, `{email}`...)
+    fn parametros(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Campos de la petición que provocan el error
+    fn campos(&self) -> Vec<ErrorCampo> {
+        Vec::new()
+    }
+
+    /// Mensaje del catálogo en el idioma pedido
+    ///
+    /// El parámetro `{errores}` se rellena con los mensajes de los campos.
+    fn mensaje_en(&self, idioma: Idioma) -> String {
+        let mut parametros = self.parametros();
+
+        if i18n::usa_parametro(self.codigo(), idioma, "errores") {
+            let errores: Vec<String> = self
+                .campos_en(idioma)
+                .into_iter()
+                .map(|c| c.mensaje)
+                .collect();
+            parametros.push(("errores", errores.join("; ")));
+        }
+
+        i18n::traducir(self.codigo(), idioma, &parametros)
+    }
+
+    /// Campos con el mensaje traducido; los códigos sin entrada en el
+    /// catálogo conservan su mensaje original
+    fn campos_en(&self, idioma: Idioma) -> Vec<ErrorCampo> {
+        let parametros = self.parametros();
+
+        self.campos()
+            .into_iter()
+            .map(|mut campo| {
+                if i18n::existe(&campo.codigo) {
+                    campo.mensaje = i18n::traducir(&campo.codigo, idioma, &parametros);
+                }
+                campo
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_campo_se_serializa_con_claves_en_ingles() {
+        let campo = ErrorCampo::new("nombre", "SALA_NOMBRE_LONGITUD", "Nombre inválido");
+        let json = serde_json::to_value(&campo).unwrap();
+
+        assert_eq!(json["field"], "nombre");
+        assert_eq!(json["code"], "SALA_NOMBRE_LONGITUD");
+        assert_eq!(json["message"], "Nombre inválido");
+    }
+}
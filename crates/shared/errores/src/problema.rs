@@ -0,0 +1,99 @@
+use crate::{ErrorCampo, ErrorDominio, Idioma};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub const CONTENT_TYPE_PROBLEMA: &str = "application/problem+json";
+
+/// Cuerpo de error REST en formato "problem details" (RFC 9457)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Problema {
+    #[serde(rename = "type")]
+    #[schema(example = "urn:reservas-salas:error:SALA_NO_ENCONTRADA")]
+    pub tipo: String,
+    #[serde(rename = "title")]
+    #[schema(example = "Recurso no encontrado")]
+    pub titulo: String,
+    #[schema(example = 404)]
+    pub status: u16,
+    #[serde(rename = "detail")]
+    #[schema(example = "Sala no encontrada")]
+    pub detalle: String,
+    /// Código estable del error
+    #[serde(rename = "code")]
+    #[schema(example = "SALA_NO_ENCONTRADA")]
+    pub codigo: String,
+    /// Errores por campo (solo en errores de validación)
+    #[serde(rename = "errors", skip_serializing_if = "Vec::is_empty")]
+    pub errores: Vec<ErrorCampo>,
+}
+
+impl Problema {
+    /// Problema con los mensajes en español
+    pub fn desde<E: ErrorDominio + ?Sized>(error: &E) -> Self {
+        Self::desde_en(error, Idioma::default())
+    }
+
+    pub fn desde_en<E: ErrorDominio + ?Sized>(error: &E, idioma: Idioma) -> Self {
+        let categoria = error.categoria();
+        let codigo = error.codigo();
+
+        Self {
+            tipo: format!("urn:{}:error:{}", crate::DOMINIO, codigo),
+            titulo: categoria.titulo_en(idioma).to_string(),
+            status: categoria.status_http(),
+            detalle: error.mensaje_en(idioma),
+            codigo: codigo.to_string(),
+            errores: error.campos_en(idioma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Categoria;
+
+    struct ErrorPrueba;
+
+    impl ErrorDominio for ErrorPrueba {
+        fn codigo(&self) -> &'static str {
+            "SALA_VALIDACION"
+        }
+
+        fn categoria(&self) -> Categoria {
+            Categoria::Validacion
+        }
+
+        fn campos(&self) -> Vec<ErrorCampo> {
+            vec![ErrorCampo::con_codigo("capacidad", "SALA_CAPACIDAD_RANGO")]
+        }
+    }
+
+    #[test]
+    fn problema_desde_error_de_dominio() {
+        let problema = Problema::desde(&ErrorPrueba);
+
+        assert_eq!(problema.status, 400);
+        assert_eq!(problema.codigo, "SALA_VALIDACION");
+        assert_eq!(problema.tipo, "urn:reservas-salas:error:SALA_VALIDACION");
+        assert_eq!(
+            problema.detalle,
+            "Errores de validación: La capacidad debe ser entre 1 y 1000"
+        );
+        assert_eq!(problema.errores[0].campo, "capacidad");
+    }
+
+    #[test]
+    fn problema_en_ingles_traduce_detalle_y_campos() {
+        let problema = Problema::desde_en(&ErrorPrueba, Idioma::En);
+
+        assert_eq!(
+            problema.detalle,
+            "Validation errors: Capacity must be between 1 and 1000"
+        );
+        assert_eq!(
+            problema.errores[0].mensaje,
+            "Capacity must be between 1 and 1000"
+        );
+    }
+}
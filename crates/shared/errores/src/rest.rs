@@ -0,0 +1,155 @@
+//! Respuestas `application/problem+json` localizadas para axum
+//!
+//! Los handlers devuelven el problema en español junto con una forma de
+//! regenerarlo; la capa [`localizar_problemas`] lo traduce al idioma del
+//! cliente. La preferencia del perfil (una extensión [`Idioma`] que añade la
+//! capa de autenticación a la respuesta) tiene prioridad sobre
+//! `Accept-Language`.
+
+use crate::{ErrorDominio, Idioma, Problema, CONTENT_TYPE_PROBLEMA};
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+/// Regenera el problema de una respuesta en otro idioma
+#[derive(Clone)]
+struct ProblemaLocalizable(Arc<dyn Fn(Idioma) -> Problema + Send + Sync>);
+
+impl IntoResponse for Problema {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let cuerpo = serde_json::to_vec(&self).unwrap_or_default();
+
+        (
+            status,
+            [(header::CONTENT_TYPE, CONTENT_TYPE_PROBLEMA)],
+            cuerpo,
+        )
+            .into_response()
+    }
+}
+
+/// Respuesta de error que [`localizar_problemas`] puede traducir
+pub fn respuesta_problema<E>(error: E) -> Response
+where
+    E: ErrorDominio + Send + Sync + 'static,
+{
+    let mut respuesta = Problema::desde(&error).into_response();
+    respuesta
+        .extensions_mut()
+        .insert(ProblemaLocalizable(Arc::new(move |idioma| {
+            Problema::desde_en(&error, idioma)
+        })));
+    respuesta
+}
+
+/// Idioma pedido en la cabecera `Accept-Language`
+pub fn idioma_de_cabeceras(headers: &HeaderMap) -> Option<Idioma> {
+    headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Idioma::desde_accept_language)
+}
+
+/// Middleware que traduce los problemas al idioma del cliente
+pub async fn localizar_problemas(req: Request, next: Next) -> Response {
+    let pedido = idioma_de_cabeceras(req.headers());
+    let respuesta = next.run(req).await;
+
+    let Some(localizable) = respuesta.extensions().get::<ProblemaLocalizable>().cloned() else {
+        return respuesta;
+    };
+
+    let idioma = respuesta
+        .extensions()
+        .get::<Idioma>()
+        .copied()
+        .or(pedido)
+        .unwrap_or_default();
+
+    let mut traducida = (localizable.0)(idioma).into_response();
+    traducida.headers_mut().insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(idioma.codigo()),
+    );
+    traducida
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Categoria;
+    use axum::body::{to_bytes, Body};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    struct SalaNoEncontrada;
+
+    impl ErrorDominio for SalaNoEncontrada {
+        fn codigo(&self) -> &'static str {
+            "SALA_NO_ENCONTRADA"
+        }
+
+        fn categoria(&self) -> Categoria {
+            Categoria::NoEncontrado
+        }
+    }
+
+    async fn detalle(router: Router, accept_language: Option<&str>) -> (String, String) {
+        let mut peticion = axum::http::Request::builder().uri("/");
+        if let Some(valor) = accept_language {
+            peticion = peticion.header(header::ACCEPT_LANGUAGE, valor);
+        }
+
+        let respuesta = router
+            .oneshot(peticion.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let idioma = respuesta.headers()[header::CONTENT_LANGUAGE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let cuerpo = to_bytes(respuesta.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&cuerpo).unwrap();
+
+        (idioma, json["detail"].as_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn traduce_segun_accept_language() {
+        let router = Router::new()
+            .route("/", get(|| async { respuesta_problema(SalaNoEncontrada) }))
+            .layer(axum::middleware::from_fn(localizar_problemas));
+
+        assert_eq!(
+            detalle(router.clone(), Some("en-GB,en;q=0.8")).await,
+            ("en".to_string(), "Room not found".to_string())
+        );
+        assert_eq!(
+            detalle(router, None).await,
+            ("es".to_string(), "Sala no encontrada".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn preferencia_del_perfil_gana_a_la_cabecera() {
+        let router = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    let mut respuesta = respuesta_problema(SalaNoEncontrada);
+                    respuesta.extensions_mut().insert(Idioma::Es);
+                    respuesta
+                }),
+            )
+            .layer(axum::middleware::from_fn(localizar_problemas));
+
+        assert_eq!(
+            detalle(router, Some("en")).await.1,
+            "Sala no encontrada".to_string()
+        );
+    }
+}
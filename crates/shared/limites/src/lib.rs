@@ -0,0 +1,276 @@
+//! Limitación de peticiones por IP para los servidores HTTP y gRPC
+//!
+//! [`LimitePeticionesLayer`] es una capa de tower con una cubeta de fichas por
+//! dirección IP: admite ráfagas de hasta `rafaga` peticiones y las repone a
+//! `por_segundo`. Al agotarse responde sin llegar al servicio:
+//!
+//! - HTTP: `429 Too Many Requests` con `Retry-After`.
+//! - gRPC: `RESOURCE_EXHAUSTED` (`grpc-status: 8`) en una respuesta solo de cabeceras.
+//!
+//! La IP se obtiene de las extensiones de la petición con la función que se
+//! pase al crear la capa ([`ip_axum`] o [`ip_tonic`]); si no hay IP, la
+//! petición no se limita.
+
+use http::header::{CONTENT_TYPE, RETRY_AFTER};
+use http::{Extensions, HeaderValue, Request, Response, StatusCode};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+/// A partir de cuántas IPs registradas se purgan las cubetas ya llenas
+const PURGA_A_PARTIR_DE: usize = 4096;
+
+/// Obtiene la IP del cliente de las extensiones de la petición
+pub type ExtraerIp = fn(&Extensions) -> Option<IpAddr>;
+
+/// IP del cliente en servidores axum servidos con `into_make_service_with_connect_info`
+#[cfg(feature = "axum")]
+pub fn ip_axum(extensiones: &Extensions) -> Option<IpAddr> {
+    extensiones
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|info| info.0.ip())
+}
+
+/// IP del cliente en servidores tonic
+#[cfg(feature = "tonic")]
+pub fn ip_tonic(extensiones: &Extensions) -> Option<IpAddr> {
+    extensiones
+        .get::<tonic::transport::server::TcpConnectInfo>()
+        .and_then(|info| info.remote_addr())
+        .map(|addr| addr.ip())
+}
+
+/// Peticiones permitidas por IP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cuota {
+    /// Peticiones seguidas que se admiten con la cubeta llena
+    pub rafaga: u32,
+    /// Fichas que se reponen cada segundo
+    pub por_segundo: u32,
+}
+
+impl Cuota {
+    pub fn new(rafaga: u32, por_segundo: u32) -> Self {
+        Self {
+            rafaga: rafaga.max(1),
+            por_segundo: por_segundo.max(1),
+        }
+    }
+}
+
+impl Default for Cuota {
+    fn default() -> Self {
+        Self::new(100, 20)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cubeta {
+    fichas: f64,
+    ultima: Instant,
+}
+
+struct Estado {
+    cuota: Cuota,
+    extraer_ip: ExtraerIp,
+    cubetas: Mutex<HashMap<IpAddr, Cubeta>>,
+}
+
+impl Estado {
+    /// Consume una ficha de la IP; si no quedan, devuelve los segundos a esperar
+    fn consumir(&self, ip: IpAddr, ahora: Instant) -> Result<(), u64> {
+        let rafaga = f64::from(self.cuota.rafaga);
+        let por_segundo = f64::from(self.cuota.por_segundo);
+        let llenado = |cubeta: &Cubeta| {
+            let repuestas = ahora.duration_since(cubeta.ultima).as_secs_f64() * por_segundo;
+            (cubeta.fichas + repuestas).min(rafaga)
+        };
+
+        let mut cubetas = self.cubetas.lock().unwrap();
+        if cubetas.len() >= PURGA_A_PARTIR_DE {
+            cubetas.retain(|_, cubeta| llenado(cubeta) < rafaga);
+        }
+
+        let cubeta = cubetas.entry(ip).or_insert(Cubeta {
+            fichas: rafaga,
+            ultima: ahora,
+        });
+        cubeta.fichas = llenado(cubeta);
+        cubeta.ultima = ahora;
+
+        if cubeta.fichas >= 1.0 {
+            cubeta.fichas -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - cubeta.fichas) / por_segundo).ceil() as u64)
+        }
+    }
+}
+
+/// Capa de tower que limita las peticiones por IP
+#[derive(Clone)]
+pub struct LimitePeticionesLayer {
+    estado: Arc<Estado>,
+}
+
+impl LimitePeticionesLayer {
+    pub fn new(cuota: Cuota, extraer_ip: ExtraerIp) -> Self {
+        Self {
+            estado: Arc::new(Estado {
+                cuota,
+                extraer_ip,
+                cubetas: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+impl<S> Layer<S> for LimitePeticionesLayer {
+    type Service = LimitePeticiones<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LimitePeticiones {
+            inner,
+            estado: Arc::clone(&self.estado),
+        }
+    }
+}
+
+/// Servicio creado por [`LimitePeticionesLayer`]
+#[derive(Clone)]
+pub struct LimitePeticiones<S> {
+    inner: S,
+    estado: Arc<Estado>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for LimitePeticiones<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        if let Some(ip) = (self.estado.extraer_ip)(request.extensions()) {
+            if let Err(segundos) = self.estado.consumir(ip, Instant::now()) {
+                let respuesta = respuesta_limitada(&request, segundos);
+                return Box::pin(std::future::ready(Ok(respuesta)));
+            }
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+fn respuesta_limitada<ReqBody, ResBody: Default>(
+    request: &Request<ReqBody>,
+    segundos: u64,
+) -> Response<ResBody> {
+    let es_grpc = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/grpc"));
+
+    let mut respuesta = Response::new(ResBody::default());
+    let cabeceras = respuesta.headers_mut();
+    cabeceras.insert(RETRY_AFTER, HeaderValue::from(segundos));
+
+    if es_grpc {
+        // Respuesta "trailers-only": el estado gRPC viaja en las cabeceras
+        cabeceras.insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+        cabeceras.insert("grpc-status", HeaderValue::from_static("8"));
+        cabeceras.insert(
+            "grpc-message",
+            HeaderValue::from_static("Demasiadas peticiones, espera antes de reintentar"),
+        );
+    } else {
+        *respuesta.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    }
+
+    respuesta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    fn ip_de_prueba(extensiones: &Extensions) -> Option<IpAddr> {
+        extensiones.get::<IpAddr>().copied()
+    }
+
+    fn peticion(ip: &str, content_type: &str) -> Request<()> {
+        let mut request = Request::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ip.parse::<IpAddr>().unwrap());
+        request
+    }
+
+    async fn llamar(capa: &LimitePeticionesLayer, request: Request<()>) -> Response<String> {
+        let servicio = capa.layer(service_fn(|_: Request<()>| async {
+            Ok::<_, Infallible>(Response::new("ok".to_string()))
+        }));
+        servicio.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn limita_cada_ip_por_separado() {
+        let capa = LimitePeticionesLayer::new(Cuota::new(2, 1), ip_de_prueba);
+
+        for _ in 0..2 {
+            let respuesta = llamar(&capa, peticion("10.0.0.1", "application/json")).await;
+            assert_eq!(respuesta.status(), StatusCode::OK);
+        }
+
+        let respuesta = llamar(&capa, peticion("10.0.0.1", "application/json")).await;
+        assert_eq!(respuesta.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(respuesta.headers()[RETRY_AFTER], "1");
+        assert!(respuesta.body().is_empty());
+
+        let respuesta = llamar(&capa, peticion("10.0.0.2", "application/json")).await;
+        assert_eq!(respuesta.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn grpc_recibe_resource_exhausted() {
+        let capa = LimitePeticionesLayer::new(Cuota::new(1, 1), ip_de_prueba);
+
+        llamar(&capa, peticion("10.0.0.1", "application/grpc")).await;
+        let respuesta = llamar(&capa, peticion("10.0.0.1", "application/grpc")).await;
+
+        assert_eq!(respuesta.status(), StatusCode::OK);
+        assert_eq!(respuesta.headers()["grpc-status"], "8");
+    }
+
+    #[test]
+    fn la_cubeta_se_repone_con_el_tiempo() {
+        let capa = LimitePeticionesLayer::new(Cuota::new(1, 2), ip_de_prueba);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let inicio = Instant::now();
+
+        assert!(capa.estado.consumir(ip, inicio).is_ok());
+        assert_eq!(capa.estado.consumir(ip, inicio), Err(1));
+
+        let despues = inicio + std::time::Duration::from_millis(500);
+        assert!(capa.estado.consumir(ip, despues).is_ok());
+    }
+}
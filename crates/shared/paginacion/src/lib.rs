@@ -0,0 +1,158 @@
+//! Paginación y orden compartidos por los listados de las features
+//!
+//! Los listados usan paginación por desplazamiento (`offset` + `limite`). Cada
+//! feature define sus filtros y los campos por los que se puede ordenar; aquí
+//! solo vive lo común: la ventana pedida, la dirección del orden y la página
+//! resultante con el total de elementos que cumplen los filtros.
+
+use std::cmp::Ordering;
+
+/// Elementos por página si no se indica `limite`
+pub const LIMITE_POR_DEFECTO: u32 = 50;
+
+/// Máximo de elementos por página; valores mayores se recortan
+pub const LIMITE_MAXIMO: u32 = 200;
+
+/// Cabecera HTTP con el total de elementos que cumplen los filtros
+/// (`X-Total-Count`); el cuerpo solo lleva la página pedida
+pub const TOTAL_HEADER: &str = "x-total-count";
+
+/// Dirección del orden
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direccion {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl Direccion {
+    /// Crea la dirección desde un string ("asc" o "desc")
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "asc" => Some(Direccion::Asc),
+            "desc" => Some(Direccion::Desc),
+            _ => None,
+        }
+    }
+
+    /// Aplica la dirección a una comparación ascendente
+    pub fn aplicar(&self, orden: Ordering) -> Ordering {
+        match self {
+            Direccion::Asc => orden,
+            Direccion::Desc => orden.reverse(),
+        }
+    }
+}
+
+/// Ventana de resultados pedida
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paginacion {
+    pub offset: u32,
+    pub limite: u32,
+}
+
+impl Paginacion {
+    /// `limite` 0 equivale a no indicarlo; por encima de [`LIMITE_MAXIMO`] se recorta
+    pub fn new(offset: u32, limite: u32) -> Self {
+        let limite = match limite {
+            0 => LIMITE_POR_DEFECTO,
+            n => n.min(LIMITE_MAXIMO),
+        };
+        Self { offset, limite }
+    }
+}
+
+impl Default for Paginacion {
+    fn default() -> Self {
+        Self::new(0, LIMITE_POR_DEFECTO)
+    }
+}
+
+/// Una página de resultados
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pagina<T> {
+    pub elementos: Vec<T>,
+    /// Elementos que cumplen los filtros, sin paginar
+    pub total: usize,
+    pub offset: u32,
+    pub limite: u32,
+}
+
+impl<T> Pagina<T> {
+    /// Recorta una lista ya filtrada y ordenada a la ventana pedida
+    pub fn desde_ordenados(ordenados: Vec<T>, paginacion: Paginacion) -> Self {
+        let total = ordenados.len();
+        let elementos = ordenados
+            .into_iter()
+            .skip(paginacion.offset as usize)
+            .take(paginacion.limite as usize)
+            .collect();
+
+        Self {
+            elementos,
+            total,
+            offset: paginacion.offset,
+            limite: paginacion.limite,
+        }
+    }
+
+    /// Offset de la página siguiente, si la hay
+    pub fn siguiente_offset(&self) -> Option<u32> {
+        let siguiente = self.offset as usize + self.elementos.len();
+        (!self.elementos.is_empty() && siguiente < self.total).then_some(siguiente as u32)
+    }
+
+    /// Transforma los elementos conservando los datos de paginación
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Pagina<U> {
+        Pagina {
+            elementos: self.elementos.into_iter().map(f).collect(),
+            total: self.total,
+            offset: self.offset,
+            limite: self.limite,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limite_por_defecto_y_recortado() {
+        assert_eq!(Paginacion::new(0, 0).limite, LIMITE_POR_DEFECTO);
+        assert_eq!(Paginacion::new(0, 10_000).limite, LIMITE_MAXIMO);
+        assert_eq!(
+            Paginacion::new(5, 20),
+            Paginacion {
+                offset: 5,
+                limite: 20
+            }
+        );
+    }
+
+    #[test]
+    fn pagina_recorta_y_calcula_siguiente() {
+        let numeros: Vec<u32> = (0..7).collect();
+
+        let primera = Pagina::desde_ordenados(numeros.clone(), Paginacion::new(0, 3));
+        assert_eq!(primera.elementos, vec![0, 1, 2]);
+        assert_eq!(primera.total, 7);
+        assert_eq!(primera.siguiente_offset(), Some(3));
+
+        let ultima = Pagina::desde_ordenados(numeros.clone(), Paginacion::new(6, 3));
+        assert_eq!(ultima.elementos, vec![6]);
+        assert_eq!(ultima.siguiente_offset(), None);
+
+        let fuera = Pagina::desde_ordenados(numeros, Paginacion::new(20, 3));
+        assert!(fuera.elementos.is_empty());
+        assert_eq!(fuera.siguiente_offset(), None);
+    }
+
+    #[test]
+    fn direccion_invierte_el_orden() {
+        assert_eq!(Direccion::from_str_opt("DESC"), Some(Direccion::Desc));
+        assert_eq!(Direccion::from_str_opt("arriba"), None);
+        assert_eq!(Direccion::Desc.aplicar(1.cmp(&2)), Ordering::Greater);
+        assert_eq!(Direccion::Asc.aplicar(1.cmp(&2)), Ordering::Less);
+    }
+}